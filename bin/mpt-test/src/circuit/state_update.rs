@@ -32,7 +32,9 @@ use crate::circuit::{
 
 #[cfg(not(feature = "disable-keccak"))]
 use zkevm_circuits::{
-    keccak_circuit::{KeccakCircuit, KeccakCircuitConfig, KeccakCircuitConfigArgs},
+    keccak_circuit::{
+        KeccakCircuit, KeccakCircuitConfig, KeccakCircuitConfigArgs, KeccakCircuitParams,
+    },
     util::{SubCircuit, SubCircuitConfig},
 };
 
@@ -138,9 +140,11 @@ impl<F: Field> Circuit<F> for StateUpdateCircuit<F> {
             KeccakCircuitConfigArgs {
                 keccak_table: keccak_table.clone(),
                 challenges: challenges_expr.clone(),
+                params: KeccakCircuitParams::default(),
             },
         );
-        let mpt_config = MPTConfig::new(meta, challenges_expr, keccak_table, params);
+        let mpt_table = MptTable::construct(meta);
+        let mpt_config = MPTConfig::new(meta, challenges_expr, mpt_table, keccak_table, params);
 
         let is_first = meta.fixed_column();
         let count = meta.advice_column();
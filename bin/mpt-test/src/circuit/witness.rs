@@ -20,7 +20,7 @@ use ethers::{
 };
 use eyre::Result;
 
-use geth_utils::mpt::{ProofType, TrieModification};
+use bus_mapping::mpt::{ProofType, StateTrie, TrieModification};
 use zkevm_circuits::{
     mpt_circuit::witness_row::Node, table::mpt_table::MPTProofType, util::word::WordLoHi,
 };
@@ -58,6 +58,7 @@ struct Transforms {
     pub prev_state_root: H256,
     pub curr_state_root: H256,
     pub trie_modifications: Vec<TrieModification>,
+    pub proof_nodes: Vec<Bytes>,
 }
 
 trait TrieModificationBuilder {
@@ -135,7 +136,7 @@ impl<F: Field> Witness<F> {
         if transforms.prev_state_root == transforms.curr_state_root {
             Ok(None)
         } else {
-            let (mpt_witness, lc_witness) = Self::mpt_witness(&transforms, provider_url)?;
+            let (mpt_witness, lc_witness) = Self::mpt_witness(&transforms)?;
             Ok(Some(Self {
                 mpt_witness,
                 lc_witness,
@@ -219,6 +220,7 @@ impl<F: Field> Witness<F> {
 
         let mut initial_values = Vec::new();
         let mut changed_values = Vec::new();
+        let mut proof_nodes = Vec::new();
 
         for entry in access_list.0 {
             let AccessListItem {
@@ -242,6 +244,12 @@ impl<F: Field> Witness<F> {
                 )
                 .await?;
 
+            // the nodes to resolve the state trie of the previous block
+            proof_nodes.extend(old.account_proof.iter().cloned());
+            for storage_proof in old.storage_proof.iter() {
+                proof_nodes.extend(storage_proof.proof.iter().cloned());
+            }
+
             // if nothing changed, append all values
             if old.balance == new.balance
                 && old.nonce == new.nonce
@@ -296,18 +304,14 @@ impl<F: Field> Witness<F> {
             curr_state_root: curr_block.state_root,
             prev_state_root: prev_block.state_root,
             trie_modifications,
+            proof_nodes,
         })
     }
 
-    fn mpt_witness(
-        trns: &Transforms,
-        provider: &str,
-    ) -> Result<(Vec<Node>, FieldTrieModifications<F>)> {
-        let json = geth_utils::get_witness(
-            trns.block_no.as_u64() - 1,
-            &trns.trie_modifications,
-            provider,
-        );
+    fn mpt_witness(trns: &Transforms) -> Result<(Vec<Node>, FieldTrieModifications<F>)> {
+        let mut state_trie = StateTrie::new(trns.prev_state_root);
+        state_trie.add_proof_nodes(&trns.proof_nodes);
+        let json = state_trie.gen_witness(&trns.trie_modifications)?;
         let nodes = zkevm_circuits::mpt_circuit::load_proof(json.as_bytes())?;
 
         let witness_previous_state_root = H256::from_slice(&nodes[0].values[0][1..33]);
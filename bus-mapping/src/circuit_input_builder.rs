@@ -4,9 +4,11 @@
 mod access;
 mod block;
 mod call;
+mod capacity;
 mod chunk;
 mod execution;
 mod input_state_ref;
+mod mpt;
 #[cfg(test)]
 mod tracer_tests;
 mod transaction;
@@ -26,21 +28,26 @@ use crate::{
 pub use access::{Access, AccessSet, AccessValue, CodeSource};
 pub use block::{Block, BlockContext};
 pub use call::{Call, CallContext, CallKind};
-pub use chunk::ChunkContext;
+pub use capacity::{BuilderCheckpoint, CapacityExceeded};
+pub use chunk::{ChunkContext, ChunkRowUsage};
 use core::fmt::Debug;
 use eth_types::{
-    self, geth_types,
+    self,
+    evm_types::{Hardfork, HardforkSchedule},
+    geth_types,
     sign_types::{pk_bytes_le, pk_bytes_swap_endianness, SignData},
     Address, GethExecStep, GethExecTrace, ToWord, Word,
 };
 use ethers_providers::JsonRpcClient;
 pub use execution::{
-    CopyDataType, CopyEvent, CopyStep, ExecState, ExecStep, ExpEvent, ExpStep, NumberOrHash,
-    PrecompileEvent, PrecompileEvents, N_BYTES_PER_PAIR, N_PAIRING_PER_OP,
+    BigModExp, CopyDataType, CopyEvent, CopyStep, EcAddOp, EcMulOp, EcPairingOp, EcPairingPair,
+    ExecState, ExecStep, ExpEvent, ExpStep, NumberOrHash, PrecompileEvent, PrecompileEvents,
+    N_BYTES_PER_PAIR, N_PAIRING_PER_OP,
 };
 pub use input_state_ref::CircuitInputStateRef;
 use itertools::Itertools;
 use log::warn;
+use serde::Serialize;
 use std::{
     collections::{HashMap, HashSet},
     ops::Deref,
@@ -54,7 +61,7 @@ pub const N_EXEC_STATE: usize = 10;
 /// Runtime Config
 ///
 /// Default to mainnet block
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct FeatureConfig {
     /// Zero difficulty
     pub zero_difficulty: bool,
@@ -68,6 +75,14 @@ pub struct FeatureConfig {
     /// shouldn't be included in a mainnet block. However, rollup developers might want to
     /// include invalid tx in the L2 block to support forced exit feature.
     pub invalid_tx: bool,
+    /// Prove the state root transition of the block with the MPT circuit
+    ///
+    /// When disabled, the `MptTable` is filled with mock roots and the account and storage
+    /// updates of the State circuit are not checked against the state trie.  When enabled, the
+    /// block must carry the MPT witness generated by `CircuitInputBuilder::gen_mpt_witness`.
+    pub mpt_circuit: bool,
+    /// Hardfork whose rules apply to the block: gas costs, available opcodes and precompiles
+    pub hardfork: Hardfork,
 }
 
 impl Default for FeatureConfig {
@@ -77,6 +92,8 @@ impl Default for FeatureConfig {
             free_first_tx: false,
             enable_eip1559: true,
             invalid_tx: false,
+            mpt_circuit: false,
+            hardfork: Hardfork::default(),
         }
     }
 }
@@ -92,7 +109,7 @@ impl FeatureConfig {
 const RW_BUFFER_SIZE: usize = 30;
 
 /// Circuit Setup Parameters
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct FixedCParams {
     ///
     pub total_chunks: usize,
@@ -126,11 +143,56 @@ pub struct FixedCParams {
     /// calculated, so the same circuit will not be able to prove different
     /// witnesses.
     pub max_keccak_rows: usize,
+    /// Size of the lookup tables and rounds of the Keccak circuit
+    pub keccak_params: KeccakCircuitParams,
     /// This number indicate what 100% usage means, for example if we can support up to 2
     /// ecPairing inside circuit, and max_vertical_circuit_rows is set to 1_000_000,
     /// then if there is 1 ecPairing in the input, we will return 500_000 as the "row usage"
     /// for the ec circuit.
     pub max_vertical_circuit_rows: usize,
+    /// Maximum number of ops of each ECC precompile supported in the ECC Circuit.
+    pub max_ec_ops: PrecompileEcParams,
+}
+
+/// Size of the lookup tables and rounds of the Keccak circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct KeccakCircuitParams {
+    /// Log2 of the number of rows available to the lookup tables, which sets
+    /// how many bits are processed in a single lookup.  It must not exceed the
+    /// degree of the circuit.
+    pub degree: usize,
+    /// Number of rows used by each round of the keccak_f permutation, at least 9
+    pub rows_per_round: usize,
+}
+
+impl Default for KeccakCircuitParams {
+    fn default() -> Self {
+        Self {
+            degree: 8,
+            rows_per_round: 12,
+        }
+    }
+}
+
+/// Maximum number of ops of each ECC precompile, i.e. ecAdd, ecMul and ecPairing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PrecompileEcParams {
+    /// Maximum number of ecAdd ops.
+    pub ec_add: usize,
+    /// Maximum number of ecMul ops.
+    pub ec_mul: usize,
+    /// Maximum number of ecPairing ops.
+    pub ec_pairing: usize,
+}
+
+impl Default for PrecompileEcParams {
+    fn default() -> Self {
+        Self {
+            ec_add: 50,
+            ec_mul: 50,
+            ec_pairing: 2,
+        }
+    }
 }
 
 /// Unset Circuits Parameters
@@ -196,7 +258,9 @@ impl Default for FixedCParams {
             max_bytecode: 512,
             max_evm_rows: 0,
             max_keccak_rows: 0,
+            keccak_params: KeccakCircuitParams::default(),
             max_vertical_circuit_rows: 0,
+            max_ec_ops: PrecompileEcParams::default(),
         }
     }
 }
@@ -290,6 +354,7 @@ impl<'a, C: CircuitsParams> CircuitInputBuilder<C> {
             tx,
             tx_ctx,
             max_rws: self.circuits_params.max_rws(),
+            hardfork: self.feature_config.hardfork,
         }
     }
 
@@ -581,6 +646,13 @@ impl<'a, C: CircuitsParams> CircuitInputBuilder<C> {
             // add `-1` to include previous set and deal with transaction cross-chunk case
             self.chunk_ctx
                 .bump(self.block_ctx.rwc.0, next_tx_index - 1, next_copy_index);
+            // with an unbounded chunking context the chunks are allocated on the fly
+            if self.chunks.len() == self.chunk_ctx.idx {
+                self.chunks.push(Chunk {
+                    fixed_param: self.chunks[self.chunk_ctx.idx - 1].fixed_param,
+                    ..Chunk::default()
+                });
+            }
             self.cur_chunk_mut().prev_last_call = last_call;
         }
     }
@@ -746,6 +818,61 @@ impl CircuitInputBuilder<FixedCParams> {
     ) -> Result<CircuitInputBuilder<FixedCParams>, Error> {
         // accumulates gas across all txs in the block
         let (last_step, last_call) = self.begin_handle_block(eth_block, geth_traces)?;
+        self.end_handle_block(eth_block, last_step, last_call)?;
+        Ok(self)
+    }
+
+    /// Handle a block like [`Self::handle_block`], but deciding the chunk
+    /// boundaries while handling it instead of requiring the number of chunks
+    /// in advance: a new chunk starts whenever the current one runs out of
+    /// `max_rws`, and `circuits_params.total_chunks` is ignored and set to
+    /// the number of chunks used.
+    ///
+    /// The other capacities of the circuits params are shared by all the
+    /// chunks, so more chunks don't help with them: if the block exceeds any,
+    /// [`Error::CapacityExceeded`] is returned.  The rows of the EVM and Keccak
+    /// circuits depend on their layout, so they are checked when converting
+    /// the chunks to their witness in `zkevm-circuits`.
+    pub fn handle_block_with_planned_chunks(
+        mut self,
+        eth_block: &EthBlock,
+        geth_traces: &[eth_types::GethExecTrace],
+    ) -> Result<CircuitInputBuilder<FixedCParams>, Error> {
+        self.chunks = vec![Chunk {
+            fixed_param: self.circuits_params,
+            ..Chunk::default()
+        }];
+        self.chunk_ctx = ChunkContext::new_unbounded();
+
+        let (last_step, last_call) = self.begin_handle_block(eth_block, geth_traces)?;
+        if let Some(exceeded) = self.block_capacity_exceeded() {
+            return Err(Error::CapacityExceeded(exceeded));
+        }
+
+        // The chunk being handled is the last one
+        let total_chunks = self.chunk_ctx.idx + 1;
+        log::debug!(
+            "block {:?} planned in {} chunks",
+            eth_block.number,
+            total_chunks
+        );
+        self.circuits_params.total_chunks = total_chunks;
+        self.chunk_ctx.total_chunks = total_chunks;
+
+        self.end_handle_block(eth_block, last_step, last_call)?;
+        Ok(self)
+    }
+
+    /// Last part of handle_block, filling the virtual steps of the remaining
+    /// chunks and the EndBlock step.
+    fn end_handle_block(
+        &mut self,
+        eth_block: &EthBlock,
+        last_step: Option<ExecStep>,
+        last_call: Option<Call>,
+    ) -> Result<(), Error> {
+        self.apply_withdrawals();
+
         // since there is no next step, we cook dummy next step from last step to reuse
         // existing field while update its `rwc`.
         let mut dummy_next_step = {
@@ -822,11 +949,45 @@ impl CircuitInputBuilder<FixedCParams> {
         // Truncate chunks to the actual used amount & correct ctx.total_chunks
         // Set length to the actual used amount of chunks
         self.chunks.truncate(self.chunk_ctx.idx + 1);
-        self.chunks.iter_mut().for_each(|chunk| {
+        for idx in 0..used_chunks {
+            let row_usage = self.chunk_row_usage(&self.chunks[idx].ctx);
+            let chunk = &mut self.chunks[idx];
             chunk.ctx.total_chunks = used_chunks;
-        });
+            chunk.row_usage = row_usage;
+        }
 
-        Ok(self)
+        Ok(())
+    }
+
+    /// Compute the rows used by the chunk with the given context.
+    fn chunk_row_usage(&self, ctx: &ChunkContext) -> ChunkRowUsage {
+        let txs = &self.block.txs[ctx.initial_tx_index..ctx.end_tx_index];
+        let exec_steps = txs
+            .iter()
+            .flat_map(|tx| tx.steps())
+            .filter(|step| (ctx.initial_rwc..ctx.end_rwc).contains(&step.rwc.0))
+            .count();
+        // Each copied byte takes two rows, a read and a write
+        let copy_rows = self.block.copy_events[ctx.initial_copy_index..ctx.end_copy_index]
+            .iter()
+            .fold(0, |acc, c| acc + c.bytes.len())
+            * 2;
+        ChunkRowUsage {
+            rws: ctx.end_rwc - ctx.initial_rwc,
+            txs: txs.len(),
+            exec_steps,
+            copy_rows,
+        }
+    }
+
+    /// Credit the withdrawals of the block to their addresses, after all the
+    /// transactions.  These balance updates don't go through the rw table, the
+    /// withdrawals are only committed to by the withdrawal root.
+    fn apply_withdrawals(&mut self) {
+        for withdrawal in self.block.withdrawals() {
+            let (_, account) = self.sdb.get_account_mut(&withdrawal.address);
+            account.balance += withdrawal.amount_in_wei();
+        }
     }
 
     fn set_end_block(&mut self) -> Result<(), Error> {
@@ -924,6 +1085,11 @@ impl<C: CircuitsParams> CircuitInputBuilder<C> {
         // With a 0 value the keccak circuit computes dynamically the minimum number of rows
         // needed.
         let max_keccak_rows = 0;
+        let max_ec_ops = PrecompileEcParams {
+            ec_add: self.block.precompile_events.get_ec_add_events().len(),
+            ec_mul: self.block.precompile_events.get_ec_mul_events().len(),
+            ec_pairing: self.block.precompile_events.get_ec_pairing_events().len(),
+        };
         FixedCParams {
             total_chunks: self.circuits_params.total_chunks(),
             max_rws,
@@ -935,7 +1101,9 @@ impl<C: CircuitsParams> CircuitInputBuilder<C> {
             max_bytecode,
             max_evm_rows,
             max_keccak_rows,
+            keccak_params: KeccakCircuitParams::default(),
             max_vertical_circuit_rows: 0,
+            max_ec_ops,
         }
     }
 }
@@ -1114,6 +1282,7 @@ pub struct BuilderClient<P: JsonRpcClient> {
     chain_id: Word,
     circuits_params: FixedCParams,
     feature_config: FeatureConfig,
+    hardfork_schedule: Option<HardforkSchedule>,
 }
 
 /// Get State Accesses from TxExecTraces
@@ -1187,6 +1356,34 @@ impl<P: JsonRpcClient> BuilderClient<P> {
             chain_id: chain_id.into(),
             circuits_params,
             feature_config,
+            hardfork_schedule: HardforkSchedule::from_chain_id(chain_id),
+        })
+    }
+
+    /// Select the hardfork of each block with `hardfork_schedule`, for a chain
+    /// whose schedule isn't known.  Without a schedule, the hardfork of the
+    /// [`FeatureConfig`] applies to all the blocks.
+    pub fn with_hardfork_schedule(mut self, hardfork_schedule: HardforkSchedule) -> Self {
+        self.hardfork_schedule = Some(hardfork_schedule);
+        self
+    }
+
+    /// Returns the [`FeatureConfig`] of `eth_block`, with the hardfork of the
+    /// block selected by its number and timestamp.
+    pub fn feature_config(&self, eth_block: &EthBlock) -> Result<FeatureConfig, Error> {
+        let Some(schedule) = &self.hardfork_schedule else {
+            return Ok(self.feature_config);
+        };
+        let number = eth_block
+            .number
+            .ok_or(Error::EthTypeError(eth_types::Error::IncompleteBlock))?
+            .as_u64();
+        let hardfork = schedule
+            .hardfork(number, eth_block.timestamp.low_u64())
+            .ok_or(Error::UnsupportedHardfork(number))?;
+        Ok(FeatureConfig {
+            hardfork,
+            ..self.feature_config
         })
     }
 
@@ -1303,7 +1500,7 @@ impl<P: JsonRpcClient> BuilderClient<P> {
             code_db,
             block,
             self.circuits_params,
-            self.feature_config,
+            self.feature_config(eth_block)?,
         );
         let builder = builder.handle_block(eth_block, geth_traces)?;
         Ok(builder)
@@ -8,7 +8,12 @@ use crate::{
     operation::{OperationContainer, RWCounter},
     Error,
 };
-use eth_types::{evm_unimplemented, Address, Word, H256};
+use eth_types::{
+    evm_types::gas_utils::blob_base_fee,
+    evm_unimplemented,
+    geth_types::{get_blob_gas_used, get_excess_blob_gas},
+    Address, Word, H256,
+};
 use itertools::Itertools;
 use std::collections::HashMap;
 
@@ -64,6 +69,12 @@ pub struct Block {
     pub difficulty: Word,
     /// base fee
     pub base_fee: Word,
+    /// excess blob gas
+    pub excess_blob_gas: u64,
+    /// blob gas used
+    pub blob_gas_used: u64,
+    /// blob base fee, derived from the excess blob gas
+    pub blob_base_fee: Word,
     /// State root of the previous block
     pub prev_state_root: Word,
     /// Container of operations done in this block.
@@ -86,6 +97,9 @@ pub struct Block {
     pub precompile_events: PrecompileEvents,
     /// Original block from geth
     pub eth_block: eth_types::Block<eth_types::Transaction>,
+    /// JSON encoded MPT witness of the trie modifications done in this block,
+    /// consumed by the MPT circuit.
+    pub mpt_witness: Option<String>,
 }
 
 impl Block {
@@ -103,6 +117,8 @@ impl Block {
             );
         }
 
+        let excess_blob_gas = get_excess_blob_gas(eth_block).as_u64();
+
         Ok(Self {
             chain_id,
             history_hashes,
@@ -126,6 +142,9 @@ impl Block {
                 eth_block.difficulty
             },
             base_fee: eth_block.base_fee_per_gas.unwrap_or_default(),
+            excess_blob_gas,
+            blob_gas_used: get_blob_gas_used(eth_block).as_u64(),
+            blob_base_fee: blob_base_fee(excess_blob_gas),
             prev_state_root,
             container: OperationContainer::new(),
             txs: Vec::new(),
@@ -138,6 +157,7 @@ impl Block {
             sha3_inputs: Vec::new(),
             precompile_events: PrecompileEvents { events: Vec::new() },
             eth_block: eth_block.clone(),
+            mpt_witness: None,
         })
     }
 
@@ -151,9 +171,9 @@ impl Block {
         &mut self.txs
     }
 
-    /// Return the list of withdrawals of this block.
+    /// Return the list of withdrawals of this block, empty before Shanghai.
     pub fn withdrawals(&self) -> Vec<Withdrawal> {
-        let eth_withdrawals = self.eth_block.withdrawals.clone().unwrap();
+        let eth_withdrawals = self.eth_block.withdrawals.clone().unwrap_or_default();
         eth_withdrawals
             .iter()
             .map({
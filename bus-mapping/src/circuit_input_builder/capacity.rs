@@ -0,0 +1,308 @@
+//! Incremental handling of the transactions of a block, for sequencers that
+//! select the transactions of the block they build.
+//!
+//! Each transaction is handled on top of the previous ones, and rolled back if
+//! the block exceeds the capacities of the [`FixedCParams`] with it, so that
+//! the sealed block is always provable:
+//! ```ignore
+//! let mut builder = CircuitInputBuilder::new(sdb, code_db, block, params, features);
+//! for (eth_tx, geth_trace) in candidates {
+//!     if let Some(exceeded) = builder.handle_tx_within_capacity(&eth_tx, &geth_trace)? {
+//!         log::info!("block full: {:?}", exceeded);
+//!         break;
+//!     }
+//! }
+//! let builder = builder.finalize_block(&eth_block)?;
+//! ```
+
+use super::{
+    chunk::Chunk, BlockContext, Call, ChunkContext, CircuitInputBuilder, ExecStep, FixedCParams,
+};
+use crate::{operation::OperationContainer, Error};
+use eth_types::GethExecTrace;
+
+type EthBlock = eth_types::Block<eth_types::Transaction>;
+
+/// Capacity of the [`FixedCParams`] exceeded by the handled transactions.
+///
+/// The capacities which depend on the layout of the circuits, like the rows of
+/// the EVM and Keccak circuits when padded to a fixed size, are checked on the
+/// witness of the block in `zkevm-circuits` instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapacityExceeded {
+    /// [`FixedCParams::max_rws`] in the last chunk
+    Rws,
+    /// [`FixedCParams::max_txs`]
+    Txs,
+    /// [`FixedCParams::max_calldata`]
+    Calldata,
+    /// [`FixedCParams::max_copy_rows`]
+    CopyRows,
+    /// [`FixedCParams::max_exp_steps`]
+    ExpSteps,
+    /// [`FixedCParams::max_bytecode`]
+    Bytecode,
+    /// ecAdd ops of [`FixedCParams::max_ec_ops`]
+    EcAdd,
+    /// ecMul ops of [`FixedCParams::max_ec_ops`]
+    EcMul,
+    /// ecPairing ops of [`FixedCParams::max_ec_ops`]
+    EcPairing,
+    /// [`FixedCParams::max_evm_rows`] in a chunk, checked in `zkevm-circuits`
+    EvmRows,
+    /// [`FixedCParams::max_keccak_rows`], checked in `zkevm-circuits`
+    KeccakRows,
+}
+
+/// State of a [`CircuitInputBuilder`] between two transactions, to roll back
+/// the transactions handled after it.  The accounts and codes modified since
+/// the checkpoint are journaled in the [`StateDB`](crate::state_db::StateDB)
+/// and [`CodeDB`](crate::state_db::CodeDB) of the builder instead.
+#[derive(Debug, Clone)]
+pub struct BuilderCheckpoint {
+    block_ctx: BlockContext,
+    chunk_ctx: ChunkContext,
+    chunks: Vec<Chunk>,
+    container_lens: [usize; OperationContainer::NUM_FIELDS],
+    txs: usize,
+    copy_events: usize,
+    sha3_inputs: usize,
+    exp_events: usize,
+    precompile_events: usize,
+}
+
+impl CircuitInputBuilder<FixedCParams> {
+    /// Save the state of the builder, to [`rollback`](Self::rollback) the
+    /// transactions handled afterwards.  Only the last checkpoint can be
+    /// rolled back to.
+    pub fn checkpoint(&mut self) -> BuilderCheckpoint {
+        self.sdb.checkpoint();
+        self.code_db.checkpoint();
+        BuilderCheckpoint {
+            block_ctx: self.block_ctx.clone(),
+            chunk_ctx: self.chunk_ctx.clone(),
+            chunks: self.chunks.clone(),
+            container_lens: self.block.container.lens(),
+            txs: self.block.txs.len(),
+            copy_events: self.block.copy_events.len(),
+            sha3_inputs: self.block.sha3_inputs.len(),
+            exp_events: self.block.exp_events.len(),
+            precompile_events: self.block.precompile_events.events.len(),
+        }
+    }
+
+    /// Drop the operations, events and state changes of the transactions
+    /// handled since `checkpoint`.
+    pub fn rollback(&mut self, checkpoint: BuilderCheckpoint) {
+        self.sdb.revert_to_checkpoint();
+        self.code_db.revert_to_checkpoint();
+        self.block_ctx = checkpoint.block_ctx;
+        self.chunk_ctx = checkpoint.chunk_ctx;
+        self.chunks = checkpoint.chunks;
+        self.block.container.truncate(checkpoint.container_lens);
+        self.block.txs.truncate(checkpoint.txs);
+        self.block.copy_events.truncate(checkpoint.copy_events);
+        self.block.sha3_inputs.truncate(checkpoint.sha3_inputs);
+        self.block.exp_events.truncate(checkpoint.exp_events);
+        self.block
+            .precompile_events
+            .events
+            .truncate(checkpoint.precompile_events);
+    }
+
+    /// Returns the first capacity of the [`FixedCParams`] exceeded by the
+    /// transactions handled so far, if any.
+    pub fn capacity_exceeded(&self) -> Option<CapacityExceeded> {
+        // the chunks before the last one end before running out of rws
+        let rws = self.chunk_rws() + self.last_exec_step_rws_reserved();
+        if rws > self.circuits_params.max_rws {
+            return Some(CapacityExceeded::Rws);
+        }
+        self.block_capacity_exceeded()
+    }
+
+    /// Returns the first capacity of the [`FixedCParams`] shared by all the
+    /// chunks of the block, i.e. all but the rws, exceeded by the
+    /// transactions handled so far, if any.
+    pub(crate) fn block_capacity_exceeded(&self) -> Option<CapacityExceeded> {
+        let params = &self.circuits_params;
+        let block = &self.block;
+
+        let calldata = block
+            .txs
+            .iter()
+            .map(|tx| tx.tx.call_data.len())
+            .sum::<usize>();
+        // each copied byte takes two rows, a read and a write, and the copy
+        // circuit has two more rows
+        let copy_rows = block
+            .copy_events
+            .iter()
+            .map(|event| event.bytes.len() * 2)
+            .sum::<usize>()
+            + 2;
+        let exp_steps = block
+            .exp_events
+            .iter()
+            .map(|event| event.steps.len())
+            .sum::<usize>();
+        let events = &block.precompile_events;
+
+        [
+            (CapacityExceeded::Txs, block.txs.len(), params.max_txs),
+            (CapacityExceeded::Calldata, calldata, params.max_calldata),
+            (CapacityExceeded::CopyRows, copy_rows, params.max_copy_rows),
+            (CapacityExceeded::ExpSteps, exp_steps, params.max_exp_steps),
+            (
+                CapacityExceeded::Bytecode,
+                self.code_db.num_rows_required_for_bytecode_table(),
+                params.max_bytecode,
+            ),
+            (
+                CapacityExceeded::EcAdd,
+                events.get_ec_add_events().len(),
+                params.max_ec_ops.ec_add,
+            ),
+            (
+                CapacityExceeded::EcMul,
+                events.get_ec_mul_events().len(),
+                params.max_ec_ops.ec_mul,
+            ),
+            (
+                CapacityExceeded::EcPairing,
+                events.get_ec_pairing_events().len(),
+                params.max_ec_ops.ec_pairing,
+            ),
+        ]
+        .into_iter()
+        .find(|(_, used, capacity)| used > capacity)
+        .map(|(exceeded, _, _)| exceeded)
+    }
+
+    /// Handle the next transaction of the block, `eth_tx` with its execution
+    /// trace `geth_trace`.  If the block exceeds a capacity of the
+    /// [`FixedCParams`] with it, the transaction is rolled back and the
+    /// exceeded capacity returned.
+    ///
+    /// The `transaction_index` of `eth_tx` must be the number of transactions
+    /// handled before it.  Once all the transactions are handled, the block
+    /// must be completed with [`Self::finalize_block`].
+    pub fn handle_tx_within_capacity(
+        &mut self,
+        eth_tx: &eth_types::Transaction,
+        geth_trace: &GethExecTrace,
+    ) -> Result<Option<CapacityExceeded>, Error> {
+        let checkpoint = self.checkpoint();
+        // Transaction index starts from 1
+        let tx_id = self.block.txs.len() as u64 + 1;
+        if let Err(err) = self.handle_tx(eth_tx, geth_trace, false, tx_id) {
+            self.rollback(checkpoint);
+            return Err(err);
+        }
+
+        let exceeded = self.capacity_exceeded();
+        if exceeded.is_some() {
+            self.rollback(checkpoint);
+        }
+        Ok(exceeded)
+    }
+
+    /// Complete the block whose transactions have been handled with
+    /// [`Self::handle_tx_within_capacity`].  `eth_block` must contain exactly
+    /// these transactions.
+    pub fn finalize_block(mut self, eth_block: &EthBlock) -> Result<Self, Error> {
+        if eth_block.transactions.len() != self.block.txs.len() {
+            return Err(Error::InternalError(
+                "block transactions differ from the handled ones",
+            ));
+        }
+        self.block.eth_block = eth_block.clone();
+        self.set_value_ops_call_context_rwc_eor();
+
+        let (last_step, last_call) = self.last_tx_step();
+        self.end_handle_block(eth_block, last_step, last_call)?;
+        Ok(self)
+    }
+
+    /// Returns the last step of the last handled transaction, and the call of
+    /// the step executed before it, as returned when handling it.
+    fn last_tx_step(&self) -> (Option<ExecStep>, Option<Call>) {
+        let Some(tx) = self.block.txs.last() else {
+            return (None, None);
+        };
+        let steps = tx.steps();
+        // an invalid transaction has a single step, without any call
+        let last_call = steps
+            .len()
+            .checked_sub(2)
+            .map(|idx| tx.calls()[steps[idx].call_index].clone());
+        (steps.last().cloned(), last_call)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::BlockData;
+    use eth_types::{geth_types::GethData, Word};
+    use mock::{eth, TestContext};
+
+    #[test]
+    fn handle_tx_within_capacity_rollback() {
+        let block: GethData = TestContext::<3, 2>::new(
+            None,
+            |accs| {
+                accs[0].address(mock::MOCK_ACCOUNTS[0]).balance(eth(10));
+                accs[1].address(mock::MOCK_ACCOUNTS[1]).balance(eth(10));
+                accs[2].address(mock::MOCK_ACCOUNTS[2]).balance(eth(10));
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[0].address).to(accs[2].address);
+                txs[1]
+                    .from(accs[1].address)
+                    .to(accs[2].address)
+                    .value(eth(1));
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let circuits_params = FixedCParams {
+            max_txs: 1,
+            ..Default::default()
+        };
+        let mut builder = BlockData::new_from_geth_data_with_params(block.clone(), circuits_params)
+            .new_circuit_input_builder();
+        let txs = &block.eth_block.transactions;
+
+        assert_eq!(
+            builder
+                .handle_tx_within_capacity(&txs[0], &block.geth_traces[0])
+                .unwrap(),
+            None
+        );
+        let container_lens = builder.block.container.lens();
+        let rwc = builder.block_ctx.rwc;
+        let balance = builder.sdb.get_balance(&txs[1].from);
+
+        // the second transaction doesn't fit, and is rolled back
+        assert_eq!(
+            builder
+                .handle_tx_within_capacity(&txs[1], &block.geth_traces[1])
+                .unwrap(),
+            Some(CapacityExceeded::Txs)
+        );
+        assert_eq!(builder.block.txs.len(), 1);
+        assert_eq!(builder.block.container.lens(), container_lens);
+        assert_eq!(builder.block_ctx.rwc, rwc);
+        assert_eq!(builder.sdb.get_balance(&txs[1].from), balance);
+        assert_ne!(balance, Word::zero());
+
+        let mut eth_block = block.eth_block.clone();
+        eth_block.transactions.truncate(1);
+        let builder = builder.finalize_block(&eth_block).unwrap();
+        assert_eq!(builder.chunks.len(), 1);
+        assert_eq!(builder.chunks[0].ctx.end_tx_index, 1);
+    }
+}
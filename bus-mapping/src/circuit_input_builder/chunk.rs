@@ -16,6 +16,25 @@ pub struct Chunk {
     pub padding: Option<ExecStep>,
     ///
     pub prev_last_call: Option<Call>,
+    /// Rows used by the chunk, set once the block is handled
+    pub row_usage: ChunkRowUsage,
+}
+
+/// Usage of the capacities of [`FixedCParams`] by a chunk.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkRowUsage {
+    /// Rw operations in the chunk, including the ones of the chunk virtual
+    /// steps, see [`FixedCParams::max_rws`]
+    pub rws: usize,
+    /// Transactions executed in the chunk, a transaction crossing the chunk
+    /// boundary being counted in both chunks
+    pub txs: usize,
+    /// Execution steps of the transactions in the chunk, excluding the chunk
+    /// virtual steps
+    pub exec_steps: usize,
+    /// Rows of the copy events in the chunk, see
+    /// [`FixedCParams::max_copy_rows`]
+    pub copy_rows: usize,
 }
 
 /// Context of chunking, used to track the current chunk index and inner rw counter
@@ -66,6 +85,12 @@ impl ChunkContext {
         }
     }
 
+    /// New chunking context without a number of chunks fixed in advance, the
+    /// last chunk being decided once the whole block is handled.
+    pub fn new_unbounded() -> Self {
+        Self::new(usize::MAX)
+    }
+
     /// New chunking context with one chunk
     pub fn new_one_chunk() -> Self {
         Self {
@@ -105,3 +130,105 @@ impl ChunkContext {
         self.total_chunks - self.idx - 1 == 0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        circuit_input_builder::{CapacityExceeded, FixedCParams},
+        mock::BlockData,
+        Error,
+    };
+    use eth_types::{bytecode, geth_types::GethData};
+    use mock::test_ctx::{helpers::*, TestContext};
+
+    #[test]
+    fn planned_chunks_fit_max_rws() {
+        let mut code = bytecode! {};
+        for i in 0..64u64 {
+            code.push(32, i);
+            code.op_pop();
+        }
+        code.op_stop();
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let max_rws = 60;
+        let circuits_params = FixedCParams {
+            max_rws,
+            max_bytecode: 4096,
+            ..Default::default()
+        };
+        let builder = BlockData::new_from_geth_data_with_params(block.clone(), circuits_params)
+            .new_circuit_input_builder()
+            .handle_block_with_planned_chunks(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let total_chunks = builder.chunks.len();
+        assert!(total_chunks > 1);
+        assert_eq!(builder.circuits_params.total_chunks, total_chunks);
+        for (idx, chunk) in builder.chunks.iter().enumerate() {
+            assert_eq!(chunk.ctx.idx, idx);
+            assert_eq!(chunk.ctx.total_chunks, total_chunks);
+            assert_eq!(chunk.fixed_param.max_rws, max_rws);
+            assert!(chunk.row_usage.rws < max_rws);
+        }
+        for chunks in builder.chunks.windows(2) {
+            assert_eq!(chunks[0].ctx.end_rwc, chunks[1].ctx.initial_rwc);
+        }
+        // every step is accounted in exactly one chunk
+        assert_eq!(
+            builder
+                .chunks
+                .iter()
+                .map(|chunk| chunk.row_usage.exec_steps)
+                .sum::<usize>(),
+            builder.block.txs()[0].steps().len()
+        );
+    }
+
+    #[test]
+    fn planned_chunks_exceed_block_capacity() {
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode! {
+            PUSH1(0x20) // size
+            PUSH1(0x00) // offset
+            PUSH1(0x00) // dest_offset
+            CALLDATACOPY
+            STOP
+        })
+        .unwrap()
+        .into();
+
+        // more chunks don't fit more bytecode
+        let circuits_params = FixedCParams {
+            max_rws: 60,
+            max_bytecode: 4,
+            ..Default::default()
+        };
+        let result = BlockData::new_from_geth_data_with_params(block.clone(), circuits_params)
+            .new_circuit_input_builder()
+            .handle_block_with_planned_chunks(&block.eth_block, &block.geth_traces);
+        assert!(matches!(
+            result,
+            Err(Error::CapacityExceeded(CapacityExceeded::Bytecode))
+        ));
+
+        let circuits_params = FixedCParams {
+            max_rws: 60,
+            max_copy_rows: 2,
+            ..Default::default()
+        };
+        let result = BlockData::new_from_geth_data_with_params(block.clone(), circuits_params)
+            .new_circuit_input_builder()
+            .handle_block_with_planned_chunks(&block.eth_block, &block.geth_traces);
+        assert!(matches!(
+            result,
+            Err(Error::CapacityExceeded(CapacityExceeded::CopyRows))
+        ));
+    }
+}
@@ -2,14 +2,24 @@
 
 use crate::{
     circuit_input_builder::CallContext,
-    error::{ExecError, OogError},
+    error::{DepthError, ExecError, OogError},
     exec_trace::OperationRef,
     operation::RWCounter,
     precompile::{PrecompileAuxData, PrecompileCalls},
 };
-use eth_types::{evm_types::OpcodeId, sign_types::SignData, GethExecStep, Word, H256};
+use eth_types::{
+    evm_types::OpcodeId, sign_types::SignData, GethExecStep, ToBigEndian, ToLittleEndian, Word,
+    H256,
+};
 use gadgets::impl_expr;
-use halo2_proofs::plonk::Expression;
+use halo2_proofs::{
+    halo2curves::{
+        bn256::{Fq, Fq2, Fr, G1Affine, G2Affine},
+        group::prime::PrimeCurveAffine,
+        CurveAffine,
+    },
+    plonk::Expression,
+};
 use strum_macros::EnumIter;
 
 /// An execution step of the EVM.
@@ -138,6 +148,11 @@ impl ExecStep {
     pub fn is_precompile_oog_err(&self) -> bool {
         matches!(self.error, Some(ExecError::OutOfGas(OogError::Precompile)))
     }
+
+    /// Returns `true` if `error` is the call depth limit being exceeded
+    pub fn is_call_depth_err(&self) -> bool {
+        matches!(self.error, Some(ExecError::Depth(DepthError::Call)))
+    }
 }
 
 /// Execution state
@@ -305,8 +320,29 @@ impl CopyEvent {
         self.rw_counter_increase(self.bytes.len() * 2)
     }
 
+    /// Whether this is a copy within the memory of a single call (MCOPY). All the source bytes
+    /// are read before any destination byte is written, so that overlapping ranges are copied as
+    /// if through an intermediate buffer.
+    pub fn is_memory_copy(&self) -> bool {
+        self.src_type == CopyDataType::Memory
+            && self.dst_type == CopyDataType::Memory
+            && self.src_id == self.dst_id
+    }
+
     // increase in rw counter from the start of the copy event to step index
     fn rw_counter_increase(&self, step_index: usize) -> u64 {
+        if self.is_memory_copy() {
+            // Reads take the first half of the rw counters, writes the second half.
+            let length = u64::try_from(self.bytes.len()).unwrap();
+            let idx = u64::try_from(step_index).unwrap();
+            return if idx >= 2 * length {
+                2 * length
+            } else if idx % 2 == 0 {
+                idx / 2
+            } else {
+                length + idx / 2
+            };
+        }
         let source_rw_increase = match self.src_type {
             CopyDataType::Bytecode | CopyDataType::TxCalldata | CopyDataType::RlcAcc => 0,
             CopyDataType::Memory => std::cmp::min(
@@ -390,9 +426,93 @@ impl PrecompileEvents {
     pub fn get_ecrecover_events(&self) -> Vec<SignData> {
         self.events
             .iter()
-            .map(|e| {
-                let PrecompileEvent::Ecrecover(sign_data) = e;
-                sign_data
+            .filter_map(|e| match e {
+                PrecompileEvent::Ecrecover(sign_data) => Some(sign_data),
+                _ => None,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Get the inputs of all SHA256 events.
+    pub fn get_sha256_events(&self) -> Vec<Vec<u8>> {
+        self.events
+            .iter()
+            .filter_map(|e| match e {
+                PrecompileEvent::Sha256(input) => Some(input),
+                _ => None,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Get the inputs of all RIPEMD-160 events.
+    pub fn get_ripemd160_events(&self) -> Vec<Vec<u8>> {
+        self.events
+            .iter()
+            .filter_map(|e| match e {
+                PrecompileEvent::Ripemd160(input) => Some(input),
+                _ => None,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Get all MODEXP events.
+    pub fn get_modexp_events(&self) -> Vec<BigModExp> {
+        self.events
+            .iter()
+            .filter_map(|e| match e {
+                PrecompileEvent::ModExp(modexp) => Some(modexp),
+                _ => None,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Get all ecAdd events.
+    pub fn get_ec_add_events(&self) -> Vec<EcAddOp> {
+        self.events
+            .iter()
+            .filter_map(|e| match e {
+                PrecompileEvent::EcAdd(op) => Some(op),
+                _ => None,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Get all ecMul events.
+    pub fn get_ec_mul_events(&self) -> Vec<EcMulOp> {
+        self.events
+            .iter()
+            .filter_map(|e| match e {
+                PrecompileEvent::EcMul(op) => Some(op),
+                _ => None,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Get all ecPairing events.
+    pub fn get_ec_pairing_events(&self) -> Vec<EcPairingOp> {
+        self.events
+            .iter()
+            .filter_map(|e| match e {
+                PrecompileEvent::EcPairing(op) => Some(op.as_ref()),
+                _ => None,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Get the inputs of all BLAKE2F events.
+    pub fn get_blake2f_events(&self) -> Vec<Vec<u8>> {
+        self.events
+            .iter()
+            .filter_map(|e| match e {
+                PrecompileEvent::Blake2F(input) => Some(input),
+                _ => None,
             })
             .cloned()
             .collect()
@@ -404,6 +524,20 @@ impl PrecompileEvents {
 pub enum PrecompileEvent {
     /// Represents the I/O from Ecrecover call.
     Ecrecover(SignData),
+    /// Represents the input to a successful SHA256 call.
+    Sha256(Vec<u8>),
+    /// Represents the input to a successful RIPEMD-160 call.
+    Ripemd160(Vec<u8>),
+    /// Represents the 213 input bytes to a successful BLAKE2F call.
+    Blake2F(Vec<u8>),
+    /// Represents the I/O from a MODEXP call, successful or not.
+    ModExp(BigModExp),
+    /// Represents the I/O from an ecAdd call, successful or not.
+    EcAdd(EcAddOp),
+    /// Represents the I/O from an ecMul call, successful or not.
+    EcMul(EcMulOp),
+    /// Represents the I/O from an ecPairing call with at least one pair, successful or not.
+    EcPairing(Box<EcPairingOp>),
 }
 
 impl Default for PrecompileEvent {
@@ -412,9 +546,222 @@ impl Default for PrecompileEvent {
     }
 }
 
+/// The I/O of a MODEXP call, with a base, exponent and modulus of at most
+/// `MODEXP_SIZE_LIMIT` bytes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BigModExp {
+    /// Input bytes of the call, truncated to `MODEXP_INPUT_LIMIT` bytes.
+    pub input: Vec<u8>,
+    /// Big-endian base.
+    pub base: Vec<u8>,
+    /// Big-endian exponent.
+    pub exponent: Vec<u8>,
+    /// Big-endian modulus.
+    pub modulus: Vec<u8>,
+    /// `base ^ exponent % modulus` in as many big-endian bytes as the modulus, all zero when the
+    /// modulus is zero.
+    pub output: Vec<u8>,
+}
+
 /// The number of pairing inputs per pairing operation. If the inputs provided to the precompile
-/// call are < 4, we append (G1::infinity, G2::generator) until we have the required no. of inputs.
+/// call are < 4, we append zero pairs (G1::infinity, G2::infinity) until we have the required no.
+/// of inputs. This is the same as right-padding the input with zero bytes, and doesn't change the
+/// result of the pairing check.
 pub const N_PAIRING_PER_OP: usize = 4;
 
 /// The number of bytes taken to represent a pair (G1, G2).
 pub const N_BYTES_PER_PAIR: usize = 192;
+
+/// Parses the big-endian coordinates of a G1 point, returning `None` if a coordinate is not in the
+/// base field or the point is neither on the curve nor the point at infinity `(0, 0)`.
+fn g1_from_words(x: Word, y: Word) -> Option<G1Affine> {
+    let x = Option::<Fq>::from(Fq::from_bytes(&x.to_le_bytes()))?;
+    let y = Option::<Fq>::from(Fq::from_bytes(&y.to_le_bytes()))?;
+    G1Affine::from_xy(x, y).into()
+}
+
+/// Reads the `idx`-th big-endian word of an input, right-padded with zeros.
+fn input_word(input: &[u8], idx: usize) -> Word {
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = input.get(32 * idx + i).copied().unwrap_or_default();
+    }
+    Word::from_big_endian(&bytes)
+}
+
+/// The I/O of an ecAdd call, `R = P + Q` on the bn254 curve.
+#[derive(Clone, Debug, Default)]
+pub struct EcAddOp {
+    /// The first point, as the (x, y) words of the input.
+    pub p: (Word, Word),
+    /// The second point, as the (x, y) words of the input.
+    pub q: (Word, Word),
+    /// The sum of the points, or `None` if a point is invalid.
+    pub r: Option<G1Affine>,
+}
+
+impl EcAddOp {
+    /// Creates the op from the input bytes of the call, right-padded with zeros.
+    pub fn new(input: &[u8]) -> Self {
+        let p = (input_word(input, 0), input_word(input, 1));
+        let q = (input_word(input, 2), input_word(input, 3));
+        let r = g1_from_words(p.0, p.1)
+            .zip(g1_from_words(q.0, q.1))
+            .map(|(p, q)| G1Affine::from(p + q));
+        Self { p, q, r }
+    }
+
+    /// Whether the inputs are valid points, i.e. whether the call succeeds.
+    pub fn is_valid(&self) -> bool {
+        self.r.is_some()
+    }
+}
+
+/// The I/O of an ecMul call, `R = s.P` on the bn254 curve.
+#[derive(Clone, Debug, Default)]
+pub struct EcMulOp {
+    /// The point, as the (x, y) words of the input.
+    pub p: (Word, Word),
+    /// The scalar, reduced modulo the order of the curve.
+    pub s: Fr,
+    /// The product, or `None` if the point is invalid.
+    pub r: Option<G1Affine>,
+}
+
+impl EcMulOp {
+    /// Creates the op from the input bytes of the call, right-padded with zeros.
+    pub fn new(input: &[u8]) -> Self {
+        let p = (input_word(input, 0), input_word(input, 1));
+        // `from_raw` reduces the 256-bit scalar modulo the order of the curve.
+        let s = Fr::from_raw(input_word(input, 2).0);
+        let r = g1_from_words(p.0, p.1).map(|p| G1Affine::from(p * s));
+        Self { p, s, r }
+    }
+
+    /// Whether the input is a valid point, i.e. whether the call succeeds.
+    pub fn is_valid(&self) -> bool {
+        self.r.is_some()
+    }
+}
+
+/// A (G1, G2) pair of an ecPairing call, as the words of the input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EcPairingPair {
+    /// The (x, y) coordinates of the G1 point.
+    pub g1_point: (Word, Word),
+    /// The coordinates of the G2 point in the order of the input, i.e. (x.c1, x.c0, y.c1, y.c0).
+    pub g2_point: (Word, Word, Word, Word),
+}
+
+impl EcPairingPair {
+    /// Creates a pair from its points.
+    pub fn new(g1: G1Affine, g2: G2Affine) -> Self {
+        let word = |fq: Fq| Word::from_little_endian(&fq.to_bytes());
+        Self {
+            g1_point: (word(g1.x), word(g1.y)),
+            g2_point: (word(g2.x.c1), word(g2.x.c0), word(g2.y.c1), word(g2.y.c0)),
+        }
+    }
+
+    /// The zero pair (G1::infinity, G2::infinity) used to pad the input of a call.
+    pub fn padding_pair() -> Self {
+        Self::default()
+    }
+
+    /// Creates a pair from its `N_BYTES_PER_PAIR` input bytes, right-padded with zeros.
+    fn from_bytes_be(input: &[u8]) -> Self {
+        let word = |idx| input_word(input, idx);
+        Self {
+            g1_point: (word(0), word(1)),
+            g2_point: (word(2), word(3), word(4), word(5)),
+        }
+    }
+
+    /// The `N_BYTES_PER_PAIR` input bytes of the pair.
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        [
+            self.g1_point.0,
+            self.g1_point.1,
+            self.g2_point.0,
+            self.g2_point.1,
+            self.g2_point.2,
+            self.g2_point.3,
+        ]
+        .iter()
+        .flat_map(|word| word.to_be_bytes())
+        .collect()
+    }
+
+    /// Returns the points of the pair, or `None` if a coordinate is not in the base field or a
+    /// point is neither on its curve nor the point at infinity.
+    pub fn as_g1_g2(&self) -> Option<(G1Affine, G2Affine)> {
+        let g1 = g1_from_words(self.g1_point.0, self.g1_point.1)?;
+        let fq = |word: Word| Option::<Fq>::from(Fq::from_bytes(&word.to_le_bytes()));
+        let x = Fq2 {
+            c0: fq(self.g2_point.1)?,
+            c1: fq(self.g2_point.0)?,
+        };
+        let y = Fq2 {
+            c0: fq(self.g2_point.3)?,
+            c1: fq(self.g2_point.2)?,
+        };
+        let g2 = Option::<G2Affine>::from(G2Affine::from_xy(x, y))?;
+        Some((g1, g2))
+    }
+}
+
+/// The I/O of an ecPairing call with at most `N_PAIRING_PER_OP` pairs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EcPairingOp {
+    /// The pairs of the input, padded with zero pairs.
+    pub pairs: [EcPairingPair; N_PAIRING_PER_OP],
+    /// The output of the call, 1 if the pairing check succeeds and 0 otherwise.
+    pub output: Word,
+    /// The input bytes of the call, without padding.
+    pub input_bytes: Vec<u8>,
+}
+
+impl Default for EcPairingOp {
+    fn default() -> Self {
+        Self::dummy_pairing_check_ok()
+    }
+}
+
+impl EcPairingOp {
+    /// Creates the op from the input bytes and the output of the call.
+    pub fn new(input: &[u8], output: Word) -> Self {
+        debug_assert!(input.len() <= N_PAIRING_PER_OP * N_BYTES_PER_PAIR);
+        let pairs = std::array::from_fn(|idx| {
+            input
+                .get(idx * N_BYTES_PER_PAIR..)
+                .map_or_else(EcPairingPair::padding_pair, EcPairingPair::from_bytes_be)
+        });
+        Self {
+            pairs,
+            output,
+            input_bytes: input.to_vec(),
+        }
+    }
+
+    /// An op with non-trivial pairs that satisfies the pairing check, i.e.
+    /// e(G1, G2) * e(-G1, G2) * e(G1, G2) * e(-G1, G2) == 1.
+    pub fn dummy_pairing_check_ok() -> Self {
+        let (g1, g2) = (G1Affine::generator(), G2Affine::generator());
+        let pairs = [
+            EcPairingPair::new(g1, g2),
+            EcPairingPair::new(-g1, g2),
+            EcPairingPair::new(g1, g2),
+            EcPairingPair::new(-g1, g2),
+        ];
+        Self {
+            input_bytes: pairs.iter().flat_map(EcPairingPair::to_bytes_be).collect(),
+            pairs,
+            output: Word::one(),
+        }
+    }
+
+    /// Whether all the points are valid, i.e. whether the call succeeds.
+    pub fn is_valid(&self) -> bool {
+        self.pairs.iter().all(|pair| pair.as_g1_g2().is_some())
+    }
+}
@@ -3,15 +3,18 @@
 use super::{
     get_call_memory_offset_length, get_create_init_code, Block, BlockContext, Call, CallContext,
     CallKind, ChunkContext, CodeSource, CopyEvent, ExecState, ExecStep, ExpEvent, PrecompileEvent,
-    Transaction, TransactionContext,
+    Transaction, TransactionContext, N_BYTES_PER_PAIR, N_PAIRING_PER_OP,
 };
 use crate::{
-    error::{DepthError, ExecError, InsufficientBalanceError, NonceUintOverflowError},
+    error::{
+        ContractAddressCollisionError, DepthError, ExecError, InsufficientBalanceError,
+        NonceUintOverflowError,
+    },
     exec_trace::OperationRef,
     operation::{
         AccountField, AccountOp, CallContextField, CallContextOp, MemoryOp, Op, OpEnum, Operation,
-        StackOp, Target, TxAccessListAccountOp, TxLogField, TxLogOp, TxReceiptField, TxReceiptOp,
-        RW,
+        StackOp, Target, TxAccessListAccountOp, TxCreatedAccountOp, TxLogField, TxLogOp,
+        TxReceiptField, TxReceiptOp, RW,
     },
     precompile::{is_precompiled, PrecompileCalls},
     state_db::{CodeDB, StateDB},
@@ -19,7 +22,8 @@ use crate::{
 };
 use eth_types::{
     evm_types::{
-        gas_utils::memory_expansion_gas_cost, GasCost, MemoryAddress, OpcodeId, StackAddress,
+        gas_utils::memory_expansion_gas_cost, GasCost, Hardfork, MemoryAddress, OpcodeId,
+        StackAddress,
     },
     Address, Bytecode, GethExecStep, ToAddress, ToBigEndian, ToWord, Word, H256, U256,
 };
@@ -45,6 +49,8 @@ pub struct CircuitInputStateRef<'a> {
     pub tx_ctx: &'a mut TransactionContext,
     /// Max rw number limit
     pub max_rws: Option<usize>,
+    /// Hardfork whose rules apply to the block
+    pub hardfork: Hardfork,
 }
 
 impl<'a> CircuitInputStateRef<'a> {
@@ -567,6 +573,44 @@ impl<'a> CircuitInputStateRef<'a> {
         )
     }
 
+    /// Mark address as created in the current transaction.
+    pub fn tx_created_account_write(
+        &mut self,
+        step: &mut ExecStep,
+        address: Address,
+    ) -> Result<(), Error> {
+        let is_created = self.sdb.check_account_created(&address);
+        self.push_op_reversible(
+            step,
+            TxCreatedAccountOp {
+                tx_id: self.tx_ctx.id(),
+                address,
+                is_created: true,
+                is_created_prev: is_created,
+            },
+        )
+    }
+
+    /// Read whether address has been created in the current transaction.
+    pub fn tx_created_account_read(
+        &mut self,
+        step: &mut ExecStep,
+        address: Address,
+    ) -> Result<bool, Error> {
+        let is_created = self.sdb.check_account_created(&address);
+        self.push_op(
+            step,
+            RW::READ,
+            TxCreatedAccountOp {
+                tx_id: self.tx_ctx.id(),
+                address,
+                is_created,
+                is_created_prev: is_created,
+            },
+        )?;
+        Ok(is_created)
+    }
+
     /// Push 2 reversible [`AccountOp`] to update `sender` and `receiver`'s
     /// balance by `value`. If `fee` is existing (not None), also need to push 1
     /// non-reversible [`AccountOp`] to update `sender` balance by `fee`.
@@ -959,6 +1003,14 @@ impl<'a> CircuitInputStateRef<'a> {
                     None
                 }
             }
+            OperationRef(Target::TxCreatedAccount, idx) => {
+                let operation = &self.block.container.tx_created_account[*idx];
+                if operation.rw().is_write() && operation.reversible() {
+                    Some(OpEnum::TxCreatedAccount(operation.op().reverse()))
+                } else {
+                    None
+                }
+            }
             OperationRef(Target::TxRefund, idx) => {
                 let operation = &self.block.container.tx_refund[*idx];
                 if operation.rw().is_write() && operation.reversible() {
@@ -1007,6 +1059,14 @@ impl<'a> CircuitInputStateRef<'a> {
                         .remove_account_storage_from_access_list(&(op.address, op.key));
                 }
             }
+            OpEnum::TxCreatedAccount(op) => {
+                if !op.is_created_prev && op.is_created {
+                    self.sdb.add_created_account(op.address);
+                }
+                if op.is_created_prev && !op.is_created {
+                    self.sdb.remove_created_account(&op.address);
+                }
+            }
             OpEnum::Account(op) => self.check_update_sdb_account(RW::WRITE, op),
             OpEnum::TxRefund(op) => {
                 self.sdb.set_refund(op.value);
@@ -1085,15 +1145,16 @@ impl<'a> CircuitInputStateRef<'a> {
             }
         }
         if need_restore {
-            // The only case where `current_exec_steps` are more than 1 is for precompiled contract
-            // calls. In this case, we have: [..., CALLOP, PRECOMPILE_EXEC_STEP]
-            // as the current execution steps. And in case of return, we
-            // restore context from the internal precompile execution
+            // The only cases where `current_exec_steps` are more than 1 are precompiled contract
+            // calls and calls exceeding the depth limit. In these cases, we have:
+            // [..., CALLOP, PRECOMPILE_EXEC_STEP | ERROR_DEPTH_STEP] as the current execution
+            // steps. And in case of return, we restore context from the internal execution
             // step to the `CALLOP` step.
             if current_exec_steps.len() > 1 {
                 debug_assert!(
                     current_exec_steps[1].is_precompiled()
                         || current_exec_steps[1].is_precompile_oog_err()
+                        || current_exec_steps[1].is_call_depth_err()
                 );
             }
 
@@ -1212,6 +1273,7 @@ impl<'a> CircuitInputStateRef<'a> {
             && !call.is_success
             && !exec_step.is_precompiled()
             && !exec_step.is_precompile_oog_err()
+            && !exec_step.is_call_depth_err()
         {
             // add call failure ops for exception cases
             self.call_context_read(
@@ -1281,7 +1343,10 @@ impl<'a> CircuitInputStateRef<'a> {
                     _ => [Word::zero(), Word::zero()],
                 }
             };
-        let gas_refund = if exec_step.error.is_some() {
+        let gas_refund = if exec_step.is_call_depth_err() {
+            // the callee of a call exceeding the depth limit returns all of its gas
+            exec_step.gas_left
+        } else if exec_step.error.is_some() {
             0
         } else if exec_step.is_precompiled() {
             exec_step.gas_left - exec_step.gas_cost
@@ -1395,7 +1460,7 @@ impl<'a> CircuitInputStateRef<'a> {
             return Ok(Some(error));
         }
 
-        if matches!(step.op, OpcodeId::INVALID(_)) {
+        if matches!(step.op, OpcodeId::INVALID(_)) || !self.hardfork.is_opcode_enabled(step.op) {
             return Ok(Some(ExecError::InvalidOpcode));
         }
 
@@ -1571,13 +1636,24 @@ impl<'a> CircuitInputStateRef<'a> {
                 })));
             }
 
-            // Address collision
-            if matches!(step.op, OpcodeId::CREATE2) {
-                let address = self.create2_address(step)?;
+            // Address collision, an account with either a nonce or code already
+            // lives at the contract address.
+            if matches!(step.op, OpcodeId::CREATE | OpcodeId::CREATE2) {
+                let (address, collision_error) = if step.op == OpcodeId::CREATE {
+                    (
+                        self.create_address()?,
+                        ContractAddressCollisionError::Create,
+                    )
+                } else {
+                    (
+                        self.create2_address(step)?,
+                        ContractAddressCollisionError::Create2,
+                    )
+                };
 
-                let (found, _) = self.sdb.get_account(&address);
-                if found {
-                    return Ok(Some(ExecError::ContractAddressCollision));
+                let (found, account) = self.sdb.get_account(&address);
+                if found && (account.nonce != 0 || account.code_hash != CodeDB::empty_code_hash()) {
+                    return Ok(Some(ExecError::ContractAddressCollision(collision_error)));
                 }
             }
 
@@ -1591,35 +1667,53 @@ impl<'a> CircuitInputStateRef<'a> {
                 //   because the callGasTemp might probably be smaller than the gas
                 //   on top of the stack (step.stack.last())
                 // Therefore we postpone the oog handling to the implementor of callop.
-                if is_precompiled(&code_address) {
+                if is_precompiled(&code_address, self.hardfork) {
                     let precompile_call: PrecompileCalls = code_address[19].into();
-                    match precompile_call {
-                        PrecompileCalls::Sha256
-                        | PrecompileCalls::Ripemd160
-                        | PrecompileCalls::Blake2F
-                        | PrecompileCalls::Bn128Add
-                        | PrecompileCalls::Bn128Mul
-                        | PrecompileCalls::Bn128Pairing
-                        | PrecompileCalls::Modexp => {
-                            // Log the precompile address and gas left.
-                            // Failure due to precompile being unsupported.
-                            // Failure cases are routed to `PrecompileFailed` dummy gadget.
-                            log::trace!(
-                                "Precompile failed: code_address = {}, step.gas = {}",
-                                code_address,
-                                step.gas,
-                            );
+                    let (args_offset, args_length) = get_call_memory_offset_length(
+                        step,
+                        if matches!(step.op, OpcodeId::CALL | OpcodeId::CALLCODE) {
+                            3
+                        } else {
+                            2
+                        },
+                    )?;
+                    // BLAKE2F rejects a final block indicator flag other than 0 or 1 before
+                    // charging any gas. The BLAKE2F circuit only accepts valid flags, so such
+                    // calls are routed to the `PrecompileFailed` dummy gadget as well.
+                    if precompile_call == PrecompileCalls::Blake2F && args_length == 213 {
+                        let memory = &self.call_ctx()?.memory;
+                        let flag_offset = (args_offset + 212) as usize;
+                        let flag = memory.0.get(flag_offset).copied().unwrap_or_default();
+                        if flag > 1 {
                             return Ok(Some(ExecError::UnimplementedPrecompiles));
                         }
-                        pre_call => {
-                            log::trace!(
-                                "Precompile call failed: addr={:?}, step.gas={:?}",
-                                pre_call,
-                                step.gas
-                            );
-                            return Ok(None);
+                    }
+                    // The ECC circuit only verifies ecAdd and ecMul inputs of the exact length,
+                    // and ecPairing inputs of at most `N_PAIRING_PER_OP` pairs. Failures with
+                    // other inputs are routed to the `PrecompileFailed` dummy gadget.
+                    let is_unsupported_ec_input = match precompile_call {
+                        PrecompileCalls::Bn128Add | PrecompileCalls::Bn128Mul => {
+                            precompile_call.input_len() != Some(args_length as usize)
                         }
+                        PrecompileCalls::Bn128Pairing => {
+                            args_length as usize > N_PAIRING_PER_OP * N_BYTES_PER_PAIR
+                        }
+                        _ => false,
+                    };
+                    if is_unsupported_ec_input {
+                        log::trace!(
+                            "Precompile failed: code_address = {}, step.gas = {}",
+                            code_address,
+                            step.gas,
+                        );
+                        return Ok(Some(ExecError::UnimplementedPrecompiles));
                     }
+                    log::trace!(
+                        "Precompile call failed: addr={:?}, step.gas={:?}",
+                        precompile_call,
+                        step.gas
+                    );
+                    return Ok(None);
                 }
             }
 
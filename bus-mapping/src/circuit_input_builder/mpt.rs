@@ -0,0 +1,155 @@
+//! Account and storage trie modifications of a [`Block`], used to generate the
+//! witness of the MPT circuit.
+
+use super::{Block, CircuitInputBuilder, CircuitsParams};
+use crate::{
+    mpt::{ProofType, StateTrie, TrieModification},
+    operation::AccountField,
+    Error,
+};
+use eth_types::{Address, ToBigEndian, Word, H256, U64};
+use std::collections::BTreeMap;
+
+impl Block {
+    /// Return the ordered list of trie modifications done in this block.
+    ///
+    /// There is one modification for each account field and each storage slot
+    /// accessed in the block, carrying its final value.  The order is the one
+    /// of the State circuit rows sorted by address: account fields first,
+    /// sorted by address and field, then storage slots, sorted by transaction,
+    /// address and key.  This way the i-th proof generated for these
+    /// modifications corresponds to the i-th update of the `MptTable`.
+    pub fn trie_modifications(&self) -> Vec<TrieModification> {
+        // (value_prev of the first access, value of the last access) by key
+        let mut accounts: BTreeMap<(Address, AccountField), (Word, Word)> = BTreeMap::new();
+        for op in self.container.account.iter().map(|op| op.op()) {
+            accounts
+                .entry((op.address, op.field))
+                .and_modify(|(_, value)| *value = op.value)
+                .or_insert((op.value_prev, op.value));
+        }
+        let mut storage: BTreeMap<(usize, Address, Word), (Word, Word)> = BTreeMap::new();
+        for op in self.container.storage.iter().map(|op| op.op()) {
+            storage
+                .entry((op.tx_id, op.address, op.key))
+                .and_modify(|(_, value)| *value = op.value)
+                .or_insert((op.value_prev, op.value));
+        }
+
+        let account_modifications =
+            accounts
+                .into_iter()
+                .map(|((address, field), (value_prev, value))| match field {
+                    AccountField::Nonce => TrieModification {
+                        typ: ProofType::NonceChanged,
+                        address,
+                        nonce: U64::from(value.low_u64()),
+                        ..Default::default()
+                    },
+                    AccountField::Balance => TrieModification {
+                        typ: ProofType::BalanceChanged,
+                        address,
+                        balance: value,
+                        ..Default::default()
+                    },
+                    AccountField::CodeHash => TrieModification {
+                        // A code hash of 0 that isn't modified means the account
+                        // doesn't exist, which must be proven instead.
+                        typ: if value_prev.is_zero() && value.is_zero() {
+                            ProofType::AccountDoesNotExist
+                        } else {
+                            ProofType::CodeHashChanged
+                        },
+                        address,
+                        code_hash: H256::from(value.to_be_bytes()),
+                        ..Default::default()
+                    },
+                });
+        let storage_modifications = storage.into_iter().map(
+            |((_, address, key), (value_prev, value))| TrieModification {
+                typ: if value_prev.is_zero() && value.is_zero() {
+                    ProofType::StorageDoesNotExist
+                } else {
+                    ProofType::StorageChanged
+                },
+                address,
+                key: H256::from(key.to_be_bytes()),
+                value,
+                ..Default::default()
+            },
+        );
+
+        account_modifications.chain(storage_modifications).collect()
+    }
+}
+
+impl<C: CircuitsParams> CircuitInputBuilder<C> {
+    /// Generate the MPT witness proving the [`Block::trie_modifications`]
+    /// against `state_trie`, which must hold the state of the previous block:
+    /// either built with [`StateTrie::from_proofs`] from the `eth_getProof`
+    /// responses of the accessed accounts and storage slots, or with
+    /// [`StateTrie::from_state_db`] from the state before the block.  The
+    /// modifications are applied to `state_trie`, and the JSON encoded witness
+    /// is stored in [`Block::mpt_witness`].
+    ///
+    /// Must be called after the block has been handled.
+    pub fn gen_mpt_witness(&mut self, state_trie: &mut StateTrie) -> Result<(), Error> {
+        let modifications = self.block.trie_modifications();
+        self.block.mpt_witness = Some(state_trie.gen_witness(&modifications)?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mock::BlockData, mpt::ProofType};
+    use eth_types::{bytecode, geth_types::GethData, Word};
+    use mock::test_ctx::{helpers::*, TestContext};
+
+    #[test]
+    fn trie_modifications_sstore() {
+        let code = bytecode! {
+            PUSH1(0x02) // value
+            PUSH1(0x01) // key
+            SSTORE
+            PUSH1(0x03) // key
+            SLOAD
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        let builder = builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+        let modifications = builder.block.trie_modifications();
+
+        // Account fields come first, ordered by address.
+        let first_storage = modifications
+            .iter()
+            .position(|m| {
+                matches!(
+                    m.typ,
+                    ProofType::StorageChanged | ProofType::StorageDoesNotExist
+                )
+            })
+            .unwrap();
+        assert!(modifications[..first_storage]
+            .windows(2)
+            .all(|w| w[0].address <= w[1].address));
+
+        let storage = &modifications[first_storage..];
+        assert_eq!(storage.len(), 2);
+        assert!(matches!(storage[0].typ, ProofType::StorageChanged));
+        assert_eq!(storage[0].value, Word::from(2));
+        assert!(matches!(storage[1].typ, ProofType::StorageDoesNotExist));
+        assert_eq!(storage[1].value, Word::zero());
+    }
+}
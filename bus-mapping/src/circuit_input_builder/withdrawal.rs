@@ -1,6 +1,6 @@
 //! Withdrawal & WithdrawalContext utility module.
 
-use eth_types::Address;
+use eth_types::{Address, Word};
 
 use crate::Error;
 
@@ -43,8 +43,8 @@ impl Withdrawal {
         })
     }
     /// Return the amount in this withdrawal
-    pub fn amount_in_wei(&self) -> u64 {
-        self.amount * (10 ^ 9)
+    pub fn amount_in_wei(&self) -> Word {
+        Word::from(self.amount) * Word::from(1_000_000_000u64)
     }
 
     /// Constructor for padding withdrawal in withdrawal circuit
@@ -5,9 +5,12 @@ use eth_types::{evm_types::OpcodeId, Address, GethExecStep, Word, H256};
 use ethers_providers::ProviderError;
 use std::error::Error as StdError;
 
-use crate::geth_errors::{
-    GETH_ERR_GAS_UINT_OVERFLOW, GETH_ERR_OUT_OF_GAS, GETH_ERR_STACK_OVERFLOW,
-    GETH_ERR_STACK_UNDERFLOW,
+use crate::{
+    circuit_input_builder::CapacityExceeded,
+    geth_errors::{
+        GETH_ERR_GAS_UINT_OVERFLOW, GETH_ERR_OUT_OF_GAS, GETH_ERR_STACK_OVERFLOW,
+        GETH_ERR_STACK_UNDERFLOW,
+    },
 };
 
 /// Error type for any BusMapping related failure.
@@ -42,6 +45,17 @@ pub enum Error {
     InternalError(&'static str),
     /// Rw number overflow
     RwsNotEnough(usize, usize),
+    /// Capacity of the circuits params exceeded by the block
+    CapacityExceeded(CapacityExceeded),
+    /// The lengths of the base, exponent and modulus of a MODEXP call, at least one of which
+    /// exceeds the size supported by the circuits.
+    ModExpOversize(Word, Word, Word),
+    /// Number of a block before London, the first hardfork supported by the circuits.
+    UnsupportedHardfork(u64),
+    /// Trie node not found among the nodes known to the state trie
+    TrieNodeNotFound(H256),
+    /// Trie node which can't be decoded, or with an unexpected layout
+    InvalidTrieNode(Vec<u8>),
 }
 
 impl From<eth_types::Error> for Error {
@@ -76,7 +90,7 @@ pub enum OogError {
     /// expansion gas cost
     DynamicMemoryExpansion,
     /// Out of Gas for CALLDATACOPY, CODECOPY, EXTCODECOPY, RETURNDATACOPY,
-    /// which copy a specified chunk of memory
+    /// MCOPY, which copy a specified chunk of memory
     MemoryCopy,
     /// Out of Gas for BALANCE, EXTCODESIZE, EXTCODEHASH, which possibly touch
     /// an extra account
@@ -116,7 +130,8 @@ impl From<&OpcodeId> for OogError {
             OpcodeId::CALLDATACOPY
             | OpcodeId::CODECOPY
             | OpcodeId::EXTCODECOPY
-            | OpcodeId::RETURNDATACOPY => OogError::MemoryCopy,
+            | OpcodeId::RETURNDATACOPY
+            | OpcodeId::MCOPY => OogError::MemoryCopy,
             OpcodeId::BALANCE | OpcodeId::EXTCODESIZE | OpcodeId::EXTCODEHASH => {
                 OogError::AccountAccess
             }
@@ -167,6 +182,15 @@ pub enum DepthError {
     Create2,
 }
 
+/// Contract address collision errors by opcode/state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContractAddressCollisionError {
+    /// Contract address collision during CREATE opcode.
+    Create,
+    /// Contract address collision during CREATE2 opcode.
+    Create2,
+}
+
 /// EVM Execution Error
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ExecError {
@@ -185,8 +209,8 @@ pub enum ExecError {
     Depth(DepthError),
     /// For CALL, CALLCODE, CREATE, CREATE2
     InsufficientBalance(InsufficientBalanceError),
-    /// For CREATE2
-    ContractAddressCollision,
+    /// For CREATE, CREATE2
+    ContractAddressCollision(ContractAddressCollisionError),
     /// contract must not begin with 0xef due to EIP #3541 EVM Object Format
     /// (EOF)
     InvalidCreationCode,
@@ -3,7 +3,9 @@ use crate::{
     circuit_input_builder::{
         Call, CircuitInputStateRef, CopyDataType, CopyEvent, ExecState, ExecStep, NumberOrHash,
     },
-    operation::{AccountField, AccountOp, CallContextField, TxReceiptField, TxRefundOp, RW},
+    operation::{
+        AccountField, AccountOp, CallContextField, StorageOp, TxReceiptField, TxRefundOp, RW,
+    },
     state_db::CodeDB,
     Error,
 };
@@ -90,7 +92,7 @@ fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Erro
 
     let init_code_gas_cost = if state.tx.is_create() {
         // Calculate gas cost of init code for EIP-3860.
-        (state.tx.call_data.len() as u64 + 31) / 32 * eth_types::evm_types::INIT_CODE_WORD_GAS
+        (state.tx.call_data.len() as u64 + 31) / 32 * state.hardfork.init_code_word_gas()
     } else {
         0
     };
@@ -123,7 +125,10 @@ fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Erro
         )?;
     }
 
-    // Transfer with fee
+    // Transfer with fee. For EIP-4844 txs the blob fee is charged on top of the
+    // execution gas fee and is never refunded.
+    let fee = state.tx.gas_price * state.tx.gas()
+        + Word::from(state.tx.blob_gas()) * state.block.blob_base_fee;
     state.transfer(
         &mut exec_step,
         call.caller_address,
@@ -131,7 +136,7 @@ fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Erro
         callee_exists,
         call.is_create(),
         call.value,
-        Some(state.tx.gas_price * state.tx.gas()),
+        Some(fee),
     )?;
 
     // In case of contract creation we wish to verify the correctness of the
@@ -191,6 +196,8 @@ fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Erro
                     value_prev: 0.into(),
                 },
             )?;
+            // EIP 6780, mark callee as created in the current transaction
+            state.tx_created_account_write(&mut exec_step, call.address)?;
             for (field, value) in [
                 (CallContextField::Depth, call.depth.into()),
                 (
@@ -341,9 +348,58 @@ fn gen_end_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Error>
 
     end_tx(state, &mut exec_step, &call)?;
 
+    destruct_accounts(state, &mut exec_step)?;
+
     Ok(exec_step)
 }
 
+/// Wipe the accounts destructed by `SELFDESTRUCT` in the tx. Like in geth, the
+/// accounts are only removed once the tx has finished, so their code can still
+/// be called until then. The code hash, nonce, balance and every non-zero
+/// storage slot known to the StateDB are set to zero.
+fn destruct_accounts(
+    state: &mut CircuitInputStateRef,
+    exec_step: &mut ExecStep,
+) -> Result<(), Error> {
+    for address in state.sdb.destructed_accounts() {
+        let account = state.sdb.get_account(&address).1.clone();
+        // Write the code hash first: once nonce and balance are cleared the
+        // account would be encoded as non-existing.
+        for (field, value_prev) in [
+            (
+                AccountField::CodeHash,
+                if account.is_empty() {
+                    Word::zero()
+                } else {
+                    account.code_hash.to_word()
+                },
+            ),
+            (AccountField::Nonce, account.nonce.into()),
+            (AccountField::Balance, account.balance),
+        ] {
+            state.account_write(exec_step, address, field, Word::zero(), value_prev, false)?;
+        }
+
+        for (key, value_prev) in state.sdb.get_storage_slots(&address) {
+            let committed_value = *state.sdb.get_committed_storage(&address, &key).1;
+            state.push_op(
+                exec_step,
+                RW::WRITE,
+                StorageOp::new(
+                    address,
+                    key,
+                    Word::zero(),
+                    value_prev,
+                    state.tx_ctx.id(),
+                    committed_value,
+                ),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
 pub(crate) fn begin_tx(
     state: &mut CircuitInputStateRef,
     exec_step: &mut ExecStep,
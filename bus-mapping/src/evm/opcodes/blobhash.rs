@@ -0,0 +1,159 @@
+use super::Opcode;
+use crate::{
+    circuit_input_builder::{CircuitInputStateRef, ExecStep},
+    operation::CallContextField,
+    Error,
+};
+use eth_types::GethExecStep;
+
+/// Placeholder structure used to implement [`Opcode`] trait over it
+/// corresponding to the [`OpcodeId::BLOBHASH`](crate::evm::OpcodeId::BLOBHASH)
+/// `OpcodeId`.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Blobhash;
+
+impl Opcode for Blobhash {
+    fn gen_associated_ops(
+        state: &mut CircuitInputStateRef,
+        geth_steps: &[GethExecStep],
+    ) -> Result<Vec<ExecStep>, Error> {
+        let geth_step = &geth_steps[0];
+        let mut exec_step = state.new_step(geth_step)?;
+
+        // Stack read of the blob index
+        let index = geth_step.stack.last()?;
+        state.stack_read(&mut exec_step, geth_step.stack.last_filled(), index)?;
+
+        // CallContext read of the TxId
+        let tx_id = state.tx_ctx.id();
+        state.call_context_read(
+            &mut exec_step,
+            state.call()?.call_id,
+            CallContextField::TxId,
+            tx_id.into(),
+        )?;
+
+        // Stack write of the versioned hash, which is zero if the index is out
+        // of range
+        let blob_hash = geth_steps[1].stack.last()?;
+        state.stack_write(&mut exec_step, geth_step.stack.last_filled(), blob_hash)?;
+
+        Ok(vec![exec_step])
+    }
+}
+
+#[cfg(test)]
+mod blobhash_tests {
+    use crate::{
+        circuit_input_builder::ExecState,
+        mock::BlockData,
+        operation::{CallContextField, CallContextOp, StackOp, RW},
+        Error,
+    };
+    use eth_types::{
+        bytecode,
+        evm_types::{OpcodeId, StackAddress},
+        geth_types::GethData,
+        ToWord, Word, H256,
+    };
+    use mock::test_ctx::{helpers::*, TestContext};
+    use pretty_assertions::assert_eq;
+
+    fn test_ok(index: Word, blob_hashes: Vec<H256>) -> Result<(), Error> {
+        let code = bytecode! {
+            PUSH32(index)
+            BLOBHASH
+            STOP
+        };
+
+        // Get the execution steps from the external tracer
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            |mut txs, accs| {
+                txs[0]
+                    .from(accs[1].address)
+                    .to(accs[0].address)
+                    .transaction_type(3)
+                    .max_fee_per_blob_gas(Word::one())
+                    .blob_versioned_hashes(blob_hashes.clone());
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        let builder = builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::BLOBHASH))
+            .unwrap();
+
+        let expected = blob_hashes
+            .get(index.low_u64() as usize)
+            .filter(|_| index.bits() <= 64)
+            .map(|hash| hash.to_word())
+            .unwrap_or_default();
+
+        let call_id = builder.block.txs()[0].calls()[0].call_id;
+        assert_eq!(
+            {
+                let operation =
+                    &builder.block.container.call_context[step.bus_mapping_instance[1].as_usize()];
+                (operation.rw(), operation.op())
+            },
+            (
+                RW::READ,
+                &CallContextOp {
+                    call_id,
+                    field: CallContextField::TxId,
+                    value: Word::one(),
+                }
+            )
+        );
+
+        assert_eq!(
+            [0, 2]
+                .map(|idx| &builder.block.container.stack
+                    [step.bus_mapping_instance[idx].as_usize()])
+                .map(|operation| (operation.rw(), operation.op())),
+            [
+                (RW::READ, &StackOp::new(1, StackAddress::from(1023), index)),
+                (
+                    RW::WRITE,
+                    &StackOp::new(1, StackAddress::from(1023), expected)
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    fn mock_blob_hashes(n: u8) -> Vec<H256> {
+        (1..=n)
+            .map(|i| {
+                let mut hash = [i; 32];
+                // versioned hashes must start with VERSIONED_HASH_VERSION_KZG
+                hash[0] = 0x01;
+                H256(hash)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn blobhash_opcode_impl() -> Result<(), Error> {
+        test_ok(Word::zero(), mock_blob_hashes(1))?;
+        test_ok(Word::from(2), mock_blob_hashes(3))
+    }
+
+    #[test]
+    fn blobhash_opcode_out_of_range() -> Result<(), Error> {
+        test_ok(Word::from(2), mock_blob_hashes(2))?;
+        test_ok(Word::MAX, mock_blob_hashes(1))
+    }
+}
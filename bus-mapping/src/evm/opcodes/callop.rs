@@ -3,12 +3,13 @@ use crate::{
     circuit_input_builder::{
         CallKind, CircuitInputStateRef, CodeSource, CopyDataType, CopyEvent, ExecStep, NumberOrHash,
     },
+    error::{DepthError, ExecError},
     evm::opcodes::{
         error_oog_precompile::ErrorOOGPrecompile,
         precompiles::gen_associated_ops as precompile_associated_ops,
     },
     operation::{AccountField, CallContextField, TxAccessListAccountOp},
-    precompile::{execute_precompiled, is_precompiled, PrecompileCalls},
+    precompile::{execute_precompiled, is_precompiled, PrecompileAuxData, PrecompileCalls},
     state_db::CodeDB,
     Error,
 };
@@ -180,7 +181,7 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
 
         let code_address = call.code_address();
         let is_precompile = code_address
-            .map(|ref addr| is_precompiled(addr))
+            .map(|ref addr| is_precompiled(addr, state.hardfork))
             .unwrap_or(false);
         // CALLCODE does not need to do real transfer
         // Transfer value only when all these conditions met:
@@ -240,8 +241,7 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
         let callee_gas_left = eip150_gas(geth_step.gas - gas_cost, gas_specified);
         let callee_gas_left_with_stipend = callee_gas_left + stipend;
 
-        // There are 4 branches from here.
-        // add failure case for insufficient balance or error depth in the future.
+        // There are 5 branches from here.
         if geth_steps[0].op == OpcodeId::CALL
             && geth_steps[1].depth == geth_steps[0].depth + 1
             && geth_steps[1].gas != callee_gas_left_with_stipend
@@ -284,6 +284,7 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
                         &[]
                     },
                     callee_gas_left_with_stipend,
+                    state.hardfork,
                 );
 
                 // mutate the callee memory by at least the precompile call's result that will be
@@ -451,7 +452,9 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
                     None
                 };
 
-                if has_oog_err {
+                // MODEXP handles the case of insufficient gas itself, as the required gas
+                // depends on the exponent.
+                if has_oog_err && precompile_call != PrecompileCalls::Modexp {
                     let mut oog_step = ErrorOOGPrecompile::gen_associated_ops(
                         state,
                         &geth_steps[1],
@@ -459,6 +462,12 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
                     )?;
                     oog_step.gas_left = callee_gas_left_with_stipend;
                     oog_step.gas_cost = precompile_call_gas_cost;
+                    // The required gas of some precompiles (e.g. BLAKE2F) depends on the input.
+                    oog_step.aux_data = Some(PrecompileAuxData::Base {
+                        input_bytes: input_bytes.unwrap_or_default(),
+                        output_bytes: vec![],
+                        return_bytes: vec![],
+                    });
                     // Make the Precompile execution step to handle return logic and restore to
                     // caller context (similar as STOP and RETURN).
                     state.handle_return(&mut [&mut exec_step, &mut oog_step], geth_steps, true)?;
@@ -572,7 +581,50 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
 
                 Ok(vec![exec_step])
             }
-            // 4. insufficient balance or error depth cases.
+            // 4. Call depth limit exceeded.
+            (false, _, _) if !is_valid_depth => {
+                for (field, value) in [
+                    (CallContextField::ProgramCounter, (geth_step.pc + 1).into()),
+                    (
+                        CallContextField::StackPointer,
+                        (geth_step.stack.stack_pointer().0 + N_ARGS - 1).into(),
+                    ),
+                    (
+                        CallContextField::GasLeft,
+                        (geth_step.gas - gas_cost - callee_gas_left).into(),
+                    ),
+                    (CallContextField::MemorySize, next_memory_word_size.into()),
+                    (
+                        CallContextField::ReversibleWriteCounter,
+                        (exec_step.reversible_write_counter + 1).into(),
+                    ),
+                ] {
+                    state.call_context_write(&mut exec_step, current_call.call_id, field, value)?;
+                }
+                for (field, value) in [
+                    (CallContextField::CallerId, current_call.call_id.into()),
+                    (CallContextField::Depth, call.depth.into()),
+                ] {
+                    state.call_context_write(&mut exec_step, call.call_id, field, value)?;
+                }
+
+                // The callee fails immediately in an ErrorDepth step, which returns all the gas
+                // given to it and restores the caller context.
+                let mut depth_step = state.new_step(&geth_steps[1])?;
+                depth_step.error = Some(ExecError::Depth(DepthError::Call));
+                depth_step.gas_left = callee_gas_left_with_stipend;
+                depth_step.gas_cost = 0;
+                state.call_context_read(
+                    &mut depth_step,
+                    call.call_id,
+                    CallContextField::Depth,
+                    call.depth.into(),
+                )?;
+                state.caller_ctx_mut()?.return_data.clear();
+                state.handle_return(&mut [&mut exec_step, &mut depth_step], geth_steps, true)?;
+                Ok(vec![exec_step, depth_step])
+            }
+            // 5. insufficient balance case.
             (false, _, _) => {
                 for (field, value) in [
                     (CallContextField::LastCalleeId, call.call_id.into()),
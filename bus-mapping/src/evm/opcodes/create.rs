@@ -2,7 +2,7 @@ use crate::{
     circuit_input_builder::{
         CircuitInputStateRef, CopyDataType, CopyEvent, ExecStep, NumberOrHash,
     },
-    error::ExecError,
+    error::{ContractAddressCollisionError, ExecError},
     evm::Opcode,
     operation::{AccountField, AccountOp, CallContextField, MemoryOp, RW},
     state_db::CodeDB,
@@ -127,7 +127,11 @@ impl<const IS_CREATE2: bool> Opcode for Create<IS_CREATE2> {
             // ErrContractAddressCollision
             let code_hash_previous = if callee_exists {
                 if is_precheck_ok && is_address_collision {
-                    exec_step.error = Some(ExecError::ContractAddressCollision);
+                    exec_step.error = Some(ExecError::ContractAddressCollision(if IS_CREATE2 {
+                        ContractAddressCollisionError::Create2
+                    } else {
+                        ContractAddressCollisionError::Create
+                    }));
                 }
                 callee_account.code_hash
             } else {
@@ -232,6 +236,8 @@ impl<const IS_CREATE2: bool> Opcode for Create<IS_CREATE2> {
                     value_prev: 0.into(),
                 },
             )?;
+            // EIP 6780, mark callee as created in the current transaction
+            state.tx_created_account_write(&mut exec_step, callee.address)?;
 
             if length > 0 {
                 for (field, value) in [
@@ -393,7 +399,12 @@ mod tests {
             .last()
             .unwrap();
 
-        assert_eq!(step.error, Some(ExecError::ContractAddressCollision));
+        assert_eq!(
+            step.error,
+            Some(ExecError::ContractAddressCollision(
+                ContractAddressCollisionError::Create2
+            ))
+        );
 
         let container = builder.block.container.clone();
         println!("{:?}", container.stack);
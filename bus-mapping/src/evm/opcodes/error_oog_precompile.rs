@@ -2,6 +2,7 @@ use crate::{
     circuit_input_builder::{Call, CircuitInputStateRef, ExecStep},
     error::{ExecError, OogError},
     operation::CallContextField,
+    precompile::PrecompileCalls,
     Error,
 };
 use eth_types::{GethExecStep, ToWord};
@@ -32,6 +33,30 @@ impl ErrorOOGPrecompile {
             call.call_data_length.into(),
         )?;
 
+        // The required gas of BLAKE2F is the number of rounds, given by the first 4 bytes of
+        // the input when it's 213 bytes long.
+        let precompile_call: PrecompileCalls = call.code_address().unwrap().0[19].into();
+        if precompile_call == PrecompileCalls::Blake2F {
+            state.call_context_read(
+                &mut exec_step,
+                call.call_id,
+                CallContextField::CallerId,
+                call.caller_id.into(),
+            )?;
+            state.call_context_read(
+                &mut exec_step,
+                call.call_id,
+                CallContextField::CallDataOffset,
+                call.call_data_offset.into(),
+            )?;
+            if call.call_data_length == 213 {
+                for idx in 0..4 {
+                    state
+                        .memory_read_caller(&mut exec_step, (call.call_data_offset + idx).into())?;
+                }
+            }
+        }
+
         Ok(exec_step)
     }
 }
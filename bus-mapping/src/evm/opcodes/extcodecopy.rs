@@ -25,9 +25,12 @@ impl Opcode for Extcodecopy {
         let code_offset = geth_step.stack.nth_last(2)?;
         let length = geth_steps[0].stack.nth_last(3)?;
 
-        let (_, account) = state.sdb.get_account(&address);
-        let code_hash = account.code_hash;
-        let code = state.code(code_hash)?;
+        let account = state.sdb.get_account(&address).1;
+        let code = if account.is_empty() {
+            vec![]
+        } else {
+            state.code(account.code_hash)?
+        };
 
         let call_ctx = state.call_ctx_mut()?;
         let memory = &mut call_ctx.memory;
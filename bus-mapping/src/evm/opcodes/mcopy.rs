@@ -0,0 +1,242 @@
+use super::Opcode;
+use crate::{
+    circuit_input_builder::{
+        CircuitInputStateRef, CopyDataType, CopyEvent, ExecStep, NumberOrHash,
+    },
+    Error,
+};
+use eth_types::GethExecStep;
+
+/// Placeholder structure used to implement [`Opcode`] trait over it
+/// corresponding to the [`OpcodeId::MCOPY`](crate::evm::OpcodeId::MCOPY)
+/// `OpcodeId`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Mcopy;
+
+impl Opcode for Mcopy {
+    fn gen_associated_ops(
+        state: &mut CircuitInputStateRef,
+        geth_steps: &[GethExecStep],
+    ) -> Result<Vec<ExecStep>, Error> {
+        let geth_step = &geth_steps[0];
+        let mut exec_steps = vec![gen_mcopy_step(state, geth_step)?];
+
+        // reconstruction
+        let dst_offset = geth_step.stack.nth_last(0)?;
+        let src_offset = geth_step.stack.nth_last(1)?;
+        let length = geth_step.stack.nth_last(2)?;
+        let call_ctx = state.call_ctx_mut()?;
+        let memory = &mut call_ctx.memory;
+        memory.extend_for_range(src_offset, length);
+        memory.extend_for_range(dst_offset, length);
+
+        // The memory writes of the copy event perform the actual copy.
+        let copy_event = gen_copy_event(state, geth_step, &mut exec_steps[0])?;
+        state.push_copy(&mut exec_steps[0], copy_event);
+        Ok(exec_steps)
+    }
+}
+
+fn gen_mcopy_step(
+    state: &mut CircuitInputStateRef,
+    geth_step: &GethExecStep,
+) -> Result<ExecStep, Error> {
+    let mut exec_step = state.new_step(geth_step)?;
+    let dst_offset = geth_step.stack.nth_last(0)?;
+    let src_offset = geth_step.stack.nth_last(1)?;
+    let length = geth_step.stack.nth_last(2)?;
+
+    state.stack_read(
+        &mut exec_step,
+        geth_step.stack.nth_last_filled(0),
+        dst_offset,
+    )?;
+    state.stack_read(
+        &mut exec_step,
+        geth_step.stack.nth_last_filled(1),
+        src_offset,
+    )?;
+    state.stack_read(&mut exec_step, geth_step.stack.nth_last_filled(2), length)?;
+
+    Ok(exec_step)
+}
+
+fn gen_copy_steps(
+    state: &mut CircuitInputStateRef,
+    exec_step: &mut ExecStep,
+    src_addr: u64,
+    dst_addr: u64,
+    bytes_left: u64,
+) -> Result<Vec<(u8, bool)>, Error> {
+    // All the source bytes are read before the first write, so the copy behaves as if the bytes
+    // went through an intermediate buffer when the source and destination ranges overlap.
+    let mut copy_steps = Vec::with_capacity(bytes_left as usize);
+    for idx in 0..bytes_left {
+        let value = state.memory_read(exec_step, (src_addr + idx).into())?;
+        copy_steps.push((value, false));
+    }
+    for (idx, &(value, _)) in copy_steps.iter().enumerate() {
+        state.memory_write(exec_step, (dst_addr + idx as u64).into(), value)?;
+    }
+
+    Ok(copy_steps)
+}
+
+fn gen_copy_event(
+    state: &mut CircuitInputStateRef,
+    geth_step: &GethExecStep,
+    exec_step: &mut ExecStep,
+) -> Result<CopyEvent, Error> {
+    let rw_counter_start = state.block_ctx.rwc;
+
+    let dst_offset = geth_step.stack.nth_last(0)?;
+    let src_offset = geth_step.stack.nth_last(1)?;
+    let length = geth_step.stack.nth_last(2)?.as_u64();
+
+    // Offsets could be Uint64 overflow if length is zero, in which case they are unused.
+    let (src_addr, dst_addr) = if length == 0 {
+        (0, 0)
+    } else {
+        (src_offset.as_u64(), dst_offset.as_u64())
+    };
+    let src_addr_end = src_addr + length;
+
+    let copy_steps = gen_copy_steps(state, exec_step, src_addr, dst_addr, length)?;
+
+    let call_id = state.call()?.call_id;
+    Ok(CopyEvent {
+        src_type: CopyDataType::Memory,
+        src_id: NumberOrHash::Number(call_id),
+        src_addr,
+        src_addr_end,
+        dst_type: CopyDataType::Memory,
+        dst_id: NumberOrHash::Number(call_id),
+        dst_addr,
+        log_id: None,
+        rw_counter_start,
+        bytes: copy_steps,
+    })
+}
+
+#[cfg(test)]
+mod mcopy_tests {
+    use crate::{
+        circuit_input_builder::{CopyDataType, ExecState, NumberOrHash},
+        mock::BlockData,
+        operation::{MemoryOp, StackOp, RW},
+    };
+    use eth_types::{
+        bytecode,
+        evm_types::{OpcodeId, StackAddress},
+        geth_types::GethData,
+        Word,
+    };
+    use mock::test_ctx::{helpers::*, TestContext};
+    use pretty_assertions::assert_eq;
+
+    fn test_ok(dst_offset: usize, src_offset: usize, length: usize) {
+        let code = bytecode! {
+            PUSH32(Word::from_big_endian(&(1..=32).collect::<Vec<u8>>()))
+            PUSH1(0x00)
+            MSTORE
+            .op_mcopy(dst_offset, src_offset, length)
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        let builder = builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::MCOPY))
+            .unwrap();
+
+        assert_eq!(
+            [0, 1, 2]
+                .map(|idx| &builder.block.container.stack[step.bus_mapping_instance[idx].as_usize()])
+                .map(|operation| (operation.rw(), operation.op())),
+            [
+                (
+                    RW::READ,
+                    &StackOp::new(1, StackAddress::from(1021), Word::from(dst_offset)),
+                ),
+                (
+                    RW::READ,
+                    &StackOp::new(1, StackAddress::from(1022), Word::from(src_offset)),
+                ),
+                (
+                    RW::READ,
+                    &StackOp::new(1, StackAddress::from(1023), Word::from(length)),
+                ),
+            ]
+        );
+
+        // Memory before the copy: 1..=32 followed by zeros.
+        let memory = (1..=32u8)
+            .chain(std::iter::repeat(0))
+            .take(src_offset.max(dst_offset) + length)
+            .collect::<Vec<u8>>();
+        let copied = &memory[src_offset..src_offset + length];
+
+        let memory_ops = (3..3 + 2 * length)
+            .map(|idx| &builder.block.container.memory[step.bus_mapping_instance[idx].as_usize()])
+            .map(|operation| (operation.rw(), operation.op().clone()))
+            .collect::<Vec<_>>();
+        let expected =
+            copied
+                .iter()
+                .enumerate()
+                .map(|(idx, &byte)| (RW::READ, MemoryOp::new(1, (src_offset + idx).into(), byte)))
+                .chain(copied.iter().enumerate().map(|(idx, &byte)| {
+                    (RW::WRITE, MemoryOp::new(1, (dst_offset + idx).into(), byte))
+                }))
+                .collect::<Vec<_>>();
+        assert_eq!(memory_ops, expected);
+
+        let copy_events = builder.block.copy_events.clone();
+        assert_eq!(copy_events.len(), 1);
+        let copy_event = &copy_events[0];
+        assert_eq!(copy_event.src_type, CopyDataType::Memory);
+        assert_eq!(copy_event.src_id, NumberOrHash::Number(1));
+        assert_eq!(copy_event.dst_type, CopyDataType::Memory);
+        assert_eq!(copy_event.dst_id, NumberOrHash::Number(1));
+        assert!(copy_event.is_memory_copy());
+        assert_eq!(
+            copy_event.bytes.iter().map(|(b, _)| *b).collect::<Vec<_>>(),
+            copied
+        );
+        assert_eq!(copy_event.rw_counter_delta(), 2 * length as u64);
+    }
+
+    #[test]
+    fn mcopy_opcode_impl() {
+        test_ok(0x40, 0x00, 0x20);
+    }
+
+    #[test]
+    fn mcopy_opcode_overlap_forward() {
+        test_ok(0x08, 0x00, 0x20);
+    }
+
+    #[test]
+    fn mcopy_opcode_overlap_backward() {
+        test_ok(0x00, 0x08, 0x20);
+    }
+
+    #[test]
+    fn mcopy_opcode_empty() {
+        test_ok(0x00, 0x00, 0x00);
+    }
+}
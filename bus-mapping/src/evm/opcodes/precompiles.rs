@@ -1,15 +1,23 @@
 use eth_types::{GethExecStep, ToWord, Word};
 
 use crate::{
-    circuit_input_builder::{Call, CircuitInputStateRef, ExecState, ExecStep},
+    circuit_input_builder::{Call, CircuitInputStateRef, ExecState, ExecStep, PrecompileEvent},
     operation::CallContextField,
     precompile::{PrecompileAuxData, PrecompileCalls},
     Error,
 };
 
+mod ec_add;
+mod ec_mul;
+mod ec_pairing;
 mod ecrecover;
+mod modexp;
 
+use ec_add::opt_data as opt_data_ec_add;
+use ec_mul::opt_data as opt_data_ec_mul;
+use ec_pairing::opt_data as opt_data_ec_pairing;
 use ecrecover::opt_data as opt_data_ecrecover;
+use modexp::opt_data as opt_data_modexp;
 
 pub fn gen_associated_ops(
     state: &mut CircuitInputStateRef,
@@ -24,10 +32,41 @@ pub fn gen_associated_ops(
     let mut exec_step = state.new_step(&geth_step)?;
     exec_step.exec_state = ExecState::Precompile(precompile);
 
-    common_call_ctx_reads(state, &mut exec_step, &call)?;
+    match precompile {
+        // The gas cost of the hash precompiles only depends on the input length.
+        PrecompileCalls::Sha256 | PrecompileCalls::Ripemd160 => call_ctx_reads(
+            state,
+            &mut exec_step,
+            &call,
+            &[
+                CallContextField::IsSuccess,
+                CallContextField::CalleeAddress,
+                CallContextField::CallDataLength,
+            ],
+        )?,
+        _ => call_ctx_reads(state, &mut exec_step, &call, &COMMON_CALL_CTX_FIELDS)?,
+    }
 
     let (opt_event, aux_data) = match precompile {
         PrecompileCalls::Ecrecover => opt_data_ecrecover(input_bytes, output_bytes, return_bytes),
+        PrecompileCalls::Modexp => opt_data_modexp(input_bytes, output_bytes, return_bytes)?,
+        PrecompileCalls::Bn128Add => opt_data_ec_add(input_bytes, output_bytes, return_bytes),
+        PrecompileCalls::Bn128Mul => opt_data_ec_mul(input_bytes, output_bytes, return_bytes),
+        PrecompileCalls::Bn128Pairing => {
+            opt_data_ec_pairing(input_bytes, output_bytes, return_bytes)
+        }
+        PrecompileCalls::Sha256 | PrecompileCalls::Ripemd160 | PrecompileCalls::Blake2F => (
+            call.is_success.then(|| match precompile {
+                PrecompileCalls::Sha256 => PrecompileEvent::Sha256(input_bytes.to_vec()),
+                PrecompileCalls::Ripemd160 => PrecompileEvent::Ripemd160(input_bytes.to_vec()),
+                _ => PrecompileEvent::Blake2F(input_bytes.to_vec()),
+            }),
+            Some(PrecompileAuxData::Base {
+                input_bytes: input_bytes.to_vec(),
+                output_bytes: output_bytes.to_vec(),
+                return_bytes: return_bytes.to_vec(),
+            }),
+        ),
         PrecompileCalls::Identity => (
             None,
             Some(PrecompileAuxData::Base {
@@ -36,17 +75,6 @@ pub fn gen_associated_ops(
                 return_bytes: return_bytes.to_vec(),
             }),
         ),
-        _ => {
-            log::warn!("precompile {:?} unsupported in circuits", precompile);
-            (
-                None,
-                Some(PrecompileAuxData::Base {
-                    input_bytes: input_bytes.to_vec(),
-                    output_bytes: output_bytes.to_vec(),
-                    return_bytes: return_bytes.to_vec(),
-                }),
-            )
-        }
     };
     log::trace!("precompile event {opt_event:?}, aux data {aux_data:?}");
 
@@ -58,39 +86,35 @@ pub fn gen_associated_ops(
     Ok(exec_step)
 }
 
-fn common_call_ctx_reads(
+/// Call context fields read by the precompile steps.
+const COMMON_CALL_CTX_FIELDS: [CallContextField; 7] = [
+    CallContextField::IsSuccess,
+    CallContextField::CalleeAddress,
+    CallContextField::CallerId,
+    CallContextField::CallDataOffset,
+    CallContextField::CallDataLength,
+    CallContextField::ReturnDataOffset,
+    CallContextField::ReturnDataLength,
+];
+
+fn call_ctx_reads(
     state: &mut CircuitInputStateRef,
     exec_step: &mut ExecStep,
     call: &Call,
+    fields: &[CallContextField],
 ) -> Result<(), Error> {
-    for (field, value) in [
-        (
-            CallContextField::IsSuccess,
-            Word::from(call.is_success as u64),
-        ),
-        (
-            CallContextField::CalleeAddress,
-            call.code_address().unwrap().to_word(),
-        ),
-        (CallContextField::CallerId, call.caller_id.into()),
-        (
-            CallContextField::CallDataOffset,
-            call.call_data_offset.into(),
-        ),
-        (
-            CallContextField::CallDataLength,
-            call.call_data_length.into(),
-        ),
-        (
-            CallContextField::ReturnDataOffset,
-            call.return_data_offset.into(),
-        ),
-        (
-            CallContextField::ReturnDataLength,
-            call.return_data_length.into(),
-        ),
-    ] {
-        state.call_context_read(exec_step, call.call_id, field, value)?;
+    for field in fields {
+        let value = match field {
+            CallContextField::IsSuccess => Word::from(call.is_success as u64),
+            CallContextField::CalleeAddress => call.code_address().unwrap().to_word(),
+            CallContextField::CallerId => call.caller_id.into(),
+            CallContextField::CallDataOffset => call.call_data_offset.into(),
+            CallContextField::CallDataLength => call.call_data_length.into(),
+            CallContextField::ReturnDataOffset => call.return_data_offset.into(),
+            CallContextField::ReturnDataLength => call.return_data_length.into(),
+            _ => unreachable!("{field:?} is not read by the precompile steps"),
+        };
+        state.call_context_read(exec_step, call.call_id, field.clone(), value)?;
     }
 
     Ok(())
@@ -0,0 +1,27 @@
+use crate::{
+    circuit_input_builder::{EcMulOp, PrecompileEvent},
+    precompile::PrecompileAuxData,
+};
+
+pub(crate) fn opt_data(
+    input_bytes: &[u8],
+    output_bytes: &[u8],
+    return_bytes: &[u8],
+) -> (Option<PrecompileEvent>, Option<PrecompileAuxData>) {
+    // The ECC circuit also verifies invalid inputs, which make the call fail.
+    let op = EcMulOp::new(input_bytes);
+    debug_assert_eq!(
+        op.is_valid(),
+        !output_bytes.is_empty(),
+        "ecMul validity mismatch"
+    );
+
+    (
+        Some(PrecompileEvent::EcMul(op)),
+        Some(PrecompileAuxData::Base {
+            input_bytes: input_bytes.to_vec(),
+            output_bytes: output_bytes.to_vec(),
+            return_bytes: return_bytes.to_vec(),
+        }),
+    )
+}
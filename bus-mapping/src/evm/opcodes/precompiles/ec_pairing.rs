@@ -0,0 +1,44 @@
+use eth_types::Word;
+
+use crate::{
+    circuit_input_builder::{EcPairingOp, PrecompileEvent, N_BYTES_PER_PAIR, N_PAIRING_PER_OP},
+    precompile::PrecompileAuxData,
+};
+
+pub(crate) fn opt_data(
+    input_bytes: &[u8],
+    output_bytes: &[u8],
+    return_bytes: &[u8],
+) -> (Option<PrecompileEvent>, Option<PrecompileAuxData>) {
+    // An empty input always succeeds, and an input that isn't made of whole pairs always fails,
+    // so the ECC circuit is only needed for the other calls, successful or not.
+    let n_bytes = input_bytes.len();
+    if n_bytes > N_PAIRING_PER_OP * N_BYTES_PER_PAIR {
+        log::warn!("ecPairing with more than {N_PAIRING_PER_OP} pairs unsupported in circuits");
+    }
+    let opt_event = (n_bytes % N_BYTES_PER_PAIR == 0
+        && (1..=N_PAIRING_PER_OP * N_BYTES_PER_PAIR).contains(&n_bytes))
+    .then(|| {
+        let output = if output_bytes.is_empty() {
+            Word::zero()
+        } else {
+            Word::from_big_endian(output_bytes)
+        };
+        let op = EcPairingOp::new(input_bytes, output);
+        debug_assert_eq!(
+            op.is_valid(),
+            !output_bytes.is_empty(),
+            "ecPairing validity mismatch"
+        );
+        PrecompileEvent::EcPairing(Box::new(op))
+    });
+
+    (
+        opt_event,
+        Some(PrecompileAuxData::Base {
+            input_bytes: input_bytes.to_vec(),
+            output_bytes: output_bytes.to_vec(),
+            return_bytes: return_bytes.to_vec(),
+        }),
+    )
+}
@@ -0,0 +1,55 @@
+use num::{BigUint, Zero};
+
+use crate::{
+    circuit_input_builder::{BigModExp, PrecompileEvent},
+    precompile::{parse_modexp_input, ModExpAuxData, PrecompileAuxData},
+    Error,
+};
+
+pub(crate) fn opt_data(
+    input_bytes: &[u8],
+    output_bytes: &[u8],
+    return_bytes: &[u8],
+) -> Result<(Option<PrecompileEvent>, Option<PrecompileAuxData>), Error> {
+    let [base, exponent, modulus] = parse_modexp_input(input_bytes)?;
+    let output = {
+        let modulus_value = BigUint::from_bytes_be(&modulus);
+        let value = if modulus_value.is_zero() {
+            BigUint::zero()
+        } else {
+            BigUint::from_bytes_be(&base).modpow(&BigUint::from_bytes_be(&exponent), &modulus_value)
+        };
+        // Left-pad the result to the length of the modulus.
+        let bytes = value.to_bytes_be();
+        let mut output = vec![0u8; modulus.len()];
+        if !value.is_zero() {
+            output[modulus.len() - bytes.len()..].copy_from_slice(&bytes);
+        }
+        output
+    };
+    debug_assert!(
+        output_bytes.is_empty() || output_bytes == output,
+        "modexp output mismatch"
+    );
+
+    let aux_data = ModExpAuxData::new(
+        &[base.clone(), exponent.clone(), modulus.clone()],
+        input_bytes,
+        &output,
+        return_bytes,
+    );
+
+    // The event is needed even when the call runs out of gas, as the required gas depends on the
+    // exponent, which the EVM circuit only gets from the ModExp table.
+    let event = BigModExp {
+        input: input_bytes.to_vec(),
+        base,
+        exponent,
+        modulus,
+        output,
+    };
+    Ok((
+        Some(PrecompileEvent::ModExp(event)),
+        Some(PrecompileAuxData::Modexp(aux_data)),
+    ))
+}
@@ -0,0 +1,473 @@
+use super::Opcode;
+use crate::{
+    circuit_input_builder::{CircuitInputStateRef, ExecStep},
+    operation::{AccountField, AccountOp, CallContextField},
+    Error,
+};
+use eth_types::{GethExecStep, ToAddress, ToWord, Word, H256};
+
+/// Placeholder structure used to implement [`Opcode`] trait over it
+/// corresponding to the
+/// [`OpcodeId::SELFDESTRUCT`](crate::evm::OpcodeId::SELFDESTRUCT) `OpcodeId`.
+///
+/// The balance of the current account is always sent to the beneficiary.
+/// Before Cancun the account is always destroyed, while since EIP-6780 it's
+/// only destroyed when it has been created in the same transaction. The account is only recorded as
+/// destructed at this step, so its code stays callable for the rest of the transaction;
+/// the account and its storage are wiped at the end of the transaction.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Selfdestruct;
+
+impl Opcode for Selfdestruct {
+    fn gen_associated_ops(
+        state: &mut CircuitInputStateRef,
+        geth_steps: &[GethExecStep],
+    ) -> Result<Vec<ExecStep>, Error> {
+        let geth_step = &geth_steps[0];
+        let mut exec_step = state.new_step(geth_step)?;
+
+        let call = state.call()?.clone();
+        let sender = call.address;
+        let beneficiary_word = geth_step.stack.last()?;
+        let beneficiary = beneficiary_word.to_address();
+
+        state.stack_read(
+            &mut exec_step,
+            geth_step.stack.last_filled(),
+            beneficiary_word,
+        )?;
+
+        for (field, value) in [
+            (CallContextField::TxId, state.tx_ctx.id().into()),
+            (CallContextField::IsStatic, (call.is_static as u64).into()),
+            (
+                CallContextField::RwCounterEndOfReversion,
+                call.rw_counter_end_of_reversion.into(),
+            ),
+            (CallContextField::IsPersistent, call.is_persistent.to_word()),
+            (CallContextField::CalleeAddress, sender.to_word()),
+        ] {
+            state.call_context_read(&mut exec_step, call.call_id, field, value)?;
+        }
+
+        // Add beneficiary to access list.
+        state.tx_access_list_write(&mut exec_step, beneficiary)?;
+
+        let beneficiary_account = state.sdb.get_account(&beneficiary).1;
+        let beneficiary_exists = !beneficiary_account.is_empty();
+        let beneficiary_code_hash = if beneficiary_exists {
+            beneficiary_account.code_hash
+        } else {
+            H256::zero()
+        };
+        state.account_read(
+            &mut exec_step,
+            beneficiary,
+            AccountField::CodeHash,
+            beneficiary_code_hash.to_word(),
+        )?;
+
+        let is_created = state.tx_created_account_read(&mut exec_step, sender)?;
+        let is_destructed = is_created || !state.hardfork.is_eip6780_enabled();
+
+        let (found, sender_account) = state.sdb.get_account(&sender);
+        if !found {
+            return Err(Error::AccountNotFound(sender));
+        }
+        let value = sender_account.balance;
+        state.account_read(&mut exec_step, sender, AccountField::Balance, value)?;
+
+        // The balance is moved to the beneficiary. When the beneficiary is the
+        // account itself, the balance is kept unless the account is destroyed.
+        let is_self = beneficiary == sender;
+        if !is_self || is_destructed {
+            state.push_op_reversible(
+                &mut exec_step,
+                AccountOp {
+                    address: sender,
+                    field: AccountField::Balance,
+                    value: Word::zero(),
+                    value_prev: value,
+                },
+            )?;
+        }
+        if !is_self {
+            state.transfer_to(
+                &mut exec_step,
+                beneficiary,
+                beneficiary_exists,
+                false,
+                value,
+                true,
+            )?;
+        }
+
+        if is_destructed && call.is_persistent {
+            state.sdb.destruct_account(sender);
+        }
+
+        state.call_context_read(
+            &mut exec_step,
+            call.call_id,
+            CallContextField::IsSuccess,
+            1.into(),
+        )?;
+
+        state.handle_return(&mut [&mut exec_step], geth_steps, !call.is_root)?;
+        Ok(vec![exec_step])
+    }
+}
+
+#[cfg(test)]
+mod selfdestruct_tests {
+    use crate::{
+        circuit_input_builder::{ExecState, FeatureConfig},
+        mock::BlockData,
+        operation::{AccountField, AccountOp, StorageOp, Target, TxCreatedAccountOp, RW},
+        state_db::{Account, CodeDB},
+    };
+    use eth_types::{
+        address, bytecode,
+        evm_types::{Hardfork, OpcodeId},
+        geth_types::GethData,
+        Bytecode, ToAddress, ToWord, Word,
+    };
+    use mock::TestContext;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn selfdestruct_not_created_in_tx_keeps_account() {
+        let beneficiary = address!("0x0000000000000000000000000000000000cafe01");
+        let code = bytecode! {
+            PUSH20(beneficiary.to_word())
+            SELFDESTRUCT
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .balance(Word::from(1u64 << 20))
+                    .code(code);
+                accs[1]
+                    .address(address!("0x0000000000000000000000000000000000000020"))
+                    .balance(Word::from(1u64 << 20));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[1].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        let builder = builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::SELFDESTRUCT))
+            .unwrap();
+        let container = &builder.block.container;
+
+        let created_ops = step
+            .bus_mapping_instance
+            .iter()
+            .filter_map(|op_ref| match op_ref.target() {
+                Target::TxCreatedAccount => Some(&container.tx_created_account[op_ref.as_usize()]),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(created_ops.len(), 1);
+        assert_eq!(created_ops[0].rw(), RW::READ);
+        assert_eq!(
+            created_ops[0].op(),
+            &TxCreatedAccountOp {
+                tx_id: 1,
+                address: address!("0x0000000000000000000000000000000000000010"),
+                is_created: false,
+                is_created_prev: false,
+            }
+        );
+
+        // Only the balance of the account is cleared; nonce and code hash are
+        // kept since the account was not created in this transaction.
+        let account_writes = step
+            .bus_mapping_instance
+            .iter()
+            .filter_map(|op_ref| match op_ref.target() {
+                Target::Account => Some(&container.account[op_ref.as_usize()]),
+                _ => None,
+            })
+            .filter(|op| op.rw() == RW::WRITE)
+            .map(|op| op.op().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            account_writes,
+            vec![
+                AccountOp {
+                    address: address!("0x0000000000000000000000000000000000000010"),
+                    field: AccountField::Balance,
+                    value: Word::zero(),
+                    value_prev: Word::from(1u64 << 20),
+                },
+                AccountOp {
+                    address: beneficiary,
+                    field: AccountField::CodeHash,
+                    value: CodeDB::empty_code_hash().to_word(),
+                    value_prev: Word::zero(),
+                },
+                AccountOp {
+                    address: beneficiary,
+                    field: AccountField::Balance,
+                    value: Word::from(1u64 << 20),
+                    value_prev: Word::zero(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn selfdestruct_account_is_wiped_at_end_of_tx() {
+        let beneficiary = address!("0x0000000000000000000000000000000000cafe01");
+        // Runtime code of the created contract: write a storage slot and
+        // destruct the contract.
+        let runtime_code = bytecode! {
+            PUSH1(1)
+            PUSH1(0)
+            SSTORE
+            PUSH20(beneficiary.to_word())
+            SELFDESTRUCT
+        };
+        let runtime_bytes = runtime_code.code();
+        let init_code = bytecode! {
+            PUSH32(Word::from_big_endian(&runtime_bytes))
+            PUSH1(0)
+            MSTORE
+            PUSH1(runtime_bytes.len()) // size
+            PUSH1(32 - runtime_bytes.len()) // offset
+            RETURN
+        };
+        let init_bytes = init_code.code();
+
+        // The factory creates the contract and calls it twice. The second call
+        // still runs the code, since the contract is only removed at the end of
+        // the transaction.
+        let mut code = Bytecode::default();
+        for (i, chunk) in init_bytes.chunks(32).enumerate() {
+            let mut word = [0u8; 32];
+            word[..chunk.len()].copy_from_slice(chunk);
+            code.append(&bytecode! {
+                PUSH32(Word::from_big_endian(&word))
+                PUSH1(32 * i)
+                MSTORE
+            });
+        }
+        code.append(&bytecode! {
+            PUSH1(init_bytes.len()) // size
+            PUSH1(0) // offset
+            PUSH1(0) // value
+            CREATE
+        });
+        for _ in 0..2 {
+            code.append(&bytecode! {
+                PUSH1(0) // retLength
+                PUSH1(0) // retOffset
+                PUSH1(0) // argsLength
+                PUSH1(0) // argsOffset
+                PUSH1(0) // value
+                DUP6 // address
+                GAS
+                CALL
+                POP
+            });
+        }
+        code.append(&bytecode! { STOP });
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .balance(Word::from(1u64 << 20))
+                    .code(code);
+                accs[1]
+                    .address(address!("0x0000000000000000000000000000000000000020"))
+                    .balance(Word::from(1u64 << 20));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[1].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        // The address of the created contract is on the stack of the factory
+        // once CREATE has returned.
+        let struct_logs = &block.geth_traces[0].struct_logs;
+        let create_index = struct_logs
+            .iter()
+            .position(|step| step.op == OpcodeId::CREATE)
+            .unwrap();
+        let created = struct_logs[create_index + 1..]
+            .iter()
+            .find(|step| step.depth == 1)
+            .unwrap()
+            .stack
+            .last()
+            .unwrap()
+            .to_address();
+
+        let builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        let builder = builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let steps = builder.block.txs()[0].steps();
+        let count_steps = |opcode| {
+            steps
+                .iter()
+                .filter(|step| step.exec_state == ExecState::Op(opcode))
+                .count()
+        };
+        assert_eq!(count_steps(OpcodeId::SSTORE), 2);
+        assert_eq!(count_steps(OpcodeId::SELFDESTRUCT), 2);
+
+        let end_tx_step = steps.last().unwrap();
+        assert_eq!(end_tx_step.exec_state, ExecState::EndTx);
+        let container = &builder.block.container;
+        let account_writes = end_tx_step
+            .bus_mapping_instance
+            .iter()
+            .filter_map(|op_ref| match op_ref.target() {
+                Target::Account => Some(&container.account[op_ref.as_usize()]),
+                _ => None,
+            })
+            .filter(|op| op.rw() == RW::WRITE && op.op().address == created)
+            .map(|op| (op.op().field, op.op().value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            account_writes,
+            vec![
+                (AccountField::CodeHash, Word::zero()),
+                (AccountField::Nonce, Word::zero()),
+                (AccountField::Balance, Word::zero()),
+            ]
+        );
+        let storage_writes = end_tx_step
+            .bus_mapping_instance
+            .iter()
+            .filter_map(|op_ref| match op_ref.target() {
+                Target::Storage => Some(container.storage[op_ref.as_usize()].op().clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            storage_writes,
+            vec![StorageOp::new(
+                created,
+                Word::zero(),
+                Word::zero(),
+                Word::one(),
+                1,
+                Word::zero()
+            )]
+        );
+
+        assert_eq!(builder.sdb.get_account(&created).1, &Account::zero());
+    }
+
+    #[test]
+    fn selfdestruct_before_cancun_destroys_account() {
+        let address = address!("0x0000000000000000000000000000000000000010");
+        let code = bytecode! {
+            PUSH20(address.to_word())
+            SELFDESTRUCT
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address)
+                    .balance(Word::from(1u64 << 20))
+                    .code(code);
+                accs[1]
+                    .address(address!("0x0000000000000000000000000000000000000020"))
+                    .balance(Word::from(1u64 << 20));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[1].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        // The trace of SELFDESTRUCT is the same in Shanghai and Cancun, only
+        // its effect on the account differs.
+        let builder = BlockData::new_from_geth_data(block.clone())
+            .new_circuit_input_builder_with_feature(FeatureConfig {
+                hardfork: Hardfork::Shanghai,
+                ..Default::default()
+            });
+        let builder = builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        // The account is its own beneficiary, and its balance is burnt since
+        // it's destroyed although it was not created in this transaction.
+        let steps = builder.block.txs()[0].steps();
+        let step = steps
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::SELFDESTRUCT))
+            .unwrap();
+        let container = &builder.block.container;
+        let account_writes = step
+            .bus_mapping_instance
+            .iter()
+            .filter_map(|op_ref| match op_ref.target() {
+                Target::Account => Some(&container.account[op_ref.as_usize()]),
+                _ => None,
+            })
+            .filter(|op| op.rw() == RW::WRITE)
+            .map(|op| op.op().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            account_writes,
+            vec![AccountOp {
+                address,
+                field: AccountField::Balance,
+                value: Word::zero(),
+                value_prev: Word::from(1u64 << 20),
+            }]
+        );
+
+        let end_tx_step = steps.last().unwrap();
+        assert_eq!(end_tx_step.exec_state, ExecState::EndTx);
+        let account_wipes = end_tx_step
+            .bus_mapping_instance
+            .iter()
+            .filter_map(|op_ref| match op_ref.target() {
+                Target::Account => Some(&container.account[op_ref.as_usize()]),
+                _ => None,
+            })
+            .filter(|op| op.rw() == RW::WRITE && op.op().address == address)
+            .map(|op| op.op().field)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            account_wipes,
+            vec![
+                AccountField::CodeHash,
+                AccountField::Nonce,
+                AccountField::Balance
+            ]
+        );
+        assert_eq!(builder.sdb.get_account(&address).1, &Account::zero());
+    }
+}
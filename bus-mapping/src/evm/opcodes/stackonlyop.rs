@@ -65,7 +65,7 @@ mod stackonlyop_tests {
     };
     use eth_types::{
         bytecode,
-        evm_types::{OpcodeId, StackAddress},
+        evm_types::{OpcodeId, StackAddress, MIN_BLOB_GASPRICE},
         geth_types::GethData,
         word, Bytecode, Hash, ToWord, Word,
     };
@@ -448,6 +448,23 @@ mod stackonlyop_tests {
         );
     }
 
+    #[test]
+    fn blobbasefee_opcode_impl() {
+        stack_only_opcode_impl::<0, 1>(
+            OpcodeId::BLOBBASEFEE,
+            bytecode! {
+                BLOBBASEFEE
+                STOP
+            },
+            vec![],
+            vec![StackOp::new(
+                1,
+                StackAddress(1023),
+                Word::from(MIN_BLOB_GASPRICE),
+            )],
+        );
+    }
+
     #[test]
     fn push0_opcode_impl() {
         stack_only_opcode_impl::<0, 1>(
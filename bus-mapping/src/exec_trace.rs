@@ -21,6 +21,7 @@ impl fmt::Debug for OperationRef {
                 Target::TransientStorage => "TransientStorage",
                 Target::TxAccessListAccount => "TxAccessListAccount",
                 Target::TxAccessListAccountStorage => "TxAccessListAccountStorage",
+                Target::TxCreatedAccount => "TxCreatedAccount",
                 Target::TxRefund => "TxRefund",
                 Target::Account => "Account",
                 Target::CallContext => "CallContext",
@@ -0,0 +1,453 @@
+//! Generation of the MPT circuit witness.
+//!
+//! The [`StateTrie`] holds the Merkle Patricia Tries of the state, built from
+//! a [`StateDB`] or resolved from the nodes of `eth_getProof` responses.  For
+//! each [`TrieModification`], the proofs of the modified key before and after
+//! the modification are converted into the nodes proven by the MPT circuit.
+
+mod node;
+mod trie;
+mod witness;
+
+use crate::{
+    state_db::{CodeDB, StateDB},
+    Error,
+};
+use eth_types::{Address, EIP1186ProofResponse, ToBigEndian, Word, H256, U256, U64};
+use ethers_core::utils::{
+    keccak256,
+    rlp::{Rlp, RlpStream},
+};
+use node::Node;
+use serde::Serialize;
+use std::collections::HashMap;
+use trie::{keybytes_to_hex, NodeDb, Trie, EMPTY_ROOT};
+use witness::{convert_proof_to_witness, end_node, start_node, ProofInput};
+
+/// Type of the proof of a [`TrieModification`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ProofType {
+    /// Disabled
+    #[default]
+    Disabled,
+    /// Nonce updated
+    NonceChanged,
+    /// Balance updated
+    BalanceChanged,
+    /// Code hash updated
+    CodeHashChanged,
+    /// Account destroyed
+    AccountDestructed,
+    /// Account does not exist
+    AccountDoesNotExist,
+    /// Storage updated
+    StorageChanged,
+    /// Storage does not exist
+    StorageDoesNotExist,
+    /// Account created, with its storage cleared.  Proven as a nonce update.
+    AccountCreate,
+}
+
+/// Modification of an account field or of a storage slot, proven by the MPT
+/// circuit.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct TrieModification {
+    /// Type of the modification
+    pub typ: ProofType,
+    /// Storage key
+    pub key: H256,
+    /// Storage value
+    pub value: U256,
+    /// Account address
+    pub address: Address,
+    /// Account nonce
+    pub nonce: U64,
+    /// Account balance
+    pub balance: U256,
+    /// Account code hash
+    pub code_hash: H256,
+}
+
+/// Account as stored in the leaves of the account trie.
+#[derive(Debug, Clone)]
+struct TrieAccount {
+    nonce: U256,
+    balance: U256,
+    storage_root: H256,
+    code_hash: H256,
+}
+
+impl Default for TrieAccount {
+    fn default() -> Self {
+        Self {
+            nonce: U256::zero(),
+            balance: U256::zero(),
+            storage_root: *EMPTY_ROOT,
+            code_hash: CodeDB::empty_code_hash(),
+        }
+    }
+}
+
+impl TrieAccount {
+    fn encode(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(4);
+        stream
+            .append(&self.nonce)
+            .append(&self.balance)
+            .append(&self.storage_root)
+            .append(&self.code_hash);
+        stream.out().to_vec()
+    }
+
+    fn decode(encoded: &[u8]) -> Result<Self, Error> {
+        let rlp = Rlp::new(encoded);
+        let decode = || {
+            Ok::<_, ethers_core::utils::rlp::DecoderError>(Self {
+                nonce: rlp.val_at(0)?,
+                balance: rlp.val_at(1)?,
+                storage_root: rlp.val_at(2)?,
+                code_hash: rlp.val_at(3)?,
+            })
+        };
+        decode().map_err(|_| Error::InvalidTrieNode(encoded.to_vec()))
+    }
+}
+
+/// Encoding of a storage value in the leaves of a storage trie, empty for a
+/// zero value which is deleted from the trie.
+fn encode_storage_value(value: Word) -> Vec<u8> {
+    if value.is_zero() {
+        Vec::new()
+    } else {
+        ethers_core::utils::rlp::encode(&value).to_vec()
+    }
+}
+
+/// Merkle Patricia Tries of the state: the account trie, and the storage
+/// tries of the accounts.
+///
+/// The nodes which are not in memory are resolved from the encoded nodes
+/// added by [`StateTrie::add_proof_nodes`], so that the witness of a block
+/// can be generated from the `eth_getProof` responses of the accessed
+/// accounts and storage slots at the previous block.
+#[derive(Debug, Clone, Default)]
+pub struct StateTrie {
+    db: NodeDb,
+    accounts: Trie,
+    storages: HashMap<Address, Trie>,
+}
+
+impl StateTrie {
+    /// State trie with the given root, whose nodes are resolved from the
+    /// nodes added by [`StateTrie::add_proof_nodes`].
+    pub fn new(root: H256) -> Self {
+        Self {
+            accounts: Trie::new(root),
+            ..Default::default()
+        }
+    }
+
+    /// Add the encoded trie nodes of proofs, indexed by their hash.
+    pub fn add_proof_nodes<T: AsRef<[u8]>>(&mut self, nodes: impl IntoIterator<Item = T>) {
+        self.db.extend(nodes.into_iter().map(|node| {
+            let node = node.as_ref().to_vec();
+            (H256(keccak256(&node)), node)
+        }));
+    }
+
+    /// State trie with the given root, and the nodes of the account and
+    /// storage proofs.
+    pub fn from_proofs(root: H256, proofs: &[EIP1186ProofResponse]) -> Self {
+        let mut state_trie = Self::new(root);
+        for proof in proofs {
+            state_trie.add_proof_nodes(&proof.account_proof);
+            for storage_proof in proof.storage_proof.iter() {
+                state_trie.add_proof_nodes(&storage_proof.proof);
+            }
+        }
+        state_trie
+    }
+
+    /// State trie holding the accounts of a [`StateDB`].  Empty accounts, and
+    /// accounts with a zero code hash which are known not to exist, are left
+    /// out.
+    pub fn from_state_db(sdb: &StateDB) -> Result<Self, Error> {
+        let mut state_trie = Self::default();
+        for (address, account) in sdb.accounts() {
+            if account.is_empty() || account.code_hash.is_zero() {
+                continue;
+            }
+            let mut storage = Trie::default();
+            for (key, value) in account.storage.iter() {
+                storage.update(
+                    &state_trie.db,
+                    &keccak256(key.to_be_bytes()),
+                    encode_storage_value(*value),
+                )?;
+            }
+            state_trie.set_account(
+                *address,
+                &TrieAccount {
+                    nonce: account.nonce.into(),
+                    balance: account.balance,
+                    storage_root: storage.hash(),
+                    code_hash: account.code_hash,
+                },
+            )?;
+            state_trie.storages.insert(*address, storage);
+        }
+        Ok(state_trie)
+    }
+
+    /// Root hash of the state trie.
+    pub fn root(&self) -> H256 {
+        self.accounts.hash()
+    }
+
+    /// Apply the modifications to the state trie, and returns the JSON
+    /// encoded MPT circuit witness proving them, as loaded by
+    /// `zkevm_circuits::mpt_circuit::load_proof`.
+    ///
+    /// The witness of each modification starts from the state root left by
+    /// the previous one.
+    pub fn gen_witness(&mut self, modifications: &[TrieModification]) -> Result<String, Error> {
+        let nodes = self.witness_nodes(modifications)?;
+        serde_json::to_string(&nodes).map_err(Error::SerdeError)
+    }
+
+    fn witness_nodes(&mut self, modifications: &[TrieModification]) -> Result<Vec<Node>, Error> {
+        let mut nodes = Vec::new();
+        for modification in modifications {
+            if matches!(
+                modification.typ,
+                ProofType::StorageChanged | ProofType::StorageDoesNotExist
+            ) {
+                nodes.extend(self.storage_witness(modification)?);
+            } else {
+                nodes.extend(self.account_witness(modification)?);
+            }
+        }
+        Ok(nodes)
+    }
+
+    fn get_account(&self, address: Address) -> Result<Option<TrieAccount>, Error> {
+        self.accounts
+            .get(&self.db, &keccak256(address))?
+            .map(|encoded| TrieAccount::decode(&encoded))
+            .transpose()
+    }
+
+    fn set_account(&mut self, address: Address, account: &TrieAccount) -> Result<(), Error> {
+        self.accounts
+            .update(&self.db, &keccak256(address), account.encode())
+    }
+
+    fn account_witness(&mut self, modification: &TrieModification) -> Result<Vec<Node>, Error> {
+        let address = modification.address;
+        let address_hash = keccak256(address);
+        let proof_s = self.accounts.prove(&self.db, &address_hash)?;
+        let s_root = self.root();
+
+        let mut account = self.get_account(address)?.unwrap_or_default();
+        match modification.typ {
+            ProofType::NonceChanged => account.nonce = modification.nonce.as_u64().into(),
+            ProofType::BalanceChanged => account.balance = modification.balance,
+            ProofType::CodeHashChanged => account.code_hash = modification.code_hash,
+            ProofType::AccountCreate => {
+                account = TrieAccount {
+                    balance: account.balance,
+                    ..Default::default()
+                };
+                self.storages.insert(address, Trie::default());
+            }
+            _ => {}
+        }
+        match modification.typ {
+            ProofType::AccountDestructed => {
+                self.accounts.update(&self.db, &address_hash, Vec::new())?;
+                self.storages.remove(&address);
+            }
+            ProofType::AccountDoesNotExist => {}
+            _ => self.set_account(address, &account)?,
+        }
+
+        let proof_c = self.accounts.prove(&self.db, &address_hash)?;
+        let proof_type = match modification.typ {
+            ProofType::AccountCreate => ProofType::NonceChanged,
+            typ => typ,
+        };
+
+        let mut nodes = vec![start_node(proof_type, s_root, self.root())];
+        nodes.extend(convert_proof_to_witness(&ProofInput {
+            trie: &self.accounts,
+            db: &self.db,
+            is_account_proof: true,
+            address: address.as_bytes().to_vec(),
+            key: keybytes_to_hex(&address_hash),
+            non_existing: modification.typ == ProofType::AccountDoesNotExist,
+            s: proof_s,
+            c: proof_c,
+        })?);
+        nodes.push(end_node());
+        Ok(nodes)
+    }
+
+    fn storage_witness(&mut self, modification: &TrieModification) -> Result<Vec<Node>, Error> {
+        let address = modification.address;
+        let address_hash = keccak256(address);
+        let key_hash = keccak256(modification.key);
+        // the storage modification must follow the creation of the account
+        let mut account = self
+            .get_account(address)?
+            .ok_or(Error::AccountNotFound(address))?;
+        let storage = self
+            .storages
+            .entry(address)
+            .or_insert_with(|| Trie::new(account.storage_root));
+
+        let account_proof_s = self.accounts.prove(&self.db, &address_hash)?;
+        let storage_proof_s = storage.prove(&self.db, &key_hash)?;
+        let s_root = self.accounts.hash();
+
+        if modification.typ == ProofType::StorageChanged {
+            storage.update(
+                &self.db,
+                &key_hash,
+                encode_storage_value(modification.value),
+            )?;
+            account.storage_root = storage.hash();
+            self.accounts
+                .update(&self.db, &address_hash, account.encode())?;
+        }
+
+        let account_proof_c = self.accounts.prove(&self.db, &address_hash)?;
+        let storage_proof_c = storage.prove(&self.db, &key_hash)?;
+
+        let mut nodes = vec![start_node(modification.typ, s_root, self.accounts.hash())];
+        nodes.extend(convert_proof_to_witness(&ProofInput {
+            trie: &self.accounts,
+            db: &self.db,
+            is_account_proof: true,
+            address: address.as_bytes().to_vec(),
+            key: keybytes_to_hex(&address_hash),
+            non_existing: false,
+            s: account_proof_s,
+            c: account_proof_c,
+        })?);
+        nodes.extend(convert_proof_to_witness(&ProofInput {
+            trie: storage,
+            db: &self.db,
+            is_account_proof: false,
+            address: modification.key.as_bytes().to_vec(),
+            key: keybytes_to_hex(&key_hash),
+            non_existing: modification.typ == ProofType::StorageDoesNotExist,
+            s: storage_proof_s,
+            c: storage_proof_c,
+        })?);
+        nodes.push(end_node());
+        Ok(nodes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_db::Account;
+
+    fn account(idx: u64) -> Account {
+        Account {
+            nonce: idx,
+            balance: Word::from(idx * 100),
+            storage: HashMap::from([
+                (Word::from(1), Word::from(idx)),
+                (Word::from(2), Word::one()),
+            ]),
+            code_hash: CodeDB::empty_code_hash(),
+        }
+    }
+
+    #[test]
+    fn witness_roots() {
+        let mut sdb = StateDB::new();
+        for idx in 1..=16 {
+            sdb.set_account(&Address::from_low_u64_be(idx), account(idx));
+        }
+        let mut state_trie = StateTrie::from_state_db(&sdb).unwrap();
+        let root = state_trie.root();
+
+        let modifications = vec![
+            TrieModification {
+                typ: ProofType::NonceChanged,
+                address: Address::from_low_u64_be(1),
+                nonce: U64::from(2),
+                ..Default::default()
+            },
+            // creates the account
+            TrieModification {
+                typ: ProofType::BalanceChanged,
+                address: Address::from_low_u64_be(17),
+                balance: Word::from(1700),
+                ..Default::default()
+            },
+            TrieModification {
+                typ: ProofType::StorageChanged,
+                address: Address::from_low_u64_be(2),
+                key: H256::from_low_u64_be(3),
+                value: Word::from(3),
+                ..Default::default()
+            },
+            // deletes the storage slot
+            TrieModification {
+                typ: ProofType::StorageChanged,
+                address: Address::from_low_u64_be(3),
+                key: H256::from_low_u64_be(1),
+                value: Word::zero(),
+                ..Default::default()
+            },
+            TrieModification {
+                typ: ProofType::StorageDoesNotExist,
+                address: Address::from_low_u64_be(4),
+                key: H256::from_low_u64_be(4),
+                ..Default::default()
+            },
+            TrieModification {
+                typ: ProofType::AccountDoesNotExist,
+                address: Address::from_low_u64_be(18),
+                ..Default::default()
+            },
+        ];
+        let nodes = state_trie.witness_nodes(&modifications).unwrap();
+
+        // the same modifications applied to the StateDB
+        let mut expected = sdb.clone();
+        let (_, acc) = expected.get_account_mut(&Address::from_low_u64_be(1));
+        acc.nonce = 2;
+        let (_, acc) = expected.get_account_mut(&Address::from_low_u64_be(17));
+        acc.balance = Word::from(1700);
+        let (_, acc) = expected.get_account_mut(&Address::from_low_u64_be(2));
+        acc.storage.insert(Word::from(3), Word::from(3));
+        let (_, acc) = expected.get_account_mut(&Address::from_low_u64_be(3));
+        acc.storage.remove(&Word::from(1));
+        let expected_root = StateTrie::from_state_db(&expected).unwrap().root();
+        assert_eq!(state_trie.root(), expected_root);
+
+        // the witness of each modification starts from the root left by the
+        // previous one
+        let roots: Vec<(H256, H256)> = nodes
+            .iter()
+            .filter(|node| {
+                node.start
+                    .as_ref()
+                    .is_some_and(|start| start.proof_type != ProofType::Disabled)
+            })
+            .map(|node| {
+                let root = |row: &Vec<u8>| H256::from_slice(&row[1..33]);
+                (root(&node.values[0]), root(&node.values[1]))
+            })
+            .collect();
+        assert_eq!(roots.len(), modifications.len());
+        assert_eq!(roots[0].0, root);
+        assert!(roots.windows(2).all(|pair| pair[0].1 == pair[1].0));
+        assert_eq!(roots.last().unwrap().1, expected_root);
+    }
+}
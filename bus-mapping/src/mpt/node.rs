@@ -0,0 +1,115 @@
+//! Nodes of the MPT circuit witness, serialized in the JSON format loaded by
+//! `zkevm_circuits::mpt_circuit::load_proof`.
+
+use super::ProofType;
+use serde::{Serialize, Serializer};
+
+/// Length of the rows of the witness values.
+pub(crate) const VALUE_LEN: usize = 34;
+
+/// Row of the witness values filled with zeros.
+pub(crate) fn zero_row() -> Vec<u8> {
+    vec![0; VALUE_LEN]
+}
+
+/// Hex encoding of bytes, where empty bytes stand for a [`zero_row`].
+fn encode(bytes: &[u8]) -> String {
+    if bytes.is_empty() {
+        hex::encode(zero_row())
+    } else {
+        hex::encode(bytes)
+    }
+}
+
+fn hex<T: AsRef<[u8]>, S: Serializer>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&encode(bytes.as_ref()))
+}
+
+fn hex_seq<T: AsRef<[Vec<u8>]>, S: Serializer>(
+    items: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(items.as_ref().iter().map(|bytes| encode(bytes)))
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct BranchNode {
+    pub(crate) modified_index: usize,
+    pub(crate) drifted_index: usize,
+    #[serde(serialize_with = "hex_seq")]
+    pub(crate) list_rlp_bytes: [Vec<u8>; 2],
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct ExtensionNode {
+    #[serde(serialize_with = "hex")]
+    pub(crate) list_rlp_bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct StartNode {
+    pub(crate) disable_preimage_check: bool,
+    pub(crate) proof_type: ProofType,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct ExtensionBranchNode {
+    pub(crate) is_extension: bool,
+    pub(crate) is_mod_extension: [bool; 2],
+    pub(crate) is_placeholder: [bool; 2],
+    pub(crate) extension: ExtensionNode,
+    pub(crate) branch: BranchNode,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct AccountNode {
+    #[serde(serialize_with = "hex")]
+    pub(crate) address: Vec<u8>,
+    #[serde(serialize_with = "hex")]
+    pub(crate) key: Vec<u8>,
+    #[serde(serialize_with = "hex_seq")]
+    pub(crate) list_rlp_bytes: [Vec<u8>; 2],
+    #[serde(serialize_with = "hex_seq")]
+    pub(crate) value_rlp_bytes: [Vec<u8>; 2],
+    #[serde(serialize_with = "hex_seq")]
+    pub(crate) value_list_rlp_bytes: [Vec<u8>; 2],
+    #[serde(serialize_with = "hex")]
+    pub(crate) drifted_rlp_bytes: Vec<u8>,
+    #[serde(serialize_with = "hex")]
+    pub(crate) wrong_rlp_bytes: Vec<u8>,
+    pub(crate) is_mod_extension: [bool; 2],
+    #[serde(serialize_with = "hex_seq")]
+    pub(crate) mod_list_rlp_bytes: [Vec<u8>; 2],
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct StorageNode {
+    #[serde(serialize_with = "hex")]
+    pub(crate) address: Vec<u8>,
+    #[serde(serialize_with = "hex")]
+    pub(crate) key: Vec<u8>,
+    #[serde(serialize_with = "hex_seq")]
+    pub(crate) list_rlp_bytes: [Vec<u8>; 2],
+    #[serde(serialize_with = "hex_seq")]
+    pub(crate) value_rlp_bytes: [Vec<u8>; 2],
+    #[serde(serialize_with = "hex")]
+    pub(crate) drifted_rlp_bytes: Vec<u8>,
+    #[serde(serialize_with = "hex")]
+    pub(crate) wrong_rlp_bytes: Vec<u8>,
+    pub(crate) is_mod_extension: [bool; 2],
+    #[serde(serialize_with = "hex_seq")]
+    pub(crate) mod_list_rlp_bytes: [Vec<u8>; 2],
+}
+
+/// Node of the MPT circuit witness.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct Node {
+    pub(crate) start: Option<StartNode>,
+    pub(crate) extension_branch: Option<ExtensionBranchNode>,
+    pub(crate) account: Option<AccountNode>,
+    pub(crate) storage: Option<StorageNode>,
+    #[serde(serialize_with = "hex_seq")]
+    pub(crate) values: Vec<Vec<u8>>,
+    #[serde(serialize_with = "hex_seq")]
+    pub(crate) keccak_data: Vec<Vec<u8>>,
+}
@@ -0,0 +1,546 @@
+//! In-memory Merkle Patricia Trie, following the node layout of geth so that
+//! its proofs are the ones expected by the MPT circuit.
+//!
+//! The trie can start from a root hash only: the nodes which are not in memory
+//! are resolved from a [`NodeDb`] of encoded nodes, typically filled with the
+//! nodes of `eth_getProof` responses.
+
+use crate::Error;
+use eth_types::H256;
+use ethers_core::utils::{
+    keccak256,
+    rlp::{Rlp, RlpStream},
+};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+/// Nibble terminating the key of a leaf.
+const TERMINATOR: u8 = 16;
+
+lazy_static! {
+    /// Root of the empty trie, the hash of the RLP encoded empty string.
+    pub(crate) static ref EMPTY_ROOT: H256 = H256(keccak256([0x80]));
+}
+
+/// Encoded trie nodes by hash.
+pub(crate) type NodeDb = HashMap<H256, Vec<u8>>;
+
+/// Node of the trie.  The keys are in nibbles, and end with [`TERMINATOR`]
+/// for the leaves.
+#[derive(Debug, Clone, Default)]
+pub(crate) enum TrieNode {
+    /// Empty trie or child
+    #[default]
+    Empty,
+    /// Leaf or extension node
+    Short {
+        /// Nibbles of the leaf or extension node
+        key: Vec<u8>,
+        /// Value of the leaf, or branch of the extension node
+        val: Box<TrieNode>,
+    },
+    /// Branch node.  The keys of the state and storage tries have the same
+    /// length, so that a branch never holds a value.
+    Full(Box<[TrieNode; 16]>),
+    /// Node not in memory, resolved from the [`NodeDb`] when needed
+    Hash(H256),
+    /// Value of a leaf
+    Value(Vec<u8>),
+}
+
+impl TrieNode {
+    fn is_empty(&self) -> bool {
+        matches!(self, TrieNode::Empty)
+    }
+
+    /// RLP encoding of the node, where each child is embedded if its
+    /// encoding is shorter than 32 bytes, or replaced by its hash otherwise.
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        match self {
+            TrieNode::Empty => {
+                stream.append_empty_data();
+            }
+            TrieNode::Short { key, val } => {
+                stream
+                    .begin_list(2)
+                    .append(&hex_to_compact(key))
+                    .append_raw(&val.encode_ref(), 1);
+            }
+            TrieNode::Full(children) => {
+                stream.begin_list(17);
+                for child in children.iter() {
+                    stream.append_raw(&child.encode_ref(), 1);
+                }
+                // the value of the branch
+                stream.append_empty_data();
+            }
+            TrieNode::Hash(hash) => {
+                stream.append(hash);
+            }
+            TrieNode::Value(value) => {
+                stream.append(value);
+            }
+        }
+        stream.out().to_vec()
+    }
+
+    /// Encoding of the node as the child of another node.
+    fn encode_ref(&self) -> Vec<u8> {
+        match self {
+            TrieNode::Short { .. } | TrieNode::Full(_) => {
+                let encoded = self.encode();
+                if encoded.len() < 32 {
+                    encoded
+                } else {
+                    TrieNode::Hash(H256(keccak256(&encoded))).encode()
+                }
+            }
+            _ => self.encode(),
+        }
+    }
+
+    /// Decode an encoded node.
+    pub(crate) fn decode(encoded: &[u8]) -> Result<Self, Error> {
+        let invalid = || Error::InvalidTrieNode(encoded.to_vec());
+        let rlp = Rlp::new(encoded);
+        let item = |idx| rlp.at(idx).map_err(|_| invalid());
+        let data = |idx| item(idx)?.data().map(<[u8]>::to_vec).map_err(|_| invalid());
+        match rlp.item_count().map_err(|_| invalid())? {
+            2 => {
+                let key = compact_to_hex(&data(0)?);
+                let val = if has_terminator(&key) {
+                    TrieNode::Value(data(1)?)
+                } else {
+                    Self::decode_ref(&item(1)?)?
+                };
+                Ok(TrieNode::Short {
+                    key,
+                    val: Box::new(val),
+                })
+            }
+            17 => {
+                let mut children: [TrieNode; 16] = Default::default();
+                for (idx, child) in children.iter_mut().enumerate() {
+                    *child = Self::decode_ref(&item(idx)?)?;
+                }
+                if !data(16)?.is_empty() {
+                    return Err(invalid());
+                }
+                Ok(TrieNode::Full(Box::new(children)))
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Decode a child, embedded or referenced by hash.
+    fn decode_ref(rlp: &Rlp) -> Result<Self, Error> {
+        if rlp.is_list() {
+            return Self::decode(rlp.as_raw());
+        }
+        match rlp.data() {
+            Ok([]) => Ok(TrieNode::Empty),
+            Ok(hash) if hash.len() == 32 => Ok(TrieNode::Hash(H256::from_slice(hash))),
+            _ => Err(Error::InvalidTrieNode(rlp.as_raw().to_vec())),
+        }
+    }
+}
+
+/// Proof of a key, with the information about the trie around it needed by
+/// the witness of the MPT circuit.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Proof {
+    /// Encoded nodes on the path of the key, from the root.  The path stops
+    /// at the leaf of the key, or at the node proving its absence.
+    pub(crate) nodes: Vec<Vec<u8>>,
+    /// Encoded node of the last branch child other than the one on the path,
+    /// which becomes the leaf at the position of the branch when the key is
+    /// deleted, or drifts into a new branch when the key is inserted.  `None`
+    /// if it can't be resolved.
+    pub(crate) neighbour: Option<Vec<u8>>,
+    /// Nibbles of the extension nodes on the path
+    pub(crate) ext_nibbles: Vec<Vec<u8>>,
+    /// Whether the last node of the path is a leaf
+    pub(crate) is_last_leaf: bool,
+}
+
+/// Merkle Patricia Trie with keys of 32 bytes.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Trie {
+    root: TrieNode,
+}
+
+impl Trie {
+    /// Trie with the given root, whose nodes are resolved from a [`NodeDb`].
+    pub(crate) fn new(root: H256) -> Self {
+        if root == *EMPTY_ROOT {
+            Self::default()
+        } else {
+            Self {
+                root: TrieNode::Hash(root),
+            }
+        }
+    }
+
+    /// Root hash of the trie.  The root node is always hashed, even when its
+    /// encoding is shorter than 32 bytes.
+    pub(crate) fn hash(&self) -> H256 {
+        match &self.root {
+            TrieNode::Empty => *EMPTY_ROOT,
+            TrieNode::Hash(hash) => *hash,
+            root => H256(keccak256(root.encode())),
+        }
+    }
+
+    /// Returns the value of `key`, if any.
+    pub(crate) fn get(&self, db: &NodeDb, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let mut key = &keybytes_to_hex(key)[..];
+        let mut resolved;
+        let mut node = &self.root;
+        loop {
+            match node {
+                TrieNode::Empty => return Ok(None),
+                TrieNode::Value(value) => return Ok(Some(value.clone())),
+                TrieNode::Short {
+                    key: short_key,
+                    val,
+                } => {
+                    if !key.starts_with(short_key) {
+                        return Ok(None);
+                    }
+                    key = &key[short_key.len()..];
+                    node = val;
+                }
+                TrieNode::Full(children) => {
+                    node = &children[key[0] as usize];
+                    key = &key[1..];
+                }
+                TrieNode::Hash(hash) => {
+                    resolved = resolve(db, *hash)?;
+                    node = &resolved;
+                }
+            }
+        }
+    }
+
+    /// Set the value of `key`, or delete it if `value` is empty.
+    pub(crate) fn update(&mut self, db: &NodeDb, key: &[u8], value: Vec<u8>) -> Result<(), Error> {
+        let key = keybytes_to_hex(key);
+        let root = std::mem::take(&mut self.root);
+        self.root = if value.is_empty() {
+            delete(db, root, &key)?.1
+        } else {
+            insert(db, root, &key, TrieNode::Value(value))?
+        };
+        Ok(())
+    }
+
+    /// Returns the proof of `key`.
+    pub(crate) fn prove(&self, db: &NodeDb, key: &[u8]) -> Result<Proof, Error> {
+        self.prove_nibbles(db, &keybytes_to_hex(key))
+    }
+
+    /// Returns the proof of the path of `nibbles`.
+    pub(crate) fn prove_nibbles(&self, db: &NodeDb, nibbles: &[u8]) -> Result<Proof, Error> {
+        let mut proof = Proof::default();
+        prove(db, &self.root, nibbles, &mut proof)?;
+        Ok(proof)
+    }
+}
+
+fn resolve(db: &NodeDb, hash: H256) -> Result<TrieNode, Error> {
+    let encoded = db.get(&hash).ok_or(Error::TrieNodeNotFound(hash))?;
+    TrieNode::decode(encoded)
+}
+
+fn insert(db: &NodeDb, node: TrieNode, key: &[u8], value: TrieNode) -> Result<TrieNode, Error> {
+    if key.is_empty() {
+        return Ok(value);
+    }
+    match node {
+        TrieNode::Empty => Ok(TrieNode::Short {
+            key: key.to_vec(),
+            val: Box::new(value),
+        }),
+        TrieNode::Short {
+            key: short_key,
+            val,
+        } => {
+            let match_len = prefix_len(key, &short_key);
+            // the whole key matches, keep the node and update its value
+            if match_len == short_key.len() {
+                let val = insert(db, *val, &key[match_len..], value)?;
+                return Ok(TrieNode::Short {
+                    key: short_key,
+                    val: Box::new(val),
+                });
+            }
+            // otherwise branch out where the keys differ
+            let mut children: [TrieNode; 16] = Default::default();
+            children[short_key[match_len] as usize] =
+                insert(db, TrieNode::Empty, &short_key[match_len + 1..], *val)?;
+            children[key[match_len] as usize] =
+                insert(db, TrieNode::Empty, &key[match_len + 1..], value)?;
+            let branch = TrieNode::Full(Box::new(children));
+            if match_len == 0 {
+                Ok(branch)
+            } else {
+                // extension node leading to the branch
+                Ok(TrieNode::Short {
+                    key: key[..match_len].to_vec(),
+                    val: Box::new(branch),
+                })
+            }
+        }
+        TrieNode::Full(mut children) => {
+            let idx = key[0] as usize;
+            children[idx] = insert(db, std::mem::take(&mut children[idx]), &key[1..], value)?;
+            Ok(TrieNode::Full(children))
+        }
+        TrieNode::Hash(hash) => insert(db, resolve(db, hash)?, key, value),
+        TrieNode::Value(_) => Err(Error::InternalError("trie key is longer than the path")),
+    }
+}
+
+/// Delete `key` from the subtrie of `node`, and returns whether it was found,
+/// with the new subtrie reduced to its minimal form.
+fn delete(db: &NodeDb, node: TrieNode, key: &[u8]) -> Result<(bool, TrieNode), Error> {
+    match node {
+        TrieNode::Empty => Ok((false, TrieNode::Empty)),
+        TrieNode::Value(_) => Ok((true, TrieNode::Empty)),
+        TrieNode::Short {
+            key: short_key,
+            val,
+        } => {
+            let match_len = prefix_len(key, &short_key);
+            if match_len < short_key.len() {
+                return Ok((
+                    false,
+                    TrieNode::Short {
+                        key: short_key,
+                        val,
+                    },
+                ));
+            }
+            if match_len == key.len() {
+                return Ok((true, TrieNode::Empty));
+            }
+            let (found, child) = delete(db, *val, &key[short_key.len()..])?;
+            let node = match child {
+                // merge the extension node with the short node its branch
+                // was reduced to
+                TrieNode::Short {
+                    key: child_key,
+                    val,
+                } if found => TrieNode::Short {
+                    key: [short_key, child_key].concat(),
+                    val,
+                },
+                child => TrieNode::Short {
+                    key: short_key,
+                    val: Box::new(child),
+                },
+            };
+            Ok((found, node))
+        }
+        TrieNode::Full(mut children) => {
+            let idx = key[0] as usize;
+            let (found, child) = delete(db, std::mem::take(&mut children[idx]), &key[1..])?;
+            children[idx] = child;
+            if !found || !children[idx].is_empty() {
+                return Ok((found, TrieNode::Full(children)));
+            }
+
+            // a branch left with a single child is reduced to a short node
+            let remaining: Vec<usize> = (0..16).filter(|pos| !children[*pos].is_empty()).collect();
+            let [pos] = remaining[..] else {
+                return Ok((true, TrieNode::Full(children)));
+            };
+            let child = match std::mem::take(&mut children[pos]) {
+                TrieNode::Hash(hash) => resolve(db, hash)?,
+                child => child,
+            };
+            let node = match child {
+                TrieNode::Short {
+                    key: child_key,
+                    val,
+                } => TrieNode::Short {
+                    key: [vec![pos as u8], child_key].concat(),
+                    val,
+                },
+                child => TrieNode::Short {
+                    key: vec![pos as u8],
+                    val: Box::new(child),
+                },
+            };
+            Ok((true, node))
+        }
+        TrieNode::Hash(hash) => delete(db, resolve(db, hash)?, key),
+    }
+}
+
+fn prove(db: &NodeDb, node: &TrieNode, key: &[u8], proof: &mut Proof) -> Result<(), Error> {
+    if key.is_empty() {
+        return Ok(());
+    }
+    match node {
+        TrieNode::Empty | TrieNode::Value(_) => Ok(()),
+        TrieNode::Short {
+            key: short_key,
+            val,
+        } => {
+            proof.nodes.push(node.encode());
+            proof.is_last_leaf = has_terminator(short_key);
+            if !proof.is_last_leaf {
+                proof.ext_nibbles.push(short_key.clone());
+            }
+            if key.starts_with(short_key) {
+                prove(db, val, &key[short_key.len()..], proof)
+            } else {
+                // the trie doesn't contain the key
+                Ok(())
+            }
+        }
+        TrieNode::Full(children) => {
+            proof.nodes.push(node.encode());
+            proof.is_last_leaf = false;
+            let idx = key[0] as usize;
+            if let Some(neighbour) = children
+                .iter()
+                .enumerate()
+                .rev()
+                .find(|(pos, child)| *pos != idx && !child.is_empty())
+                .map(|(_, child)| child)
+            {
+                proof.neighbour = match neighbour {
+                    TrieNode::Hash(hash) => db.get(hash).cloned(),
+                    neighbour => Some(neighbour.encode()),
+                };
+            }
+            prove(db, &children[idx], &key[1..], proof)
+        }
+        TrieNode::Hash(hash) => prove(db, &resolve(db, *hash)?, key, proof),
+    }
+}
+
+fn prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+fn has_terminator(nibbles: &[u8]) -> bool {
+    nibbles.last() == Some(&TERMINATOR)
+}
+
+/// Nibbles of `key`, ending with the [`TERMINATOR`].
+pub(crate) fn keybytes_to_hex(key: &[u8]) -> Vec<u8> {
+    key.iter()
+        .flat_map(|byte| [byte / 16, byte % 16])
+        .chain([TERMINATOR])
+        .collect()
+}
+
+/// Bytes of the nibbles of a key, without the [`TERMINATOR`].
+pub(crate) fn hex_to_keybytes(nibbles: &[u8]) -> Vec<u8> {
+    let nibbles = nibbles.strip_suffix(&[TERMINATOR]).unwrap_or(nibbles);
+    nibbles
+        .chunks(2)
+        .map(|pair| pair[0] * 16 + pair.get(1).copied().unwrap_or_default())
+        .collect()
+}
+
+/// Compact encoding of nibbles, whose first byte flags whether it's a leaf
+/// key and whether the number of nibbles is odd.
+pub(crate) fn hex_to_compact(nibbles: &[u8]) -> Vec<u8> {
+    let (nibbles, is_leaf) = match nibbles.strip_suffix(&[TERMINATOR]) {
+        Some(nibbles) => (nibbles, true),
+        None => (nibbles, false),
+    };
+    let mut flags = (is_leaf as u8) << 5;
+    let nibbles = if nibbles.len() % 2 == 1 {
+        flags |= 1 << 4 | nibbles[0];
+        &nibbles[1..]
+    } else {
+        nibbles
+    };
+    [flags]
+        .into_iter()
+        .chain(nibbles.chunks(2).map(|pair| pair[0] * 16 + pair[1]))
+        .collect()
+}
+
+fn compact_to_hex(compact: &[u8]) -> Vec<u8> {
+    let Some(flags) = compact.first() else {
+        return Vec::new();
+    };
+    let mut nibbles = keybytes_to_hex(compact);
+    if flags >> 5 == 0 {
+        // extension node, without terminator
+        nibbles.pop();
+    }
+    // skip the flags, and the padding nibble of an even number of nibbles
+    let skip = if flags >> 4 & 1 == 1 { 1 } else { 2 };
+    nibbles.split_off(skip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn trie_root() {
+        let db = NodeDb::new();
+        let mut trie = Trie::default();
+        assert_eq!(trie.hash(), *EMPTY_ROOT);
+
+        // keys of different lengths, as in the trie tests of geth
+        for (key, value) in [
+            ("doe", "reindeer"),
+            ("dog", "puppy"),
+            ("dogglesworth", "cat"),
+        ] {
+            trie.update(&db, key.as_bytes(), value.as_bytes().to_vec())
+                .unwrap();
+        }
+        assert_eq!(
+            trie.hash(),
+            H256::from_str("0x8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3")
+                .unwrap()
+        );
+        assert_eq!(trie.get(&db, b"dog").unwrap(), Some(b"puppy".to_vec()));
+    }
+
+    #[test]
+    fn trie_delete_and_resolve() {
+        let mut db = NodeDb::new();
+        let mut trie = Trie::default();
+        let keys: Vec<_> = (0u8..8).map(|idx| keccak256([idx])).collect();
+        for key in keys.iter() {
+            trie.update(&db, key, vec![1; 40]).unwrap();
+        }
+
+        // deleting a key restores the root of the trie without it
+        trie.update(&db, &keys[7], vec![]).unwrap();
+        let mut expected = Trie::default();
+        for key in keys[..7].iter() {
+            expected.update(&db, key, vec![1; 40]).unwrap();
+        }
+        assert_eq!(trie.hash(), expected.hash());
+
+        // the nodes of a proof are enough to read the key from the root hash
+        let proof = expected.prove(&db, &keys[3]).unwrap();
+        assert!(proof.is_last_leaf);
+        db.extend(
+            proof
+                .nodes
+                .into_iter()
+                .map(|node| (H256(keccak256(&node)), node)),
+        );
+        let trie = Trie::new(expected.hash());
+        assert_eq!(trie.get(&db, &keys[3]).unwrap(), Some(vec![1; 40]));
+        assert!(matches!(
+            trie.get(&db, &keys[4]),
+            Err(Error::TrieNodeNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,1075 @@
+//! Conversion of the proofs of a key before and after a modification into
+//! the nodes of the MPT circuit witness.
+//!
+//! The layout of the nodes follows the witness generator of the MPT circuit,
+//! which used to be run from `geth-utils` through its Go bindings.
+
+use super::{
+    node::{
+        zero_row, AccountNode, BranchNode, ExtensionBranchNode, ExtensionNode, Node, StartNode,
+        StorageNode, VALUE_LEN,
+    },
+    trie::{hex_to_compact, hex_to_keybytes, NodeDb, Proof, Trie},
+    ProofType,
+};
+use crate::Error;
+use eth_types::H256;
+use ethers_core::utils::rlp::Rlp;
+
+/// Number of leaf rows holding the extension node before and after its
+/// modification.
+const MOD_EXTENSION_ROW_LEN: usize = 6;
+
+/// Rows of the values of an account leaf.
+#[derive(Debug, Clone, Copy)]
+enum AccountRow {
+    KeyS,
+    KeyC,
+    NonceS,
+    BalanceS,
+    StorageS,
+    CodehashS,
+    NonceC,
+    BalanceC,
+    StorageC,
+    CodehashC,
+    Drifted,
+    Wrong,
+}
+
+/// Proofs of a key before (S) and after (C) its modification, with the
+/// information needed to convert them into witness nodes.
+pub(crate) struct ProofInput<'a> {
+    /// Trie after the modification
+    pub(crate) trie: &'a Trie,
+    /// Nodes to resolve the trie
+    pub(crate) db: &'a NodeDb,
+    /// Whether the proofs are in the account trie or in a storage trie
+    pub(crate) is_account_proof: bool,
+    /// Account address, or storage key
+    pub(crate) address: Vec<u8>,
+    /// Nibbles of the hashed address or key
+    pub(crate) key: Vec<u8>,
+    /// Whether the key is proven not to exist
+    pub(crate) non_existing: bool,
+    /// Proof before the modification
+    pub(crate) s: Proof,
+    /// Proof after the modification
+    pub(crate) c: Proof,
+}
+
+impl ProofInput<'_> {
+    /// Neighbour of the longer proof, the one with an additional branch if
+    /// the modification adds or removes one.
+    fn neighbour(&self) -> Option<&[u8]> {
+        if self.s.nodes.len() > self.c.nodes.len() {
+            self.s.neighbour.as_deref()
+        } else {
+            self.c.neighbour.as_deref()
+        }
+    }
+
+    fn is_shorter_proof_last_leaf(&self) -> bool {
+        if self.s.nodes.len() > self.c.nodes.len() {
+            self.c.is_last_leaf
+        } else {
+            self.s.is_last_leaf
+        }
+    }
+}
+
+/// Rows of an extension node above a branch.
+#[derive(Debug, Clone)]
+struct ExtensionRows {
+    list_rlp_bytes: Vec<u8>,
+    values: Vec<Vec<u8>>,
+}
+
+impl Default for ExtensionRows {
+    fn default() -> Self {
+        Self {
+            list_rlp_bytes: Vec::new(),
+            values: vec![zero_row(); 4],
+        }
+    }
+}
+
+/// Node starting the witness of a modification, holding the roots of the
+/// trie before and after it.
+pub(crate) fn start_node(proof_type: ProofType, s_root: H256, c_root: H256) -> Node {
+    let root_row = |root: H256| [&[160][..], root.as_bytes(), &[0]].concat();
+    Node {
+        start: Some(StartNode {
+            disable_preimage_check: false,
+            proof_type,
+        }),
+        values: vec![root_row(s_root), root_row(c_root)],
+        ..Default::default()
+    }
+}
+
+/// Node ending the witness of a modification.
+pub(crate) fn end_node() -> Node {
+    let mut row = zero_row();
+    row[0] = 160;
+    Node {
+        start: Some(StartNode {
+            disable_preimage_check: false,
+            proof_type: ProofType::Disabled,
+        }),
+        values: vec![row.clone(), row],
+        ..Default::default()
+    }
+}
+
+/// Copy as many bytes of `src` as fit into `dst`.
+fn copy_into(dst: &mut [u8], src: &[u8]) {
+    let len = dst.len().min(src.len());
+    dst[..len].copy_from_slice(&src[..len]);
+}
+
+/// Returns whether the encoded node is a branch, or otherwise a leaf or an
+/// extension node.
+fn is_branch(node: &[u8]) -> Result<bool, Error> {
+    match Rlp::new(node).item_count() {
+        Ok(17) => Ok(true),
+        Ok(2) => Ok(false),
+        _ => Err(Error::InvalidTrieNode(node.to_vec())),
+    }
+}
+
+/// Converts the proofs of a key before and after a modification into witness
+/// nodes.  Alongside, it collects the byte streams to be hashed by the Keccak
+/// circuit.
+pub(crate) fn convert_proof_to_witness(input: &ProofInput) -> Result<Vec<Node>, Error> {
+    let (proof1, proof2) = (&input.s.nodes, &input.c.nodes);
+    let (len1, len2) = (proof1.len(), proof2.len());
+    let min_len = len1.min(len2);
+
+    // When a value in the trie is updated, both proofs are of the same length.
+    // Otherwise, when a value is added (not updated) and there is no node which
+    // needs to be changed into a branch, one proof has a leaf and one does not
+    // have it.  The third option is when a value is added and the existing leaf
+    // is turned into a branch, in this case we have an additional branch in C
+    // proof (when deleting a value causes that a branch with two leaves turns
+    // into a leaf, we have an additional branch in S proof).
+    let additional_branch = if len1 < len2 && len1 > 0 {
+        !is_branch(&proof1[len1 - 1])?
+    } else if len2 < len1 && len2 > 0 {
+        !is_branch(&proof2[len2 - 1])?
+    } else {
+        false
+    };
+    let up_to = if len1 != len2 && additional_branch {
+        min_len - 1
+    } else {
+        min_len
+    };
+
+    let mut key_index = 0;
+    let mut is_extension = false;
+    let mut extension_node_ind = 0;
+    let mut ext = ExtensionRows::default();
+    let mut nodes = Vec::new();
+
+    for i in 0..up_to {
+        if !is_branch(&proof1[i])? {
+            let are_there_nibbles =
+                !input.s.ext_nibbles.is_empty() || !input.c.ext_nibbles.is_empty();
+            // A short node which is not the last one is an extension node.  In a
+            // non-existing proof the last node can also be an extension node, with
+            // nil in the underlying branch.
+            if i != up_to - 1 || (are_there_nibbles && input.non_existing) {
+                is_extension = true;
+                let (number_of_nibbles, rows) = prepare_extensions(
+                    &input.s.ext_nibbles,
+                    extension_node_ind,
+                    &proof1[i],
+                    &proof2[i],
+                );
+                ext = rows;
+                key_index += number_of_nibbles;
+                extension_node_ind += 1;
+                continue;
+            }
+
+            nodes.push(prepare_leaf_node(
+                input,
+                &proof1[len1 - 1],
+                &proof2[len1 - 1],
+                None,
+            )?);
+        } else {
+            let ext_nodes = is_extension.then(|| [&proof1[i - 1][..], &proof2[i - 1][..]]);
+            nodes.push(prepare_branch_node(
+                &proof1[i],
+                &proof2[i],
+                ext_nodes,
+                &ext,
+                input.key[key_index],
+                input.key[key_index],
+                [false, false],
+            ));
+            key_index += 1;
+            is_extension = false;
+        }
+    }
+
+    if len1 != len2 {
+        if additional_branch {
+            // to compute the drifted position
+            let leaf_row0 = if len1 > len2 {
+                &proof2[len2 - 1]
+            } else {
+                &proof1[len1 - 1]
+            };
+
+            let (is_modified_ext_node, number_of_nibbles, branch_node) =
+                add_branch_and_placeholder(input, leaf_row0, key_index, extension_node_ind)?;
+            nodes.push(branch_node);
+
+            // add the leaf after the branch placeholder
+            let mut leaf_node = if !is_modified_ext_node {
+                prepare_leaf_node(
+                    input,
+                    &proof1[len1 - 1],
+                    &proof2[len2 - 1],
+                    input.neighbour(),
+                )?
+            } else {
+                prepare_leaf_and_placeholder_node(input, [len2 > len1, len1 > len2])?
+            };
+
+            // When a proof element is a modified extension node (new extension node
+            // appears at the position of the existing extension node), additional
+            // rows are added (extension node before and after modification).
+            if is_modified_ext_node {
+                equip_leaf_with_mod_extension_node(
+                    input,
+                    &mut leaf_node,
+                    key_index,
+                    extension_node_ind,
+                    number_of_nibbles,
+                )?;
+            }
+            nodes.push(leaf_node);
+        } else {
+            nodes.push(prepare_leaf_and_placeholder_node(input, [false, false])?);
+        }
+    } else if (len1 == 0 && len2 == 0) || is_branch(&proof2[len2 - 1])? {
+        // When only the branches are returned by a non-existing proof, a
+        // placeholder leaf is added to enable the lookup.
+        if input.is_account_proof {
+            nodes.push(prepare_account_leaf_placeholder_node(input, key_index)?);
+        } else {
+            nodes.push(prepare_storage_leaf_placeholder_node(input, key_index));
+        }
+    }
+
+    Ok(nodes)
+}
+
+/// Fills the rows 1 to 16 with the children of the branch, each in its own row.
+fn prepare_branch_witness(rows: &mut [Vec<u8>], branch: &[u8], branch_rlp_offset: usize) {
+    let mut row_ind = 1;
+    let mut col_ind = 0;
+    // bytes of the current child still to be placed in the row
+    let mut remaining = 0;
+    // the last byte is the value of the branch
+    for &byte in &branch[branch_rlp_offset..branch.len() - 1] {
+        if remaining == 0 && byte == 128 {
+            rows[row_ind][0] = byte;
+            row_ind += 1;
+        } else if remaining == 0 {
+            // hashed or non-hashed node
+            remaining = if byte == 160 { 32 } else { byte as usize - 192 };
+            col_ind = 0;
+            rows[row_ind][col_ind] = byte;
+        } else {
+            col_ind += 1;
+            rows[row_ind][col_ind] = byte;
+            remaining -= 1;
+            if remaining == 0 {
+                row_ind += 1;
+                col_ind = 0;
+            }
+        }
+    }
+}
+
+fn prepare_branch_node(
+    branch1: &[u8],
+    branch2: &[u8],
+    ext_nodes: Option<[&[u8]; 2]>,
+    ext: &ExtensionRows,
+    modified_index: u8,
+    drifted_index: u8,
+    is_placeholder: [bool; 2],
+) -> Node {
+    let rlp_offset = |branch: &[u8]| match branch[0] {
+        248 => 2,
+        249 => 3,
+        _ => 1,
+    };
+    let (branch1_rlp_offset, branch2_rlp_offset) = (rlp_offset(branch1), rlp_offset(branch2));
+
+    let mut values = vec![zero_row(); 17];
+    prepare_branch_witness(&mut values, branch1, branch1_rlp_offset);
+    // the modified child after the modification
+    let mut rows = vec![zero_row(); 17];
+    prepare_branch_witness(&mut rows, branch2, branch2_rlp_offset);
+    values[0] = rows.swap_remove(1 + modified_index as usize);
+    values.extend(ext.values.iter().cloned());
+
+    let mut keccak_data = vec![branch1.to_vec(), branch2.to_vec()];
+    if let Some(ext_nodes) = ext_nodes {
+        keccak_data.extend(ext_nodes.map(<[u8]>::to_vec));
+    }
+
+    Node {
+        extension_branch: Some(ExtensionBranchNode {
+            is_extension: ext_nodes.is_some(),
+            is_mod_extension: [false, false],
+            is_placeholder,
+            extension: ExtensionNode {
+                list_rlp_bytes: ext.list_rlp_bytes.clone(),
+            },
+            branch: BranchNode {
+                modified_index: modified_index as usize,
+                drifted_index: drifted_index as usize,
+                list_rlp_bytes: [
+                    branch1[..branch1_rlp_offset].to_vec(),
+                    branch2[..branch2_rlp_offset].to_vec(),
+                ],
+            },
+        }),
+        values,
+        keccak_data,
+        ..Default::default()
+    }
+}
+
+/// Returns the position in the branch to which the leaf drifted because
+/// another leaf has been added to the same slot.
+fn get_drifted_position(leaf_key_row: &[u8], number_of_nibbles: usize) -> u8 {
+    let start = if leaf_key_row[0] != 248 { 1 } else { 2 };
+    let key_len = (leaf_key_row[start] - 128) as usize;
+    let mut nibbles = Vec::new();
+    let first = leaf_key_row[start + 1];
+    // the second condition is for an extension node
+    if first != 32 && first != 0 {
+        nibbles.push(if first < 32 { first - 16 } else { first - 48 });
+    }
+    // the first byte doesn't have any nibbles
+    for byte in &leaf_key_row[start + 2..start + 1 + key_len] {
+        nibbles.extend([byte / 16, byte % 16]);
+    }
+    nibbles[number_of_nibbles]
+}
+
+/// Prepares a branch and its placeholder counterpart, used when one of the
+/// proofs has one branch more than the other.  Returns whether the last node
+/// of the shorter proof is an extension node being modified, with the number
+/// of nibbles of the extension node above the branch.
+fn add_branch_and_placeholder(
+    input: &ProofInput,
+    leaf_row0: &[u8],
+    key_index: usize,
+    extension_node_ind: usize,
+) -> Result<(bool, usize, Node), Error> {
+    let (proof1, proof2) = (&input.s.nodes, &input.c.nodes);
+    let (len1, len2) = (proof1.len(), proof2.len());
+
+    let is_extension = len1 == len2 + 2 || len2 == len1 + 2;
+    let ext_node = is_extension.then(|| {
+        if len1 > len2 {
+            &proof1[len1 - 3][..]
+        } else {
+            &proof2[len2 - 3][..]
+        }
+    });
+    let (number_of_nibbles, ext) = match ext_node {
+        Some(ext_node) => {
+            let ext_nibbles = if len1 > len2 {
+                &input.s.ext_nibbles
+            } else {
+                &input.c.ext_nibbles
+            };
+            prepare_extensions(ext_nibbles, extension_node_ind, ext_node, ext_node)
+        }
+        None => (0, ExtensionRows::default()),
+    };
+
+    // When a new extension node is inserted at the position of an existing
+    // extension node, the longer proof contains the new extension node and the
+    // underlying branch, while the shorter proof stops at the old extension
+    // node.  The old extension node, shortened in the trie after the
+    // modification, is added to the leaf rows by
+    // `equip_leaf_with_mod_extension_node`.
+    let long_ext_node = if len1 > len2 {
+        &proof2[len2 - 1]
+    } else {
+        &proof1[len1 - 1]
+    };
+    let is_modified_ext_node = !is_branch(long_ext_node)? && !input.is_shorter_proof_last_leaf();
+
+    // the first nibble of the leaf that was turned into a branch, which is its
+    // position once it moved into the new branch
+    let drifted_ind = get_drifted_position(leaf_row0, number_of_nibbles);
+    let (branch, is_placeholder) = if len1 > len2 {
+        (&proof1[len1 - 2], [false, true])
+    } else {
+        (&proof2[len2 - 2], [true, false])
+    };
+    let node = prepare_branch_node(
+        branch,
+        branch,
+        ext_node.map(|ext_node| [ext_node, ext_node]),
+        &ext,
+        input.key[key_index + number_of_nibbles],
+        drifted_ind,
+        is_placeholder,
+    );
+
+    Ok((is_modified_ext_node, number_of_nibbles, node))
+}
+
+/// Returns the number of nibbles of the extension node, with the rows of the
+/// extension node before and after the modification.
+fn prepare_extensions(
+    ext_nibbles: &[Vec<u8>],
+    extension_node_ind: usize,
+    proof_el1: &[u8],
+    proof_el2: &[u8],
+) -> (usize, ExtensionRows) {
+    let [mut v1, mut v2, mut v3, mut v4] = [zero_row(), zero_row(), zero_row(), zero_row()];
+    let list_rlp_bytes = prepare_extension(&mut v1, &mut v2, proof_el1, true);
+    prepare_extension(&mut v3, &mut v4, proof_el2, false);
+
+    let even_number_of_nibbles = proof_el1[2] == 0;
+    let key_len = get_extension_node_key_len(proof_el1);
+    let number_of_nibbles = get_extension_number_of_nibbles(proof_el1);
+
+    // The nibbles are needed to compute the key RLC.  There can be up to 64
+    // nibbles but only 32 bytes in a row, so every second nibble is stored
+    // (having the whole byte and one nibble is enough to compute the other).
+    let start_nibble_pos = if key_len > 1 && even_number_of_nibbles {
+        1
+    } else {
+        2
+    };
+    for (ind, nibble) in ext_nibbles[extension_node_ind]
+        .iter()
+        .skip(start_nibble_pos)
+        .step_by(2)
+        .enumerate()
+    {
+        v3[2 + ind] = *nibble;
+    }
+
+    (
+        number_of_nibbles,
+        ExtensionRows {
+            list_rlp_bytes,
+            values: vec![v1, v2, v3, v4],
+        },
+    )
+}
+
+/// Returns the number of bytes of the key of the extension node, and the
+/// position of its first byte.
+fn get_extension_len_start_key(proof_el: &[u8]) -> (usize, usize) {
+    // `proof_el[1] <= 32` means only one nibble, stored as `16 + nibble`
+    if proof_el[1] <= 32 {
+        (1, 1)
+    } else if proof_el[0] <= 247 {
+        ((proof_el[1] - 128) as usize, 2)
+    } else {
+        ((proof_el[2] - 128) as usize, 3)
+    }
+}
+
+fn get_extension_node_key_len(proof_el: &[u8]) -> usize {
+    get_extension_len_start_key(proof_el).0
+}
+
+fn get_extension_number_of_nibbles(proof_el: &[u8]) -> usize {
+    let even_number_of_nibbles = proof_el[2] == 0;
+    match get_extension_node_key_len(proof_el) {
+        0 => 0,
+        1 => 1,
+        key_len if even_number_of_nibbles => (key_len - 1) * 2,
+        key_len => (key_len - 1) * 2 + 1,
+    }
+}
+
+fn get_extension_node_nibbles(proof_el: &[u8]) -> Vec<u8> {
+    let (len_key, start_key) = get_extension_len_start_key(proof_el);
+    let mut nibbles = Vec::new();
+    if proof_el[start_key] != 0 {
+        nibbles.push(proof_el[start_key] - 16);
+    }
+    // the first byte doesn't have any nibbles
+    for byte in &proof_el[start_key + 1..start_key + len_key] {
+        nibbles.extend([byte / 16, byte % 16]);
+    }
+    nibbles
+}
+
+/// Sets the key of the extension node into `v1` if `set_key`, and its branch
+/// into `v2`.  Returns the RLP bytes of the extension node list.
+fn prepare_extension(v1: &mut [u8], v2: &mut [u8], proof_el: &[u8], set_key: bool) -> Vec<u8> {
+    let mut list_rlp_bytes = vec![proof_el[0]];
+
+    let (mut len_key, mut start_key) = get_extension_len_start_key(proof_el);
+    if len_key != 1 {
+        // the value row contains the RLP byte of the key length
+        start_key -= 1;
+        len_key += 1;
+    }
+    if start_key == 3 {
+        list_rlp_bytes.push(proof_el[1]);
+    }
+
+    if set_key {
+        v1[..len_key].copy_from_slice(&proof_el[start_key..start_key + len_key]);
+    }
+
+    let encoded_node_len = proof_el[start_key + len_key];
+    let node_len = if encoded_node_len > 192 {
+        // non-hashed branch
+        (encoded_node_len - 192) as usize
+    } else if encoded_node_len == 160 {
+        // hashed branch
+        32
+    } else {
+        0
+    };
+    v2[0] = encoded_node_len;
+    let node_start = start_key + len_key + 1;
+    v2[1..1 + node_len].copy_from_slice(&proof_el[node_start..node_start + node_len]);
+
+    list_rlp_bytes
+}
+
+/// Adds the rows of the extension node before and after the modification to
+/// the leaf, when an existing extension node gets shortened or elongated (in
+/// terms of nibbles) because of another extension node being added or
+/// deleted.
+fn equip_leaf_with_mod_extension_node(
+    input: &ProofInput,
+    leaf_node: &mut Node,
+    key_index: usize,
+    extension_node_ind: usize,
+    number_of_nibbles: usize,
+) -> Result<(), Error> {
+    let (proof1, proof2) = (&input.s.nodes, &input.c.nodes);
+    let (len1, len2) = (proof1.len(), proof2.len());
+
+    let (long_ext_node, ext_nibbles) = if len1 > len2 {
+        (&proof2[len2 - 1], &input.c.ext_nibbles)
+    } else {
+        (&proof1[len1 - 1], &input.s.ext_nibbles)
+    };
+    let (_, ext_s) = prepare_extensions(
+        ext_nibbles,
+        extension_node_ind,
+        long_ext_node,
+        long_ext_node,
+    );
+
+    // nibbles of the extension node that gets shortened because of the newly
+    // inserted extension node
+    let long_nibbles = get_extension_node_nibbles(long_ext_node);
+
+    // There is no short extension node when only one nibble is left, in this
+    // case there is simply a branch instead.
+    let short_ext_node_is_branch = long_nibbles.len() == number_of_nibbles + 1;
+
+    let (short_ext_node, ext_c) = if !short_ext_node_is_branch {
+        let short_ext_node = if len2 > len1 {
+            // The shortened extension node is a child of the new branch, next to the
+            // leaf: it's found in the trie after the modification at the path of the
+            // old extension node nibbles.
+            let mut long_ext_node_key = input.key.clone();
+            long_ext_node_key[key_index + number_of_nibbles..key_index + long_nibbles.len()]
+                .copy_from_slice(&long_nibbles[number_of_nibbles..]);
+            let proof = input.trie.prove_nibbles(input.db, &long_ext_node_key)?;
+            proof
+                .nodes
+                .get(len2 - 1)
+                .cloned()
+                .ok_or(Error::InternalError("shortened extension node not found"))?
+        } else {
+            let short_nibbles = &long_nibbles[number_of_nibbles + 1..];
+            let mut compact = hex_to_compact(short_nibbles);
+            // the extension node can't have the length of its list in a second byte
+            let long_start_branch = 2 + (long_ext_node[1] - 128) as usize;
+            if short_nibbles.len() > 1 {
+                compact.insert(0, 128 + compact.len() as u8);
+            }
+            let mut short_ext_node = [&compact[..], &long_ext_node[long_start_branch..]].concat();
+            short_ext_node.insert(0, 192 + short_ext_node.len() as u8);
+            short_ext_node
+        };
+
+        let nibbles = get_extension_node_nibbles(&short_ext_node);
+        let (_, ext_c) = prepare_extensions(&[nibbles], 0, &short_ext_node, &short_ext_node);
+        (short_ext_node, ext_c)
+    } else {
+        // Nothing is to be put in the witness of the short node, the long node is
+        // copied to let the circuit know that the short node is a branch.
+        (Vec::new(), ext_s.clone())
+    };
+
+    let list_rlp_bytes = [ext_s.list_rlp_bytes, ext_c.list_rlp_bytes];
+    if let Some(account) = leaf_node.account.as_mut() {
+        account.mod_list_rlp_bytes = list_rlp_bytes;
+    } else if let Some(storage) = leaf_node.storage.as_mut() {
+        storage.mod_list_rlp_bytes = list_rlp_bytes;
+    }
+
+    // The long and short extension nodes are put in the last rows of the leaf
+    // rather than in the branch rows, which would increase the space needed for
+    // every branch.  The rows of the branch of the extension nodes are skipped.
+    let values = [ext_s.values, ext_c.values]
+        .into_iter()
+        .flat_map(|values| values.into_iter().enumerate())
+        .filter_map(|(idx, row)| (idx != 1).then_some(row));
+    let start = leaf_node.values.len() - MOD_EXTENSION_ROW_LEN;
+    for (row, value) in leaf_node.values[start..].iter_mut().zip(values) {
+        *row = value;
+    }
+    leaf_node
+        .keccak_data
+        .extend([long_ext_node.clone(), short_ext_node]);
+
+    Ok(())
+}
+
+fn prepare_leaf_node(
+    input: &ProofInput,
+    leaf_s: &[u8],
+    leaf_c: &[u8],
+    neighbour: Option<&[u8]>,
+) -> Result<Node, Error> {
+    if input.is_account_proof {
+        prepare_account_leaf_node(input, leaf_s, leaf_c, neighbour, false, [false, false])
+    } else {
+        Ok(prepare_storage_leaf_node(
+            input,
+            leaf_s,
+            leaf_c,
+            neighbour,
+            input.non_existing,
+            [false, false],
+            [false, false],
+        ))
+    }
+}
+
+/// Prepares a leaf and its placeholder counterpart, used when one of the
+/// proofs doesn't have the leaf.
+fn prepare_leaf_and_placeholder_node(
+    input: &ProofInput,
+    is_mod_extension: [bool; 2],
+) -> Result<Node, Error> {
+    let (proof1, proof2) = (&input.s.nodes, &input.c.nodes);
+    let (len1, len2) = (proof1.len(), proof2.len());
+    let (leaf, is_placeholder) = if len1 > len2 {
+        (&proof1[len1 - 1], [false, true])
+    } else {
+        (&proof2[len2 - 1], [true, false])
+    };
+
+    if input.is_account_proof {
+        prepare_account_leaf_node(input, leaf, leaf, None, false, is_mod_extension)
+    } else {
+        Ok(prepare_storage_leaf_node(
+            input,
+            leaf,
+            leaf,
+            None,
+            false,
+            is_placeholder,
+            is_mod_extension,
+        ))
+    }
+}
+
+/// Returns the nonce and balance rows of the account leaf, with the position
+/// of its storage root.
+fn get_nonce_balance_value(leaf: &[u8], key_len: usize) -> (Vec<u8>, Vec<u8>, usize) {
+    // A value byte up to 128 is the value itself (0 is stored as 128),
+    // otherwise it's 128 + the length of the value.
+    let rlp_item = |start: usize| {
+        let len = if leaf[start] <= 128 {
+            1
+        } else {
+            (leaf[start] - 128) as usize + 1
+        };
+        (&leaf[start..start + len], start + len)
+    };
+
+    let (nonce, balance_start) = rlp_item(3 + key_len + 4);
+    let (balance, storage_start) = rlp_item(balance_start);
+
+    let mut nonce_value = zero_row();
+    let mut balance_value = zero_row();
+    copy_into(&mut nonce_value, nonce);
+    copy_into(&mut balance_value, balance);
+
+    (nonce_value, balance_value, storage_start)
+}
+
+/// Returns the storage root and code hash rows of the account leaf.
+fn get_storage_root_code_hash_value(
+    leaf: &[u8],
+    storage_start: usize,
+) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    let hash_value = |start: usize| {
+        if leaf[start] != 160 {
+            return Err(Error::InvalidTrieNode(leaf.to_vec()));
+        }
+        let mut value = zero_row();
+        value[..33].copy_from_slice(&leaf[start..start + 33]);
+        Ok(value)
+    };
+    Ok((hash_value(storage_start)?, hash_value(storage_start + 33)?))
+}
+
+fn prepare_account_leaf_node(
+    input: &ProofInput,
+    leaf_s: &[u8],
+    leaf_c: &[u8],
+    neighbour: Option<&[u8]>,
+    is_placeholder: bool,
+    is_mod_extension: [bool; 2],
+) -> Result<Node, Error> {
+    let key_len_s = (leaf_s[2] - 128) as usize;
+    let key_len_c = (leaf_c[2] - 128) as usize;
+
+    let mut key_row_s = zero_row();
+    let mut key_row_c = zero_row();
+    key_row_s[..key_len_s + 1].copy_from_slice(&leaf_s[2..3 + key_len_s]);
+    key_row_c[..key_len_c + 1].copy_from_slice(&leaf_c[2..3 + key_len_c]);
+
+    let (key_drifted, drifted_rlp_bytes) = match neighbour {
+        Some(neighbour) => {
+            let (key, _, rlp_bytes, _) = prepare_storage_leaf_info(neighbour, false);
+            (key, rlp_bytes)
+        }
+        None => (zero_row(), vec![0]),
+    };
+
+    // For a non-existing account proof, the key rows store the key of the wrong
+    // leaf, while the key of the required leaf (which doesn't exist) is stored in
+    // the wrong row.
+    let mut wrong_value = zero_row();
+    let wrong_rlp_bytes = leaf_c[..2].to_vec();
+    wrong_value[0] = leaf_c[2];
+    let mut nibbles_num = (key_len_c - 1) * 2;
+    let offset = if leaf_c[3] != 32 {
+        // odd number of nibbles
+        nibbles_num += 1;
+        wrong_value[1] = input.key[64 - nibbles_num] + 48;
+        1
+    } else {
+        wrong_value[1] = 32;
+        0
+    };
+    // the last nibbles of the address, without the terminator
+    let remaining_nibbles = &input.key[64 - nibbles_num..64];
+    for i in 0..key_len_c - 1 {
+        wrong_value[2 + i] =
+            remaining_nibbles[2 * i + offset] * 16 + remaining_nibbles[2 * i + 1 + offset];
+    }
+
+    // The value is a string of a list, both with their length in a second byte:
+    // [248,112,157,59,...,184,80,248,78,...]
+    for (leaf, key_len) in [(leaf_s, key_len_s), (leaf_c, key_len_c)] {
+        let rlp_string_len = leaf[3 + key_len + 1];
+        let rlp_list_len = leaf[3 + key_len + 3];
+        if leaf[3 + key_len].wrapping_sub(183) != 1
+            || leaf[3 + key_len + 2].wrapping_sub(247) != 1
+            || rlp_string_len != rlp_list_len.wrapping_add(2)
+        {
+            return Err(Error::InvalidTrieNode(leaf.to_vec()));
+        }
+    }
+
+    let value_rows = |leaf: &[u8], key_len: usize| -> Result<[Vec<u8>; 4], Error> {
+        if is_placeholder {
+            return Ok([zero_row(), zero_row(), zero_row(), zero_row()]);
+        }
+        let (nonce, balance, storage_start) = get_nonce_balance_value(leaf, key_len);
+        let (storage_root, code_hash) = get_storage_root_code_hash_value(leaf, storage_start)?;
+        Ok([nonce, balance, storage_root, code_hash])
+    };
+    let [nonce_s, balance_s, storage_s, codehash_s] = value_rows(leaf_s, key_len_s)?;
+    let [nonce_c, balance_c, storage_c, codehash_c] = value_rows(leaf_c, key_len_c)?;
+
+    let mut values = vec![Vec::new(); AccountRow::Wrong as usize + 1];
+    values[AccountRow::KeyS as usize] = key_row_s;
+    values[AccountRow::KeyC as usize] = key_row_c;
+    values[AccountRow::NonceS as usize] = nonce_s;
+    values[AccountRow::BalanceS as usize] = balance_s;
+    values[AccountRow::StorageS as usize] = storage_s;
+    values[AccountRow::CodehashS as usize] = codehash_s;
+    values[AccountRow::NonceC as usize] = nonce_c;
+    values[AccountRow::BalanceC as usize] = balance_c;
+    values[AccountRow::StorageC as usize] = storage_c;
+    values[AccountRow::CodehashC as usize] = codehash_c;
+    values[AccountRow::Drifted as usize] = key_drifted;
+    values[AccountRow::Wrong as usize] = wrong_value;
+    // set by `equip_leaf_with_mod_extension_node` for a modified extension node
+    values.extend(vec![zero_row(); MOD_EXTENSION_ROW_LEN]);
+
+    let mut keccak_data = vec![leaf_s.to_vec(), leaf_c.to_vec(), input.address.clone()];
+    keccak_data.extend(neighbour.map(<[u8]>::to_vec));
+
+    Ok(Node {
+        account: Some(AccountNode {
+            address: input.address.clone(),
+            key: hex_to_keybytes(&input.key),
+            list_rlp_bytes: [leaf_s[..2].to_vec(), leaf_c[..2].to_vec()],
+            value_rlp_bytes: [
+                leaf_s[3 + key_len_s..5 + key_len_s].to_vec(),
+                leaf_c[3 + key_len_c..5 + key_len_c].to_vec(),
+            ],
+            value_list_rlp_bytes: [
+                leaf_s[5 + key_len_s..7 + key_len_s].to_vec(),
+                leaf_c[5 + key_len_c..7 + key_len_c].to_vec(),
+            ],
+            drifted_rlp_bytes,
+            wrong_rlp_bytes,
+            is_mod_extension,
+            ..Default::default()
+        }),
+        values,
+        keccak_data,
+        ..Default::default()
+    })
+}
+
+/// Returns the number of bytes of the key of a leaf below `key_index` nibbles
+/// used by the branches and extension nodes above it.
+fn get_leaf_key_len(key_index: usize) -> usize {
+    (64 - key_index) / 2 + 1
+}
+
+/// Prepares a placeholder account leaf, added when the non-existing proof
+/// ends with a branch.
+fn prepare_account_leaf_placeholder_node(
+    input: &ProofInput,
+    key_index: usize,
+) -> Result<Node, Error> {
+    let key_len = get_leaf_key_len(key_index);
+    let remaining_nibbles = &input.key[key_index..];
+    // the value length is not enough for the placeholder leaf
+    let mut leaf = vec![0; 40];
+    leaf[0] = 248;
+    leaf[1] = key_len as u8 + 73;
+    leaf[2] = key_len as u8 + 128;
+    leaf[3 + key_len] = 184;
+    leaf[3 + key_len + 2] = 248;
+    leaf[3 + key_len + 3] = leaf[3 + key_len + 1].wrapping_sub(2);
+    let offset = if key_index % 2 == 0 {
+        leaf[3] = 32;
+        0
+    } else {
+        leaf[3] = remaining_nibbles[0] + 48;
+        1
+    };
+    for i in 0..key_len - 1 {
+        leaf[4 + i] =
+            remaining_nibbles[2 * i + offset] * 16 + remaining_nibbles[2 * i + 1 + offset];
+    }
+
+    let mut node = prepare_account_leaf_node(input, &leaf, &leaf, None, true, [false, false])?;
+    if let Some(account) = node.account.as_mut() {
+        account.value_rlp_bytes = [vec![184, 70], vec![184, 70]];
+        account.value_list_rlp_bytes = [vec![248, 68], vec![248, 68]];
+    }
+    for row in [
+        AccountRow::StorageS,
+        AccountRow::StorageC,
+        AccountRow::CodehashS,
+        AccountRow::CodehashC,
+    ] {
+        node.values[row as usize][0] = 160;
+    }
+
+    Ok(node)
+}
+
+/// Prepares a placeholder storage leaf, added when the non-existing proof
+/// ends with a branch or when the storage trie is empty.
+fn prepare_storage_leaf_placeholder_node(input: &ProofInput, key_index: usize) -> Node {
+    // The placeholder leaf in the empty trie occupies 35 bytes:
+    // [227,161,32,0,...,0]
+    let key_len = get_leaf_key_len(key_index);
+    let mut leaf = vec![0; VALUE_LEN + 1];
+    leaf[0] = 192 + 1 + key_len as u8 + 1;
+    leaf[1] = key_len as u8 + 128;
+    leaf[2] = if key_index % 2 == 0 {
+        32
+    } else {
+        input.key[key_index] + 48
+    };
+
+    prepare_storage_leaf_node(
+        input,
+        &leaf,
+        &leaf,
+        None,
+        false,
+        [true, true],
+        [false, false],
+    )
+}
+
+/// Returns the key and value rows of a storage leaf, with the RLP bytes of its
+/// list and of its value.
+fn prepare_storage_leaf_info(
+    row: &[u8],
+    is_placeholder: bool,
+) -> (Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>) {
+    let mut key = zero_row();
+    let mut value = zero_row();
+
+    let (mut key_rlp, key_len, offset) = if row.len() < 32 {
+        // the node doesn't get hashed
+        if row[1] < 128 {
+            // last level: [194,32,1], or only one nibble in the leaf: [194,48,1]
+            copy_into(&mut key, &row[1..2]);
+            (row[..1].to_vec(), 1, 1)
+        } else {
+            // [196,130,32,0,1]
+            let key_len = (row[1] - 128) as usize;
+            copy_into(&mut key, &row[1..key_len + 2]);
+            (row[..1].to_vec(), key_len, 2)
+        }
+    } else if row[0] == 248 {
+        // [248,67,160,59,138,106,70,105,186,37,13,38,205,122,69,158,202,157,...
+        let key_len = (row[2] - 128) as usize;
+        copy_into(&mut key, &row[2..key_len + 3]);
+        (row[..2].to_vec(), key_len, 3)
+    } else if row[1] < 128 {
+        // last level: [227,32,161,160,187,239,...], or one nibble: [227,48,161,160,...]
+        key[0] = row[0];
+        key[1] = row[1];
+        (row[..1].to_vec(), 2, 0)
+    } else {
+        // [226,160,59,138,106,70,105,186,37,13,38,...
+        let key_len = (row[1] - 128) as usize;
+        copy_into(&mut key, &row[1..key_len + 2]);
+        (row[..1].to_vec(), key_len, 2)
+    };
+
+    let value_start = key_len + offset;
+    let value_rlp = if !is_placeholder {
+        copy_into(&mut value, &row[value_start + 1..]);
+        row[value_start..value_start + 1].to_vec()
+    } else {
+        // The value of the placeholder is 0, of length 1, so that the list
+        // holds the key bytes and one value byte.
+        key_rlp = vec![(192 + key_len + 2) as u8];
+        vec![0]
+    };
+
+    (key, value, key_rlp, value_rlp)
+}
+
+/// Returns the RLP bytes of the wrong leaf of a non-existing storage proof,
+/// with the row holding the key of the required leaf.
+fn prepare_non_existing_storage_row(leaf_c: &[u8], key_nibbles: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let mut non_existing_storage_row = zero_row();
+
+    let (wrong_rlp_bytes, start) = if leaf_c[0] == 248 {
+        (leaf_c[..2].to_vec(), 3)
+    } else {
+        (leaf_c[..1].to_vec(), 2)
+    };
+    let key_len_c = (leaf_c[start - 1] - 128) as usize;
+
+    let mut nibbles_num = (key_len_c - 1) * 2;
+    non_existing_storage_row[0] = leaf_c[start - 1];
+    let first_key_byte = if key_len_c > 1 { leaf_c[start] } else { 0 };
+    let offset = if first_key_byte != 32 {
+        // odd number of nibbles
+        nibbles_num += 1;
+        non_existing_storage_row[1] = key_nibbles[64 - nibbles_num] + 48;
+        1
+    } else {
+        non_existing_storage_row[1] = 32;
+        0
+    };
+    // the last nibbles of the key, without the terminator
+    let remaining_nibbles = &key_nibbles[64 - nibbles_num..64];
+    for i in 0..key_len_c - 1 {
+        non_existing_storage_row[2 + i] =
+            remaining_nibbles[2 * i + offset] * 16 + remaining_nibbles[2 * i + 1 + offset];
+    }
+
+    (wrong_rlp_bytes, non_existing_storage_row)
+}
+
+fn prepare_storage_leaf_node(
+    input: &ProofInput,
+    leaf_s: &[u8],
+    leaf_c: &[u8],
+    neighbour: Option<&[u8]>,
+    non_existing: bool,
+    is_placeholder: [bool; 2],
+    is_mod_extension: [bool; 2],
+) -> Node {
+    let (key_s, value_s, list_rlp_bytes_s, value_rlp_bytes_s) =
+        prepare_storage_leaf_info(leaf_s, is_placeholder[0]);
+    let (key_c, value_c, list_rlp_bytes_c, value_rlp_bytes_c) =
+        prepare_storage_leaf_info(leaf_c, is_placeholder[1]);
+
+    let (key_drifted, drifted_rlp_bytes) = match neighbour {
+        Some(neighbour) => {
+            let (key, _, rlp_bytes, _) = prepare_storage_leaf_info(neighbour, false);
+            (key, rlp_bytes)
+        }
+        None => (zero_row(), vec![0]),
+    };
+
+    let (wrong_rlp_bytes, non_existing_storage_row) = if non_existing {
+        prepare_non_existing_storage_row(leaf_c, &input.key)
+    } else {
+        (Vec::new(), zero_row())
+    };
+
+    let mut values = vec![
+        key_s,
+        value_s,
+        key_c,
+        value_c,
+        key_drifted,
+        non_existing_storage_row,
+    ];
+    // set by `equip_leaf_with_mod_extension_node` for a modified extension node
+    values.extend(vec![zero_row(); MOD_EXTENSION_ROW_LEN]);
+
+    let mut keccak_data = vec![leaf_s.to_vec(), leaf_c.to_vec(), input.address.clone()];
+    keccak_data.extend(neighbour.map(<[u8]>::to_vec));
+
+    Node {
+        storage: Some(StorageNode {
+            address: input.address.clone(),
+            key: hex_to_keybytes(&input.key),
+            list_rlp_bytes: [list_rlp_bytes_s, list_rlp_bytes_c],
+            value_rlp_bytes: [value_rlp_bytes_s, value_rlp_bytes_c],
+            drifted_rlp_bytes,
+            wrong_rlp_bytes,
+            is_mod_extension,
+            ..Default::default()
+        }),
+        values,
+        keccak_data,
+        ..Default::default()
+    }
+}
@@ -118,6 +118,8 @@ pub enum Target {
     TxReceipt,
     /// Means the target of the operation is the TxLog.
     TxLog,
+    /// Means the target of the operation is the TxCreatedAccount.
+    TxCreatedAccount,
 
     /// Chunking: StepState
     StepState,
@@ -140,6 +142,7 @@ impl Target {
             self,
             Target::TxAccessListAccount
                 | Target::TxAccessListAccountStorage
+                | Target::TxCreatedAccount
                 | Target::TxRefund
                 | Target::Account
                 | Target::Storage
@@ -612,6 +615,57 @@ impl Op for TxAccessListAccountStorageOp {
     }
 }
 
+/// Represents a change in the set of accounts created in the current
+/// transaction, implied by a `BeginTx` or `CREATE*` step and read by
+/// `SELFDESTRUCT` to decide whether the account is destroyed (EIP-6780).
+#[derive(Clone, PartialEq, Eq)]
+pub struct TxCreatedAccountOp {
+    /// Transaction ID: Transaction index in the block starting at 1.
+    pub tx_id: usize,
+    /// Account Address
+    pub address: Address,
+    /// Whether the account has been created in the transaction.
+    pub is_created: bool,
+    /// Whether the account had been created in the transaction before this
+    /// operation.
+    pub is_created_prev: bool,
+}
+
+impl fmt::Debug for TxCreatedAccountOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TxCreatedAccountOp { ")?;
+        f.write_fmt(format_args!(
+            "tx_id: {:?}, addr: {:?}, is_created_prev: {:?}, is_created: {:?}",
+            self.tx_id, self.address, self.is_created_prev, self.is_created
+        ))?;
+        f.write_str(" }")
+    }
+}
+
+impl PartialOrd for TxCreatedAccountOp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TxCreatedAccountOp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.tx_id, &self.address).cmp(&(&other.tx_id, &other.address))
+    }
+}
+
+impl Op for TxCreatedAccountOp {
+    fn into_enum(self) -> OpEnum {
+        OpEnum::TxCreatedAccount(self)
+    }
+
+    fn reverse(&self) -> Self {
+        let mut rev = self.clone();
+        swap(&mut rev.is_created, &mut rev.is_created_prev);
+        rev
+    }
+}
+
 /// Represents a change in the Transaction Refund AccessList implied by an
 /// `SSTORE`, `STOP`, `RETURN` or `REVERT` step of the
 /// [`ExecStep`](crate::circuit_input_builder::ExecStep).
@@ -1161,6 +1215,8 @@ pub enum OpEnum {
     TxAccessListAccount(TxAccessListAccountOp),
     /// TxAccessListAccountStorage
     TxAccessListAccountStorage(TxAccessListAccountStorageOp),
+    /// TxCreatedAccount
+    TxCreatedAccount(TxCreatedAccountOp),
     /// TxRefund
     TxRefund(TxRefundOp),
     /// Account
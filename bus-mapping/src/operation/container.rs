@@ -1,7 +1,7 @@
 use super::{
     AccountOp, CallContextOp, MemoryOp, Op, OpEnum, Operation, PaddingOp, RWCounter, StackOp,
     StartOp, StepStateOp, StorageOp, Target, TransientStorageOp, TxAccessListAccountOp,
-    TxAccessListAccountStorageOp, TxLogOp, TxReceiptOp, TxRefundOp, RW,
+    TxAccessListAccountStorageOp, TxCreatedAccountOp, TxLogOp, TxReceiptOp, TxRefundOp, RW,
 };
 use crate::exec_trace::OperationRef;
 use itertools::Itertools;
@@ -34,6 +34,8 @@ pub struct OperationContainer {
     pub tx_access_list_account: Vec<Operation<TxAccessListAccountOp>>,
     /// Operations of TxAccessListAccountStorageOp
     pub tx_access_list_account_storage: Vec<Operation<TxAccessListAccountStorageOp>>,
+    /// Operations of TxCreatedAccountOp
+    pub tx_created_account: Vec<Operation<TxCreatedAccountOp>>,
     /// Operations of TxRefundOp
     pub tx_refund: Vec<Operation<TxRefundOp>>,
     /// Operations of AccountOp
@@ -58,6 +60,50 @@ impl Default for OperationContainer {
     }
 }
 
+/// Implements the length bookkeeping of the [`OperationContainer`] over its
+/// `fields`, which must be all of them: the destructuring in `lens` fails to
+/// compile if one is missing.
+macro_rules! impl_container_lens {
+    ($($field:ident),* $(,)?) => {
+        impl OperationContainer {
+            /// Number of fields of the container, one per [`Target`]
+            pub(crate) const NUM_FIELDS: usize = [$(stringify!($field)),*].len();
+
+            /// Returns the number of operations of each target, to
+            /// [`truncate`](Self::truncate) the container back to it.
+            pub(crate) fn lens(&self) -> [usize; Self::NUM_FIELDS] {
+                let Self { $($field),* } = self;
+                [$($field.len()),*]
+            }
+
+            /// Drops the operations inserted after the container had the given
+            /// [`lens`](Self::lens), which are in the order of the fields.
+            pub(crate) fn truncate(&mut self, lens: [usize; Self::NUM_FIELDS]) {
+                let [$($field),*] = lens;
+                $(self.$field.truncate($field);)*
+            }
+        }
+    };
+}
+
+impl_container_lens!(
+    memory,
+    stack,
+    storage,
+    transient_storage,
+    tx_access_list_account,
+    tx_access_list_account_storage,
+    tx_created_account,
+    tx_refund,
+    account,
+    call_context,
+    tx_receipt,
+    tx_log,
+    start,
+    padding,
+    step_state,
+);
+
 // TODO: impl Index for OperationContainer
 impl OperationContainer {
     /// Generates a new instance of an `OperationContainer`.
@@ -69,6 +115,7 @@ impl OperationContainer {
             transient_storage: Vec::new(),
             tx_access_list_account: Vec::new(),
             tx_access_list_account_storage: Vec::new(),
+            tx_created_account: Vec::new(),
             tx_refund: Vec::new(),
             account: Vec::new(),
             call_context: Vec::new(),
@@ -153,6 +200,14 @@ impl OperationContainer {
                     self.tx_access_list_account_storage.len() - 1,
                 ))
             }
+            OpEnum::TxCreatedAccount(op) => {
+                self.tx_created_account.push(if reversible {
+                    Operation::new_reversible(rwc, rwc_inner_chunk, rw, op)
+                } else {
+                    Operation::new(rwc, rwc_inner_chunk, rw, op)
+                });
+                OperationRef::from((Target::TxCreatedAccount, self.tx_created_account.len() - 1))
+            }
             OpEnum::TxRefund(op) => {
                 self.tx_refund.push(if reversible {
                     Operation::new_reversible(rwc, rwc_inner_chunk, rw, op)
@@ -1,15 +1,39 @@
 //! precompile helpers
 
 use eth_types::{
-    evm_types::{GasCost, OpcodeId},
+    evm_types::{GasCost, Hardfork, OpcodeId},
     Address, Bytecode, ToBigEndian, Word,
 };
+use std::iter;
+
+use crate::Error;
 #[cfg(not(target_arch = "wasm32"))]
 use revm_precompile::{Precompile, PrecompileError, Precompiles};
 
+/// Maximum length in bytes of the base, exponent and modulus of a MODEXP call supported by the
+/// circuits, i.e. 1024-bit integers.
+pub const MODEXP_SIZE_LIMIT: usize = 128;
+
+/// Length of the MODEXP input header, i.e. the lengths of the base, exponent and modulus as
+/// 32-byte big-endian words.
+pub const MODEXP_HEADER_LEN: usize = 96;
+
+/// Number of MODEXP input bytes that can be used by a call within the size limits. Any input
+/// bytes beyond are ignored.
+pub const MODEXP_INPUT_LIMIT: usize = MODEXP_HEADER_LEN + 3 * MODEXP_SIZE_LIMIT;
+
+/// Precompiled contracts available in `hardfork`.
+#[cfg(not(target_arch = "wasm32"))]
+fn precompiles(hardfork: Hardfork) -> &'static Precompiles {
+    match hardfork {
+        Hardfork::London | Hardfork::Shanghai => Precompiles::berlin(),
+        Hardfork::Cancun => Precompiles::cancun(),
+    }
+}
+
 #[allow(unused_variables)]
-/// Check if address is a precompiled or not.
-pub fn is_precompiled(address: &Address) -> bool {
+/// Check if address is a precompiled in `hardfork` or not.
+pub fn is_precompiled(address: &Address, hardfork: Hardfork) -> bool {
     #[cfg(target_arch = "wasm32")]
     if address.0[0..19] == [0u8; 19] && (1..=9).contains(&address.0[19]) {
         // TODO add support for precompiles in WASM
@@ -19,7 +43,7 @@ pub fn is_precompiled(address: &Address) -> bool {
     }
 
     #[cfg(not(target_arch = "wasm32"))]
-    Precompiles::berlin()
+    precompiles(hardfork)
         .get(address.as_fixed_bytes())
         .is_some()
 }
@@ -29,6 +53,7 @@ pub(crate) fn execute_precompiled(
     address: &Address,
     input: &[u8],
     gas: u64,
+    hardfork: Hardfork,
 ) -> (Vec<u8>, u64, bool) {
     #[cfg(target_arch = "wasm32")]
     // TODO add support for precompiles in WASM
@@ -37,7 +62,7 @@ pub(crate) fn execute_precompiled(
     #[cfg(not(target_arch = "wasm32"))]
     {
         let Some(Precompile::Standard(precompile_fn)) =
-            Precompiles::berlin().get(address.as_fixed_bytes())
+            precompiles(hardfork).get(address.as_fixed_bytes())
         else {
             panic!("calling non-exist precompiled contract address")
         };
@@ -151,6 +176,7 @@ impl PrecompileCalls {
         match self {
             Self::Ecrecover | Self::Bn128Add => Some(128),
             Self::Bn128Mul => Some(96),
+            Self::Modexp => Some(MODEXP_INPUT_LIMIT),
             _ => None,
         }
     }
@@ -285,6 +311,71 @@ impl EcrecoverAuxData {
     }
 }
 
+/// Split the input of a MODEXP call into the big-endian base, exponent and modulus, reading
+/// missing input bytes as zeros. Returns an error when one of them exceeds
+/// [`MODEXP_SIZE_LIMIT`].
+pub fn parse_modexp_input(input: &[u8]) -> Result<[Vec<u8>; 3], Error> {
+    let mut bytes = input.iter().copied().chain(iter::repeat(0));
+    let [base_len, exp_len, mod_len] = [(); 3].map(|_| {
+        let word: Vec<u8> = bytes.by_ref().take(32).collect();
+        Word::from_big_endian(&word)
+    });
+    if [base_len, exp_len, mod_len]
+        .iter()
+        .any(|len| *len > Word::from(MODEXP_SIZE_LIMIT))
+    {
+        return Err(Error::ModExpOversize(base_len, exp_len, mod_len));
+    }
+    Ok([base_len, exp_len, mod_len].map(|len| bytes.by_ref().take(len.as_usize()).collect()))
+}
+
+/// Auxiliary data for MODEXP
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModExpAuxData {
+    /// Length of the base.
+    pub base_len: usize,
+    /// Length of the exponent.
+    pub exp_len: usize,
+    /// Length of the modulus.
+    pub mod_len: usize,
+    /// Index of the most significant bit of the first 32 bytes of the exponent, or 0 when they
+    /// are all zero. Together with the lengths, it determines the gas cost (EIP-2565).
+    pub exp_msb: u64,
+    /// Input bytes to the modexp call.
+    pub input_bytes: Vec<u8>,
+    /// Output bytes of the modexp computation, which are known even when the call runs out of
+    /// gas.
+    pub output_bytes: Vec<u8>,
+    /// Bytes returned to the caller from the modexp call.
+    pub return_bytes: Vec<u8>,
+}
+
+impl ModExpAuxData {
+    /// Create a new instance of modexp auxiliary data.
+    pub fn new(
+        [base, exponent, modulus]: &[Vec<u8>; 3],
+        input: &[u8],
+        output: &[u8],
+        return_bytes: &[u8],
+    ) -> Self {
+        let exp_head = &exponent[..exponent.len().min(32)];
+        let exp_msb = exp_head
+            .iter()
+            .position(|byte| *byte != 0)
+            .map(|idx| 8 * (exp_head.len() - idx) as u64 - exp_head[idx].leading_zeros() as u64 - 1)
+            .unwrap_or_default();
+        Self {
+            base_len: base.len(),
+            exp_len: exponent.len(),
+            mod_len: modulus.len(),
+            exp_msb,
+            input_bytes: input.to_vec(),
+            output_bytes: output.to_vec(),
+            return_bytes: return_bytes.to_vec(),
+        }
+    }
+}
+
 /// Auxiliary data attached to an internal state for precompile verification.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PrecompileAuxData {
@@ -299,6 +390,8 @@ pub enum PrecompileAuxData {
     },
     /// Ecrecover.
     Ecrecover(EcrecoverAuxData),
+    /// Modexp.
+    Modexp(ModExpAuxData),
 }
 
 impl Default for PrecompileAuxData {
@@ -23,16 +23,37 @@ const VALUE_ZERO: Word = Word::zero();
 
 /// Memory storage for contract code by code hash.
 #[derive(Debug, Clone, Default)]
-pub struct CodeDB(HashMap<Hash, Vec<u8>>);
+pub struct CodeDB {
+    codes: HashMap<Hash, Vec<u8>>,
+    // Hashes of the codes inserted since the last checkpoint, if any.
+    journal: Option<Vec<Hash>>,
+}
 
 impl CodeDB {
     /// Insert code indexed by code hash, and return the code hash.
     pub fn insert(&mut self, code: Vec<u8>) -> Hash {
         let hash = Self::hash(&code);
-        self.0.insert(hash, code);
+        if self.codes.insert(hash, code).is_none() {
+            if let Some(journal) = &mut self.journal {
+                journal.push(hash);
+            }
+        }
         hash
     }
 
+    /// Start recording the codes inserted from now on, to remove them with
+    /// [`Self::revert_to_checkpoint`].
+    pub fn checkpoint(&mut self) {
+        self.journal = Some(Vec::new());
+    }
+
+    /// Remove the codes inserted since the last [`Self::checkpoint`].
+    pub fn revert_to_checkpoint(&mut self) {
+        for hash in self.journal.take().expect("no checkpoint") {
+            self.codes.remove(&hash);
+        }
+    }
+
     /// Compute hash of given code.
     pub fn hash(code: &[u8]) -> Hash {
         H256(keccak256(code))
@@ -45,12 +66,12 @@ impl CodeDB {
 
     /// Compute number of rows required for bytecode table.
     pub fn num_rows_required_for_bytecode_table(&self) -> usize {
-        self.0.values().map(|bytecode| bytecode.len() + 1).sum()
+        self.codes.values().map(|bytecode| bytecode.len() + 1).sum()
     }
 
     /// Query Bytecode by H256
     pub fn get_from_h256(&self, codehash: &H256) -> Option<Bytecode> {
-        self.0.get(codehash).cloned().map(|code| code.into())
+        self.codes.get(codehash).cloned().map(|code| code.into())
     }
 
     /// Query Bytecode by U256
@@ -61,12 +82,15 @@ impl CodeDB {
 
 impl From<Vec<Vec<u8>>> for CodeDB {
     fn from(bytecodes: Vec<Vec<u8>>) -> Self {
-        Self(HashMap::from_iter(
-            bytecodes
-                .iter()
-                .cloned()
-                .map(|bytecode| (Self::hash(&bytecode), bytecode)),
-        ))
+        Self {
+            codes: HashMap::from_iter(
+                bytecodes
+                    .iter()
+                    .cloned()
+                    .map(|bytecode| (Self::hash(&bytecode), bytecode)),
+            ),
+            journal: None,
+        }
     }
 }
 
@@ -75,7 +99,7 @@ impl IntoIterator for CodeDB {
     type IntoIter = std::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0
+        self.codes
             .values()
             .cloned()
             .map(Bytecode::from)
@@ -145,7 +169,13 @@ pub struct StateDB {
     // Accounts that have been through `SELFDESTRUCT` under the situation that `is_persistent` is
     // `true`. These accounts will be reset once `commit_tx` is called.
     destructed_account: HashSet<Address>,
+    // Accounts that have been created in the current transaction. Used by `SELFDESTRUCT` to
+    // decide whether the account is actually destructed (EIP-6780).
+    created_account: HashSet<Address>,
     refund: u64,
+    // Accounts as they were before their first modification since the last checkpoint, if any,
+    // `None` for the ones which didn't exist.
+    journal: Option<HashMap<Address, Option<Account>>>,
 }
 
 impl StateDB {
@@ -156,6 +186,7 @@ impl StateDB {
 
     /// Set an [`Account`] at `addr` in the StateDB.
     pub fn set_account(&mut self, addr: &Address, acc: Account) {
+        self.journal_account(addr);
         self.state.insert(*addr, acc);
     }
 
@@ -168,10 +199,16 @@ impl StateDB {
         }
     }
 
+    /// Iterate over the [`Account`]s of the state, by address.
+    pub fn accounts(&self) -> impl Iterator<Item = (&Address, &Account)> {
+        self.state.iter()
+    }
+
     /// Get a mutable reference to the [`Account`] at `addr`.  If the
     /// [`Account`] is not found in the state, a zero one will be inserted
     /// and returned along with false.
     pub fn get_account_mut(&mut self, addr: &Address) -> (bool, &mut Account) {
+        self.journal_account(addr);
         let found = if self.state.contains_key(addr) {
             true
         } else {
@@ -305,6 +342,49 @@ impl StateDB {
         self.destructed_account.insert(addr);
     }
 
+    /// Get the accounts set as self destructed in the current transaction,
+    /// sorted by address.
+    pub fn destructed_accounts(&self) -> Vec<Address> {
+        self.destructed_account.iter().copied().sorted().collect()
+    }
+
+    /// Get the non-zero storage slots of the [`Account`] at `addr`, sorted by
+    /// key.
+    /// Returns dirty storage state, which includes writes in current tx
+    pub fn get_storage_slots(&self, addr: &Address) -> Vec<(Word, Word)> {
+        let (_, acc) = self.get_account(addr);
+        acc.storage
+            .keys()
+            .chain(
+                self.dirty_storage
+                    .keys()
+                    .filter(|(address, _)| address == addr)
+                    .map(|(_, key)| key),
+            )
+            .unique()
+            .map(|key| (*key, *self.get_storage(addr, key).1))
+            .filter(|(_, value)| !value.is_zero())
+            .sorted()
+            .collect()
+    }
+
+    /// Check whether `addr` has been created in the current transaction.
+    pub fn check_account_created(&self, addr: &Address) -> bool {
+        self.created_account.contains(addr)
+    }
+
+    /// Mark `addr` as created in the current transaction. Returns `true` if
+    /// it's not marked before.
+    pub fn add_created_account(&mut self, addr: Address) -> bool {
+        self.created_account.insert(addr)
+    }
+
+    /// Unmark `addr` as created in the current transaction.
+    pub fn remove_created_account(&mut self, addr: &Address) {
+        let exist = self.created_account.remove(addr);
+        debug_assert!(exist);
+    }
+
     /// Retrieve refund.
     pub fn refund(&self) -> u64 {
         self.refund
@@ -326,10 +406,11 @@ impl StateDB {
             *ptr = value;
         }
         self.dirty_storage = HashMap::new();
-        for addr in self.destructed_account.clone() {
+        for addr in std::mem::take(&mut self.destructed_account) {
             let (_, account) = self.get_account_mut(&addr);
             *account = ACCOUNT_ZERO.clone();
         }
+        self.created_account = HashSet::new();
         self.refund = 0;
     }
 
@@ -337,6 +418,42 @@ impl StateDB {
     pub fn clear_transient_storage(&mut self) {
         self.transient_storage = HashMap::new();
     }
+
+    /// Start recording the [`Account`]s modified from now on, to restore them
+    /// with [`Self::revert_to_checkpoint`].  The fields with transaction
+    /// lifespan are not recorded, so it must be called between transactions.
+    pub fn checkpoint(&mut self) {
+        self.journal = Some(HashMap::new());
+    }
+
+    /// Restore the [`Account`]s modified since the last [`Self::checkpoint`],
+    /// and clear the fields with transaction lifespan, as they were at the
+    /// checkpoint.
+    pub fn revert_to_checkpoint(&mut self) {
+        for (addr, account) in self.journal.take().expect("no checkpoint") {
+            match account {
+                Some(account) => self.state.insert(addr, account),
+                None => self.state.remove(&addr),
+            };
+        }
+        self.access_list_account = HashSet::new();
+        self.access_list_account_storage = HashSet::new();
+        self.dirty_storage = HashMap::new();
+        self.transient_storage = HashMap::new();
+        self.destructed_account = HashSet::new();
+        self.created_account = HashSet::new();
+        self.refund = 0;
+    }
+
+    /// Record the [`Account`] at `addr` before its first modification since
+    /// the last checkpoint.
+    fn journal_account(&mut self, addr: &Address) {
+        if let Some(journal) = &mut self.journal {
+            journal
+                .entry(*addr)
+                .or_insert_with(|| self.state.get(addr).cloned());
+        }
+    }
 }
 
 #[cfg(test)]
@@ -398,4 +515,40 @@ mod statedb_tests {
         assert!(found);
         assert_eq!(value, &Word::from(102));
     }
+
+    #[test]
+    fn statedb_revert_to_checkpoint() {
+        let addr_a = address!("0x0000000000000000000000000000000000000001");
+        let addr_b = address!("0x0000000000000000000000000000000000000002");
+        let mut statedb = StateDB::new();
+        statedb.increase_nonce(&addr_a);
+        statedb.checkpoint();
+
+        // Modify an existing account, create another one and leave a tx unfinished
+        statedb.increase_nonce(&addr_a);
+        statedb.set_storage(&addr_a, &Word::from(1), &Word::from(2));
+        statedb.commit_tx();
+        statedb.get_account_mut(&addr_b).1.balance = Word::from(3);
+        statedb.set_storage(&addr_b, &Word::from(4), &Word::from(5));
+        statedb.add_account_to_access_list(addr_b);
+
+        statedb.revert_to_checkpoint();
+        assert_eq!(statedb.get_nonce(&addr_a), 1);
+        assert!(statedb.get_account(&addr_a).1.storage.is_empty());
+        assert!(!statedb.get_account(&addr_b).0);
+        assert_eq!(
+            statedb.get_storage(&addr_b, &Word::from(4)).1,
+            &Word::zero()
+        );
+        assert!(!statedb.check_account_in_access_list(&addr_b));
+
+        let mut code_db = CodeDB::default();
+        let hash = code_db.insert(vec![0x00]);
+        code_db.checkpoint();
+        code_db.insert(vec![0x00]);
+        let new_hash = code_db.insert(vec![0x01]);
+        code_db.revert_to_checkpoint();
+        assert!(code_db.get_from_h256(&hash).is_some());
+        assert!(code_db.get_from_h256(&new_hash).is_none());
+    }
 }
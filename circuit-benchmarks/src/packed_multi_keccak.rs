@@ -21,7 +21,10 @@ mod tests {
     use rand::SeedableRng;
     use rand_xorshift::XorShiftRng;
     use std::env::var;
-    use zkevm_circuits::{keccak_circuit::TestKeccakCircuit, util::SubCircuit};
+    use zkevm_circuits::{
+        keccak_circuit::{KeccakCircuitParams, TestKeccakCircuit},
+        util::SubCircuit,
+    };
 
     #[cfg_attr(not(feature = "benches"), ignore)]
     #[test]
@@ -40,11 +43,16 @@ mod tests {
         // Use the complete circuit
         let inputs = vec![(0u8..135).collect::<Vec<_>>(); 3];
 
-        // Create the circuit. Leave last dozens of rows for blinding.
+        // Create the circuit, with lookup tables as high as the circuit. Leave last dozens of
+        // rows for blinding.
         let circuit = TestKeccakCircuit::new(
             2usize.pow(degree) - TestKeccakCircuit::<Fr>::unusable_rows(),
             inputs,
-        );
+        )
+        .with_params(KeccakCircuitParams {
+            degree: degree as usize,
+            ..Default::default()
+        });
 
         // Initialize the polynomial commitment parameters
         let mut rng = XorShiftRng::from_seed([
@@ -3,7 +3,9 @@
 #[cfg(test)]
 mod tests {
     use ark_std::{end_timer, start_timer};
-    use bus_mapping::circuit_input_builder::FixedCParams;
+    use bus_mapping::circuit_input_builder::{
+        FixedCParams, KeccakCircuitParams, PrecompileEcParams,
+    };
     use eth_types::{address, bytecode, geth_types::GethData, Word};
     use ethers_signers::{LocalWallet, Signer};
     use halo2_proofs::{
@@ -90,7 +92,13 @@ mod tests {
             max_bytecode: 512,
             max_evm_rows: 0,
             max_keccak_rows: 0,
+            keccak_params: KeccakCircuitParams::default(),
             max_vertical_circuit_rows: 0,
+            max_ec_ops: PrecompileEcParams {
+                ec_add: 0,
+                ec_mul: 0,
+                ec_pairing: 0,
+            },
         };
         let (_, mut circuits, mut instances, _) =
             SuperCircuit::build(block, circuits_params, Fr::from(0x100)).unwrap();
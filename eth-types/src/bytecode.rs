@@ -494,6 +494,8 @@ impl_other_opcodes! {
     (op_chainid, CHAINID),
     (op_selfbalance, SELFBALANCE),
     // (op_basefee, BASEFEE), ignored
+    (op_blobhash, BLOBHASH, index: I),
+    (op_blobbasefee, BLOBBASEFEE),
     (op_pop, POP),
     (op_mload, MLOAD, offset: O),
     (op_mstore, MSTORE, offset: O, value: V),
@@ -506,6 +508,7 @@ impl_other_opcodes! {
     (op_msize, MSIZE),
     (op_gas, GAS),
     // (op_jumpdest, JUMPDEST), manually implemented
+    (op_mcopy, MCOPY, dest_offset: D, offset: B, size: C),
     (op_dup1, DUP1),
     (op_dup2, DUP2),
     (op_dup3, DUP3),
@@ -35,6 +35,8 @@ pub enum Error {
     WordToMemAddr,
     /// Signature parsing error.
     Signature(libsecp256k1::Error),
+    /// Name of a hardfork which isn't supported.
+    UnsupportedHardfork(String),
 }
 
 impl From<libsecp256k1::Error> for Error {
@@ -4,12 +4,14 @@
 // use std::fmt;
 
 pub mod gas_utils;
+pub mod hardfork;
 pub mod memory;
 pub mod opcode_ids;
 pub mod stack;
 pub mod storage;
 pub mod transient_storage;
 
+pub use hardfork::{Hardfork, HardforkSchedule};
 pub use memory::{Memory, MemoryAddress};
 pub use opcode_ids::OpcodeId;
 pub use stack::{Stack, StackAddress};
@@ -18,50 +20,32 @@ pub use transient_storage::TransientStorage;
 
 /// According to EIP-3541, disallow new code starting with 0xEF to be deployed.
 pub const INVALID_INIT_CODE_FIRST_BYTE: u8 = 0xef;
-/// Once per word of the init code when creating a contract.
+/// Once per word of the init code when creating a contract, see EIP-3860.
 pub const INIT_CODE_WORD_GAS: u64 = 2;
+/// Maximum size of the code of a contract, see EIP-170.
+pub const MAX_CODE_SIZE: u64 = 24576;
 /// Quotient for max refund of gas used
 pub const MAX_REFUND_QUOTIENT_OF_GAS_USED: usize = 5;
 /// Gas stipend when CALL or CALLCODE is attached with value.
 pub const GAS_STIPEND_CALL_WITH_VALUE: u64 = 2300;
 
+/// Blob gas consumed by each blob of an EIP-4844 transaction.
+pub const GAS_PER_BLOB: u64 = 1 << 17;
+/// Maximum number of blobs of a block, which also bounds the number of blob
+/// versioned hashes of a transaction (MAX_BLOB_GAS_PER_BLOCK / GAS_PER_BLOB).
+pub const MAX_BLOB_HASHES_PER_TX: usize = 6;
+/// Minimum price of a unit of blob gas.
+pub const MIN_BLOB_GASPRICE: u64 = 1;
+/// Controls the maximum rate of change of the blob base fee.
+pub const BLOB_BASE_FEE_UPDATE_FRACTION: u64 = 3338477;
+/// Version byte of the blob versioned hashes derived from KZG commitments.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
 /// This constant ((2^32 - 1) * 32) is the highest number that can be used without overflowing the
 /// square operation of gas calculation.
 /// <https://github.com/ethereum/go-ethereum/blob/e6b6a8b738069ad0579f6798ee59fde93ed13b43/core/vm/gas_table.go#L38>
 pub const MAX_EXPANDED_MEMORY_ADDRESS: u64 = 0x1FFFFFFFE0;
 
-#[cfg(feature = "shanghai")]
-mod gas_create {
-    // For EIP-3860, there are 2 special gas cost constraints in geth
-    // [gasCreate2Eip3860](https://github.com/ethereum/go-ethereum/blob/eb83e7c54021573eaceb14236af3a7a8c64f6027/core/vm/gas_table.go#L321)
-    // (similar for CREATE).
-    // 1. size <= 49152 (MaxInitCodeSize)
-    // 2. gasCost = memoryGasCost + (2 + 6) * ((size + 31) / 32) should not overflow for Uint64.
-    // No need to constrain the second condition, since the maximum gas cost
-    // cannot overflow for Uint64 (36028809887100925 calculated by
-    // `memorySize = 0x1FFFFFFFE0` and `size = 49152`) if the first condition is
-    // satisfied.
-
-    /// Maximum init code size to permit in a creation transaction and create instructions.
-    pub const MAX_INIT_CODE_SIZE: u64 = 2 * super::MAX_CODE_SIZE;
-    /// Once per word of the init code when creating a contract.
-    pub const INIT_CODE_WORD_GAS: u64 = 2;
-    /// Gas per code word for CREATE.
-    pub const CREATE_GAS_PER_CODE_WORD: u64 = INIT_CODE_WORD_GAS;
-    /// Gas per code word for CREATE2.
-    pub const CREATE2_GAS_PER_CODE_WORD: u64 = INIT_CODE_WORD_GAS + super::GasCost::COPY_SHA3.0;
-}
-#[cfg(not(feature = "shanghai"))]
-mod gas_create {
-    /// Maximum init code size (0x1FFFFFFFE0) if not EIP-3860.
-    pub use super::MAX_EXPANDED_MEMORY_ADDRESS as MAX_INIT_CODE_SIZE;
-    /// Gas per code word for CREATE if not EIP-3860.
-    pub const CREATE_GAS_PER_CODE_WORD: u64 = 0;
-    /// Gas per code word for CREATE2 if not EIP-3860.
-    pub const CREATE2_GAS_PER_CODE_WORD: u64 = super::GasCost::COPY_SHA3;
-}
-pub use gas_create::*;
-
 /// Defines the gas consumption.
 pub struct GasCost;
 
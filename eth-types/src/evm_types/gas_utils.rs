@@ -1,7 +1,8 @@
 //! Utility functions to help calculate gas
 
-use super::GasCost;
+use super::{GasCost, BLOB_BASE_FEE_UPDATE_FRACTION, MIN_BLOB_GASPRICE};
 use crate::Word;
+use num_bigint::BigUint;
 
 /// Calculate memory expansion gas cost by current and next memory word size.
 pub fn memory_expansion_gas_cost(curr_memory_word_size: u64, next_memory_word_size: u64) -> u64 {
@@ -47,3 +48,73 @@ pub fn eip150_gas(gas_left: u64, gas_specified: Word) -> u64 {
 
     capped_gas
 }
+
+/// Calculate the blob base fee (EIP-4844) from the excess blob gas of a block.
+pub fn blob_base_fee(excess_blob_gas: u64) -> Word {
+    fake_exponential(
+        MIN_BLOB_GASPRICE,
+        excess_blob_gas,
+        BLOB_BASE_FEE_UPDATE_FRACTION,
+    )
+}
+
+/// Approximate `factor * e ** (numerator / denominator)` using Taylor
+/// expansion, as specified in EIP-4844.
+fn fake_exponential(factor: u64, numerator: u64, denominator: u64) -> Word {
+    let numerator = BigUint::from(numerator);
+    let denominator = BigUint::from(denominator);
+
+    let mut i = 1u64;
+    let mut output = BigUint::default();
+    let mut numerator_accum = BigUint::from(factor) * &denominator;
+    while numerator_accum != BigUint::default() {
+        output += &numerator_accum;
+        numerator_accum = numerator_accum * &numerator / (&denominator * i);
+        i += 1;
+    }
+
+    Word::from_little_endian(&(output / denominator).to_bytes_le())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fake_exponential() {
+        for (factor, numerator, denominator, expected) in [
+            (1, 0, 1, 1u64),
+            (38493, 0, 1000, 38493),
+            (0, 1234, 2345, 0),
+            (1, 2, 1, 6),
+            (1, 4, 2, 6),
+            (1, 3, 1, 16),
+            (1, 6, 2, 18),
+            (1, 4, 1, 49),
+            (1, 8, 2, 50),
+            (10, 8, 2, 542),
+            (11, 8, 2, 596),
+            (1, 5, 1, 136),
+            (1, 5, 2, 11),
+            (2, 5, 2, 23),
+            (1, 50000000, 2225652, 5709098764),
+        ] {
+            assert_eq!(
+                fake_exponential(factor, numerator, denominator),
+                Word::from(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_blob_base_fee() {
+        for (excess_blob_gas, expected) in [
+            (0, 1u64),
+            (2314057, 1),
+            (2314058, 2),
+            (10 * 1024 * 1024, 23),
+        ] {
+            assert_eq!(blob_base_fee(excess_blob_gas), Word::from(expected));
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Hardforks changing the rules of the EVM, and their activation on a chain.
+
+use super::{GasCost, OpcodeId, INIT_CODE_WORD_GAS, MAX_CODE_SIZE, MAX_EXPANDED_MEMORY_ADDRESS};
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Hardforks supported by the circuits, in order of activation.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum Hardfork {
+    /// London, with the EIP-1559 fee market and the EIP-3529 refund reduction
+    London,
+    /// Shanghai, with PUSH0 (EIP-3855) and the limit and metering of init
+    /// code (EIP-3860)
+    Shanghai,
+    /// Cancun, with transient storage (EIP-1153), blob transactions
+    /// (EIP-4844), MCOPY (EIP-5656), the SELFDESTRUCT restriction (EIP-6780)
+    /// and BLOBBASEFEE (EIP-7516)
+    #[default]
+    Cancun,
+}
+
+impl Hardfork {
+    /// Returns true if init code is limited and metered, see EIP-3860.
+    pub fn is_eip3860_enabled(&self) -> bool {
+        *self >= Hardfork::Shanghai
+    }
+
+    /// Maximum init code size of a creation transaction or a CREATE, CREATE2.
+    /// Without EIP-3860, it's only bounded by the memory expansion.
+    pub fn max_init_code_size(&self) -> u64 {
+        if self.is_eip3860_enabled() {
+            2 * MAX_CODE_SIZE
+        } else {
+            MAX_EXPANDED_MEMORY_ADDRESS
+        }
+    }
+
+    /// Gas per word of the init code of a creation transaction or a CREATE,
+    /// CREATE2.
+    pub fn init_code_word_gas(&self) -> u64 {
+        if self.is_eip3860_enabled() {
+            INIT_CODE_WORD_GAS
+        } else {
+            0
+        }
+    }
+
+    /// Gas per word of the init code of a CREATE.
+    pub fn create_gas_per_code_word(&self) -> u64 {
+        self.init_code_word_gas()
+    }
+
+    /// Gas per word of the init code of a CREATE2, which also hashes it.
+    pub fn create2_gas_per_code_word(&self) -> u64 {
+        self.init_code_word_gas() + GasCost::COPY_SHA3
+    }
+
+    /// Returns true if SELFDESTRUCT only destroys the accounts created in the
+    /// same transaction, see EIP-6780. Otherwise it always destroys the
+    /// account.
+    pub fn is_eip6780_enabled(&self) -> bool {
+        *self >= Hardfork::Cancun
+    }
+
+    /// Returns true if `opcode` is available, otherwise it's executed as an
+    /// invalid opcode.
+    pub fn is_opcode_enabled(&self, opcode: OpcodeId) -> bool {
+        match opcode {
+            OpcodeId::PUSH0 => *self >= Hardfork::Shanghai,
+            OpcodeId::TLOAD
+            | OpcodeId::TSTORE
+            | OpcodeId::MCOPY
+            | OpcodeId::BLOBHASH
+            | OpcodeId::BLOBBASEFEE => *self >= Hardfork::Cancun,
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for Hardfork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl FromStr for Hardfork {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "london" => Ok(Hardfork::London),
+            "shanghai" => Ok(Hardfork::Shanghai),
+            "cancun" => Ok(Hardfork::Cancun),
+            _ => Err(Error::UnsupportedHardfork(s.to_string())),
+        }
+    }
+}
+
+/// Activation of the hardforks on a chain: London by block number, and the
+/// later ones by block timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardforkSchedule {
+    /// Number of the first London block
+    pub london_block: u64,
+    /// Timestamp of the first Shanghai block
+    pub shanghai_time: Option<u64>,
+    /// Timestamp of the first Cancun block
+    pub cancun_time: Option<u64>,
+}
+
+impl HardforkSchedule {
+    /// Ethereum mainnet
+    pub const MAINNET: Self = Self {
+        london_block: 12_965_000,
+        shanghai_time: Some(1_681_338_455),
+        cancun_time: Some(1_710_338_135),
+    };
+    /// Sepolia testnet
+    pub const SEPOLIA: Self = Self {
+        london_block: 0,
+        shanghai_time: Some(1_677_557_088),
+        cancun_time: Some(1_706_655_072),
+    };
+    /// Holesky testnet
+    pub const HOLESKY: Self = Self {
+        london_block: 0,
+        shanghai_time: Some(1_696_000_704),
+        cancun_time: Some(1_707_305_664),
+    };
+
+    /// Returns the schedule of a known chain.
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        match chain_id {
+            1 => Some(Self::MAINNET),
+            11_155_111 => Some(Self::SEPOLIA),
+            17_000 => Some(Self::HOLESKY),
+            _ => None,
+        }
+    }
+
+    /// Returns the hardfork of the block `number` with `timestamp`, or `None`
+    /// if it's before London.
+    pub fn hardfork(&self, number: u64, timestamp: u64) -> Option<Hardfork> {
+        let is_active = |time: Option<u64>| time.is_some_and(|time| timestamp >= time);
+        if number < self.london_block {
+            None
+        } else if is_active(self.cancun_time) {
+            Some(Hardfork::Cancun)
+        } else if is_active(self.shanghai_time) {
+            Some(Hardfork::Shanghai)
+        } else {
+            Some(Hardfork::London)
+        }
+    }
+}
+
+#[cfg(test)]
+mod hardfork_tests {
+    use super::*;
+
+    #[test]
+    fn mainnet_hardforks() {
+        let schedule = HardforkSchedule::MAINNET;
+        assert_eq!(schedule.hardfork(12_964_999, 1_628_166_812), None);
+        assert_eq!(
+            schedule.hardfork(12_965_000, 1_628_166_822),
+            Some(Hardfork::London)
+        );
+        assert_eq!(
+            schedule.hardfork(17_034_870, 1_681_338_455),
+            Some(Hardfork::Shanghai)
+        );
+        assert_eq!(
+            schedule.hardfork(19_426_587, 1_710_338_135),
+            Some(Hardfork::Cancun)
+        );
+    }
+
+    #[test]
+    fn hardfork_rules() {
+        assert!(!Hardfork::London.is_opcode_enabled(OpcodeId::PUSH0));
+        assert!(Hardfork::Shanghai.is_opcode_enabled(OpcodeId::PUSH0));
+        assert!(!Hardfork::Shanghai.is_opcode_enabled(OpcodeId::MCOPY));
+        assert!(Hardfork::Cancun.is_opcode_enabled(OpcodeId::TSTORE));
+        assert!(!Hardfork::Shanghai.is_eip6780_enabled());
+        assert!(Hardfork::Cancun.is_eip6780_enabled());
+        assert_eq!(Hardfork::London.max_init_code_size(), 0x1FFFFFFFE0);
+        assert_eq!(Hardfork::Shanghai.max_init_code_size(), 49152);
+        assert_eq!(Hardfork::London.create2_gas_per_code_word(), 6);
+        assert_eq!(Hardfork::Cancun.create2_gas_per_code_word(), 8);
+        assert_eq!("Shanghai".parse::<Hardfork>().unwrap(), Hardfork::Shanghai);
+    }
+}
@@ -1,7 +1,7 @@
 //! Types needed for generating Ethereum traces
 
 use crate::{
-    evm_types::{self, GasCost},
+    evm_types::{self, GasCost, Hardfork},
     keccak256,
     sign_types::{biguint_to_32bytes_le, ct_option_ok_or, recover_pk, SignData, SECP256K1_Q},
     AccessList, Address, Block, Bytecode, Bytes, Error, GethExecTrace, Hash, ToBigEndian,
@@ -10,9 +10,10 @@ use crate::{
 use ethers_core::{
     types::{
         transaction::{eip2718::TypedTransaction, response},
-        Eip1559TransactionRequest, Eip2930TransactionRequest, NameOrAddress, TransactionRequest,
+        Eip1559TransactionRequest, Eip2930TransactionRequest, NameOrAddress, OtherFields,
+        TransactionRequest,
     },
-    utils::get_contract_address,
+    utils::{get_contract_address, rlp::RlpStream},
 };
 use ethers_signers::{LocalWallet, Signer};
 use halo2_proofs::halo2curves::{group::ff::PrimeField, secp256k1};
@@ -35,6 +36,8 @@ pub enum TxType {
     Eip1559,
     /// EIP 2930 tx
     Eip2930,
+    /// EIP 4844 tx
+    Eip4844,
 }
 
 impl From<TxType> for usize {
@@ -70,11 +73,28 @@ impl TxType {
         matches!(*self, TxType::Eip2930)
     }
 
+    /// If this type is Eip4844 or not
+    pub fn is_eip4844(&self) -> bool {
+        matches!(*self, TxType::Eip4844)
+    }
+
+    /// Return the EIP-2718 `TransactionType`, which is 0 for legacy
+    /// transactions.
+    pub fn eip2718_type(&self) -> U64 {
+        match *self {
+            TxType::Eip155 | TxType::PreEip155 => U64::zero(),
+            TxType::Eip2930 => U64::from(1),
+            TxType::Eip1559 => U64::from(2),
+            TxType::Eip4844 => U64::from(3),
+        }
+    }
+
     /// Get the type of transaction
     pub fn get_tx_type(tx: &crate::Transaction) -> Self {
         match tx.transaction_type {
             Some(x) if x == U64::from(1) => Self::Eip2930,
             Some(x) if x == U64::from(2) => Self::Eip1559,
+            Some(x) if x == U64::from(3) => Self::Eip4844,
             _ => match tx.v.as_u64() {
                 0 | 1 | 27 | 28 => Self::PreEip155,
                 _ => Self::Eip155,
@@ -98,6 +118,10 @@ impl TxType {
                 assert!(v <= 1);
                 v
             }
+            TxType::Eip4844 => {
+                assert!(v <= 1);
+                v
+            }
         };
 
         recovery_id as u8
@@ -130,7 +154,85 @@ pub fn get_rlp_unsigned(tx: &crate::Transaction) -> Vec<u8> {
             let typed_tx: TypedTransaction = tx.into();
             typed_tx.rlp().to_vec()
         }
+        // `ethers` doesn't support blob transactions, so they are encoded by hand.
+        TxType::Eip4844 => Transaction::from(tx).rlp_unsigned_bytes,
+    }
+}
+
+/// Key of the blob versioned hashes in the `other` fields of a transaction.
+const BLOB_VERSIONED_HASHES: &str = "blobVersionedHashes";
+/// Key of the max fee per blob gas in the `other` fields of a transaction.
+const MAX_FEE_PER_BLOB_GAS: &str = "maxFeePerBlobGas";
+/// Key of the excess blob gas in the `other` fields of a block.
+const EXCESS_BLOB_GAS: &str = "excessBlobGas";
+/// Key of the blob gas used in the `other` fields of a block.
+const BLOB_GAS_USED: &str = "blobGasUsed";
+
+/// Get the blob versioned hashes of an EIP-4844 transaction, which `ethers`
+/// keeps in the `other` fields.  Empty for any other transaction type.
+pub fn get_blob_versioned_hashes(tx: &crate::Transaction) -> Vec<H256> {
+    tx.other
+        .get_deserialized(BLOB_VERSIONED_HASHES)
+        .and_then(Result::ok)
+        .unwrap_or_default()
+}
+
+/// Get the max fee per blob gas of an EIP-4844 transaction, which `ethers`
+/// keeps in the `other` fields.
+pub fn get_max_fee_per_blob_gas(tx: &crate::Transaction) -> Option<Word> {
+    tx.other
+        .get_deserialized(MAX_FEE_PER_BLOB_GAS)
+        .and_then(Result::ok)
+}
+
+/// Build the `other` fields of a transaction holding its EIP-4844 fields.
+pub fn blob_tx_other_fields(
+    max_fee_per_blob_gas: Option<Word>,
+    blob_versioned_hashes: &[H256],
+) -> OtherFields {
+    let mut fields = serde_json::Map::new();
+    if let Some(max_fee_per_blob_gas) = max_fee_per_blob_gas {
+        fields.insert(
+            MAX_FEE_PER_BLOB_GAS.to_string(),
+            serde_json::to_value(max_fee_per_blob_gas).unwrap(),
+        );
     }
+    if !blob_versioned_hashes.is_empty() {
+        fields.insert(
+            BLOB_VERSIONED_HASHES.to_string(),
+            serde_json::to_value(blob_versioned_hashes).unwrap(),
+        );
+    }
+    serde_json::from_value(fields.into()).unwrap()
+}
+
+/// Get the excess blob gas of a block, which `ethers` keeps in the `other`
+/// fields.  Zero for blocks before Cancun.
+pub fn get_excess_blob_gas<TX>(block: &Block<TX>) -> U64 {
+    block
+        .other
+        .get_deserialized(EXCESS_BLOB_GAS)
+        .and_then(Result::ok)
+        .unwrap_or_default()
+}
+
+/// Get the blob gas used by a block, which `ethers` keeps in the `other`
+/// fields.  Zero for blocks before Cancun.
+pub fn get_blob_gas_used<TX>(block: &Block<TX>) -> U64 {
+    block
+        .other
+        .get_deserialized(BLOB_GAS_USED)
+        .and_then(Result::ok)
+        .unwrap_or_default()
+}
+
+/// Build the `other` fields of a block holding its EIP-4844 header fields.
+pub fn blob_block_other_fields(excess_blob_gas: U64, blob_gas_used: U64) -> OtherFields {
+    serde_json::from_value(serde_json::json!({
+        EXCESS_BLOB_GAS: excess_blob_gas,
+        BLOB_GAS_USED: blob_gas_used,
+    }))
+    .unwrap()
 }
 
 /// Definition of all of the data related to an account.
@@ -211,6 +313,10 @@ pub struct BlockConstants {
     pub gas_limit: Word,
     /// base fee
     pub base_fee: Word,
+    /// excess blob gas
+    pub excess_blob_gas: U64,
+    /// blob gas used
+    pub blob_gas_used: U64,
 }
 
 impl<TX> TryFrom<&Block<TX>> for BlockConstants {
@@ -228,12 +334,15 @@ impl<TX> TryFrom<&Block<TX>> for BlockConstants {
             },
             gas_limit: block.gas_limit,
             base_fee: block.base_fee_per_gas.ok_or(Error::IncompleteBlock)?,
+            excess_blob_gas: get_excess_blob_gas(block),
+            blob_gas_used: get_blob_gas_used(block),
         })
     }
 }
 
 impl BlockConstants {
     /// Generates a new `BlockConstants` instance from it's fields.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         coinbase: Address,
         timestamp: Word,
@@ -241,6 +350,8 @@ impl BlockConstants {
         difficulty: Word,
         gas_limit: Word,
         base_fee: Word,
+        excess_blob_gas: U64,
+        blob_gas_used: U64,
     ) -> BlockConstants {
         BlockConstants {
             coinbase,
@@ -249,6 +360,8 @@ impl BlockConstants {
             difficulty,
             gas_limit,
             base_fee,
+            excess_blob_gas,
+            blob_gas_used,
         }
     }
 }
@@ -297,6 +410,10 @@ pub struct Transaction {
     pub call_data: Bytes,
     /// Access list
     pub access_list: Option<AccessList>,
+    /// Max fee per blob gas (EIP-4844)
+    pub max_fee_per_blob_gas: Option<Word>,
+    /// Blob versioned hashes (EIP-4844)
+    pub blob_versioned_hashes: Vec<H256>,
 
     /// "v" value of the transaction signature
     pub v: u64,
@@ -330,6 +447,8 @@ impl From<&Transaction> for crate::Transaction {
             v: tx.v.into(),
             r: tx.r,
             s: tx.s,
+            transaction_type: Some(tx.tx_type.eip2718_type()),
+            other: blob_tx_other_fields(tx.max_fee_per_blob_gas, &tx.blob_versioned_hashes),
             ..Default::default()
         }
     }
@@ -337,8 +456,9 @@ impl From<&Transaction> for crate::Transaction {
 
 impl From<&crate::Transaction> for Transaction {
     fn from(tx: &crate::Transaction) -> Transaction {
-        Transaction {
-            tx_type: TxType::get_tx_type(tx),
+        let tx_type = TxType::get_tx_type(tx);
+        let mut geth_tx = Transaction {
+            tx_type,
             from: tx.from,
             to: tx.to,
             nonce: tx.nonce.as_u64().into(),
@@ -349,13 +469,24 @@ impl From<&crate::Transaction> for Transaction {
             gas_fee_cap: tx.max_fee_per_gas,
             call_data: tx.input.clone(),
             access_list: tx.access_list.clone(),
+            max_fee_per_blob_gas: get_max_fee_per_blob_gas(tx),
+            blob_versioned_hashes: get_blob_versioned_hashes(tx),
             v: tx.v.as_u64(),
             r: tx.r,
             s: tx.s,
-            rlp_bytes: tx.rlp().to_vec(),
-            rlp_unsigned_bytes: get_rlp_unsigned(tx),
+            rlp_bytes: vec![],
+            rlp_unsigned_bytes: vec![],
             hash: tx.hash,
+        };
+        if tx_type.is_eip4844() {
+            let chain_id = tx.chain_id.unwrap_or_default().as_u64();
+            geth_tx.rlp_bytes = geth_tx.rlp_eip4844(chain_id, true);
+            geth_tx.rlp_unsigned_bytes = geth_tx.rlp_eip4844(chain_id, false);
+        } else {
+            geth_tx.rlp_bytes = tx.rlp().to_vec();
+            geth_tx.rlp_unsigned_bytes = get_rlp_unsigned(tx);
         }
+        geth_tx
     }
 }
 
@@ -406,14 +537,22 @@ impl Transaction {
             secp256k1::Fq::from_repr(sig_s_le),
             Error::Signature(libsecp256k1::Error::InvalidSignature),
         )?;
-        // msg = rlp([nonce, gasPrice, gas, to, value, data, sig_v, r, s])
-        let req: TransactionRequest = self.into();
-        let msg = req.chain_id(chain_id).rlp();
+        let (msg, v) = if self.tx_type.is_eip4844() {
+            // msg = 0x03 || rlp([chain_id, nonce, ..., blob_versioned_hashes]), v = y_parity
+            let v = Some(self.v)
+                .filter(|v| *v <= 1)
+                .ok_or(Error::Signature(libsecp256k1::Error::InvalidSignature))?;
+            (self.rlp_eip4844(chain_id, false).into(), v as u8)
+        } else {
+            // msg = rlp([nonce, gasPrice, gas, to, value, data, sig_v, r, s])
+            let req: TransactionRequest = self.into();
+            let v = self
+                .v
+                .checked_sub(35 + chain_id * 2)
+                .ok_or(Error::Signature(libsecp256k1::Error::InvalidSignature))?;
+            (req.chain_id(chain_id).rlp(), v as u8)
+        };
         let msg_hash: [u8; 32] = keccak256(&msg);
-        let v = self
-            .v
-            .checked_sub(35 + chain_id * 2)
-            .ok_or(Error::Signature(libsecp256k1::Error::InvalidSignature))? as u8;
         let pk = recover_pk(v, &self.r, &self.s, &msg_hash)?;
         // msg_hash = msg_hash % q
         let msg_hash = BigUint::from_bytes_be(msg_hash.as_slice());
@@ -431,6 +570,43 @@ impl Transaction {
         })
     }
 
+    /// RLP encoding of an EIP-4844 transaction, with the signature when
+    /// `signed` is set:
+    /// 0x03 || rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas,
+    /// gas_limit, to, value, data, access_list, max_fee_per_blob_gas,
+    /// blob_versioned_hashes, y_parity, r, s])
+    pub fn rlp_eip4844(&self, chain_id: u64, signed: bool) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        stream.begin_list(if signed { 14 } else { 11 });
+        stream.append(&chain_id);
+        stream.append(&self.nonce);
+        stream.append(&self.gas_tip_cap.unwrap_or_default());
+        stream.append(&self.gas_fee_cap.unwrap_or_default());
+        stream.append(&self.gas_limit);
+        // A blob transaction can't create a contract.
+        stream.append(&self.to_or_zero());
+        stream.append(&self.value);
+        stream.append(&self.call_data);
+        stream.append(&self.access_list.clone().unwrap_or_default());
+        stream.append(&self.max_fee_per_blob_gas.unwrap_or_default());
+        stream.append_list::<H256, _>(&self.blob_versioned_hashes);
+        if signed {
+            stream.append(&self.v);
+            stream.append(&self.r);
+            stream.append(&self.s);
+        }
+        [
+            vec![TxType::Eip4844.eip2718_type().as_u64() as u8],
+            stream.out().to_vec(),
+        ]
+        .concat()
+    }
+
+    /// Blob gas consumed by the blobs of the transaction.
+    pub fn blob_gas(&self) -> u64 {
+        evm_types::GAS_PER_BLOB * self.blob_versioned_hashes.len() as u64
+    }
+
     /// Compute call data gas cost from call data
     pub fn call_data_gas_cost(&self) -> u64 {
         self.call_data
@@ -438,12 +614,12 @@ impl Transaction {
             .fold(0, |acc, byte| acc + if *byte == 0 { 4 } else { 16 })
     }
 
-    /// Compute the intrinsic gas cost
-    pub fn intrinsic_gas_cost(&self) -> u64 {
+    /// Compute the intrinsic gas cost under the rules of `hardfork`
+    pub fn intrinsic_gas_cost(&self, hardfork: Hardfork) -> u64 {
         let is_create = self.is_create() as u64;
         // Calculate gas cost of init code for EIP-3860.
         let init_code_gas_cost =
-            ((self.call_data.len() as u64 + 31) / 32) * evm_types::INIT_CODE_WORD_GAS;
+            ((self.call_data.len() as u64 + 31) / 32) * hardfork.init_code_word_gas();
         is_create * (GasCost::CREATION_TX + init_code_gas_cost)
             + (1 - is_create) * GasCost::TX
             + self.call_data_gas_cost()
@@ -484,10 +660,11 @@ impl Transaction {
             s: self.s,
             v: U64::from(self.v),
             block_number: Some(block_number),
-            transaction_type: Some(U64::from(self.tx_type as u64)),
+            transaction_type: Some(self.tx_type.eip2718_type()),
             max_priority_fee_per_gas: self.gas_tip_cap,
             max_fee_per_gas: self.gas_fee_cap,
             chain_id: Some(chain_id),
+            other: blob_tx_other_fields(self.max_fee_per_blob_gas, &self.blob_versioned_hashes),
             ..response::Transaction::default()
         }
     }
@@ -520,10 +697,18 @@ impl GethData {
             let wallet = wallets.get(&tx.from).unwrap();
             assert_eq!(Word::from(wallet.chain_id()), self.chain_id);
             let geth_tx: Transaction = (&*tx).into();
-            let req: TransactionRequest = (&geth_tx).into();
-            let sig = wallet
-                .sign_transaction_sync(&req.chain_id(self.chain_id.as_u64()).into())
-                .unwrap();
+            let sig = if geth_tx.tx_type.is_eip4844() {
+                let msg = geth_tx.rlp_eip4844(self.chain_id.as_u64(), false);
+                let mut sig = wallet.sign_hash(H256(keccak256(msg))).unwrap();
+                // `sign_hash` returns a non EIP-155 `v`, blob transactions use the y parity.
+                sig.v -= 27;
+                sig
+            } else {
+                let req: TransactionRequest = (&geth_tx).into();
+                wallet
+                    .sign_transaction_sync(&req.chain_id(self.chain_id.as_u64()).into())
+                    .unwrap()
+            };
             tx.v = U64::from(sig.v);
             tx.r = sig.r;
             tx.s = sig.s;
@@ -1,6 +1,7 @@
 //! This module generates traces by connecting to an external tracer
 
 use eth_types::{
+    evm_types::Hardfork,
     geth_types::{Account, BlockConstants, Transaction, Withdrawal},
     Address, Error, GethExecTrace, Word,
 };
@@ -12,6 +13,8 @@ use std::collections::BTreeMap;
 pub struct TraceConfig {
     /// chain id
     pub chain_id: Word,
+    /// hardfork whose rules apply to the block
+    pub hardfork: Hardfork,
     /// history hashes contains most recent 256 block hashes in history, where
     /// the latest one is at history_hashes[history_hashes.len() - 1].
     pub history_hashes: Vec<Word>,
@@ -7,6 +7,7 @@ import (
 
 	"github.com/ethereum/go-ethereum/common"
 	"github.com/ethereum/go-ethereum/common/hexutil"
+	"github.com/ethereum/go-ethereum/consensus/misc/eip4844"
 	"github.com/ethereum/go-ethereum/core"
 	"github.com/ethereum/go-ethereum/core/rawdb"
 	"github.com/ethereum/go-ethereum/core/state"
@@ -86,12 +87,14 @@ func FormatLogs(logs []logger.StructLog) []StructLogRes {
 }
 
 type Block struct {
-	Coinbase   common.Address `json:"coinbase"`
-	Timestamp  *hexutil.Big   `json:"timestamp"`
-	Number     *hexutil.Big   `json:"number"`
-	Difficulty *hexutil.Big   `json:"difficulty"`
-	GasLimit   *hexutil.Big   `json:"gas_limit"`
-	BaseFee    *hexutil.Big   `json:"base_fee"`
+	Coinbase      common.Address  `json:"coinbase"`
+	Timestamp     *hexutil.Big    `json:"timestamp"`
+	Number        *hexutil.Big    `json:"number"`
+	Difficulty    *hexutil.Big    `json:"difficulty"`
+	GasLimit      *hexutil.Big    `json:"gas_limit"`
+	BaseFee       *hexutil.Big    `json:"base_fee"`
+	ExcessBlobGas *hexutil.Uint64 `json:"excess_blob_gas"`
+	BlobGasUsed   *hexutil.Uint64 `json:"blob_gas_used"`
 }
 
 type Account struct {
@@ -102,24 +105,28 @@ type Account struct {
 }
 
 type Transaction struct {
-	From       common.Address   `json:"from"`
-	To         *common.Address  `json:"to"`
-	Nonce      hexutil.Uint64   `json:"nonce"`
-	Value      *hexutil.Big     `json:"value"`
-	GasLimit   hexutil.Uint64   `json:"gas_limit"`
-	GasPrice   *hexutil.Big     `json:"gas_price"`
-	GasFeeCap  *hexutil.Big     `json:"gas_fee_cap"`
-	GasTipCap  *hexutil.Big     `json:"gas_tip_cap"`
-	CallData   hexutil.Bytes    `json:"call_data"`
-	AccessList types.AccessList `json:"access_list"`
-	Type       string           `json:"tx_type"`
-	V          int64            `json:"v"`
-	R          *hexutil.Big     `json:"r"`
-	S          *hexutil.Big     `json:"s"`
+	From          common.Address   `json:"from"`
+	To            *common.Address  `json:"to"`
+	Nonce         hexutil.Uint64   `json:"nonce"`
+	Value         *hexutil.Big     `json:"value"`
+	GasLimit      hexutil.Uint64   `json:"gas_limit"`
+	GasPrice      *hexutil.Big     `json:"gas_price"`
+	GasFeeCap     *hexutil.Big     `json:"gas_fee_cap"`
+	GasTipCap     *hexutil.Big     `json:"gas_tip_cap"`
+	CallData      hexutil.Bytes    `json:"call_data"`
+	AccessList    types.AccessList `json:"access_list"`
+	BlobGasFeeCap *hexutil.Big     `json:"max_fee_per_blob_gas"`
+	BlobHashes    []common.Hash    `json:"blob_versioned_hashes"`
+	Type          string           `json:"tx_type"`
+	V             int64            `json:"v"`
+	R             *hexutil.Big     `json:"r"`
+	S             *hexutil.Big     `json:"s"`
 }
 
 type TraceConfig struct {
 	ChainID *hexutil.Big `json:"chain_id"`
+	// Hardfork is one of "London", "Shanghai" or "Cancun".
+	Hardfork string `json:"hardfork"`
 	// HistoryHashes contains most recent 256 block hashes in history,
 	// where the latest one is at HistoryHashes[len(HistoryHashes)-1].
 	HistoryHashes []*hexutil.Big             `json:"history_hashes"`
@@ -138,7 +145,27 @@ func toBigInt(value *hexutil.Big) *big.Int {
 	return big.NewInt(0)
 }
 
+// hardforkTimes returns the activation time of Shanghai and Cancun, which are
+// nil if the hardfork is not active.
+func hardforkTimes(hardfork string) (*uint64, *uint64, error) {
+	switch hardfork {
+	case "London":
+		return nil, nil, nil
+	case "Shanghai":
+		return newUint64(0), nil, nil
+	case "Cancun":
+		return newUint64(0), newUint64(0), nil
+	default:
+		return nil, nil, fmt.Errorf("unsupported hardfork: %q", hardfork)
+	}
+}
+
 func Trace(config TraceConfig) ([]*ExecutionResult, error) {
+	shanghaiTime, cancunTime, err := hardforkTimes(config.Hardfork)
+	if err != nil {
+		return nil, err
+	}
+
 	chainConfig := params.ChainConfig{
 		ChainID:        toBigInt(config.ChainID),
 		HomesteadBlock: big.NewInt(0),
@@ -155,8 +182,8 @@ func Trace(config TraceConfig) ([]*ExecutionResult, error) {
 		MuirGlacierBlock:              big.NewInt(0),
 		BerlinBlock:                   big.NewInt(0),
 		LondonBlock:                   big.NewInt(0),
-		ShanghaiTime:                  newUint64(0),
-		CancunTime:                    newUint64(0),
+		ShanghaiTime:                  shanghaiTime,
+		CancunTime:                    cancunTime,
 		TerminalTotalDifficulty:       big.NewInt(0),
 		TerminalTotalDifficultyPassed: true,
 	}
@@ -193,6 +220,11 @@ func Trace(config TraceConfig) ([]*ExecutionResult, error) {
 			AccessList:        txAccessList,
 			SkipAccountChecks: false,
 		}
+		if tx.Type == "Eip4844" {
+			// A non-nil BlobHashes marks the message as a blob transaction.
+			messages[i].BlobHashes = append([]common.Hash{}, tx.BlobHashes...)
+			messages[i].BlobGasFeeCap = toBigInt(tx.BlobGasFeeCap)
+		}
 
 		txsGasLimit += uint64(tx.GasLimit)
 	}
@@ -204,6 +236,11 @@ func Trace(config TraceConfig) ([]*ExecutionResult, error) {
 	// Difficulty is one of MixHash or Difficulty.
 	randao := common.BigToHash(toBigInt(config.Block.Difficulty))
 
+	var excessBlobGas uint64
+	if config.Block.ExcessBlobGas != nil {
+		excessBlobGas = uint64(*config.Block.ExcessBlobGas)
+	}
+
 	blockCtx := vm.BlockContext{
 		CanTransfer: core.CanTransfer,
 		Transfer:    core.Transfer,
@@ -221,6 +258,7 @@ func Trace(config TraceConfig) ([]*ExecutionResult, error) {
 		Difficulty:  toBigInt(config.Block.Difficulty),
 		Random:      &randao,
 		BaseFee:     toBigInt(config.Block.BaseFee),
+		BlobBaseFee: eip4844.CalcBlobFee(excessBlobGas),
 		GasLimit:    blockGasLimit,
 	}
 
@@ -239,7 +277,6 @@ func Trace(config TraceConfig) ([]*ExecutionResult, error) {
 	stateDB.Finalise(true)
 
 	var (
-		err     error
 		usedGas uint64
 		raw     json.RawMessage
 		tx      types.Transaction
@@ -282,7 +319,7 @@ func Trace(config TraceConfig) ([]*ExecutionResult, error) {
 func ToTxData(message core.Message, ChainID *big.Int) types.TxData {
 	var data types.TxData
 	switch {
-	case message.BlobHashes != nil:
+	case message.BlobHashes != nil && message.To != nil:
 		al := types.AccessList{}
 		if message.AccessList != nil {
 			al = message.AccessList
@@ -4,10 +4,7 @@ go 1.21
 
 require github.com/ethereum/go-ethereum v1.13.5-0.20240402092557-0bd03dbc5597
 
-require (
-	github.com/holiman/uint256 v1.2.4
-	golang.org/x/crypto v0.21.0
-)
+require github.com/holiman/uint256 v1.2.4
 
 require (
 	github.com/DataDog/zstd v1.5.2 // indirect
@@ -66,6 +63,7 @@ require (
 	github.com/tklauser/go-sysconf v0.3.12 // indirect
 	github.com/tklauser/numcpus v0.6.1 // indirect
 	github.com/tyler-smith/go-bip39 v1.1.0 // indirect
+	golang.org/x/crypto v0.21.0 // indirect
 	golang.org/x/exp v0.0.0-20231110203233-9a3e6036ecaa // indirect
 	golang.org/x/mod v0.14.0 // indirect
 	golang.org/x/sync v0.5.0 // indirect
@@ -8,7 +8,6 @@ import (
 	"encoding/json"
 	"fmt"
 	"main/gethutil"
-	"main/gethutil/mpt/witness"
 	"unsafe"
 )
 
@@ -44,30 +43,6 @@ type Config struct {
 	Values   []string `json:"Values"`
 }
 
-type GetWitnessRequest struct {
-	BlockNum int    `json:"BlockNum"`
-	NodeUrl  string `json:"NodeUrl"`
-	Mods     []witness.TrieModification
-}
-
-//export GetMptWitness
-func GetMptWitness(proofConf *C.char) *C.char {
-	var config GetWitnessRequest
-
-	err := json.Unmarshal([]byte(C.GoString(proofConf)), &config)
-	if err != nil {
-		panic(err)
-	}
-
-	proof := witness.GetWitness(config.NodeUrl, config.BlockNum, config.Mods)
-	b, err := json.Marshal(proof)
-	if err != nil {
-		fmt.Println(err)
-	}
-
-	return C.CString(string(b))
-}
-
 //export FreeString
 func FreeString(str *C.char) {
 	C.free(unsafe.Pointer(str))
@@ -1,6 +1,8 @@
 use crate::{get_client, GenDataOutput};
 use bus_mapping::{
-    circuit_input_builder::{BuilderClient, CircuitInputBuilder, FixedCParams},
+    circuit_input_builder::{
+        BuilderClient, CircuitInputBuilder, FixedCParams, KeccakCircuitParams, PrecompileEcParams,
+    },
     mock::BlockData,
 };
 use eth_types::geth_types::GethData;
@@ -72,8 +74,19 @@ const MAX_EVM_ROWS: usize = 10000;
 const MAX_EXP_STEPS: usize = 1000;
 
 const MAX_KECCAK_ROWS: usize = 38000;
+/// KECCAK_PARAMS
+const KECCAK_PARAMS: KeccakCircuitParams = KeccakCircuitParams {
+    degree: 8,
+    rows_per_round: 12,
+};
 /// MAX_VERTICAL_CIRCUIT_ROWS
 const MAX_VERTICAL_CIRCUIT_ROWS: usize = 0;
+/// MAX_EC_OPS
+const MAX_EC_OPS: PrecompileEcParams = PrecompileEcParams {
+    ec_add: 0,
+    ec_mul: 0,
+    ec_pairing: 0,
+};
 
 const CIRCUITS_PARAMS: FixedCParams = FixedCParams {
     total_chunks: TOTAL_CHUNKS,
@@ -86,7 +99,9 @@ const CIRCUITS_PARAMS: FixedCParams = FixedCParams {
     max_evm_rows: MAX_EVM_ROWS,
     max_exp_steps: MAX_EXP_STEPS,
     max_keccak_rows: MAX_KECCAK_ROWS,
+    keccak_params: KECCAK_PARAMS,
     max_vertical_circuit_rows: MAX_VERTICAL_CIRCUIT_ROWS,
+    max_ec_ops: MAX_EC_OPS,
 };
 
 const EVM_CIRCUIT_DEGREE: u32 = 18;
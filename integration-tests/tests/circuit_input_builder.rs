@@ -1,7 +1,8 @@
 #![cfg(feature = "circuit_input_builder")]
 
 use bus_mapping::circuit_input_builder::{
-    build_state_code_db, get_state_accesses, BuilderClient, FixedCParams,
+    build_state_code_db, get_state_accesses, BuilderClient, FixedCParams, KeccakCircuitParams,
+    PrecompileEcParams,
 };
 use integration_tests::{get_client, log_init, GenDataOutput};
 use lazy_static::lazy_static;
@@ -26,7 +27,9 @@ async fn test_circuit_input_builder_block(block_num: u64) {
             max_evm_rows: 0,
             max_exp_steps: 1000,
             max_keccak_rows: 0,
+            keccak_params: KeccakCircuitParams::default(),
             max_vertical_circuit_rows: 0,
+            max_ec_ops: PrecompileEcParams::default(),
         },
     )
     .await
@@ -4,11 +4,10 @@ use crate::{
     withdrawal::MockWithdrawal, MockTransaction, MOCK_BASEFEE, MOCK_CHAIN_ID, MOCK_DIFFICULTY,
     MOCK_GASLIMIT,
 };
-use eth_types::{Address, Block, Bytes, Hash, Transaction, Word, H64, U64};
-use ethers_core::{
-    types::{Bloom, OtherFields},
-    utils::keccak256,
+use eth_types::{
+    geth_types::blob_block_other_fields, Address, Block, Bytes, Hash, Transaction, Word, H64, U64,
 };
+use ethers_core::{types::Bloom, utils::keccak256};
 
 #[derive(Clone, Debug)]
 /// Mock structure which represents an Ethereum Block and can be used for tests.
@@ -35,6 +34,8 @@ pub struct MockBlock {
     nonce: H64,
     base_fee_per_gas: Option<Word>, // London upgrade, EIP-1559
     withdrawal_hash: Option<Hash>,  // Shanghai upgrade, EIP-4895
+    excess_blob_gas: U64,           // Cancun upgrade, EIP-4844
+    blob_gas_used: U64,             // Cancun upgrade, EIP-4844
     // Other information
     total_difficulty: Word,
     seal_fields: Vec<Bytes>,
@@ -70,6 +71,8 @@ impl Default for MockBlock {
             nonce: H64::zero(),
             base_fee_per_gas: Some(*MOCK_BASEFEE),
             withdrawal_hash: None,
+            excess_blob_gas: U64::zero(),
+            blob_gas_used: U64::zero(),
             // Other information
             total_difficulty: Word::zero(),
             seal_fields: Vec::new(),
@@ -113,7 +116,7 @@ impl From<MockBlock> for Block<Transaction> {
                 .map(|mock_tx| (mock_tx.chain_id(mock.chain_id).to_owned()).into())
                 .collect::<Vec<Transaction>>(),
             size: Some(mock.size),
-            other: OtherFields::default(),
+            other: blob_block_other_fields(mock.excess_blob_gas, mock.blob_gas_used),
             withdrawals_root: mock.withdrawal_hash,
             withdrawals: Some(
                 mock.withdrawals
@@ -152,7 +155,7 @@ impl From<MockBlock> for Block<()> {
             uncles: mock.uncles,
             transactions: vec![],
             size: Some(mock.size),
-            other: OtherFields::default(),
+            other: blob_block_other_fields(mock.excess_blob_gas, mock.blob_gas_used),
             withdrawals_root: mock.withdrawal_hash,
             withdrawals: Some(
                 mock.withdrawals
@@ -312,6 +315,18 @@ impl MockBlock {
         self
     }
 
+    /// Set excess_blob_gas field for the MockBlock.
+    pub fn excess_blob_gas(&mut self, excess_blob_gas: u64) -> &mut Self {
+        self.excess_blob_gas = U64::from(excess_blob_gas);
+        self
+    }
+
+    /// Set blob_gas_used field for the MockBlock.
+    pub fn blob_gas_used(&mut self, blob_gas_used: u64) -> &mut Self {
+        self.blob_gas_used = U64::from(blob_gas_used);
+        self
+    }
+
     /// Set total_difficulty field for the MockBlock.
     pub fn total_difficulty(&mut self, total_difficulty: Word) -> &mut Self {
         self.total_difficulty = total_difficulty;
@@ -2,6 +2,7 @@
 
 use crate::{withdrawal::MockWithdrawal, MockAccount, MockBlock, MockTransaction};
 use eth_types::{
+    evm_types::Hardfork,
     geth_types::{Account, BlockConstants, GethData, Withdrawal},
     Block, Error, GethExecTrace, Transaction, Word,
 };
@@ -208,6 +209,8 @@ pub fn gen_geth_traces(
 ) -> Result<Vec<GethExecTrace>, Error> {
     let trace_config = TraceConfig {
         chain_id,
+        // The hardfork of the default `FeatureConfig` of the circuits
+        hardfork: Hardfork::default(),
         history_hashes: history_hashes.unwrap_or_default(),
         block_constants: BlockConstants::try_from(&block)?,
         accounts: accounts
@@ -2,12 +2,13 @@
 
 use super::{MOCK_ACCOUNTS, MOCK_CHAIN_ID, MOCK_GASPRICE};
 use eth_types::{
-    geth_types::Transaction as GethTransaction, word, AccessList, Address, Bytes, Hash,
-    Transaction, Word, U64,
+    geth_types::{blob_tx_other_fields, Transaction as GethTransaction, TxType},
+    word, AccessList, Address, Bytes, Hash, Transaction, Word, H256, U64,
 };
 use ethers_core::{
     rand::{CryptoRng, RngCore},
-    types::{OtherFields, TransactionRequest},
+    types::TransactionRequest,
+    utils::keccak256,
 };
 use ethers_signers::{LocalWallet, Signer};
 use lazy_static::lazy_static;
@@ -135,6 +136,8 @@ pub struct MockTransaction {
     pub access_list: AccessList,
     pub max_priority_fee_per_gas: Option<Word>,
     pub max_fee_per_gas: Option<Word>,
+    pub max_fee_per_blob_gas: Option<Word>,
+    pub blob_versioned_hashes: Vec<H256>,
     pub chain_id: Word,
     pub invalid: bool,
 }
@@ -160,6 +163,8 @@ impl Default for MockTransaction {
             access_list: AccessList::default(),
             max_priority_fee_per_gas: None,
             max_fee_per_gas: None,
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: Vec::new(),
             chain_id: *MOCK_CHAIN_ID,
             invalid: false,
         }
@@ -188,7 +193,7 @@ impl From<MockTransaction> for Transaction {
             max_priority_fee_per_gas: mock.max_priority_fee_per_gas,
             max_fee_per_gas: mock.max_fee_per_gas,
             chain_id: Some(mock.chain_id),
-            other: OtherFields::default(),
+            other: blob_tx_other_fields(mock.max_fee_per_blob_gas, &mock.blob_versioned_hashes),
         }
     }
 }
@@ -299,6 +304,18 @@ impl MockTransaction {
         self
     }
 
+    /// Set max_fee_per_blob_gas field for the MockTransaction.
+    pub fn max_fee_per_blob_gas(&mut self, max_fee_per_blob_gas: Word) -> &mut Self {
+        self.max_fee_per_blob_gas = Some(max_fee_per_blob_gas);
+        self
+    }
+
+    /// Set blob_versioned_hashes field for the MockTransaction.
+    pub fn blob_versioned_hashes(&mut self, blob_versioned_hashes: Vec<H256>) -> &mut Self {
+        self.blob_versioned_hashes = blob_versioned_hashes;
+        self
+    }
+
     /// Set chain_id field for the MockTransaction.
     pub(crate) fn chain_id(&mut self, chain_id: Word) -> &mut Self {
         self.chain_id = chain_id;
@@ -318,10 +335,19 @@ impl MockTransaction {
             .gas_price(self.gas_price)
             .chain_id(self.chain_id.low_u64());
 
+        let is_eip4844 = self.transaction_type.as_u64() == TxType::Eip4844.eip2718_type().as_u64();
+
         match (self.v, self.r, self.s) {
             (None, None, None) => {
                 // Compute sig params and set them in case we have a wallet as `from` attr.
-                if self.from.is_wallet() && self.hash.is_none() {
+                if is_eip4844 && self.from.is_wallet() && self.hash.is_none() {
+                    // ethers can't encode blob txs, so sign the EIP-2718 payload directly.
+                    let sighash =
+                        keccak256(GethTransaction::from(self.to_owned()).rlp_unsigned_bytes);
+                    let sig = self.from.as_wallet().sign_hash(H256(sighash)).unwrap();
+                    // Blob txs use the y parity as `v`
+                    self.sig_data((sig.v - 27, sig.r, sig.s));
+                } else if self.from.is_wallet() && self.hash.is_none() {
                     let sig = self
                         .from
                         .as_wallet()
@@ -338,8 +364,12 @@ impl MockTransaction {
 
         // Compute tx hash in case is not already set
         if self.hash.is_none() {
-            let tmp_tx = Transaction::from(self.to_owned());
-            self.hash(tmp_tx.hash());
+            let hash = if is_eip4844 {
+                H256(keccak256(GethTransaction::from(self.to_owned()).rlp_bytes))
+            } else {
+                Transaction::from(self.to_owned()).hash()
+            };
+            self.hash(hash);
         }
 
         self.to_owned()
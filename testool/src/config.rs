@@ -12,10 +12,23 @@ pub struct Config {
     pub skip_tests: Vec<SkipTests>,
 }
 
+/// Format of the tests of a suite
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuiteKind {
+    /// State tests, filled or not, executing a single transaction
+    #[default]
+    State,
+    /// Filled blockchain tests, executing a chain of blocks
+    Blockchain,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct TestSuite {
     pub id: String,
     pub path: String,
+    #[serde(default)]
+    pub kind: SuiteKind,
     pub max_gas: u64,
     pub max_steps: u64,
 }
@@ -25,6 +38,7 @@ impl Default for TestSuite {
         Self {
             id: "default".to_string(),
             path: String::default(),
+            kind: SuiteKind::default(),
             max_gas: u64::MAX,
             max_steps: u64::MAX,
         }
@@ -5,15 +5,18 @@ mod config;
 mod statetest;
 mod utils;
 
-use crate::{config::TestSuite, statetest::ResultLevel};
+use crate::{
+    config::{SuiteKind, TestSuite},
+    statetest::ResultLevel,
+};
 use anyhow::{bail, Result};
 use clap::Parser;
 use compiler::Compiler;
 use config::Config;
 use log::info;
 use statetest::{
-    geth_trace, load_statetests_suite, run_statetests_suite, run_test, CircuitsConfig, Results,
-    StateTest,
+    geth_trace, load_blocktests_suite, load_statetests_suite, run_block_test, run_blocktests_suite,
+    run_statetests_suite, run_test, BlockchainTest, CircuitsConfig, Results, StateTest,
 };
 use std::{collections::HashSet, path::PathBuf, time::SystemTime};
 use strum_macros::EnumString;
@@ -87,6 +90,53 @@ fn run_single_test(test: StateTest, circuits_config: CircuitsConfig) -> Result<(
     Ok(())
 }
 
+/// Tests collected from a suite
+enum Tests {
+    State(Vec<StateTest>),
+    Blockchain(Vec<BlockchainTest>),
+}
+
+impl Tests {
+    fn ids(&self) -> Vec<&String> {
+        match self {
+            Tests::State(tests) => tests.iter().map(|t| &t.id).collect(),
+            Tests::Blockchain(tests) => tests.iter().map(|t| &t.id).collect(),
+        }
+    }
+
+    fn run_single(self, test_id: &str, circuits_config: CircuitsConfig) -> Result<()> {
+        match self {
+            Tests::State(tests) => {
+                let test = tests.into_iter().find(|t| t.id == test_id).unwrap();
+                run_single_test(test, circuits_config)
+            }
+            Tests::Blockchain(tests) => {
+                let test = tests.into_iter().find(|t| t.id == test_id).unwrap();
+                log::info!("{} ({} blocks)", test.id, test.blocks.len());
+                log::info!(
+                    "result={:?}",
+                    run_block_test(test, TestSuite::default(), circuits_config)
+                );
+                Ok(())
+            }
+        }
+    }
+
+    fn run(
+        self,
+        circuits_config: &CircuitsConfig,
+        suite: &TestSuite,
+        results: &mut Results,
+    ) -> Result<()> {
+        match self {
+            Tests::State(tests) => run_statetests_suite(tests, circuits_config, suite, results),
+            Tests::Blockchain(tests) => {
+                run_blocktests_suite(tests, circuits_config, suite, results)
+            }
+        }
+    }
+}
+
 fn go() -> Result<()> {
     //  RAYON_NUM_THREADS=1 RUST_BACKTRACE=1 cargo run -- --path
     // "tests/src/GeneralStateTestsFiller/**/" --skip-state-circuit
@@ -109,14 +159,22 @@ fn go() -> Result<()> {
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 
     log::info!("Using suite '{}'", args.suite);
-    log::info!("Parsing and compiling tests...");
-    let compiler = Compiler::new(true, Some(PathBuf::from(CODEHASH_FILE)))?;
     let suite = config.suite(&args.suite)?.clone();
-    let state_tests = load_statetests_suite(&suite.path, config, compiler)?;
-    log::info!("{} tests collected in {}", state_tests.len(), suite.path);
+    let tests = match suite.kind {
+        SuiteKind::State => {
+            log::info!("Parsing and compiling tests...");
+            let compiler = Compiler::new(true, Some(PathBuf::from(CODEHASH_FILE)))?;
+            Tests::State(load_statetests_suite(&suite.path, config, compiler)?)
+        }
+        SuiteKind::Blockchain => {
+            log::info!("Parsing tests...");
+            Tests::Blockchain(load_blocktests_suite(&suite.path, config)?)
+        }
+    };
+    log::info!("{} tests collected in {}", tests.ids().len(), suite.path);
 
     if args.ls {
-        let mut list: Vec<_> = state_tests.into_iter().map(|t| t.id).collect();
+        let mut list = tests.ids();
         list.sort();
         for test in list {
             info!("{}", test);
@@ -125,19 +183,18 @@ fn go() -> Result<()> {
     }
     if let Some(test_id) = args.inspect {
         // Test only one and return
-        let mut state_tests_filtered: Vec<_> =
-            state_tests.iter().filter(|t| t.id == test_id).collect();
-        if state_tests_filtered.is_empty() {
+        let ids = tests.ids();
+        if !ids.contains(&&test_id) {
             info!(
                 "Test '{}' not found but found some that partially matches:",
                 test_id
             );
-            for test in state_tests.iter().filter(|t| t.id.contains(&test_id)) {
-                info!("{}", test.id);
+            for id in ids.into_iter().filter(|id| id.contains(&test_id)) {
+                info!("{}", id);
             }
             bail!("test '{}' not found", test_id);
         }
-        run_single_test(state_tests_filtered.remove(0).clone(), circuits_config)?;
+        tests.run_single(&test_id, circuits_config)?;
         return Ok(());
     };
 
@@ -207,7 +264,7 @@ fn go() -> Result<()> {
 
         previous_results.set_cache(PathBuf::from(csv_filename));
         previous_results.write_cache()?;
-        run_statetests_suite(state_tests, &circuits_config, &suite, &mut previous_results)?;
+        tests.run(&circuits_config, &suite, &mut previous_results)?;
 
         // filter non-csv files and files from the same commit
         let mut files: Vec<_> = std::fs::read_dir(REPORT_FOLDER)
@@ -243,7 +300,7 @@ fn go() -> Result<()> {
         };
 
         log::info!("Executing...");
-        run_statetests_suite(state_tests, &circuits_config, &suite, &mut results)?;
+        tests.run(&circuits_config, &suite, &mut results)?;
         let success = results.success();
 
         log::info!("Generating report...");
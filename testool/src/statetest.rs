@@ -1,4 +1,6 @@
+mod blockchain;
 mod executor;
+mod filled;
 mod json;
 mod parse;
 mod results;
@@ -6,9 +8,12 @@ pub mod spec;
 mod suite;
 mod yaml;
 
-pub use executor::{geth_trace, run_test, CircuitsConfig};
+pub use blockchain::BlockchainTest;
+pub use executor::{geth_trace, run_block_test, run_test, CircuitsConfig};
 pub use json::JsonStateTestBuilder;
 pub use results::{ResultLevel, Results};
 pub use spec::{AccountMatch, StateTest, StateTestResult};
-pub use suite::{load_statetests_suite, run_statetests_suite};
+pub use suite::{
+    load_blocktests_suite, load_statetests_suite, run_blocktests_suite, run_statetests_suite,
+};
 pub use yaml::YamlStateTestBuilder;
@@ -0,0 +1,508 @@
+use super::{
+    filled::{parse_accounts_pre, AccountPre},
+    parse,
+    spec::{AccountMatch, Env, StateTestResult},
+};
+use crate::utils::{MainnetFork, TEST_FORK};
+use anyhow::{bail, Result};
+use eth_types::{
+    geth_types::{self, Account},
+    Address, H256, U256, U64,
+};
+use ethers_core::utils::keccak256;
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockHeader {
+    base_fee_per_gas: Option<String>,
+    coinbase: String,
+    difficulty: String,
+    gas_limit: String,
+    hash: String,
+    number: String,
+    parent_hash: String,
+    state_root: String,
+    timestamp: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTransaction {
+    #[serde(rename = "type")]
+    tx_type: Option<String>,
+    chain_id: Option<String>,
+    access_list: Option<parse::RawAccessList>,
+    data: String,
+    gas_limit: String,
+    gas_price: Option<String>,
+    max_priority_fee_per_gas: Option<String>,
+    max_fee_per_gas: Option<String>,
+    nonce: String,
+    sender: String,
+    to: String,
+    value: String,
+    v: String,
+    r: String,
+    s: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawWithdrawal {
+    index: String,
+    validator_index: String,
+    address: String,
+    amount: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawBlock {
+    block_header: Option<BlockHeader>,
+    expect_exception: Option<String>,
+    #[serde(default)]
+    transactions: Vec<RawTransaction>,
+    #[serde(default)]
+    uncle_headers: Vec<serde_json::Value>,
+    #[serde(default)]
+    withdrawals: Vec<RawWithdrawal>,
+    /// contents of a block expected to be rejected, decoded from its RLP
+    #[serde(rename = "rlp_decoded")]
+    rlp_decoded: Option<Box<RawBlock>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawBlockchainTest {
+    blocks: Vec<RawBlock>,
+    genesis_block_header: BlockHeader,
+    network: String,
+    pre: HashMap<String, AccountPre>,
+    post_state: Option<HashMap<String, AccountPre>>,
+    post_state_hash: Option<String>,
+}
+
+/// A block of a [`BlockchainTest`], with its transactions already signed.
+#[derive(Debug, Clone)]
+pub struct TestBlock {
+    /// block constants, where `previous_hash` is the parent block hash
+    pub env: Env,
+    pub hash: H256,
+    /// expected state root after the block
+    pub state_root: H256,
+    pub transactions: Vec<geth_types::Transaction>,
+    pub withdrawals: Vec<geth_types::Withdrawal>,
+    pub uncles: usize,
+    /// exception the block is expected to be rejected with, leaving the state
+    /// unchanged
+    pub expect_exception: Option<String>,
+}
+
+/// A chain of blocks executed on top of the `pre` state.  Blocks that are
+/// expected to be rejected are kept with their expected exception, except
+/// those that can't be decoded, which are only counted since they can't be
+/// executed.
+#[derive(Debug, Clone)]
+pub struct BlockchainTest {
+    pub path: String,
+    pub id: String,
+    pub pre: BTreeMap<Address, Account>,
+    pub genesis_hash: H256,
+    pub blocks: Vec<TestBlock>,
+    pub post: StateTestResult,
+    /// expected state root after the last block, if the post state is not
+    /// given
+    pub state_root: Option<H256>,
+    /// number of blocks expected to be rejected that can't be decoded
+    pub undecodable_blocks: usize,
+}
+
+/// generates `BlockchainTest` vectors from a filled ethereum json blockchain
+/// test, as found in the `BlockchainTests` folder
+pub fn load_json(path: &str, source: &str) -> Result<Vec<BlockchainTest>> {
+    let mut blockchain_tests = Vec::new();
+    let tests: HashMap<String, RawBlockchainTest> = serde_json::from_str(source)?;
+
+    for (test_name, test) in tests {
+        if MainnetFork::from_str(&test.network).ok() != Some(TEST_FORK) {
+            continue;
+        }
+
+        let mut blocks = Vec::new();
+        let mut undecodable_blocks = 0;
+        for block in &test.blocks {
+            let body = block.rlp_decoded.as_deref().unwrap_or(block);
+            let parsed = body
+                .block_header
+                .as_ref()
+                .map(|header| parse_block(header, body));
+            match (parsed, &block.expect_exception) {
+                (Some(Ok(parsed)), expect_exception) => blocks.push(TestBlock {
+                    expect_exception: expect_exception.clone(),
+                    ..parsed
+                }),
+                (None | Some(Err(_)), Some(_)) => undecodable_blocks += 1,
+                (None, None) => bail!("block without header in {test_name}"),
+                (Some(Err(err)), None) => return Err(err),
+            }
+        }
+
+        let post = test
+            .post_state
+            .as_ref()
+            .map(parse_accounts_post)
+            .transpose()?
+            .unwrap_or_default();
+
+        blockchain_tests.push(BlockchainTest {
+            path: path.to_string(),
+            id: test_name,
+            pre: parse_accounts_pre(&test.pre)?,
+            genesis_hash: parse::parse_hash(&test.genesis_block_header.hash)?,
+            blocks,
+            post,
+            state_root: test
+                .post_state_hash
+                .as_ref()
+                .map(|v| parse::parse_hash(v))
+                .transpose()?,
+            undecodable_blocks,
+        });
+    }
+
+    Ok(blockchain_tests)
+}
+
+/// parse a block header and its body
+fn parse_block(header: &BlockHeader, block: &RawBlock) -> Result<TestBlock> {
+    let env = Env {
+        current_base_fee: header
+            .base_fee_per_gas
+            .as_ref()
+            .map(|v| parse::parse_u256(v))
+            .transpose()?
+            .unwrap_or_default(),
+        current_coinbase: parse::parse_address(&header.coinbase)?,
+        current_difficulty: parse::parse_u256(&header.difficulty)?,
+        current_gas_limit: parse::parse_u64(&header.gas_limit)?,
+        current_number: parse::parse_u64(&header.number)?,
+        current_timestamp: parse::parse_u64(&header.timestamp)?,
+        previous_hash: parse::parse_hash(&header.parent_hash)?,
+    };
+
+    let transactions = block
+        .transactions
+        .iter()
+        .map(|tx| parse_transaction(tx, env.current_base_fee))
+        .collect::<Result<_>>()?;
+
+    let withdrawals = block
+        .withdrawals
+        .iter()
+        .map(|wd| {
+            Ok(geth_types::Withdrawal {
+                id: parse::parse_u64(&wd.index)?,
+                validator_id: parse::parse_u64(&wd.validator_index)?,
+                address: parse::parse_address(&wd.address)?,
+                amount: parse::parse_u64(&wd.amount)?,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(TestBlock {
+        env,
+        hash: parse::parse_hash(&header.hash)?,
+        state_root: parse::parse_hash(&header.state_root)?,
+        transactions,
+        withdrawals,
+        uncles: block.uncle_headers.len(),
+        expect_exception: None,
+    })
+}
+
+/// parse a signed transaction
+fn parse_transaction(tx: &RawTransaction, base_fee: U256) -> Result<geth_types::Transaction> {
+    let max_priority_fee_per_gas = tx
+        .max_priority_fee_per_gas
+        .as_ref()
+        .map(|v| parse::parse_u256(v))
+        .transpose()?;
+    let max_fee_per_gas = tx
+        .max_fee_per_gas
+        .as_ref()
+        .map(|v| parse::parse_u256(v))
+        .transpose()?;
+
+    // Set gas price to `min(max_priority_fee_per_gas + base_fee, max_fee_per_gas)` for
+    // EIP-1559 transaction.
+    // <https://github.com/ethereum/go-ethereum/blob/1485814f89d8206bb4a1c8e10a4a2893920f683a/core/state_transition.go#L167>
+    let gas_price = match &tx.gas_price {
+        Some(gas_price) => parse::parse_u256(gas_price)?,
+        None => max_fee_per_gas
+            .unwrap_or_default()
+            .min(max_priority_fee_per_gas.unwrap_or_default() + base_fee),
+    };
+
+    let mut tx = eth_types::Transaction {
+        from: parse::parse_address(&tx.sender)?,
+        to: parse::parse_to_address(&tx.to)?,
+        nonce: parse::parse_u256(&tx.nonce)?,
+        gas: parse::parse_u256(&tx.gas_limit)?,
+        value: parse::parse_u256(&tx.value)?,
+        gas_price: Some(gas_price),
+        max_priority_fee_per_gas,
+        max_fee_per_gas,
+        input: parse::parse_bytes(&tx.data)?,
+        access_list: parse::parse_access_list(&tx.access_list)?,
+        v: parse::parse_u64(&tx.v)?.into(),
+        r: parse::parse_u256(&tx.r)?,
+        s: parse::parse_u256(&tx.s)?,
+        transaction_type: tx
+            .tx_type
+            .as_ref()
+            .map(|v| parse::parse_u64(v))
+            .transpose()?
+            .map(U64::from),
+        chain_id: tx
+            .chain_id
+            .as_ref()
+            .map(|v| parse::parse_u256(v))
+            .transpose()?,
+        ..Default::default()
+    };
+    tx.hash = keccak256(tx.rlp()).into();
+
+    Ok(geth_types::Transaction::from(&tx))
+}
+
+/// parse a vector of address=>(storage,balance,code,nonce) entry, all of
+/// them expected
+fn parse_accounts_post(
+    accounts_post: &HashMap<String, AccountPre>,
+) -> Result<HashMap<Address, AccountMatch>> {
+    Ok(parse_accounts_pre(accounts_post)?
+        .into_iter()
+        .map(|(address, acc)| {
+            let account = AccountMatch {
+                address,
+                balance: Some(acc.balance),
+                code: Some(acc.code),
+                nonce: Some(acc.nonce.as_u64()),
+                storage: acc.storage,
+            };
+            (address, account)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use eth_types::Bytes;
+
+    const JSON: &str = r#"
+{
+    "withdrawalAndTransfer_Shanghai" : {
+        "blocks" : [
+            {
+                "blockHeader" : {
+                    "baseFeePerGas" : "0x0a",
+                    "coinbase" : "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+                    "difficulty" : "0x00",
+                    "gasLimit" : "0x0f4240",
+                    "gasUsed" : "0x5208",
+                    "hash" : "0x1111111111111111111111111111111111111111111111111111111111111111",
+                    "number" : "0x01",
+                    "parentHash" : "0x2222222222222222222222222222222222222222222222222222222222222222",
+                    "stateRoot" : "0x3333333333333333333333333333333333333333333333333333333333333333",
+                    "timestamp" : "0x03e8"
+                },
+                "transactions" : [
+                    {
+                        "data" : "0x",
+                        "gasLimit" : "0x5208",
+                        "gasPrice" : "0x0a",
+                        "nonce" : "0x00",
+                        "r" : "0x01",
+                        "s" : "0x02",
+                        "sender" : "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b",
+                        "to" : "0x095e7baea6a6c7c4c2dfeb977efac326af552d87",
+                        "v" : "0x25",
+                        "value" : "0x0186a0"
+                    }
+                ],
+                "uncleHeaders" : [
+                ],
+                "withdrawals" : [
+                    {
+                        "address" : "0x095e7baea6a6c7c4c2dfeb977efac326af552d87",
+                        "amount" : "0x01",
+                        "index" : "0x00",
+                        "validatorIndex" : "0x05"
+                    }
+                ]
+            },
+            {
+                "expectException" : "BlockException.UNCLE_HEADERS",
+                "rlp" : "0x00",
+                "rlp_decoded" : {
+                    "blockHeader" : {
+                        "baseFeePerGas" : "0x0a",
+                        "coinbase" : "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+                        "difficulty" : "0x00",
+                        "gasLimit" : "0x0f4240",
+                        "hash" : "0x5555555555555555555555555555555555555555555555555555555555555555",
+                        "number" : "0x02",
+                        "parentHash" : "0x1111111111111111111111111111111111111111111111111111111111111111",
+                        "stateRoot" : "0x3333333333333333333333333333333333333333333333333333333333333333",
+                        "timestamp" : "0x07d0"
+                    },
+                    "transactions" : [
+                    ],
+                    "uncleHeaders" : [
+                        {
+                        }
+                    ],
+                    "withdrawals" : [
+                    ]
+                }
+            },
+            {
+                "expectException" : "BlockException.RLP_STRUCTURES_ENCODING",
+                "rlp" : "0x00"
+            }
+        ],
+        "genesisBlockHeader" : {
+            "baseFeePerGas" : "0x0a",
+            "coinbase" : "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+            "difficulty" : "0x00",
+            "gasLimit" : "0x0f4240",
+            "hash" : "0x2222222222222222222222222222222222222222222222222222222222222222",
+            "number" : "0x00",
+            "parentHash" : "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "stateRoot" : "0x4444444444444444444444444444444444444444444444444444444444444444",
+            "timestamp" : "0x00"
+        },
+        "network" : "Shanghai",
+        "postState" : {
+            "0x095e7baea6a6c7c4c2dfeb977efac326af552d87" : {
+                "balance" : "0x3b9c50a0",
+                "code" : "0x",
+                "nonce" : "0x00",
+                "storage" : {
+                }
+            }
+        },
+        "pre" : {
+            "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b" : {
+                "balance" : "0x0de0b6b3a7640000",
+                "code" : "0x",
+                "nonce" : "0x00",
+                "storage" : {
+                }
+            }
+        }
+    },
+    "withdrawalAndTransfer_London" : {
+        "blocks" : [
+        ],
+        "genesisBlockHeader" : {
+            "coinbase" : "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+            "difficulty" : "0x020000",
+            "gasLimit" : "0x0f4240",
+            "hash" : "0x2222222222222222222222222222222222222222222222222222222222222222",
+            "number" : "0x00",
+            "parentHash" : "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "stateRoot" : "0x4444444444444444444444444444444444444444444444444444444444444444",
+            "timestamp" : "0x00"
+        },
+        "network" : "London",
+        "postStateHash" : "0x4444444444444444444444444444444444444444444444444444444444444444",
+        "pre" : {
+        }
+    }
+}
+"#;
+    #[test]
+    fn test_blockchain_json_parse() -> Result<()> {
+        let mut tests = load_json("test_path", JSON)?;
+        assert_eq!(tests.len(), 1);
+        let test = tests.remove(0);
+
+        let acc095e = Address::from_str("0x095e7baea6a6c7c4c2dfeb977efac326af552d87")?;
+        let acca94f = Address::from_str("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b")?;
+
+        assert_eq!(test.id, "withdrawalAndTransfer_Shanghai");
+        assert_eq!(test.genesis_hash, H256::repeat_byte(0x22));
+        assert_eq!(test.state_root, None);
+        assert_eq!(test.pre.keys().collect::<Vec<_>>(), vec![&acca94f]);
+        assert_eq!(
+            test.post,
+            HashMap::from([(
+                acc095e,
+                AccountMatch {
+                    address: acc095e,
+                    balance: Some(U256::from(1000100000u64)),
+                    code: Some(Bytes::default()),
+                    nonce: Some(0),
+                    storage: HashMap::new(),
+                },
+            )])
+        );
+
+        // the block expected to be rejected that can't be decoded is not part
+        // of the chain
+        assert_eq!(test.blocks.len(), 2);
+        assert_eq!(test.undecodable_blocks, 1);
+        let block = &test.blocks[0];
+        assert_eq!(
+            block.env,
+            Env {
+                current_base_fee: U256::from(10u64),
+                current_coinbase: Address::from_str("0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba")?,
+                current_difficulty: U256::zero(),
+                current_gas_limit: 1000000,
+                current_number: 1,
+                current_timestamp: 1000,
+                previous_hash: H256::repeat_byte(0x22),
+            }
+        );
+        assert_eq!(block.hash, H256::repeat_byte(0x11));
+        assert_eq!(block.state_root, H256::repeat_byte(0x33));
+        assert_eq!(block.uncles, 0);
+        assert_eq!(block.expect_exception, None);
+
+        assert_eq!(block.transactions.len(), 1);
+        let tx = &block.transactions[0];
+        assert_eq!(tx.from, acca94f);
+        assert_eq!(tx.to, Some(acc095e));
+        assert_eq!(tx.gas_price, U256::from(10u64));
+        assert_eq!(tx.value, U256::from(100000u64));
+        assert_eq!(tx.v, 0x25);
+        assert_eq!(tx.hash, keccak256(&tx.rlp_bytes).into());
+
+        assert_eq!(block.withdrawals.len(), 1);
+        let wd = &block.withdrawals[0];
+        assert_eq!(
+            (wd.id, wd.validator_id, wd.address, wd.amount),
+            (0, 5, acc095e, 1)
+        );
+
+        let block = &test.blocks[1];
+        assert_eq!(block.hash, H256::repeat_byte(0x55));
+        assert_eq!(block.env.previous_hash, H256::repeat_byte(0x11));
+        assert_eq!(block.uncles, 1);
+        assert_eq!(
+            block.expect_exception.as_deref(),
+            Some("BlockException.UNCLE_HEADERS")
+        );
+        assert!(block.transactions.is_empty());
+
+        Ok(())
+    }
+}
@@ -1,15 +1,28 @@
-use super::{AccountMatch, StateTest, StateTestResult};
-use crate::{config::TestSuite, utils::ETH_CHAIN_ID};
+use super::{blockchain::TestBlock, AccountMatch, BlockchainTest, StateTest, StateTestResult};
+use crate::{
+    config::TestSuite,
+    utils::{ETH_CHAIN_ID, TEST_FORK},
+};
 use bus_mapping::{
-    circuit_input_builder::{CircuitInputBuilder, FixedCParams},
+    circuit_input_builder::{
+        CircuitInputBuilder, FeatureConfig, FixedCParams, KeccakCircuitParams, PrecompileEcParams,
+    },
     mock::BlockData,
+    mpt::StateTrie,
+    state_db::{self, CodeDB, StateDB},
+};
+use eth_types::{
+    geth_types::{self, GethData},
+    Address, Bytes, Error, GethExecTrace, H256, U256, U64,
 };
-use eth_types::{geth_types, Address, Bytes, Error, GethExecTrace, U256, U64};
 use ethers_core::{k256::ecdsa::SigningKey, types::Withdrawal, utils::keccak256};
 use ethers_signers::{LocalWallet, Signer};
 use external_tracer::TraceConfig;
 use halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr};
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
 use thiserror::Error;
 use zkevm_circuits::{
     super_circuit::SuperCircuit,
@@ -33,6 +46,8 @@ pub enum StateTestError {
         expected: U256,
         found: U256,
     },
+    #[error("StateRootMismatch(expected:{expected:?}, found:{found:?})")]
+    StateRootMismatch { expected: H256, found: H256 },
     #[error("CircuitUnsatisfied(num_failure: {num_failure:?}  first: {first_failure:?}")]
     CircuitUnsatisfied {
         num_failure: usize,
@@ -48,6 +63,8 @@ pub enum StateTestError {
     SkipTestDifficulty,
     #[error("SkipTestBalanceOverflow")]
     SkipTestBalanceOverflow,
+    #[error("SkipTestUndecodableBlocks({0})")]
+    SkipTestUndecodableBlocks(usize),
     #[error("Exception(expected:{expected:?}, found:{found:?})")]
     Exception { expected: bool, found: String },
 }
@@ -64,6 +81,7 @@ impl StateTestError {
             StateTestError::SkipTestMaxSteps(_)
                 | StateTestError::SkipTestMaxGasLimit(_)
                 | StateTestError::SkipTestSelfDestruct
+                | StateTestError::SkipTestUndecodableBlocks(_)
         )
     }
 }
@@ -75,13 +93,25 @@ pub struct CircuitsConfig {
 }
 
 fn check_post(
-    builder: &CircuitInputBuilder<FixedCParams>,
+    sdb: &StateDB,
+    code_db: &CodeDB,
     post: &HashMap<Address, AccountMatch>,
+    state_root: Option<H256>,
 ) -> Result<(), StateTestError> {
     log::trace!("check post");
+    // check if the generated state root is the expected one
+    if let Some(expected) = state_root {
+        let found = StateTrie::from_state_db(sdb)
+            .map_err(|err| StateTestError::CircuitInput(err.to_string()))?
+            .root();
+        if expected != found {
+            log::error!("state root mismatch, expected {expected:?} actual {found:?}");
+            return Err(StateTestError::StateRootMismatch { expected, found });
+        }
+    }
     // check if the generated account data is the expected one
     for (address, expected) in post {
-        let (_, actual) = builder.sdb.get_account(address);
+        let (_, actual) = sdb.get_account(address);
 
         if expected.balance.map(|v| v == actual.balance) == Some(false) {
             log::error!("balance mismatch, expected {expected:?} actual {actual:?}");
@@ -102,8 +132,7 @@ fn check_post(
         if let Some(expected_code) = &expected.code {
             let actual_code = (!actual.code_hash.is_zero())
                 .then(|| {
-                    builder
-                        .code_db
+                    code_db
                         .get_from_h256(&actual.code_hash)
                         .map(|bytecode| bytecode.code())
                         .expect("code exists")
@@ -151,6 +180,7 @@ fn into_traceconfig(st: StateTest) -> (String, TraceConfig, StateTestResult) {
         st.id,
         TraceConfig {
             chain_id: U256::from(ETH_CHAIN_ID),
+            hardfork: TEST_FORK.hardfork(),
             history_hashes: vec![U256::from_big_endian(st.env.previous_hash.as_bytes())],
             block_constants: geth_types::BlockConstants {
                 coinbase: st.env.current_coinbase,
@@ -159,6 +189,7 @@ fn into_traceconfig(st: StateTest) -> (String, TraceConfig, StateTestResult) {
                 difficulty: st.env.current_difficulty,
                 gas_limit: U256::from(st.env.current_gas_limit),
                 base_fee: st.env.current_base_fee,
+                ..Default::default()
             },
 
             transactions: vec![geth_types::Transaction {
@@ -173,6 +204,8 @@ fn into_traceconfig(st: StateTest) -> (String, TraceConfig, StateTestResult) {
                 gas_tip_cap: st.max_priority_fee_per_gas,
                 call_data: st.data,
                 access_list: st.access_list,
+                max_fee_per_blob_gas: None,
+                blob_versioned_hashes: vec![],
                 v,
                 r: sig.r,
                 s: sig.s,
@@ -210,64 +243,30 @@ fn check_geth_traces(
         return Err(StateTestError::SkipTestSelfDestruct);
     }
 
-    if geth_traces[0].struct_logs.len() as u64 > suite.max_steps {
-        return Err(StateTestError::SkipTestMaxSteps(
-            geth_traces[0].struct_logs.len(),
-        ));
+    let steps: usize = geth_traces.iter().map(|gt| gt.struct_logs.len()).sum();
+    if steps as u64 > suite.max_steps {
+        return Err(StateTestError::SkipTestMaxSteps(steps));
     }
 
-    if suite.max_gas > 0 && geth_traces[0].gas > suite.max_gas {
-        return Err(StateTestError::SkipTestMaxGasLimit(geth_traces[0].gas));
+    let gas: u64 = geth_traces.iter().map(|gt| gt.gas).sum();
+    if suite.max_gas > 0 && gas > suite.max_gas {
+        return Err(StateTestError::SkipTestMaxGasLimit(gas));
     }
     if verbose {
-        if let Err(e) = crate::utils::print_trace(geth_traces[0].clone()) {
-            log::error!("fail to pretty print trace {e:?}");
+        for geth_trace in geth_traces {
+            if let Err(e) = crate::utils::print_trace(geth_trace.clone()) {
+                log::error!("fail to pretty print trace {e:?}");
+            }
         }
     }
     Ok(())
 }
 
-pub fn run_test(
-    st: StateTest,
-    suite: TestSuite,
-    circuits_config: CircuitsConfig,
-) -> Result<(), StateTestError> {
-    // get the geth traces
-
-    let (_, trace_config, post) = into_traceconfig(st.clone());
-
-    let geth_traces = external_tracer::trace(&trace_config);
-
-    let geth_traces = match (geth_traces, st.exception) {
-        (Ok(res), false) => res,
-        (Ok(_), true) => {
-            return Err(StateTestError::Exception {
-                expected: true,
-                found: "no error".into(),
-            })
-        }
-        (Err(_), true) => return Ok(()),
-        (Err(err), false) => {
-            if let Error::TracingError(ref err) = err {
-                if err.contains("max initcode size exceeded") {
-                    return Err(StateTestError::Exception {
-                        expected: true,
-                        found: err.to_string(),
-                    });
-                }
-            }
-            return Err(StateTestError::Exception {
-                expected: false,
-                found: err.to_string(),
-            });
-        }
-    };
-
-    check_geth_traces(&geth_traces, &suite, circuits_config.verbose)?;
-
+/// builds the block executed by the tracer with `trace_config`
+fn build_eth_block(trace_config: &TraceConfig) -> eth_types::Block<eth_types::Transaction> {
     let transactions = trace_config
         .transactions
-        .into_iter()
+        .iter()
         .enumerate()
         .map(|(index, tx)| {
             tx.to_response(
@@ -280,7 +279,7 @@ pub fn run_test(
 
     let withdrawals = trace_config
         .withdrawals
-        .into_iter()
+        .iter()
         .map(|wd| {
             Some(Withdrawal {
                 index: wd.id.into(),
@@ -291,7 +290,7 @@ pub fn run_test(
         })
         .collect();
 
-    let eth_block = eth_types::Block {
+    eth_types::Block {
         author: Some(trace_config.block_constants.coinbase),
         timestamp: trace_config.block_constants.timestamp,
         number: Some(U64::from(trace_config.block_constants.number.as_u64())),
@@ -301,31 +300,33 @@ pub fn run_test(
         withdrawals,
         transactions,
         ..eth_types::Block::default()
-    };
-
-    let wallet: LocalWallet = SigningKey::from_slice(&st.secret_key).unwrap().into();
-    let mut wallets = HashMap::new();
-    wallets.insert(
-        wallet.address(),
-        wallet.with_chain_id(trace_config.chain_id.as_u64()),
-    );
+    }
+}
 
-    // process the transaction
-    let mut geth_data = eth_types::geth_types::GethData {
-        chain_id: trace_config.chain_id,
-        history_hashes: trace_config.history_hashes.clone(),
-        geth_traces: geth_traces.clone(),
-        accounts: trace_config.accounts.values().cloned().collect(),
-        eth_block: eth_block.clone(),
+/// generates the circuit inputs of the block in `geth_data` and verifies the
+/// circuits with them
+fn verify_circuits(
+    geth_data: GethData,
+    circuits_config: &CircuitsConfig,
+) -> Result<CircuitInputBuilder<FixedCParams>, StateTestError> {
+    let max_txs = geth_data.eth_block.transactions.len().max(1);
+    let max_withdrawals = geth_data
+        .eth_block
+        .withdrawals
+        .as_ref()
+        .map_or(0, |wds| wds.len())
+        .max(1);
+    // the rules of the fork whose post state is checked
+    let feature_config = FeatureConfig {
+        hardfork: TEST_FORK.hardfork(),
+        ..Default::default()
     };
 
-    let builder;
-
-    if !circuits_config.super_circuit {
+    let builder = if !circuits_config.super_circuit {
         let circuits_params = FixedCParams {
             total_chunks: 1,
-            max_txs: 1,
-            max_withdrawals: 1,
+            max_txs,
+            max_withdrawals,
             max_rws: 55000,
             max_calldata: 5000,
             max_bytecode: 5000,
@@ -333,12 +334,16 @@ pub fn run_test(
             max_evm_rows: 0,
             max_exp_steps: 5000,
             max_keccak_rows: 0,
+            keccak_params: KeccakCircuitParams::default(),
             max_vertical_circuit_rows: 0,
+            max_ec_ops: PrecompileEcParams::default(),
         };
+        let eth_block = geth_data.eth_block.clone();
+        let geth_traces = geth_data.geth_traces.clone();
         let block_data = BlockData::new_from_geth_data_with_params(geth_data, circuits_params);
 
-        builder = block_data
-            .new_circuit_input_builder()
+        let builder = block_data
+            .new_circuit_input_builder_with_feature(feature_config)
             .handle_block(&eth_block, &geth_traces)
             .map_err(|err| StateTestError::CircuitInput(err.to_string()))?;
 
@@ -360,13 +365,12 @@ pub fn run_test(
                     found: err.to_string(),
                 },
             })?;
+        builder
     } else {
-        geth_data.sign(&wallets);
-
         let circuits_params = FixedCParams {
             total_chunks: 1,
-            max_txs: 1,
-            max_withdrawals: 1,
+            max_txs,
+            max_withdrawals,
             max_calldata: 32,
             max_rws: 256,
             max_copy_rows: 256,
@@ -374,11 +378,23 @@ pub fn run_test(
             max_bytecode: 512,
             max_evm_rows: 0,
             max_keccak_rows: 0,
+            keccak_params: KeccakCircuitParams::default(),
             max_vertical_circuit_rows: 0,
+            max_ec_ops: PrecompileEcParams {
+                ec_add: 0,
+                ec_mul: 0,
+                ec_pairing: 0,
+            },
         };
-        let (k, mut circuits, mut instances, _builder) =
-            SuperCircuit::<Fr>::build(geth_data, circuits_params, Fr::from(0x100)).unwrap();
-        builder = _builder;
+        let eth_block = geth_data.eth_block.clone();
+        let geth_traces = geth_data.geth_traces.clone();
+        let builder = BlockData::new_from_geth_data_with_params(geth_data, circuits_params)
+            .new_circuit_input_builder_with_feature(feature_config)
+            .handle_block(&eth_block, &geth_traces)
+            .map_err(|err| StateTestError::CircuitInput(err.to_string()))?;
+        let (k, mut circuits, mut instances) =
+            SuperCircuit::<Fr>::build_from_circuit_input_builder(&builder, Fr::from(0x100))
+                .unwrap();
 
         let circuit = circuits.remove(0);
         let instance = instances.remove(0);
@@ -390,8 +406,302 @@ pub fn run_test(
                 num_failure: err.len(),
                 first_failure: err[0].to_string(),
             })?;
+        builder
     };
-    check_post(&builder, &post)?;
+
+    Ok(builder)
+}
+
+pub fn run_test(
+    st: StateTest,
+    suite: TestSuite,
+    circuits_config: CircuitsConfig,
+) -> Result<(), StateTestError> {
+    // get the geth traces
+
+    let (_, trace_config, post) = into_traceconfig(st.clone());
+
+    let geth_traces = external_tracer::trace(&trace_config);
+
+    let geth_traces = match (geth_traces, st.exception) {
+        (Ok(res), false) => res,
+        (Ok(_), true) => {
+            return Err(StateTestError::Exception {
+                expected: true,
+                found: "no error".into(),
+            })
+        }
+        (Err(_), true) => return Ok(()),
+        (Err(err), false) => {
+            if let Error::TracingError(ref err) = err {
+                if err.contains("max initcode size exceeded") {
+                    return Err(StateTestError::Exception {
+                        expected: true,
+                        found: err.to_string(),
+                    });
+                }
+            }
+            return Err(StateTestError::Exception {
+                expected: false,
+                found: err.to_string(),
+            });
+        }
+    };
+
+    check_geth_traces(&geth_traces, &suite, circuits_config.verbose)?;
+
+    let eth_block = build_eth_block(&trace_config);
+
+    let wallet: LocalWallet = SigningKey::from_slice(&st.secret_key).unwrap().into();
+    let mut wallets = HashMap::new();
+    wallets.insert(
+        wallet.address(),
+        wallet.with_chain_id(trace_config.chain_id.as_u64()),
+    );
+
+    // process the transaction
+    let mut geth_data = GethData {
+        chain_id: trace_config.chain_id,
+        history_hashes: trace_config.history_hashes.clone(),
+        geth_traces,
+        accounts: trace_config.accounts.values().cloned().collect(),
+        eth_block,
+    };
+    if circuits_config.super_circuit {
+        geth_data.sign(&wallets);
+    }
+
+    let builder = verify_circuits(geth_data, &circuits_config)?;
+    check_post(&builder.sdb, &builder.code_db, &post, st.state_root)?;
 
     Ok(())
 }
+
+/// accounts of the state in `sdb`, in the form expected by the tracer
+fn accounts_of(sdb: &StateDB, code_db: &CodeDB) -> BTreeMap<Address, geth_types::Account> {
+    sdb.accounts()
+        .filter(|(_, account)| !account.is_empty() && !account.code_hash.is_zero())
+        .map(|(address, account)| {
+            let code = code_db
+                .get_from_h256(&account.code_hash)
+                .map(|bytecode| bytecode.code())
+                .unwrap_or_default();
+            let storage = account
+                .storage
+                .iter()
+                .filter(|(_, value)| !value.is_zero())
+                .map(|(key, value)| (*key, *value))
+                .collect();
+            (
+                *address,
+                geth_types::Account {
+                    address: *address,
+                    nonce: account.nonce.into(),
+                    balance: account.balance,
+                    code: code.into(),
+                    storage,
+                },
+            )
+        })
+        .collect()
+}
+
+/// executes the blocks of a blockchain test one after the other, verifying
+/// the circuits and the state root of each one, and checks the final state
+/// against the expected post state.
+pub fn run_block_test(
+    bt: BlockchainTest,
+    suite: TestSuite,
+    circuits_config: CircuitsConfig,
+) -> Result<(), StateTestError> {
+    // the rejection of the blocks that can't be decoded is not checked
+    if bt.undecodable_blocks > 0 {
+        return Err(StateTestError::SkipTestUndecodableBlocks(
+            bt.undecodable_blocks,
+        ));
+    }
+
+    let mut sdb = StateDB::new();
+    let mut code_db = CodeDB::default();
+    for account in bt.pre.values() {
+        code_db.insert(account.code.to_vec());
+        sdb.set_account(&account.address, state_db::Account::from(account.clone()));
+    }
+    let mut history_hashes = vec![U256::from_big_endian(bt.genesis_hash.as_bytes())];
+
+    for block in bt.blocks {
+        let (number, hash) = (block.env.current_number, block.hash);
+        let expect_exception = block.expect_exception.clone();
+        let result = run_block(
+            block,
+            &sdb,
+            &code_db,
+            &history_hashes,
+            &suite,
+            &circuits_config,
+        );
+
+        (sdb, code_db) = match (result, expect_exception) {
+            (Ok(state), None) => state,
+            (Err(err), None) => return Err(err),
+            (Err(err), Some(_)) if err.is_skip() => return Err(err),
+            // the rejected block leaves the state unchanged
+            (Err(_), Some(_)) => continue,
+            (Ok(_), Some(exception)) => {
+                return Err(StateTestError::Exception {
+                    expected: true,
+                    found: format!("no error in block {number}, expected {exception}"),
+                })
+            }
+        };
+        history_hashes.push(U256::from_big_endian(hash.as_bytes()));
+        if history_hashes.len() > 256 {
+            history_hashes.remove(0);
+        }
+    }
+
+    check_post(&sdb, &code_db, &bt.post, bt.state_root)
+}
+
+/// Execute a block of a `BlockchainTest` on top of `sdb` and verify the
+/// circuits, returning the state after the block.
+fn run_block(
+    block: TestBlock,
+    sdb: &StateDB,
+    code_db: &CodeDB,
+    history_hashes: &[U256],
+    suite: &TestSuite,
+    circuits_config: &CircuitsConfig,
+) -> Result<(StateDB, CodeDB), StateTestError> {
+    // uncles are rejected since the merge
+    if block.uncles > 0 {
+        return Err(StateTestError::Exception {
+            expected: false,
+            found: format!("block {} has uncles", block.env.current_number),
+        });
+    }
+
+    let trace_config = TraceConfig {
+        chain_id: U256::from(ETH_CHAIN_ID),
+        hardfork: TEST_FORK.hardfork(),
+        history_hashes: history_hashes.to_vec(),
+        block_constants: geth_types::BlockConstants {
+            coinbase: block.env.current_coinbase,
+            timestamp: U256::from(block.env.current_timestamp),
+            number: U64::from(block.env.current_number),
+            difficulty: block.env.current_difficulty,
+            gas_limit: U256::from(block.env.current_gas_limit),
+            base_fee: block.env.current_base_fee,
+            ..Default::default()
+        },
+        accounts: accounts_of(sdb, code_db),
+        transactions: block.transactions,
+        withdrawals: block.withdrawals,
+        ..Default::default()
+    };
+
+    let geth_traces =
+        external_tracer::trace(&trace_config).map_err(|err| StateTestError::Exception {
+            expected: false,
+            found: err.to_string(),
+        })?;
+
+    check_geth_traces(&geth_traces, suite, circuits_config.verbose)?;
+
+    let mut eth_block = build_eth_block(&trace_config);
+    eth_block.hash = Some(block.hash);
+    eth_block.parent_hash = block.env.previous_hash;
+
+    let geth_data = GethData {
+        chain_id: trace_config.chain_id,
+        history_hashes: trace_config.history_hashes,
+        geth_traces,
+        accounts: trace_config.accounts.into_values().collect(),
+        eth_block,
+    };
+
+    let builder = verify_circuits(geth_data, circuits_config)?;
+    check_post(
+        &builder.sdb,
+        &builder.code_db,
+        &HashMap::new(),
+        Some(block.state_root),
+    )?;
+
+    Ok((builder.sdb, builder.code_db))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::statetest::spec::Env;
+
+    #[test]
+    fn block_with_withdrawals() -> Result<(), StateTestError> {
+        let (existing, new) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+        let account = |address, balance: u64| geth_types::Account {
+            address,
+            balance: U256::from(balance),
+            ..Default::default()
+        };
+        let withdrawal = |id, address, amount| geth_types::Withdrawal {
+            id,
+            validator_id: id,
+            address,
+            amount,
+        };
+
+        // amounts are in Gwei, credited after the transactions of the block
+        let mut expected = StateDB::new();
+        for account in [
+            account(existing, 3_000_000_100),
+            account(new, 2_000_000_000),
+        ] {
+            expected.set_account(&account.address, state_db::Account::from(account));
+        }
+        let state_root = StateTrie::from_state_db(&expected)
+            .map_err(|err| StateTestError::CircuitInput(err.to_string()))?
+            .root();
+
+        let mut bt = BlockchainTest {
+            path: String::default(),
+            id: "withdrawals".to_string(),
+            pre: BTreeMap::from([(existing, account(existing, 100))]),
+            genesis_hash: H256::repeat_byte(0x22),
+            blocks: vec![TestBlock {
+                env: Env {
+                    current_base_fee: U256::from(10u64),
+                    current_coinbase: Address::repeat_byte(0x33),
+                    current_difficulty: U256::zero(),
+                    current_gas_limit: 1_000_000,
+                    current_number: 1,
+                    current_timestamp: 1000,
+                    previous_hash: H256::repeat_byte(0x22),
+                },
+                hash: H256::repeat_byte(0x44),
+                state_root,
+                transactions: vec![],
+                withdrawals: vec![
+                    withdrawal(0, existing, 1),
+                    withdrawal(1, new, 2),
+                    withdrawal(2, existing, 2),
+                ],
+                uncles: 0,
+                expect_exception: None,
+            }],
+            post: HashMap::new(),
+            state_root: Some(state_root),
+            undecodable_blocks: 0,
+        };
+        run_block_test(bt.clone(), TestSuite::default(), CircuitsConfig::default())?;
+
+        // the state root changes with the credited amounts
+        bt.blocks[0].withdrawals.pop();
+        assert!(matches!(
+            run_block_test(bt, TestSuite::default(), CircuitsConfig::default()),
+            Err(StateTestError::StateRootMismatch { .. })
+        ));
+
+        Ok(())
+    }
+}
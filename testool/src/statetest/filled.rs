@@ -0,0 +1,329 @@
+use super::{
+    json::{parse_env, TestEnv},
+    parse,
+    spec::StateTest,
+};
+use crate::utils::{MainnetFork, TEST_FORK};
+use anyhow::Result;
+use eth_types::{geth_types::Account, Address, H256, U256};
+use ethers_core::{k256::ecdsa::SigningKey, utils::secret_key_to_address};
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Deserialize)]
+struct Indexes {
+    data: usize,
+    gas: usize,
+    value: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PostState {
+    hash: String,
+    indexes: Indexes,
+    expect_exception: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct AccountPre {
+    balance: String,
+    code: String,
+    nonce: String,
+    storage: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Transaction {
+    access_lists: Option<Vec<Option<parse::RawAccessList>>>,
+    data: Vec<String>,
+    gas_limit: Vec<String>,
+    max_priority_fee_per_gas: Option<String>,
+    max_fee_per_gas: Option<String>,
+    gas_price: Option<String>,
+    nonce: String,
+    secret_key: String,
+    to: String,
+    value: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct FilledStateTest {
+    env: TestEnv,
+    pre: HashMap<String, AccountPre>,
+    post: HashMap<String, Vec<PostState>>,
+    transaction: Transaction,
+}
+
+/// generates `StateTest` vectors from a filled ethereum json state test, as
+/// found in the `GeneralStateTests` folder. Since the code is already
+/// compiled, no compiler is required, and the post state is checked against
+/// the expected state root instead of a per-account expectation.
+pub fn load_json(path: &str, source: &str) -> Result<Vec<StateTest>> {
+    let mut state_tests = Vec::new();
+    let tests: HashMap<String, FilledStateTest> = serde_json::from_str(source)?;
+
+    for (test_name, test) in tests {
+        let posts: Vec<_> = test
+            .post
+            .iter()
+            .filter(|(fork, _)| MainnetFork::from_str(fork).ok() == Some(TEST_FORK))
+            .flat_map(|(_, posts)| posts)
+            .collect();
+        if posts.is_empty() {
+            continue;
+        }
+
+        let env = parse_env(&test.env)?;
+        let pre = parse_accounts_pre(&test.pre)?;
+
+        let to = parse::parse_to_address(&test.transaction.to)?;
+        let secret_key = parse::parse_bytes(&test.transaction.secret_key)?;
+        let from = secret_key_to_address(&SigningKey::from_slice(&secret_key)?);
+        let nonce = parse::parse_u64(&test.transaction.nonce)?;
+
+        let max_priority_fee_per_gas = test
+            .transaction
+            .max_priority_fee_per_gas
+            .map_or(Ok(None), |s| parse::parse_u256(&s).map(Some))?;
+        let max_fee_per_gas = test
+            .transaction
+            .max_fee_per_gas
+            .map_or(Ok(None), |s| parse::parse_u256(&s).map(Some))?;
+
+        // Set gas price to `min(max_priority_fee_per_gas + base_fee, max_fee_per_gas)` for
+        // EIP-1559 transaction.
+        // <https://github.com/ethereum/go-ethereum/blob/1485814f89d8206bb4a1c8e10a4a2893920f683a/core/state_transition.go#L167>
+        let gas_price = match &test.transaction.gas_price {
+            Some(gas_price) => parse::parse_u256(gas_price)?,
+            None => max_fee_per_gas
+                .unwrap_or_default()
+                .min(max_priority_fee_per_gas.unwrap_or_default() + env.current_base_fee),
+        };
+
+        let data_s: Vec<_> = test
+            .transaction
+            .data
+            .iter()
+            .map(|item| parse::parse_bytes(item))
+            .collect::<Result<_>>()?;
+
+        // access lists, if present, are defined per data entry
+        let access_list_s: Vec<_> = match &test.transaction.access_lists {
+            Some(access_lists) => access_lists
+                .iter()
+                .map(parse::parse_access_list)
+                .collect::<Result<_>>()?,
+            None => vec![None; data_s.len()],
+        };
+
+        let gas_limit_s: Vec<_> = test
+            .transaction
+            .gas_limit
+            .iter()
+            .map(|item| parse::parse_u64(item))
+            .collect::<Result<_>>()?;
+
+        let value_s: Vec<_> = test
+            .transaction
+            .value
+            .iter()
+            .map(|item| parse::parse_u256(item))
+            .collect::<Result<_>>()?;
+
+        for post in posts {
+            let Indexes {
+                data: idx_data,
+                gas: idx_gas,
+                value: idx_value,
+            } = post.indexes;
+
+            state_tests.push(StateTest {
+                path: path.to_string(),
+                id: format!("{test_name}_d{idx_data}_g{idx_gas}_v{idx_value}"),
+                env: env.clone(),
+                pre: pre.clone(),
+                result: HashMap::new(),
+                state_root: Some(parse::parse_hash(&post.hash)?),
+                from,
+                to,
+                secret_key: secret_key.clone(),
+                nonce,
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+                gas_price,
+                gas_limit: gas_limit_s[idx_gas],
+                value: value_s[idx_value],
+                data: data_s[idx_data].clone(),
+                access_list: access_list_s.get(idx_data).cloned().flatten(),
+                exception: post.expect_exception.is_some(),
+            });
+        }
+    }
+
+    Ok(state_tests)
+}
+
+/// parse a vector of address=>(storage,balance,code,nonce) entry
+pub(super) fn parse_accounts_pre(
+    accounts_pre: &HashMap<String, AccountPre>,
+) -> Result<BTreeMap<Address, Account>> {
+    let mut accounts = BTreeMap::new();
+    for (address, acc) in accounts_pre {
+        let address = parse::parse_address(address)?;
+        let mut storage = HashMap::new();
+        for (k, v) in &acc.storage {
+            storage.insert(parse::parse_u256(k)?, parse::parse_u256(v)?);
+        }
+        let account = Account {
+            address,
+            balance: parse::parse_u256(&acc.balance)?,
+            nonce: parse::parse_u64(&acc.nonce)?.into(),
+            code: parse::parse_bytes(&acc.code)?,
+            storage,
+        };
+        accounts.insert(address, account);
+    }
+    Ok(accounts)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::statetest::spec::{Env, DEFAULT_BASE_FEE};
+    use eth_types::{address, AccessList, AccessListItem, Bytes};
+
+    const JSON: &str = r#"
+{
+    "add11" : {
+        "env" : {
+            "currentCoinbase" : "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+            "currentDifficulty" : "0x020000",
+            "currentGasLimit" : "0xff112233445566",
+            "currentNumber" : "0x01",
+            "currentRandom" : "0x0000000000000000000000000000000000000000000000000000000000020000",
+            "currentTimestamp" : "0x03e8",
+            "previousHash" : "0x5e20a0453cecd065ea59c37ac63e079ee08998b6045136a8ce6635c7912ec0b6"
+        },
+        "post" : {
+            "London" : [
+                {
+                    "hash" : "0x0000000000000000000000000000000000000000000000000000000000000001",
+                    "indexes" : { "data" : 0, "gas" : 0, "value" : 0 },
+                    "logs" : "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"
+                }
+            ],
+            "Shanghai" : [
+                {
+                    "hash" : "0xe8010ce590f401c9d61fef8ab05bea9bcec24281b795e5868809bc4e515aa530",
+                    "indexes" : { "data" : 1, "gas" : 0, "value" : 0 },
+                    "logs" : "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+                    "txbytes" : "0x01"
+                }
+            ]
+        },
+        "pre" : {
+            "0x095e7baea6a6c7c4c2dfeb977efac326af552d87" : {
+                "balance" : "0x0de0b6b3a7640000",
+                "code" : "0x600160010160005500",
+                "nonce" : "0x00",
+                "storage" : {
+                    "0x01" : "0x02"
+                }
+            }
+        },
+        "transaction" : {
+            "accessLists" : [
+                null,
+                [
+                    {
+                        "address" : "0x009e7baea6a6c7c4c2dfeb977efac326af552d87",
+                        "storageKeys" : [
+                            "0x0000000000000000000000000000000000000000000000000000000000000000"
+                        ]
+                    }
+                ]
+            ],
+            "data" : [
+                "0x6001",
+                "0x6002"
+            ],
+            "gasLimit" : [
+                "0x061a80"
+            ],
+            "gasPrice" : "0x0a",
+            "nonce" : "0x00",
+            "secretKey" : "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8",
+            "sender" : "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b",
+            "to" : "0x095e7baea6a6c7c4c2dfeb977efac326af552d87",
+            "value" : [
+                "0x0186a0"
+            ]
+        }
+    }
+}
+"#;
+    #[test]
+    fn test_filled_json_parse() -> Result<()> {
+        let mut tests = load_json("test_path", JSON)?;
+        assert_eq!(tests.len(), 1);
+        let test = tests.remove(0);
+
+        let acc095e = Address::from_str("0x095e7baea6a6c7c4c2dfeb977efac326af552d87")?;
+
+        let expected = StateTest {
+            path: "test_path".to_string(),
+            id: "add11_d1_g0_v0".to_string(),
+            env: Env {
+                current_base_fee: U256::from(DEFAULT_BASE_FEE),
+                current_coinbase: Address::from_str("0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba")?,
+                current_difficulty: U256::from(131072u64),
+                current_gas_limit: 0xFF112233445566,
+                current_number: 1,
+                current_timestamp: 1000,
+                previous_hash: H256::from_str(
+                    "0x5e20a0453cecd065ea59c37ac63e079ee08998b6045136a8ce6635c7912ec0b6",
+                )?,
+            },
+            secret_key: Bytes::from(hex::decode(
+                "45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8",
+            )?),
+            from: Address::from_str("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b")?,
+            to: Some(acc095e),
+            gas_limit: 400000,
+            gas_price: U256::from(10u64),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            nonce: 0,
+            value: U256::from(100000u64),
+            data: Bytes::from(hex::decode("6002")?),
+            access_list: Some(AccessList(vec![AccessListItem {
+                address: address!("0x009e7baea6a6c7c4c2dfeb977efac326af552d87"),
+                storage_keys: vec![H256::zero()],
+            }])),
+            pre: BTreeMap::from([(
+                acc095e,
+                Account {
+                    address: acc095e,
+                    nonce: 0.into(),
+                    balance: U256::from(1000000000000000000u64),
+                    code: Bytes::from(hex::decode("600160010160005500")?),
+                    storage: HashMap::from([(U256::from(1u64), U256::from(2u64))]),
+                },
+            )]),
+            result: HashMap::new(),
+            state_root: Some(H256::from_str(
+                "0xe8010ce590f401c9d61fef8ab05bea9bcec24281b795e5868809bc4e515aa530",
+            )?),
+            exception: false,
+        };
+
+        assert_eq!(expected, test);
+
+        Ok(())
+    }
+}
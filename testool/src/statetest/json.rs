@@ -15,7 +15,7 @@ fn default_block_base_fee() -> String {
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct TestEnv {
+pub(super) struct TestEnv {
     #[serde(default = "default_block_base_fee")]
     current_base_fee: String,
     current_coinbase: String,
@@ -26,6 +26,20 @@ struct TestEnv {
     previous_hash: String,
 }
 
+/// parse env section
+pub(super) fn parse_env(env: &TestEnv) -> Result<Env> {
+    Ok(Env {
+        current_base_fee: parse::parse_u256(&env.current_base_fee)
+            .unwrap_or_else(|_| U256::from(DEFAULT_BASE_FEE)),
+        current_coinbase: parse::parse_address(&env.current_coinbase)?,
+        current_difficulty: parse::parse_u256(&env.current_difficulty)?,
+        current_gas_limit: parse::parse_u64(&env.current_gas_limit)?,
+        current_number: parse::parse_u64(&env.current_number)?,
+        current_timestamp: parse::parse_u64(&env.current_timestamp)?,
+        previous_hash: parse::parse_hash(&env.previous_hash)?,
+    })
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct Indexes {
     data: serde_json::value::Value,
@@ -113,7 +127,7 @@ impl<'a> JsonStateTestBuilder<'a> {
         let tests: HashMap<String, JsonStateTest> = serde_json::from_str(source)?;
 
         for (test_name, test) in tests {
-            let env = Self::parse_env(&test.env)?;
+            let env = parse_env(&test.env)?;
             let pre = self.parse_accounts_pre(&test.pre)?;
 
             let to = parse::parse_to_address(&test.transaction.to)?;
@@ -208,6 +222,7 @@ impl<'a> JsonStateTestBuilder<'a> {
                                 env: env.clone(),
                                 pre: pre.clone(),
                                 result: result.clone(),
+                                state_root: None,
                                 from,
                                 to,
                                 secret_key: secret_key.clone(),
@@ -230,20 +245,6 @@ impl<'a> JsonStateTestBuilder<'a> {
         Ok(state_tests)
     }
 
-    /// parse env section
-    fn parse_env(env: &TestEnv) -> Result<Env> {
-        Ok(Env {
-            current_base_fee: parse::parse_u256(&env.current_base_fee)
-                .unwrap_or_else(|_| U256::from(DEFAULT_BASE_FEE)),
-            current_coinbase: parse::parse_address(&env.current_coinbase)?,
-            current_difficulty: parse::parse_u256(&env.current_difficulty)?,
-            current_gas_limit: parse::parse_u64(&env.current_gas_limit)?,
-            current_number: parse::parse_u64(&env.current_number)?,
-            current_timestamp: parse::parse_u64(&env.current_timestamp)?,
-            previous_hash: parse::parse_hash(&env.previous_hash)?,
-        })
-    }
-
     /// parse a vector of address=>(storage,balance,code,nonce) entry
     fn parse_accounts_pre(
         &mut self,
@@ -480,6 +481,7 @@ mod test {
                     storage: HashMap::from([(U256::zero(), U256::from(2u64))]),
                 },
             )]),
+            state_root: None,
             exception: false,
         };
 
@@ -225,7 +225,7 @@ fn parse_call_bytes(compiler: &Compiler, tags: HashMap<String, String>) -> Resul
 }
 
 // Parse access list
-fn parse_access_list(raw_access_list: &Option<RawAccessList>) -> Result<Option<AccessList>> {
+pub fn parse_access_list(raw_access_list: &Option<RawAccessList>) -> Result<Option<AccessList>> {
     if let Some(raw_access_list) = raw_access_list {
         let mut items = Vec::with_capacity(raw_access_list.len());
         for raw in raw_access_list {
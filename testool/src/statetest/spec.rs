@@ -74,6 +74,8 @@ pub struct StateTest {
     pub access_list: Option<AccessList>,
     pub pre: BTreeMap<Address, Account>,
     pub result: StateTestResult,
+    /// expected state root after the tx, if known
+    pub state_root: Option<H256>,
     pub exception: bool,
 }
 
@@ -142,6 +144,9 @@ impl std::fmt::Display for StateTest {
         table.add_row(row!["data", format(&hex::encode(&self.data), "")]);
         table.add_row(row!["access_list", format!("{:?}", self.access_list)]);
         table.add_row(row!["exception", self.exception]);
+        if let Some(state_root) = self.state_root {
+            table.add_row(row!["state_root", format!("{state_root:?}")]);
+        }
 
         let mut addrs: Vec<_> = self.pre.keys().collect();
         addrs.extend(self.result.keys());
@@ -311,6 +316,7 @@ impl StateTest {
             access_list: None,
             pre,
             result: HashMap::new(),
+            state_root: None,
             exception: false,
         };
 
@@ -1,4 +1,8 @@
-use super::{executor::run_test, CircuitsConfig, JsonStateTestBuilder, Results, StateTest};
+use super::{
+    blockchain,
+    executor::{run_block_test, run_test, StateTestError},
+    filled, BlockchainTest, CircuitsConfig, JsonStateTestBuilder, Results, StateTest,
+};
 use crate::{
     compiler::Compiler,
     config::{Config, TestSuite},
@@ -11,28 +15,34 @@ use anyhow::{Context, Result};
 use rayon::prelude::*;
 use std::{
     panic::AssertUnwindSafe,
+    path::PathBuf,
     sync::{Arc, RwLock},
 };
 
-pub fn load_statetests_suite(
-    path: &str,
-    config: Config,
-    compiler: Compiler,
-) -> Result<Vec<StateTest>> {
+/// files matching the `path` glob which are not skipped in the `config`
+fn suite_files(path: &str, config: &Config) -> Result<Vec<PathBuf>> {
     let skip_paths: Vec<&String> = config.skip_paths.iter().flat_map(|t| &t.paths).collect();
-    let skip_tests: Vec<&String> = config.skip_tests.iter().flat_map(|t| &t.tests).collect();
 
-    let files = glob::glob(path)
+    Ok(glob::glob(path)
         .context("failed to read glob")?
         .filter_map(|v| v.ok())
         .filter(|f| {
             !skip_paths
                 .iter()
                 .any(|e| f.as_path().to_string_lossy().contains(*e))
-        });
+        })
+        .collect())
+}
+
+pub fn load_statetests_suite(
+    path: &str,
+    config: Config,
+    compiler: Compiler,
+) -> Result<Vec<StateTest>> {
+    let skip_tests: Vec<&String> = config.skip_tests.iter().flat_map(|t| &t.tests).collect();
 
     let mut tests = Vec::new();
-    for file in files {
+    for file in suite_files(path, &config)? {
         if let Some(ext) = file.extension() {
             let ext = &*ext.to_string_lossy();
             if !["yml", "json"].contains(&ext) {
@@ -43,7 +53,10 @@ pub fn load_statetests_suite(
             log::debug!(target: "testool", "Reading file {:?}", file);
             let mut tcs = match ext {
                 "yml" => YamlStateTestBuilder::new(&compiler).load_yaml(&path, &src)?,
-                "json" => JsonStateTestBuilder::new(&compiler).load_json(&path, &src)?,
+                "json" if path.ends_with("Filler.json") => {
+                    JsonStateTestBuilder::new(&compiler).load_json(&path, &src)?
+                }
+                "json" => filled::load_json(&path, &src)?,
                 _ => unreachable!(),
             };
 
@@ -54,17 +67,69 @@ pub fn load_statetests_suite(
     Ok(tests)
 }
 
+pub fn load_blocktests_suite(path: &str, config: Config) -> Result<Vec<BlockchainTest>> {
+    let skip_tests: Vec<&String> = config.skip_tests.iter().flat_map(|t| &t.tests).collect();
+
+    let mut tests = Vec::new();
+    for file in suite_files(path, &config)? {
+        if file.extension().map_or(true, |ext| ext != "json") {
+            continue;
+        }
+        let path = file.as_path().to_string_lossy();
+        let src = std::fs::read_to_string(&file)?;
+        log::debug!(target: "testool", "Reading file {:?}", file);
+        let mut tcs = blockchain::load_json(&path, &src)?;
+
+        tcs.retain(|v| !skip_tests.contains(&&v.id));
+        tests.append(&mut tcs);
+    }
+    Ok(tests)
+}
+
 pub fn run_statetests_suite(
     tcs: Vec<StateTest>,
     circuits_config: &CircuitsConfig,
     suite: &TestSuite,
     results: &mut Results,
+) -> Result<()> {
+    run_suite(
+        tcs,
+        |tc| (tc.id.clone(), tc.path.clone()),
+        |tc| run_test(tc, suite.clone(), circuits_config.clone()),
+        results,
+    )
+}
+
+pub fn run_blocktests_suite(
+    tcs: Vec<BlockchainTest>,
+    circuits_config: &CircuitsConfig,
+    suite: &TestSuite,
+    results: &mut Results,
+) -> Result<()> {
+    run_suite(
+        tcs,
+        |tc| (tc.id.clone(), tc.path.clone()),
+        |tc| run_block_test(tc, suite.clone(), circuits_config.clone()),
+        results,
+    )
+}
+
+/// runs the tests with `run` in parallel, and inserts their results keyed by
+/// the `(id, path)` of each test
+fn run_suite<T: Clone + Send>(
+    tcs: Vec<T>,
+    id_path: impl Fn(&T) -> (String, String) + Sync,
+    run: impl Fn(T) -> Result<(), StateTestError> + Sync,
+    results: &mut Results,
 ) -> Result<()> {
     // Filter already cached entries
     let all_test_count = tcs.len();
-    let tcs: Vec<StateTest> = tcs
+    let tcs: Vec<T> = tcs
         .into_iter()
-        .filter(|t| !results.contains(&format!("{}#{}", t.id, t.path)))
+        .filter(|t| {
+            let (id, path) = id_path(t);
+            !results.contains(&format!("{id}#{path}"))
+        })
         .collect();
 
     log::info!(
@@ -78,7 +143,7 @@ pub fn run_statetests_suite(
     // for each test
     let test_count = tcs.len();
     tcs.into_par_iter().for_each(|ref tc| {
-        let (test_id, path) = (tc.id.clone(), tc.path.clone());
+        let (test_id, path) = id_path(tc);
         std::panic::set_hook(Box::new(|_info| {}));
 
         log::debug!(
@@ -89,9 +154,7 @@ pub fn run_statetests_suite(
             test_id,
             path,
         );
-        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
-            run_test(tc.clone(), suite.clone(), circuits_config.clone())
-        }));
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| run(tc.clone())));
 
         // handle panic
         let result = match result {
@@ -193,6 +193,7 @@ impl<'a> YamlStateTestBuilder<'a> {
                                 env: env.clone(),
                                 pre: pre.clone(),
                                 result: result.clone(),
+                                state_root: None,
                                 from,
                                 secret_key: secret_key.clone(),
                                 to,
@@ -759,6 +760,7 @@ arith:
                     storage: HashMap::new(),
                 },
             )]),
+            state_root: None,
             exception: false,
         };
 
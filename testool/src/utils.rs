@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use anyhow::{bail, Result};
-use eth_types::{bytecode::OpcodeWithData, Bytecode, GethExecTrace, U256};
+use eth_types::{bytecode::OpcodeWithData, evm_types::Hardfork, Bytecode, GethExecTrace, U256};
 use log::{error, info};
 use prettytable::Table;
 use std::process::{Command, Stdio};
@@ -59,6 +59,18 @@ impl FromStr for MainnetFork {
 }
 
 impl MainnetFork {
+    /// The hardfork of the circuits and the tracer with the rules of this
+    /// fork. The forks before London are not supported by the circuits.
+    pub fn hardfork(&self) -> Hardfork {
+        match self {
+            Self::Shanghai => Hardfork::Shanghai,
+            Self::Merge | Self::GrayGlacier | Self::ArrowGlacier | Self::Altair | Self::London => {
+                Hardfork::London
+            }
+            fork => unimplemented!("{fork:?} is not supported by the circuits"),
+        }
+    }
+
     pub fn in_network_range(expect: &[String]) -> Result<bool, anyhow::Error> {
         let in_network = if expect.is_empty() {
             true
@@ -16,8 +16,10 @@ use itertools::Itertools;
 use mock::MOCK_ACCOUNTS;
 use std::{array, env, iter};
 use zkevm_circuits::{
+    blake2f_circuit::{Blake2fCircuitConfig, Blake2fCircuitConfigArgs},
     bytecode_circuit::{BytecodeCircuitConfig, BytecodeCircuitConfigArgs},
     copy_circuit::{CopyCircuitConfig, CopyCircuitConfigArgs},
+    ecc_circuit::{EccCircuitConfig, EccCircuitConfigArgs},
     evm_circuit::{
         param::{
             LOOKUP_CONFIG, N_COPY_COLUMNS, N_PHASE1_COLUMNS, N_PHASE2_COLUMNS, N_U16_LOOKUPS,
@@ -27,12 +29,16 @@ use zkevm_circuits::{
         EvmCircuit, EvmCircuitConfig, EvmCircuitConfigArgs,
     },
     exp_circuit::ExpCircuitConfig,
-    keccak_circuit::{KeccakCircuitConfig, KeccakCircuitConfigArgs},
+    keccak_circuit::{KeccakCircuitConfig, KeccakCircuitConfigArgs, KeccakCircuitParams},
+    modexp_circuit::{ModExpCircuitConfig, ModExpCircuitConfigArgs},
     pi_circuit::{PiCircuitConfig, PiCircuitConfigArgs},
+    ripemd160_circuit::{Ripemd160CircuitConfig, Ripemd160CircuitConfigArgs},
+    sha256_circuit::{Sha256CircuitConfig, Sha256CircuitConfigArgs},
     state_circuit::{StateCircuitConfig, StateCircuitConfigArgs},
     table::{
-        BlockTable, BytecodeTable, ChunkCtxTable, CopyTable, ExpTable, KeccakTable, MptTable,
-        RwTable, SigTable, TxTable, UXTable, WdTable,
+        Blake2fTable, BlockTable, BytecodeTable, ChunkCtxTable, CopyTable, EccTable, ExpTable,
+        KeccakTable, ModExpTable, MptTable, Ripemd160Table, RwTable, Sha256Table, SigTable,
+        TxTable, UXTable, WdTable,
     },
     tx_circuit::{TxCircuitConfig, TxCircuitConfigArgs},
     util::{chunk_ctx::ChunkContextConfig, Challenges, SubCircuitConfig},
@@ -54,12 +60,7 @@ fn main() {
 /// Prints the stats of EVM circuit per execution state.
 fn evm_states_stats() {
     print_circuit_stats_by_states(
-        |state| {
-            !matches!(
-                state,
-                ExecutionState::ErrorInvalidOpcode | ExecutionState::SELFDESTRUCT
-            )
-        },
+        |state| !matches!(state, ExecutionState::ErrorInvalidOpcode),
         |opcode| match opcode {
             OpcodeId::RETURNDATACOPY => {
                 bytecode! {
@@ -88,12 +89,7 @@ fn evm_states_stats() {
 /// Prints the stats of State circuit per execution state.
 fn state_states_stats() {
     print_circuit_stats_by_states(
-        |state| {
-            !matches!(
-                state,
-                ExecutionState::ErrorInvalidOpcode | ExecutionState::SELFDESTRUCT
-            )
-        },
+        |state| !matches!(state, ExecutionState::ErrorInvalidOpcode),
         bytecode_prefix_op_big_rws,
         |block, _, step_index| {
             let step = &block.txs[0].steps()[step_index];
@@ -225,13 +221,23 @@ fn get_exec_steps_occupancy() {
         LOOKUP_CONFIG[7].1,
         sig_table,
         LOOKUP_CONFIG[8].1,
+        sha256_table,
+        LOOKUP_CONFIG[9].1,
+        ripemd160_table,
+        LOOKUP_CONFIG[10].1,
+        blake2f_table,
+        LOOKUP_CONFIG[11].1,
+        modexp_table,
+        LOOKUP_CONFIG[12].1,
+        ecc_table,
+        LOOKUP_CONFIG[13].1,
         chunk_ctx_table,
-        LOOKUP_CONFIG[9].1
+        LOOKUP_CONFIG[14].1
     );
 }
 
 #[allow(unused_variables)]
-fn record_stats<F: eth_types::Field>(
+fn record_stats<F: eth_types::Field + halo2_base::utils::ScalarField>(
     stats: &mut StatsCollection<F>,
     meta: &mut ConstraintSystem<F>,
 ) {
@@ -263,6 +269,16 @@ fn record_stats<F: eth_types::Field>(
     stats.record_shared("keccak_table", meta);
     let sig_table = SigTable::construct(meta);
     stats.record_shared("sig_table", meta);
+    let sha256_table = Sha256Table::construct(meta);
+    stats.record_shared("sha256_table", meta);
+    let ripemd160_table = Ripemd160Table::construct(meta);
+    stats.record_shared("ripemd160_table", meta);
+    let blake2f_table = Blake2fTable::construct(meta);
+    stats.record_shared("blake2f_table", meta);
+    let modexp_table = ModExpTable::construct(meta);
+    stats.record_shared("modexp_table", meta);
+    let ecc_table = EccTable::construct(meta);
+    stats.record_shared("ecc_table", meta);
     let u8_table = UXTable::construct(meta);
     stats.record_shared("u8_table", meta);
     let u10_table = UXTable::construct(meta);
@@ -289,10 +305,53 @@ fn record_stats<F: eth_types::Field>(
         KeccakCircuitConfigArgs {
             keccak_table: keccak_table.clone(),
             challenges: challenges.clone(),
+            params: KeccakCircuitParams::default(),
         },
     );
     stats.record("keccak", meta);
 
+    let sha256_circuit = Sha256CircuitConfig::new(
+        meta,
+        Sha256CircuitConfigArgs {
+            sha256_table: sha256_table.clone(),
+            challenges: challenges.clone(),
+        },
+    );
+    stats.record("sha256", meta);
+    let ripemd160_circuit = Ripemd160CircuitConfig::new(
+        meta,
+        Ripemd160CircuitConfigArgs {
+            ripemd160_table: ripemd160_table.clone(),
+            challenges: challenges.clone(),
+        },
+    );
+    stats.record("ripemd160", meta);
+    let blake2f_circuit = Blake2fCircuitConfig::new(
+        meta,
+        Blake2fCircuitConfigArgs {
+            blake2f_table: blake2f_table.clone(),
+            challenges: challenges.clone(),
+        },
+    );
+    stats.record("blake2f", meta);
+    let modexp_circuit = ModExpCircuitConfig::new(
+        meta,
+        ModExpCircuitConfigArgs {
+            modexp_table: modexp_table.clone(),
+            u8_table,
+            challenges: challenges.clone(),
+        },
+    );
+    stats.record("modexp", meta);
+    let ecc_circuit = EccCircuitConfig::new(
+        meta,
+        EccCircuitConfigArgs {
+            ecc_table,
+            challenges: challenges.clone(),
+        },
+    );
+    stats.record("ecc", meta);
+
     let pi_circuit = PiCircuitConfig::new(
         meta,
         PiCircuitConfigArgs {
@@ -369,6 +428,11 @@ fn record_stats<F: eth_types::Field>(
             u8_table,
             u16_table,
             sig_table,
+            sha256_table,
+            ripemd160_table,
+            blake2f_table,
+            modexp_table,
+            ecc_table,
             chunk_ctx_config,
             feature_config,
         },
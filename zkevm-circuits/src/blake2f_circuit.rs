@@ -0,0 +1,691 @@
+//! The BLAKE2F circuit implementation.
+//!
+//! The compression function F of BLAKE2b (EIP-152) is computed in units of
+//! `NUM_ROWS_PER_UNIT` rows: a header unit that decomposes the input and
+//! initializes the state, a unit per round, each row of which applies the
+//! mixing function G once, and a footer unit that computes the output. All
+//! words are stored as bits, one bit per column.
+//!
+//! Every row of a round only stores the words computed by its G, so the
+//! inputs of G are read from the rows that last updated them, at fixed
+//! rotations. The header stores the initial state at the positions the last
+//! 4 rows of a round store it, so that the first round and the footer don't
+//! need to know whether they follow a header or a round.
+mod param;
+pub(crate) mod util;
+mod witness;
+
+#[cfg(any(test, feature = "test-circuits"))]
+mod dev;
+#[cfg(test)]
+mod test;
+#[cfg(feature = "test-circuits")]
+pub use dev::Blake2fCircuit as TestBlake2fCircuit;
+
+use self::{
+    param::*,
+    util::{
+        decode, query_bits, query_word, rotate_right, state_word_rotation, to_le_bytes, xor2,
+        xor_constant, Blake2fInput,
+    },
+    witness::{multi_blake2f, num_units, Blake2fRow},
+};
+use crate::{
+    evm_circuit::util::constraint_builder::{BaseConstraintBuilder, ConstrainBuilderCommon},
+    table::{Blake2fTable, HashTable, LookupTable},
+    util::{Challenges, SubCircuit, SubCircuitConfig},
+    witness::{Block, Chunk},
+};
+use eth_types::Field;
+use gadgets::util::{not, select, sum, xor, Expr};
+use halo2_proofs::{
+    circuit::{Layouter, Region, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Fixed},
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+/// Blake2fCircuitConfig
+#[derive(Clone, Debug)]
+pub struct Blake2fCircuitConfig<F> {
+    q_enable: Column<Fixed>,
+    q_first: Column<Fixed>,
+    q_rows: [Column<Fixed>; NUM_ROWS_PER_UNIT],
+    a1: [Column<Advice>; NUM_BITS_PER_WORD_SUM3],
+    c1: [Column<Advice>; NUM_BITS_PER_WORD_SUM2],
+    a2: [Column<Advice>; NUM_BITS_PER_WORD_SUM3],
+    b2: [Column<Advice>; NUM_BITS_PER_WORD],
+    c2: [Column<Advice>; NUM_BITS_PER_WORD_SUM2],
+    d2: [Column<Advice>; NUM_BITS_PER_WORD],
+    h: [Column<Advice>; NUM_HASH_WORDS],
+    m: [Column<Advice>; NUM_MESSAGE_WORDS],
+    is_header: Column<Advice>,
+    is_round: Column<Advice>,
+    is_footer: Column<Advice>,
+    round: Column<Advice>,
+    sigmas: [Column<Advice>; NUM_SIGMAS],
+    is_last: Column<Advice>,
+    diff_inv: Column<Advice>,
+    /// The columns for other circuits to lookup BLAKE2F results
+    pub blake2f_table: Blake2fTable,
+    _marker: PhantomData<F>,
+}
+
+/// Circuit configuration arguments
+pub struct Blake2fCircuitConfigArgs<F: Field> {
+    /// Blake2fTable
+    pub blake2f_table: Blake2fTable,
+    /// Challenges randomness
+    pub challenges: Challenges<Expression<F>>,
+}
+
+impl<F: Field> SubCircuitConfig<F> for Blake2fCircuitConfig<F> {
+    type ConfigArgs = Blake2fCircuitConfigArgs<F>;
+
+    /// Return a new Blake2fCircuitConfig
+    fn new(
+        meta: &mut ConstraintSystem<F>,
+        Self::ConfigArgs {
+            blake2f_table,
+            challenges,
+        }: Self::ConfigArgs,
+    ) -> Self {
+        let q_enable = meta.fixed_column();
+        let q_first = meta.fixed_column();
+        let q_rows = [(); NUM_ROWS_PER_UNIT].map(|_| meta.fixed_column());
+        let a1 = [(); NUM_BITS_PER_WORD_SUM3].map(|_| meta.advice_column());
+        let c1 = [(); NUM_BITS_PER_WORD_SUM2].map(|_| meta.advice_column());
+        let a2 = [(); NUM_BITS_PER_WORD_SUM3].map(|_| meta.advice_column());
+        let b2 = [(); NUM_BITS_PER_WORD].map(|_| meta.advice_column());
+        let c2 = [(); NUM_BITS_PER_WORD_SUM2].map(|_| meta.advice_column());
+        let d2 = [(); NUM_BITS_PER_WORD].map(|_| meta.advice_column());
+        let h = [(); NUM_HASH_WORDS].map(|_| meta.advice_column());
+        let m = [(); NUM_MESSAGE_WORDS].map(|_| meta.advice_column());
+        let is_header = meta.advice_column();
+        let is_round = meta.advice_column();
+        let is_footer = meta.advice_column();
+        let round = meta.advice_column();
+        let sigmas = [(); NUM_SIGMAS].map(|_| meta.advice_column());
+        let is_last = meta.advice_column();
+        let diff_inv = meta.advice_column();
+        let is_enabled = blake2f_table.is_enabled;
+        let rounds = blake2f_table.rounds;
+        let input_rlc = blake2f_table.input_rlc;
+        let output_rlc = blake2f_table.output_rlc;
+        let q_last_row = q_rows[NUM_ROWS_PER_UNIT - 1];
+        let r = challenges.keccak_input();
+        // The new values of (a, b, c, d) computed in a row
+        let outputs = [&a2[..], &b2[..], &c2[..], &d2[..]];
+
+        meta.create_gate("boolean checks", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            for column in a1
+                .iter()
+                .chain(c1.iter())
+                .chain(a2.iter())
+                .chain(b2.iter())
+                .chain(c2.iter())
+                .chain(d2.iter())
+                .chain([is_header, is_round, is_footer, is_last].iter())
+            {
+                cb.require_boolean("bit", meta.query_advice(*column, Rotation::cur()));
+            }
+            cb.require_boolean(
+                "a unit has at most one kind",
+                sum::expr(
+                    [is_header, is_round, is_footer]
+                        .map(|column| meta.query_advice(column, Rotation::cur())),
+                ),
+            );
+            cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+        });
+
+        // The kind of the unit, the round counter and the message schedule are
+        // the same in all rows of a unit
+        meta.create_gate("carry unit data", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            for column in [is_header, is_round, is_footer, round, is_last, diff_inv]
+                .iter()
+                .chain(sigmas.iter())
+            {
+                cb.require_equal(
+                    "unit data stays the same",
+                    meta.query_advice(*column, Rotation::cur()),
+                    meta.query_advice(*column, Rotation::prev()),
+                );
+            }
+            cb.gate(
+                meta.query_fixed(q_enable, Rotation::cur())
+                    - meta.query_fixed(q_rows[0], Rotation::cur()),
+            )
+        });
+
+        // A header or a round is followed by a round, unless it's the last
+        // one, in which case it's followed by the footer. Any other unit is
+        // followed by a header or an empty unit.
+        meta.create_gate("unit transition", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let not_first = not::expr(meta.query_fixed(q_first, Rotation::cur()));
+            let prev_is_round = not_first.clone() * meta.query_advice(is_round, Rotation::prev());
+            let prev_is_active =
+                prev_is_round.clone() + not_first * meta.query_advice(is_header, Rotation::prev());
+            let prev_is_last = meta.query_advice(is_last, Rotation::prev());
+            cb.require_equal(
+                "is_round",
+                meta.query_advice(is_round, Rotation::cur()),
+                prev_is_active.clone() * not::expr(prev_is_last.clone()),
+            );
+            cb.require_equal(
+                "is_footer",
+                meta.query_advice(is_footer, Rotation::cur()),
+                prev_is_active * prev_is_last,
+            );
+            cb.require_equal(
+                "the round counter increments from round to round",
+                meta.query_advice(round, Rotation::cur()),
+                prev_is_round.clone() * (meta.query_advice(round, Rotation::prev()) + 1.expr()),
+            );
+            for (idx, sigma) in sigmas.iter().enumerate() {
+                let prev_sigma = sigmas[(idx + NUM_SIGMAS - 1) % NUM_SIGMAS];
+                cb.require_equal(
+                    "the message schedule advances from round to round",
+                    meta.query_advice(*sigma, Rotation::cur()),
+                    select::expr(
+                        prev_is_round.clone(),
+                        meta.query_advice(prev_sigma, Rotation::prev()),
+                        (idx == 0).expr(),
+                    ),
+                );
+            }
+            cb.gate(meta.query_fixed(q_rows[0], Rotation::cur()))
+        });
+
+        // is_last = (rounds - (round + 1)) == 0 in a round, rounds == 0 in a header
+        meta.create_gate("is_last", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let is_header = meta.query_advice(is_header, Rotation::cur());
+            let is_round = meta.query_advice(is_round, Rotation::cur());
+            let diff = meta.query_advice(rounds, Rotation::cur())
+                - is_round.clone() * (meta.query_advice(round, Rotation::cur()) + 1.expr());
+            let is_last = meta.query_advice(is_last, Rotation::cur());
+            cb.require_equal(
+                "is_last",
+                is_last.clone(),
+                1.expr() - diff.clone() * meta.query_advice(diff_inv, Rotation::cur()),
+            );
+            cb.require_zero("is_last only when diff is zero", diff * is_last);
+            cb.gate(meta.query_fixed(q_last_row, Rotation::cur()) * (is_header + is_round))
+        });
+
+        meta.create_gate("first row", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            cb.require_zero("rounds", meta.query_advice(rounds, Rotation::cur()));
+            cb.require_zero("input_rlc", meta.query_advice(input_rlc, Rotation::cur()));
+            cb.gate(meta.query_fixed(q_first, Rotation::cur()))
+        });
+
+        // The data of the input is set in the last row of the header and stays
+        // the same until the next header
+        meta.create_gate("carry input data", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            for column in [rounds, input_rlc].iter().chain(h.iter()).chain(m.iter()) {
+                cb.require_equal(
+                    "input data stays the same",
+                    meta.query_advice(*column, Rotation::cur()),
+                    meta.query_advice(*column, Rotation::prev()),
+                );
+            }
+            cb.gate(
+                meta.query_fixed(q_enable, Rotation::cur())
+                    - meta.query_fixed(q_first, Rotation::cur())
+                    - meta.query_fixed(q_last_row, Rotation::cur())
+                        * meta.query_advice(is_header, Rotation::cur()),
+            )
+        });
+
+        // The header decomposes the input. The first 4 rows contain the message
+        // words in (a1, c1, a2, b2), the first row also contains the number of
+        // rounds in c2 and the final block flag in d2. The last 4 rows contain
+        // the initial state.
+        meta.create_gate("header", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let last_row = NUM_ROWS_PER_UNIT as i32 - 1;
+
+            let mut message_bytes = Vec::new();
+            for (idx, m) in m.iter().enumerate() {
+                let columns = [&a1[..], &c1[..], &a2[..], &b2[..]][idx % 4];
+                let bits = query_word(meta, columns, (idx / 4) as i32 - last_row);
+                cb.require_equal(
+                    "message word",
+                    meta.query_advice(*m, Rotation::cur()),
+                    decode::expr(&bits),
+                );
+                message_bytes.extend(to_le_bytes(&bits));
+            }
+
+            let rounds_bits = query_bits(meta, &c2[..NUM_ROUNDS_BYTES * 8], -last_row);
+            cb.require_equal(
+                "rounds",
+                meta.query_advice(rounds, Rotation::cur()),
+                decode::expr(&rounds_bits),
+            );
+            let f_bits = query_word(meta, &d2, -last_row);
+            for bit in f_bits.iter().skip(1) {
+                cb.require_zero("the final block flag is a bit", bit.clone());
+            }
+            let f = f_bits[0].clone();
+
+            // The initial state is (h, IV) with v[12] ^= t0, v[13] ^= t1 and
+            // v[14] = !v[14] for the final block
+            let mut state = Vec::new();
+            for word in 0..NUM_WORDS_PER_STATE {
+                // The rotation is given from the first row of the next unit
+                let rot = state_word_rotation(word, 0, false) + 1;
+                state.push(query_word(meta, outputs[word / 4], rot));
+            }
+            let mut hash_bytes = Vec::new();
+            for (idx, h) in h.iter().enumerate() {
+                cb.require_equal(
+                    "hash word",
+                    meta.query_advice(*h, Rotation::cur()),
+                    decode::expr(&state[idx]),
+                );
+                hash_bytes.extend(to_le_bytes(&state[idx]));
+            }
+            for (idx, iv) in IV.iter().enumerate() {
+                let word = decode::expr(&state[NUM_HASH_WORDS + idx]);
+                match idx {
+                    4 | 5 => {}
+                    6 => cb.require_equal(
+                        "v[14]",
+                        word,
+                        select::expr(f.clone(), (!iv).expr(), (*iv).expr()),
+                    ),
+                    _ => cb.require_equal("IV", word, (*iv).expr()),
+                }
+            }
+            let t_bytes: Vec<_> = [(12, IV[4]), (13, IV[5])]
+                .iter()
+                .flat_map(|(word, iv)| to_le_bytes(&xor_constant(&state[*word], *iv)))
+                .collect();
+
+            // The input bytes are rounds (big-endian), h, m, t and f
+            let input_bytes = rounds_bits
+                .chunks(8)
+                .rev()
+                .map(decode::expr)
+                .chain(hash_bytes)
+                .chain(message_bytes)
+                .chain(t_bytes)
+                .chain(std::iter::once(f));
+            cb.require_equal(
+                "input_rlc",
+                meta.query_advice(input_rlc, Rotation::cur()),
+                input_bytes.fold(0.expr(), |acc, byte| acc * r.clone() + byte),
+            );
+            cb.gate(
+                meta.query_fixed(q_last_row, Rotation::cur())
+                    * meta.query_advice(is_header, Rotation::cur()),
+            )
+        });
+
+        // The mixing function G:
+        //   a1 = a + b + x
+        //   d1 = (d ^ a1) >>> 32
+        //   c1 = c + d1
+        //   b1 = (b ^ c1) >>> 24
+        //   a2 = a1 + b1 + y
+        //   d2 = (d1 ^ a2) >>> 16
+        //   c2 = c1 + d2
+        //   b2 = (b1 ^ c2) >>> 63
+        // where d1 and b1 are not stored but kept as expressions.
+        for (row, q_row) in q_rows.iter().enumerate() {
+            meta.create_gate("G", |meta| {
+                let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+                let [a, b, c, d] = [0, 1, 2, 3].map(|idx| {
+                    let word = G_WORDS[row][idx];
+                    query_word(meta, outputs[idx], state_word_rotation(word, row, true))
+                });
+                let sigmas = sigmas.map(|sigma| meta.query_advice(sigma, Rotation::cur()));
+                let [x, y] = [0, 1].map(|idx| {
+                    sum::expr(sigmas.iter().zip(SIGMA).map(|(sigma_selector, sigma)| {
+                        sigma_selector.clone()
+                            * meta.query_advice(m[sigma[2 * row + idx]], Rotation::cur())
+                    }))
+                });
+
+                let a1 = query_bits(meta, &a1, 0);
+                cb.require_equal(
+                    "a1 = a + b + x",
+                    decode::expr(&a1),
+                    decode::expr(&a) + decode::expr(&b) + x,
+                );
+                let d1 = rotate_right(&xor2(&d, &a1[..NUM_BITS_PER_WORD]), 32);
+                let c1 = query_bits(meta, &c1, 0);
+                cb.require_equal(
+                    "c1 = c + d1",
+                    decode::expr(&c1),
+                    decode::expr(&c) + decode::expr(&d1),
+                );
+                let b1 = rotate_right(&xor2(&b, &c1[..NUM_BITS_PER_WORD]), 24);
+                let a2 = query_bits(meta, &a2, 0);
+                cb.require_equal(
+                    "a2 = a1 + b1 + y",
+                    decode::expr(&a2),
+                    decode::expr(&a1[..NUM_BITS_PER_WORD]) + decode::expr(&b1) + y,
+                );
+                let d2 = query_bits(meta, &d2, 0);
+                cb.require_equal(
+                    "d2 = (d1 ^ a2) >>> 16",
+                    decode::expr(&d2),
+                    decode::expr(&rotate_right(&xor2(&d1, &a2[..NUM_BITS_PER_WORD]), 16)),
+                );
+                let c2 = query_bits(meta, &c2, 0);
+                cb.require_equal(
+                    "c2 = c1 + d2",
+                    decode::expr(&c2),
+                    decode::expr(&c1[..NUM_BITS_PER_WORD]) + decode::expr(&d2),
+                );
+                cb.require_equal(
+                    "b2 = (b1 ^ c2) >>> 63",
+                    decode::expr(&query_bits(meta, &b2, 0)),
+                    decode::expr(&rotate_right(&xor2(&b1, &c2[..NUM_BITS_PER_WORD]), 63)),
+                );
+                cb.gate(
+                    meta.query_fixed(*q_row, Rotation::cur())
+                        * meta.query_advice(is_round, Rotation::cur()),
+                )
+            });
+        }
+
+        // Row i of the footer computes the output word h[i] ^ v[i] ^ v[i + 8],
+        // with the bits of h[i] in a1
+        for (row, q_row) in q_rows.iter().enumerate() {
+            meta.create_gate("footer", |meta| {
+                let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+                let h_bits = query_word(meta, &a1, 0);
+                cb.require_equal(
+                    "hash word",
+                    meta.query_advice(h[row], Rotation::cur()),
+                    decode::expr(&h_bits),
+                );
+                let [v_lo, v_hi] = [row, row + 8].map(|word| {
+                    query_word(
+                        meta,
+                        outputs[word / 4],
+                        state_word_rotation(word, row, false),
+                    )
+                });
+                let output_bytes = to_le_bytes(
+                    &h_bits
+                        .iter()
+                        .zip(xor2(&v_lo, &v_hi))
+                        .map(|(h, v)| xor::expr(h.clone(), v))
+                        .collect::<Vec<_>>(),
+                );
+                let prev_output_rlc = if row == 0 {
+                    0.expr()
+                } else {
+                    meta.query_advice(output_rlc, Rotation::prev())
+                };
+                cb.require_equal(
+                    "output_rlc",
+                    meta.query_advice(output_rlc, Rotation::cur()),
+                    output_bytes
+                        .into_iter()
+                        .fold(prev_output_rlc, |acc, byte| acc * r.clone() + byte),
+                );
+                cb.gate(
+                    meta.query_fixed(*q_row, Rotation::cur())
+                        * meta.query_advice(is_footer, Rotation::cur()),
+                )
+            });
+        }
+
+        meta.create_gate("only the last row of the footer is in the table", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let is_footer = meta.query_advice(is_footer, Rotation::cur());
+            cb.require_equal(
+                "is_enabled",
+                meta.query_advice(is_enabled, Rotation::cur()),
+                meta.query_fixed(q_last_row, Rotation::cur()) * is_footer.clone(),
+            );
+            cb.require_zero(
+                "output_rlc",
+                not::expr(is_footer) * meta.query_advice(output_rlc, Rotation::cur()),
+            );
+            cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+        });
+
+        Self {
+            q_enable,
+            q_first,
+            q_rows,
+            a1,
+            c1,
+            a2,
+            b2,
+            c2,
+            d2,
+            h,
+            m,
+            is_header,
+            is_round,
+            is_footer,
+            round,
+            sigmas,
+            is_last,
+            diff_inv,
+            blake2f_table,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: Field> Blake2fCircuitConfig<F> {
+    pub(crate) fn assign(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        witness: &[Blake2fRow<F>],
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "assign blake2f rows",
+            |mut region| {
+                for (offset, blake2f_row) in witness.iter().enumerate() {
+                    self.set_row(&mut region, offset, blake2f_row)?;
+                }
+                self.blake2f_table.annotate_columns_in_region(&mut region);
+                self.annotate_circuit(&mut region);
+                Ok(())
+            },
+        )
+    }
+
+    fn set_row(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        row: &Blake2fRow<F>,
+    ) -> Result<(), Error> {
+        let row_idx = offset % NUM_ROWS_PER_UNIT;
+
+        // Fixed selectors
+        let mut selectors = vec![
+            ("q_enable", self.q_enable, true),
+            ("q_first", self.q_first, offset == 0),
+        ];
+        for (idx, q_row) in self.q_rows.iter().enumerate() {
+            selectors.push(("q_row", *q_row, row_idx == idx));
+        }
+        for (name, column, value) in selectors {
+            region.assign_fixed(
+                || format!("assign {} {}", name, offset),
+                column,
+                offset,
+                || Value::known(F::from(value as u64)),
+            )?;
+        }
+
+        // Bits
+        for (name, columns, bits) in [
+            ("a1", &self.a1[..], &row.a1[..]),
+            ("c1", &self.c1[..], &row.c1[..]),
+            ("a2", &self.a2[..], &row.a2[..]),
+            ("b2", &self.b2[..], &row.b2[..]),
+            ("c2", &self.c2[..], &row.c2[..]),
+            ("d2", &self.d2[..], &row.d2[..]),
+        ] {
+            for (idx, (column, bit)) in columns.iter().zip(bits.iter()).enumerate() {
+                region.assign_advice(
+                    || format!("assign {} {} {}", name, idx, offset),
+                    *column,
+                    offset,
+                    || Value::known(F::from(*bit as u64)),
+                )?;
+            }
+        }
+
+        // Input and unit data
+        let words = self
+            .h
+            .iter()
+            .zip(row.h.iter())
+            .chain(self.m.iter().zip(row.m.iter()));
+        for (idx, (column, word)) in words.enumerate() {
+            region.assign_advice(
+                || format!("assign word {} {}", idx, offset),
+                *column,
+                offset,
+                || Value::known(F::from(*word)),
+            )?;
+        }
+        for (idx, column) in self.sigmas.iter().enumerate() {
+            region.assign_advice(
+                || format!("assign sigma {} {}", idx, offset),
+                *column,
+                offset,
+                || Value::known(F::from((row.sigma == idx) as u64)),
+            )?;
+        }
+        for (name, column, value) in [
+            ("is_header", self.is_header, F::from(row.is_header as u64)),
+            ("is_round", self.is_round, F::from(row.is_round as u64)),
+            ("is_footer", self.is_footer, F::from(row.is_footer as u64)),
+            ("round", self.round, F::from(row.round as u64)),
+            ("is_last", self.is_last, F::from(row.is_last as u64)),
+            ("diff_inv", self.diff_inv, row.diff_inv),
+        ] {
+            region.assign_advice(
+                || format!("assign {} {}", name, offset),
+                column,
+                offset,
+                || Value::known(value),
+            )?;
+        }
+
+        self.blake2f_table.assign_row(
+            region,
+            offset,
+            [
+                Value::known(F::from(row.is_enabled as u64)),
+                Value::known(F::from(row.rounds as u64)),
+                row.input_rlc,
+                row.output_rlc,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn annotate_circuit(&self, region: &mut Region<F>) {
+        region.name_column(|| "BLAKE2F_q_enable", self.q_enable);
+        region.name_column(|| "BLAKE2F_q_first", self.q_first);
+        region.name_column(|| "BLAKE2F_is_header", self.is_header);
+        region.name_column(|| "BLAKE2F_is_round", self.is_round);
+        region.name_column(|| "BLAKE2F_is_footer", self.is_footer);
+        region.name_column(|| "BLAKE2F_round", self.round);
+        region.name_column(|| "BLAKE2F_is_last", self.is_last);
+        region.name_column(|| "BLAKE2F_diff_inv", self.diff_inv);
+    }
+}
+
+/// Blake2fCircuit
+#[derive(Default, Clone, Debug)]
+pub struct Blake2fCircuit<F: Field> {
+    inputs: Vec<Vec<u8>>,
+    num_rows: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> SubCircuit<F> for Blake2fCircuit<F> {
+    type Config = Blake2fCircuitConfig<F>;
+
+    fn unusable_rows() -> usize {
+        // The bits of `b2` are queried at 9 distinct rotations, so returns
+        // (9 + 3) unusable rows.
+        12
+    }
+
+    /// The `chunk.fixed_param.max_vertical_circuit_rows` parameter, when set,
+    /// pads the circuit to a fixed number of units, independently of the
+    /// units required by the inputs.
+    fn new_from_block(block: &Block<F>, chunk: &Chunk<F>) -> Self {
+        Self::new(
+            chunk.fixed_param.max_vertical_circuit_rows,
+            block.precompile_events.get_blake2f_events(),
+        )
+    }
+
+    /// Return the minimum number of rows required to prove the block
+    fn min_num_rows_block(block: &Block<F>, chunk: &Chunk<F>) -> (usize, usize) {
+        (
+            block
+                .precompile_events
+                .get_blake2f_events()
+                .iter()
+                .filter_map(|input| Blake2fInput::parse(input))
+                .map(|input| num_units(input.rounds) * NUM_ROWS_PER_UNIT)
+                .sum(),
+            chunk.fixed_param.max_vertical_circuit_rows,
+        )
+    }
+
+    /// Make the assignments to the Blake2fCircuit
+    fn synthesize_sub(
+        &self,
+        config: &Self::Config,
+        challenges: &Challenges<Value<F>>,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let witness = self.generate_witness(*challenges)?;
+        config.assign(layouter, witness.as_slice())
+    }
+}
+
+impl<F: Field> Blake2fCircuit<F> {
+    /// Creates a new circuit instance
+    pub fn new(num_rows: usize, inputs: Vec<Vec<u8>>) -> Self {
+        Blake2fCircuit {
+            inputs,
+            num_rows,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The number of units, i.e. rounds plus 2 per input, that fit in this circuit
+    pub fn capacity(&self) -> Option<usize> {
+        if self.num_rows > 0 {
+            Some(self.num_rows.saturating_sub(Self::unusable_rows()) / NUM_ROWS_PER_UNIT)
+        } else {
+            None
+        }
+    }
+
+    /// Sets the witness using the inputs to be compressed
+    pub(crate) fn generate_witness(
+        &self,
+        challenges: Challenges<Value<F>>,
+    ) -> Result<Vec<Blake2fRow<F>>, Error> {
+        multi_blake2f(self.inputs.as_slice(), challenges, self.capacity())
+    }
+}
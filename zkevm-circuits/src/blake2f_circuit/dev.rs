@@ -0,0 +1,15 @@
+pub use super::Blake2fCircuit;
+
+use crate::{
+    blake2f_circuit::{Blake2fCircuitConfig, Blake2fCircuitConfigArgs},
+    impl_hash_circuit,
+    table::Blake2fTable,
+};
+
+impl_hash_circuit!(
+    Blake2fCircuit,
+    Blake2fCircuitConfig,
+    Blake2fCircuitConfigArgs,
+    Blake2fTable,
+    blake2f_table
+);
@@ -0,0 +1,60 @@
+pub(crate) const NUM_BITS_PER_BYTE: usize = 8;
+pub(crate) const NUM_BYTES_PER_WORD: usize = 8;
+pub(crate) const NUM_BITS_PER_WORD: usize = NUM_BYTES_PER_WORD * NUM_BITS_PER_BYTE;
+/// The sum of 2 words needs 1 extra bit for the carry
+pub(crate) const NUM_BITS_PER_WORD_SUM2: usize = NUM_BITS_PER_WORD + 1;
+/// The sum of 3 words needs 2 extra bits for the carry
+pub(crate) const NUM_BITS_PER_WORD_SUM3: usize = NUM_BITS_PER_WORD + 2;
+pub(crate) const NUM_WORDS_PER_STATE: usize = 16;
+pub(crate) const NUM_HASH_WORDS: usize = 8;
+pub(crate) const NUM_MESSAGE_WORDS: usize = 16;
+pub(crate) const NUM_SIGMAS: usize = 10;
+/// Number of applications of the mixing function G in a round
+pub(crate) const NUM_G_PER_ROUND: usize = 8;
+pub(crate) const NUM_ROWS_PER_UNIT: usize = NUM_G_PER_ROUND;
+pub(crate) const MAX_DEGREE: usize = 5;
+
+/// The input is `rounds (4 bytes, big-endian) || h (64 bytes) || m (128 bytes)
+/// || t (16 bytes) || f (1 byte)`, all words little-endian
+pub(crate) const NUM_ROUNDS_BYTES: usize = 4;
+pub(crate) const INPUT_LEN: usize =
+    NUM_ROUNDS_BYTES + (NUM_HASH_WORDS + NUM_MESSAGE_WORDS + 2) * NUM_BYTES_PER_WORD + 1;
+pub(crate) const OUTPUT_LEN: usize = NUM_HASH_WORDS * NUM_BYTES_PER_WORD;
+
+/// The state words the mixing function G works on in each row of a round.
+/// The first 4 rows mix the columns of the state, the last 4 its diagonals.
+/// A state word `w` is always the `w / 4`-th argument of G.
+pub(crate) const G_WORDS: [[usize; 4]; NUM_G_PER_ROUND] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+pub(crate) const IV: [u64; NUM_HASH_WORDS] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+pub(crate) const SIGMA: [[usize; NUM_MESSAGE_WORDS]; NUM_SIGMAS] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
@@ -0,0 +1,73 @@
+use super::{util::blake2f, *};
+use crate::{
+    table::hash_table::test::{check_variadic_size, verify_hash_circuit},
+    util::unusable_rows,
+};
+use halo2_proofs::halo2curves::bn256::Fr;
+
+/// Test vectors 4 to 7 of EIP-152
+const VECTORS: [(&str, &str); 4] = [
+    (
+        "0000000048c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b61626300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000001",
+        "08c9bcf367e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d282e6ad7f520e511f6c3e2b8c68059b9442be0454267ce079217e1319cde05b",
+    ),
+    (
+        "0000000c48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b61626300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000001",
+        "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+    ),
+    (
+        "0000000c48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b61626300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000000",
+        "75ab69d3190a562c51aef8d88f1c2775876944407270c42c9844252c26d2875298743e7f6d5ea2f2d3e8d226039cd31b4e426ac4f2d3d666a610c2116fde4735",
+    ),
+    (
+        "0000000148c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b61626300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000001",
+        "b63a380cb2897d521994a85234ee2c181b5f844d2c624c002677e9703449d2fba551b3a8333bcdf5f2f7e08993d53923de3d64fcc68c034e717b9293fed7a421",
+    ),
+];
+
+fn vectors() -> Vec<(Vec<u8>, Vec<u8>)> {
+    VECTORS
+        .iter()
+        .map(|(input, output)| (hex::decode(input).unwrap(), hex::decode(output).unwrap()))
+        .collect()
+}
+
+fn inputs() -> Vec<Vec<u8>> {
+    vectors().into_iter().map(|(input, _)| input).collect()
+}
+
+#[test]
+fn blake2f_circuit_unusable_rows() {
+    assert_eq!(
+        Blake2fCircuit::<Fr>::unusable_rows(),
+        unusable_rows::<Fr, Blake2fCircuit::<Fr>>(()),
+    )
+}
+
+#[test]
+fn blake2f_native() {
+    for (input, output) in vectors() {
+        assert_eq!(blake2f(&input).unwrap().to_vec(), output);
+    }
+    let (mut input, _) = vectors()[0].clone();
+    input[INPUT_LEN - 1] = 2;
+    assert_eq!(blake2f(&input), None);
+    assert_eq!(blake2f(&input[1..]), None);
+}
+
+fn verify<F: Field>(k: u32, inputs: Vec<Vec<u8>>, success: bool) {
+    let circuit = Blake2fCircuit::new(2usize.pow(k), inputs.clone());
+    verify_hash_circuit::<F, _, _, _>(k, &circuit, &inputs, None, success, |config| {
+        &config.blake2f_table
+    });
+}
+
+#[test]
+fn blake2f_circuit_simple() {
+    verify::<Fr>(10, inputs(), true);
+}
+
+#[test]
+fn blake2f_circuit_variadic_size_check() {
+    check_variadic_size::<Fr, _>(10, Blake2fCircuit::new, inputs());
+}
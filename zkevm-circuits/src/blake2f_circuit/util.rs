@@ -0,0 +1,185 @@
+use super::param::*;
+pub(crate) use crate::sha256_circuit::util::{decode, query_bits, rotate_right};
+use eth_types::Field;
+use gadgets::util::{not, xor};
+use halo2_proofs::plonk::{Advice, Column, Expression, VirtualCells};
+
+/// Query the 64 bits of a word at the given rotation, ignoring the carry bits.
+pub(crate) fn query_word<F: Field>(
+    meta: &mut VirtualCells<F>,
+    columns: &[Column<Advice>],
+    rot: i32,
+) -> Vec<Expression<F>> {
+    query_bits(meta, &columns[..NUM_BITS_PER_WORD], rot)
+}
+
+/// Split a value into `N` little-endian bits.
+pub(crate) fn into_bits<const N: usize>(value: u128) -> [bool; N] {
+    std::array::from_fn(|idx| (value >> idx) & 1 == 1)
+}
+
+/// Bitwise xor of two bit vectors.
+pub(crate) fn xor2<F: Field>(a: &[Expression<F>], b: &[Expression<F>]) -> Vec<Expression<F>> {
+    a.iter().zip(b).map(|(a, b)| xor::expr(a, b)).collect()
+}
+
+/// Bitwise xor of bits with a constant.
+pub(crate) fn xor_constant<F: Field>(bits: &[Expression<F>], value: u64) -> Vec<Expression<F>> {
+    bits.iter()
+        .enumerate()
+        .map(|(idx, bit)| {
+            if (value >> idx) & 1 == 1 {
+                not::expr(bit)
+            } else {
+                bit.clone()
+            }
+        })
+        .collect()
+}
+
+/// Little-endian bytes of a word given as little-endian bits.
+pub(crate) fn to_le_bytes<F: Field>(bits: &[Expression<F>]) -> Vec<Expression<F>> {
+    bits.chunks(NUM_BITS_PER_BYTE).map(decode::expr).collect()
+}
+
+/// The rotation from the given row of a unit to the row holding the latest
+/// value of the state word `word`.
+///
+/// Every row of a round stores the new values of the words in `G_WORDS`, so the
+/// latest value is in the last row before `row` that updates `word`. When
+/// `same_unit` is false, or when no such row exists, it's in the previous unit,
+/// whose last 4 rows together hold all the words of the state.
+pub(crate) fn state_word_rotation(word: usize, row: usize, same_unit: bool) -> i32 {
+    let last_row = |rows: std::ops::Range<usize>| {
+        rows.rev()
+            .find(|last_row| G_WORDS[*last_row].contains(&word))
+    };
+    match same_unit.then(|| last_row(0..row)).flatten() {
+        Some(last_row) => last_row as i32 - row as i32,
+        None => last_row(0..NUM_ROWS_PER_UNIT).unwrap() as i32 - (NUM_ROWS_PER_UNIT + row) as i32,
+    }
+}
+
+/// The words computed in the mixing function G, including the carries of the
+/// additions.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct GWords {
+    /// a + b + x
+    pub(crate) a1: u128,
+    /// c + d1, with d1 = (d ^ a1) >>> 32
+    pub(crate) c1: u128,
+    /// a1 + b1 + y, with b1 = (b ^ c1) >>> 24
+    pub(crate) a2: u128,
+    /// (b1 ^ c2) >>> 63
+    pub(crate) b2: u64,
+    /// c1 + d2
+    pub(crate) c2: u128,
+    /// (d1 ^ a2) >>> 16
+    pub(crate) d2: u64,
+}
+
+impl GWords {
+    /// The new values of (a, b, c, d)
+    pub(crate) fn outputs(&self) -> [u64; 4] {
+        [self.a2 as u64, self.b2, self.c2 as u64, self.d2]
+    }
+}
+
+/// The mixing function G.
+pub(crate) fn g(a: u64, b: u64, c: u64, d: u64, x: u64, y: u64) -> GWords {
+    let a1 = a as u128 + b as u128 + x as u128;
+    let d1 = (d ^ a1 as u64).rotate_right(32);
+    let c1 = c as u128 + d1 as u128;
+    let b1 = (b ^ c1 as u64).rotate_right(24);
+    let a2 = (a1 as u64) as u128 + b1 as u128 + y as u128;
+    let d2 = (d1 ^ a2 as u64).rotate_right(16);
+    let c2 = (c1 as u64) as u128 + d2 as u128;
+    let b2 = (b1 ^ c2 as u64).rotate_right(63);
+    GWords {
+        a1,
+        c1,
+        a2,
+        b2,
+        c2,
+        d2,
+    }
+}
+
+/// The parsed input of the BLAKE2 compression function F.
+#[derive(Clone, Debug)]
+pub(crate) struct Blake2fInput {
+    pub(crate) rounds: u32,
+    pub(crate) h: [u64; NUM_HASH_WORDS],
+    pub(crate) m: [u64; NUM_MESSAGE_WORDS],
+    pub(crate) t: [u64; 2],
+    pub(crate) f: bool,
+}
+
+impl Blake2fInput {
+    /// Parse the input as specified in EIP-152, returns `None` when it's invalid.
+    pub(crate) fn parse(input: &[u8]) -> Option<Self> {
+        if input.len() != INPUT_LEN || input[INPUT_LEN - 1] > 1 {
+            return None;
+        }
+        let mut words = input[NUM_ROUNDS_BYTES..INPUT_LEN - 1]
+            .chunks(NUM_BYTES_PER_WORD)
+            .map(|word| u64::from_le_bytes(word.try_into().unwrap()));
+        Some(Self {
+            rounds: u32::from_be_bytes(input[..NUM_ROUNDS_BYTES].try_into().unwrap()),
+            h: std::array::from_fn(|_| words.next().unwrap()),
+            m: std::array::from_fn(|_| words.next().unwrap()),
+            t: std::array::from_fn(|_| words.next().unwrap()),
+            f: input[INPUT_LEN - 1] == 1,
+        })
+    }
+
+    /// The state before the first round
+    pub(crate) fn initial_state(&self) -> [u64; NUM_WORDS_PER_STATE] {
+        let mut v = [0; NUM_WORDS_PER_STATE];
+        v[..NUM_HASH_WORDS].copy_from_slice(&self.h);
+        v[NUM_HASH_WORDS..].copy_from_slice(&IV);
+        v[12] ^= self.t[0];
+        v[13] ^= self.t[1];
+        if self.f {
+            v[14] = !v[14];
+        }
+        v
+    }
+}
+
+/// Apply a round to the state, returns the words computed by every G.
+pub(crate) fn round(
+    v: &mut [u64; NUM_WORDS_PER_STATE],
+    m: &[u64; NUM_MESSAGE_WORDS],
+    round_idx: usize,
+) -> [GWords; NUM_G_PER_ROUND] {
+    let sigma = SIGMA[round_idx % NUM_SIGMAS];
+    std::array::from_fn(|idx| {
+        let [a, b, c, d] = G_WORDS[idx];
+        let words = g(
+            v[a],
+            v[b],
+            v[c],
+            v[d],
+            m[sigma[2 * idx]],
+            m[sigma[2 * idx + 1]],
+        );
+        [v[a], v[b], v[c], v[d]] = words.outputs();
+        words
+    })
+}
+
+/// The BLAKE2 compression function F as specified in EIP-152, returns `None`
+/// when the input is invalid.
+pub(crate) fn blake2f(input: &[u8]) -> Option<[u8; OUTPUT_LEN]> {
+    let input = Blake2fInput::parse(input)?;
+    let mut v = input.initial_state();
+    for idx in 0..input.rounds as usize {
+        round(&mut v, &input.m, idx);
+    }
+    let mut output = [0; OUTPUT_LEN];
+    for (idx, bytes) in output.chunks_mut(NUM_BYTES_PER_WORD).enumerate() {
+        bytes.copy_from_slice(&(input.h[idx] ^ v[idx] ^ v[idx + 8]).to_le_bytes());
+    }
+    Some(output)
+}
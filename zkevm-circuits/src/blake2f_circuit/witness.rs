@@ -0,0 +1,204 @@
+use super::{
+    param::*,
+    util::{into_bits, round, Blake2fInput},
+};
+use crate::util::Challenges;
+use eth_types::Field;
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+/// Blake2fRow
+#[derive(Clone, Debug)]
+pub(crate) struct Blake2fRow<F> {
+    pub(crate) a1: [bool; NUM_BITS_PER_WORD_SUM3],
+    pub(crate) c1: [bool; NUM_BITS_PER_WORD_SUM2],
+    pub(crate) a2: [bool; NUM_BITS_PER_WORD_SUM3],
+    pub(crate) b2: [bool; NUM_BITS_PER_WORD],
+    pub(crate) c2: [bool; NUM_BITS_PER_WORD_SUM2],
+    pub(crate) d2: [bool; NUM_BITS_PER_WORD],
+    pub(crate) rounds: u32,
+    pub(crate) h: [u64; NUM_HASH_WORDS],
+    pub(crate) m: [u64; NUM_MESSAGE_WORDS],
+    pub(crate) input_rlc: Value<F>,
+    pub(crate) is_header: bool,
+    pub(crate) is_round: bool,
+    pub(crate) is_footer: bool,
+    pub(crate) round: u32,
+    pub(crate) sigma: usize,
+    pub(crate) is_last: bool,
+    pub(crate) diff_inv: F,
+    pub(crate) is_enabled: bool,
+    pub(crate) output_rlc: Value<F>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum UnitKind {
+    Header,
+    Round,
+    Footer,
+    Empty,
+}
+
+impl<F: Field> Blake2fRow<F> {
+    /// A row of a new unit, which carries the data of the last row
+    fn new(last: Option<&Self>, kind: UnitKind) -> Self {
+        // The round counter and the message schedule only advance from round to round
+        let (round, sigma) = match last {
+            Some(last) if last.is_round => (last.round + 1, (last.sigma + 1) % NUM_SIGMAS),
+            _ => (0, 0),
+        };
+        Self {
+            a1: [false; NUM_BITS_PER_WORD_SUM3],
+            c1: [false; NUM_BITS_PER_WORD_SUM2],
+            a2: [false; NUM_BITS_PER_WORD_SUM3],
+            b2: [false; NUM_BITS_PER_WORD],
+            c2: [false; NUM_BITS_PER_WORD_SUM2],
+            d2: [false; NUM_BITS_PER_WORD],
+            rounds: last.map(|last| last.rounds).unwrap_or_default(),
+            h: last.map(|last| last.h).unwrap_or_default(),
+            m: last.map(|last| last.m).unwrap_or_default(),
+            input_rlc: last
+                .map(|last| last.input_rlc)
+                .unwrap_or(Value::known(F::ZERO)),
+            is_header: kind == UnitKind::Header,
+            is_round: kind == UnitKind::Round,
+            is_footer: kind == UnitKind::Footer,
+            round,
+            sigma,
+            is_last: false,
+            diff_inv: F::ZERO,
+            is_enabled: false,
+            output_rlc: Value::known(F::ZERO),
+        }
+    }
+
+    /// Set the outputs of G, i.e. the new values of (a, b, c, d)
+    fn set_outputs(&mut self, [a, b, c, d]: [u64; 4]) {
+        self.a2 = into_bits(a as u128);
+        self.b2 = into_bits(b as u128);
+        self.c2 = into_bits(c as u128);
+        self.d2 = into_bits(d as u128);
+    }
+
+    /// Set whether no round follows this unit, given the number of rounds
+    fn set_is_last(&mut self, rounds: u32) {
+        let diff = rounds as u64
+            - if self.is_round {
+                self.round as u64 + 1
+            } else {
+                0
+            };
+        self.is_last = diff == 0;
+        self.diff_inv = F::from(diff).invert().unwrap_or(F::ZERO);
+    }
+}
+
+/// Number of units needed to compress an input with the given number of rounds
+pub(crate) fn num_units(rounds: u32) -> usize {
+    rounds as usize + 2
+}
+
+fn push_unit<F: Field>(rows: &mut Vec<Blake2fRow<F>>, unit: Blake2fRow<F>) -> &mut [Blake2fRow<F>] {
+    let start = rows.len();
+    rows.extend(std::iter::repeat(unit).take(NUM_ROWS_PER_UNIT));
+    &mut rows[start..]
+}
+
+fn blake2f<F: Field>(
+    rows: &mut Vec<Blake2fRow<F>>,
+    bytes: &[u8],
+    input: &Blake2fInput,
+    challenges: Challenges<Value<F>>,
+) {
+    let r = challenges.keccak_input();
+
+    // Header: the first rows still carry the data of the previous input
+    let mut header = Blake2fRow::new(rows.last(), UnitKind::Header);
+    header.set_is_last(input.rounds);
+    let header = push_unit(rows, header);
+    for (idx, row) in header.iter_mut().take(NUM_MESSAGE_WORDS / 4).enumerate() {
+        row.a1 = into_bits(input.m[4 * idx] as u128);
+        row.c1 = into_bits(input.m[4 * idx + 1] as u128);
+        row.a2 = into_bits(input.m[4 * idx + 2] as u128);
+        row.b2 = into_bits(input.m[4 * idx + 3] as u128);
+    }
+    header[0].c2 = into_bits(input.rounds as u128);
+    header[0].d2 = into_bits(input.f as u128);
+    let v = input.initial_state();
+    for (idx, row) in header.iter_mut().enumerate().skip(NUM_ROWS_PER_UNIT - 4) {
+        row.set_outputs(G_WORDS[idx].map(|word| v[word]));
+    }
+    let last = header.last_mut().unwrap();
+    last.rounds = input.rounds;
+    last.h = input.h;
+    last.m = input.m;
+    last.input_rlc = r.map(|r| {
+        bytes
+            .iter()
+            .fold(F::ZERO, |acc, byte| acc * r + F::from(*byte as u64))
+    });
+
+    // Rounds
+    let mut v = v;
+    for round_idx in 0..input.rounds as usize {
+        let mut unit = Blake2fRow::new(rows.last(), UnitKind::Round);
+        unit.set_is_last(input.rounds);
+        let words = round(&mut v, &input.m, round_idx);
+        for (row, words) in push_unit(rows, unit).iter_mut().zip(words) {
+            row.a1 = into_bits(words.a1);
+            row.c1 = into_bits(words.c1);
+            row.a2 = into_bits(words.a2);
+            row.c2 = into_bits(words.c2);
+            row.b2 = into_bits(words.b2 as u128);
+            row.d2 = into_bits(words.d2 as u128);
+        }
+    }
+
+    // Footer
+    let unit = Blake2fRow::new(rows.last(), UnitKind::Footer);
+    let mut output_rlc = Value::known(F::ZERO);
+    for (idx, row) in push_unit(rows, unit).iter_mut().enumerate() {
+        let h = input.h[idx];
+        row.a1 = into_bits(h as u128);
+        let output = h ^ v[idx] ^ v[idx + 8];
+        output_rlc = output_rlc.zip(r).map(|(acc, r)| {
+            output
+                .to_le_bytes()
+                .iter()
+                .fold(acc, |acc, byte| acc * r + F::from(*byte as u64))
+        });
+        row.output_rlc = output_rlc;
+    }
+    rows.last_mut().unwrap().is_enabled = true;
+}
+
+pub(crate) fn multi_blake2f<F: Field>(
+    bytes: &[Vec<u8>],
+    challenges: Challenges<Value<F>>,
+    capacity: Option<usize>,
+) -> Result<Vec<Blake2fRow<F>>, Error> {
+    let mut rows: Vec<Blake2fRow<F>> = Vec::new();
+    for bytes in bytes {
+        let input = Blake2fInput::parse(bytes).ok_or_else(|| {
+            log::error!("Invalid blake2f input of {} bytes", bytes.len());
+            Error::Synthesis
+        })?;
+        blake2f(&mut rows, bytes, &input, challenges);
+    }
+    if let Some(capacity) = capacity {
+        // Check that we are not over capacity
+        if rows.len() > capacity * NUM_ROWS_PER_UNIT {
+            log::error!(
+                "Blake2f inputs exceed capacity.  needed_rows = {}, available_rows = {}",
+                rows.len(),
+                capacity * NUM_ROWS_PER_UNIT
+            );
+            return Err(Error::BoundsFailure);
+        }
+        // Pad with empty units to the expected capacity
+        while rows.len() < capacity * NUM_ROWS_PER_UNIT {
+            let unit = Blake2fRow::new(rows.last(), UnitKind::Empty);
+            push_unit(&mut rows, unit);
+        }
+    }
+    Ok(rows)
+}
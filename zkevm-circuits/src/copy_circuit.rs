@@ -28,6 +28,7 @@ use bus_mapping::{
 use eth_types::Field;
 use gadgets::{
     binary_number::{BinaryNumberChip, BinaryNumberConfig},
+    is_zero::{IsZeroChip, IsZeroConfig, IsZeroInstruction},
     less_than::{LtChip, LtConfig, LtInstruction},
     util::{and, not, or, Expr},
 };
@@ -66,6 +67,14 @@ pub struct CopyCircuitConfig<F> {
     pub is_code: Column<Advice>,
     /// Whether the row is enabled or not.
     pub q_enable: Column<Fixed>,
+    /// Whether the copy event is a copy within the memory of a single call
+    /// (MCOPY). In that case all the source bytes are read before the first
+    /// destination byte is written, so the read rows take the first half of
+    /// the rw counters and the write rows the second half.
+    pub is_memory_copy: Column<Advice>,
+    /// IsZero chip to check that the read and write rows of a step are both
+    /// memory with the same call id, which is the case iff `is_memory_copy`.
+    is_memory_copy_check: IsZeroConfig<F>,
     /// The Copy Table contains the columns that are exposed via the lookup
     /// expressions
     pub copy_table: CopyTable,
@@ -131,6 +140,8 @@ impl<F: Field> SubCircuitConfig<F> for CopyCircuitConfig<F> {
         let rw_counter = copy_table.rw_counter;
         let rwc_inc_left = copy_table.rwc_inc_left;
         let tag_bits = copy_table.tag;
+        let is_memory_copy = meta.advice_column();
+        let is_memory_copy_inv = meta.advice_column();
 
         // annotate table columns
         tx_table.annotate_columns(meta);
@@ -145,6 +156,21 @@ impl<F: Field> SubCircuitConfig<F> for CopyCircuitConfig<F> {
             |meta| meta.query_advice(addr, Rotation::cur()),
             |meta| meta.query_advice(src_addr_end, Rotation::cur()),
         );
+        let is_memory_copy_check = IsZeroChip::configure(
+            meta,
+            |meta| meta.query_selector(q_step),
+            |meta| {
+                let [id_shift, tag_shift_read, tag_shift_write] =
+                    memory_copy_check_shifts::<F>().map(Expression::Constant);
+                let memory = tag.constant_expr::<F>(CopyDataType::Memory);
+                (meta.query_advice(id.lo(), Rotation::cur())
+                    - meta.query_advice(id.lo(), Rotation::next()))
+                    * id_shift
+                    + (tag.value(Rotation::cur())(meta) - memory.clone()) * tag_shift_read
+                    + (tag.value(Rotation::next())(meta) - memory) * tag_shift_write
+            },
+            is_memory_copy_inv,
+        );
 
         meta.create_gate("verify row", |meta| {
             let mut cb = BaseConstraintBuilder::default();
@@ -178,7 +204,7 @@ impl<F: Field> SubCircuitConfig<F> for CopyCircuitConfig<F> {
                 - meta.query_advice(is_last, Rotation::cur())
                 - meta.query_advice(is_last, Rotation::next());
             cb.condition(
-                not_last_two_rows
+                not_last_two_rows.clone()
                     * (not::expr(tag.value_equals(CopyDataType::Padding, Rotation::cur())(
                         meta,
                     ))),
@@ -213,8 +239,10 @@ impl<F: Field> SubCircuitConfig<F> for CopyCircuitConfig<F> {
                 ]),
                 not::expr(meta.query_advice(is_pad, Rotation::cur())),
             ]);
+            let is_memory_copy = meta.query_advice(is_memory_copy, Rotation::cur());
             cb.condition(
-                not::expr(meta.query_advice(is_last, Rotation::cur())),
+                not::expr(meta.query_advice(is_last, Rotation::cur()))
+                    * not::expr(is_memory_copy.expr()),
                 |cb| {
                     cb.require_equal(
                         "rows[0].rw_counter + rw_diff == rows[1].rw_counter",
@@ -226,6 +254,32 @@ impl<F: Field> SubCircuitConfig<F> for CopyCircuitConfig<F> {
                         meta.query_advice(rwc_inc_left, Rotation::cur()) - rw_diff.clone(),
                         meta.query_advice(rwc_inc_left, Rotation::next()),
                     );
+                },
+            );
+            // For a memory copy, the reads and the writes each take consecutive rw counters,
+            // while the end of the rw counter range stays the same.
+            cb.condition(
+                not::expr(meta.query_advice(is_last, Rotation::cur())) * is_memory_copy.expr(),
+                |cb| {
+                    cb.require_equal(
+                        "rows[0].rw_counter + rows[0].rwc_inc_left == rows[1].rw_counter + rows[1].rwc_inc_left",
+                        meta.query_advice(rw_counter, Rotation::cur())
+                            + meta.query_advice(rwc_inc_left, Rotation::cur()),
+                        meta.query_advice(rw_counter, Rotation::next())
+                            + meta.query_advice(rwc_inc_left, Rotation::next()),
+                    );
+                },
+            );
+            cb.condition(not_last_two_rows * is_memory_copy, |cb| {
+                cb.require_equal(
+                    "rows[0].rw_counter + 1 == rows[2].rw_counter for memory copy",
+                    meta.query_advice(rw_counter, Rotation::cur()) + 1.expr(),
+                    meta.query_advice(rw_counter, Rotation(2)),
+                );
+            });
+            cb.condition(
+                not::expr(meta.query_advice(is_last, Rotation::cur())),
+                |cb| {
                     cb.require_equal(
                         "rows[0].rlc_acc == rows[1].rlc_acc",
                         meta.query_advice(rlc_acc, Rotation::cur()),
@@ -342,6 +396,37 @@ impl<F: Field> SubCircuitConfig<F> for CopyCircuitConfig<F> {
                 meta.query_advice(is_pad, Rotation::next()),
             );
 
+            let is_memory_copy_cur = meta.query_advice(is_memory_copy, Rotation::cur());
+            cb.require_equal(
+                "is_memory_copy == 1 iff Memory => Memory within the same call",
+                is_memory_copy_cur.expr(),
+                is_memory_copy_check.expr(),
+            );
+            cb.require_equal(
+                "is_memory_copy is same for read-write rows",
+                is_memory_copy_cur.expr(),
+                meta.query_advice(is_memory_copy, Rotation::next()),
+            );
+            cb.condition(is_memory_copy_cur, |cb| {
+                cb.require_equal(
+                    "read tag is Memory for memory copy",
+                    tag.value(Rotation::cur())(meta),
+                    tag.constant_expr::<F>(CopyDataType::Memory),
+                );
+                cb.require_equal(
+                    "write tag is Memory for memory copy",
+                    tag.value(Rotation::next())(meta),
+                    tag.constant_expr::<F>(CopyDataType::Memory),
+                );
+                // The write of the step comes after all the reads, and is followed by the
+                // writes of the remaining bytes.
+                cb.require_equal(
+                    "rwc_inc_left == bytes_left for write row of memory copy",
+                    meta.query_advice(rwc_inc_left, Rotation::next()),
+                    meta.query_advice(bytes_left, Rotation::cur()),
+                );
+            });
+
             cb.gate(and::expr([meta.query_selector(q_step)]))
         });
 
@@ -472,6 +557,8 @@ impl<F: Field> SubCircuitConfig<F> for CopyCircuitConfig<F> {
             is_pad,
             is_code,
             q_enable,
+            is_memory_copy,
+            is_memory_copy_check,
             addr_lt_addr_end,
             copy_table,
             copy_table_tag: tag,
@@ -505,6 +592,29 @@ pub fn constrain_must_terminate<F: Field>(
     });
 }
 
+/// Shifts of the id and tag differences in the value checked by
+/// `is_memory_copy_check`. The id limb is at most 128 bits and tags are less
+/// than 8, so the value is zero iff both tags are Memory and the ids are equal.
+fn memory_copy_check_shifts<F: Field>() -> [F; 3] {
+    let tag_shift_read = F::from_u128(1 << 64).square();
+    [F::ONE, tag_shift_read, tag_shift_read * F::from(256)]
+}
+
+/// Witness the value checked by `is_memory_copy_check` for a read-write pair of
+/// rows.
+fn memory_copy_check_value<F: Field>(
+    (read_id, read_tag): (Value<F>, CopyDataType),
+    (write_id, write_tag): (Value<F>, CopyDataType),
+) -> Value<F> {
+    let [id_shift, tag_shift_read, tag_shift_write] = memory_copy_check_shifts::<F>();
+    let memory = F::from(CopyDataType::Memory as u64);
+    (read_id - write_id) * Value::known(id_shift)
+        + Value::known(
+            (F::from(read_tag as u64) - memory) * tag_shift_read
+                + (F::from(write_tag as u64) - memory) * tag_shift_write,
+        )
+}
+
 impl<F: Field> CopyCircuitConfig<F> {
     /// Assign an individual copy event to the Copy Circuit.
     pub fn assign_copy_event(
@@ -516,11 +626,17 @@ impl<F: Field> CopyCircuitConfig<F> {
         challenges: Challenges<Value<F>>,
         copy_event: &CopyEvent,
     ) -> Result<(), Error> {
-        for (step_idx, (tag, table_row, circuit_row)) in
-            CopyTable::assignments(copy_event, challenges)
+        let is_memory_copy_chip = IsZeroChip::construct(self.is_memory_copy_check.clone());
+        let assignments = CopyTable::assignments(copy_event, challenges);
+        let id_lo = |step_idx: usize| {
+            let (tag, table_row, _) = &assignments[step_idx];
+            let (id_lo, _) = table_row
                 .iter()
-                .enumerate()
-        {
+                .find(|(_, label)| *label == "id_lo")
+                .unwrap();
+            (*id_lo, *tag)
+        };
+        for (step_idx, (tag, table_row, circuit_row)) in assignments.iter().enumerate() {
             let is_read = step_idx % 2 == 0;
 
             // Copy table assignments
@@ -579,6 +695,21 @@ impl<F: Field> CopyCircuitConfig<F> {
             // tag
             tag_chip.assign(region, *offset, tag)?;
 
+            // is_memory_copy
+            region.assign_advice(
+                || format!("is_memory_copy at row: {}", *offset),
+                self.is_memory_copy,
+                *offset,
+                || Value::known(F::from(copy_event.is_memory_copy() as u64)),
+            )?;
+            if is_read {
+                is_memory_copy_chip.assign(
+                    region,
+                    *offset,
+                    memory_copy_check_value(id_lo(step_idx), id_lo(step_idx + 1)),
+                )?;
+            }
+
             // lt chip
             if is_read {
                 lt_chip.assign(
@@ -769,6 +900,21 @@ impl<F: Field> CopyCircuitConfig<F> {
             *offset,
             || Value::known(F::ZERO),
         )?;
+        // is_memory_copy
+        region.assign_advice(
+            || format!("assign is_memory_copy {}", *offset),
+            self.is_memory_copy,
+            *offset,
+            || Value::known(F::ZERO),
+        )?;
+        IsZeroChip::construct(self.is_memory_copy_check.clone()).assign(
+            region,
+            *offset,
+            memory_copy_check_value(
+                (Value::known(F::ZERO), CopyDataType::Padding),
+                (Value::known(F::ZERO), CopyDataType::Padding),
+            ),
+        )?;
         // tag
         tag_chip.assign(region, *offset, &CopyDataType::Padding)?;
         // Assign LT gadget
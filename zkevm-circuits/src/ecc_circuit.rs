@@ -29,20 +29,20 @@ use halo2_proofs::{
     circuit::{Layouter, Value},
     halo2curves::{
         bn256::{Fq, Fq12, Fq2, Fr, G1Affine, G2Affine},
+        group::prime::PrimeCurveAffine,
         CurveAffine,
     },
     plonk::{ConstraintSystem, Error, Expression},
 };
 use itertools::Itertools;
 use log::error;
-use snark_verifier::util::arithmetic::PrimeCurveAffine;
 
 use crate::{
     evm_circuit::{param::N_BYTES_WORD, EvmCircuit},
     keccak_circuit::KeccakCircuit,
     table::{EccTable, LookupTable},
     util::{Challenges, SubCircuit, SubCircuitConfig},
-    witness::Block,
+    witness::{Block, Chunk},
 };
 
 mod dev;
@@ -52,7 +52,7 @@ mod util;
 use util::{
     EcAddAssigned, EcAddDecomposed, EcMulAssigned, EcMulDecomposed, EcOpsAssigned,
     EcPairingAssigned, EcPairingDecomposed, G1Assigned, G1Decomposed, G2Decomposed, ScalarAssigned,
-    LOG_TOTAL_NUM_ROWS,
+    LOG_TOTAL_NUM_ROWS, LOOKUP_BITS,
 };
 
 macro_rules! log_context_cursor {
@@ -86,7 +86,7 @@ pub struct EccCircuitConfig<F: Field> {
     _marker: PhantomData<F>,
 }
 
-impl<F: Field> SubCircuitConfig<F> for EccCircuitConfig<F> {
+impl<F: Field + halo2_base::utils::ScalarField> SubCircuitConfig<F> for EccCircuitConfig<F> {
     type ConfigArgs = EccCircuitConfigArgs<F>;
 
     fn new(
@@ -106,7 +106,7 @@ impl<F: Field> SubCircuitConfig<F> for EccCircuitConfig<F> {
             &num_advice,
             &[17], // num lookup advice
             1,     // num fixed
-            13,    // lookup bits
+            LOOKUP_BITS,
             limb_bits,
             num_limbs,
             modulus::<Fq>(),
@@ -157,7 +157,7 @@ pub struct EccCircuit<F: Field, const XI_0: i64> {
     _marker: PhantomData<F>,
 }
 
-impl<F: Field, const XI_0: i64> EccCircuit<F, XI_0> {
+impl<F: Field + halo2_base::utils::ScalarField, const XI_0: i64> EccCircuit<F, XI_0> {
     /// Return the minimum number of rows required to prove an input of a
     /// particular size.
     pub fn min_num_rows() -> usize {
@@ -234,7 +234,6 @@ impl<F: Field, const XI_0: i64> EccCircuit<F, XI_0> {
                 macro_rules! decompose_ec_op {
                     ($op_type:ident, $ops:expr, $n_ops:expr, $decompose_fn:ident) => {
                         $ops.iter()
-                            .filter(|op| !op.skip_by_ecc_circuit())
                             .chain(std::iter::repeat(&$op_type::default()))
                             .take($n_ops)
                             .map(|op| {
@@ -612,18 +611,6 @@ impl<F: Field, const XI_0: i64> EccCircuit<F, XI_0> {
         let p_is_on_curve_or_infinity =
             self.is_on_curveg1_or_infinity(ctx, ecc_chip, &px, px_is_zero, &py, py_is_zero);
 
-        // point at infinity
-        let infinity = EcPoint::construct(
-            ecc_chip
-                .field_chip()
-                .load_constant(ctx, fe_to_biguint(&Fq::zero())),
-            ecc_chip
-                .field_chip()
-                .load_constant(ctx, fe_to_biguint(&Fq::zero())),
-        );
-        // for invalid case, take a random point.
-        let dummy_g1 = ecc_chip.load_random_point::<G1Affine>(ctx);
-
         let point_p = EcPoint::construct(px, py);
         let is_valid = ecc_chip.field_chip().range().gate().and_many(
             ctx,
@@ -633,24 +620,71 @@ impl<F: Field, const XI_0: i64> EccCircuit<F, XI_0> {
                 QuantumCell::Existing(p_is_on_curve_or_infinity),
             ],
         );
-        let point_p = ecc_chip.select(ctx, &point_p, &dummy_g1, &is_valid);
 
         let scalar_s = self.handle_fr(ctx, fr_chip, op.s);
 
+        // The scalar multiplication supports neither the point at infinity nor a zero scalar, in
+        // which cases, like for invalid inputs, R == (0, 0). We then multiply a random point by 1
+        // instead.
+        let p_is_zero = ecc_chip.field_chip().range().gate().and(
+            ctx,
+            QuantumCell::Existing(px_is_zero),
+            QuantumCell::Existing(py_is_zero),
+        );
+        let s_is_zero = fr_chip.is_zero(ctx, &scalar_s.scalar);
+        let is_valid_zero = ecc_chip.field_chip().range().gate().or(
+            ctx,
+            QuantumCell::Existing(p_is_zero),
+            QuantumCell::Existing(s_is_zero),
+        );
+        let is_not_zero = ecc_chip
+            .field_chip()
+            .range()
+            .gate()
+            .not(ctx, QuantumCell::Existing(is_valid_zero));
+        let is_valid_non_zero = ecc_chip.field_chip().range().gate().and(
+            ctx,
+            QuantumCell::Existing(is_valid),
+            QuantumCell::Existing(is_not_zero),
+        );
+
+        // for invalid and zero cases, take a random point.
+        let dummy_g1 = ecc_chip.load_random_point::<G1Affine>(ctx);
+        let point_p_safe = ecc_chip.select(ctx, &point_p, &dummy_g1, &is_valid_non_zero);
+        let scalar_limbs = scalar_s
+            .scalar
+            .limbs()
+            .iter()
+            .enumerate()
+            .map(|(i, &limb)| {
+                ecc_chip.field_chip().range().gate().select(
+                    ctx,
+                    QuantumCell::Existing(limb),
+                    QuantumCell::Constant(if i == 0 { F::one() } else { F::zero() }),
+                    QuantumCell::Existing(is_valid_non_zero),
+                )
+            })
+            .collect_vec();
+
+        // point at infinity
+        let infinity = EcPoint::construct(
+            ecc_chip
+                .field_chip()
+                .load_constant(ctx, fe_to_biguint(&Fq::zero())),
+            ecc_chip
+                .field_chip()
+                .load_constant(ctx, fe_to_biguint(&Fq::zero())),
+        );
+
         let res = op.r.unwrap_or(G1Affine::identity());
         let point_r = self.handle_g1(ctx, ecc_chip, res, powers_of_256);
 
         log::trace!("[ECC] EcMul Inputs Assigned:");
         log_context_cursor!(ctx);
 
-        let point_r_got = ecc_chip.scalar_mult(
-            ctx,
-            &point_p,
-            &scalar_s.scalar.limbs().to_vec(),
-            fr_chip.limb_bits,
-            4,
-        );
-        let point_r_got = ecc_chip.select(ctx, &point_r_got, &infinity, &is_valid);
+        let point_r_got =
+            ecc_chip.scalar_mult(ctx, &point_p_safe, &scalar_limbs, fr_chip.limb_bits, 4);
+        let point_r_got = ecc_chip.select(ctx, &point_r_got, &infinity, &is_valid_non_zero);
         ecc_chip.assert_equal(ctx, &point_r.ec_point, &point_r_got);
 
         log::trace!("[ECC] EcMul Assignment END:");
@@ -1307,17 +1341,19 @@ impl<F: Field, const XI_0: i64> EccCircuit<F, XI_0> {
     }
 }
 
-impl<F: Field, const XI_0: i64> SubCircuit<F> for EccCircuit<F, XI_0> {
+impl<F: Field + halo2_base::utils::ScalarField, const XI_0: i64> SubCircuit<F>
+    for EccCircuit<F, XI_0>
+{
     type Config = EccCircuitConfig<F>;
 
-    fn new_from_block(block: &Block<F>) -> Self {
+    fn new_from_block(block: &Block<F>, chunk: &Chunk<F>) -> Self {
         Self {
-            max_add_ops: block.circuits_params.max_ec_ops.ec_add,
-            max_mul_ops: block.circuits_params.max_ec_ops.ec_mul,
-            max_pairing_ops: block.circuits_params.max_ec_ops.ec_pairing,
-            add_ops: block.get_ec_add_ops(),
-            mul_ops: block.get_ec_mul_ops(),
-            pairing_ops: block.get_ec_pairing_ops(),
+            max_add_ops: chunk.fixed_param.max_ec_ops.ec_add,
+            max_mul_ops: chunk.fixed_param.max_ec_ops.ec_mul,
+            max_pairing_ops: chunk.fixed_param.max_ec_ops.ec_pairing,
+            add_ops: block.precompile_events.get_ec_add_events(),
+            mul_ops: block.precompile_events.get_ec_mul_events(),
+            pairing_ops: block.precompile_events.get_ec_pairing_events(),
             _marker: PhantomData,
         }
     }
@@ -1346,27 +1382,36 @@ impl<F: Field, const XI_0: i64> SubCircuit<F> for EccCircuit<F, XI_0> {
         Ok(())
     }
 
-    fn min_num_rows_block(block: &Block<F>) -> (usize, usize) {
-        let row_num = if block.circuits_params.max_vertical_circuit_rows == 0 {
+    fn min_num_rows_block(block: &Block<F>, chunk: &Chunk<F>) -> (usize, usize) {
+        let max_ec_ops = &chunk.fixed_param.max_ec_ops;
+        // Without any op, only the lookup table of the range chip is assigned.
+        if max_ec_ops.ec_add + max_ec_ops.ec_mul + max_ec_ops.ec_pairing == 0 {
+            return (0, 1 << LOOKUP_BITS);
+        }
+
+        let row_num = if chunk.fixed_param.max_vertical_circuit_rows == 0 {
             Self::min_num_rows()
         } else {
-            block.circuits_params.max_vertical_circuit_rows
+            chunk.fixed_param.max_vertical_circuit_rows
         };
 
-        let ec_adds = block.get_ec_add_ops().len();
-        let ec_muls = block.get_ec_mul_ops().len();
-        let ec_pairings = block.get_ec_pairing_ops().len();
-        let max_ec_ops = &block.circuits_params.max_ec_ops;
-        log::debug!("ecc circuit row usage: ecadd {ec_adds}/{}, ecmul {ec_muls}/{}, ecpairing {ec_pairings}/{}",
-        max_ec_ops.ec_add, max_ec_ops.ec_mul, max_ec_ops.ec_pairing);
+        let ec_adds = block.precompile_events.get_ec_add_events().len();
+        let ec_muls = block.precompile_events.get_ec_mul_events().len();
+        let ec_pairings = block.precompile_events.get_ec_pairing_events().len();
+        log::debug!(
+            "ecc circuit row usage: ecadd {ec_adds}/{}, ecmul {ec_muls}/{}, ecpairing {ec_pairings}/{}",
+            max_ec_ops.ec_add,
+            max_ec_ops.ec_mul,
+            max_ec_ops.ec_pairing
+        );
 
         // Instead of showing actual minimum row usage,
         // halo2-lib based circuits use min_row_num to represent a percentage of total-used capacity
         // This functionality allows l2geth to decide if additional ops can be added.
         let min_row_num = [
-            (row_num / max_ec_ops.ec_add) * ec_adds,
-            (row_num / max_ec_ops.ec_mul) * ec_muls,
-            (row_num / max_ec_ops.ec_pairing) * ec_pairings,
+            (row_num / max_ec_ops.ec_add.max(1)) * ec_adds,
+            (row_num / max_ec_ops.ec_mul.max(1)) * ec_muls,
+            (row_num / max_ec_ops.ec_pairing.max(1)) * ec_pairings,
         ]
         .into_iter()
         .max()
@@ -1,7 +1,7 @@
 use eth_types::Field;
 use halo2_proofs::{
     circuit::{Layouter, SimpleFloorPlanner},
-    plonk::{Challenge, Circuit, ConstraintSystem, Error},
+    plonk::{Circuit, ConstraintSystem, Error},
 };
 
 use crate::{
@@ -11,9 +11,12 @@ use crate::{
 
 use super::{EccCircuit, EccCircuitConfig, EccCircuitConfigArgs};
 
-impl<F: Field, const XI_0: i64> Circuit<F> for EccCircuit<F, XI_0> {
-    type Config = (EccCircuitConfig<F>, Challenges<Challenge>);
+impl<F: Field + halo2_base::utils::ScalarField, const XI_0: i64> Circuit<F>
+    for EccCircuit<F, XI_0>
+{
+    type Config = (EccCircuitConfig<F>, Challenges);
     type FloorPlanner = SimpleFloorPlanner;
+    type Params = ();
 
     fn without_witnesses(&self) -> Self {
         Self::default()
@@ -40,7 +43,7 @@ impl<F: Field, const XI_0: i64> Circuit<F> for EccCircuit<F, XI_0> {
         config: Self::Config,
         mut layouter: impl Layouter<F>,
     ) -> Result<(), Error> {
-        let challenge_values = config.1.values(&layouter);
+        let challenge_values = config.1.values(&mut layouter);
         self.synthesize_sub(&config.0, &challenge_values, &mut layouter)
     }
 }
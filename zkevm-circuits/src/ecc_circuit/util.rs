@@ -5,6 +5,9 @@ use halo2_ecc::{bigint::CRTInteger, ecc::EcPoint, fields::FieldExtPoint};
 // Total number of rows allowable for ECC circuit
 pub const LOG_TOTAL_NUM_ROWS: u32 = 20;
 
+// Number of bits of the lookup table of the range chip
+pub const LOOKUP_BITS: usize = 13;
+
 // Cell usage accounting for EcAdd, EcMul and EcPairing
 // Roud up to nearest 100
 pub(super) const EC_ADD_CELLS: usize = 6_900; // actual: 6_851
@@ -22,14 +22,15 @@ pub use crate::witness;
 use crate::{
     evm_circuit::param::{MAX_STEP_HEIGHT, STEP_STATE_HEIGHT},
     table::{
-        BlockTable, BytecodeTable, CopyTable, ExpTable, KeccakTable, LookupTable, RwTable,
-        SigTable, TxTable, UXTable,
+        Blake2fTable, BlockTable, BytecodeTable, CopyTable, EccTable, ExpTable, HashTable,
+        KeccakTable, LookupTable, ModExpTable, Ripemd160Table, RwTable, Sha256Table, SigTable,
+        TxTable, UXTable,
     },
     util::{chunk_ctx::ChunkContextConfig, Challenges, SubCircuit, SubCircuitConfig},
     witness::{Chunk, RwMap},
 };
 use bus_mapping::{circuit_input_builder::FeatureConfig, evm::OpcodeId};
-use eth_types::Field;
+use eth_types::{evm_types::Hardfork, Field};
 use execution::ExecutionConfig;
 use itertools::Itertools;
 use strum::IntoEnumIterator;
@@ -53,6 +54,11 @@ pub struct EvmCircuitConfig<F> {
     keccak_table: KeccakTable,
     exp_table: ExpTable,
     sig_table: SigTable,
+    sha256_table: Sha256Table,
+    ripemd160_table: Ripemd160Table,
+    blake2f_table: Blake2fTable,
+    modexp_table: ModExpTable,
+    ecc_table: EccTable,
     /// rw permutation config
     pub rw_permutation_config: PermutationChipConfig<F>,
 
@@ -61,6 +67,9 @@ pub struct EvmCircuitConfig<F> {
 
     // chunk_ctx_config
     chunk_ctx_config: ChunkContextConfig<F>,
+
+    // hardfork of the fixed table
+    hardfork: Hardfork,
 }
 
 /// Circuit configuration arguments
@@ -87,6 +96,16 @@ pub struct EvmCircuitConfigArgs<F: Field> {
     pub u16_table: UXTable<16>,
     /// SigTable
     pub sig_table: SigTable,
+    /// Sha256Table
+    pub sha256_table: Sha256Table,
+    /// Ripemd160Table
+    pub ripemd160_table: Ripemd160Table,
+    /// Blake2fTable
+    pub blake2f_table: Blake2fTable,
+    /// ModExpTable
+    pub modexp_table: ModExpTable,
+    /// EccTable
+    pub ecc_table: EccTable,
     /// chunk_ctx config
     pub chunk_ctx_config: ChunkContextConfig<F>,
     /// Feature config
@@ -111,6 +130,11 @@ impl<F: Field> SubCircuitConfig<F> for EvmCircuitConfig<F> {
             u8_table,
             u16_table,
             sig_table,
+            sha256_table,
+            ripemd160_table,
+            blake2f_table,
+            modexp_table,
+            ecc_table,
             chunk_ctx_config,
             feature_config,
         }: Self::ConfigArgs,
@@ -131,6 +155,11 @@ impl<F: Field> SubCircuitConfig<F> for EvmCircuitConfig<F> {
             &keccak_table,
             &exp_table,
             &sig_table,
+            &sha256_table,
+            &ripemd160_table,
+            &blake2f_table,
+            &modexp_table,
+            &ecc_table,
             &chunk_ctx_config.chunk_ctx_table,
             &chunk_ctx_config.is_first_chunk,
             &chunk_ctx_config.is_last_chunk,
@@ -150,6 +179,11 @@ impl<F: Field> SubCircuitConfig<F> for EvmCircuitConfig<F> {
         u8_table.annotate_columns(meta);
         u16_table.annotate_columns(meta);
         sig_table.annotate_columns(meta);
+        sha256_table.annotate_columns(meta);
+        ripemd160_table.annotate_columns(meta);
+        blake2f_table.annotate_columns(meta);
+        modexp_table.annotate_columns(meta);
+        ecc_table.annotate_columns(meta);
         chunk_ctx_config.chunk_ctx_table.annotate_columns(meta);
 
         let rw_permutation_config = PermutationChip::configure(
@@ -173,9 +207,15 @@ impl<F: Field> SubCircuitConfig<F> for EvmCircuitConfig<F> {
             keccak_table,
             exp_table,
             sig_table,
+            sha256_table,
+            ripemd160_table,
+            blake2f_table,
+            modexp_table,
+            ecc_table,
             rw_permutation_config,
             chunk_ctx_config,
             pi_chunk_continuity,
+            hardfork: feature_config.hardfork,
         }
     }
 }
@@ -191,7 +231,11 @@ impl<F: Field> EvmCircuitConfig<F> {
             || "fixed table",
             |mut region| {
                 for (offset, row) in std::iter::once([F::ZERO; 4])
-                    .chain(fixed_table_tags.iter().flat_map(|tag| tag.build()))
+                    .chain(
+                        fixed_table_tags
+                            .iter()
+                            .flat_map(|tag| tag.build(self.hardfork)),
+                    )
                     .enumerate()
                 {
                     for (column, value) in self.fixed_table.iter().zip_eq(row) {
@@ -213,6 +257,9 @@ pub struct EvmCircuit<F: Field> {
     /// Chunk
     pub chunk: Option<Chunk<F>>,
     fixed_table_tags: Vec<FixedTableTag>,
+    /// Assign the steps in parallel, enabled by default with the `parallel`
+    /// feature.  The assignments are the same either way.
+    pub parallel_assignment: bool,
 }
 
 impl<F: Field> EvmCircuit<F> {
@@ -222,6 +269,7 @@ impl<F: Field> EvmCircuit<F> {
             block: Some(block),
             chunk: Some(chunk),
             fixed_table_tags: FixedTableTag::iter().collect(),
+            parallel_assignment: cfg!(feature = "parallel"),
         }
     }
     #[cfg(any(test, feature = "test-circuits"))]
@@ -233,6 +281,7 @@ impl<F: Field> EvmCircuit<F> {
             block: Some(block),
             chunk: Some(chunk),
             fixed_table_tags,
+            parallel_assignment: cfg!(feature = "parallel"),
         }
     }
     #[cfg(any(test, feature = "test-circuits"))]
@@ -293,7 +342,7 @@ impl<F: Field> SubCircuit<F> for EvmCircuit<F> {
             Self::get_num_rows_required(block, chunk);
         let num_rows_required_for_fixed_table: usize = detect_fixed_table_tags(block)
             .iter()
-            .map(|tag| tag.build::<F>().count())
+            .map(|tag| tag.build::<F>(block.feature_config.hardfork).count())
             .sum();
         (
             std::cmp::max(
@@ -316,9 +365,13 @@ impl<F: Field> SubCircuit<F> for EvmCircuit<F> {
 
         config.load_fixed_table(layouter, self.fixed_table_tags.clone())?;
 
-        let _max_offset_index = config
-            .execution
-            .assign_block(layouter, block, chunk, challenges)?;
+        let _max_offset_index = config.execution.assign_block(
+            layouter,
+            block,
+            chunk,
+            challenges,
+            self.parallel_assignment,
+        )?;
 
         let (rw_rows_padding, _) = RwMap::table_assignments_padding(
             &chunk.chrono_rws.table_assignments(true),
@@ -522,6 +575,11 @@ impl<F: Field> Circuit<F> for EvmCircuit<F> {
         let chunk_ctx_config = ChunkContextConfig::new(meta, &challenges_expr);
 
         let sig_table = SigTable::construct(meta);
+        let sha256_table = Sha256Table::construct(meta);
+        let ripemd160_table = Ripemd160Table::construct(meta);
+        let blake2f_table = Blake2fTable::construct(meta);
+        let modexp_table = ModExpTable::construct(meta);
+        let ecc_table = EccTable::construct(meta);
         (
             EvmCircuitConfig::new(
                 meta,
@@ -537,6 +595,11 @@ impl<F: Field> Circuit<F> for EvmCircuit<F> {
                     u8_table,
                     u16_table,
                     sig_table,
+                    sha256_table,
+                    ripemd160_table,
+                    blake2f_table,
+                    modexp_table,
+                    ecc_table,
                     chunk_ctx_config,
                     feature_config: params,
                 },
@@ -582,6 +645,33 @@ impl<F: Field> Circuit<F> for EvmCircuit<F> {
         config.u8_table.load(&mut layouter)?;
         config.u16_table.load(&mut layouter)?;
         config.sig_table.dev_load(&mut layouter, block)?;
+        config.sha256_table.dev_load(
+            &mut layouter,
+            &block.precompile_events.get_sha256_events(),
+            &challenges,
+        )?;
+        config.ripemd160_table.dev_load(
+            &mut layouter,
+            &block.precompile_events.get_ripemd160_events(),
+            &challenges,
+        )?;
+        config.blake2f_table.dev_load(
+            &mut layouter,
+            &block.precompile_events.get_blake2f_events(),
+            &challenges,
+        )?;
+        config.modexp_table.dev_load(
+            &mut layouter,
+            &block.precompile_events.get_modexp_events(),
+            &challenges,
+        )?;
+        config.ecc_table.dev_load(
+            &mut layouter,
+            &block.precompile_events.get_ec_add_events(),
+            &block.precompile_events.get_ec_mul_events(),
+            &block.precompile_events.get_ec_pairing_events(),
+            &challenges,
+        )?;
 
         // synthesize chunk context
         config.chunk_ctx_config.assign_chunk_context(
@@ -603,7 +693,9 @@ mod evm_circuit_stats {
         witness::{block_convert, chunk_convert},
     };
     use bus_mapping::{
-        circuit_input_builder::{FeatureConfig, FixedCParams},
+        circuit_input_builder::{
+            FeatureConfig, FixedCParams, KeccakCircuitParams, PrecompileEcParams,
+        },
         mock::BlockData,
     };
 
@@ -696,7 +788,9 @@ mod evm_circuit_stats {
             max_bytecode: 512,
             max_evm_rows: 0,
             max_keccak_rows: 0,
+            keccak_params: KeccakCircuitParams::default(),
             max_vertical_circuit_rows: 0,
+            max_ec_ops: PrecompileEcParams::default(),
         };
         let builder = BlockData::new_from_geth_data_with_params(block.clone(), circuits_params)
             .new_circuit_input_builder()
@@ -24,6 +24,7 @@ use crate::{
     table::{chunk_ctx_table::ChunkCtxFieldTag, LookupTable},
     util::{
         cell_manager::{CMFixedWidthStrategy, CellManager, CellType},
+        parallel::{par_map, split_range, RegionRecord},
         Challenges, Expr,
     },
 };
@@ -32,7 +33,7 @@ use eth_types::{evm_unimplemented, Field};
 
 use gadgets::{is_zero::IsZeroConfig, util::not};
 use halo2_proofs::{
-    circuit::{Layouter, Region, Value},
+    circuit::{layouter::RegionLayouter, Layouter, Region, Value},
     plonk::{
         Advice, Column, ConstraintSystem, Error, Expression, FirstPhase, Fixed, SecondPhase,
         Selector, ThirdPhase, VirtualCells,
@@ -52,6 +53,8 @@ mod balance;
 mod begin_chunk;
 mod begin_tx;
 mod bitwise;
+mod blobbasefee;
+mod blobhash;
 mod block_ctx;
 mod blockhash;
 mod byte;
@@ -66,12 +69,12 @@ mod codecopy;
 mod codesize;
 mod comparator;
 mod create;
-mod dummy;
 mod dup;
 mod end_block;
 mod end_chunk;
 mod end_tx;
 mod error_code_store;
+mod error_depth;
 mod error_invalid_creation_code;
 mod error_invalid_jump;
 mod error_invalid_opcode;
@@ -84,6 +87,7 @@ mod error_oog_exp;
 mod error_oog_log;
 mod error_oog_memory_copy;
 mod error_oog_precompile;
+mod error_oog_self_destruct;
 mod error_oog_sha3;
 mod error_oog_sload_sstore;
 mod error_oog_static_memory;
@@ -103,6 +107,7 @@ mod jump;
 mod jumpdest;
 mod jumpi;
 mod logs;
+mod mcopy;
 mod memory;
 mod msize;
 mod mul_div_mod;
@@ -121,6 +126,7 @@ mod returndatasize;
 mod sar;
 mod sdiv_smod;
 mod selfbalance;
+mod selfdestruct;
 mod sha3;
 mod shl_shr;
 mod signed_comparator;
@@ -142,6 +148,8 @@ use address::AddressGadget;
 use balance::BalanceGadget;
 use begin_tx::BeginTxGadget;
 use bitwise::BitwiseGadget;
+use blobbasefee::BlobBaseFeeGadget;
+use blobhash::BlobHashGadget;
 use blockhash::BlockHashGadget;
 use byte::ByteGadget;
 use calldatacopy::CallDataCopyGadget;
@@ -155,11 +163,11 @@ use codecopy::CodeCopyGadget;
 use codesize::CodesizeGadget;
 use comparator::ComparatorGadget;
 use create::CreateGadget;
-use dummy::DummyGadget;
 use dup::DupGadget;
 use end_block::EndBlockGadget;
 use end_tx::EndTxGadget;
 use error_code_store::ErrorCodeStoreGadget;
+use error_depth::ErrorDepthGadget;
 use error_invalid_creation_code::ErrorInvalidCreationCodeGadget;
 use error_invalid_jump::ErrorInvalidJumpGadget;
 use error_invalid_opcode::ErrorInvalidOpcodeGadget;
@@ -171,6 +179,7 @@ use error_oog_dynamic_memory::ErrorOOGDynamicMemoryGadget;
 use error_oog_exp::ErrorOOGExpGadget;
 use error_oog_log::ErrorOOGLogGadget;
 use error_oog_memory_copy::ErrorOOGMemoryCopyGadget;
+use error_oog_self_destruct::ErrorOOGSelfDestructGadget;
 use error_oog_sha3::ErrorOOGSha3Gadget;
 use error_oog_sload_sstore::ErrorOOGSloadSstoreGadget;
 use error_oog_static_memory::ErrorOOGStaticMemoryGadget;
@@ -190,6 +199,7 @@ use jump::JumpGadget;
 use jumpdest::JumpdestGadget;
 use jumpi::JumpiGadget;
 use logs::LogGadget;
+use mcopy::McopyGadget;
 
 use crate::evm_circuit::execution::error_oog_precompile::ErrorOOGPrecompileGadget;
 use memory::MemoryGadget;
@@ -201,7 +211,10 @@ use origin::OriginGadget;
 use padding::PaddingGadget;
 use pc::PcGadget;
 use pop::PopGadget;
-use precompiles::{EcrecoverGadget, IdentityGadget};
+use precompiles::{
+    Blake2fGadget, EcAddGadget, EcMulGadget, EcPairingGadget, EcrecoverGadget,
+    HashPrecompileGadget, IdentityGadget, ModExpGadget,
+};
 use push::PushGadget;
 use return_revert::ReturnRevertGadget;
 use returndatacopy::ReturnDataCopyGadget;
@@ -209,6 +222,7 @@ use returndatasize::ReturnDataSizeGadget;
 use sar::SarGadget;
 use sdiv_smod::SignedDivModGadget;
 use selfbalance::SelfbalanceGadget;
+use selfdestruct::SelfDestructGadget;
 use shl_shr::ShlShrGadget;
 use signed_comparator::SignedComparatorGadget;
 use signextend::SignextendGadget;
@@ -283,6 +297,8 @@ pub struct ExecutionConfig<F> {
     calldatasize_gadget: Box<CallDataSizeGadget<F>>,
     caller_gadget: Box<CallerGadget<F>>,
     chainid_gadget: Box<ChainIdGadget<F>>,
+    blobhash_gadget: Box<BlobHashGadget<F>>,
+    blobbasefee_gadget: Box<BlobBaseFeeGadget<F>>,
     codecopy_gadget: Box<CodeCopyGadget<F>>,
     codesize_gadget: Box<CodesizeGadget<F>>,
     comparator_gadget: Box<ComparatorGadget<F>>,
@@ -298,6 +314,7 @@ pub struct ExecutionConfig<F> {
     jumpdest_gadget: Box<JumpdestGadget<F>>,
     jumpi_gadget: Box<JumpiGadget<F>>,
     log_gadget: Box<LogGadget<F>>,
+    mcopy_gadget: Box<McopyGadget<F>>,
     memory_gadget: Box<MemoryGadget<F>>,
     msize_gadget: Box<MsizeGadget<F>>,
     mul_div_mod_gadget: Box<MulDivModGadget<F>>,
@@ -317,7 +334,7 @@ pub struct ExecutionConfig<F> {
     returndatacopy_gadget: Box<ReturnDataCopyGadget<F>>,
     create_gadget: Box<CreateGadget<F, false, { ExecutionState::CREATE }>>,
     create2_gadget: Box<CreateGadget<F, true, { ExecutionState::CREATE2 }>>,
-    selfdestruct_gadget: Box<DummyGadget<F, 1, 0, { ExecutionState::SELFDESTRUCT }>>,
+    selfdestruct_gadget: Box<SelfDestructGadget<F>>,
     signed_comparator_gadget: Box<SignedComparatorGadget<F>>,
     signextend_gadget: Box<SignextendGadget<F>>,
     sload_gadget: Box<SloadGadget<F>>,
@@ -338,28 +355,30 @@ pub struct ExecutionConfig<F> {
     error_oog_static_memory_gadget: Box<ErrorOOGStaticMemoryGadget<F>>,
     error_stack: Box<ErrorStackGadget<F>>,
     error_write_protection: Box<ErrorWriteProtectionGadget<F>>,
+    error_depth: Box<ErrorDepthGadget<F>>,
     error_oog_dynamic_memory_gadget: Box<ErrorOOGDynamicMemoryGadget<F>>,
     error_oog_log: Box<ErrorOOGLogGadget<F>>,
     error_oog_sha3: Box<ErrorOOGSha3Gadget<F>>,
     error_oog_account_access: Box<ErrorOOGAccountAccessGadget<F>>,
-    error_oog_ext_codecopy: Box<DummyGadget<F, 0, 0, { ExecutionState::ErrorOutOfGasEXTCODECOPY }>>,
     error_oog_create: Box<ErrorOOGCreateGadget<F>>,
-    error_oog_self_destruct:
-        Box<DummyGadget<F, 0, 0, { ExecutionState::ErrorOutOfGasSELFDESTRUCT }>>,
+    error_oog_self_destruct: Box<ErrorOOGSelfDestructGadget<F>>,
     error_oog_code_store: Box<ErrorCodeStoreGadget<F>>,
     error_invalid_jump: Box<ErrorInvalidJumpGadget<F>>,
     error_invalid_opcode: Box<ErrorInvalidOpcodeGadget<F>>,
-    #[allow(dead_code, reason = "under active development")]
-    error_depth: Box<DummyGadget<F, 0, 0, { ExecutionState::ErrorDepth }>>,
-    #[allow(dead_code, reason = "under active development")]
-    error_contract_address_collision:
-        Box<DummyGadget<F, 0, 0, { ExecutionState::ErrorContractAddressCollision }>>,
     error_invalid_creation_code: Box<ErrorInvalidCreationCodeGadget<F>>,
     error_precompile_failed: Box<ErrorPrecompileFailedGadget<F>>,
     error_return_data_out_of_bound: Box<ErrorReturnDataOutOfBoundGadget<F>>,
     // precompile calls
     precompile_ecrecover_gadget: Box<EcrecoverGadget<F>>,
+    precompile_sha256_gadget: Box<HashPrecompileGadget<F, { ExecutionState::PrecompileSha256 }>>,
+    precompile_ripemd160_gadget:
+        Box<HashPrecompileGadget<F, { ExecutionState::PrecompileRipemd160 }>>,
     precompile_identity_gadget: Box<IdentityGadget<F>>,
+    precompile_blake2f_gadget: Box<Blake2fGadget<F>>,
+    precompile_modexp_gadget: Box<ModExpGadget<F>>,
+    precompile_bn256add_gadget: Box<EcAddGadget<F>>,
+    precompile_bn256mul_gadget: Box<EcMulGadget<F>>,
+    precompile_bn256pairing_gadget: Box<EcPairingGadget<F>>,
     invalid_tx: Option<Box<InvalidTxGadget<F>>>,
 }
 
@@ -382,6 +401,11 @@ impl<F: Field> ExecutionConfig<F> {
         keccak_table: &dyn LookupTable<F>,
         exp_table: &dyn LookupTable<F>,
         sig_table: &dyn LookupTable<F>,
+        sha256_table: &dyn LookupTable<F>,
+        ripemd160_table: &dyn LookupTable<F>,
+        blake2f_table: &dyn LookupTable<F>,
+        modexp_table: &dyn LookupTable<F>,
+        ecc_table: &dyn LookupTable<F>,
         chunk_ctx_table: &dyn LookupTable<F>,
         is_first_chunk: &IsZeroConfig<F>,
         is_last_chunk: &IsZeroConfig<F>,
@@ -611,6 +635,8 @@ impl<F: Field> ExecutionConfig<F> {
             calldatasize_gadget: configure_gadget!(),
             caller_gadget: configure_gadget!(),
             chainid_gadget: configure_gadget!(),
+            blobhash_gadget: configure_gadget!(),
+            blobbasefee_gadget: configure_gadget!(),
             codecopy_gadget: configure_gadget!(),
             codesize_gadget: configure_gadget!(),
             comparator_gadget: configure_gadget!(),
@@ -624,6 +650,7 @@ impl<F: Field> ExecutionConfig<F> {
             jumpdest_gadget: configure_gadget!(),
             jumpi_gadget: configure_gadget!(),
             log_gadget: configure_gadget!(),
+            mcopy_gadget: configure_gadget!(),
             memory_gadget: configure_gadget!(),
             msize_gadget: configure_gadget!(),
             mul_div_mod_gadget: configure_gadget!(),
@@ -670,7 +697,6 @@ impl<F: Field> ExecutionConfig<F> {
             error_oog_memory_copy: configure_gadget!(),
             error_oog_account_access: configure_gadget!(),
             error_oog_sha3: configure_gadget!(),
-            error_oog_ext_codecopy: configure_gadget!(),
             error_oog_exp: configure_gadget!(),
             error_oog_create: configure_gadget!(),
             error_oog_self_destruct: configure_gadget!(),
@@ -679,13 +705,19 @@ impl<F: Field> ExecutionConfig<F> {
             error_invalid_opcode: configure_gadget!(),
             error_write_protection: configure_gadget!(),
             error_depth: configure_gadget!(),
-            error_contract_address_collision: configure_gadget!(),
             error_invalid_creation_code: configure_gadget!(),
             error_precompile_failed: configure_gadget!(),
             error_return_data_out_of_bound: configure_gadget!(),
             // precompile calls
             precompile_identity_gadget: configure_gadget!(),
             precompile_ecrecover_gadget: configure_gadget!(),
+            precompile_sha256_gadget: configure_gadget!(),
+            precompile_ripemd160_gadget: configure_gadget!(),
+            precompile_blake2f_gadget: configure_gadget!(),
+            precompile_modexp_gadget: configure_gadget!(),
+            precompile_bn256add_gadget: configure_gadget!(),
+            precompile_bn256mul_gadget: configure_gadget!(),
+            precompile_bn256pairing_gadget: configure_gadget!(),
             // step and presets
             step: step_curr,
             height_map,
@@ -707,6 +739,11 @@ impl<F: Field> ExecutionConfig<F> {
             keccak_table,
             exp_table,
             sig_table,
+            sha256_table,
+            ripemd160_table,
+            blake2f_table,
+            modexp_table,
+            ecc_table,
             chunk_ctx_table,
             &challenges,
             &cell_manager,
@@ -1052,6 +1089,11 @@ impl<F: Field> ExecutionConfig<F> {
         keccak_table: &dyn LookupTable<F>,
         exp_table: &dyn LookupTable<F>,
         sig_table: &dyn LookupTable<F>,
+        sha256_table: &dyn LookupTable<F>,
+        ripemd160_table: &dyn LookupTable<F>,
+        blake2f_table: &dyn LookupTable<F>,
+        modexp_table: &dyn LookupTable<F>,
+        ecc_table: &dyn LookupTable<F>,
         chunk_ctx_table: &dyn LookupTable<F>,
         challenges: &Challenges<Expression<F>>,
         cell_manager: &CellManager<CMFixedWidthStrategy>,
@@ -1073,6 +1115,11 @@ impl<F: Field> ExecutionConfig<F> {
                         Table::Keccak => keccak_table,
                         Table::Exp => exp_table,
                         Table::Sig => sig_table,
+                        Table::Sha256 => sha256_table,
+                        Table::Ripemd160 => ripemd160_table,
+                        Table::Blake2f => blake2f_table,
+                        Table::ModExp => modexp_table,
+                        Table::Ecc => ecc_table,
                         Table::ChunkCtx => chunk_ctx_table,
                     }
                     .table_exprs(meta);
@@ -1126,12 +1173,15 @@ impl<F: Field> ExecutionConfig<F> {
     /// Assign block
     /// When exact is enabled, assign exact steps in block without padding for
     /// unit test purpose
+    /// When parallel is enabled, the real steps are split in ranges assigned in
+    /// parallel, see [`Self::assign_steps_in_parallel`].
     pub fn assign_block(
         &self,
         layouter: &mut impl Layouter<F>,
         block: &Block<F>,
         chunk: &Chunk<F>,
         challenges: &Challenges<Value<F>>,
+        parallel: bool,
     ) -> Result<usize, Error> {
         // Track number of calls to `layouter.assign_region` as layouter assignment passes.
         let mut assign_pass = 0;
@@ -1171,7 +1221,7 @@ impl<F: Field> ExecutionConfig<F> {
                     }
                 };
 
-                let mut tx_call_steps = maybe_begin_chunk
+                let tx_call_steps = maybe_begin_chunk
                     .into_iter()
                     .chain(chunk_txs.iter().flat_map(|tx| {
                         tx.steps()
@@ -1186,10 +1236,33 @@ impl<F: Field> ExecutionConfig<F> {
                     }))
                     // this dummy step is just for real step assignment proceed to `second last`
                     .chain(std::iter::once((&dummy_tx, &cur_chunk_last_call, padding)))
-                    .peekable();
+                    .collect::<Vec<_>>();
 
                 let evm_rows = chunk.fixed_param.max_evm_rows;
 
+                let mut second_last_real_step = None;
+                let mut second_last_real_step_offset = 0;
+
+                // part1 in parallel: assign real steps, before the region is borrowed by
+                // `assign_padding_or_step`
+                if parallel {
+                    let offsets = self.assign_steps_in_parallel(
+                        &mut region,
+                        offset,
+                        block,
+                        chunk,
+                        &tx_call_steps,
+                        challenges,
+                        assign_pass,
+                    )?;
+                    let last = tx_call_steps.len() - 1;
+                    if last > 0 {
+                        second_last_real_step = Some(tx_call_steps[last - 1]);
+                        second_last_real_step_offset = offsets[last - 1];
+                    }
+                    offset = offsets[last];
+                }
+
                 let mut assign_padding_or_step = |cur_tx_call_step: TxCallStep,
                                                   mut offset: usize,
                                                   next_tx_call_step: Option<TxCallStep>,
@@ -1248,13 +1321,11 @@ impl<F: Field> ExecutionConfig<F> {
                     Ok(offset) // return latest offset
                 };
 
-                let mut second_last_real_step = None;
-                let mut second_last_real_step_offset = 0;
-
-                // part1: assign real steps
+                // part1 serially: assign real steps
+                let mut tx_call_steps = tx_call_steps.into_iter().peekable();
                 while let Some(cur) = tx_call_steps.next() {
                     let next = tx_call_steps.peek();
-                    if next.is_none() {
+                    if parallel || next.is_none() {
                         break;
                     }
 
@@ -1386,6 +1457,60 @@ impl<F: Field> ExecutionConfig<F> {
         region.name_column(|| "Copy_Constr_const", self.constants);
     }
 
+    /// Assign the real steps, which are all the steps but the last one, given
+    /// as their next step only.  The offset of each step is known in advance
+    /// from the heights of the previous ones, so the steps are split in ranges
+    /// assigned in parallel, and then replayed into `region` in order: the
+    /// assignments are the same as when assigning the steps one after the
+    /// other.  Returns the offsets of all the steps.
+    #[allow(clippy::too_many_arguments)]
+    fn assign_steps_in_parallel(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        block: &Block<F>,
+        chunk: &Chunk<F>,
+        steps: &[TxCallStep],
+        challenges: &Challenges<Value<F>>,
+        assign_pass: usize,
+    ) -> Result<Vec<usize>, Error> {
+        let offsets = steps
+            .iter()
+            .scan(offset, |offset, (_, _, step)| {
+                let step_offset = *offset;
+                *offset += step.execution_state().get_step_height();
+                Some(step_offset)
+            })
+            .collect::<Vec<_>>();
+
+        let records = par_map(&split_range(steps.len() - 1), |range| {
+            let mut record = RegionRecord::default();
+            let mut range_region = Region::from(&mut record as &mut dyn RegionLayouter<F>);
+            for idx in range.clone() {
+                let (_, _, step) = steps[idx];
+                let height = step.execution_state().get_step_height();
+                self.assign_exec_step(
+                    &mut range_region,
+                    offsets[idx],
+                    block,
+                    chunk,
+                    steps[idx],
+                    height,
+                    Some(steps[idx + 1]),
+                    challenges,
+                    assign_pass,
+                )?;
+                self.assign_q_step(&mut range_region, offsets[idx], height)?;
+            }
+            Ok::<_, Error>(record)
+        });
+        for record in records {
+            record?.replay(region)?;
+        }
+
+        Ok(offsets)
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn assign_same_exec_step_in_range(
         &self,
@@ -1540,6 +1665,8 @@ impl<F: Field> ExecutionConfig<F> {
             ExecutionState::CALLER => assign_exec_step!(self.caller_gadget),
             ExecutionState::CALLVALUE => assign_exec_step!(self.call_value_gadget),
             ExecutionState::CHAINID => assign_exec_step!(self.chainid_gadget),
+            ExecutionState::BLOBHASH => assign_exec_step!(self.blobhash_gadget),
+            ExecutionState::BLOBBASEFEE => assign_exec_step!(self.blobbasefee_gadget),
             ExecutionState::CODECOPY => assign_exec_step!(self.codecopy_gadget),
             ExecutionState::CODESIZE => assign_exec_step!(self.codesize_gadget),
             ExecutionState::CMP => assign_exec_step!(self.comparator_gadget),
@@ -1554,6 +1681,7 @@ impl<F: Field> ExecutionConfig<F> {
             ExecutionState::JUMPDEST => assign_exec_step!(self.jumpdest_gadget),
             ExecutionState::JUMPI => assign_exec_step!(self.jumpi_gadget),
             ExecutionState::LOG => assign_exec_step!(self.log_gadget),
+            ExecutionState::MCOPY => assign_exec_step!(self.mcopy_gadget),
             ExecutionState::MEMORY => assign_exec_step!(self.memory_gadget),
             ExecutionState::MSIZE => assign_exec_step!(self.msize_gadget),
             ExecutionState::MUL_DIV_MOD => assign_exec_step!(self.mul_div_mod_gadget),
@@ -1572,12 +1700,10 @@ impl<F: Field> ExecutionConfig<F> {
             ExecutionState::BLOCKCTX => assign_exec_step!(self.block_ctx_gadget),
             ExecutionState::BLOCKHASH => assign_exec_step!(self.blockhash_gadget),
             ExecutionState::SELFBALANCE => assign_exec_step!(self.selfbalance_gadget),
-            // dummy gadgets
             ExecutionState::EXTCODECOPY => assign_exec_step!(self.extcodecopy_gadget),
             ExecutionState::CREATE => assign_exec_step!(self.create_gadget),
             ExecutionState::CREATE2 => assign_exec_step!(self.create2_gadget),
             ExecutionState::SELFDESTRUCT => assign_exec_step!(self.selfdestruct_gadget),
-            // end of dummy gadgets
             ExecutionState::SHA3 => assign_exec_step!(self.sha3_gadget),
             ExecutionState::SHL_SHR => assign_exec_step!(self.shl_shr_gadget),
             ExecutionState::SIGNEXTEND => assign_exec_step!(self.signextend_gadget),
@@ -1618,9 +1744,6 @@ impl<F: Field> ExecutionConfig<F> {
             ExecutionState::ErrorOutOfGasSHA3 => {
                 assign_exec_step!(self.error_oog_sha3)
             }
-            ExecutionState::ErrorOutOfGasEXTCODECOPY => {
-                assign_exec_step!(self.error_oog_ext_codecopy)
-            }
             ExecutionState::ErrorOutOfGasEXP => {
                 assign_exec_step!(self.error_oog_exp)
             }
@@ -1630,7 +1753,6 @@ impl<F: Field> ExecutionConfig<F> {
             ExecutionState::ErrorOutOfGasSELFDESTRUCT => {
                 assign_exec_step!(self.error_oog_self_destruct)
             }
-
             ExecutionState::ErrorCodeStore => {
                 assign_exec_step!(self.error_oog_code_store)
             }
@@ -1647,6 +1769,9 @@ impl<F: Field> ExecutionConfig<F> {
             ExecutionState::ErrorWriteProtection => {
                 assign_exec_step!(self.error_write_protection)
             }
+            ExecutionState::ErrorDepth => {
+                assign_exec_step!(self.error_depth)
+            }
             ExecutionState::ErrorInvalidCreationCode => {
                 assign_exec_step!(self.error_invalid_creation_code)
             }
@@ -1659,9 +1784,30 @@ impl<F: Field> ExecutionConfig<F> {
             ExecutionState::PrecompileEcrecover => {
                 assign_exec_step!(self.precompile_ecrecover_gadget)
             }
+            ExecutionState::PrecompileSha256 => {
+                assign_exec_step!(self.precompile_sha256_gadget)
+            }
+            ExecutionState::PrecompileRipemd160 => {
+                assign_exec_step!(self.precompile_ripemd160_gadget)
+            }
             ExecutionState::PrecompileIdentity => {
                 assign_exec_step!(self.precompile_identity_gadget)
             }
+            ExecutionState::PrecompileBlake2f => {
+                assign_exec_step!(self.precompile_blake2f_gadget)
+            }
+            ExecutionState::PrecompileBigModExp => {
+                assign_exec_step!(self.precompile_modexp_gadget)
+            }
+            ExecutionState::PrecompileBn256Add => {
+                assign_exec_step!(self.precompile_bn256add_gadget)
+            }
+            ExecutionState::PrecompileBn256ScalarMul => {
+                assign_exec_step!(self.precompile_bn256mul_gadget)
+            }
+            ExecutionState::PrecompileBn256Pairing => {
+                assign_exec_step!(self.precompile_bn256pairing_gadget)
+            }
 
             unimpl_state => evm_unimplemented!("unimplemented ExecutionState: {:?}", unimpl_state),
         }
@@ -183,7 +183,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             tx.is_create.expr(),
             tx.value.clone(),
             &mut reversion_info,
-            Some(tx.mul_gas_fee_by_gas.product().clone()),
+            Some(tx.gas_fee.clone()),
         );
 
         let caller_nonce_hash_bytes = cb.query_word32();
@@ -262,6 +262,14 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                 WordLoHi::zero(),
                 Some(&mut reversion_info),
             );
+            // EIP 6780, mark the contract as created in the current transaction
+            cb.tx_created_account_write(
+                tx_id.expr(),
+                call_callee_address.to_word(),
+                1.expr(),
+                0.expr(),
+                Some(&mut reversion_info),
+            );
             for (field_tag, value) in [
                 (CallContextFieldTag::Depth, WordLoHi::one()),
                 (
@@ -299,7 +307,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             }
 
             cb.require_step_state_transition(StepStateTransition {
-                // 21 + a reads and writes:
+                // 22 + a reads and writes:
                 //   - Write CallContext TxId
                 //   - Write CallContext RwCounterEndOfReversion
                 //   - Write CallContext IsPersistent
@@ -311,6 +319,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                 //   - Write TxAccessListAccount (Coinbase) for EIP-3651
                 //   - a TransferWithGasFeeGadget
                 //   - Write Account (Callee) Nonce (Reversible)
+                //   - Write TxCreatedAccount (Callee) (Reversible)
                 //   - Write CallContext Depth
                 //   - Write CallContext CallerAddress
                 //   - Write CallContext CalleeAddress
@@ -325,17 +334,18 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                 //   - Write CallContext IsCreate
                 //   - Write CallContext CodeHash
                 rw_counter: Delta(
-                    23.expr() + transfer_with_gas_fee.rw_delta() + PRECOMPILE_COUNT.expr(),
+                    24.expr() + transfer_with_gas_fee.rw_delta() + PRECOMPILE_COUNT.expr(),
                 ),
                 call_id: To(call_id.expr()),
                 is_root: To(true.expr()),
                 is_create: To(tx.is_create.expr()),
                 code_hash: To(cb.curr.state.code_hash.to_word()),
                 gas_left: To(gas_left.clone()),
-                // There are a + 1 reversible writes:
+                // There are a + 2 reversible writes:
                 //  - a TransferWithGasFeeGadget
                 //  - Callee Account Nonce
-                reversible_write_counter: To(transfer_with_gas_fee.reversible_w_delta() + 1.expr()),
+                //  - Callee TxCreatedAccount
+                reversible_write_counter: To(transfer_with_gas_fee.reversible_w_delta() + 2.expr()),
                 log_id: To(0.expr()),
                 ..StepStateTransition::new_context()
             });
@@ -503,12 +513,13 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
         rws.offset_add(PRECOMPILE_COUNT as usize);
 
         let is_coinbase_warm = rws.next().tx_access_list_value_pair().1;
+        let hardfork = block.feature_config.hardfork;
+        let is_precompiled_callee = is_precompiled(&tx.to_or_contract_addr(), hardfork);
         let mut callee_code_hash = zero;
-        if !is_precompiled(&tx.to_or_contract_addr()) {
+        if !is_precompiled_callee {
             callee_code_hash = rws.next().account_codehash_pair().1;
         }
-        let callee_exists =
-            is_precompiled(&tx.to_or_contract_addr()) || !callee_code_hash.is_zero();
+        let callee_exists = is_precompiled_callee || !callee_code_hash.is_zero();
         self.transfer_with_gas_fee.assign(
             region,
             offset,
@@ -519,7 +530,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             Some(gas_fee),
         )?;
         self.begin_tx.assign(region, offset, tx)?;
-        self.tx.assign(region, offset, tx)?;
+        self.tx.assign(region, offset, block, tx)?;
 
         self.tx_caller_address_is_zero.assign_u256(
             region,
@@ -0,0 +1,173 @@
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        param::N_BYTES_U64,
+        step::ExecutionState,
+        util::{
+            common_gadget::{SameContextGadget, WordByteCapGadget},
+            constraint_builder::{
+                ConstrainBuilderCommon, EVMConstraintBuilder, StepStateTransition,
+                Transition::Delta,
+            },
+            CachedRegion, Cell, WordLoHi,
+        },
+        witness::{Block, Call, Chunk, ExecStep, Transaction},
+    },
+    table::{CallContextFieldTag, TxContextFieldTag},
+    util::word::WordExpr,
+};
+use bus_mapping::evm::OpcodeId;
+use eth_types::Field;
+use gadgets::util::{not, Expr};
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+#[derive(Clone, Debug)]
+pub(crate) struct BlobHashGadget<F> {
+    same_context: SameContextGadget<F>,
+    tx_id: Cell<F>,
+    blob_hashes_length: Cell<F>,
+    index: WordByteCapGadget<F, N_BYTES_U64>,
+    blob_hash: WordLoHi<Cell<F>>,
+}
+
+impl<F: Field> ExecutionGadget<F> for BlobHashGadget<F> {
+    const NAME: &'static str = "BLOBHASH";
+
+    const EXECUTION_STATE: ExecutionState = ExecutionState::BLOBHASH;
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let blob_hashes_length = cb.query_cell();
+        let index = WordByteCapGadget::construct(cb, blob_hashes_length.expr());
+        cb.stack_pop(index.original_word().to_word());
+
+        // Lookup in call_ctx the TxId
+        let tx_id = cb.call_context(None, CallContextFieldTag::TxId);
+        cb.tx_context_lookup(
+            tx_id.expr(),
+            TxContextFieldTag::BlobVersionedHashesLength,
+            None,
+            WordLoHi::from_lo_unchecked(blob_hashes_length.expr()),
+        );
+
+        let blob_hash = cb.query_word_unchecked();
+        cb.condition(index.lt_cap(), |cb| {
+            cb.tx_context_lookup(
+                tx_id.expr(),
+                TxContextFieldTag::BlobVersionedHash,
+                Some(index.valid_value()),
+                blob_hash.to_word(),
+            );
+        });
+        cb.condition(not::expr(index.lt_cap()), |cb| {
+            cb.require_zero_word(
+                "Out of range index for blob hash lookup",
+                blob_hash.to_word(),
+            );
+        });
+
+        cb.stack_push(blob_hash.to_word());
+
+        let step_state_transition = StepStateTransition {
+            rw_counter: Delta(3.expr()),
+            program_counter: Delta(1.expr()),
+            gas_left: Delta(-OpcodeId::BLOBHASH.constant_gas_cost().expr()),
+            ..Default::default()
+        };
+
+        let opcode = cb.query_cell();
+        let same_context = SameContextGadget::construct(cb, opcode, step_state_transition);
+        Self {
+            same_context,
+            tx_id,
+            blob_hashes_length,
+            index,
+            blob_hash,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block<F>,
+        _chunk: &Chunk<F>,
+        tx: &Transaction,
+        _: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        self.same_context.assign_exec_step(region, offset, step)?;
+
+        self.tx_id
+            .assign(region, offset, Value::known(F::from(tx.id)))?;
+
+        let blob_hashes_length = F::from(tx.blob_versioned_hashes.len() as u64);
+        self.blob_hashes_length
+            .assign(region, offset, Value::known(blob_hashes_length))?;
+
+        let index = block.get_rws(step, 0).stack_value();
+        self.index
+            .assign(region, offset, index, blob_hashes_length)?;
+
+        self.blob_hash
+            .assign_u256(region, offset, block.get_rws(step, 2).stack_value())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_util::CircuitTestBuilder;
+    use eth_types::{bytecode, Word, H256, U256};
+    use mock::test_ctx::{helpers::*, TestContext};
+
+    fn test_ok(index: U256, num_blob_hashes: u8) {
+        let code = bytecode! {
+            PUSH32(index)
+            BLOBHASH
+            STOP
+        };
+
+        let blob_hashes = (1..=num_blob_hashes)
+            .map(|i| {
+                let mut hash = [i; 32];
+                // versioned hashes must start with VERSIONED_HASH_VERSION_KZG
+                hash[0] = 0x01;
+                H256(hash)
+            })
+            .collect::<Vec<_>>();
+
+        let ctx = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+                // A blob tx must carry at least one blob, so fall back to a
+                // legacy tx when there is none.
+                if !blob_hashes.is_empty() {
+                    txs[0]
+                        .transaction_type(3)
+                        .max_fee_per_blob_gas(Word::one())
+                        .blob_versioned_hashes(blob_hashes.clone());
+                }
+            },
+            |block, _tx| block,
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+
+    #[test]
+    fn blobhash_gadget_simple() {
+        test_ok(0.into(), 1);
+        test_ok(5.into(), 6);
+    }
+
+    #[test]
+    fn blobhash_gadget_out_of_range() {
+        test_ok(1.into(), 1);
+        test_ok(0.into(), 0);
+        test_ok(U256::MAX, 2);
+    }
+}
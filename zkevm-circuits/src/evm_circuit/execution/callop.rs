@@ -45,7 +45,7 @@ use std::cmp::min;
 
 /// Gadget for call related opcodes. It supports `OpcodeId::CALL`,
 /// `OpcodeId::CALLCODE`, `OpcodeId::DELEGATECALL` and `OpcodeId::STATICCALL`.
-/// both for successful and failure(insufficient balance error) cases.
+/// both for successful and failure (insufficient balance and depth error) cases.
 #[derive(Clone, Debug)]
 
 pub(crate) struct CallOpGadget<F> {
@@ -84,6 +84,8 @@ pub(crate) struct CallOpGadget<F> {
     precompile_input_bytes_rlc: Cell<F>,
     precompile_output_bytes_rlc: Cell<F>,
     precompile_return_bytes_rlc: Cell<F>,
+    // the number of rounds of a BLAKE2F call, i.e. the first 4 input bytes.
+    precompile_blake2f_rounds: Cell<F>,
     precompile_input_rws: Cell<F>,
     precompile_output_rws: Cell<F>,
     precompile_return_rws: Cell<F>,
@@ -236,6 +238,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
         let precompile_output_rws = cb.query_cell();
         let precompile_return_rws = cb.query_cell();
         let precompile_input_len = cb.query_cell();
+        let precompile_blake2f_rounds = cb.query_cell();
 
         // Verify transfer only for CALL opcode in the successful case.  If value == 0,
         // skip the transfer (this is necessary for non-existing accounts, which
@@ -514,9 +517,11 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
                     call_gadget.rd_address.offset(),
                     call_gadget.rd_address.length(),
                     precompile_return_length.expr(),
+                    precompile_input_len.expr(),
                     precompile_input_bytes_rlc.expr(),
                     precompile_output_bytes_rlc.expr(),
                     precompile_return_bytes_rlc.expr(),
+                    precompile_blake2f_rounds.expr(),
                 );
 
                 (
@@ -584,8 +589,9 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             },
         );
 
-        // handle is_insufficient_balance or !is_depth_ok step transition
-        cb.condition(not::expr(is_precheck_ok.expr()), |cb| {
+        // handle is_insufficient_balance step transition, when depth is ok
+        let is_balance_precheck_fail = is_depth_ok.expr() * is_insufficient_balance.expr();
+        cb.condition(is_balance_precheck_fail, |cb| {
             // Save caller's call state
             cb.call_context_lookup_write(
                 None,
@@ -613,6 +619,70 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             });
         });
 
+        // handle !is_depth_ok, which enters the callee context to fail immediately in the
+        // ErrorDepth step, returning all its gas.
+        cb.condition(not::expr(is_depth_ok.expr()), |cb| {
+            // Save caller's call state
+            for (field_tag, value) in [
+                (
+                    CallContextFieldTag::ProgramCounter,
+                    cb.curr.state.program_counter.expr() + 1.expr(),
+                ),
+                (
+                    CallContextFieldTag::StackPointer,
+                    cb.curr.state.stack_pointer.expr() + stack_pointer_delta.clone(),
+                ),
+                (
+                    CallContextFieldTag::GasLeft,
+                    cb.curr.state.gas_left.expr() - gas_cost.clone() - callee_gas_left.clone(),
+                ),
+                (
+                    CallContextFieldTag::MemorySize,
+                    memory_expansion.next_memory_word_size(),
+                ),
+                (
+                    CallContextFieldTag::ReversibleWriteCounter,
+                    cb.curr.state.reversible_write_counter.expr() + 1.expr(),
+                ),
+            ] {
+                cb.call_context_lookup_write(None, field_tag, WordLoHi::from_lo_unchecked(value));
+            }
+
+            // Setup the context read by the ErrorDepth step.
+            for (field_tag, value) in [
+                (CallContextFieldTag::CallerId, cb.curr.state.call_id.expr()),
+                (CallContextFieldTag::Depth, depth.expr() + 1.expr()),
+            ] {
+                cb.call_context_lookup_write(
+                    Some(callee_call_id.expr()),
+                    field_tag,
+                    WordLoHi::from_lo_unchecked(value),
+                );
+            }
+
+            // A non-existing callee is given the empty code hash.
+            let callee_code_hash = WordLoHi::select(
+                call_gadget.callee_not_exists.expr(),
+                cb.empty_code_hash(),
+                call_gadget.callee_code_hash.to_word(),
+            );
+            cb.require_step_state_transition(StepStateTransition {
+                rw_counter: Delta(rw_counter_delta.clone() + 7.expr()),
+                call_id: To(callee_call_id.expr()),
+                is_root: To(false.expr()),
+                is_create: To(false.expr()),
+                code_hash: To(callee_code_hash),
+                program_counter: Delta(1.expr()),
+                stack_pointer: Delta(stack_pointer_delta.clone()),
+                gas_left: To(callee_gas_left.clone()
+                    + call_gadget.has_value.clone() * GAS_STIPEND_CALL_WITH_VALUE.expr()),
+                memory_word_size: To(0.expr()),
+                reversible_write_counter: To(0.expr()),
+                ..StepStateTransition::default()
+            });
+            cb.require_next_state(ExecutionState::ErrorDepth);
+        });
+
         // handle all other calls.
         cb.condition(
             and::expr([
@@ -793,6 +863,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             precompile_input_bytes_rlc,
             precompile_output_bytes_rlc,
             precompile_return_bytes_rlc,
+            precompile_blake2f_rounds,
             precompile_input_rws,
             precompile_output_rws,
             precompile_return_rws,
@@ -812,6 +883,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
         step: &ExecStep,
     ) -> Result<(), Error> {
         let opcode = step.opcode().unwrap();
+        let hardfork = block.feature_config.hardfork;
         let is_call = opcode == OpcodeId::CALL;
         let is_callcode = opcode == OpcodeId::CALLCODE;
         let is_delegatecall = opcode == OpcodeId::DELEGATECALL;
@@ -968,7 +1040,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
 
         let (_is_precompile_call, precompile_addr) = {
             let precompile_addr = callee_address.to_address();
-            let is_precompiled_call = is_precompiled(&precompile_addr);
+            let is_precompiled_call = is_precompiled(&precompile_addr, hardfork);
             (is_precompiled_call, precompile_addr)
         };
         let code_address: F = callee_address.to_address().to_scalar().unwrap();
@@ -976,7 +1048,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             .assign(region, offset, code_address)?;
         self.is_precompile_lt
             .assign(region, offset, code_address, 0x0Au64.into())?;
-        let precompile_return_length = if is_precompiled(&callee_address.to_address()) {
+        let precompile_return_length = if is_precompiled(&callee_address.to_address(), hardfork) {
             rws.offset_add(14); // skip
             let value_rw = rws.next();
             assert_eq!(
@@ -1010,10 +1082,11 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             precompile_input_bytes_rlc,
             precompile_output_bytes_rlc,
             precompile_return_bytes_rlc,
+            blake2f_rounds,
             input_rws,
             output_rws,
             return_rws,
-        ) = if is_precheck_ok && is_precompiled(&callee_address.to_address()) {
+        ) = if is_precheck_ok && is_precompiled(&callee_address.to_address(), hardfork) {
             let precompile_call: PrecompileCalls = precompile_addr.0[19].into();
             let input_len = if let Some(input_len) = precompile_call.input_len() {
                 min(input_len, cd_length.as_usize())
@@ -1046,6 +1119,13 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
                 .keccak_input()
                 .map(|randomness| rlc::value(return_bytes.iter().rev(), randomness));
 
+            let blake2f_rounds = match precompile_call {
+                PrecompileCalls::Blake2F if input_bytes.len() == 213 => {
+                    u32::from_be_bytes(input_bytes[0..4].try_into().unwrap())
+                }
+                _ => 0,
+            };
+
             let input_rws = input_bytes.len() as u64;
             let output_rws = output_bytes.len() as u64;
             let return_rws = (return_bytes.len() * 2) as u64;
@@ -1055,6 +1135,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
                 input_bytes_rlc,
                 output_bytes_rlc,
                 return_bytes_rlc,
+                blake2f_rounds,
                 input_rws,
                 output_rws,
                 return_rws,
@@ -1065,6 +1146,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
                 Value::known(F::ZERO),
                 Value::known(F::ZERO),
                 Value::known(F::ZERO),
+                0u32,
                 0u64,
                 0u64,
                 0u64,
@@ -1082,6 +1164,11 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             .assign(region, offset, precompile_output_bytes_rlc)?;
         self.precompile_return_bytes_rlc
             .assign(region, offset, precompile_return_bytes_rlc)?;
+        self.precompile_blake2f_rounds.assign(
+            region,
+            offset,
+            Value::known(F::from(blake2f_rounds as u64)),
+        )?;
         self.precompile_input_rws
             .assign(region, offset, Value::known(F::from(input_rws)))?;
         self.precompile_output_rws
@@ -1098,7 +1185,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             F::from_u128(remainder),
         )?;
 
-        if is_precompiled(&callee_address.to_address()) {
+        if is_precompiled(&callee_address.to_address(), hardfork) {
             self.precompile_gadget.assign(
                 region,
                 offset,
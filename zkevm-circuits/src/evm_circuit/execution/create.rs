@@ -32,10 +32,7 @@ use crate::{
 use bus_mapping::{
     circuit_input_builder::CopyDataType, evm::OpcodeId, operation::Target, state_db::CodeDB,
 };
-use eth_types::{
-    evm_types::{GasCost, INIT_CODE_WORD_GAS},
-    Field, OpsIdentity, ToBigEndian, ToScalar, ToWord, U256,
-};
+use eth_types::{evm_types::GasCost, Field, OpsIdentity, ToBigEndian, ToScalar, ToWord, U256};
 use ethers_core::utils::keccak256;
 use gadgets::util::{and, select};
 use halo2_proofs::{
@@ -61,6 +58,7 @@ pub(crate) struct CreateGadget<F, const IS_CREATE2: bool, const S: ExecutionStat
     caller_balance: WordLoHiCell<F>,
     callee_reversion_info: ReversionInfo<F>,
     callee_nonce: Cell<F>,
+    callee_was_created: Cell<F>,
     prev_code_hash: WordLoHiCell<F>,
     prev_code_hash_is_zero: IsZeroWordGadget<F, WordLoHi<Expression<F>>>,
     transfer: TransferGadget<F, false>,
@@ -160,11 +158,12 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
             init_code.length() + (N_BYTES_WORD - 1).expr(),
             N_BYTES_WORD as u64,
         );
+        let hardfork = cb.feature_config.hardfork;
         let keccak_gas_cost = init_code_word_size.quotient()
             * select::expr(
                 is_create2.expr(),
-                (INIT_CODE_WORD_GAS + GasCost::COPY_SHA3).expr(),
-                INIT_CODE_WORD_GAS.expr(),
+                hardfork.create2_gas_per_code_word().expr(),
+                hardfork.create_gas_per_code_word().expr(),
             );
         let gas_cost = GasCost::CREATE.expr() + memory_expansion.gas_cost() + keccak_gas_cost;
         let gas_remaining = cb.curr.state.gas_left.expr() - gas_cost.clone();
@@ -175,6 +174,7 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
         let init_code_rlc = cb.query_cell_phase2();
         let prev_code_hash = cb.query_word_unchecked();
         let callee_nonce = cb.query_cell();
+        let callee_was_created = cb.query_bool();
         let (prev_code_hash_is_zero, not_address_collision) =
             cb.condition(is_precheck_ok.expr(), |cb| {
                 // increase caller's nonce
@@ -333,7 +333,7 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
                     cb,
                     create.caller_address(),
                     contract_addr.to_word(),
-                    false.expr(),
+                    not::expr(prev_code_hash_is_zero.expr()),
                     true.expr(),
                     value.clone(),
                     &mut callee_reversion_info,
@@ -349,6 +349,15 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
                     Some(&mut callee_reversion_info),
                 );
 
+                // EIP 6780, mark the contract as created in the current transaction
+                cb.tx_created_account_write(
+                    tx_id.expr(),
+                    contract_addr.to_word(),
+                    1.expr(),
+                    callee_was_created.expr(),
+                    Some(&mut callee_reversion_info),
+                );
+
                 cb.condition(init_code.has_length(), |cb| {
                     for (field_tag, value) in [
                         (
@@ -405,7 +414,7 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
                         code_hash: To(create.code_hash()),
                         gas_left: To(callee_gas_left),
                         reversible_write_counter: To(
-                            1.expr() + transfer.reversible_w_delta().expr()
+                            2.expr() + transfer.reversible_w_delta().expr()
                         ),
                         ..StepStateTransition::new_context()
                     })
@@ -430,7 +439,7 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
                         stack_pointer: Delta(2.expr() + is_create2.expr()),
                         gas_left: Delta(-gas_cost.expr()),
                         reversible_write_counter: Delta(
-                            3.expr() + transfer.reversible_w_delta().expr(),
+                            4.expr() + transfer.reversible_w_delta().expr(),
                         ),
                         ..Default::default()
                     })
@@ -505,6 +514,7 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
             prev_code_hash,
             prev_code_hash_is_zero,
             callee_nonce,
+            callee_was_created,
             is_create2,
         }
     }
@@ -588,10 +598,19 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
             .assign_u256(region, offset, callee_prev_code_hash)?;
         self.prev_code_hash_is_zero
             .assign_u256(region, offset, callee_prev_code_hash)?;
-        self.not_address_collision.assign_u256(
+        // Witness of `callee_nonce + prev_code_hash * (prev_code_hash - empty_code_hash)`,
+        // computed limb-wise as in the circuit.
+        let prev_code_hash_word = WordLoHi::<F>::from(callee_prev_code_hash);
+        let empty_code_hash_word = WordLoHi::<F>::from(CodeDB::empty_code_hash());
+        self.not_address_collision.assign(
             region,
             offset,
-            U256::from(is_address_collision as u8),
+            WordLoHi::new([
+                F::from(callee_nonce)
+                    + prev_code_hash_word.lo()
+                        * (prev_code_hash_word.lo() - empty_code_hash_word.lo()),
+                prev_code_hash_word.hi() * (prev_code_hash_word.hi() - empty_code_hash_word.hi()),
+            ]),
         )?;
 
         let copy_rw_increase = init_code_length.as_usize();
@@ -620,11 +639,12 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
             offset,
             (31u64 + init_code_length.as_u64()).into(),
         )?;
+        let hardfork = block.feature_config.hardfork;
         let initcode_gas_cost = u64::try_from(init_code_word_size).unwrap()
             * if is_create2 {
-                INIT_CODE_WORD_GAS + GasCost::COPY_SHA3
+                hardfork.create2_gas_per_code_word()
             } else {
-                INIT_CODE_WORD_GAS
+                hardfork.create_gas_per_code_word()
             };
         let gas_left =
             step.gas_left - GasCost::CREATE - memory_expansion_gas_cost - initcode_gas_cost;
@@ -649,6 +669,13 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
                     true,
                     None,
                 )?;
+                rws.next(); // callee nonce = 1
+                let callee_was_created = rws.next().tx_created_account_value_pair().1;
+                self.callee_was_created.assign(
+                    region,
+                    offset,
+                    Value::known(F::from(callee_was_created as u64)),
+                )?;
             }
 
             let code_hash = CodeDB::hash(&values);
@@ -681,7 +708,7 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
             self.was_warm
                 .assign(region, offset, Value::known(F::from(was_warm.into())))?;
             self.callee_nonce
-                .assign(region, offset, Value::known(F::ZERO))?;
+                .assign(region, offset, Value::known(F::from(callee_nonce)))?;
 
             code_hash
         } else {
@@ -705,7 +732,6 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
             } else if init_code_length.as_usize() == 0 {
                 F::ONE
             } else {
-                rws.next(); // callee nonce += 1
                 rws.next(); // caller id
                 let rw = rws.next();
                 debug_assert_eq!(rw.tag(), Target::CallContext);
@@ -732,8 +758,10 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
 mod test {
     use crate::test_util::CircuitTestBuilder;
     use eth_types::{
-        address, bytecode, evm_types::OpcodeId, geth_types::Account, word, Address, Bytecode, Word,
+        address, bytecode, evm_types::OpcodeId, geth_types::Account, word, Address, Bytecode,
+        ToBigEndian, Word,
     };
+    use ethers_core::utils::{get_contract_address, get_create2_address};
     use itertools::Itertools;
     use lazy_static::lazy_static;
     use mock::{eth, TestContext};
@@ -944,6 +972,90 @@ mod test {
         run_test_circuits(test_context(caller));
     }
 
+    #[test]
+    fn test_create_to_prefunded_address() {
+        // The contract address already holds a balance, so the account exists
+        // without colliding and its code hash is not written on creation.
+        for is_create2 in [true, false] {
+            let init_code = initialization_bytecode(true);
+            let contract_address = if is_create2 {
+                get_create2_address(
+                    *CALLER_ADDRESS,
+                    Word::from(45).to_be_bytes(),
+                    init_code.code(),
+                )
+            } else {
+                get_contract_address(*CALLER_ADDRESS, 1u64)
+            };
+            let root_code = creator_bytecode(init_code, 23414.into(), is_create2, true);
+            let ctx = TestContext::<3, 1>::new(
+                None,
+                |accs| {
+                    accs[0]
+                        .address(address!("0x000000000000000000000000000000000000cafe"))
+                        .balance(eth(10));
+                    accs[1]
+                        .address(*CALLER_ADDRESS)
+                        .code(root_code)
+                        .nonce(1)
+                        .balance(eth(10));
+                    accs[2].address(contract_address).balance(eth(1));
+                },
+                |mut txs, accs| {
+                    txs[0]
+                        .from(accs[0].address)
+                        .to(accs[1].address)
+                        .gas(word!("0x2386F26FC10000"));
+                },
+                |block, _| block,
+            )
+            .unwrap();
+            CircuitTestBuilder::new_from_test_ctx(ctx).run();
+        }
+    }
+
+    #[test]
+    fn test_create_address_collision_with_existing_account() {
+        // The contract address already has a non-zero nonce, so both CREATE and
+        // CREATE2 fail with an address collision.
+        for is_create2 in [true, false] {
+            let init_code = initialization_bytecode(false);
+            let contract_address = if is_create2 {
+                get_create2_address(
+                    *CALLER_ADDRESS,
+                    Word::from(45).to_be_bytes(),
+                    init_code.code(),
+                )
+            } else {
+                get_contract_address(*CALLER_ADDRESS, 1u64)
+            };
+            let root_code = creator_bytecode(init_code, 23414.into(), is_create2, true);
+            let ctx = TestContext::<3, 1>::new(
+                None,
+                |accs| {
+                    accs[0]
+                        .address(address!("0x000000000000000000000000000000000000cafe"))
+                        .balance(eth(10));
+                    accs[1]
+                        .address(*CALLER_ADDRESS)
+                        .code(root_code)
+                        .nonce(1)
+                        .balance(eth(10));
+                    accs[2].address(contract_address).nonce(1);
+                },
+                |mut txs, accs| {
+                    txs[0]
+                        .from(accs[0].address)
+                        .to(accs[1].address)
+                        .gas(word!("0x2386F26FC10000"));
+                },
+                |block, _| block,
+            )
+            .unwrap();
+            CircuitTestBuilder::new_from_test_ctx(ctx).run();
+        }
+    }
+
     // Ignore this test case. It could run successfully but slow for CI.
     #[ignore]
     #[test]
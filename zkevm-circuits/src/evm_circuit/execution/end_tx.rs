@@ -41,6 +41,7 @@ pub(crate) struct EndTxGadget<F> {
     coinbase_code_hash_is_zero: IsZeroWordGadget<F, WordLoHiCell<F>>,
     coinbase_reward: TransferToGadget<F>,
     is_persistent: Cell<F>,
+    destruct_rws: Cell<F>,
     end_tx: EndTxHelperGadget<F>,
 }
 
@@ -112,12 +113,18 @@ impl<F: Field> ExecutionGadget<F> for EndTxGadget<F> {
             None,
         );
 
+        // The accounts destructed by SELFDESTRUCT in the tx are wiped after the
+        // receipt is written, one write per account field and non-zero storage
+        // slot. Their number depends on the storage of the accounts, so only
+        // the consistency of those writes is checked, by the state circuit.
+        let destruct_rws = cb.query_cell();
+
         let end_tx = EndTxHelperGadget::construct(
             cb,
             tx_id.expr(),
             is_persistent.expr(),
             gas_used,
-            9.expr() + coinbase_reward.rw_delta(),
+            9.expr() + coinbase_reward.rw_delta() + destruct_rws.expr(),
         );
 
         Self {
@@ -136,6 +143,7 @@ impl<F: Field> ExecutionGadget<F> for EndTxGadget<F> {
             coinbase_code_hash_is_zero,
             coinbase_reward,
             is_persistent,
+            destruct_rws,
             end_tx,
         }
     }
@@ -222,6 +230,12 @@ impl<F: Field> ExecutionGadget<F> for EndTxGadget<F> {
             offset,
             Value::known(F::from(call.is_persistent as u64)),
         )?;
+        // The rws left after the coinbase reward are the tx receipt writes,
+        // followed by the wipe of the destructed accounts.
+        let receipt_rws = if tx.id == 1 { 3 } else { 4 };
+        let destruct_rws = step.rw_indices_len() - rws.offset() - receipt_rws;
+        self.destruct_rws
+            .assign(region, offset, Value::known(F::from(destruct_rws as u64)))?;
         self.end_tx.assign(region, offset, block, tx)?;
 
         Ok(())
@@ -0,0 +1,143 @@
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        step::ExecutionState,
+        util::{
+            common_gadget::RestoreContextGadget,
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            CachedRegion, Cell,
+        },
+    },
+    table::CallContextFieldTag,
+    witness::{Block, Call, Chunk, ExecStep, Transaction},
+};
+use eth_types::Field;
+use gadgets::util::Expr;
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+/// Gadget for the callee of a CALL, CALLCODE, DELEGATECALL or STATICCALL made
+/// at the maximum call depth, which fails without executing anything and
+/// returns all of its gas to the caller.
+#[derive(Clone, Debug)]
+pub(crate) struct ErrorDepthGadget<F> {
+    depth: Cell<F>,
+    restore_context: RestoreContextGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for ErrorDepthGadget<F> {
+    const NAME: &'static str = "ErrorDepth";
+
+    const EXECUTION_STATE: ExecutionState = ExecutionState::ErrorDepth;
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        // The call is made at depth 1025, so its callee would be at depth 1026.
+        let depth = cb.call_context(None, CallContextFieldTag::Depth);
+        cb.require_equal("depth == 1025 + 1", depth.expr(), 1026.expr());
+
+        let restore_context = RestoreContextGadget::construct2(
+            cb,
+            false.expr(),
+            0.expr(),
+            0.expr(),
+            0.expr(), // ReturnDataOffset
+            0.expr(), // ReturnDataLength
+            0.expr(),
+            0.expr(),
+        );
+
+        Self {
+            depth,
+            restore_context,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block<F>,
+        _chunk: &Chunk<F>,
+        _transaction: &Transaction,
+        call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        self.depth
+            .assign(region, offset, Value::known(F::from(call.depth as u64)))?;
+        self.restore_context
+            .assign(region, offset, block, call, step, 1)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_util::CircuitTestBuilder;
+    use bus_mapping::circuit_input_builder::FixedCParams;
+    use eth_types::{bytecode, evm_types::OpcodeId, word, Word};
+    use mock::{test_ctx::helpers::account_0_code_account_1_no_code, TestContext};
+
+    fn test_ok(opcode: OpcodeId) {
+        // The contract calls itself until the call at depth 1025 fails.
+        let mut code = bytecode! {
+            PUSH1(0x00)
+            PUSH1(0x00)
+            PUSH1(0x00)
+            PUSH1(0x00)
+        };
+        if matches!(opcode, OpcodeId::CALL | OpcodeId::CALLCODE) {
+            code.push(1, Word::zero());
+        }
+        code.append(&bytecode! {
+            ADDRESS
+            PUSH2(0xffff)
+            GAS
+            SUB
+        });
+        code.write_op(opcode);
+        code.append(&bytecode! {
+            PUSH1(0x01)
+            SUB
+        });
+
+        let ctx = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            |mut txs, accs| {
+                txs[0]
+                    .to(accs[0].address)
+                    .from(accs[1].address)
+                    .gas(word!("0x2386F26FC10000"));
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx)
+            .params(FixedCParams {
+                max_rws: 300000,
+                ..Default::default()
+            })
+            .run();
+    }
+
+    #[test]
+    fn error_depth_call() {
+        test_ok(OpcodeId::CALL);
+    }
+
+    #[test]
+    fn error_depth_callcode() {
+        test_ok(OpcodeId::CALLCODE);
+    }
+
+    #[test]
+    fn error_depth_delegatecall() {
+        test_ok(OpcodeId::DELEGATECALL);
+    }
+
+    #[test]
+    fn error_depth_staticcall() {
+        test_ok(OpcodeId::STATICCALL);
+    }
+}
@@ -12,8 +12,8 @@ use eth_types::Field;
 use gadgets::util::Expr;
 use halo2_proofs::{circuit::Value, plonk::Error};
 
-/// Gadget for invalid opcodes. It verifies by a fixed lookup for
-/// ResponsibleOpcode.
+/// Gadget for invalid opcodes, including the opcodes not yet available in the
+/// configured hardfork. It verifies by a fixed lookup for ResponsibleOpcode.
 #[derive(Clone, Debug)]
 pub(crate) struct ErrorInvalidOpcodeGadget<F> {
     opcode: Cell<F>,
@@ -68,8 +68,15 @@ impl<F: Field> ExecutionGadget<F> for ErrorInvalidOpcodeGadget<F> {
 
 #[cfg(test)]
 mod test {
-    use crate::{evm_circuit::test::rand_bytes, test_util::CircuitTestBuilder};
-    use eth_types::{bytecode::Bytecode, Word};
+    use crate::{
+        evm_circuit::{step::ExecutionState, test::rand_bytes},
+        test_util::CircuitTestBuilder,
+    };
+    use eth_types::{
+        bytecode::Bytecode,
+        evm_types::{Hardfork, OpcodeId},
+        Word,
+    };
     use lazy_static::lazy_static;
     use mock::{generate_mock_call_bytecode, MockCallBytecodeParams, TestContext};
 
@@ -100,6 +107,30 @@ mod test {
         }
     }
 
+    #[test]
+    fn invalid_opcode_before_hardfork() {
+        // PUSH0 is introduced in Shanghai, and MCOPY and TLOAD in Cancun.
+        for (hardfork, opcode, execution_state) in [
+            (Hardfork::London, OpcodeId::PUSH0, ExecutionState::PUSH),
+            (Hardfork::Shanghai, OpcodeId::MCOPY, ExecutionState::MCOPY),
+            (Hardfork::Shanghai, OpcodeId::TLOAD, ExecutionState::TLOAD),
+        ] {
+            let is_responsible = |execution_state: ExecutionState, hardfork| {
+                execution_state
+                    .responsible_opcodes(hardfork)
+                    .iter()
+                    .any(|op| op.opcode() == opcode)
+            };
+            assert!(is_responsible(ExecutionState::ErrorInvalidOpcode, hardfork));
+            assert!(!is_responsible(execution_state, hardfork));
+            assert!(!is_responsible(
+                ExecutionState::ErrorInvalidOpcode,
+                Hardfork::Cancun
+            ));
+            assert!(is_responsible(execution_state, Hardfork::Cancun));
+        }
+    }
+
     fn test_root_ok(invalid_code: &[u8]) {
         let mut code = Bytecode::default();
         invalid_code.iter().for_each(|b| {
@@ -6,14 +6,18 @@ use crate::{
         util::{
             common_gadget::RestoreContextGadget,
             constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
-            math_gadget::{BinaryNumberGadget, ConstantDivisionGadget, LtGadget},
+            from_bytes,
+            math_gadget::{BinaryNumberGadget, ConstantDivisionGadget, IsEqualGadget, LtGadget},
             CachedRegion, Cell,
         },
     },
     table::CallContextFieldTag,
     witness::{Block, Call, Chunk, ExecStep, Transaction},
 };
-use bus_mapping::precompile::PrecompileCalls;
+use bus_mapping::{
+    circuit_input_builder::N_BYTES_PER_PAIR,
+    precompile::{PrecompileAuxData, PrecompileCalls},
+};
 use eth_types::{evm_types::GasCost, Field, ToScalar};
 use gadgets::util::{sum, Expr};
 use halo2_proofs::{circuit::Value, plonk::Error};
@@ -23,8 +27,13 @@ pub(crate) struct ErrorOOGPrecompileGadget<F> {
     precompile_addr: Cell<F>,
     addr_bits: BinaryNumberGadget<F, 4>,
     call_data_length: Cell<F>,
-    // n_pairs: ConstantDivisionGadget<F, N_BYTES_MEMORY_WORD_SIZE>,
+    n_pairs: ConstantDivisionGadget<F, N_BYTES_MEMORY_WORD_SIZE>,
     n_words: ConstantDivisionGadget<F, N_BYTES_MEMORY_WORD_SIZE>,
+    // BLAKE2F's required gas is the number of rounds in the first 4 bytes of a 213-byte input.
+    caller_id: Cell<F>,
+    call_data_offset: Cell<F>,
+    is_blake2f_input_len: IsEqualGadget<F>,
+    blake2f_rounds: [Cell<F>; 4],
     required_gas: Cell<F>,
     insufficient_gas: LtGadget<F, N_BYTES_GAS>,
     restore_context: RestoreContextGadget<F>,
@@ -44,23 +53,55 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGPrecompileGadget<F> {
 
         // read call data length
         let call_data_length = cb.call_context(None, CallContextFieldTag::CallDataLength);
-        // let n_pairs = cb.condition(
-        //     addr_bits.value_equals(PrecompileCalls::Bn128Pairing),
-        //     |cb| {
-        //         ConstantDivisionGadget::construct(
-        //             cb,
-        //             call_data_length.expr(),
-        //             N_BYTES_EC_PAIR as u64,
-        //         )
-        //     },
-        // );
-        let n_words = cb.condition(addr_bits.value_equals(PrecompileCalls::Identity), |cb| {
-            ConstantDivisionGadget::construct(
-                cb,
-                call_data_length.expr() + (N_BYTES_WORD - 1).expr(),
-                N_BYTES_WORD as u64,
-            )
-        });
+        let n_pairs = cb.condition(
+            addr_bits.value_equals(PrecompileCalls::Bn128Pairing),
+            |cb| {
+                ConstantDivisionGadget::construct(
+                    cb,
+                    call_data_length.expr(),
+                    N_BYTES_PER_PAIR as u64,
+                )
+            },
+        );
+        let n_words = cb.condition(
+            sum::expr([
+                addr_bits.value_equals(PrecompileCalls::Sha256),
+                addr_bits.value_equals(PrecompileCalls::Ripemd160),
+                addr_bits.value_equals(PrecompileCalls::Identity),
+            ]),
+            |cb| {
+                ConstantDivisionGadget::construct(
+                    cb,
+                    call_data_length.expr() + (N_BYTES_WORD - 1).expr(),
+                    N_BYTES_WORD as u64,
+                )
+            },
+        );
+
+        // read the number of rounds from the caller's memory
+        let (caller_id, call_data_offset, is_blake2f_input_len, blake2f_rounds) =
+            cb.condition(addr_bits.value_equals(PrecompileCalls::Blake2F), |cb| {
+                let caller_id = cb.call_context(None, CallContextFieldTag::CallerId);
+                let call_data_offset = cb.call_context(None, CallContextFieldTag::CallDataOffset);
+                let is_blake2f_input_len = cb.is_eq(call_data_length.expr(), 213.expr());
+                let blake2f_rounds = cb.query_bytes();
+                cb.condition(is_blake2f_input_len.expr(), |cb| {
+                    for (idx, byte) in blake2f_rounds.iter().enumerate() {
+                        cb.memory_lookup(
+                            0.expr(),
+                            call_data_offset.expr() + idx.expr(),
+                            byte.expr(),
+                            Some(caller_id.expr()),
+                        );
+                    }
+                });
+                (
+                    caller_id,
+                    call_data_offset,
+                    is_blake2f_input_len,
+                    blake2f_rounds,
+                )
+            });
 
         // calculate required gas for precompile
         let precompiles_required_gas = [
@@ -68,28 +109,47 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGPrecompileGadget<F> {
                 addr_bits.value_equals(PrecompileCalls::Ecrecover),
                 GasCost::PRECOMPILE_ECRECOVER_BASE.expr(),
             ),
-            // addr_bits.value_equals(PrecompileCalls::Sha256),
-            // addr_bits.value_equals(PrecompileCalls::Ripemd160),
-            // addr_bits.value_equals(PrecompileCalls::Blake2F),
+            (
+                addr_bits.value_equals(PrecompileCalls::Sha256),
+                GasCost::PRECOMPILE_SHA256_BASE.expr()
+                    + n_words.quotient() * GasCost::PRECOMPILE_SHA256_PER_WORD.expr(),
+            ),
+            (
+                addr_bits.value_equals(PrecompileCalls::Ripemd160),
+                GasCost::PRECOMPILE_RIPEMD160_BASE.expr()
+                    + n_words.quotient() * GasCost::PRECOMPILE_RIPEMD160_PER_WORD.expr(),
+            ),
             (
                 addr_bits.value_equals(PrecompileCalls::Identity),
                 GasCost::PRECOMPILE_IDENTITY_BASE.expr()
                     + n_words.quotient() * GasCost::PRECOMPILE_IDENTITY_PER_WORD.expr(),
             ),
+            (
+                addr_bits.value_equals(PrecompileCalls::Blake2F),
+                // rounds are big-endian
+                is_blake2f_input_len.expr()
+                    * from_bytes::expr(
+                        &blake2f_rounds
+                            .iter()
+                            .rev()
+                            .map(|byte| byte.expr())
+                            .collect::<Vec<_>>(),
+                    ),
+            ),
             // modexp is handled in ModExpGadget
-            // (
-            //     addr_bits.value_equals(PrecompileCalls::Bn128Add),
-            //     GasCost::PRECOMPILE_BN256ADD.expr(),
-            // ),
-            // (
-            //     addr_bits.value_equals(PrecompileCalls::Bn128Mul),
-            //     GasCost::PRECOMPILE_BN256MUL.expr(),
-            // ),
-            // (
-            //     addr_bits.value_equals(PrecompileCalls::Bn128Pairing),
-            //     GasCost::PRECOMPILE_BN256PAIRING.expr()
-            //         + n_pairs.quotient() * GasCost::PRECOMPILE_BN256PAIRING_PER_PAIR.expr(),
-            // ),
+            (
+                addr_bits.value_equals(PrecompileCalls::Bn128Add),
+                GasCost::PRECOMPILE_BN256ADD.expr(),
+            ),
+            (
+                addr_bits.value_equals(PrecompileCalls::Bn128Mul),
+                GasCost::PRECOMPILE_BN256MUL.expr(),
+            ),
+            (
+                addr_bits.value_equals(PrecompileCalls::Bn128Pairing),
+                GasCost::PRECOMPILE_BN256PAIRING.expr()
+                    + n_pairs.quotient() * GasCost::PRECOMPILE_BN256PAIRING_PER_PAIR.expr(),
+            ),
         ];
 
         cb.require_equal(
@@ -132,8 +192,12 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGPrecompileGadget<F> {
             precompile_addr,
             required_gas,
             insufficient_gas,
-            // n_pairs,
+            n_pairs,
             n_words,
+            caller_id,
+            call_data_offset,
+            is_blake2f_input_len,
+            blake2f_rounds,
             addr_bits,
             call_data_length,
             restore_context,
@@ -168,9 +232,8 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGPrecompileGadget<F> {
         )?;
 
         // n_pairs
-        // let n_pairs = call.call_data_length / 192;
-        // self.n_pairs
-        //     .assign(region, offset, call.call_data_length as u128)?;
+        self.n_pairs
+            .assign(region, offset, call.call_data_length as u128)?;
 
         // n_words
         self.n_words.assign(
@@ -179,21 +242,56 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGPrecompileGadget<F> {
             (call.call_data_length + (N_BYTES_WORD as u64) - 1) as u128,
         )?;
 
+        // blake2f rounds
+        self.caller_id
+            .assign(region, offset, Value::known(F::from(call.caller_id as u64)))?;
+        self.call_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_offset)),
+        )?;
+        self.is_blake2f_input_len.assign(
+            region,
+            offset,
+            F::from(call.call_data_length),
+            F::from(213),
+        )?;
+        let blake2f_rounds = match &step.aux_data {
+            Some(PrecompileAuxData::Base { input_bytes, .. }) if input_bytes.len() == 213 => {
+                input_bytes[0..4].try_into().unwrap()
+            }
+            _ => [0u8; 4],
+        };
+        for (cell, byte) in self.blake2f_rounds.iter().zip(blake2f_rounds) {
+            cell.assign(region, offset, Value::known(F::from(byte as u64)))?;
+        }
+
         // required_gas
         let precompile_call: PrecompileCalls = precompile_addr.to_fixed_bytes()[19].into();
         let required_gas = match precompile_call {
             PrecompileCalls::Ecrecover => precompile_call.base_gas_cost(),
-            // PrecompileCalls::Bn128Pairing => {
-            //     precompile_call.base_gas_cost()
-            //         + n_pairs * GasCost::PRECOMPILE_BN256PAIRING_PER_PAIR
-            // }
+            PrecompileCalls::Sha256 | PrecompileCalls::Ripemd160 => {
+                let n_words = (call.call_data_length + 31) / 32;
+                let per_word = if precompile_call == PrecompileCalls::Sha256 {
+                    GasCost::PRECOMPILE_SHA256_PER_WORD
+                } else {
+                    GasCost::PRECOMPILE_RIPEMD160_PER_WORD
+                };
+                precompile_call.base_gas_cost() + n_words * per_word
+            }
+            PrecompileCalls::Blake2F => u32::from_be_bytes(blake2f_rounds) as u64,
+            PrecompileCalls::Bn128Pairing => {
+                let n_pairs = call.call_data_length / N_BYTES_PER_PAIR as u64;
+                precompile_call.base_gas_cost()
+                    + n_pairs * GasCost::PRECOMPILE_BN256PAIRING_PER_PAIR
+            }
             PrecompileCalls::Identity => {
                 let n_words = (call.call_data_length + 31) / 32;
                 precompile_call.base_gas_cost() + n_words * GasCost::PRECOMPILE_IDENTITY_PER_WORD
             }
-            // PrecompileCalls::Bn128Add | PrecompileCalls::Bn128Mul => {
-            //     precompile_call.base_gas_cost()
-            // }
+            PrecompileCalls::Bn128Add | PrecompileCalls::Bn128Mul => {
+                precompile_call.base_gas_cost()
+            }
             _ => unreachable!(),
         };
 
@@ -209,8 +307,13 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGPrecompileGadget<F> {
         )?;
 
         // restore context
+        let rw_offset = match precompile_call {
+            PrecompileCalls::Blake2F if call.call_data_length == 213 => 8,
+            PrecompileCalls::Blake2F => 4,
+            _ => 2,
+        };
         self.restore_context
-            .assign(region, offset, block, call, step, 2)?;
+            .assign(region, offset, block, call, step, rw_offset)?;
 
         Ok(())
     }
@@ -254,6 +357,93 @@ mod test {
                         - 1).to_word(),
                     ..Default::default()
                 },
+                PrecompileCallArgs {
+                    name: "sha256 multi-bytes (more than 32 bytes)",
+                    setup_code: bytecode! {
+                        // place params in memory
+                        PUSH30(word!("0x0123456789abcdef0f1e2d3c4b5a6978"))
+                        PUSH1(0x00) // place from 0x00 in memory
+                        MSTORE
+                        PUSH30(word!("0xaabbccdd001122331039abcdefefef84"))
+                        PUSH1(0x20) // place from 0x20 in memory
+                        MSTORE
+                    },
+                    // hash 63 bytes from memory addr 0
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x3f.into(),
+                    ret_offset: 0x48.into(),
+                    ret_size: 0x20.into(),
+                    address: PrecompileCalls::Sha256.address().to_word(),
+                    gas: (PrecompileCalls::Sha256.base_gas_cost()
+                        + 2 * GasCost::PRECOMPILE_SHA256_PER_WORD
+                        - 1).to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "ripemd160 multi-bytes (more than 32 bytes)",
+                    setup_code: bytecode! {
+                        // place params in memory
+                        PUSH30(word!("0x0123456789abcdef0f1e2d3c4b5a6978"))
+                        PUSH1(0x00) // place from 0x00 in memory
+                        MSTORE
+                        PUSH30(word!("0xaabbccdd001122331039abcdefefef84"))
+                        PUSH1(0x20) // place from 0x20 in memory
+                        MSTORE
+                    },
+                    // hash 63 bytes from memory addr 0
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x3f.into(),
+                    ret_offset: 0x48.into(),
+                    ret_size: 0x20.into(),
+                    address: PrecompileCalls::Ripemd160.address().to_word(),
+                    gas: (PrecompileCalls::Ripemd160.base_gas_cost()
+                        + 2 * GasCost::PRECOMPILE_RIPEMD160_PER_WORD
+                        - 1).to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "blake2f 12 rounds",
+                    setup_code: bytecode! {
+                        // place the rounds, followed by a valid final block indicator flag
+                        PUSH32(word!("0x0000000c00000000000000000000000000000000000000000000000000000000"))
+                        PUSH1(0x00)
+                        MSTORE
+                        PUSH32(word!("0x0000000000000000000000000000000000000000010000000000000000000000"))
+                        PUSH1(0xc0)
+                        MSTORE
+                    },
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0xd5.into(),
+                    ret_offset: 0x00.into(),
+                    ret_size: 0x40.into(),
+                    address: PrecompileCalls::Blake2F.address().to_word(),
+                    gas: 11.into(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "ecAdd",
+                    setup_code: bytecode! {},
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x80.into(),
+                    ret_offset: 0x80.into(),
+                    ret_size: 0x40.into(),
+                    address: PrecompileCalls::Bn128Add.address().to_word(),
+                    gas: (PrecompileCalls::Bn128Add.base_gas_cost() - 1).to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "ecPairing 2 pairs",
+                    setup_code: bytecode! {},
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x180.into(),
+                    ret_offset: 0x00.into(),
+                    ret_size: 0x20.into(),
+                    address: PrecompileCalls::Bn128Pairing.address().to_word(),
+                    gas: (PrecompileCalls::Bn128Pairing.base_gas_cost()
+                        + 2 * GasCost::PRECOMPILE_BN256PAIRING_PER_PAIR
+                        - 1).to_word(),
+                    ..Default::default()
+                },
             ]
         };
     }
@@ -0,0 +1,297 @@
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        param::N_BYTES_GAS,
+        step::ExecutionState,
+        util::{
+            and,
+            common_gadget::CommonErrorGadget,
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            math_gadget::{IsZeroWordGadget, LtGadget},
+            not, select, AccountAddress, CachedRegion, Cell, StepRws,
+        },
+        witness::{Block, Call, Chunk, ExecStep, Transaction},
+    },
+    table::{AccountFieldTag, CallContextFieldTag},
+    util::{
+        word::{Word32Cell, WordExpr, WordLoHiCell},
+        Expr,
+    },
+};
+use eth_types::{
+    evm_types::{GasCost, OpcodeId},
+    Field, ToAddress,
+};
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+/// Gadget to implement the corresponding out of gas errors for
+/// [`OpcodeId::SELFDESTRUCT`].
+#[derive(Clone, Debug)]
+pub(crate) struct ErrorOOGSelfDestructGadget<F> {
+    opcode: Cell<F>,
+    beneficiary: AccountAddress<F>,
+    tx_id: Cell<F>,
+    is_warm: Cell<F>,
+    beneficiary_code_hash: WordLoHiCell<F>,
+    beneficiary_not_exists: IsZeroWordGadget<F, WordLoHiCell<F>>,
+    callee_address: WordLoHiCell<F>,
+    balance: Word32Cell<F>,
+    balance_is_zero: IsZeroWordGadget<F, Word32Cell<F>>,
+    insufficient_gas_cost: LtGadget<F, N_BYTES_GAS>,
+    common_error_gadget: CommonErrorGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for ErrorOOGSelfDestructGadget<F> {
+    const NAME: &'static str = "ErrorOutOfGasSELFDESTRUCT";
+
+    const EXECUTION_STATE: ExecutionState = ExecutionState::ErrorOutOfGasSELFDESTRUCT;
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let opcode = cb.query_cell();
+        cb.require_equal(
+            "ErrorOutOfGasSELFDESTRUCT opcode must be SELFDESTRUCT",
+            opcode.expr(),
+            OpcodeId::SELFDESTRUCT.expr(),
+        );
+
+        let beneficiary = cb.query_account_address();
+        cb.stack_pop(beneficiary.to_word());
+
+        let tx_id = cb.call_context(None, CallContextFieldTag::TxId);
+        let is_warm = cb.query_bool();
+        // read is_warm
+        cb.account_access_list_read(tx_id.expr(), beneficiary.to_word(), is_warm.expr());
+
+        // For non-existing accounts the code_hash must be 0 in the rw_table.
+        let beneficiary_code_hash = cb.query_word_unchecked();
+        cb.account_read(
+            beneficiary.to_word(),
+            AccountFieldTag::CodeHash,
+            beneficiary_code_hash.to_word(),
+        );
+        let beneficiary_not_exists = cb.is_zero_word(&beneficiary_code_hash);
+
+        let callee_address = cb.call_context_read_as_word(None, CallContextFieldTag::CalleeAddress);
+        let balance = cb.query_word32();
+        cb.account_read(
+            callee_address.to_word(),
+            AccountFieldTag::Balance,
+            balance.to_word(),
+        );
+        let balance_is_zero = cb.is_zero_word(&balance);
+
+        // Same gas cost model as `SelfDestructGadget`.
+        let gas_cost = GasCost::SELFDESTRUCT.expr()
+            + select::expr(
+                is_warm.expr(),
+                0.expr(),
+                GasCost::COLD_ACCOUNT_ACCESS.expr(),
+            )
+            + and::expr([
+                beneficiary_not_exists.expr(),
+                not::expr(balance_is_zero.expr()),
+            ]) * GasCost::NEW_ACCOUNT.expr();
+
+        let insufficient_gas_cost = cb.is_lt(cb.curr.state.gas_left.expr(), gas_cost);
+        cb.require_equal(
+            "Gas left is less than gas cost",
+            insufficient_gas_cost.expr(),
+            1.expr(),
+        );
+
+        let common_error_gadget =
+            CommonErrorGadget::construct(cb, opcode.expr(), cb.rw_counter_offset());
+
+        Self {
+            opcode,
+            beneficiary,
+            tx_id,
+            is_warm,
+            beneficiary_code_hash,
+            beneficiary_not_exists,
+            callee_address,
+            balance,
+            balance_is_zero,
+            insufficient_gas_cost,
+            common_error_gadget,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block<F>,
+        _chunk: &Chunk<F>,
+        tx: &Transaction,
+        call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        let opcode = step.opcode().unwrap();
+        self.opcode
+            .assign(region, offset, Value::known(F::from(opcode.as_u64())))?;
+
+        let mut rws = StepRws::new(block, step);
+        let beneficiary = rws.next().stack_value().to_address();
+        self.beneficiary.assign_h160(region, offset, beneficiary)?;
+
+        self.tx_id
+            .assign(region, offset, Value::known(F::from(tx.id)))?;
+        rws.next(); // TxId
+
+        let is_warm = rws.next().tx_access_list_value_pair().1;
+        self.is_warm
+            .assign(region, offset, Value::known(F::from(is_warm as u64)))?;
+
+        let beneficiary_code_hash = rws.next().account_codehash_pair().0;
+        self.beneficiary_code_hash
+            .assign_u256(region, offset, beneficiary_code_hash)?;
+        self.beneficiary_not_exists
+            .assign_u256(region, offset, beneficiary_code_hash)?;
+
+        self.callee_address
+            .assign_h160(region, offset, call.address)?;
+        rws.next(); // CalleeAddress
+
+        let balance = rws.next().account_balance_pair().0;
+        self.balance.assign_u256(region, offset, balance)?;
+        self.balance_is_zero.assign_u256(region, offset, balance)?;
+
+        let gas_cost = GasCost::SELFDESTRUCT
+            + if is_warm {
+                0
+            } else {
+                GasCost::COLD_ACCOUNT_ACCESS
+            }
+            + if beneficiary_code_hash.is_zero() && !balance.is_zero() {
+                GasCost::NEW_ACCOUNT
+            } else {
+                0
+            };
+        self.insufficient_gas_cost.assign_value(
+            region,
+            offset,
+            Value::known(F::from(step.gas_left)),
+            Value::known(F::from(gas_cost)),
+        )?;
+
+        self.common_error_gadget
+            .assign(region, offset, block, call, step, 8)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_util::CircuitTestBuilder;
+    use eth_types::{
+        address, bytecode,
+        evm_types::{GasCost, OpcodeId},
+        Address, Bytecode, ToWord, Word,
+    };
+    use mock::{eth, TestContext, MOCK_ACCOUNTS};
+
+    fn selfdestruct_code(beneficiary: Address) -> Bytecode {
+        bytecode! {
+            PUSH20(beneficiary.to_word())
+            SELFDESTRUCT
+        }
+    }
+
+    // Gas charged by SELFDESTRUCT for a beneficiary that is cold and, when
+    // `new_account` is set, doesn't exist yet.
+    fn selfdestruct_gas(is_warm: bool, new_account: bool) -> u64 {
+        GasCost::SELFDESTRUCT
+            + if is_warm {
+                0
+            } else {
+                GasCost::COLD_ACCOUNT_ACCESS
+            }
+            + if new_account { GasCost::NEW_ACCOUNT } else { 0 }
+    }
+
+    fn test_root_ok(beneficiary_exists: bool, balance: Word, gas_left: u64) {
+        let beneficiary = address!("0x00000000000000000000000000000000000b0b01");
+        let ctx = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .balance(balance)
+                    .code(selfdestruct_code(beneficiary));
+                accs[1].address(MOCK_ACCOUNTS[1]).balance(eth(10));
+                if beneficiary_exists {
+                    accs[2].address(beneficiary).balance(eth(1));
+                } else {
+                    accs[2].address(MOCK_ACCOUNTS[2]).balance(eth(1));
+                }
+            },
+            |mut txs, accs| {
+                txs[0]
+                    .to(accs[0].address)
+                    .from(accs[1].address)
+                    .gas((GasCost::TX + OpcodeId::PUSH20.constant_gas_cost() + gas_left).into());
+            },
+            |block, _tx| block,
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+
+    #[test]
+    fn error_oog_selfdestruct_constant() {
+        test_root_ok(true, eth(1), GasCost::SELFDESTRUCT - 1);
+    }
+
+    #[test]
+    fn error_oog_selfdestruct_cold_beneficiary() {
+        test_root_ok(true, eth(1), selfdestruct_gas(false, false) - 1);
+        test_root_ok(false, Word::zero(), selfdestruct_gas(false, false) - 1);
+    }
+
+    #[test]
+    fn error_oog_selfdestruct_new_account() {
+        test_root_ok(false, eth(1), selfdestruct_gas(false, true) - 1);
+    }
+
+    #[test]
+    fn error_oog_selfdestruct_internal() {
+        let beneficiary = address!("0x00000000000000000000000000000000000b0b01");
+        let (addr_a, addr_b) = (MOCK_ACCOUNTS[0], MOCK_ACCOUNTS[1]);
+
+        // code A calls code B with not enough gas to SELFDESTRUCT to a cold
+        // beneficiary.
+        let code_a = bytecode! {
+            PUSH1(0x00) // retLength
+            PUSH1(0x00) // retOffset
+            PUSH1(0x00) // argsLength
+            PUSH1(0x00) // argsOffset
+            PUSH1(0x00) // value
+            PUSH32(addr_b.to_word()) // addr
+            PUSH32(Word::from(selfdestruct_gas(false, false) - 1)) // gas insufficient
+            CALL
+            STOP
+        };
+
+        let ctx = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(addr_a).code(code_a);
+                accs[1]
+                    .address(addr_b)
+                    .balance(eth(1))
+                    .code(selfdestruct_code(beneficiary));
+                accs[2].address(MOCK_ACCOUNTS[2]).balance(eth(10));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[2].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+}
@@ -112,7 +112,7 @@ impl<F: Field> ExecutionGadget<F> for InvalidTxGadget<F> {
             .expect("unexpected U256 -> Scalar conversion failure");
         let balance = rws.next().account_balance_pair().0;
         self.begin_tx.assign(region, offset, tx)?;
-        self.tx.assign(region, offset, tx)?;
+        self.tx.assign(region, offset, block, tx)?;
         self.account_nonce
             .assign(region, offset, Value::known(account_nonce))?;
         self.is_nonce_match
@@ -121,7 +121,8 @@ impl<F: Field> ExecutionGadget<F> for InvalidTxGadget<F> {
             region,
             offset,
             tx.gas().scalar(),
-            tx.intrinsic_gas_cost().scalar(),
+            tx.intrinsic_gas_cost(block.feature_config.hardfork)
+                .scalar(),
         )?;
         self.balance.assign_u256(region, offset, balance)?;
         self.insufficient_balance.assign(
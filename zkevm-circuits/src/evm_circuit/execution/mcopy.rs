@@ -0,0 +1,225 @@
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        param::N_BYTES_MEMORY_WORD_SIZE,
+        step::ExecutionState,
+        util::{
+            common_gadget::SameContextGadget,
+            constraint_builder::{
+                EVMConstraintBuilder, StepStateTransition,
+                Transition::{Delta, To},
+            },
+            memory_gadget::{
+                CommonMemoryAddressGadget, MemoryAddressGadget, MemoryCopierGasGadget,
+                MemoryExpansionGadget,
+            },
+            CachedRegion, StepRws,
+        },
+        witness::{Block, Call, Chunk, ExecStep, Transaction},
+    },
+    util::{
+        word::{WordExpr, WordLoHi},
+        Expr,
+    },
+};
+use bus_mapping::{circuit_input_builder::CopyDataType, evm::OpcodeId};
+use eth_types::{evm_types::GasCost, Field};
+use halo2_proofs::plonk::Error;
+
+#[derive(Clone, Debug)]
+pub(crate) struct McopyGadget<F> {
+    same_context: SameContextGadget<F>,
+    /// The source and destination ranges share the length popped from the
+    /// stack, and both of them can expand the memory.
+    src_memory_addr: MemoryAddressGadget<F>,
+    dst_memory_addr: MemoryAddressGadget<F>,
+    memory_expansion: MemoryExpansionGadget<F, 2, N_BYTES_MEMORY_WORD_SIZE>,
+    memory_copier_gas: MemoryCopierGasGadget<F, { GasCost::COPY }>,
+}
+
+impl<F: Field> ExecutionGadget<F> for McopyGadget<F> {
+    const NAME: &'static str = "MCOPY";
+
+    const EXECUTION_STATE: ExecutionState = ExecutionState::MCOPY;
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let opcode = cb.query_cell();
+
+        let dst_offset = cb.query_word_unchecked();
+        let src_offset = cb.query_word_unchecked();
+        let length = cb.query_memory_address();
+
+        // Pop dst_offset, src_offset, length from stack
+        cb.stack_pop(dst_offset.to_word());
+        cb.stack_pop(src_offset.to_word());
+        cb.stack_pop(length.to_word());
+
+        let src_memory_addr = MemoryAddressGadget::construct(cb, src_offset, length.clone());
+        let dst_memory_addr = MemoryAddressGadget::construct(cb, dst_offset, length);
+
+        // Calculate the next memory size and the gas cost for this memory
+        // access, which covers both the source and the destination ranges.
+        let memory_expansion = MemoryExpansionGadget::construct(
+            cb,
+            [src_memory_addr.address(), dst_memory_addr.address()],
+        );
+        let memory_copier_gas = MemoryCopierGasGadget::construct(
+            cb,
+            dst_memory_addr.length(),
+            memory_expansion.gas_cost(),
+        );
+
+        // The copy circuit reads all the source bytes before writing the
+        // destination, so overlapping ranges are handled like a memmove.
+        cb.condition(dst_memory_addr.has_length(), |cb| {
+            cb.copy_table_lookup(
+                WordLoHi::from_lo_unchecked(cb.curr.state.call_id.expr()),
+                CopyDataType::Memory.expr(),
+                WordLoHi::from_lo_unchecked(cb.curr.state.call_id.expr()),
+                CopyDataType::Memory.expr(),
+                src_memory_addr.offset(),
+                src_memory_addr.address(),
+                dst_memory_addr.offset(),
+                dst_memory_addr.length(),
+                0.expr(), // for MCOPY rlc_acc is 0
+                // `length` memory reads and `length` memory writes
+                dst_memory_addr.length() + dst_memory_addr.length(),
+            );
+        });
+
+        // State transition
+        let step_state_transition = StepStateTransition {
+            rw_counter: Delta(cb.rw_counter_offset()),
+            program_counter: Delta(1.expr()),
+            stack_pointer: Delta(3.expr()),
+            gas_left: Delta(
+                -(OpcodeId::MCOPY.constant_gas_cost().expr() + memory_copier_gas.gas_cost()),
+            ),
+            memory_word_size: To(memory_expansion.next_memory_word_size()),
+            ..Default::default()
+        };
+        let same_context = SameContextGadget::construct(cb, opcode, step_state_transition);
+
+        Self {
+            same_context,
+            src_memory_addr,
+            dst_memory_addr,
+            memory_expansion,
+            memory_copier_gas,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block<F>,
+        _chunk: &Chunk<F>,
+        _tx: &Transaction,
+        _call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        self.same_context.assign_exec_step(region, offset, step)?;
+
+        let mut rws = StepRws::new(block, step);
+        let [dst_offset, src_offset, length] = [0, 1, 2].map(|_| rws.next().stack_value());
+
+        let src_address = self
+            .src_memory_addr
+            .assign(region, offset, src_offset, length)?;
+        let dst_address = self
+            .dst_memory_addr
+            .assign(region, offset, dst_offset, length)?;
+
+        let (_, memory_expansion_gas_cost) = self.memory_expansion.assign(
+            region,
+            offset,
+            step.memory_word_size(),
+            [src_address, dst_address],
+        )?;
+        self.memory_copier_gas.assign(
+            region,
+            offset,
+            length.as_u64(),
+            memory_expansion_gas_cost,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{evm_circuit::test::rand_bytes, test_util::CircuitTestBuilder};
+    use bus_mapping::circuit_input_builder::FixedCParams;
+    use eth_types::{bytecode, Word};
+    use mock::test_ctx::TestContext;
+
+    fn test_ok(dst_offset: Word, src_offset: Word, length: usize) {
+        let code = bytecode! {
+            .op_mstore(0x00, Word::from_big_endian(&rand_bytes(32)))
+            .op_mstore(0x20, Word::from_big_endian(&rand_bytes(32)))
+            PUSH32(length)
+            PUSH32(src_offset)
+            PUSH32(dst_offset)
+            MCOPY
+            // Read back the destination to check the copied bytes.
+            .op_mload(0x00)
+            .op_mload(0x20)
+            STOP
+        };
+
+        CircuitTestBuilder::new_from_test_ctx(
+            TestContext::<2, 1>::simple_ctx_with_bytecode(code).unwrap(),
+        )
+        .params(FixedCParams {
+            max_rws: 4096,
+            max_copy_rows: 4096,
+            ..Default::default()
+        })
+        .run();
+    }
+
+    #[test]
+    fn mcopy_gadget_simple() {
+        test_ok(0x40.into(), 0x00.into(), 0x20);
+    }
+
+    #[test]
+    fn mcopy_gadget_overlap_forward() {
+        // The destination is after the source.
+        test_ok(0x08.into(), 0x00.into(), 0x30);
+    }
+
+    #[test]
+    fn mcopy_gadget_overlap_backward() {
+        // The destination is before the source.
+        test_ok(0x00.into(), 0x08.into(), 0x30);
+    }
+
+    #[test]
+    fn mcopy_gadget_same_range() {
+        test_ok(0x10.into(), 0x10.into(), 0x20);
+    }
+
+    #[test]
+    fn mcopy_gadget_expand_src() {
+        // Reading past the end of the memory expands it with zeros.
+        test_ok(0x00.into(), 0x100.into(), 0x40);
+    }
+
+    #[test]
+    fn mcopy_gadget_expand_dst() {
+        test_ok(0x150.into(), 0x00.into(), 0x40);
+    }
+
+    #[test]
+    fn mcopy_gadget_zero_length() {
+        test_ok(0x00.into(), 0x00.into(), 0x00);
+    }
+
+    #[test]
+    fn mcopy_gadget_overflow_offset_and_zero_length() {
+        test_ok(Word::MAX, Word::MAX, 0x00);
+    }
+}
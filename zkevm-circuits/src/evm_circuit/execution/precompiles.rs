@@ -1,5 +1,23 @@
+mod blake2f;
+pub use blake2f::Blake2fGadget;
+
+mod ec_add;
+pub use ec_add::EcAddGadget;
+
+mod ec_mul;
+pub use ec_mul::EcMulGadget;
+
+mod ec_pairing;
+pub use ec_pairing::EcPairingGadget;
+
 mod ecrecover;
 pub use ecrecover::EcrecoverGadget;
 
+mod hash;
+pub use hash::HashPrecompileGadget;
+
 mod identity;
 pub use identity::IdentityGadget;
+
+mod modexp;
+pub use modexp::ModExpGadget;
@@ -0,0 +1,276 @@
+use bus_mapping::{circuit_input_builder::Call, precompile::PrecompileAuxData};
+use eth_types::{Field, ToScalar};
+use gadgets::util::{select, Expr};
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        step::ExecutionState,
+        util::{
+            common_gadget::RestoreContextGadget,
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            math_gadget::IsEqualGadget,
+            CachedRegion, Cell,
+        },
+    },
+    table::CallContextFieldTag,
+    witness::{Block, Chunk, ExecStep, Transaction},
+};
+
+/// Length of the input to the BLAKE2F precompile as specified in EIP-152.
+const BLAKE2F_INPUT_LEN: u64 = 213;
+/// Length of the output of the BLAKE2F precompile.
+const BLAKE2F_OUTPUT_LEN: u64 = 64;
+
+#[derive(Clone, Debug)]
+pub struct Blake2fGadget<F> {
+    /// Number of rounds, i.e. the required gas. This needs to be the first cell queried, as
+    /// it's constrained in the caller's step by the `PrecompileGadget`.
+    rounds: Cell<F>,
+    is_valid_length: IsEqualGadget<F>,
+    is_success: Cell<F>,
+    callee_address: Cell<F>,
+    caller_id: Cell<F>,
+    call_data_offset: Cell<F>,
+    call_data_length: Cell<F>,
+    return_data_offset: Cell<F>,
+    return_data_length: Cell<F>,
+    restore_context: RestoreContextGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for Blake2fGadget<F> {
+    const EXECUTION_STATE: ExecutionState = ExecutionState::PrecompileBlake2f;
+
+    const NAME: &'static str = "BLAKE2F";
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let rounds = cb.query_cell();
+
+        let [is_success, callee_address, caller_id, call_data_offset, call_data_length, return_data_offset, return_data_length] =
+            [
+                CallContextFieldTag::IsSuccess,
+                CallContextFieldTag::CalleeAddress,
+                CallContextFieldTag::CallerId,
+                CallContextFieldTag::CallDataOffset,
+                CallContextFieldTag::CallDataLength,
+                CallContextFieldTag::ReturnDataOffset,
+                CallContextFieldTag::ReturnDataLength,
+            ]
+            .map(|tag| cb.call_context(None, tag));
+
+        // The call fails, consuming all the gas, if and only if the input length is not 213
+        // bytes. Inputs with an invalid final block indicator flag are handled by the
+        // ErrorPrecompileFailed gadget, and the case of insufficient gas by the
+        // ErrorOogPrecompile gadget.
+        let is_valid_length = cb.is_eq(call_data_length.expr(), BLAKE2F_INPUT_LEN.expr());
+        cb.require_equal(
+            "BLAKE2F succeeds iff the input is 213 bytes",
+            is_success.expr(),
+            is_valid_length.expr(),
+        );
+
+        let gas_cost = select::expr(
+            is_success.expr(),
+            rounds.expr(),
+            cb.curr.state.gas_left.expr(),
+        );
+
+        cb.precompile_info_lookup(
+            cb.execution_state().as_u64().expr(),
+            callee_address.expr(),
+            cb.execution_state().precompile_base_gas_cost().expr(),
+        );
+
+        let restore_context = RestoreContextGadget::construct2(
+            cb,
+            is_success.expr(),
+            gas_cost.expr(),
+            0.expr(),
+            0x00.expr(),                                   // ReturnDataOffset
+            is_success.expr() * BLAKE2F_OUTPUT_LEN.expr(), // ReturnDataLength
+            0.expr(),
+            0.expr(),
+        );
+
+        Self {
+            rounds,
+            is_valid_length,
+            is_success,
+            callee_address,
+            caller_id,
+            call_data_offset,
+            call_data_length,
+            return_data_offset,
+            return_data_length,
+            restore_context,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block<F>,
+        _chunk: &Chunk<F>,
+        _tx: &Transaction,
+        call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        let rounds = match &step.aux_data {
+            Some(PrecompileAuxData::Base { input_bytes, .. })
+                if input_bytes.len() == BLAKE2F_INPUT_LEN as usize =>
+            {
+                u32::from_be_bytes(input_bytes[0..4].try_into().unwrap())
+            }
+            _ => 0,
+        };
+        self.rounds
+            .assign(region, offset, Value::known(F::from(rounds as u64)))?;
+        self.is_valid_length.assign(
+            region,
+            offset,
+            F::from(call.call_data_length),
+            F::from(BLAKE2F_INPUT_LEN),
+        )?;
+        self.is_success.assign(
+            region,
+            offset,
+            Value::known(F::from(u64::from(call.is_success))),
+        )?;
+        self.callee_address.assign(
+            region,
+            offset,
+            Value::known(call.code_address().unwrap().to_scalar().unwrap()),
+        )?;
+        self.caller_id.assign(
+            region,
+            offset,
+            Value::known(F::from(call.caller_id.try_into().unwrap())),
+        )?;
+        self.call_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_offset)),
+        )?;
+        self.call_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_length)),
+        )?;
+        self.return_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_offset)),
+        )?;
+        self.return_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_length)),
+        )?;
+        self.restore_context
+            .assign(region, offset, block, call, step, 7)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bus_mapping::{
+        evm::OpcodeId,
+        precompile::{PrecompileCallArgs, PrecompileCalls},
+    };
+    use eth_types::{bytecode, word, ToWord};
+    use itertools::Itertools;
+    use mock::TestContext;
+
+    use crate::test_util::CircuitTestBuilder;
+
+    lazy_static::lazy_static! {
+        // Test vector 5 of EIP-152, i.e. the compression of "abc" in 12 rounds.
+        static ref SETUP_CODE: eth_types::Bytecode = bytecode! {
+            PUSH32(word!("0x0000000c48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f"))
+            PUSH1(0x00)
+            MSTORE
+            PUSH32(word!("0x3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e13"))
+            PUSH1(0x20)
+            MSTORE
+            PUSH32(word!("0x19cde05b61626300000000000000000000000000000000000000000000000000"))
+            PUSH1(0x40)
+            MSTORE
+            PUSH32(word!("0x0000000003000000000000000000000000000000010000000000000000000000"))
+            PUSH1(0xc0)
+            MSTORE
+        };
+
+        static ref TEST_VECTOR: Vec<PrecompileCallArgs> = {
+            vec![
+                PrecompileCallArgs {
+                    name: "12 rounds success",
+                    setup_code: SETUP_CODE.clone(),
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0xd5.into(),
+                    ret_offset: 0x00.into(),
+                    ret_size: 0x40.into(),
+                    gas: 0xFFF.into(),
+                    address: PrecompileCalls::Blake2F.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "12 rounds success, partial return data",
+                    setup_code: SETUP_CODE.clone(),
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0xd5.into(),
+                    ret_offset: 0xe0.into(),
+                    ret_size: 0x10.into(),
+                    gas: 0xFFF.into(),
+                    address: PrecompileCalls::Blake2F.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "invalid input length (precompile call should fail)",
+                    setup_code: SETUP_CODE.clone(),
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0xd4.into(),
+                    ret_offset: 0x00.into(),
+                    ret_size: 0x40.into(),
+                    gas: 0xFFF.into(),
+                    address: PrecompileCalls::Blake2F.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "insufficient gas (precompile call should fail)",
+                    setup_code: SETUP_CODE.clone(),
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0xd5.into(),
+                    ret_offset: 0x00.into(),
+                    ret_size: 0x40.into(),
+                    address: PrecompileCalls::Blake2F.address().to_word(),
+                    // set gas to be insufficient for 12 rounds
+                    gas: 11.into(),
+                    ..Default::default()
+                },
+            ]
+        };
+    }
+
+    #[test]
+    fn precompile_blake2f_test() {
+        let call_kinds = vec![
+            OpcodeId::CALL,
+            OpcodeId::STATICCALL,
+            OpcodeId::DELEGATECALL,
+            OpcodeId::CALLCODE,
+        ];
+
+        for (test_vector, &call_kind) in TEST_VECTOR.iter().cartesian_product(&call_kinds) {
+            let bytecode = test_vector.with_call_op(call_kind);
+
+            CircuitTestBuilder::new_from_test_ctx(
+                TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+            )
+            .run();
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use bus_mapping::precompile::{PrecompileAuxData, PrecompileCalls};
+use eth_types::{evm_types::GasCost, Field, ToScalar, U256};
+use gadgets::util::{select, Expr};
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        step::ExecutionState,
+        util::{
+            common_gadget::RestoreContextGadget,
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            rlc, CachedRegion, Cell,
+        },
+    },
+    table::CallContextFieldTag,
+    util::word::{Word32Cell, WordExpr},
+    witness::{Block, Call, Chunk, ExecStep, Transaction},
+};
+
+#[derive(Clone, Debug)]
+pub struct EcMulGadget<F> {
+    /// The input and output RLCs are the first cells queried, as they are constrained in the
+    /// caller's step by the `PrecompileGadget`.
+    input_rlc: Cell<F>,
+    output_rlc: Cell<F>,
+    /// RLCs of the 32-byte big-endian coordinates of P and R = s.P.
+    p_x_rlc: Cell<F>,
+    p_y_rlc: Cell<F>,
+    r_x_rlc: Cell<F>,
+    r_y_rlc: Cell<F>,
+    /// The 256-bit scalar s, as in the input.
+    scalar: Word32Cell<F>,
+    is_success: Cell<F>,
+    callee_address: Cell<F>,
+    caller_id: Cell<F>,
+    call_data_offset: Cell<F>,
+    call_data_length: Cell<F>,
+    return_data_offset: Cell<F>,
+    return_data_length: Cell<F>,
+    restore_context: RestoreContextGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for EcMulGadget<F> {
+    const EXECUTION_STATE: ExecutionState = ExecutionState::PrecompileBn256ScalarMul;
+
+    const NAME: &'static str = "BN_MUL";
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let [input_rlc, output_rlc, p_x_rlc, p_y_rlc, r_x_rlc, r_y_rlc] =
+            [(); 6].map(|_| cb.query_cell_phase2());
+        let scalar = cb.query_word32();
+
+        let [is_success, callee_address, caller_id, call_data_offset, call_data_length, return_data_offset, return_data_length] =
+            [
+                CallContextFieldTag::IsSuccess,
+                CallContextFieldTag::CalleeAddress,
+                CallContextFieldTag::CallerId,
+                CallContextFieldTag::CallDataOffset,
+                CallContextFieldTag::CallDataLength,
+                CallContextFieldTag::ReturnDataOffset,
+                CallContextFieldTag::ReturnDataLength,
+            ]
+            .map(|tag| cb.call_context(None, tag));
+
+        // The input is the 96 bytes Px || Py || s, and the output the 64 bytes Rx || Ry.
+        let r_pow_32 = cb.challenges().keccak_powers_of_randomness::<32>()[31].clone();
+        let r_pow_64 = r_pow_32.expr() * r_pow_32.expr();
+        // The limbs of the scalar are little-endian, i.e. in the order of the RLC powers.
+        let scalar_rlc = rlc::expr(
+            &scalar
+                .limbs
+                .iter()
+                .map(|byte| byte.expr())
+                .collect::<Vec<_>>(),
+            cb.challenges().keccak_input(),
+        );
+        cb.require_equal(
+            "input_rlc = RLC(Px || Py || s)",
+            input_rlc.expr(),
+            p_x_rlc.expr() * r_pow_64 + p_y_rlc.expr() * r_pow_32.expr() + scalar_rlc,
+        );
+        cb.require_equal(
+            "output_rlc = RLC(Rx || Ry)",
+            output_rlc.expr(),
+            r_x_rlc.expr() * r_pow_32 + r_y_rlc.expr(),
+        );
+
+        // The ECC circuit verifies that P is a valid point, which is the case iff the call
+        // succeeds, and that R = s.P. The scalar of the ECC table is reduced modulo the order of
+        // the curve, i.e. the modulus of the circuit field, so it is the native value of s.
+        cb.ecc_table_lookup(
+            u64::from(PrecompileCalls::Bn128Mul).expr(),
+            is_success.expr(),
+            p_x_rlc.expr(),
+            p_y_rlc.expr(),
+            scalar.to_word().compress(),
+            0.expr(),
+            0.expr(),
+            r_x_rlc.expr(),
+            r_y_rlc.expr(),
+        );
+
+        let gas_cost = select::expr(
+            is_success.expr(),
+            GasCost::PRECOMPILE_BN256MUL.expr(),
+            cb.curr.state.gas_left.expr(),
+        );
+
+        cb.precompile_info_lookup(
+            cb.execution_state().as_u64().expr(),
+            callee_address.expr(),
+            cb.execution_state().precompile_base_gas_cost().expr(),
+        );
+
+        let restore_context = RestoreContextGadget::construct2(
+            cb,
+            is_success.expr(),
+            gas_cost.expr(),
+            0.expr(),
+            0x00.expr(),                     // ReturnDataOffset
+            is_success.expr() * 0x40.expr(), // ReturnDataLength
+            0.expr(),
+            0.expr(),
+        );
+
+        Self {
+            input_rlc,
+            output_rlc,
+            p_x_rlc,
+            p_y_rlc,
+            r_x_rlc,
+            r_y_rlc,
+            scalar,
+            is_success,
+            callee_address,
+            caller_id,
+            call_data_offset,
+            call_data_length,
+            return_data_offset,
+            return_data_length,
+            restore_context,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block<F>,
+        _chunk: &Chunk<F>,
+        _tx: &Transaction,
+        call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        let Some(PrecompileAuxData::Base {
+            input_bytes,
+            output_bytes,
+            ..
+        }) = &step.aux_data
+        else {
+            log::error!("unexpected aux_data {:?} for ecMul", step.aux_data);
+            return Err(Error::Synthesis);
+        };
+
+        let challenge = region.challenges().keccak_input();
+        let bytes_rlc = |bytes: &[u8]| challenge.map(|r| rlc::value(bytes.iter().rev(), r));
+        // The output is empty when the call fails.
+        let word_rlc = |bytes: &[u8], idx: usize| {
+            bytes_rlc(bytes.get(idx * 32..(idx + 1) * 32).unwrap_or_default())
+        };
+        for (cell, value) in [
+            (&self.input_rlc, bytes_rlc(input_bytes)),
+            (&self.output_rlc, bytes_rlc(output_bytes)),
+            (&self.p_x_rlc, word_rlc(input_bytes, 0)),
+            (&self.p_y_rlc, word_rlc(input_bytes, 1)),
+            (&self.r_x_rlc, word_rlc(output_bytes, 0)),
+            (&self.r_y_rlc, word_rlc(output_bytes, 1)),
+        ] {
+            cell.assign(region, offset, value)?;
+        }
+        self.scalar.assign_u256(
+            region,
+            offset,
+            U256::from_big_endian(input_bytes.get(64..96).unwrap_or_default()),
+        )?;
+
+        self.is_success.assign(
+            region,
+            offset,
+            Value::known(F::from(u64::from(call.is_success))),
+        )?;
+        self.callee_address.assign(
+            region,
+            offset,
+            Value::known(call.code_address().unwrap().to_scalar().unwrap()),
+        )?;
+        self.caller_id.assign(
+            region,
+            offset,
+            Value::known(F::from(call.caller_id.try_into().unwrap())),
+        )?;
+        self.call_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_offset)),
+        )?;
+        self.call_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_length)),
+        )?;
+        self.return_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_offset)),
+        )?;
+        self.return_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_length)),
+        )?;
+        self.restore_context
+            .assign(region, offset, block, call, step, 7)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bus_mapping::{
+        evm::OpcodeId,
+        precompile::{PrecompileCallArgs, PrecompileCalls},
+    };
+    use eth_types::{bytecode, word, ToWord};
+    use itertools::Itertools;
+    use mock::TestContext;
+
+    use crate::test_util::CircuitTestBuilder;
+
+    lazy_static::lazy_static! {
+        static ref TEST_VECTOR: Vec<PrecompileCallArgs> = {
+            vec![
+                PrecompileCallArgs {
+                    name: "ecMul (valid inputs, 2.G1)",
+                    setup_code: bytecode! {
+                        // P = (1, 2)
+                        PUSH1(0x01)
+                        PUSH1(0x00)
+                        MSTORE
+                        PUSH1(0x02)
+                        PUSH1(0x20)
+                        MSTORE
+                        // s = 2
+                        PUSH1(0x02)
+                        PUSH1(0x40)
+                        MSTORE
+                    },
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x60.into(),
+                    ret_offset: 0x60.into(),
+                    ret_size: 0x40.into(),
+                    address: PrecompileCalls::Bn128Mul.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "ecMul (valid inputs, scalar larger than the curve order)",
+                    setup_code: bytecode! {
+                        // P = (1, 2)
+                        PUSH1(0x01)
+                        PUSH1(0x00)
+                        MSTORE
+                        PUSH1(0x02)
+                        PUSH1(0x20)
+                        MSTORE
+                        // s = 2^256 - 1
+                        PUSH32(word!("0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"))
+                        PUSH1(0x40)
+                        MSTORE
+                    },
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x60.into(),
+                    ret_offset: 0x60.into(),
+                    ret_size: 0x40.into(),
+                    address: PrecompileCalls::Bn128Mul.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "ecMul (valid inputs, zero scalar)",
+                    setup_code: bytecode! {
+                        // P = (1, 2), s = 0
+                        PUSH1(0x01)
+                        PUSH1(0x00)
+                        MSTORE
+                        PUSH1(0x02)
+                        PUSH1(0x20)
+                        MSTORE
+                    },
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x60.into(),
+                    ret_offset: 0x60.into(),
+                    ret_size: 0x40.into(),
+                    address: PrecompileCalls::Bn128Mul.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "ecMul (invalid input, P not on the curve)",
+                    setup_code: bytecode! {
+                        // P = (1, 3)
+                        PUSH1(0x01)
+                        PUSH1(0x00)
+                        MSTORE
+                        PUSH1(0x03)
+                        PUSH1(0x20)
+                        MSTORE
+                        // s = 2
+                        PUSH1(0x02)
+                        PUSH1(0x40)
+                        MSTORE
+                    },
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x60.into(),
+                    ret_offset: 0x60.into(),
+                    ret_size: 0x40.into(),
+                    address: PrecompileCalls::Bn128Mul.address().to_word(),
+                    ..Default::default()
+                },
+            ]
+        };
+    }
+
+    #[test]
+    fn precompile_ec_mul_test() {
+        let call_kinds = vec![
+            OpcodeId::CALL,
+            OpcodeId::STATICCALL,
+            OpcodeId::DELEGATECALL,
+            OpcodeId::CALLCODE,
+        ];
+
+        for (test_vector, &call_kind) in TEST_VECTOR.iter().cartesian_product(&call_kinds) {
+            let bytecode = test_vector.with_call_op(call_kind);
+
+            CircuitTestBuilder::new_from_test_ctx(
+                TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+            )
+            .run();
+        }
+    }
+}
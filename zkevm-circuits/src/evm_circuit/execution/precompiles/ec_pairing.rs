@@ -0,0 +1,430 @@
+use bus_mapping::{
+    circuit_input_builder::{N_BYTES_PER_PAIR, N_PAIRING_PER_OP},
+    precompile::{PrecompileAuxData, PrecompileCalls},
+};
+use eth_types::{evm_types::GasCost, Field, ToScalar};
+use gadgets::util::{and, not, select, sum, Expr};
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        step::ExecutionState,
+        util::{
+            common_gadget::RestoreContextGadget,
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            math_gadget::{IsEqualGadget, IsZeroGadget, LtGadget},
+            rlc, CachedRegion, Cell,
+        },
+    },
+    table::CallContextFieldTag,
+    witness::{Block, Call, Chunk, ExecStep, Transaction},
+};
+
+#[derive(Clone, Debug)]
+pub struct EcPairingGadget<F> {
+    /// The input length, the output and the input RLC are the first cells queried, as they are
+    /// constrained in the caller's step by the `PrecompileGadget`.
+    input_len: Cell<F>,
+    output: Cell<F>,
+    input_rlc: Cell<F>,
+    /// input_len = n_pairs * N_BYTES_PER_PAIR + rem
+    n_pairs: Cell<F>,
+    rem: Cell<F>,
+    rem_lt_pair: LtGadget<F, 1>,
+    is_valid_len: IsZeroGadget<F>,
+    is_empty: IsZeroGadget<F>,
+    /// n_pairs == 1, 2, 3, to pad the input up to `N_PAIRING_PER_OP` pairs.
+    is_n_pairs: [IsEqualGadget<F>; 3],
+    /// RLC of the input right-padded with zeros to `N_PAIRING_PER_OP` pairs.
+    padded_input_rlc: Cell<F>,
+    is_success: Cell<F>,
+    callee_address: Cell<F>,
+    caller_id: Cell<F>,
+    call_data_offset: Cell<F>,
+    call_data_length: Cell<F>,
+    return_data_offset: Cell<F>,
+    return_data_length: Cell<F>,
+    restore_context: RestoreContextGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for EcPairingGadget<F> {
+    const EXECUTION_STATE: ExecutionState = ExecutionState::PrecompileBn256Pairing;
+
+    const NAME: &'static str = "BN_PAIRING";
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let input_len = cb.query_cell();
+        let output = cb.query_bool();
+        let input_rlc = cb.query_cell_phase2();
+
+        let [is_success, callee_address, caller_id, call_data_offset, call_data_length, return_data_offset, return_data_length] =
+            [
+                CallContextFieldTag::IsSuccess,
+                CallContextFieldTag::CalleeAddress,
+                CallContextFieldTag::CallerId,
+                CallContextFieldTag::CallDataOffset,
+                CallContextFieldTag::CallDataLength,
+                CallContextFieldTag::ReturnDataOffset,
+                CallContextFieldTag::ReturnDataLength,
+            ]
+            .map(|tag| cb.call_context(None, tag));
+
+        // The circuit supports up to `N_PAIRING_PER_OP` pairs. Longer inputs that fail are handled
+        // by the `PrecompileFailed` gadget.
+        let n_pairs = cb.query_cell();
+        let rem = cb.query_byte();
+        cb.range_lookup(n_pairs.expr(), N_PAIRING_PER_OP as u64 + 1);
+        let rem_lt_pair = cb.is_lt(rem.expr(), N_BYTES_PER_PAIR.expr());
+        cb.require_equal("rem < N_BYTES_PER_PAIR", rem_lt_pair.expr(), 1.expr());
+        cb.require_equal(
+            "input_len = n_pairs * N_BYTES_PER_PAIR + rem",
+            input_len.expr(),
+            n_pairs.expr() * N_BYTES_PER_PAIR.expr() + rem.expr(),
+        );
+        let is_valid_len = cb.is_zero(rem.expr());
+        let is_empty = cb.is_zero(n_pairs.expr());
+
+        // Padding the input with zero bytes shifts its RLC by the number of padding bytes.
+        let r_pow_32 = cb.challenges().keccak_powers_of_randomness::<32>()[31].clone();
+        let r_pow_64 = r_pow_32.expr() * r_pow_32.expr();
+        let r_pow_192 = r_pow_64.expr() * r_pow_64.expr() * r_pow_64;
+        let r_pow_384 = r_pow_192.expr() * r_pow_192.expr();
+        let r_pow_576 = r_pow_384.expr() * r_pow_192.expr();
+        let is_n_pairs = [1, 2, 3].map(|n| cb.is_eq(n_pairs.expr(), n.expr()));
+        let padded_input_rlc = cb.query_cell_phase2();
+        cb.require_equal(
+            "padded_input_rlc = input_rlc * r^(N_BYTES_PER_PAIR * (N_PAIRING_PER_OP - n_pairs))",
+            padded_input_rlc.expr(),
+            input_rlc.expr()
+                * (is_n_pairs[0].expr() * r_pow_576
+                    + is_n_pairs[1].expr() * r_pow_384
+                    + is_n_pairs[2].expr() * r_pow_192
+                    + not::expr(sum::expr(is_n_pairs.iter().map(|is_n| is_n.expr())))),
+        );
+
+        // An input that isn't made of whole pairs is invalid.
+        cb.condition(not::expr(is_valid_len.expr()), |cb| {
+            cb.require_zero(
+                "ecPairing fails if the input length is not a multiple of N_BYTES_PER_PAIR",
+                is_success.expr(),
+            );
+        });
+        // The pairing check of an empty input is trivially satisfied.
+        cb.condition(and::expr([is_valid_len.expr(), is_empty.expr()]), |cb| {
+            cb.require_equal(
+                "ecPairing succeeds with no pairs",
+                is_success.expr(),
+                1.expr(),
+            );
+            cb.require_equal("ecPairing outputs 1 with no pairs", output.expr(), 1.expr());
+        });
+        // The ECC circuit verifies that the points are valid, which is the case iff the call
+        // succeeds, and the pairing check.
+        cb.condition(
+            and::expr([is_valid_len.expr(), not::expr(is_empty.expr())]),
+            |cb| {
+                cb.ecc_table_lookup(
+                    u64::from(PrecompileCalls::Bn128Pairing).expr(),
+                    is_success.expr(),
+                    0.expr(),
+                    0.expr(),
+                    0.expr(),
+                    0.expr(),
+                    padded_input_rlc.expr(),
+                    output.expr(),
+                    0.expr(),
+                );
+            },
+        );
+        cb.condition(not::expr(is_success.expr()), |cb| {
+            cb.require_zero("ecPairing outputs nothing on failure", output.expr());
+        });
+
+        let gas_cost = select::expr(
+            is_success.expr(),
+            GasCost::PRECOMPILE_BN256PAIRING.expr()
+                + n_pairs.expr() * GasCost::PRECOMPILE_BN256PAIRING_PER_PAIR.expr(),
+            cb.curr.state.gas_left.expr(),
+        );
+
+        cb.precompile_info_lookup(
+            cb.execution_state().as_u64().expr(),
+            callee_address.expr(),
+            cb.execution_state().precompile_base_gas_cost().expr(),
+        );
+
+        let restore_context = RestoreContextGadget::construct2(
+            cb,
+            is_success.expr(),
+            gas_cost.expr(),
+            0.expr(),
+            0x00.expr(),                     // ReturnDataOffset
+            is_success.expr() * 0x20.expr(), // ReturnDataLength
+            0.expr(),
+            0.expr(),
+        );
+
+        Self {
+            input_len,
+            output,
+            input_rlc,
+            n_pairs,
+            rem,
+            rem_lt_pair,
+            is_valid_len,
+            is_empty,
+            is_n_pairs,
+            padded_input_rlc,
+            is_success,
+            callee_address,
+            caller_id,
+            call_data_offset,
+            call_data_length,
+            return_data_offset,
+            return_data_length,
+            restore_context,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block<F>,
+        _chunk: &Chunk<F>,
+        _tx: &Transaction,
+        call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        let Some(PrecompileAuxData::Base {
+            input_bytes,
+            output_bytes,
+            ..
+        }) = &step.aux_data
+        else {
+            log::error!("unexpected aux_data {:?} for ecPairing", step.aux_data);
+            return Err(Error::Synthesis);
+        };
+
+        let input_len = call.call_data_length;
+        let n_pairs = input_len / N_BYTES_PER_PAIR as u64;
+        let rem = input_len % N_BYTES_PER_PAIR as u64;
+        // The output is a 32-byte 0 or 1, empty when the call fails.
+        let output = output_bytes.last().copied().unwrap_or_default();
+        for (cell, value) in [
+            (&self.input_len, input_len),
+            (&self.output, output as u64),
+            (&self.n_pairs, n_pairs),
+            (&self.rem, rem),
+        ] {
+            cell.assign(region, offset, Value::known(F::from(value)))?;
+        }
+        self.rem_lt_pair.assign(
+            region,
+            offset,
+            F::from(rem),
+            F::from(N_BYTES_PER_PAIR as u64),
+        )?;
+        self.is_valid_len.assign(region, offset, F::from(rem))?;
+        self.is_empty.assign(region, offset, F::from(n_pairs))?;
+        for (is_n_pairs, n) in self.is_n_pairs.iter().zip(1..) {
+            is_n_pairs.assign(region, offset, F::from(n_pairs), F::from(n))?;
+        }
+
+        let challenge = region.challenges().keccak_input();
+        let n_padding_bytes = if (1..N_PAIRING_PER_OP as u64).contains(&n_pairs) {
+            (N_PAIRING_PER_OP - n_pairs as usize) * N_BYTES_PER_PAIR
+        } else {
+            0
+        };
+        let padded_input_bytes = input_bytes
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(0).take(n_padding_bytes))
+            .collect::<Vec<u8>>();
+        self.input_rlc.assign(
+            region,
+            offset,
+            challenge.map(|r| rlc::value(input_bytes.iter().rev(), r)),
+        )?;
+        self.padded_input_rlc.assign(
+            region,
+            offset,
+            challenge.map(|r| rlc::value(padded_input_bytes.iter().rev(), r)),
+        )?;
+
+        self.is_success.assign(
+            region,
+            offset,
+            Value::known(F::from(u64::from(call.is_success))),
+        )?;
+        self.callee_address.assign(
+            region,
+            offset,
+            Value::known(call.code_address().unwrap().to_scalar().unwrap()),
+        )?;
+        self.caller_id.assign(
+            region,
+            offset,
+            Value::known(F::from(call.caller_id.try_into().unwrap())),
+        )?;
+        self.call_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_offset)),
+        )?;
+        self.call_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_length)),
+        )?;
+        self.return_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_offset)),
+        )?;
+        self.return_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_length)),
+        )?;
+        self.restore_context
+            .assign(region, offset, block, call, step, 7)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bus_mapping::{
+        evm::OpcodeId,
+        precompile::{PrecompileCallArgs, PrecompileCalls},
+    };
+    use eth_types::{bytecode, word, ToWord};
+    use itertools::Itertools;
+    use mock::TestContext;
+
+    use crate::test_util::CircuitTestBuilder;
+
+    lazy_static::lazy_static! {
+        // The pairs (G1, G2) and (-G1, G2), which satisfy the pairing check together.
+        static ref SETUP_CODE: eth_types::Bytecode = bytecode! {
+            // G1 = (1, 2)
+            PUSH1(0x01)
+            PUSH1(0x00)
+            MSTORE
+            PUSH1(0x02)
+            PUSH1(0x20)
+            MSTORE
+            // G2
+            PUSH32(word!("0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"))
+            PUSH1(0x40)
+            MSTORE
+            PUSH32(word!("0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"))
+            PUSH1(0x60)
+            MSTORE
+            PUSH32(word!("0x090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"))
+            PUSH1(0x80)
+            MSTORE
+            PUSH32(word!("0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"))
+            PUSH1(0xa0)
+            MSTORE
+            // -G1 = (1, p - 2)
+            PUSH1(0x01)
+            PUSH1(0xc0)
+            MSTORE
+            PUSH32(word!("0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45"))
+            PUSH1(0xe0)
+            MSTORE
+            // G2
+            PUSH32(word!("0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"))
+            PUSH2(0x100)
+            MSTORE
+            PUSH32(word!("0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"))
+            PUSH2(0x120)
+            MSTORE
+            PUSH32(word!("0x090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"))
+            PUSH2(0x140)
+            MSTORE
+            PUSH32(word!("0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"))
+            PUSH2(0x160)
+            MSTORE
+        };
+
+        static ref TEST_VECTOR: Vec<PrecompileCallArgs> = {
+            vec![
+                PrecompileCallArgs {
+                    name: "ecPairing (no pairs)",
+                    setup_code: bytecode! {},
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x00.into(),
+                    ret_offset: 0x00.into(),
+                    ret_size: 0x20.into(),
+                    address: PrecompileCalls::Bn128Pairing.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "ecPairing (one pair, check fails)",
+                    setup_code: SETUP_CODE.clone(),
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0xc0.into(),
+                    ret_offset: 0x180.into(),
+                    ret_size: 0x20.into(),
+                    address: PrecompileCalls::Bn128Pairing.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "ecPairing (two pairs, check succeeds)",
+                    setup_code: SETUP_CODE.clone(),
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x180.into(),
+                    ret_offset: 0x180.into(),
+                    ret_size: 0x20.into(),
+                    address: PrecompileCalls::Bn128Pairing.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "ecPairing (invalid input length)",
+                    setup_code: SETUP_CODE.clone(),
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0xbf.into(),
+                    ret_offset: 0x180.into(),
+                    ret_size: 0x20.into(),
+                    address: PrecompileCalls::Bn128Pairing.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "ecPairing (invalid input, G1 point not on the curve)",
+                    setup_code: SETUP_CODE.clone(),
+                    call_data_offset: 0x20.into(),
+                    call_data_length: 0xc0.into(),
+                    ret_offset: 0x180.into(),
+                    ret_size: 0x20.into(),
+                    address: PrecompileCalls::Bn128Pairing.address().to_word(),
+                    ..Default::default()
+                },
+            ]
+        };
+    }
+
+    #[test]
+    fn precompile_ec_pairing_test() {
+        let call_kinds = vec![
+            OpcodeId::CALL,
+            OpcodeId::STATICCALL,
+            OpcodeId::DELEGATECALL,
+            OpcodeId::CALLCODE,
+        ];
+
+        for (test_vector, &call_kind) in TEST_VECTOR.iter().cartesian_product(&call_kinds) {
+            let bytecode = test_vector.with_call_op(call_kind);
+
+            CircuitTestBuilder::new_from_test_ctx(
+                TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+            )
+            .run();
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use bus_mapping::circuit_input_builder::Call;
+use eth_types::{evm_types::GasCost, Field, ToScalar};
+use gadgets::util::{select, Expr};
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        param::{N_BYTES_MEMORY_WORD_SIZE, N_BYTES_WORD},
+        step::ExecutionState,
+        util::{
+            common_gadget::RestoreContextGadget, constraint_builder::EVMConstraintBuilder,
+            math_gadget::ConstantDivisionGadget, CachedRegion, Cell,
+        },
+    },
+    table::CallContextFieldTag,
+    witness::{Block, Chunk, ExecStep, Transaction},
+};
+
+/// Gadget for the SHA256 and RIPEMD160 precompiles, whose gas cost only
+/// depends on the input length. The digest itself is verified against the
+/// sha256 or ripemd160 table in the caller's step.
+#[derive(Clone, Debug)]
+pub struct HashPrecompileGadget<F, const S: ExecutionState> {
+    input_word_size: ConstantDivisionGadget<F, N_BYTES_MEMORY_WORD_SIZE>,
+    is_success: Cell<F>,
+    callee_address: Cell<F>,
+    call_data_length: Cell<F>,
+    restore_context: RestoreContextGadget<F>,
+}
+
+impl<F: Field, const S: ExecutionState> HashPrecompileGadget<F, S> {
+    fn per_word_gas_cost() -> u64 {
+        match S {
+            ExecutionState::PrecompileSha256 => GasCost::PRECOMPILE_SHA256_PER_WORD,
+            ExecutionState::PrecompileRipemd160 => GasCost::PRECOMPILE_RIPEMD160_PER_WORD,
+            _ => unreachable!("{S:?} is not a hash precompile"),
+        }
+    }
+}
+
+impl<F: Field, const S: ExecutionState> ExecutionGadget<F> for HashPrecompileGadget<F, S> {
+    const EXECUTION_STATE: ExecutionState = S;
+
+    const NAME: &'static str = match S {
+        ExecutionState::PrecompileSha256 => "SHA256",
+        _ => "RIPEMD160",
+    };
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let [is_success, callee_address, call_data_length] = [
+            CallContextFieldTag::IsSuccess,
+            CallContextFieldTag::CalleeAddress,
+            CallContextFieldTag::CallDataLength,
+        ]
+        .map(|tag| cb.call_context(None, tag));
+
+        let input_word_size = ConstantDivisionGadget::construct(
+            cb,
+            call_data_length.expr() + (N_BYTES_WORD - 1).expr(),
+            N_BYTES_WORD as u64,
+        );
+
+        let base_gas_cost = S.precompile_base_gas_cost();
+        let gas_cost = select::expr(
+            is_success.expr(),
+            base_gas_cost.expr() + input_word_size.quotient() * Self::per_word_gas_cost().expr(),
+            cb.curr.state.gas_left.expr(),
+        );
+
+        cb.precompile_info_lookup(
+            cb.execution_state().as_u64().expr(),
+            callee_address.expr(),
+            base_gas_cost.expr(),
+        );
+
+        // The only failure of the hash precompiles is in the case of insufficient gas for the
+        // call, which is diverted and handled in the ErrorOogPrecompile gadget.
+        let restore_context = RestoreContextGadget::construct2(
+            cb,
+            is_success.expr(),
+            gas_cost.expr(),
+            0.expr(),
+            0x00.expr(),         // ReturnDataOffset
+            N_BYTES_WORD.expr(), // ReturnDataLength: the digest, left-padded to 32 bytes
+            0.expr(),
+            0.expr(),
+        );
+
+        Self {
+            input_word_size,
+            is_success,
+            callee_address,
+            call_data_length,
+            restore_context,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block<F>,
+        _chunk: &Chunk<F>,
+        _tx: &Transaction,
+        call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        self.input_word_size.assign(
+            region,
+            offset,
+            (call.call_data_length + (N_BYTES_WORD as u64) - 1).into(),
+        )?;
+        self.is_success.assign(
+            region,
+            offset,
+            Value::known(F::from(u64::from(call.is_success))),
+        )?;
+        self.callee_address.assign(
+            region,
+            offset,
+            Value::known(call.code_address().unwrap().to_scalar().unwrap()),
+        )?;
+        self.call_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_length)),
+        )?;
+        self.restore_context
+            .assign(region, offset, block, call, step, 3)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bus_mapping::{
+        evm::OpcodeId,
+        precompile::{PrecompileCallArgs, PrecompileCalls},
+    };
+    use eth_types::{bytecode, word, ToWord};
+    use itertools::Itertools;
+    use mock::TestContext;
+
+    use crate::test_util::CircuitTestBuilder;
+
+    lazy_static::lazy_static! {
+        static ref TEST_VECTOR: Vec<PrecompileCallArgs> = {
+            [PrecompileCalls::Sha256, PrecompileCalls::Ripemd160]
+                .into_iter()
+                .flat_map(|precompile| {
+                    let address = precompile.address().to_word();
+                    vec![
+                        PrecompileCallArgs {
+                            name: "empty input",
+                            setup_code: bytecode! {},
+                            call_data_offset: 0x00.into(),
+                            call_data_length: 0x00.into(),
+                            ret_offset: 0x00.into(),
+                            ret_size: 0x20.into(),
+                            gas: 0xFFF.into(),
+                            address,
+                            ..Default::default()
+                        },
+                        PrecompileCallArgs {
+                            name: "single-byte success",
+                            setup_code: bytecode! {
+                                // place params in memory
+                                PUSH1(0xff)
+                                PUSH1(0x00)
+                                MSTORE
+                            },
+                            call_data_offset: 0x1f.into(),
+                            call_data_length: 0x01.into(),
+                            ret_offset: 0x3f.into(),
+                            ret_size: 0x20.into(),
+                            gas: 0xFFF.into(),
+                            address,
+                            ..Default::default()
+                        },
+                        PrecompileCallArgs {
+                            name: "multi-bytes success (more than 32 bytes)",
+                            setup_code: bytecode! {
+                                // place params in memory
+                                PUSH30(word!("0x0123456789abcdef0f1e2d3c4b5a6978"))
+                                PUSH1(0x00) // place from 0x00 in memory
+                                MSTORE
+                                PUSH30(word!("0xaabbccdd001122331039abcdefefef84"))
+                                PUSH1(0x20) // place from 0x20 in memory
+                                MSTORE
+                            },
+                            // hash 63 bytes from memory addr 0
+                            call_data_offset: 0x00.into(),
+                            call_data_length: 0x3f.into(),
+                            // return only 16 bytes of the digest and write from memory addr 72
+                            ret_offset: 0x48.into(),
+                            ret_size: 0x10.into(),
+                            gas: 0xFFF.into(),
+                            address,
+                            ..Default::default()
+                        },
+                        PrecompileCallArgs {
+                            name: "no return data",
+                            setup_code: bytecode! {
+                                // place params in memory
+                                PUSH16(word!("0x0123456789abcdef0f1e2d3c4b5a6978"))
+                                PUSH1(0x00)
+                                MSTORE
+                            },
+                            call_data_offset: 0x00.into(),
+                            call_data_length: 0x20.into(),
+                            ret_offset: 0x00.into(),
+                            ret_size: 0x00.into(),
+                            gas: 0xFFF.into(),
+                            address,
+                            ..Default::default()
+                        },
+                        PrecompileCallArgs {
+                            name: "insufficient gas (precompile call should fail)",
+                            setup_code: bytecode! {
+                                // place params in memory
+                                PUSH16(word!("0x0123456789abcdef0f1e2d3c4b5a6978"))
+                                PUSH1(0x00)
+                                MSTORE
+                            },
+                            call_data_offset: 0x00.into(),
+                            call_data_length: 0x10.into(),
+                            ret_offset: 0x20.into(),
+                            ret_size: 0x20.into(),
+                            address,
+                            // set gas to be insufficient
+                            gas: 2.into(),
+                            ..Default::default()
+                        },
+                    ]
+                })
+                .collect()
+        };
+    }
+
+    #[test]
+    fn precompile_hash_test() {
+        let call_kinds = vec![
+            OpcodeId::CALL,
+            OpcodeId::STATICCALL,
+            OpcodeId::DELEGATECALL,
+            OpcodeId::CALLCODE,
+        ];
+
+        for (test_vector, &call_kind) in TEST_VECTOR.iter().cartesian_product(&call_kinds) {
+            let bytecode = test_vector.with_call_op(call_kind);
+
+            CircuitTestBuilder::new_from_test_ctx(
+                TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+            )
+            .run();
+        }
+    }
+}
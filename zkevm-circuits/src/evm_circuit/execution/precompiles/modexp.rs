@@ -0,0 +1,419 @@
+use bus_mapping::precompile::{PrecompileAuxData, MODEXP_INPUT_LIMIT};
+use eth_types::{evm_types::GasCost, Field, ToScalar};
+use gadgets::util::{select, Expr};
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        param::{N_BYTES_GAS, N_BYTES_U64},
+        step::ExecutionState,
+        util::{
+            common_gadget::RestoreContextGadget,
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            math_gadget::{IsZeroGadget, LtGadget, MinMaxGadget},
+            rlc, CachedRegion, Cell,
+        },
+    },
+    table::CallContextFieldTag,
+    witness::{Block, Call, Chunk, ExecStep, Transaction},
+};
+
+/// The length of the exponent beyond which only the first 32 bytes count for the gas cost
+const MODEXP_EXP_HEAD_LEN: u64 = 32;
+
+#[derive(Clone, Debug)]
+pub struct ModExpGadget<F> {
+    /// The lengths, the MSB of the exponent and the output are the first cells queried, as they
+    /// are constrained in the caller's step by the `PrecompileGadget`, with a lookup to the
+    /// ModExp table.
+    input_len: Cell<F>,
+    base_len: Cell<F>,
+    exp_len: Cell<F>,
+    mod_len: Cell<F>,
+    exp_msb: Cell<F>,
+    output_rlc: Cell<F>,
+    input_len_limit: MinMaxGadget<F, N_BYTES_U64>,
+    max_len: MinMaxGadget<F, 1>,
+    words: Cell<F>,
+    is_long_exp: LtGadget<F, 1>,
+    is_adjusted_zero: IsZeroGadget<F>,
+    quotient: Cell<F>,
+    remainder: Cell<F>,
+    required_gas: MinMaxGadget<F, 3>,
+    is_insufficient_gas: LtGadget<F, N_BYTES_GAS>,
+    is_success: Cell<F>,
+    callee_address: Cell<F>,
+    caller_id: Cell<F>,
+    call_data_offset: Cell<F>,
+    call_data_length: Cell<F>,
+    return_data_offset: Cell<F>,
+    return_data_length: Cell<F>,
+    restore_context: RestoreContextGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for ModExpGadget<F> {
+    const EXECUTION_STATE: ExecutionState = ExecutionState::PrecompileBigModExp;
+
+    const NAME: &'static str = "MODEXP";
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let [input_len, base_len, exp_len, mod_len, exp_msb] = [(); 5].map(|_| cb.query_cell());
+        let output_rlc = cb.query_cell_phase2();
+
+        let [is_success, callee_address, caller_id, call_data_offset, call_data_length, return_data_offset, return_data_length] =
+            [
+                CallContextFieldTag::IsSuccess,
+                CallContextFieldTag::CalleeAddress,
+                CallContextFieldTag::CallerId,
+                CallContextFieldTag::CallDataOffset,
+                CallContextFieldTag::CallDataLength,
+                CallContextFieldTag::ReturnDataOffset,
+                CallContextFieldTag::ReturnDataLength,
+            ]
+            .map(|tag| cb.call_context(None, tag));
+
+        // The input bytes beyond MODEXP_INPUT_LIMIT are never used, as the lengths of the base,
+        // exponent and modulus are within the supported size.
+        let input_len_limit = cb.min_max(call_data_length.expr(), MODEXP_INPUT_LIMIT.expr());
+        cb.require_equal(
+            "input_len = min(call_data_length, MODEXP_INPUT_LIMIT)",
+            input_len.expr(),
+            input_len_limit.min(),
+        );
+
+        // EIP-2565: the multiplication complexity is words^2, with words = ceil(max_len / 8).
+        // The lengths are at most 128 bytes, as they come from the ModExp table.
+        let max_len = cb.min_max(base_len.expr(), mod_len.expr());
+        let words = cb.query_cell();
+        let words_padding = words.expr() * 8.expr() - max_len.max();
+        cb.range_lookup(words_padding.clone(), 16);
+        cb.range_lookup(words_padding + 8.expr(), 16);
+        let multiplication_complexity = words.expr() * words.expr();
+
+        // The iteration count is the bit length of the exponent minus 1, with only the first 32
+        // bytes of the exponent counted bit by bit, and at least 1.
+        let is_long_exp = cb.is_lt(MODEXP_EXP_HEAD_LEN.expr(), exp_len.expr());
+        let adjusted_exp_len =
+            is_long_exp.expr() * (exp_len.expr() - MODEXP_EXP_HEAD_LEN.expr()) * 8.expr()
+                + exp_msb.expr();
+        let is_adjusted_zero = cb.is_zero(adjusted_exp_len.clone());
+        let iteration_count = adjusted_exp_len + is_adjusted_zero.expr();
+
+        // required_gas = max(200, multiplication_complexity * iteration_count / 3)
+        let quotient = cb.query_cell();
+        let remainder = cb.query_cell();
+        cb.range_lookup(remainder.expr(), 5);
+        cb.range_lookup(remainder.expr() + 2.expr(), 5);
+        cb.require_equal(
+            "multiplication_complexity * iteration_count = quotient * 3 + remainder",
+            multiplication_complexity * iteration_count,
+            quotient.expr() * 3.expr() + remainder.expr(),
+        );
+        let required_gas = cb.min_max(GasCost::PRECOMPILE_MODEXP_MIN.expr(), quotient.expr());
+
+        // MODEXP only fails when the gas is insufficient, in which case it consumes all the gas.
+        let is_insufficient_gas = cb.is_lt(cb.curr.state.gas_left.expr(), required_gas.max());
+        cb.require_equal(
+            "MODEXP succeeds iff the gas is sufficient",
+            is_success.expr(),
+            1.expr() - is_insufficient_gas.expr(),
+        );
+        let gas_cost = select::expr(
+            is_success.expr(),
+            required_gas.max(),
+            cb.curr.state.gas_left.expr(),
+        );
+
+        cb.precompile_info_lookup(
+            cb.execution_state().as_u64().expr(),
+            callee_address.expr(),
+            cb.execution_state().precompile_base_gas_cost().expr(),
+        );
+
+        let restore_context = RestoreContextGadget::construct2(
+            cb,
+            is_success.expr(),
+            gas_cost.expr(),
+            0.expr(),
+            0x00.expr(),                        // ReturnDataOffset
+            is_success.expr() * mod_len.expr(), // ReturnDataLength
+            0.expr(),
+            0.expr(),
+        );
+
+        Self {
+            input_len,
+            base_len,
+            exp_len,
+            mod_len,
+            exp_msb,
+            output_rlc,
+            input_len_limit,
+            max_len,
+            words,
+            is_long_exp,
+            is_adjusted_zero,
+            quotient,
+            remainder,
+            required_gas,
+            is_insufficient_gas,
+            is_success,
+            callee_address,
+            caller_id,
+            call_data_offset,
+            call_data_length,
+            return_data_offset,
+            return_data_length,
+            restore_context,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block<F>,
+        _chunk: &Chunk<F>,
+        _tx: &Transaction,
+        call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        let Some(PrecompileAuxData::Modexp(aux_data)) = &step.aux_data else {
+            log::error!("unexpected aux_data {:?} for modexp", step.aux_data);
+            return Err(Error::Synthesis);
+        };
+
+        let input_len = call.call_data_length.min(MODEXP_INPUT_LIMIT as u64);
+        let [base_len, exp_len, mod_len] =
+            [aux_data.base_len, aux_data.exp_len, aux_data.mod_len].map(|len| len as u64);
+        for (cell, value) in [
+            (&self.input_len, input_len),
+            (&self.base_len, base_len),
+            (&self.exp_len, exp_len),
+            (&self.mod_len, mod_len),
+            (&self.exp_msb, aux_data.exp_msb),
+        ] {
+            cell.assign(region, offset, Value::known(F::from(value)))?;
+        }
+        self.output_rlc.assign(
+            region,
+            offset,
+            region
+                .challenges()
+                .keccak_input()
+                .map(|r| rlc::value(aux_data.output_bytes.iter().rev(), r)),
+        )?;
+        self.input_len_limit.assign(
+            region,
+            offset,
+            F::from(call.call_data_length),
+            F::from(MODEXP_INPUT_LIMIT as u64),
+        )?;
+
+        // Gas cost, as in EIP-2565
+        self.max_len
+            .assign(region, offset, F::from(base_len), F::from(mod_len))?;
+        let words = (base_len.max(mod_len) + 7) / 8;
+        self.words
+            .assign(region, offset, Value::known(F::from(words)))?;
+        self.is_long_exp.assign(
+            region,
+            offset,
+            F::from(MODEXP_EXP_HEAD_LEN),
+            F::from(exp_len),
+        )?;
+        let adjusted_exp_len = exp_len.saturating_sub(MODEXP_EXP_HEAD_LEN) * 8 + aux_data.exp_msb;
+        self.is_adjusted_zero
+            .assign(region, offset, F::from(adjusted_exp_len))?;
+        let product = words * words * adjusted_exp_len.max(1);
+        self.quotient
+            .assign(region, offset, Value::known(F::from(product / 3)))?;
+        self.remainder
+            .assign(region, offset, Value::known(F::from(product % 3)))?;
+        let (_, required_gas) = self.required_gas.assign(
+            region,
+            offset,
+            F::from(GasCost::PRECOMPILE_MODEXP_MIN),
+            F::from(product / 3),
+        )?;
+        self.is_insufficient_gas
+            .assign(region, offset, F::from(step.gas_left), required_gas)?;
+
+        self.is_success.assign(
+            region,
+            offset,
+            Value::known(F::from(u64::from(call.is_success))),
+        )?;
+        self.callee_address.assign(
+            region,
+            offset,
+            Value::known(call.code_address().unwrap().to_scalar().unwrap()),
+        )?;
+        self.caller_id.assign(
+            region,
+            offset,
+            Value::known(F::from(call.caller_id.try_into().unwrap())),
+        )?;
+        self.call_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_offset)),
+        )?;
+        self.call_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_length)),
+        )?;
+        self.return_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_offset)),
+        )?;
+        self.return_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_length)),
+        )?;
+        self.restore_context
+            .assign(region, offset, block, call, step, 7)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bus_mapping::{
+        evm::OpcodeId,
+        precompile::{PrecompileCallArgs, PrecompileCalls},
+    };
+    use eth_types::{bytecode, word, ToWord};
+    use itertools::Itertools;
+    use mock::TestContext;
+
+    use crate::test_util::CircuitTestBuilder;
+
+    lazy_static::lazy_static! {
+        // 3^5 % 7, with 1-byte base, exponent and modulus.
+        static ref SETUP_CODE: eth_types::Bytecode = bytecode! {
+            PUSH1(0x01)
+            PUSH1(0x00)
+            MSTORE
+            PUSH1(0x01)
+            PUSH1(0x20)
+            MSTORE
+            PUSH1(0x01)
+            PUSH1(0x40)
+            MSTORE
+            PUSH32(word!("0x0305070000000000000000000000000000000000000000000000000000000000"))
+            PUSH1(0x60)
+            MSTORE
+        };
+
+        // 2^e % 13, with a 33-byte exponent e = 0x0011 00..00 01.
+        static ref SETUP_CODE_LONG_EXP: eth_types::Bytecode = bytecode! {
+            PUSH1(0x01)
+            PUSH1(0x00)
+            MSTORE
+            PUSH1(0x21)
+            PUSH1(0x20)
+            MSTORE
+            PUSH1(0x01)
+            PUSH1(0x40)
+            MSTORE
+            PUSH1(0x02)
+            PUSH1(0x60)
+            MSTORE8
+            PUSH1(0x11)
+            PUSH1(0x62)
+            MSTORE8
+            PUSH1(0x01)
+            PUSH1(0x81)
+            MSTORE8
+            PUSH1(0x0d)
+            PUSH1(0x82)
+            MSTORE8
+        };
+
+        static ref TEST_VECTOR: Vec<PrecompileCallArgs> = {
+            vec![
+                PrecompileCallArgs {
+                    name: "modexp success",
+                    setup_code: SETUP_CODE.clone(),
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x63.into(),
+                    ret_offset: 0x9f.into(),
+                    ret_size: 0x01.into(),
+                    gas: 0xFFF.into(),
+                    address: PrecompileCalls::Modexp.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "modexp missing modulus byte (zero modulus)",
+                    setup_code: SETUP_CODE.clone(),
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x62.into(),
+                    ret_offset: 0x9f.into(),
+                    ret_size: 0x01.into(),
+                    gas: 0xFFF.into(),
+                    address: PrecompileCalls::Modexp.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "modexp input longer than the supported limit",
+                    setup_code: SETUP_CODE.clone(),
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x200.into(),
+                    ret_offset: 0x9f.into(),
+                    ret_size: 0x01.into(),
+                    gas: 0xFFF.into(),
+                    address: PrecompileCalls::Modexp.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "modexp long exponent",
+                    setup_code: SETUP_CODE_LONG_EXP.clone(),
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x83.into(),
+                    ret_offset: 0x9f.into(),
+                    ret_size: 0x01.into(),
+                    gas: 0xFFF.into(),
+                    address: PrecompileCalls::Modexp.address().to_word(),
+                    ..Default::default()
+                },
+                PrecompileCallArgs {
+                    name: "modexp insufficient gas (precompile call should fail)",
+                    setup_code: SETUP_CODE.clone(),
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x63.into(),
+                    ret_offset: 0x9f.into(),
+                    ret_size: 0x01.into(),
+                    // the minimum cost of MODEXP is 200
+                    gas: 199.into(),
+                    address: PrecompileCalls::Modexp.address().to_word(),
+                    ..Default::default()
+                },
+            ]
+        };
+    }
+
+    #[test]
+    fn precompile_modexp_test() {
+        let call_kinds = vec![
+            OpcodeId::CALL,
+            OpcodeId::STATICCALL,
+            OpcodeId::DELEGATECALL,
+            OpcodeId::CALLCODE,
+        ];
+
+        for (test_vector, &call_kind) in TEST_VECTOR.iter().cartesian_product(&call_kinds) {
+            let bytecode = test_vector.with_call_op(call_kind);
+
+            CircuitTestBuilder::new_from_test_ctx(
+                TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+            )
+            .run();
+        }
+    }
+}
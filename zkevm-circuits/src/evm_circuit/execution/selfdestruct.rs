@@ -0,0 +1,423 @@
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        step::ExecutionState,
+        util::{
+            and,
+            common_gadget::{RestoreContextGadget, TransferToGadget},
+            constraint_builder::{
+                ConstrainBuilderCommon, EVMConstraintBuilder, ReversionInfo, StepStateTransition,
+                Transition::{Delta, Same},
+            },
+            math_gadget::{IsEqualWordGadget, IsZeroWordGadget},
+            not, or, select, AccountAddress, CachedRegion, Cell, StepRws,
+        },
+        witness::{Block, Call, Chunk, ExecStep, Transaction},
+    },
+    table::{AccountFieldTag, CallContextFieldTag},
+    util::{
+        word::{Word32Cell, WordExpr, WordLoHi, WordLoHiCell},
+        Expr,
+    },
+};
+use eth_types::{evm_types::GasCost, Field, ToAddress, ToWord};
+use halo2_proofs::{
+    circuit::Value,
+    plonk::{Error, Expression},
+};
+
+/// Gadget for the SELFDESTRUCT opcode: the balance of the current account is
+/// always moved to the beneficiary. Before Cancun the account is always
+/// destroyed, while with EIP-6780 it's only destroyed when it has been created
+/// in the same transaction.
+/// The account itself is wiped in EndTx, so it's left untouched here apart from
+/// its balance.
+#[derive(Clone, Debug)]
+pub(crate) struct SelfDestructGadget<F> {
+    opcode: Cell<F>,
+    beneficiary: AccountAddress<F>,
+    tx_id: Cell<F>,
+    is_static: Cell<F>,
+    reversion_info: ReversionInfo<F>,
+    callee_address: WordLoHiCell<F>,
+    is_warm: Cell<F>,
+    beneficiary_code_hash: WordLoHiCell<F>,
+    beneficiary_not_exists: IsZeroWordGadget<F, WordLoHiCell<F>>,
+    is_created: Cell<F>,
+    balance: Word32Cell<F>,
+    balance_is_zero: IsZeroWordGadget<F, Word32Cell<F>>,
+    is_self: IsEqualWordGadget<F, WordLoHi<Expression<F>>, WordLoHi<Expression<F>>>,
+    transfer: TransferToGadget<F>,
+    restore_context: RestoreContextGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for SelfDestructGadget<F> {
+    const NAME: &'static str = "SELFDESTRUCT";
+
+    const EXECUTION_STATE: ExecutionState = ExecutionState::SELFDESTRUCT;
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let opcode = cb.query_cell();
+        cb.opcode_lookup(opcode.expr(), 1.expr());
+
+        let beneficiary = cb.query_account_address();
+        cb.stack_pop(beneficiary.to_word());
+
+        let tx_id = cb.call_context(None, CallContextFieldTag::TxId);
+
+        // Constrain we're not in a STATICCALL context.
+        let is_static = cb.call_context(None, CallContextFieldTag::IsStatic);
+        cb.require_zero("is_static is false", is_static.expr());
+
+        let mut reversion_info = cb.reversion_info_read(None);
+        let callee_address = cb.call_context_read_as_word(None, CallContextFieldTag::CalleeAddress);
+
+        // Add beneficiary to access list.
+        let is_warm = cb.query_bool();
+        cb.account_access_list_write_unchecked(
+            tx_id.expr(),
+            beneficiary.to_word(),
+            1.expr(),
+            is_warm.expr(),
+            Some(&mut reversion_info),
+        );
+
+        // For non-existing accounts the code_hash must be 0 in the rw_table.
+        let beneficiary_code_hash = cb.query_word_unchecked();
+        cb.account_read(
+            beneficiary.to_word(),
+            AccountFieldTag::CodeHash,
+            beneficiary_code_hash.to_word(),
+        );
+        let beneficiary_not_exists = cb.is_zero_word(&beneficiary_code_hash);
+
+        // EIP-6780: the account is only destroyed if it has been created in the
+        // current transaction.
+        let is_created = cb.query_bool();
+        cb.tx_created_account_read(tx_id.expr(), callee_address.to_word(), is_created.expr());
+        let is_destructed = if cb.feature_config.hardfork.is_eip6780_enabled() {
+            is_created.expr()
+        } else {
+            1.expr()
+        };
+
+        let balance = cb.query_word32();
+        cb.account_read(
+            callee_address.to_word(),
+            AccountFieldTag::Balance,
+            balance.to_word(),
+        );
+        let balance_is_zero = cb.is_zero_word(&balance);
+
+        let is_self = cb.is_eq_word(&beneficiary.to_word(), &callee_address.to_word());
+        let not_self = not::expr(is_self.expr());
+
+        // The balance is moved to the beneficiary. When the beneficiary is the
+        // account itself, the balance is only burnt if the account is destroyed.
+        let is_balance_cleared = or::expr([not_self.expr(), is_destructed]);
+        cb.condition(is_balance_cleared.expr(), |cb| {
+            cb.account_write(
+                callee_address.to_word(),
+                AccountFieldTag::Balance,
+                WordLoHi::zero(),
+                balance.to_word(),
+                Some(&mut reversion_info),
+            );
+        });
+        let transfer = cb.condition(not_self.expr(), |cb| {
+            TransferToGadget::construct(
+                cb,
+                beneficiary.to_word(),
+                not::expr(beneficiary_not_exists.expr()),
+                false.expr(),
+                balance.clone(),
+                Some(&mut reversion_info),
+            )
+        });
+
+        // Call ends with SELFDESTRUCT must be successful
+        cb.call_context_lookup_read(None, CallContextFieldTag::IsSuccess, WordLoHi::one());
+
+        let gas_cost = GasCost::SELFDESTRUCT.expr()
+            + select::expr(
+                is_warm.expr(),
+                0.expr(),
+                GasCost::COLD_ACCOUNT_ACCESS.expr(),
+            )
+            + and::expr([
+                beneficiary_not_exists.expr(),
+                not::expr(balance_is_zero.expr()),
+            ]) * GasCost::NEW_ACCOUNT.expr();
+
+        // 1 access list write, balance clearing and transfer to the beneficiary.
+        let reversible_write_counter_increase =
+            1.expr() + is_balance_cleared + not_self * transfer.rw_delta();
+
+        let is_to_end_tx = cb.next.execution_state_selector([ExecutionState::EndTx]);
+        cb.require_equal(
+            "Go to EndTx only when is_root",
+            cb.curr.state.is_root.expr(),
+            is_to_end_tx,
+        );
+
+        // When it's a root call
+        cb.condition(cb.curr.state.is_root.expr(), |cb| {
+            cb.require_step_state_transition(StepStateTransition {
+                call_id: Same,
+                rw_counter: Delta(cb.rw_counter_offset()),
+                gas_left: Delta(-gas_cost.expr()),
+                ..StepStateTransition::any()
+            });
+        });
+
+        // When it's an internal call. The constant gas cost of SELFDESTRUCT is
+        // subtracted from the gas returned to the caller.
+        let restore_context = cb.condition(1.expr() - cb.curr.state.is_root.expr(), |cb| {
+            RestoreContextGadget::construct(
+                cb,
+                true.expr(),
+                0.expr(),
+                0.expr(),
+                0.expr(),
+                gas_cost.expr(),
+                reversible_write_counter_increase,
+            )
+        });
+
+        Self {
+            opcode,
+            beneficiary,
+            tx_id,
+            is_static,
+            reversion_info,
+            callee_address,
+            is_warm,
+            beneficiary_code_hash,
+            beneficiary_not_exists,
+            is_created,
+            balance,
+            balance_is_zero,
+            is_self,
+            transfer,
+            restore_context,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block<F>,
+        _chunk: &Chunk<F>,
+        tx: &Transaction,
+        call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        let opcode = step.opcode().unwrap();
+        self.opcode
+            .assign(region, offset, Value::known(F::from(opcode.as_u64())))?;
+
+        let mut rws = StepRws::new(block, step);
+        let beneficiary = rws.next().stack_value().to_address();
+        self.beneficiary.assign_h160(region, offset, beneficiary)?;
+
+        self.tx_id
+            .assign(region, offset, Value::known(F::from(tx.id)))?;
+        self.is_static
+            .assign(region, offset, Value::known(F::from(call.is_static as u64)))?;
+        self.reversion_info.assign(
+            region,
+            offset,
+            call.rw_counter_end_of_reversion,
+            call.is_persistent,
+        )?;
+        self.callee_address
+            .assign_h160(region, offset, call.address)?;
+        rws.offset_add(5); // TxId, IsStatic, RwCounterEndOfReversion, IsPersistent, CalleeAddress
+
+        let is_warm = rws.next().tx_access_list_value_pair().1;
+        self.is_warm
+            .assign(region, offset, Value::known(F::from(is_warm as u64)))?;
+
+        let beneficiary_code_hash = rws.next().account_codehash_pair().0;
+        self.beneficiary_code_hash
+            .assign_u256(region, offset, beneficiary_code_hash)?;
+        self.beneficiary_not_exists
+            .assign_u256(region, offset, beneficiary_code_hash)?;
+
+        let is_created = rws.next().tx_created_account_value_pair().0;
+        self.is_created
+            .assign(region, offset, Value::known(F::from(is_created as u64)))?;
+        let is_destructed = is_created || !block.feature_config.hardfork.is_eip6780_enabled();
+
+        let balance = rws.next().account_balance_pair().0;
+        self.balance.assign_u256(region, offset, balance)?;
+        self.balance_is_zero.assign_u256(region, offset, balance)?;
+
+        let is_self = beneficiary == call.address;
+        self.is_self.assign_u256(
+            region,
+            offset,
+            beneficiary.to_word(),
+            call.address.to_word(),
+        )?;
+
+        if !is_self || is_destructed {
+            rws.next(); // callee balance = 0
+        }
+        if !is_self {
+            self.transfer.assign(
+                region,
+                offset,
+                &mut rws,
+                !beneficiary_code_hash.is_zero(),
+                balance,
+                false,
+            )?;
+        }
+
+        rws.next(); // IsSuccess
+
+        if !call.is_root {
+            self.restore_context
+                .assign(region, offset, block, call, step, rws.offset())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_util::CircuitTestBuilder;
+    use bus_mapping::circuit_input_builder::FeatureConfig;
+    use eth_types::{address, bytecode, evm_types::Hardfork, Address, Bytecode, ToWord, Word};
+    use mock::{eth, TestContext, MOCK_ACCOUNTS};
+
+    fn selfdestruct_code(beneficiary: Address) -> Bytecode {
+        bytecode! {
+            PUSH20(beneficiary.to_word())
+            SELFDESTRUCT
+        }
+    }
+
+    fn test_root_ok(beneficiary: Address, warm_up: bool) {
+        test_root_ok_with_hardfork(beneficiary, warm_up, Hardfork::default());
+    }
+
+    fn test_root_ok_with_hardfork(beneficiary: Address, warm_up: bool, hardfork: Hardfork) {
+        let mut code = Bytecode::default();
+        if warm_up {
+            code.append(&bytecode! {
+                PUSH20(beneficiary.to_word())
+                BALANCE
+                POP
+            });
+        }
+        code.append(&selfdestruct_code(beneficiary));
+
+        let ctx = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).balance(eth(1)).code(code);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(1u64 << 20));
+                accs[2].address(MOCK_ACCOUNTS[2]).balance(eth(10));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[2].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx)
+            .feature(FeatureConfig {
+                hardfork,
+                ..Default::default()
+            })
+            .run();
+    }
+
+    #[test]
+    fn selfdestruct_gadget_existing_beneficiary() {
+        test_root_ok(MOCK_ACCOUNTS[1], false);
+        test_root_ok(MOCK_ACCOUNTS[1], true);
+    }
+
+    #[test]
+    fn selfdestruct_gadget_non_existing_beneficiary() {
+        test_root_ok(
+            address!("0x0000000000000000000000000000000000cafe01"),
+            false,
+        );
+    }
+
+    #[test]
+    fn selfdestruct_gadget_self_beneficiary() {
+        test_root_ok(MOCK_ACCOUNTS[0], false);
+    }
+
+    #[test]
+    fn selfdestruct_gadget_before_cancun() {
+        // The account is destroyed although it was not created in the tx.
+        test_root_ok_with_hardfork(MOCK_ACCOUNTS[1], false, Hardfork::Shanghai);
+        test_root_ok_with_hardfork(MOCK_ACCOUNTS[0], false, Hardfork::Shanghai);
+    }
+
+    #[test]
+    fn selfdestruct_gadget_internal_call() {
+        let code_a = bytecode! {
+            PUSH1(0) // retLength
+            PUSH1(0) // retOffset
+            PUSH1(0) // argsLength
+            PUSH1(0) // argsOffset
+            PUSH1(0) // value
+            PUSH20(MOCK_ACCOUNTS[1].to_word())
+            GAS
+            CALL
+            STOP
+        };
+
+        let ctx = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code_a);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(eth(1))
+                    .code(selfdestruct_code(MOCK_ACCOUNTS[3]));
+                accs[2].address(MOCK_ACCOUNTS[2]).balance(eth(10));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[2].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+
+    #[test]
+    fn selfdestruct_gadget_created_in_tx() {
+        // The init code destroys the contract being deployed.
+        let ctx = TestContext::<1, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).balance(eth(10));
+            },
+            |mut txs, _accs| {
+                txs[0]
+                    .from(MOCK_ACCOUNTS[0])
+                    .value(eth(1))
+                    .input(selfdestruct_code(MOCK_ACCOUNTS[1]).into());
+            },
+            |block, _tx| block,
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+}
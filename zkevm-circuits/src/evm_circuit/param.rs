@@ -2,6 +2,7 @@
 use super::table::Table;
 use crate::evm_circuit::{step::ExecutionState, EvmCircuit};
 use bus_mapping::circuit_input_builder::FeatureConfig;
+use eth_types::evm_types::MAX_BLOB_HASHES_PER_TX;
 use halo2_proofs::{
     halo2curves::bn256::Fr,
     plonk::{Circuit, ConstraintSystem},
@@ -44,6 +45,11 @@ pub(crate) const EVM_LOOKUP_COLS: usize = FIXED_TABLE_LOOKUPS
     + KECCAK_TABLE_LOOKUPS
     + EXP_TABLE_LOOKUPS
     + SIG_TABLE_LOOKUPS
+    + SHA256_TABLE_LOOKUPS
+    + RIPEMD160_TABLE_LOOKUPS
+    + BLAKE2F_TABLE_LOOKUPS
+    + MODEXP_TABLE_LOOKUPS
+    + ECC_TABLE_LOOKUPS
     + CHUNK_CTX_TABLE_LOOKUPS;
 
 /// Lookups done per row.
@@ -57,6 +63,11 @@ pub const LOOKUP_CONFIG: &[(Table, usize)] = &[
     (Table::Keccak, KECCAK_TABLE_LOOKUPS),
     (Table::Exp, EXP_TABLE_LOOKUPS),
     (Table::Sig, SIG_TABLE_LOOKUPS),
+    (Table::Sha256, SHA256_TABLE_LOOKUPS),
+    (Table::Ripemd160, RIPEMD160_TABLE_LOOKUPS),
+    (Table::Blake2f, BLAKE2F_TABLE_LOOKUPS),
+    (Table::ModExp, MODEXP_TABLE_LOOKUPS),
+    (Table::Ecc, ECC_TABLE_LOOKUPS),
     (Table::ChunkCtx, CHUNK_CTX_TABLE_LOOKUPS),
 ];
 
@@ -87,6 +98,21 @@ pub const EXP_TABLE_LOOKUPS: usize = 1;
 /// Sig Table lookups done in EVMCircuit
 pub const SIG_TABLE_LOOKUPS: usize = 1;
 
+/// Sha256 Table lookups done in EVMCircuit
+pub const SHA256_TABLE_LOOKUPS: usize = 1;
+
+/// Ripemd160 Table lookups done in EVMCircuit
+pub const RIPEMD160_TABLE_LOOKUPS: usize = 1;
+
+/// Blake2f Table lookups done in EVMCircuit
+pub const BLAKE2F_TABLE_LOOKUPS: usize = 1;
+
+/// ModExp Table lookups done in EVMCircuit
+pub const MODEXP_TABLE_LOOKUPS: usize = 1;
+
+/// Ecc Table lookups done in EVMCircuit
+pub const ECC_TABLE_LOOKUPS: usize = 1;
+
 /// chunk_ctx Table lookups done in EVMCircuit
 pub const CHUNK_CTX_TABLE_LOOKUPS: usize = 1;
 
@@ -136,6 +162,8 @@ pub(crate) const N_BYTES_BASE_FEE: usize = N_BYTES_WORD;
 pub(crate) const N_BYTES_CHAIN_ID: usize = N_BYTES_U64;
 pub(crate) const N_BYTES_PREV_HASH: usize = 256 * N_BYTES_WORD;
 pub(crate) const N_BYTES_WITHDRAWAL_ROOT: usize = N_BYTES_WORD;
+pub(crate) const N_BYTES_EXCESS_BLOB_GAS: usize = N_BYTES_U64;
+pub(crate) const N_BYTES_BLOB_GAS_USED: usize = N_BYTES_U64;
 
 pub(crate) const N_BYTES_BLOCK: usize = N_BYTES_COINBASE
     + N_BYTES_GAS_LIMIT
@@ -145,7 +173,9 @@ pub(crate) const N_BYTES_BLOCK: usize = N_BYTES_COINBASE
     + N_BYTES_BASE_FEE
     + N_BYTES_CHAIN_ID
     + N_BYTES_PREV_HASH
-    + N_BYTES_WITHDRAWAL_ROOT;
+    + N_BYTES_WITHDRAWAL_ROOT
+    + N_BYTES_EXCESS_BLOB_GAS
+    + N_BYTES_BLOB_GAS_USED;
 
 pub(crate) const N_BYTES_EXTRA_VALUE: usize = N_BYTES_WORD // block hash
     + N_BYTES_WORD // state root
@@ -162,6 +192,8 @@ pub(crate) const N_BYTES_TX_VALUE: usize = N_BYTES_WORD;
 pub(crate) const N_BYTES_TX_CALLDATA_LEN: usize = N_BYTES_CALLDATASIZE;
 pub(crate) const N_BYTES_TX_CALLDATA_GASCOST: usize = N_BYTES_U64;
 pub(crate) const N_BYTES_TX_TXSIGNHASH: usize = N_BYTES_WORD;
+pub(crate) const N_BYTES_TX_BLOB_HASHES_LEN: usize = N_BYTES_U64;
+pub(crate) const N_BYTES_TX_BLOB_HASHES: usize = N_BYTES_WORD * MAX_BLOB_HASHES_PER_TX;
 pub(crate) const N_BYTES_TX: usize = N_BYTES_TX_NONCE
     + N_BYTES_TX_GAS_LIMIT
     + N_BYTES_TX_GASPRICE
@@ -171,7 +203,9 @@ pub(crate) const N_BYTES_TX: usize = N_BYTES_TX_NONCE
     + N_BYTES_TX_VALUE
     + N_BYTES_TX_CALLDATA_LEN
     + N_BYTES_TX_CALLDATA_GASCOST
-    + N_BYTES_TX_TXSIGNHASH;
+    + N_BYTES_TX_TXSIGNHASH
+    + N_BYTES_TX_BLOB_HASHES_LEN
+    + N_BYTES_TX_BLOB_HASHES;
 
 pub(crate) const N_BYTES_WITHDRAWAL: usize = N_BYTES_U64 //id
     + N_BYTES_U64 // validator id
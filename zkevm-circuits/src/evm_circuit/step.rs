@@ -20,11 +20,14 @@ use crate::{
 };
 use bus_mapping::{
     circuit_input_builder::ExecState,
-    error::{DepthError, ExecError, InsufficientBalanceError, NonceUintOverflowError, OogError},
+    error::{
+        ContractAddressCollisionError, DepthError, ExecError, InsufficientBalanceError,
+        NonceUintOverflowError, OogError,
+    },
     evm::OpcodeId,
     precompile::PrecompileCalls,
 };
-use eth_types::{evm_unimplemented, Field, ToWord};
+use eth_types::{evm_types::Hardfork, Field, ToWord};
 use halo2_proofs::{
     circuit::Value,
     plonk::{Advice, Column, ConstraintSystem, Error, Expression},
@@ -107,6 +110,8 @@ pub enum ExecutionState {
     /// TIMESTAMP, NUMBER, GASLIMIT, COINBASE, DIFFICULTY, BASEFEE
     BLOCKCTX,
     CHAINID,
+    BLOBHASH,
+    BLOBBASEFEE,
     SELFBALANCE,
     POP,
     /// MLOAD, MSTORE, MSTORE8
@@ -119,6 +124,7 @@ pub enum ExecutionState {
     MSIZE,
     GAS,
     JUMPDEST,
+    MCOPY,
     TLOAD,
     TSTORE,
     /// PUSH0, PUSH1, PUSH2, ..., PUSH32
@@ -141,7 +147,6 @@ pub enum ExecutionState {
     ErrorWriteProtection,
     ErrorDepth,
     ErrorInsufficientBalance,
-    ErrorContractAddressCollision,
     ErrorInvalidCreationCode,
     ErrorCodeStore, // combine ErrorMaxCodeSizeExceeded and ErrorOutOfGasCodeStore
     ErrorInvalidJump,
@@ -156,7 +161,6 @@ pub enum ExecutionState {
     ErrorOutOfGasLOG,
     ErrorOutOfGasEXP,
     ErrorOutOfGasSHA3,
-    ErrorOutOfGasEXTCODECOPY,
     ErrorOutOfGasCall,
     ErrorOutOfGasPrecompile,
     ErrorOutOfGasSloadSstore,
@@ -192,7 +196,7 @@ impl From<&ExecError> for ExecutionState {
             ExecError::StackOverflow | ExecError::StackUnderflow => ExecutionState::ErrorStack,
             ExecError::WriteProtection => ExecutionState::ErrorWriteProtection,
             ExecError::Depth(depth_error) => match depth_error {
-                DepthError::Call => ExecutionState::CALL_OP,
+                DepthError::Call => ExecutionState::ErrorDepth,
                 DepthError::Create => ExecutionState::CREATE,
                 DepthError::Create2 => ExecutionState::CREATE2,
             },
@@ -207,7 +211,10 @@ impl From<&ExecError> for ExecutionState {
                 NonceUintOverflowError::Create => ExecutionState::CREATE,
                 NonceUintOverflowError::Create2 => ExecutionState::CREATE2,
             },
-            ExecError::ContractAddressCollision => ExecutionState::CREATE2,
+            ExecError::ContractAddressCollision(collision_err) => match collision_err {
+                ContractAddressCollisionError::Create => ExecutionState::CREATE,
+                ContractAddressCollisionError::Create2 => ExecutionState::CREATE2,
+            },
             ExecError::InvalidCreationCode => ExecutionState::ErrorInvalidCreationCode,
             ExecError::InvalidJump => ExecutionState::ErrorInvalidJump,
             ExecError::ReturnDataOutOfBounds => ExecutionState::ErrorReturnDataOutOfBound,
@@ -258,13 +265,6 @@ impl From<&ExecStep> for ExecutionState {
                     return ExecutionState::LOG;
                 }
 
-                macro_rules! dummy {
-                    ($name:expr) => {{
-                        evm_unimplemented!("{:?} is implemented with DummyGadget", $name);
-                        $name
-                    }};
-                }
-
                 match op {
                     OpcodeId::ADD | OpcodeId::SUB => ExecutionState::ADD_SUB,
                     OpcodeId::ADDMOD => ExecutionState::ADDMOD,
@@ -311,11 +311,14 @@ impl From<&ExecStep> for ExecutionState {
                     OpcodeId::SHL | OpcodeId::SHR => ExecutionState::SHL_SHR,
                     OpcodeId::SLOAD => ExecutionState::SLOAD,
                     OpcodeId::SSTORE => ExecutionState::SSTORE,
+                    OpcodeId::MCOPY => ExecutionState::MCOPY,
                     OpcodeId::TLOAD => ExecutionState::TLOAD,
                     OpcodeId::TSTORE => ExecutionState::TSTORE,
                     OpcodeId::CALLDATASIZE => ExecutionState::CALLDATASIZE,
                     OpcodeId::CALLDATACOPY => ExecutionState::CALLDATACOPY,
                     OpcodeId::CHAINID => ExecutionState::CHAINID,
+                    OpcodeId::BLOBHASH => ExecutionState::BLOBHASH,
+                    OpcodeId::BLOBBASEFEE => ExecutionState::BLOBBASEFEE,
                     OpcodeId::ISZERO => ExecutionState::ISZERO,
                     OpcodeId::CALL
                     | OpcodeId::CALLCODE
@@ -331,8 +334,7 @@ impl From<&ExecStep> for ExecutionState {
                     OpcodeId::RETURNDATACOPY => ExecutionState::RETURNDATACOPY,
                     OpcodeId::CREATE => ExecutionState::CREATE,
                     OpcodeId::CREATE2 => ExecutionState::CREATE2,
-                    // dummy ops
-                    OpcodeId::SELFDESTRUCT => dummy!(ExecutionState::SELFDESTRUCT),
+                    OpcodeId::SELFDESTRUCT => ExecutionState::SELFDESTRUCT,
                     _ => unimplemented!("unimplemented opcode {:?}", op),
                 }
             }
@@ -426,7 +428,6 @@ impl ExecutionState {
                 | Self::ErrorOutOfGasLOG
                 | Self::ErrorOutOfGasEXP
                 | Self::ErrorOutOfGasSHA3
-                | Self::ErrorOutOfGasEXTCODECOPY
                 | Self::ErrorOutOfGasCall
                 | Self::ErrorOutOfGasPrecompile
                 | Self::ErrorOutOfGasSloadSstore
@@ -440,8 +441,10 @@ impl ExecutionState {
             || self.halts_in_exception()
     }
 
-    /// Get the opocdes that are related to the execution state
-    pub fn responsible_opcodes(&self) -> Vec<ResponsibleOp> {
+    /// Get the opocdes that are related to the execution state in `hardfork`.
+    /// The opcodes not yet available in `hardfork` are executed as invalid
+    /// opcodes.
+    pub fn responsible_opcodes(&self, hardfork: Hardfork) -> Vec<ResponsibleOp> {
         if matches!(self, Self::ErrorStack) {
             return OpcodeId::valid_opcodes()
                 .into_iter()
@@ -452,6 +455,17 @@ impl ExecutionState {
                 })
                 .collect();
         }
+        if matches!(self, Self::ErrorInvalidOpcode) {
+            return OpcodeId::invalid_opcodes()
+                .into_iter()
+                .chain(
+                    OpcodeId::valid_opcodes()
+                        .into_iter()
+                        .filter(|op| !hardfork.is_opcode_enabled(*op)),
+                )
+                .map(Into::into)
+                .collect();
+        }
 
         match self {
             Self::STOP => vec![OpcodeId::STOP],
@@ -497,6 +511,8 @@ impl ExecutionState {
                 OpcodeId::BASEFEE,
             ],
             Self::CHAINID => vec![OpcodeId::CHAINID],
+            Self::BLOBHASH => vec![OpcodeId::BLOBHASH],
+            Self::BLOBBASEFEE => vec![OpcodeId::BLOBBASEFEE],
             Self::SELFBALANCE => vec![OpcodeId::SELFBALANCE],
             Self::POP => vec![OpcodeId::POP],
             Self::MEMORY => {
@@ -510,6 +526,7 @@ impl ExecutionState {
             Self::MSIZE => vec![OpcodeId::MSIZE],
             Self::GAS => vec![OpcodeId::GAS],
             Self::JUMPDEST => vec![OpcodeId::JUMPDEST],
+            Self::MCOPY => vec![OpcodeId::MCOPY],
             Self::TLOAD => vec![OpcodeId::TLOAD],
             Self::TSTORE => vec![OpcodeId::TSTORE],
             Self::PUSH => vec![
@@ -600,10 +617,10 @@ impl ExecutionState {
             Self::RETURN_REVERT => vec![OpcodeId::RETURN, OpcodeId::REVERT],
             Self::CREATE2 => vec![OpcodeId::CREATE2],
             Self::SELFDESTRUCT => vec![OpcodeId::SELFDESTRUCT],
-            Self::ErrorInvalidOpcode => OpcodeId::invalid_opcodes(),
             _ => vec![],
         }
         .into_iter()
+        .filter(|op| hardfork.is_opcode_enabled(*op))
         .map(Into::into)
         .collect()
     }
@@ -6,7 +6,7 @@ use crate::{
     util::word::WordLoHi,
 };
 use bus_mapping::{evm::OpcodeId, precompile::PrecompileCalls};
-use eth_types::Field;
+use eth_types::{evm_types::Hardfork, Field};
 use gadgets::util::Expr;
 use halo2_proofs::plonk::Expression;
 use strum::IntoEnumIterator;
@@ -53,8 +53,8 @@ pub enum FixedTableTag {
 impl_expr!(FixedTableTag);
 
 impl FixedTableTag {
-    /// build up the fixed table row values
-    pub(crate) fn build<F: Field>(&self) -> Box<dyn Iterator<Item = [F; 4]>> {
+    /// build up the fixed table row values in `hardfork`
+    pub(crate) fn build<F: Field>(&self, hardfork: Hardfork) -> Box<dyn Iterator<Item = [F; 4]>> {
         let tag = F::from(*self as u64);
         match self {
             Self::Zero => Box::new((0..1).map(move |_| [tag, F::ZERO, F::ZERO, F::ZERO])),
@@ -101,8 +101,10 @@ impl FixedTableTag {
             })),
             Self::ResponsibleOpcode => {
                 Box::new(ExecutionState::iter().flat_map(move |execution_state| {
-                    execution_state.responsible_opcodes().into_iter().map(
-                        move |responsible_opcode| {
+                    execution_state
+                        .responsible_opcodes(hardfork)
+                        .into_iter()
+                        .map(move |responsible_opcode| {
                             let (op, aux) = match responsible_opcode {
                                 ResponsibleOp::Op(op) => (op, F::ZERO),
                                 ResponsibleOp::InvalidStackPtr(op, stack_ptr) => {
@@ -115,8 +117,7 @@ impl FixedTableTag {
                                 F::from(op.as_u64()),
                                 aux,
                             ]
-                        },
-                    )
+                        })
                 }))
             }
             Self::Pow2 => Box::new((0..256).map(move |value| {
@@ -193,6 +194,16 @@ pub enum Table {
     Exp,
     /// Lookup for sig table
     Sig,
+    /// Lookup for sha256 table
+    Sha256,
+    /// Lookup for ripemd160 table
+    Ripemd160,
+    /// Lookup for blake2f table
+    Blake2f,
+    /// Lookup for modexp table
+    ModExp,
+    /// Lookup for ecc table
+    Ecc,
     /// Lookup for chunk context
     ChunkCtx,
 }
@@ -371,6 +382,71 @@ pub(crate) enum Lookup<F> {
         recovered_addr: Expression<F>,
         is_valid: Expression<F>,
     },
+    /// Lookup to sha256 table.
+    Sha256Table {
+        /// Accumulator to the input.
+        input_rlc: Expression<F>,
+        /// Length of input that is being hashed.
+        input_len: Expression<F>,
+        /// Accumulator to the digest.
+        output_rlc: Expression<F>,
+    },
+    /// Lookup to ripemd160 table.
+    Ripemd160Table {
+        /// Accumulator to the input.
+        input_rlc: Expression<F>,
+        /// Length of input that is being hashed.
+        input_len: Expression<F>,
+        /// Accumulator to the digest.
+        output_rlc: Expression<F>,
+    },
+    /// Lookup to blake2f table.
+    Blake2fTable {
+        /// Number of rounds of the compression.
+        rounds: Expression<F>,
+        /// Accumulator to the 213-byte input.
+        input_rlc: Expression<F>,
+        /// Accumulator to the 64-byte output.
+        output_rlc: Expression<F>,
+    },
+    /// Lookup to modexp table.
+    ModExpTable {
+        /// Accumulator to the input, at most 480 bytes.
+        input_rlc: Expression<F>,
+        /// Number of input bytes.
+        input_len: Expression<F>,
+        /// Length of the base.
+        base_len: Expression<F>,
+        /// Length of the exponent.
+        exp_len: Expression<F>,
+        /// Length of the modulus.
+        mod_len: Expression<F>,
+        /// Index of the most significant bit of the first 32 bytes of the exponent.
+        exp_msb: Expression<F>,
+        /// Accumulator to the output.
+        output_rlc: Expression<F>,
+    },
+    /// Lookup to ecc table.
+    EccTable {
+        /// Address of the precompile, i.e. the type of the operation.
+        op_type: Expression<F>,
+        /// Whether the inputs are valid.
+        is_valid: Expression<F>,
+        /// First argument, the RLC of Px for ecAdd and ecMul.
+        arg1_rlc: Expression<F>,
+        /// Second argument, the RLC of Py for ecAdd and ecMul.
+        arg2_rlc: Expression<F>,
+        /// Third argument, the RLC of Qx for ecAdd, the reduced scalar for ecMul.
+        arg3_rlc: Expression<F>,
+        /// Fourth argument, the RLC of Qy for ecAdd.
+        arg4_rlc: Expression<F>,
+        /// Accumulator to the padded input of ecPairing.
+        input_rlc: Expression<F>,
+        /// First output, the RLC of Rx for ecAdd and ecMul, the success for ecPairing.
+        output1_rlc: Expression<F>,
+        /// Second output, the RLC of Ry for ecAdd and ecMul.
+        output2_rlc: Expression<F>,
+    },
     /// Lookup to block table, which contains constants of this block.
     ChunkCtx {
         /// Tag to specify which field to read.
@@ -400,6 +476,11 @@ impl<F: Field> Lookup<F> {
             Self::KeccakTable { .. } => Table::Keccak,
             Self::ExpTable { .. } => Table::Exp,
             Self::SigTable { .. } => Table::Sig,
+            Self::Sha256Table { .. } => Table::Sha256,
+            Self::Ripemd160Table { .. } => Table::Ripemd160,
+            Self::Blake2fTable { .. } => Table::Blake2f,
+            Self::ModExpTable { .. } => Table::ModExp,
+            Self::EccTable { .. } => Table::Ecc,
             Self::Conditional(_, lookup) => lookup.table(),
         }
     }
@@ -537,6 +618,70 @@ impl<F: Field> Lookup<F> {
                 recovered_addr.clone(),
                 is_valid.clone(),
             ],
+            Self::Sha256Table {
+                input_rlc,
+                input_len,
+                output_rlc,
+            }
+            | Self::Ripemd160Table {
+                input_rlc,
+                input_len,
+                output_rlc,
+            } => vec![
+                1.expr(), // is_enabled
+                input_rlc.clone(),
+                input_len.clone(),
+                output_rlc.clone(),
+            ],
+            Self::Blake2fTable {
+                rounds,
+                input_rlc,
+                output_rlc,
+            } => vec![
+                1.expr(), // is_enabled
+                rounds.clone(),
+                input_rlc.clone(),
+                output_rlc.clone(),
+            ],
+            Self::ModExpTable {
+                input_rlc,
+                input_len,
+                base_len,
+                exp_len,
+                mod_len,
+                exp_msb,
+                output_rlc,
+            } => vec![
+                1.expr(), // is_enabled
+                input_rlc.clone(),
+                input_len.clone(),
+                base_len.clone(),
+                exp_len.clone(),
+                mod_len.clone(),
+                exp_msb.clone(),
+                output_rlc.clone(),
+            ],
+            Self::EccTable {
+                op_type,
+                is_valid,
+                arg1_rlc,
+                arg2_rlc,
+                arg3_rlc,
+                arg4_rlc,
+                input_rlc,
+                output1_rlc,
+                output2_rlc,
+            } => vec![
+                op_type.clone(),
+                is_valid.clone(),
+                arg1_rlc.clone(),
+                arg2_rlc.clone(),
+                arg3_rlc.clone(),
+                arg4_rlc.clone(),
+                input_rlc.clone(),
+                output1_rlc.clone(),
+                output2_rlc.clone(),
+            ],
             Self::ChunkCtx { field_tag, value } => vec![field_tag.clone(), value.clone()],
             Self::Conditional(condition, lookup) => lookup
                 .input_exprs()
@@ -1068,6 +1068,54 @@ impl<'a, F: Field> EVMConstraintBuilder<'a, F> {
         );
     }
 
+    // Tx created account
+
+    pub(crate) fn tx_created_account_write(
+        &mut self,
+        tx_id: Expression<F>,
+        account_address: WordLoHi<Expression<F>>,
+        value: Expression<F>,
+        value_prev: Expression<F>,
+        reversion_info: Option<&mut ReversionInfo<F>>,
+    ) {
+        self.reversible_write(
+            "TxCreatedAccount write",
+            Target::TxCreatedAccount,
+            RwValues::new(
+                tx_id,
+                account_address.compress(),
+                0.expr(),
+                WordLoHi::zero(),
+                WordLoHi::from_lo_unchecked(value),
+                WordLoHi::from_lo_unchecked(value_prev),
+                WordLoHi::zero(),
+            ),
+            reversion_info,
+        );
+    }
+
+    pub(crate) fn tx_created_account_read(
+        &mut self,
+        tx_id: Expression<F>,
+        account_address: WordLoHi<Expression<F>>,
+        value: Expression<F>,
+    ) {
+        self.rw_lookup(
+            "TxCreatedAccount read",
+            false.expr(),
+            Target::TxCreatedAccount,
+            RwValues::new(
+                tx_id,
+                account_address.compress(),
+                0.expr(),
+                WordLoHi::zero(),
+                WordLoHi::from_lo_unchecked(value.clone()),
+                WordLoHi::from_lo_unchecked(value),
+                WordLoHi::zero(),
+            ),
+        );
+    }
+
     // Tx Refund
 
     pub(crate) fn tx_refund_read(&mut self, tx_id: Expression<F>, value: WordLoHi<Expression<F>>) {
@@ -1754,6 +1802,113 @@ impl<'a, F: Field> EVMConstraintBuilder<'a, F> {
         );
     }
 
+    /// Sha256 Table
+    pub(crate) fn sha256_table_lookup(
+        &mut self,
+        input_rlc: Expression<F>,
+        input_len: Expression<F>,
+        output_rlc: Expression<F>,
+    ) {
+        self.add_lookup(
+            "sha256 lookup",
+            Lookup::Sha256Table {
+                input_rlc,
+                input_len,
+                output_rlc,
+            },
+        );
+    }
+
+    /// Ripemd160 Table
+    pub(crate) fn ripemd160_table_lookup(
+        &mut self,
+        input_rlc: Expression<F>,
+        input_len: Expression<F>,
+        output_rlc: Expression<F>,
+    ) {
+        self.add_lookup(
+            "ripemd160 lookup",
+            Lookup::Ripemd160Table {
+                input_rlc,
+                input_len,
+                output_rlc,
+            },
+        );
+    }
+
+    /// Blake2f Table
+    pub(crate) fn blake2f_table_lookup(
+        &mut self,
+        rounds: Expression<F>,
+        input_rlc: Expression<F>,
+        output_rlc: Expression<F>,
+    ) {
+        self.add_lookup(
+            "blake2f lookup",
+            Lookup::Blake2fTable {
+                rounds,
+                input_rlc,
+                output_rlc,
+            },
+        );
+    }
+
+    /// ModExp Table
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn modexp_table_lookup(
+        &mut self,
+        input_rlc: Expression<F>,
+        input_len: Expression<F>,
+        base_len: Expression<F>,
+        exp_len: Expression<F>,
+        mod_len: Expression<F>,
+        exp_msb: Expression<F>,
+        output_rlc: Expression<F>,
+    ) {
+        self.add_lookup(
+            "modexp lookup",
+            Lookup::ModExpTable {
+                input_rlc,
+                input_len,
+                base_len,
+                exp_len,
+                mod_len,
+                exp_msb,
+                output_rlc,
+            },
+        );
+    }
+
+    /// Ecc Table
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn ecc_table_lookup(
+        &mut self,
+        op_type: Expression<F>,
+        is_valid: Expression<F>,
+        arg1_rlc: Expression<F>,
+        arg2_rlc: Expression<F>,
+        arg3_rlc: Expression<F>,
+        arg4_rlc: Expression<F>,
+        input_rlc: Expression<F>,
+        output1_rlc: Expression<F>,
+        output2_rlc: Expression<F>,
+    ) {
+        self.add_lookup(
+            "ecc lookup",
+            Lookup::EccTable {
+                op_type,
+                is_valid,
+                arg1_rlc,
+                arg2_rlc,
+                arg3_rlc,
+                arg4_rlc,
+                input_rlc,
+                output1_rlc,
+                output2_rlc,
+            },
+        );
+    }
+
     // Validation
     pub(crate) fn validate_degree(&self, degree: usize, name: &'static str) {
         // We need to subtract IMPLICIT_DEGREE from MAX_DEGREE because all expressions
@@ -1800,15 +1955,16 @@ impl<'a, F: Field> EVMConstraintBuilder<'a, F> {
             sum::expr(&conditions),
         );
 
-        // TODO: constraining the same cells repeatedly requires a height-resetting mechanism
-        // on the cell manager. In this case, since only identity precompile is added
-        // this height management maneuver is temporarily left out.
+        // Each constraint describes the layout of a different next step, so the cells it queries
+        // in the next step must start from the same position of the cell manager.
+        let next_cell_manager = self.next.cell_manager.clone();
         for ((&next_state, condition), constraint) in next_states
             .iter()
             .zip(conditions.into_iter())
             .zip(constraints.into_iter())
         {
             // constrain the next step.
+            self.next.cell_manager = next_cell_manager.clone();
             self.constrain_next_step(next_state, Some(condition), constraint);
         }
     }
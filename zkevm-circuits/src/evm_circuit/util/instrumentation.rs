@@ -107,6 +107,21 @@ impl Instrument {
                     CellType::Lookup(Table::Sig) => {
                         report.sig_table = data_entry;
                     }
+                    CellType::Lookup(Table::Sha256) => {
+                        report.sha256_table = data_entry;
+                    }
+                    CellType::Lookup(Table::Ripemd160) => {
+                        report.ripemd160_table = data_entry;
+                    }
+                    CellType::Lookup(Table::Blake2f) => {
+                        report.blake2f_table = data_entry;
+                    }
+                    CellType::Lookup(Table::ModExp) => {
+                        report.modexp_table = data_entry;
+                    }
+                    CellType::Lookup(Table::Ecc) => {
+                        report.ecc_table = data_entry;
+                    }
                     CellType::Lookup(Table::ChunkCtx) => {
                         report.chunk_ctx_table = data_entry;
                     }
@@ -138,6 +153,11 @@ pub struct ExecStateReport {
     pub keccak_table: StateReportRow,
     pub exp_table: StateReportRow,
     pub sig_table: StateReportRow,
+    pub sha256_table: StateReportRow,
+    pub ripemd160_table: StateReportRow,
+    pub blake2f_table: StateReportRow,
+    pub modexp_table: StateReportRow,
+    pub ecc_table: StateReportRow,
     pub chunk_ctx_table: StateReportRow,
 }
 
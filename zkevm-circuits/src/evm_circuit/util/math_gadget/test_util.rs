@@ -17,7 +17,7 @@ use crate::{
     table::LookupTable,
     util::{cell_manager::CellType, Challenges},
 };
-use eth_types::{Field, Word, U256};
+use eth_types::{evm_types::Hardfork, Field, Word, U256};
 pub(crate) use halo2_proofs::circuit::{Layouter, Value};
 use halo2_proofs::{
     circuit::SimpleFloorPlanner,
@@ -224,7 +224,7 @@ impl<F: Field, G: MathGadgetContainer<F>> Circuit<F> for UnitTestMathGadgetBaseC
                                         | FixedTableTag::Range1024
                                 )
                             })
-                            .flat_map(|tag| tag.build()),
+                            .flat_map(|tag| tag.build(Hardfork::default())),
                     )
                     .enumerate()
                 {
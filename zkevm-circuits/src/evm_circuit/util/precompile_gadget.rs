@@ -1,6 +1,6 @@
 use bus_mapping::precompile::PrecompileCalls;
 use eth_types::Field;
-use gadgets::util::{not, Expr};
+use gadgets::util::{not, select, Expr};
 use halo2_proofs::plonk::Expression;
 
 use crate::evm_circuit::step::{ExecutionState, ExecutionState::ErrorOutOfGasPrecompile};
@@ -20,7 +20,7 @@ impl<F: Field> PrecompileGadget<F> {
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn construct(
         cb: &mut EVMConstraintBuilder<F>,
-        _is_success: Expression<F>,
+        is_success: Expression<F>,
         callee_address: Expression<F>,
         _caller_id: Expression<F>,
         _cd_offset: Expression<F>,
@@ -28,18 +28,29 @@ impl<F: Field> PrecompileGadget<F> {
         _rd_offset: Expression<F>,
         _rd_length: Expression<F>,
         precompile_return_length: Expression<F>,
+        // number of input bytes to precompile call.
+        input_len: Expression<F>,
         // input bytes to precompile call.
-        _input_bytes_rlc: Expression<F>,
+        input_bytes_rlc: Expression<F>,
         // output result from precompile call.
-        _output_bytes_rlc: Expression<F>,
+        output_bytes_rlc: Expression<F>,
         // returned bytes back to caller.
         _return_bytes_rlc: Expression<F>,
+        // number of rounds of the BLAKE2F call, i.e. the first 4 input bytes.
+        blake2f_rounds: Expression<F>,
     ) -> Self {
         let address = BinaryNumberGadget::construct(cb, callee_address.expr());
 
         let conditions = vec![
             address.value_equals(PrecompileCalls::Identity),
             address.value_equals(PrecompileCalls::Ecrecover),
+            address.value_equals(PrecompileCalls::Sha256),
+            address.value_equals(PrecompileCalls::Ripemd160),
+            address.value_equals(PrecompileCalls::Blake2F),
+            address.value_equals(PrecompileCalls::Modexp),
+            address.value_equals(PrecompileCalls::Bn128Add),
+            address.value_equals(PrecompileCalls::Bn128Mul),
+            address.value_equals(PrecompileCalls::Bn128Pairing),
             // match more precompiles
         ]
         .into_iter()
@@ -51,6 +62,13 @@ impl<F: Field> PrecompileGadget<F> {
         let next_states = vec![
             ExecutionState::PrecompileIdentity, // add more precompile execution states
             ExecutionState::PrecompileEcrecover,
+            ExecutionState::PrecompileSha256,
+            ExecutionState::PrecompileRipemd160,
+            ExecutionState::PrecompileBlake2f,
+            ExecutionState::PrecompileBigModExp,
+            ExecutionState::PrecompileBn256Add,
+            ExecutionState::PrecompileBn256ScalarMul,
+            ExecutionState::PrecompileBn256Pairing,
         ];
 
         let constraints: Vec<BoxedClosure<F>> = vec![
@@ -70,6 +88,207 @@ impl<F: Field> PrecompileGadget<F> {
                     128.expr(),
                 );
             }),
+            Box::new(|cb| {
+                // Sha256
+                cb.require_equal(
+                    "SHA256: the whole input is hashed",
+                    input_len.clone(),
+                    cd_length.clone(),
+                );
+                cb.require_equal(
+                    "SHA256: precompile return length is 32 bytes",
+                    precompile_return_length.clone(),
+                    32.expr(),
+                );
+                cb.sha256_table_lookup(
+                    input_bytes_rlc.clone(),
+                    input_len.clone(),
+                    output_bytes_rlc.clone(),
+                );
+            }),
+            Box::new(|cb| {
+                // Ripemd160
+                cb.require_equal(
+                    "RIPEMD160: the whole input is hashed",
+                    input_len.clone(),
+                    cd_length.clone(),
+                );
+                cb.require_equal(
+                    "RIPEMD160: precompile return length is 32 bytes",
+                    precompile_return_length.clone(),
+                    32.expr(),
+                );
+                // The 20-byte digest is left-padded with zeros, which doesn't change its RLC.
+                cb.ripemd160_table_lookup(
+                    input_bytes_rlc.clone(),
+                    input_len.clone(),
+                    output_bytes_rlc.clone(),
+                );
+            }),
+            Box::new(|cb| {
+                // Blake2F
+                // The number of rounds is the first cell of the next step, see `Blake2fGadget`.
+                let rounds_next = cb.query_cell();
+                cb.require_equal(
+                    "BLAKE2F: precompile return length is 64 bytes on success",
+                    precompile_return_length.clone(),
+                    select::expr(is_success.clone(), 64.expr(), 0.expr()),
+                );
+                cb.condition(is_success.clone(), |cb| {
+                    cb.require_equal(
+                        "BLAKE2F: input length is 213 bytes",
+                        input_len.clone(),
+                        213.expr(),
+                    );
+                    cb.require_equal(
+                        "BLAKE2F: number of rounds is passed to the next step",
+                        blake2f_rounds.clone(),
+                        rounds_next.expr(),
+                    );
+                    cb.blake2f_table_lookup(
+                        blake2f_rounds.clone(),
+                        input_bytes_rlc.clone(),
+                        output_bytes_rlc.clone(),
+                    );
+                });
+            }),
+            Box::new(|cb| {
+                // ModExp
+                // The lengths, the MSB of the exponent and the output are the first cells of the
+                // next step, see `ModExpGadget`.
+                let [input_len_next, base_len, exp_len, mod_len, exp_msb] =
+                    [(); 5].map(|_| cb.query_cell());
+                let output_rlc = cb.query_cell_phase2();
+                cb.require_equal(
+                    "MODEXP: input length is passed to the next step",
+                    input_len.clone(),
+                    input_len_next.expr(),
+                );
+                cb.require_equal(
+                    "MODEXP: precompile return length is the modulus length on success",
+                    precompile_return_length.clone(),
+                    select::expr(is_success.clone(), mod_len.expr(), 0.expr()),
+                );
+                cb.condition(is_success.clone(), |cb| {
+                    cb.require_equal(
+                        "MODEXP: output bytes are the result",
+                        output_bytes_rlc.clone(),
+                        output_rlc.expr(),
+                    );
+                });
+                // The lookup is needed even on failure, as the gas cost depends on the lengths
+                // and the exponent.
+                cb.modexp_table_lookup(
+                    input_bytes_rlc.clone(),
+                    input_len.clone(),
+                    base_len.expr(),
+                    exp_len.expr(),
+                    mod_len.expr(),
+                    exp_msb.expr(),
+                    output_rlc.expr(),
+                );
+            }),
+            Box::new(|cb| {
+                // EcAdd
+                // The input and output RLCs are the first cells of the next step, see
+                // `EcAddGadget`.
+                let [input_rlc, output_rlc] = [(); 2].map(|_| cb.query_cell_phase2());
+                cb.require_equal(
+                    "ECADD: input length is 128 bytes",
+                    cd_length.clone(),
+                    128.expr(),
+                );
+                cb.require_equal(
+                    "ECADD: the whole input is used",
+                    input_len.clone(),
+                    cd_length.clone(),
+                );
+                cb.require_equal(
+                    "ECADD: input bytes are passed to the next step",
+                    input_bytes_rlc.clone(),
+                    input_rlc.expr(),
+                );
+                cb.require_equal(
+                    "ECADD: precompile return length is 64 bytes on success",
+                    precompile_return_length.clone(),
+                    select::expr(is_success.clone(), 64.expr(), 0.expr()),
+                );
+                cb.condition(is_success.clone(), |cb| {
+                    cb.require_equal(
+                        "ECADD: output bytes are the result",
+                        output_bytes_rlc.clone(),
+                        output_rlc.expr(),
+                    );
+                });
+            }),
+            Box::new(|cb| {
+                // EcMul
+                // The input and output RLCs are the first cells of the next step, see
+                // `EcMulGadget`.
+                let [input_rlc, output_rlc] = [(); 2].map(|_| cb.query_cell_phase2());
+                cb.require_equal(
+                    "ECMUL: input length is 96 bytes",
+                    cd_length.clone(),
+                    96.expr(),
+                );
+                cb.require_equal(
+                    "ECMUL: the whole input is used",
+                    input_len.clone(),
+                    cd_length.clone(),
+                );
+                cb.require_equal(
+                    "ECMUL: input bytes are passed to the next step",
+                    input_bytes_rlc.clone(),
+                    input_rlc.expr(),
+                );
+                cb.require_equal(
+                    "ECMUL: precompile return length is 64 bytes on success",
+                    precompile_return_length.clone(),
+                    select::expr(is_success.clone(), 64.expr(), 0.expr()),
+                );
+                cb.condition(is_success.clone(), |cb| {
+                    cb.require_equal(
+                        "ECMUL: output bytes are the result",
+                        output_bytes_rlc.clone(),
+                        output_rlc.expr(),
+                    );
+                });
+            }),
+            Box::new(|cb| {
+                // EcPairing
+                // The input length, the output and the input RLC are the first cells of the next
+                // step, see `EcPairingGadget`.
+                let [input_len_next, output] = [(); 2].map(|_| cb.query_cell());
+                let input_rlc = cb.query_cell_phase2();
+                cb.require_equal(
+                    "ECPAIRING: the whole input is used",
+                    input_len.clone(),
+                    cd_length.clone(),
+                );
+                cb.require_equal(
+                    "ECPAIRING: input length is passed to the next step",
+                    input_len.clone(),
+                    input_len_next.expr(),
+                );
+                cb.require_equal(
+                    "ECPAIRING: input bytes are passed to the next step",
+                    input_bytes_rlc.clone(),
+                    input_rlc.expr(),
+                );
+                cb.require_equal(
+                    "ECPAIRING: precompile return length is 32 bytes on success",
+                    precompile_return_length.clone(),
+                    select::expr(is_success.clone(), 32.expr(), 0.expr()),
+                );
+                // The 32-byte output is 0 or 1, so its RLC is the output itself.
+                cb.condition(is_success.clone(), |cb| {
+                    cb.require_equal(
+                        "ECPAIRING: output bytes are the result",
+                        output_bytes_rlc.clone(),
+                        output.expr(),
+                    );
+                });
+            }),
         ];
 
         cb.constrain_mutually_exclusive_next_step(conditions, next_states, constraints);
@@ -13,11 +13,14 @@ use crate::{
         },
         witness::{Block, Transaction},
     },
-    table::{CallContextFieldTag, TxContextFieldTag, TxReceiptFieldTag},
+    table::{BlockContextFieldTag, CallContextFieldTag, TxContextFieldTag, TxReceiptFieldTag},
     util::word::{Word32Cell, WordLoHi, WordLoHiCell},
 };
 use bus_mapping::operation::Target;
-use eth_types::{evm_types::GasCost, Field};
+use eth_types::{
+    evm_types::{GasCost, GAS_PER_BLOB},
+    Field, Word,
+};
 use gadgets::util::{select, Expr, Scalar};
 use halo2_proofs::{
     circuit::Value,
@@ -205,12 +208,20 @@ pub(crate) struct TxDataGadget<F> {
     pub(crate) call_data_gas_cost: Cell<F>,
     pub(crate) gas_price: Word32Cell<F>,
     pub(crate) value: Word32Cell<F>,
+    pub(crate) blob_hashes_length: Cell<F>,
+    pub(crate) blob_base_fee: Word32Cell<F>,
 
     pub(crate) mul_gas_fee_by_gas: MulWordByU64Gadget<F>,
+    pub(crate) mul_blob_fee_by_blob_gas: MulWordByU64Gadget<F>,
+    // Total fee paid upfront: gas * gas_price + blob_gas * blob_base_fee
+    pub(crate) gas_fee: Word32Cell<F>,
+    pub(crate) gas_fee_plus_blob_fee: AddWordsGadget<F, 2, false>,
     pub(crate) call_data_word_length: ConstantDivisionGadget<F, N_BYTES_U64>,
 
     pub(crate) gas_mul_gas_price_plus_value: Option<AddWordsGadget<F, 2, false>>,
     pub(crate) cost_sum: Option<Word32Cell<F>>,
+    // Gas per word of the init code of a creation tx in the configured hardfork
+    init_code_word_gas: u64,
 }
 
 impl<F: Field> TxDataGadget<F> {
@@ -219,12 +230,13 @@ impl<F: Field> TxDataGadget<F> {
         tx_id: Expression<F>,
         calculate_total_cost: bool,
     ) -> Self {
-        let [nonce, gas, is_create, call_data_length, call_data_gas_cost] = [
+        let [nonce, gas, is_create, call_data_length, call_data_gas_cost, blob_hashes_length] = [
             TxContextFieldTag::Nonce,
             TxContextFieldTag::Gas,
             TxContextFieldTag::IsCreate,
             TxContextFieldTag::CallDataLength,
             TxContextFieldTag::CallDataGasCost,
+            TxContextFieldTag::BlobVersionedHashesLength,
         ]
         .map(|field_tag| cb.tx_context(tx_id.expr(), field_tag, None));
         let [gas_price, value] = [TxContextFieldTag::GasPrice, TxContextFieldTag::Value]
@@ -241,15 +253,34 @@ impl<F: Field> TxDataGadget<F> {
         // Calculate transaction gas fee
         let mul_gas_fee_by_gas = MulWordByU64Gadget::construct(cb, gas_price.clone(), gas.expr());
 
+        // Calculate EIP-4844 blob fee, which is zero for txs without blob hashes
+        let blob_base_fee = cb.query_word32();
+        cb.block_lookup(
+            BlockContextFieldTag::BlobBaseFee.expr(),
+            None,
+            blob_base_fee.to_word(),
+        );
+        let mul_blob_fee_by_blob_gas = MulWordByU64Gadget::construct(
+            cb,
+            blob_base_fee.clone(),
+            blob_hashes_length.expr() * GAS_PER_BLOB.expr(),
+        );
+        let gas_fee = cb.query_word32();
+        let gas_fee_plus_blob_fee = AddWordsGadget::construct(
+            cb,
+            [
+                mul_gas_fee_by_gas.product().clone(),
+                mul_blob_fee_by_blob_gas.product().clone(),
+            ],
+            gas_fee.clone(),
+        );
+
         let call_data_word_length = cb.div_by_const(call_data_length.expr() + 31.expr(), 32);
 
         let (cost_sum, gas_mul_gas_price_plus_value) = if calculate_total_cost {
             let cost_sum = cb.query_word32();
-            let gas_mul_gas_price_plus_value = AddWordsGadget::construct(
-                cb,
-                [mul_gas_fee_by_gas.product().clone(), value.clone()],
-                cost_sum.clone(),
-            );
+            let gas_mul_gas_price_plus_value =
+                AddWordsGadget::construct(cb, [gas_fee.clone(), value.clone()], cost_sum.clone());
             (Some(cost_sum), Some(gas_mul_gas_price_plus_value))
         } else {
             (None, None)
@@ -263,12 +294,18 @@ impl<F: Field> TxDataGadget<F> {
             call_data_gas_cost,
             gas_price,
             value,
+            blob_hashes_length,
+            blob_base_fee,
             mul_gas_fee_by_gas,
+            mul_blob_fee_by_blob_gas,
+            gas_fee,
+            gas_fee_plus_blob_fee,
             call_data_word_length,
             caller_address,
             callee_address,
             gas_mul_gas_price_plus_value,
             cost_sum,
+            init_code_word_gas: cb.feature_config.hardfork.init_code_word_gas(),
         }
     }
 
@@ -276,8 +313,7 @@ impl<F: Field> TxDataGadget<F> {
         // Calculate gas cost of init code for EIP-3860.
         let init_code_gas_cost = select::expr(
             self.is_create.expr(),
-            self.call_data_word_length.quotient().expr()
-                * eth_types::evm_types::INIT_CODE_WORD_GAS.expr(),
+            self.call_data_word_length.quotient().expr() * self.init_code_word_gas.expr(),
             0.expr(),
         );
 
@@ -301,9 +337,13 @@ impl<F: Field> TxDataGadget<F> {
         &self,
         region: &mut CachedRegion<'_, '_, F>,
         offset: usize,
+        block: &Block<F>,
         tx: &Transaction,
     ) -> Result<(), Error> {
-        let gas_fee = tx.gas_price * tx.gas();
+        let exec_gas_fee = tx.gas_price * tx.gas();
+        let blob_base_fee = block.context.blob_base_fee;
+        let blob_fee = Word::from(tx.blob_gas()) * blob_base_fee;
+        let gas_fee = exec_gas_fee + blob_fee;
 
         self.nonce
             .assign(region, offset, Value::known(tx.nonce.as_u64().scalar()))?;
@@ -329,7 +369,24 @@ impl<F: Field> TxDataGadget<F> {
             .assign_h160(region, offset, tx.to_or_contract_addr())?;
         self.caller_address.assign_h160(region, offset, tx.from)?;
         self.mul_gas_fee_by_gas
-            .assign(region, offset, tx.gas_price, tx.gas(), gas_fee)?;
+            .assign(region, offset, tx.gas_price, tx.gas(), exec_gas_fee)?;
+        self.blob_hashes_length.assign(
+            region,
+            offset,
+            Value::known(F::from(tx.blob_versioned_hashes.len() as u64)),
+        )?;
+        self.blob_base_fee
+            .assign_u256(region, offset, blob_base_fee)?;
+        self.mul_blob_fee_by_blob_gas.assign(
+            region,
+            offset,
+            blob_base_fee,
+            tx.blob_gas(),
+            blob_fee,
+        )?;
+        self.gas_fee.assign_u256(region, offset, gas_fee)?;
+        self.gas_fee_plus_blob_fee
+            .assign(region, offset, [exec_gas_fee, blob_fee], gas_fee)?;
         let sum = gas_fee + tx.value;
 
         if self.cost_sum.is_some() && self.gas_mul_gas_price_plus_value.is_some() {
@@ -1,7 +1,11 @@
 //! The instance definition.
 
 use bus_mapping::circuit_input_builder::Withdrawal;
-use eth_types::{geth_types::BlockConstants, BigEndianHash, Field, Keccak};
+use eth_types::{
+    evm_types::{gas_utils::blob_base_fee, MAX_BLOB_HASHES_PER_TX},
+    geth_types::BlockConstants,
+    BigEndianHash, Field, Keccak,
+};
 use std::{iter, ops::Deref};
 
 use eth_types::{geth_types::Transaction, Address, ToBigEndian, Word, H256};
@@ -31,6 +35,12 @@ pub struct BlockValues {
     pub chain_id: u64,
     /// withdrawals_root
     pub withdrawals_root: Word,
+    /// excess_blob_gas
+    pub excess_blob_gas: u64,
+    /// blob_gas_used
+    pub blob_gas_used: u64,
+    /// blob_base_fee, derived from excess_blob_gas
+    pub blob_base_fee: Word,
     /// history_hashes
     pub history_hashes: Vec<H256>,
 }
@@ -58,6 +68,10 @@ pub struct TxValues {
     pub call_data_gas_cost: u64,
     /// tx_sign_hash
     pub tx_sign_hash: [u8; 32],
+    /// blob_versioned_hashes_len
+    pub blob_versioned_hashes_len: u64,
+    /// blob_versioned_hashes, padded with zero hashes
+    pub blob_versioned_hashes: [H256; MAX_BLOB_HASHES_PER_TX],
 }
 
 /// Extra values (not contained in block or tx tables)
@@ -131,6 +145,9 @@ impl PublicData {
             base_fee: self.block_constants.base_fee,
             chain_id: self.chain_id.as_u64(),
             withdrawals_root: self.withdrawals_root.as_fixed_bytes().into(),
+            excess_blob_gas: self.block_constants.excess_blob_gas.as_u64(),
+            blob_gas_used: self.block_constants.blob_gas_used.as_u64(),
+            blob_base_fee: blob_base_fee(self.block_constants.excess_blob_gas.as_u64()),
             history_hashes,
         }
     }
@@ -146,6 +163,13 @@ impl PublicData {
             let sign_data_res = tx.sign_data(chain_id);
             let msg_hash_le =
                 sign_data_res.map_or_else(|_| [0u8; 32], |sign_data| sign_data.msg_hash.to_bytes());
+            let mut blob_versioned_hashes = [H256::zero(); MAX_BLOB_HASHES_PER_TX];
+            for (dst, hash) in blob_versioned_hashes
+                .iter_mut()
+                .zip(tx.blob_versioned_hashes.iter())
+            {
+                *dst = *hash;
+            }
             tx_vals.push(TxValues {
                 nonce: tx.nonce.low_u64(),
                 gas_price: tx.gas_price,
@@ -163,11 +187,20 @@ impl PublicData {
                     }
                 }),
                 tx_sign_hash: msg_hash_le,
+                blob_versioned_hashes_len: tx.blob_versioned_hashes.len() as u64,
+                blob_versioned_hashes,
             });
         }
         tx_vals
     }
 
+    /// Returns the parent hash, which is the latest history hash.
+    pub fn parent_hash(&self) -> H256 {
+        self.history_hashes
+            .last()
+            .map_or_else(H256::zero, |hash| H256::from(hash.to_be_bytes()))
+    }
+
     /// Returns struct with the extra values
     pub fn get_extra_values(&self) -> ExtraValues {
         ExtraValues {
@@ -196,6 +229,8 @@ impl PublicData {
             .chain(block_values.base_fee.to_be_bytes()) // base_fee
             .chain(block_values.chain_id.to_be_bytes()) // chain_id
             .chain(block_values.withdrawals_root.to_be_bytes()) // withdrawals root
+            .chain(block_values.excess_blob_gas.to_be_bytes()) // excess blob gas
+            .chain(block_values.blob_gas_used.to_be_bytes()) // blob gas used
             .chain(
                 block_values
                     .history_hashes
@@ -217,7 +252,7 @@ impl PublicData {
                 .chain(index.to_be_bytes()) // index
                 .chain(value_bytes.to_vec()) // value
         };
-        let tx_bytes_fn = |tx_id: u64, index: u64, tx: &TxValues| {
+        let tx_bytes_fn = |tx_id: u64, tx: &TxValues| {
             vec![
                 tx.nonce.to_be_bytes().to_vec(),                     // nonce
                 tx.gas_limit.to_be_bytes().to_vec(),                 // gas_limit
@@ -229,9 +264,18 @@ impl PublicData {
                 tx.call_data_len.to_be_bytes().to_vec(),             // call_data_len
                 tx.call_data_gas_cost.to_be_bytes().to_vec(),        // call_data_gas_cost
                 tx.tx_sign_hash.iter().rev().copied().collect_vec(), // tx sign hash
+                tx.blob_versioned_hashes_len.to_be_bytes().to_vec(), // blob hashes len
             ]
-            .iter()
-            .flat_map(move |value_bytes| tx_field_byte_fn(tx_id, index, value_bytes))
+            .into_iter()
+            .map(|value_bytes| (0, value_bytes))
+            .chain(
+                // blob versioned hashes
+                tx.blob_versioned_hashes
+                    .iter()
+                    .enumerate()
+                    .map(|(index, hash)| (index as u64, hash.to_fixed_bytes().to_vec())),
+            )
+            .flat_map(move |(index, value_bytes)| tx_field_byte_fn(tx_id, index, &value_bytes))
             .collect_vec()
         };
 
@@ -245,7 +289,7 @@ impl PublicData {
             .enumerate()
             .flat_map(|(i, tx)| {
                 let i: u64 = i.try_into().unwrap();
-                tx_bytes_fn(i + 1, 0, tx)
+                tx_bytes_fn(i + 1, tx)
             });
 
         // first tx empty row happened here
@@ -314,6 +358,8 @@ pub fn public_data_convert<F: Field>(block: &Block<F>) -> PublicData {
             difficulty: block.context.difficulty,
             gas_limit: block.context.gas_limit.into(),
             base_fee: block.context.base_fee,
+            excess_blob_gas: block.context.excess_blob_gas.into(),
+            blob_gas_used: block.context.blob_gas_used.into(),
         },
         withdrawals_root: block.withdrawals_root(),
     }
@@ -13,6 +13,7 @@ mod test;
 #[cfg(feature = "test-circuits")]
 pub use dev::KeccakCircuit as TestKeccakCircuit;
 
+pub use bus_mapping::circuit_input_builder::KeccakCircuitParams;
 use std::marker::PhantomData;
 pub use KeccakCircuitConfig as KeccakConfig;
 
@@ -26,8 +27,8 @@ use crate::{
     evm_circuit::util::constraint_builder::{BaseConstraintBuilder, ConstrainBuilderCommon},
     keccak_circuit::keccak_packed_multi::{
         decode, get_num_bits_per_absorb_lookup, get_num_bits_per_base_chi_lookup,
-        get_num_bits_per_rho_pi_lookup, get_num_bits_per_theta_c_lookup, get_num_rows_per_round,
-        split, split_uniform, transform, transform_to, Part,
+        get_num_bits_per_rho_pi_lookup, get_num_bits_per_theta_c_lookup, split, split_uniform,
+        transform, transform_to, Part,
     },
     table::{KeccakTable, LookupTable},
     util::{
@@ -37,6 +38,7 @@ use crate::{
     },
     witness::{self, Chunk},
 };
+use bus_mapping::circuit_input_builder::FixedCParams;
 use eth_types::Field;
 use gadgets::util::{and, not, select, sum, Expr};
 use halo2_proofs::{
@@ -67,6 +69,7 @@ pub struct KeccakCircuitConfig<F> {
     normalize_6: [TableColumn; 2],
     chi_base_table: [TableColumn; 2],
     pack_table: [TableColumn; 2],
+    params: KeccakCircuitParams,
     _marker: PhantomData<F>,
 }
 
@@ -76,6 +79,8 @@ pub struct KeccakCircuitConfigArgs<F: Field> {
     pub keccak_table: KeccakTable,
     /// Challenges randomness
     pub challenges: Challenges<Expression<F>>,
+    /// Size of the lookup tables and rounds
+    pub params: KeccakCircuitParams,
 }
 
 impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
@@ -87,11 +92,13 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
         Self::ConfigArgs {
             keccak_table,
             challenges,
+            params,
         }: Self::ConfigArgs,
     ) -> Self {
+        let num_rows_per_round = params.rows_per_round;
         assert!(
-            get_num_rows_per_round() > NUM_BYTES_PER_WORD,
-            "KeccakCircuit requires KECCAK_ROWS>=9"
+            num_rows_per_round > NUM_BYTES_PER_WORD,
+            "KeccakCircuit requires rows_per_round>=9"
         );
         let q_enable = meta.fixed_column();
         let q_first = meta.fixed_column();
@@ -114,7 +121,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
         let pack_table = array_init::array_init(|_| meta.lookup_table_column());
 
         let mut cell_manager = CellManager::new(CMFixedHeightStrategy::new(
-            get_num_rows_per_round(),
+            num_rows_per_round,
             DEFAULT_CELL_TYPE,
         ));
 
@@ -139,7 +146,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
             for j in 0..5 {
                 let cell = cell_manager.query_cell(meta, DEFAULT_CELL_TYPE);
                 s[i][j] = cell.expr();
-                s_next[i][j] = cell.at_offset(meta, get_num_rows_per_round() as i32).expr();
+                s_next[i][j] = cell.at_offset(meta, num_rows_per_round as i32).expr();
             }
         }
         // Absorb data
@@ -150,7 +157,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
         let mut absorb_data_next = vec![0u64.expr(); NUM_WORDS_TO_ABSORB];
         let mut absorb_result_next = vec![0u64.expr(); NUM_WORDS_TO_ABSORB];
         for i in 0..NUM_WORDS_TO_ABSORB {
-            let rot = ((i + 1) * get_num_rows_per_round()) as i32;
+            let rot = ((i + 1) * num_rows_per_round) as i32;
             absorb_from_next[i] = absorb_from.at_offset(meta, rot).expr();
             absorb_data_next[i] = absorb_data.at_offset(meta, rot).expr();
             absorb_result_next[i] = absorb_result.at_offset(meta, rot).expr();
@@ -168,7 +175,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
         // rlc.
         cell_manager.get_strategy().start_region();
         let mut lookup_counter = 0;
-        let part_size = get_num_bits_per_absorb_lookup();
+        let part_size = get_num_bits_per_absorb_lookup(params);
         let input = absorb_from.expr() + absorb_data.expr();
         let absorb_fat = split::expr(meta, &mut cell_manager, &mut cb, input, 0, part_size);
         cell_manager.get_strategy().start_region();
@@ -242,7 +249,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
         // that allows us to also calculate the rotated value "for free".
         cell_manager.get_strategy().start_region();
         let mut lookup_counter = 0;
-        let part_size_c = get_num_bits_per_theta_c_lookup();
+        let part_size_c = get_num_bits_per_theta_c_lookup(params);
         let mut c_parts = Vec::new();
         for s in s.iter() {
             // Calculate c and split into parts
@@ -300,7 +307,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
         // `s[j][2 * i + 3 * j) % 5] = normalize(rot(s[i][j], RHOM[i][j]))`.
         cell_manager.get_strategy().start_region();
         let mut lookup_counter = 0;
-        let part_size = get_num_bits_per_base_chi_lookup();
+        let part_size = get_num_bits_per_base_chi_lookup(params);
         // To combine the rho/pi/chi steps we have to ensure a specific layout so
         // query those cells here first.
         // For chi we have to do `s[i][j] ^ ((~s[(i+1)%5][j]) & s[(i+2)%5][j])`. `j`
@@ -333,7 +340,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
                     if row_idx == 0 {
                         num_columns += 1;
                     }
-                    row_idx = (row_idx + 1) % get_num_rows_per_round();
+                    row_idx = (row_idx + 1) % num_rows_per_round;
                 }
             }
         }
@@ -387,7 +394,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
         // s[(i+2)%5][j])` five times, on each row (no selector needed).
         // This is calculated by making use of `CHI_BASE_LOOKUP_TABLE`.
         let mut lookup_counter = 0;
-        let part_size_base = get_num_bits_per_base_chi_lookup();
+        let part_size_base = get_num_bits_per_base_chi_lookup(params);
         for idx in 0..num_columns {
             // First fetch the cells we wan to use
             let mut input: [Expression<F>; 5] = array_init::array_init(|_| 0.expr());
@@ -436,7 +443,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
         // iota
         // Simply do the single xor on state [0][0].
         cell_manager.get_strategy().start_region();
-        let part_size = get_num_bits_per_absorb_lookup();
+        let part_size = get_num_bits_per_absorb_lookup(params);
         let input = s[0][0].clone() + round_cst_expr.clone();
         let iota_parts = split::expr(meta, &mut cell_manager, &mut cb, input, 0, part_size);
         cell_manager.get_strategy().start_region();
@@ -469,7 +476,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
         let squeeze_from = cell_manager.query_cell(meta, DEFAULT_CELL_TYPE);
         let mut squeeze_from_prev = vec![0u64.expr(); NUM_WORDS_TO_SQUEEZE];
         for (idx, squeeze_from_prev) in squeeze_from_prev.iter_mut().enumerate() {
-            let rot = (-(idx as i32) - 1) * get_num_rows_per_round() as i32;
+            let rot = (-(idx as i32) - 1) * num_rows_per_round as i32;
             *squeeze_from_prev = squeeze_from.at_offset(meta, rot).expr();
         }
         // Squeeze
@@ -549,7 +556,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
         let mut hash_bytes = Vec::new();
         for i in 0..NUM_WORDS_TO_SQUEEZE {
             for byte in squeeze_bytes.iter() {
-                let rot = (-(i as i32) - 1) * get_num_rows_per_round() as i32;
+                let rot = (-(i as i32) - 1) * num_rows_per_round as i32;
                 hash_bytes.push(byte.cell.at_offset(meta, rot).expr());
             }
         }
@@ -610,7 +617,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
         // Enforce logic for when this block is the last block for a hash
         let last_is_padding_in_block = is_paddings.last().unwrap().at_offset(
             meta,
-            -(((NUM_ROUNDS + 1 - NUM_WORDS_TO_ABSORB) * get_num_rows_per_round()) as i32),
+            -(((NUM_ROUNDS + 1 - NUM_WORDS_TO_ABSORB) * num_rows_per_round) as i32),
         );
         meta.create_gate("is final", |meta| {
             let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
@@ -628,7 +635,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
             );
             // For all the rows of a round, only the first row can have `is_final == 1`.
             cb.condition(
-                (1..get_num_rows_per_round() as i32)
+                (1..num_rows_per_round as i32)
                     .map(|i| meta.query_fixed(q_enable, Rotation(-i)))
                     .fold(0.expr(), |acc, elem| acc + elem),
                 |cb| {
@@ -647,7 +654,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
         let prev_is_padding = is_paddings
             .last()
             .unwrap()
-            .at_offset(meta, -(get_num_rows_per_round() as i32));
+            .at_offset(meta, -(num_rows_per_round as i32));
         meta.create_gate("padding", |meta| {
             let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
             let q_padding = meta.query_fixed(q_padding, Rotation::cur());
@@ -737,13 +744,10 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
             let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
 
             let q_padding = meta.query_fixed(q_padding, Rotation::cur());
-            let start_new_hash_prev =
-                start_new_hash(meta, Rotation(-(get_num_rows_per_round() as i32)));
-            let length_prev =
-                meta.query_advice(length, Rotation(-(get_num_rows_per_round() as i32)));
+            let start_new_hash_prev = start_new_hash(meta, Rotation(-(num_rows_per_round as i32)));
+            let length_prev = meta.query_advice(length, Rotation(-(num_rows_per_round as i32)));
             let length = meta.query_advice(length, Rotation::cur());
-            let data_rlc_prev =
-                meta.query_advice(data_rlc, Rotation(-(get_num_rows_per_round() as i32)));
+            let data_rlc_prev = meta.query_advice(data_rlc, Rotation(-(num_rows_per_round as i32)));
             let data_rlcs: Vec<_> = (0..NUM_BYTES_PER_WORD + 1)
                 .map(|i| meta.query_advice(data_rlc, Rotation(i as i32)))
                 .collect();
@@ -842,18 +846,30 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
             "num unused cells: {}",
             cell_manager.get_strategy().get_num_unused_cells()
         );
-        debug!("part_size absorb: {}", get_num_bits_per_absorb_lookup());
-        debug!("part_size theta: {}", get_num_bits_per_theta_c_lookup());
+        debug!(
+            "part_size absorb: {}",
+            get_num_bits_per_absorb_lookup(params)
+        );
+        debug!(
+            "part_size theta: {}",
+            get_num_bits_per_theta_c_lookup(params)
+        );
         debug!(
             "part_size theta c: {}",
-            get_num_bits_per_lookup(THETA_C_LOOKUP_RANGE)
+            get_num_bits_per_lookup(THETA_C_LOOKUP_RANGE, params)
+        );
+        debug!("part_size theta t: {}", get_num_bits_per_lookup(4, params));
+        debug!(
+            "part_size rho/pi: {}",
+            get_num_bits_per_rho_pi_lookup(params)
+        );
+        debug!(
+            "part_size chi base: {}",
+            get_num_bits_per_base_chi_lookup(params)
         );
-        debug!("part_size theta t: {}", get_num_bits_per_lookup(4));
-        debug!("part_size rho/pi: {}", get_num_bits_per_rho_pi_lookup());
-        debug!("part_size chi base: {}", get_num_bits_per_base_chi_lookup());
         debug!(
             "uniform part sizes: {:?}",
-            target_part_sizes(get_num_bits_per_theta_c_lookup())
+            target_part_sizes(get_num_bits_per_theta_c_lookup(params))
         );
 
         KeccakCircuitConfig {
@@ -872,6 +888,7 @@ impl<F: Field> SubCircuitConfig<F> for KeccakCircuitConfig<F> {
             normalize_6,
             chi_base_table,
             pack_table,
+            params,
             _marker: PhantomData,
         }
     }
@@ -967,14 +984,32 @@ impl<F: Field> KeccakCircuitConfig<F> {
     }
 
     pub(crate) fn load_aux_tables(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
-        load_normalize_table(layouter, "normalize_6", &self.normalize_6, 6u64)?;
-        load_normalize_table(layouter, "normalize_4", &self.normalize_4, 4u64)?;
-        load_normalize_table(layouter, "normalize_3", &self.normalize_3, 3u64)?;
+        load_normalize_table(
+            layouter,
+            "normalize_6",
+            &self.normalize_6,
+            6u64,
+            self.params,
+        )?;
+        load_normalize_table(
+            layouter,
+            "normalize_4",
+            &self.normalize_4,
+            4u64,
+            self.params,
+        )?;
+        load_normalize_table(
+            layouter,
+            "normalize_3",
+            &self.normalize_3,
+            3u64,
+            self.params,
+        )?;
         load_lookup_table(
             layouter,
             "chi base",
             &self.chi_base_table,
-            get_num_bits_per_base_chi_lookup(),
+            get_num_bits_per_base_chi_lookup(self.params),
             &CHI_BASE_LOOKUP_TABLE,
         )?;
         load_pack_table(layouter, &self.pack_table)
@@ -995,29 +1030,37 @@ impl<F: Field> KeccakCircuitConfig<F> {
 pub struct KeccakCircuit<F: Field> {
     inputs: Vec<Vec<u8>>,
     num_rows: usize,
+    params: KeccakCircuitParams,
     _marker: PhantomData<F>,
 }
 
 impl<F: Field> SubCircuit<F> for KeccakCircuit<F> {
     type Config = KeccakCircuitConfig<F>;
 
+    /// Unusable rows of the circuit with the default [`KeccakCircuitParams`]
     fn unusable_rows() -> usize {
-        keccak_unusable_rows()
+        keccak_unusable_rows(KeccakCircuitParams::default().rows_per_round)
+    }
+
+    fn unusable_rows_with_params(params: &FixedCParams) -> usize {
+        keccak_unusable_rows(params.keccak_params.rows_per_round)
     }
 
     /// The `chunk.fixed_param.keccak_padding` parameter, when enabled, sets
     /// up the circuit to support a fixed number of permutations/keccak_f's,
-    /// independently of the permutations required by `inputs`.
+    /// independently of the permutations required by `inputs`.  The circuit
+    /// has the `chunk.fixed_param.keccak_params`.
     fn new_from_block(block: &witness::Block<F>, chunk: &Chunk<F>) -> Self {
         Self::new(
             chunk.fixed_param.max_keccak_rows,
             block.keccak_inputs.clone(),
         )
+        .with_params(chunk.fixed_param.keccak_params)
     }
 
     /// Return the minimum number of rows required to prove the block
     fn min_num_rows_block(block: &witness::Block<F>, chunk: &Chunk<F>) -> (usize, usize) {
-        let rows_perchunk = (NUM_ROUNDS + 1) * get_num_rows_per_round();
+        let rows_perchunk = (NUM_ROUNDS + 1) * chunk.fixed_param.keccak_params.rows_per_round;
         (
             block
                 .keccak_inputs
@@ -1042,28 +1085,55 @@ impl<F: Field> SubCircuit<F> for KeccakCircuit<F> {
 }
 
 impl<F: Field> KeccakCircuit<F> {
-    /// Creates a new circuit instance
+    /// Creates a new circuit instance with the default [`KeccakCircuitParams`]
     pub fn new(num_rows: usize, inputs: Vec<Vec<u8>>) -> Self {
         KeccakCircuit {
             inputs,
             num_rows,
+            params: KeccakCircuitParams::default(),
             _marker: PhantomData,
         }
     }
 
+    /// Set the size of the lookup tables and rounds of the circuit
+    pub fn with_params(mut self, params: KeccakCircuitParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Returns the size of the lookup tables and rounds of the circuit
+    pub fn params(&self) -> KeccakCircuitParams {
+        self.params
+    }
+
     /// The number of keccak_f's that can be done in this circuit
     pub fn capacity(&self) -> Option<usize> {
         if self.num_rows > 0 {
             // Subtract two for unusable rows
-            Some(self.num_rows / ((NUM_ROUNDS + 1) * get_num_rows_per_round()) - 2)
+            Some(
+                (self.num_rows / ((NUM_ROUNDS + 1) * self.params.rows_per_round)).saturating_sub(2),
+            )
         } else {
             None
         }
     }
 
+    /// The number of keccak_f's needed to hash `inputs`, to compare with
+    /// [`Self::capacity`]
+    pub fn num_permutations(inputs: &[Vec<u8>]) -> usize {
+        // the padding takes at least one byte, so a full last block needs
+        // another one
+        inputs.iter().map(|bytes| bytes.len() / RATE + 1).sum()
+    }
+
     /// Sets the witness using the data to be hashed
     pub(crate) fn generate_witness(&self, challenges: Challenges<Value<F>>) -> Vec<KeccakRow<F>> {
-        multi_keccak(self.inputs.as_slice(), challenges, self.capacity())
-            .expect("Too many inputs for given capacity")
+        multi_keccak(
+            self.inputs.as_slice(),
+            challenges,
+            self.capacity(),
+            self.params,
+        )
+        .expect("Too many inputs for given capacity")
     }
 }
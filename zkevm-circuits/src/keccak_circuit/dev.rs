@@ -1,7 +1,7 @@
 pub use super::KeccakCircuit;
 
 use crate::{
-    keccak_circuit::{KeccakCircuitConfig, KeccakCircuitConfigArgs},
+    keccak_circuit::{KeccakCircuitConfig, KeccakCircuitConfigArgs, KeccakCircuitParams},
     table::KeccakTable,
     util::{Challenges, SubCircuit, SubCircuitConfig},
 };
@@ -14,13 +14,17 @@ use halo2_proofs::{
 impl<F: Field> Circuit<F> for KeccakCircuit<F> {
     type Config = (KeccakCircuitConfig<F>, Challenges);
     type FloorPlanner = SimpleFloorPlanner;
-    type Params = ();
+    type Params = KeccakCircuitParams;
 
     fn without_witnesses(&self) -> Self {
-        Self::default()
+        Self::default().with_params(self.params)
     }
 
-    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+    fn params(&self) -> Self::Params {
+        self.params
+    }
+
+    fn configure_with_params(meta: &mut ConstraintSystem<F>, params: Self::Params) -> Self::Config {
         let keccak_table = KeccakTable::construct(meta);
         let challenges = Challenges::construct(meta);
 
@@ -31,12 +35,17 @@ impl<F: Field> Circuit<F> for KeccakCircuit<F> {
                 KeccakCircuitConfigArgs {
                     keccak_table,
                     challenges,
+                    params,
                 },
             )
         };
         (config, challenges)
     }
 
+    fn configure(_meta: &mut ConstraintSystem<F>) -> Self::Config {
+        unreachable!();
+    }
+
     fn synthesize(
         &self,
         (config, challenges): Self::Config,
@@ -1,4 +1,4 @@
-use super::{param::*, util::*};
+use super::{param::*, util::*, KeccakCircuitParams};
 use eth_types::Field;
 use halo2_proofs::{
     circuit::{Layouter, Value},
@@ -6,27 +6,16 @@ use halo2_proofs::{
 };
 use itertools::Itertools;
 
-/// Loads a normalization table with the given parameters and KECCAK_DEGREE.
+/// Loads a normalization table with the given parameters.
 pub(crate) fn load_normalize_table<F: Field>(
     layouter: &mut impl Layouter<F>,
     name: &str,
     tables: &[TableColumn; 2],
     range: u64,
-) -> Result<(), Error> {
-    let log_height = get_degree();
-    load_normalize_table_impl(layouter, name, tables, range, log_height)
-}
-
-// Implementation of the above without environment dependency.
-fn load_normalize_table_impl<F: Field>(
-    layouter: &mut impl Layouter<F>,
-    name: &str,
-    tables: &[TableColumn; 2],
-    range: u64,
-    log_height: usize,
+    params: KeccakCircuitParams,
 ) -> Result<(), Error> {
     assert!(range <= BIT_SIZE as u64);
-    let part_size = get_num_bits_per_lookup_impl(range as usize, log_height);
+    let part_size = get_num_bits_per_lookup(range as usize, params);
     layouter.assign_table(
         || format!("{} table", name),
         |mut table| {
@@ -233,8 +222,15 @@ mod tests {
     }
 
     impl TableTestCircuit {
+        fn params(&self) -> KeccakCircuitParams {
+            KeccakCircuitParams {
+                degree: self.log_height,
+                ..Default::default()
+            }
+        }
+
         fn expected_num_entries(&self) -> usize {
-            let num_bits = get_num_bits_per_lookup_impl(self.range, self.log_height);
+            let num_bits = get_num_bits_per_lookup(self.range, self.params());
             self.range.pow(num_bits as u32)
         }
     }
@@ -258,15 +254,15 @@ mod tests {
             mut layouter: impl Layouter<F>,
         ) -> Result<(), Error> {
             if self.normalize_else_chi {
-                load_normalize_table_impl(
+                load_normalize_table(
                     &mut layouter,
                     "normalize",
                     &config,
                     self.range as u64,
-                    self.log_height,
+                    self.params(),
                 )?;
             } else {
-                let num_bits = get_num_bits_per_lookup_impl(self.range, self.log_height);
+                let num_bits = get_num_bits_per_lookup(self.range, self.params());
                 load_lookup_table(
                     &mut layouter,
                     "chi base",
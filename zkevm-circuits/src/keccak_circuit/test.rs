@@ -16,27 +16,44 @@ use std::iter::zip;
 
 use super::util::{target_part_sizes, target_part_sizes_rot, WordParts};
 
-// This needs to be tested independent since it sets the environment variable
-// which might affect other tests.
-#[ignore]
 #[test]
-fn serial_keccak_circuit_unusable_rows() {
-    for keccak_rows in NUM_BYTES_PER_WORD + 1..=32 {
-        std::env::set_var("KECCAK_ROWS", format!("{keccak_rows}"));
+fn keccak_circuit_unusable_rows() {
+    assert_eq!(
+        KeccakCircuit::<Fr>::unusable_rows(),
+        unusable_rows::<Fr, KeccakCircuit::<Fr>>(KeccakCircuitParams::default()),
+    );
+    for rows_per_round in NUM_BYTES_PER_WORD + 1..=32 {
+        let params = KeccakCircuitParams {
+            rows_per_round,
+            ..Default::default()
+        };
+        assert_eq!(
+            keccak_unusable_rows(rows_per_round),
+            unusable_rows::<Fr, KeccakCircuit::<Fr>>(params),
+        );
         assert_eq!(
-            KeccakCircuit::<Fr>::unusable_rows(),
-            unusable_rows::<Fr, KeccakCircuit::<Fr>>(()),
-        )
+            KeccakCircuit::<Fr>::unusable_rows_with_params(&FixedCParams {
+                keccak_params: params,
+                ..Default::default()
+            }),
+            keccak_unusable_rows(rows_per_round),
+        );
     }
-    std::env::set_var("KECCAK_ROWS", format!("{DEFAULT_KECCAK_ROWS}"));
 }
 
 const EMPTY_DIGEST: &str = "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
 
-fn verify<F: Field>(k: u32, inputs: Vec<Vec<u8>>, digests: Vec<String>, success: bool) {
-    let circuit = KeccakCircuit::new(2usize.pow(k), inputs.clone());
+fn verify<F: Field>(
+    k: u32,
+    params: KeccakCircuitParams,
+    inputs: Vec<Vec<u8>>,
+    digests: Vec<String>,
+    success: bool,
+) {
+    let circuit = KeccakCircuit::new(2usize.pow(k), inputs.clone()).with_params(params);
     let prover = MockProver::<F>::run(k, &circuit, vec![]).unwrap();
-    let (config, challenges) = KeccakCircuit::configure(&mut ConstraintSystem::<F>::default());
+    let (config, challenges) =
+        KeccakCircuit::configure_with_params(&mut ConstraintSystem::<F>::default(), params);
     let input_challenge = prover.get_challenge(challenges.keccak_input());
 
     // Check constraints.
@@ -124,7 +141,22 @@ fn packed_multi_keccak_simple() {
         "ac73d4fae68b8453f764007c1a20ce95994187861f0c3227a3a8e99a73a3b1db".to_string(),
         "f46dfb05481d2a50c0c3b6625d913055da3e07dcd0d6c661f27f1449b0fed7eb".to_string(),
     ];
-    verify::<Fr>(k, inputs, digests, true);
+    verify::<Fr>(k, KeccakCircuitParams::default(), inputs, digests, true);
+}
+
+#[test]
+fn packed_multi_keccak_params() {
+    let k = 14;
+    let params = KeccakCircuitParams {
+        degree: k as usize,
+        rows_per_round: 25,
+    };
+    let inputs = vec![vec![], (0u8..137).collect::<Vec<_>>()];
+    let digests = vec![
+        EMPTY_DIGEST.to_string(),
+        "ac73d4fae68b8453f764007c1a20ce95994187861f0c3227a3a8e99a73a3b1db".to_string(),
+    ];
+    verify::<Fr>(k, params, inputs, digests, true);
 }
 
 fn assigned_non_zero<F: Field>(cv: &CellValue<F>) -> bool {
@@ -1,8 +1,7 @@
 //! Utility traits, functions used in the crate.
 
-use super::{keccak_packed_multi::keccak_unusable_rows, param::*};
+use super::{keccak_packed_multi::keccak_unusable_rows, param::*, KeccakCircuitParams};
 use eth_types::{Field, ToScalar, Word};
-use std::env::var;
 
 /// Description of which bits (positions) a part contains
 #[derive(Clone, Debug)]
@@ -199,25 +198,11 @@ pub(crate) fn get_rotate_count(count: usize, part_size: usize) -> usize {
     (count + part_size - 1) / part_size
 }
 
-/// Get the degree of the circuit from the KECCAK_DEGREE env variable
-pub(crate) fn get_degree() -> usize {
-    var("KECCAK_DEGREE")
-        .unwrap_or_else(|_| "8".to_string())
-        .parse()
-        .expect("Cannot parse KECCAK_DEGREE env var as usize")
-}
-
 /// Returns how many bits we can process in a single lookup given the range of
-/// values the bit can have and the height of the circuit (via KECCAK_DEGREE).
-pub(crate) fn get_num_bits_per_lookup(range: usize) -> usize {
-    let log_height = get_degree();
-    get_num_bits_per_lookup_impl(range, log_height)
-}
-
-// Implementation of the above without environment dependency.
-pub(crate) fn get_num_bits_per_lookup_impl(range: usize, log_height: usize) -> usize {
-    let num_unusable_rows = keccak_unusable_rows();
-    let height = 2usize.pow(log_height as u32);
+/// values the bit can have and the height of the circuit (via `params.degree`).
+pub(crate) fn get_num_bits_per_lookup(range: usize, params: KeccakCircuitParams) -> usize {
+    let num_unusable_rows = keccak_unusable_rows(params.rows_per_round);
+    let height = 2usize.pow(params.degree as u32);
     let mut num_bits = 1;
     while range.pow(num_bits + 1) + num_unusable_rows <= height {
         num_bits += 1;
@@ -271,12 +256,16 @@ mod tests {
 
     #[test]
     fn num_bits_per_lookup() {
+        let params = |degree| KeccakCircuitParams {
+            degree,
+            ..Default::default()
+        };
         // Typical values.
-        assert_eq!(get_num_bits_per_lookup_impl(3, 19), 11);
-        assert_eq!(get_num_bits_per_lookup_impl(4, 19), 9);
-        assert_eq!(get_num_bits_per_lookup_impl(5, 19), 8);
-        assert_eq!(get_num_bits_per_lookup_impl(6, 19), 7);
+        assert_eq!(get_num_bits_per_lookup(3, params(19)), 11);
+        assert_eq!(get_num_bits_per_lookup(4, params(19)), 9);
+        assert_eq!(get_num_bits_per_lookup(5, params(19)), 8);
+        assert_eq!(get_num_bits_per_lookup(6, params(19)), 7);
         // The largest possible value does not overflow u64.
-        assert_eq!(get_num_bits_per_lookup_impl(3, 32) * BIT_COUNT, 60);
+        assert_eq!(get_num_bits_per_lookup(3, params(32)) * BIT_COUNT, 60);
     }
 }
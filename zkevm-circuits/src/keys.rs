@@ -0,0 +1,461 @@
+//! Serialization of the KZG params, proving and verifying keys of the circuits,
+//! to reuse them across runs instead of generating them again.
+//!
+//! Keys depend on the configuration of the circuit, so they are stored with a
+//! header made of the [`KeyConfigHash`] of the [`FixedCParams`] and
+//! [`FeatureConfig`] they have been generated for, and of their degree.  A key
+//! is only loaded if its header matches the current configuration.
+//!
+//! [`KeyCache`] stores them in a directory:
+//! ```ignore
+//! let cache = KeyCache::new("keys");
+//! // the params of a trusted setup, written with `write_params` beforehand
+//! let params = cache.params(k)?;
+//! let pk = cache.super_circuit_pk(&params, &circuit)?;
+//! let root_pk = cache.pk(
+//!     &root_params,
+//!     &root_circuit,
+//!     &KeyConfigHash::root_circuit(&circuit, num_chunks),
+//! )?;
+//! ```
+
+use crate::super_circuit::SuperCircuit;
+use bus_mapping::circuit_input_builder::{FeatureConfig, FixedCParams};
+use eth_types::keccak256;
+use halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::{keygen_pk, keygen_vk, Circuit, ProvingKey, VerifyingKey},
+    poly::{commitment::Params, kzg::commitment::ParamsKZG},
+    SerdeFormat,
+};
+#[cfg(any(test, feature = "test-circuits"))]
+use rand::rngs::OsRng;
+use serde::Serialize;
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::PathBuf,
+};
+use thiserror::Error;
+
+/// Format of the serialized params and keys, which checks that the points are
+/// on the curve when reading them.
+const SERDE_FORMAT: SerdeFormat = SerdeFormat::RawBytes;
+
+/// Circuits whose keys can be serialized
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum KeyCircuit {
+    /// SuperCircuit
+    SuperCircuit,
+    /// RootCircuit aggregating the proofs of the SuperCircuit
+    RootCircuit,
+}
+
+/// Hash of the configuration a key has been generated for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyConfigHash(pub [u8; 32]);
+
+impl KeyConfigHash {
+    /// Hash the configuration of `circuit`.  The keys of the `RootCircuit`
+    /// depend on the configuration of the aggregated `SuperCircuit`, so it's
+    /// the one given for both.
+    pub fn new(
+        circuit: KeyCircuit,
+        circuits_params: &FixedCParams,
+        feature_config: &FeatureConfig,
+    ) -> Self {
+        let config = serde_json::to_vec(&(circuit, circuits_params, feature_config))
+            .expect("circuit config is serializable");
+        Self(keccak256(&config))
+    }
+
+    /// Hash the configuration of the `SuperCircuit`.
+    pub fn super_circuit(circuit: &SuperCircuit<Fr>) -> Self {
+        Self::new(
+            KeyCircuit::SuperCircuit,
+            &circuit.circuits_params,
+            &circuit.feature_config,
+        )
+    }
+
+    /// Hash the configuration of the `RootCircuit` aggregating the proofs of
+    /// `num_chunks` chunks of `circuit`, which may be fewer than the
+    /// `total_chunks` of its params.
+    pub fn root_circuit(circuit: &SuperCircuit<Fr>, num_chunks: usize) -> Self {
+        Self::new(
+            KeyCircuit::RootCircuit,
+            &FixedCParams {
+                total_chunks: num_chunks,
+                ..circuit.circuits_params
+            },
+            &circuit.feature_config,
+        )
+    }
+
+    /// Returns the hash as hex string.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+}
+
+/// Errors of the key serialization
+#[derive(Debug, Error)]
+pub enum KeyError {
+    /// Failed to read or write
+    #[error("Io({0})")]
+    Io(#[from] io::Error),
+    /// The key has been generated for another configuration
+    #[error("ConfigMismatch(expected: {expected}, found: {found})")]
+    ConfigMismatch {
+        /// Hash of the current configuration
+        expected: String,
+        /// Hash of the configuration of the key
+        found: String,
+    },
+    /// The key or params have another degree
+    #[error("DegreeMismatch(expected: {expected}, found: {found})")]
+    DegreeMismatch {
+        /// Degree of the current params
+        expected: u32,
+        /// Degree of the key or params
+        found: u32,
+    },
+    /// The params of the given degree are missing from the cache
+    #[error("MissingParams(k: {k}, path: {path:?})")]
+    MissingParams {
+        /// Degree of the params
+        k: u32,
+        /// Path the params are expected at
+        path: PathBuf,
+    },
+    /// Failed to generate a key
+    #[error("Keygen({0:?})")]
+    Keygen(halo2_proofs::plonk::Error),
+}
+
+/// Write the KZG params.
+pub fn write_params<W: Write>(params: &ParamsKZG<Bn256>, writer: &mut W) -> io::Result<()> {
+    params.write_custom(writer, SERDE_FORMAT)
+}
+
+/// Read the KZG params, which must be of degree `k`.
+pub fn read_params<R: Read>(reader: &mut R, k: u32) -> Result<ParamsKZG<Bn256>, KeyError> {
+    let params = ParamsKZG::<Bn256>::read_custom(reader, SERDE_FORMAT)?;
+    check_degree(k, params.k())?;
+    Ok(params)
+}
+
+/// Write the header of a key
+fn write_header<W: Write>(writer: &mut W, config_hash: &KeyConfigHash, k: u32) -> io::Result<()> {
+    writer.write_all(&config_hash.0)?;
+    writer.write_all(&k.to_le_bytes())
+}
+
+/// Read the header of a key, checking it against the current configuration.
+fn read_header<R: Read>(
+    reader: &mut R,
+    config_hash: &KeyConfigHash,
+    k: u32,
+) -> Result<(), KeyError> {
+    let mut hash = [0u8; 32];
+    reader.read_exact(&mut hash)?;
+    if hash != config_hash.0 {
+        return Err(KeyError::ConfigMismatch {
+            expected: config_hash.to_hex(),
+            found: KeyConfigHash(hash).to_hex(),
+        });
+    }
+    let mut degree = [0u8; 4];
+    reader.read_exact(&mut degree)?;
+    check_degree(k, u32::from_le_bytes(degree))
+}
+
+fn check_degree(expected: u32, found: u32) -> Result<(), KeyError> {
+    if expected != found {
+        return Err(KeyError::DegreeMismatch { expected, found });
+    }
+    Ok(())
+}
+
+/// Write the verifying key generated for `config_hash`.
+pub fn write_vk<W: Write>(
+    vk: &VerifyingKey<G1Affine>,
+    config_hash: &KeyConfigHash,
+    writer: &mut W,
+) -> io::Result<()> {
+    write_header(writer, config_hash, vk.get_domain().k())?;
+    vk.write(writer, SERDE_FORMAT)
+}
+
+/// Read the verifying key of the circuit `C` configured with `circuit_params`,
+/// checking that it has been generated for `config_hash` with degree `k`.
+pub fn read_vk<C: Circuit<Fr>, R: Read>(
+    reader: &mut R,
+    config_hash: &KeyConfigHash,
+    k: u32,
+    circuit_params: C::Params,
+) -> Result<VerifyingKey<G1Affine>, KeyError> {
+    read_header(reader, config_hash, k)?;
+    Ok(VerifyingKey::read::<_, C>(
+        reader,
+        SERDE_FORMAT,
+        circuit_params,
+    )?)
+}
+
+/// Write the proving key generated for `config_hash`.
+pub fn write_pk<W: Write>(
+    pk: &ProvingKey<G1Affine>,
+    config_hash: &KeyConfigHash,
+    writer: &mut W,
+) -> io::Result<()> {
+    write_header(writer, config_hash, pk.get_vk().get_domain().k())?;
+    pk.write(writer, SERDE_FORMAT)
+}
+
+/// Read the proving key of the circuit `C` configured with `circuit_params`,
+/// checking that it has been generated for `config_hash` with degree `k`.
+pub fn read_pk<C: Circuit<Fr>, R: Read>(
+    reader: &mut R,
+    config_hash: &KeyConfigHash,
+    k: u32,
+    circuit_params: C::Params,
+) -> Result<ProvingKey<G1Affine>, KeyError> {
+    read_header(reader, config_hash, k)?;
+    Ok(ProvingKey::read::<_, C>(
+        reader,
+        SERDE_FORMAT,
+        circuit_params,
+    )?)
+}
+
+/// Directory caching the params and keys.  The keys are generated when
+/// missing, while the params must be written in it beforehand.
+#[derive(Clone, Debug)]
+pub struct KeyCache {
+    dir: PathBuf,
+}
+
+impl KeyCache {
+    /// Create a `KeyCache` in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn params_path(&self, k: u32) -> PathBuf {
+        self.dir.join(format!("params_k{}.bin", k))
+    }
+
+    fn pk_path(&self, config_hash: &KeyConfigHash, k: u32) -> PathBuf {
+        self.dir
+            .join(format!("pk_{}_k{}.bin", config_hash.to_hex(), k))
+    }
+
+    fn vk_path(&self, config_hash: &KeyConfigHash, k: u32) -> PathBuf {
+        self.dir
+            .join(format!("vk_{}_k{}.bin", config_hash.to_hex(), k))
+    }
+
+    /// Load the params of degree `k`, which must have been written in the
+    /// cache with [`Self::write_params`].
+    pub fn params(&self, k: u32) -> Result<ParamsKZG<Bn256>, KeyError> {
+        let path = self.params_path(k);
+        if !path.exists() {
+            return Err(KeyError::MissingParams { k, path });
+        }
+        read_params(&mut BufReader::new(File::open(path)?), k)
+    }
+
+    /// Write the params of a trusted setup in the cache.
+    pub fn write_params(&self, params: &ParamsKZG<Bn256>) -> Result<(), KeyError> {
+        fs::create_dir_all(&self.dir)?;
+        write_params(
+            params,
+            &mut BufWriter::new(File::create(self.params_path(params.k()))?),
+        )?;
+        Ok(())
+    }
+
+    /// Load the params of degree `k`, or set them up with a random toxic waste
+    /// if missing.  These params are insecure, so they're only meant for tests
+    /// and development.
+    #[cfg(any(test, feature = "test-circuits"))]
+    pub fn insecure_params(&self, k: u32) -> Result<ParamsKZG<Bn256>, KeyError> {
+        match self.params(k) {
+            Err(KeyError::MissingParams { .. }) => {
+                let params = ParamsKZG::<Bn256>::setup(k, OsRng);
+                self.write_params(&params)?;
+                Ok(params)
+            }
+            result => result,
+        }
+    }
+
+    /// Load the proving key of `circuit` generated for `config_hash`, or
+    /// generate it if missing.  Its verifying key is written along, to be
+    /// loaded with [`Self::vk`] by verifiers.
+    pub fn pk<C: Circuit<Fr>>(
+        &self,
+        params: &ParamsKZG<Bn256>,
+        circuit: &C,
+        config_hash: &KeyConfigHash,
+    ) -> Result<ProvingKey<G1Affine>, KeyError> {
+        let path = self.pk_path(config_hash, params.k());
+        if path.exists() {
+            return read_pk::<C, _>(
+                &mut BufReader::new(File::open(path)?),
+                config_hash,
+                params.k(),
+                circuit.params(),
+            );
+        }
+        let vk = keygen_vk(params, circuit).map_err(KeyError::Keygen)?;
+        let pk = keygen_pk(params, vk, circuit).map_err(KeyError::Keygen)?;
+        fs::create_dir_all(&self.dir)?;
+        write_pk(&pk, config_hash, &mut BufWriter::new(File::create(path)?))?;
+        write_vk(
+            pk.get_vk(),
+            config_hash,
+            &mut BufWriter::new(File::create(self.vk_path(config_hash, params.k()))?),
+        )?;
+        Ok(pk)
+    }
+
+    /// Load the verifying key of degree `k` of the circuit `C` configured with
+    /// `circuit_params`, generated for `config_hash` by [`Self::pk`].
+    pub fn vk<C: Circuit<Fr>>(
+        &self,
+        k: u32,
+        config_hash: &KeyConfigHash,
+        circuit_params: C::Params,
+    ) -> Result<VerifyingKey<G1Affine>, KeyError> {
+        read_vk::<C, _>(
+            &mut BufReader::new(File::open(self.vk_path(config_hash, k))?),
+            config_hash,
+            k,
+            circuit_params,
+        )
+    }
+
+    /// Load or generate the proving key of the `SuperCircuit`.
+    pub fn super_circuit_pk(
+        &self,
+        params: &ParamsKZG<Bn256>,
+        circuit: &SuperCircuit<Fr>,
+    ) -> Result<ProvingKey<G1Affine>, KeyError> {
+        self.pk(params, circuit, &KeyConfigHash::super_circuit(circuit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exp_circuit::ExpCircuit;
+    use bus_mapping::circuit_input_builder::KeccakCircuitParams;
+
+    #[test]
+    fn config_hash() {
+        let params = FixedCParams::default();
+        let feature_config = FeatureConfig::default();
+        let hash = KeyConfigHash::new(KeyCircuit::SuperCircuit, &params, &feature_config);
+        assert_eq!(
+            hash,
+            KeyConfigHash::new(KeyCircuit::SuperCircuit, &params, &feature_config)
+        );
+        assert_ne!(
+            hash,
+            KeyConfigHash::new(KeyCircuit::RootCircuit, &params, &feature_config)
+        );
+        assert_ne!(
+            hash,
+            KeyConfigHash::new(
+                KeyCircuit::SuperCircuit,
+                &FixedCParams {
+                    max_txs: params.max_txs + 1,
+                    ..params
+                },
+                &feature_config
+            )
+        );
+        assert_ne!(
+            hash,
+            KeyConfigHash::new(
+                KeyCircuit::SuperCircuit,
+                &FixedCParams {
+                    keccak_params: KeccakCircuitParams {
+                        rows_per_round: params.keccak_params.rows_per_round + 1,
+                        ..params.keccak_params
+                    },
+                    ..params
+                },
+                &feature_config
+            )
+        );
+        assert_ne!(
+            hash,
+            KeyConfigHash::new(
+                KeyCircuit::SuperCircuit,
+                &params,
+                &FeatureConfig {
+                    invalid_tx: !feature_config.invalid_tx,
+                    ..feature_config
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn missing_params() {
+        let k = 4;
+        let dir = std::env::temp_dir().join(format!("keys_{}", std::process::id()));
+        let cache = KeyCache::new(&dir);
+        assert!(matches!(
+            cache.params(k),
+            Err(KeyError::MissingParams { k: 4, .. })
+        ));
+
+        let params = cache.insecure_params(k).unwrap();
+        assert_eq!(cache.params(k).unwrap().s_g2(), params.s_g2());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn pk_roundtrip() {
+        let k = 13;
+        let params = ParamsKZG::<Bn256>::setup(k, OsRng);
+        let circuit = ExpCircuit::<Fr>::new(vec![], 10);
+        let config_hash = KeyConfigHash::new(
+            KeyCircuit::SuperCircuit,
+            &FixedCParams::default(),
+            &FeatureConfig::default(),
+        );
+
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        let pk = keygen_pk(&params, vk, &circuit).unwrap();
+        let mut bytes = vec![];
+        write_pk(&pk, &config_hash, &mut bytes).unwrap();
+
+        let read =
+            read_pk::<ExpCircuit<Fr>, _>(&mut bytes.as_slice(), &config_hash, k, ()).unwrap();
+        assert_eq!(
+            read.get_vk().transcript_repr(),
+            pk.get_vk().transcript_repr()
+        );
+
+        // other configuration
+        let other_hash = KeyConfigHash::new(
+            KeyCircuit::RootCircuit,
+            &FixedCParams::default(),
+            &FeatureConfig::default(),
+        );
+        assert!(matches!(
+            read_pk::<ExpCircuit<Fr>, _>(&mut bytes.as_slice(), &other_hash, k, ()),
+            Err(KeyError::ConfigMismatch { .. })
+        ));
+        // other degree
+        assert!(matches!(
+            read_pk::<ExpCircuit<Fr>, _>(&mut bytes.as_slice(), &config_hash, k + 1, ()),
+            Err(KeyError::DegreeMismatch { .. })
+        ));
+    }
+}
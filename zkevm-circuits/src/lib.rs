@@ -1,9 +1,9 @@
 //! # zk_evm
 
 // We should try not to use incomplete_features unless it is really really needed and cannot be
-// avoided like `adt_const_params` used by DummyGadget
+// avoided like `adt_const_params` used by CreateGadget and HashPrecompileGadget
 #![allow(incomplete_features)]
-// Needed by DummyGadget in evm circuit
+// Needed by CreateGadget and HashPrecompileGadget in evm circuit
 #![feature(adt_const_params)]
 // Required for adding reasons in allow(dead_code)
 #![feature(lint_reasons)]
@@ -22,18 +22,26 @@ compile_error!("bus-mapping: notwasm feature must be disabled when target arch i
 #[cfg(all(not(target_arch = "wasm32"), not(feature = "notwasm")))]
 compile_error!("bus-mapping: notwasm feature must be enabled when target arch is not wasm");
 
+pub mod blake2f_circuit;
 pub mod bytecode_circuit;
 #[allow(dead_code, reason = "under active development")]
 pub mod circuit_tools;
 pub mod copy_circuit;
+pub mod ecc_circuit;
 pub mod evm_circuit;
 pub mod exp_circuit;
 pub mod keccak_circuit;
+pub mod keys;
+pub mod modexp_circuit;
 #[allow(dead_code, reason = "under active development")]
 pub mod mpt_circuit;
 pub mod pi_circuit;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod prover;
+pub mod ripemd160_circuit;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod root_circuit;
+pub mod sha256_circuit;
 pub mod sig_circuit;
 pub mod state_circuit;
 pub mod super_circuit;
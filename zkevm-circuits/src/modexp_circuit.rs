@@ -0,0 +1,1275 @@
+//! The MODEXP circuit implementation.
+//!
+//! The circuit has two parts, laid out side by side in the same rows. The
+//! first part parses every input in a region of `NUM_ROWS_PER_INPUT` rows, a
+//! row per input byte, which holds the row of the `ModExpTable` in its last
+//! row. The second part computes base^exponent % modulus by square-and-multiply
+//! on 1024-bit integers, with a unit of `NUM_ROWS_PER_UNIT` rows per modular
+//! multiplication a * b = quot * modulus + rem, a row per 64-bit limb. Row k
+//! of a unit checks the limbs k and k + 16 of the product with carries, and
+//! rem < modulus by a subtraction with borrows. The multiplications by the
+//! base also decompose the exponent into bits, MSB first.
+//!
+//! The last unit of a computation is linked to the input it computes by a
+//! lookup from the last row of the input region. A zero modulus is replaced
+//! by 1 in the multiplications, so that the result is zero.
+mod param;
+mod witness;
+
+#[cfg(any(test, feature = "test-circuits"))]
+mod dev;
+#[cfg(test)]
+mod test;
+#[cfg(feature = "test-circuits")]
+pub use dev::ModExpCircuit as TestModExpCircuit;
+
+use self::{
+    param::*,
+    witness::{multi_modexp, num_units, InputRow, ModExpWitness, UnitRow},
+};
+use crate::{
+    evm_circuit::util::constraint_builder::{BaseConstraintBuilder, ConstrainBuilderCommon},
+    table::{LookupTable, ModExpTable, UXTable},
+    util::{Challenges, SubCircuit, SubCircuitConfig},
+    witness::{Block, Chunk},
+};
+use bus_mapping::circuit_input_builder::BigModExp;
+use eth_types::Field;
+use gadgets::util::{expr_from_bytes, not, pow_of_two, select, sum, Expr};
+use halo2_proofs::{
+    circuit::{Layouter, Region, Value},
+    plonk::{
+        Advice, Column, ConstraintSystem, Error, Expression, Fixed, SecondPhase, VirtualCells,
+    },
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+/// A 64-bit limb with its little-endian bytes
+#[derive(Clone, Copy, Debug)]
+struct Limb {
+    value: Column<Advice>,
+    bytes: [Column<Advice>; NUM_BYTES_PER_LIMB],
+}
+
+impl Limb {
+    fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+        Self {
+            value: meta.advice_column(),
+            bytes: [(); NUM_BYTES_PER_LIMB].map(|_| meta.advice_column()),
+        }
+    }
+
+    fn query_bytes<F: Field>(&self, meta: &mut VirtualCells<F>, rot: i32) -> Vec<Expression<F>> {
+        self.bytes
+            .iter()
+            .map(|byte| meta.query_advice(*byte, Rotation(rot)))
+            .collect()
+    }
+
+    fn assign<F: Field>(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        name: &str,
+        value: u64,
+    ) -> Result<(), Error> {
+        region.assign_advice(
+            || format!("assign {} {}", name, offset),
+            self.value,
+            offset,
+            || Value::known(F::from(value)),
+        )?;
+        for (idx, (column, byte)) in self.bytes.iter().zip(value.to_le_bytes()).enumerate() {
+            region.assign_advice(
+                || format!("assign {} byte {} {}", name, idx, offset),
+                *column,
+                offset,
+                || Value::known(F::from(byte as u64)),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// ModExpCircuitConfig
+#[derive(Clone, Debug)]
+pub struct ModExpCircuitConfig<F> {
+    q_enable: Column<Fixed>,
+    // Selectors of the input regions
+    q_input: Column<Fixed>,
+    q_input_first: Column<Fixed>,
+    q_input_last: Column<Fixed>,
+    q_header: Column<Fixed>,
+    q_lens: [Column<Fixed>; 3],
+    // Selectors of the units
+    q_unit: Column<Fixed>,
+    q_unit_first: Column<Fixed>,
+    q_unit_last: Column<Fixed>,
+    q_rows: [Column<Fixed>; NUM_ROWS_PER_UNIT],
+    // Input regions
+    byte: Column<Advice>,
+    input_is_event: Column<Advice>,
+    lefts: [Column<Advice>; 4],
+    left_invs: [Column<Advice>; 4],
+    left_nonzeros: [Column<Advice>; 4],
+    is_parts: [Column<Advice>; 3],
+    part_rlcs: [Column<Advice>; 3],
+    // Modular multiplications
+    a: Column<Advice>,
+    b: Column<Advice>,
+    quot: Limb,
+    rem: Limb,
+    modulus: Limb,
+    base: Limb,
+    diff: Limb,
+    borrow: Column<Advice>,
+    carries_lo: [Column<Advice>; NUM_CARRY_CELLS],
+    carries_hi: [Column<Advice>; NUM_CARRY_CELLS],
+    mod_sum: Column<Advice>,
+    mod_sum_inv: Column<Advice>,
+    is_mod_zero: Column<Advice>,
+    unit_is_event: Column<Advice>,
+    is_first: Column<Advice>,
+    is_last: Column<Advice>,
+    is_square: Column<Advice>,
+    bit: Column<Advice>,
+    // Decomposition of the exponent
+    byte_acc: Column<Advice>,
+    bit_idx: Column<Advice>,
+    bit_idx_inv: Column<Advice>,
+    exp_rlc: Column<Advice>,
+    n_bytes: Column<Advice>,
+    head_left: Column<Advice>,
+    head_left_inv: Column<Advice>,
+    is_head: Column<Advice>,
+    seen: Column<Advice>,
+    bit_len: Column<Advice>,
+    base_rlc: Column<Advice>,
+    mod_rlc: Column<Advice>,
+    rem_rlc: Column<Advice>,
+    /// The columns for other circuits to lookup MODEXP results
+    pub modexp_table: ModExpTable,
+    _marker: PhantomData<F>,
+}
+
+/// Circuit configuration arguments
+pub struct ModExpCircuitConfigArgs<F: Field> {
+    /// ModExpTable
+    pub modexp_table: ModExpTable,
+    /// u8 lookup table
+    pub u8_table: UXTable<8>,
+    /// Challenges randomness
+    pub challenges: Challenges<Expression<F>>,
+}
+
+/// The value of a carry, stored with an offset in little-endian bytes
+fn query_carry<F: Field>(
+    meta: &mut VirtualCells<F>,
+    cells: &[Column<Advice>; NUM_CARRY_CELLS],
+    rot: i32,
+) -> Expression<F> {
+    cells.iter().rev().fold(0.expr(), |acc, cell| {
+        acc * 256.expr() + meta.query_advice(*cell, Rotation(rot))
+    }) - Expression::Constant(pow_of_two(CARRY_OFFSET_BITS))
+}
+
+impl<F: Field> SubCircuitConfig<F> for ModExpCircuitConfig<F> {
+    type ConfigArgs = ModExpCircuitConfigArgs<F>;
+
+    /// Return a new ModExpCircuitConfig
+    fn new(
+        meta: &mut ConstraintSystem<F>,
+        Self::ConfigArgs {
+            modexp_table,
+            u8_table,
+            challenges,
+        }: Self::ConfigArgs,
+    ) -> Self {
+        let q_enable = meta.fixed_column();
+        let q_input = meta.fixed_column();
+        let q_input_first = meta.fixed_column();
+        let q_input_last = meta.fixed_column();
+        let q_header = meta.fixed_column();
+        let q_lens = [(); 3].map(|_| meta.fixed_column());
+        let q_unit = meta.fixed_column();
+        let q_unit_first = meta.fixed_column();
+        let q_unit_last = meta.fixed_column();
+        let q_rows = [(); NUM_ROWS_PER_UNIT].map(|_| meta.fixed_column());
+        let byte = meta.advice_column();
+        let input_is_event = meta.advice_column();
+        let lefts = [(); 4].map(|_| meta.advice_column());
+        let left_invs = [(); 4].map(|_| meta.advice_column());
+        let left_nonzeros = [(); 4].map(|_| meta.advice_column());
+        let is_parts = [(); 3].map(|_| meta.advice_column());
+        let part_rlcs = [(); 3].map(|_| meta.advice_column_in(SecondPhase));
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let [quot, rem, modulus, base, diff] = [(); 5].map(|_| Limb::construct(meta));
+        let borrow = meta.advice_column();
+        let carries_lo = [(); NUM_CARRY_CELLS].map(|_| meta.advice_column());
+        let carries_hi = [(); NUM_CARRY_CELLS].map(|_| meta.advice_column());
+        let mod_sum = meta.advice_column();
+        let mod_sum_inv = meta.advice_column();
+        let is_mod_zero = meta.advice_column();
+        let unit_is_event = meta.advice_column();
+        let is_first = meta.advice_column();
+        let is_last = meta.advice_column();
+        let is_square = meta.advice_column();
+        let bit = meta.advice_column();
+        let byte_acc = meta.advice_column();
+        let bit_idx = meta.advice_column();
+        let bit_idx_inv = meta.advice_column();
+        let exp_rlc = meta.advice_column_in(SecondPhase);
+        let n_bytes = meta.advice_column();
+        let head_left = meta.advice_column();
+        let head_left_inv = meta.advice_column();
+        let is_head = meta.advice_column();
+        let seen = meta.advice_column();
+        let bit_len = meta.advice_column();
+        let base_rlc = meta.advice_column_in(SecondPhase);
+        let mod_rlc = meta.advice_column_in(SecondPhase);
+        let rem_rlc = meta.advice_column_in(SecondPhase);
+        let lens = [
+            modexp_table.base_len,
+            modexp_table.exp_len,
+            modexp_table.mod_len,
+        ];
+        let r = challenges.keccak_input();
+        let two_64 = Expression::Constant(pow_of_two::<F>(NUM_BITS_PER_LIMB));
+        let q_last_row = q_rows[NUM_ROWS_PER_UNIT - 1];
+        let last_row = NUM_ROWS_PER_UNIT as i32 - 1;
+        let unit_len = NUM_ROWS_PER_UNIT as i32;
+
+        // Input regions
+
+        meta.create_gate("input bytes", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let query = |meta: &mut VirtualCells<F>, column: Column<Advice>, rot: i32| {
+                meta.query_advice(column, Rotation(rot))
+            };
+            let byte = query(meta, byte, 0);
+            cb.require_boolean("is_event", query(meta, input_is_event, 0));
+
+            // left != 0 <=> left_nonzero
+            let nonzeros = left_nonzeros.map(|column| query(meta, column, 0));
+            for ((left, left_inv), nonzero) in lefts.iter().zip(left_invs).zip(nonzeros.iter()) {
+                let left = query(meta, *left, 0);
+                cb.require_equal(
+                    "left_nonzero = left * left_inv",
+                    nonzero.clone(),
+                    left.clone() * query(meta, left_inv, 0),
+                );
+                cb.require_zero("left_nonzero when left != 0", left * not::expr(nonzero));
+            }
+            let [is_input, nonzero_base, nonzero_exp, nonzero_mod] = nonzeros;
+            cb.require_zero(
+                "the input is padded with zeros",
+                not::expr(is_input.clone()) * byte.clone(),
+            );
+
+            // The base, exponent and modulus follow the header, one after the other
+            let not_header = not::expr(meta.query_fixed(q_header, Rotation::cur()));
+            let is_parts_cur = is_parts.map(|column| query(meta, column, 0));
+            cb.require_equal(
+                "is_base",
+                is_parts_cur[0].clone(),
+                not_header.clone() * nonzero_base.clone(),
+            );
+            cb.require_equal(
+                "is_exp",
+                is_parts_cur[1].clone(),
+                not_header.clone() * not::expr(nonzero_base.clone()) * nonzero_exp.clone(),
+            );
+            cb.require_equal(
+                "is_mod",
+                is_parts_cur[2].clone(),
+                not_header * not::expr(nonzero_base) * not::expr(nonzero_exp) * nonzero_mod,
+            );
+
+            // The header is made of the lengths as 32-byte words, at most SIZE_LIMIT
+            let q_lens_cur = q_lens.map(|q_len| meta.query_fixed(q_len, Rotation::cur()));
+            cb.require_zero(
+                "the header bytes are zero, except the last byte of the lengths",
+                meta.query_fixed(q_header, Rotation::cur())
+                    * not::expr(sum::expr(q_lens_cur.iter()))
+                    * byte.clone(),
+            );
+            for (q_len, len) in q_lens_cur.iter().zip(lens) {
+                cb.require_zero(
+                    "the last byte of a length",
+                    q_len.clone() * (byte.clone() - query(meta, len, 0)),
+                );
+            }
+
+            // Accumulate the RLCs of the input and its parts
+            let q_first = meta.query_fixed(q_input_first, Rotation::cur());
+            let not_first = not::expr(q_first.clone());
+            for (rlc, is_included) in [(modexp_table.input_rlc, is_input)]
+                .into_iter()
+                .chain(part_rlcs.into_iter().zip(is_parts_cur.iter().cloned()))
+            {
+                let prev_rlc = not_first.clone() * query(meta, rlc, -1);
+                cb.require_equal(
+                    "rlc",
+                    query(meta, rlc, 0),
+                    select::expr(
+                        is_included,
+                        prev_rlc.clone() * r.clone() + byte.clone(),
+                        prev_rlc,
+                    ),
+                );
+            }
+
+            // Count down the bytes left, which must all be used at the end
+            let q_last = meta.query_fixed(q_input_last, Rotation::cur());
+            let is_counted = [left_nonzeros[0], is_parts[0], is_parts[1], is_parts[2]];
+            let lens = [modexp_table.input_len, lens[0], lens[1], lens[2]];
+            for ((left, is_counted), len) in lefts.iter().zip(is_counted).zip(lens) {
+                cb.require_equal(
+                    "left",
+                    query(meta, *left, 0),
+                    select::expr(
+                        q_first.clone(),
+                        query(meta, len, 0),
+                        query(meta, *left, -1) - query(meta, is_counted, -1),
+                    ),
+                );
+                cb.require_zero(
+                    "no byte left at the end",
+                    q_last.clone() * (query(meta, *left, 0) - query(meta, is_counted, 0)),
+                );
+            }
+
+            // The data of the event stays the same in the region
+            for column in [
+                input_is_event,
+                modexp_table.input_len,
+                modexp_table.base_len,
+                modexp_table.exp_len,
+                modexp_table.mod_len,
+                modexp_table.exp_msb,
+                modexp_table.output_rlc,
+            ] {
+                cb.require_zero(
+                    "event data stays the same",
+                    not_first.clone() * (query(meta, column, 0) - query(meta, column, -1)),
+                );
+            }
+            cb.gate(meta.query_fixed(q_input, Rotation::cur()))
+        });
+
+        meta.create_gate(
+            "only the last row of an input region is in the table",
+            |meta| {
+                let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+                cb.require_equal(
+                    "is_enabled",
+                    meta.query_advice(modexp_table.is_enabled, Rotation::cur()),
+                    meta.query_fixed(q_input_last, Rotation::cur())
+                        * meta.query_advice(input_is_event, Rotation::cur()),
+                );
+                cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+            },
+        );
+
+        meta.lookup_any("input byte range check", |meta| {
+            let byte = meta.query_fixed(q_input, Rotation::cur())
+                * meta.query_advice(byte, Rotation::cur());
+            vec![(byte, u8_table.table_exprs(meta)[0].clone())]
+        });
+        meta.lookup_any("length range check", |meta| {
+            let q_len = sum::expr(q_lens.map(|q_len| meta.query_fixed(q_len, Rotation::cur())));
+            let diff = q_len * (SIZE_LIMIT.expr() - meta.query_advice(byte, Rotation::cur()));
+            vec![(diff, u8_table.table_exprs(meta)[0].clone())]
+        });
+
+        // Modular multiplications
+
+        meta.create_gate("boolean checks", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let is_event = meta.query_advice(unit_is_event, Rotation::cur());
+            for column in [
+                unit_is_event,
+                is_first,
+                is_last,
+                is_square,
+                bit,
+                borrow,
+                seen,
+            ] {
+                cb.require_boolean("boolean", meta.query_advice(column, Rotation::cur()));
+            }
+            for column in [is_first, is_last, is_square] {
+                cb.require_zero(
+                    "only in the units of an event",
+                    meta.query_advice(column, Rotation::cur()) * not::expr(is_event.clone()),
+                );
+            }
+            cb.gate(meta.query_fixed(q_unit, Rotation::cur()))
+        });
+
+        for (name, limb) in [
+            ("quot", quot),
+            ("rem", rem),
+            ("modulus", modulus),
+            ("base", base),
+            ("diff", diff),
+        ] {
+            meta.create_gate("limb bytes", |meta| {
+                let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+                cb.require_equal(
+                    name,
+                    meta.query_advice(limb.value, Rotation::cur()),
+                    expr_from_bytes(&limb.query_bytes(meta, 0)),
+                );
+                cb.gate(meta.query_fixed(q_unit, Rotation::cur()))
+            });
+            for column in limb.bytes {
+                meta.lookup_any("limb byte range check", |meta| {
+                    let byte = meta.query_fixed(q_unit, Rotation::cur())
+                        * meta.query_advice(column, Rotation::cur());
+                    vec![(byte, u8_table.table_exprs(meta)[0].clone())]
+                });
+            }
+        }
+        for column in carries_lo.iter().chain(carries_hi.iter()) {
+            meta.lookup_any("carry range check", |meta| {
+                let cell = meta.query_fixed(q_unit, Rotation::cur())
+                    * meta.query_advice(*column, Rotation::cur());
+                vec![(cell, u8_table.table_exprs(meta)[0].clone())]
+            });
+        }
+
+        // The data of the unit and the state of the exponent are the same in
+        // all rows of a unit, except is_last which is only set in the first row
+        meta.create_gate("carry unit data", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            for column in [
+                unit_is_event,
+                is_first,
+                is_square,
+                bit,
+                is_mod_zero,
+                byte_acc,
+                bit_idx,
+                bit_idx_inv,
+                exp_rlc,
+                n_bytes,
+                head_left,
+                head_left_inv,
+                is_head,
+                seen,
+                bit_len,
+            ] {
+                cb.require_equal(
+                    "unit data stays the same",
+                    meta.query_advice(column, Rotation::cur()),
+                    meta.query_advice(column, Rotation::prev()),
+                );
+            }
+            cb.require_zero(
+                "is_last is only set in the first row",
+                meta.query_advice(is_last, Rotation::cur()),
+            );
+            cb.gate(
+                meta.query_fixed(q_unit, Rotation::cur())
+                    - meta.query_fixed(q_rows[0], Rotation::cur()),
+            )
+        });
+
+        // is_mod_zero = (sum of the limbs of the modulus == 0)
+        meta.create_gate("is_mod_zero", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let prev_mod_sum = not::expr(meta.query_fixed(q_rows[0], Rotation::cur()))
+                * meta.query_advice(mod_sum, Rotation::prev());
+            let mod_sum = meta.query_advice(mod_sum, Rotation::cur());
+            cb.require_equal(
+                "mod_sum",
+                mod_sum.clone(),
+                prev_mod_sum + meta.query_advice(modulus.value, Rotation::cur()),
+            );
+            let is_mod_zero = meta.query_advice(is_mod_zero, Rotation::cur());
+            let q_last_event_row = meta.query_fixed(q_last_row, Rotation::cur())
+                * meta.query_advice(unit_is_event, Rotation::cur());
+            cb.require_equal(
+                "is_mod_zero",
+                q_last_event_row.clone() * is_mod_zero.clone(),
+                q_last_event_row.clone()
+                    * not::expr(mod_sum.clone() * meta.query_advice(mod_sum_inv, Rotation::cur())),
+            );
+            cb.require_zero(
+                "is_mod_zero only when mod_sum is zero",
+                q_last_event_row * mod_sum * is_mod_zero,
+            );
+            cb.gate(meta.query_fixed(q_unit, Rotation::cur()))
+        });
+
+        // Row k checks that the limbs k and NUM_LIMBS + k of
+        //   a * b - quot * modulus - rem
+        // are zero, with carries from limb to limb, and subtracts the limb k
+        // of rem + 1 from the modulus with borrows.
+        for (k, q_row) in q_rows.iter().enumerate() {
+            meta.create_gate("modular multiplication", |meta| {
+                let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+                let is_mod_zero = meta.query_advice(is_mod_zero, Rotation::cur());
+                // The limb idx of an operand is in row idx of the unit
+                let rot = |idx: usize| Rotation(idx as i32 - k as i32);
+                // A zero modulus is replaced by 1
+                let query_modulus = |meta: &mut VirtualCells<F>, idx: usize| {
+                    meta.query_advice(modulus.value, rot(idx))
+                        + if idx == 0 {
+                            is_mod_zero.clone()
+                        } else {
+                            0.expr()
+                        }
+                };
+
+                let mut lo = 0.expr();
+                let mut hi = 0.expr();
+                for i in 0..NUM_LIMBS {
+                    // a_i * b_j - quot_i * modulus_j goes to the limb i + j of the product
+                    let j = if i <= k { k - i } else { NUM_LIMBS + k - i };
+                    let term = meta.query_advice(a, rot(i)) * meta.query_advice(b, rot(j))
+                        - meta.query_advice(quot.value, rot(i)) * query_modulus(meta, j);
+                    if i <= k {
+                        lo = lo + term;
+                    } else {
+                        hi = hi + term;
+                    }
+                }
+
+                let prev_carry_lo = if k == 0 {
+                    0.expr()
+                } else {
+                    query_carry(meta, &carries_lo, -1)
+                };
+                cb.require_equal(
+                    "limb k of a * b - quot * modulus - rem",
+                    lo - meta.query_advice(rem.value, Rotation::cur()) + prev_carry_lo,
+                    query_carry(meta, &carries_lo, 0) * two_64.clone(),
+                );
+                // The carry of the limb NUM_LIMBS - 1 is in the last row
+                let prev_carry_hi = if k == 0 {
+                    query_carry(meta, &carries_lo, last_row)
+                } else {
+                    query_carry(meta, &carries_hi, -1)
+                };
+                let carry_hi = query_carry(meta, &carries_hi, 0);
+                cb.require_equal(
+                    "limb NUM_LIMBS + k of a * b - quot * modulus",
+                    hi + prev_carry_hi,
+                    carry_hi.clone() * two_64.clone(),
+                );
+
+                // diff = modulus - rem - 1 without a final borrow, i.e. rem < modulus
+                let prev_borrow = if k == 0 {
+                    0.expr()
+                } else {
+                    meta.query_advice(borrow, Rotation::prev())
+                };
+                let borrow = meta.query_advice(borrow, Rotation::cur());
+                cb.require_equal(
+                    "diff = modulus - rem - 1",
+                    meta.query_advice(diff.value, Rotation::cur()),
+                    query_modulus(meta, k)
+                        - meta.query_advice(rem.value, Rotation::cur())
+                        - (k == 0).expr()
+                        - prev_borrow
+                        + borrow.clone() * two_64.clone(),
+                );
+
+                if k == NUM_LIMBS - 1 {
+                    cb.require_zero("no carry left", carry_hi);
+                    cb.require_zero("no borrow left", borrow);
+                }
+                cb.gate(
+                    meta.query_fixed(*q_row, Rotation::cur())
+                        * meta.query_advice(unit_is_event, Rotation::cur()),
+                )
+            });
+        }
+
+        // The first unit computes 1 * 1, and every other unit uses the result
+        // of the previous one: squarings multiply it by itself, other units by
+        // the base or 1, depending on the bit of the exponent.
+        meta.create_gate("operands", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            // The limbs of the constant 1
+            let one = meta.query_fixed(q_rows[0], Rotation::cur());
+            let is_event = meta.query_advice(unit_is_event, Rotation::cur());
+            let is_first = meta.query_advice(is_first, Rotation::cur());
+            let is_square = meta.query_advice(is_square, Rotation::cur());
+            let a = meta.query_advice(a, Rotation::cur());
+            let b = meta.query_advice(b, Rotation::cur());
+            cb.require_zero(
+                "b = a when squaring",
+                is_square.clone() * (b.clone() - a.clone()),
+            );
+            cb.require_zero(
+                "b = base or 1 when multiplying",
+                (is_event.clone() - is_square)
+                    * (b - select::expr(
+                        meta.query_advice(bit, Rotation::cur()),
+                        meta.query_advice(base.value, Rotation::cur()),
+                        one.clone(),
+                    )),
+            );
+            cb.require_zero(
+                "a = 1 in the first unit",
+                is_first.clone() * (a.clone() - one),
+            );
+            let not_first = is_event - is_first;
+            cb.require_zero(
+                "a = rem of the previous unit",
+                not_first.clone() * (a - meta.query_advice(rem.value, Rotation(-unit_len))),
+            );
+            for limb in [modulus, base] {
+                for (cur, prev) in limb
+                    .query_bytes(meta, 0)
+                    .into_iter()
+                    .zip(limb.query_bytes(meta, -unit_len))
+                {
+                    cb.require_zero(
+                        "the modulus and the base stay the same",
+                        not_first.clone() * (cur - prev),
+                    );
+                }
+            }
+            cb.gate(meta.query_fixed(q_unit, Rotation::cur()))
+        });
+
+        // An event starts with a squaring of 1, followed by multiplications
+        // by the bits of the exponent, each preceded by a squaring except the
+        // first one. Events are followed by empty units only.
+        meta.create_gate("unit transition", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let not_first_row = not::expr(meta.query_fixed(q_unit_first, Rotation::cur()));
+            let is_event = meta.query_advice(unit_is_event, Rotation::cur());
+            let is_first = meta.query_advice(is_first, Rotation::cur());
+            let is_square = meta.query_advice(is_square, Rotation::cur());
+            let is_last = meta.query_advice(is_last, Rotation::cur());
+            let prev_is_event =
+                not_first_row.clone() * meta.query_advice(unit_is_event, Rotation::prev());
+            // is_last is only set in the first row of a unit
+            let prev_is_last = meta.query_advice(is_last, Rotation(-unit_len));
+            let prev_is_square = meta.query_advice(is_square, Rotation::prev());
+            let continues = prev_is_event * not::expr(prev_is_last);
+            cb.require_equal(
+                "is_first",
+                is_first.clone(),
+                is_event.clone() * not::expr(continues.clone()),
+            );
+            cb.require_zero(
+                "an event continues until its last unit",
+                continues * not::expr(is_event.clone()),
+            );
+            cb.require_zero(
+                "no event after an empty unit",
+                not_first_row
+                    * not::expr(meta.query_advice(unit_is_event, Rotation::prev()))
+                    * is_event.clone(),
+            );
+            cb.require_zero(
+                "squarings and multiplications alternate",
+                is_event
+                    * (is_square.clone()
+                        - is_first.clone()
+                        - not::expr(is_first.clone()) * not::expr(prev_is_square)),
+            );
+            cb.require_zero(
+                "the last unit is the first one or a multiplication",
+                is_last.clone() * is_square.clone() * not::expr(is_first),
+            );
+            cb.require_zero(
+                "no bit when squaring",
+                is_square * meta.query_advice(bit, Rotation::cur()),
+            );
+            cb.require_zero(
+                "the exponent ends with a full byte",
+                is_last * meta.query_advice(bit_idx, Rotation::cur()),
+            );
+            cb.gate(meta.query_fixed(q_rows[0], Rotation::cur()))
+        });
+
+        meta.create_gate("the last event ends in the circuit", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            cb.require_zero(
+                "is_last",
+                meta.query_advice(unit_is_event, Rotation::cur())
+                    * not::expr(meta.query_advice(is_last, Rotation::cur())),
+            );
+            cb.gate(meta.query_fixed(q_unit_last, Rotation::cur()))
+        });
+
+        // Every multiplication reads the next bit of the exponent, to
+        // accumulate its bytes, count them and find the bit length of its
+        // first NUM_EXP_HEAD_BITS bits.
+        meta.create_gate("exponent", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let query = |meta: &mut VirtualCells<F>, column: Column<Advice>| {
+                meta.query_advice(column, Rotation::cur())
+            };
+            let query_prev = |meta: &mut VirtualCells<F>, column: Column<Advice>| {
+                meta.query_advice(column, Rotation::prev())
+            };
+            let is_event = query(meta, unit_is_event);
+            let is_first = query(meta, is_first);
+            let is_square = query(meta, is_square);
+            let bit = query(meta, bit);
+            let state = [
+                byte_acc, bit_idx, exp_rlc, n_bytes, head_left, seen, bit_len,
+            ];
+
+            cb.condition(is_first.clone(), |cb| {
+                for column in state {
+                    let initial = if column == head_left {
+                        NUM_EXP_HEAD_BITS.expr()
+                    } else {
+                        0.expr()
+                    };
+                    cb.require_equal("initial state", query(meta, column), initial);
+                }
+            });
+            cb.condition(is_square.clone() * not::expr(is_first), |cb| {
+                for column in state {
+                    cb.require_equal(
+                        "squarings keep the state",
+                        query(meta, column),
+                        query_prev(meta, column),
+                    );
+                }
+            });
+            cb.condition(is_event - is_square, |cb| {
+                let prev_bit_idx = query_prev(meta, bit_idx);
+                let prev_byte_acc = query_prev(meta, byte_acc);
+                let prev_head_left = query_prev(meta, head_left);
+                let prev_seen = query_prev(meta, seen);
+
+                let bit_idx_diff = prev_bit_idx.clone() - (NUM_BITS_PER_BYTE - 1).expr();
+                let is_byte_end = not::expr(bit_idx_diff.clone() * query(meta, bit_idx_inv));
+                cb.require_zero(
+                    "is_byte_end only at the last bit of a byte",
+                    bit_idx_diff * is_byte_end.clone(),
+                );
+                let full = prev_byte_acc * 2.expr() + bit.clone();
+                cb.require_equal(
+                    "byte_acc",
+                    query(meta, byte_acc),
+                    not::expr(is_byte_end.clone()) * full.clone(),
+                );
+                cb.require_equal(
+                    "bit_idx",
+                    query(meta, bit_idx),
+                    not::expr(is_byte_end.clone()) * (prev_bit_idx + 1.expr()),
+                );
+                let prev_exp_rlc = query_prev(meta, exp_rlc);
+                cb.require_equal(
+                    "exp_rlc",
+                    query(meta, exp_rlc),
+                    select::expr(
+                        is_byte_end.clone(),
+                        prev_exp_rlc.clone() * r.clone() + full,
+                        prev_exp_rlc,
+                    ),
+                );
+                cb.require_equal(
+                    "n_bytes",
+                    query(meta, n_bytes),
+                    query_prev(meta, n_bytes) + is_byte_end,
+                );
+
+                let is_head = query(meta, is_head);
+                cb.require_equal(
+                    "is_head = head_left != 0",
+                    is_head.clone(),
+                    prev_head_left.clone() * query(meta, head_left_inv),
+                );
+                cb.require_zero(
+                    "is_head when head_left != 0",
+                    prev_head_left.clone() * not::expr(is_head.clone()),
+                );
+                cb.require_equal(
+                    "head_left",
+                    query(meta, head_left),
+                    prev_head_left - is_head.clone(),
+                );
+                let seen = query(meta, seen);
+                cb.require_equal(
+                    "seen",
+                    seen.clone(),
+                    prev_seen.clone() + is_head.clone() * bit * not::expr(prev_seen),
+                );
+                let prev_bit_len = query_prev(meta, bit_len);
+                cb.require_equal(
+                    "bit_len",
+                    query(meta, bit_len),
+                    select::expr(
+                        is_head,
+                        seen * (prev_bit_len.clone() + 1.expr()),
+                        prev_bit_len,
+                    ),
+                );
+            });
+            cb.gate(meta.query_fixed(q_rows[0], Rotation::cur()))
+        });
+
+        // The RLCs of the 128-byte big-endian base, modulus and rem, accumulated
+        // from the last limb to the first one
+        meta.create_gate("limb RLCs", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let r_limb = (0..NUM_BYTES_PER_LIMB).fold(1.expr(), |acc, _| acc * r.clone());
+            let not_last_row = not::expr(meta.query_fixed(q_last_row, Rotation::cur()));
+            for (rlc, limb) in [(base_rlc, base), (mod_rlc, modulus), (rem_rlc, rem)] {
+                let limb_rlc = limb
+                    .query_bytes(meta, 0)
+                    .into_iter()
+                    .rev()
+                    .fold(0.expr(), |acc, byte| acc * r.clone() + byte);
+                cb.require_equal(
+                    "rlc",
+                    meta.query_advice(rlc, Rotation::cur()),
+                    not_last_row.clone()
+                        * meta.query_advice(rlc, Rotation::next())
+                        * r_limb.clone()
+                        + limb_rlc,
+                );
+            }
+            cb.gate(meta.query_fixed(q_unit, Rotation::cur()))
+        });
+
+        // Every input is computed by a sequence of units
+        meta.lookup_any("modexp result", |meta| {
+            let is_enabled = meta.query_advice(modexp_table.is_enabled, Rotation::cur());
+            let input = [
+                1.expr(),
+                meta.query_advice(part_rlcs[0], Rotation::cur()),
+                meta.query_advice(part_rlcs[1], Rotation::cur()),
+                meta.query_advice(part_rlcs[2], Rotation::cur()),
+                meta.query_advice(modexp_table.exp_len, Rotation::cur()),
+                meta.query_advice(modexp_table.exp_msb, Rotation::cur()),
+                meta.query_advice(modexp_table.output_rlc, Rotation::cur()),
+            ];
+            // is_last is only set in the first row of the last unit
+            let is_last = meta.query_advice(is_last, Rotation::cur());
+            let table = [
+                1.expr(),
+                meta.query_advice(base_rlc, Rotation::cur()),
+                meta.query_advice(exp_rlc, Rotation::cur()),
+                meta.query_advice(mod_rlc, Rotation::cur()),
+                meta.query_advice(n_bytes, Rotation::cur()),
+                meta.query_advice(bit_len, Rotation::cur())
+                    - meta.query_advice(seen, Rotation::cur()),
+                meta.query_advice(rem_rlc, Rotation::cur()),
+            ];
+            input
+                .into_iter()
+                .zip(table)
+                .map(|(input, table)| (is_enabled.clone() * input, is_last.clone() * table))
+                .collect()
+        });
+
+        Self {
+            q_enable,
+            q_input,
+            q_input_first,
+            q_input_last,
+            q_header,
+            q_lens,
+            q_unit,
+            q_unit_first,
+            q_unit_last,
+            q_rows,
+            byte,
+            input_is_event,
+            lefts,
+            left_invs,
+            left_nonzeros,
+            is_parts,
+            part_rlcs,
+            a,
+            b,
+            quot,
+            rem,
+            modulus,
+            base,
+            diff,
+            borrow,
+            carries_lo,
+            carries_hi,
+            mod_sum,
+            mod_sum_inv,
+            is_mod_zero,
+            unit_is_event,
+            is_first,
+            is_last,
+            is_square,
+            bit,
+            byte_acc,
+            bit_idx,
+            bit_idx_inv,
+            exp_rlc,
+            n_bytes,
+            head_left,
+            head_left_inv,
+            is_head,
+            seen,
+            bit_len,
+            base_rlc,
+            mod_rlc,
+            rem_rlc,
+            modexp_table,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: Field> ModExpCircuitConfig<F> {
+    pub(crate) fn assign(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        witness: &ModExpWitness<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "assign modexp rows",
+            |mut region| {
+                let num_rows = witness.inputs.len().max(witness.units.len());
+                for offset in 0..num_rows {
+                    self.set_fixed_row(
+                        &mut region,
+                        offset,
+                        witness.inputs.len(),
+                        witness.units.len(),
+                    )?;
+                    self.set_input_row(
+                        &mut region,
+                        offset,
+                        witness.inputs.get(offset).unwrap_or(&InputRow::empty()),
+                    )?;
+                    self.set_unit_row(
+                        &mut region,
+                        offset,
+                        witness.units.get(offset).unwrap_or(&UnitRow::empty()),
+                    )?;
+                }
+                self.modexp_table.annotate_columns_in_region(&mut region);
+                self.annotate_circuit(&mut region);
+                Ok(())
+            },
+        )
+    }
+
+    fn set_fixed_row(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        num_input_rows: usize,
+        num_unit_rows: usize,
+    ) -> Result<(), Error> {
+        let is_input = offset < num_input_rows;
+        let input_idx = offset % NUM_ROWS_PER_INPUT;
+        let is_unit = offset < num_unit_rows;
+        let row_idx = offset % NUM_ROWS_PER_UNIT;
+
+        let mut selectors = vec![
+            ("q_enable", self.q_enable, true),
+            ("q_input", self.q_input, is_input),
+            (
+                "q_input_first",
+                self.q_input_first,
+                is_input && input_idx == 0,
+            ),
+            (
+                "q_input_last",
+                self.q_input_last,
+                is_input && input_idx == NUM_ROWS_PER_INPUT - 1,
+            ),
+            (
+                "q_header",
+                self.q_header,
+                is_input && input_idx < HEADER_LEN,
+            ),
+            ("q_unit", self.q_unit, is_unit),
+            ("q_unit_first", self.q_unit_first, is_unit && offset == 0),
+            (
+                "q_unit_last",
+                self.q_unit_last,
+                is_unit && offset == num_unit_rows - NUM_ROWS_PER_UNIT,
+            ),
+        ];
+        // The lengths are 32-byte big-endian words
+        for (idx, q_len) in self.q_lens.iter().enumerate() {
+            selectors.push(("q_len", *q_len, is_input && input_idx == 32 * idx + 31));
+        }
+        for (idx, q_row) in self.q_rows.iter().enumerate() {
+            selectors.push(("q_row", *q_row, is_unit && row_idx == idx));
+        }
+        for (name, column, value) in selectors {
+            region.assign_fixed(
+                || format!("assign {} {}", name, offset),
+                column,
+                offset,
+                || Value::known(F::from(value as u64)),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn set_input_row(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        row: &InputRow<F>,
+    ) -> Result<(), Error> {
+        let mut values = vec![
+            ("byte", self.byte, F::from(row.byte as u64)),
+            (
+                "input_is_event",
+                self.input_is_event,
+                F::from(row.is_event as u64),
+            ),
+        ];
+        for (idx, left) in row.lefts.iter().enumerate() {
+            values.push(("left", self.lefts[idx], F::from(*left)));
+            values.push((
+                "left_inv",
+                self.left_invs[idx],
+                F::from(*left).invert().unwrap_or(F::ZERO),
+            ));
+            values.push((
+                "left_nonzero",
+                self.left_nonzeros[idx],
+                F::from((*left != 0) as u64),
+            ));
+        }
+        for (column, is_part) in self.is_parts.iter().zip(row.is_parts) {
+            values.push(("is_part", *column, F::from(is_part as u64)));
+        }
+        for (name, column, value) in values {
+            region.assign_advice(
+                || format!("assign {} {}", name, offset),
+                column,
+                offset,
+                || Value::known(value),
+            )?;
+        }
+        for (column, part_rlc) in self.part_rlcs.iter().zip(row.part_rlcs) {
+            region.assign_advice(
+                || format!("assign part_rlc {}", offset),
+                *column,
+                offset,
+                || part_rlc,
+            )?;
+        }
+
+        self.modexp_table.assign_row(
+            region,
+            offset,
+            [
+                Value::known(F::from(row.is_enabled as u64)),
+                row.input_rlc,
+                Value::known(F::from(row.input_len)),
+                Value::known(F::from(row.lens[0])),
+                Value::known(F::from(row.lens[1])),
+                Value::known(F::from(row.lens[2])),
+                Value::known(F::from(row.exp_msb)),
+                row.output_rlc,
+            ],
+        )
+    }
+
+    fn set_unit_row(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        row: &UnitRow<F>,
+    ) -> Result<(), Error> {
+        for (name, limb, value) in [
+            ("quot", self.quot, row.quot),
+            ("rem", self.rem, row.rem),
+            ("modulus", self.modulus, row.modulus),
+            ("base", self.base, row.base),
+            ("diff", self.diff, row.diff),
+        ] {
+            limb.assign(region, offset, name, value)?;
+        }
+
+        let state = &row.state;
+        let mod_sum = F::from_u128(row.mod_sum);
+        let mut values = vec![
+            ("a", self.a, F::from(row.a)),
+            ("b", self.b, F::from(row.b)),
+            ("borrow", self.borrow, F::from(row.borrow as u64)),
+            ("mod_sum", self.mod_sum, mod_sum),
+            (
+                "mod_sum_inv",
+                self.mod_sum_inv,
+                mod_sum.invert().unwrap_or(F::ZERO),
+            ),
+            (
+                "is_mod_zero",
+                self.is_mod_zero,
+                F::from(row.is_mod_zero as u64),
+            ),
+            (
+                "unit_is_event",
+                self.unit_is_event,
+                F::from(row.is_event as u64),
+            ),
+            ("is_first", self.is_first, F::from(row.is_first as u64)),
+            ("is_last", self.is_last, F::from(row.is_last as u64)),
+            ("is_square", self.is_square, F::from(row.is_square as u64)),
+            ("bit", self.bit, F::from(row.bit as u64)),
+            ("byte_acc", self.byte_acc, F::from(state.byte_acc)),
+            ("bit_idx", self.bit_idx, F::from(state.bit_idx)),
+            ("bit_idx_inv", self.bit_idx_inv, state.bit_idx_inv),
+            ("n_bytes", self.n_bytes, F::from(state.n_bytes)),
+            ("head_left", self.head_left, F::from(state.head_left)),
+            ("head_left_inv", self.head_left_inv, state.head_left_inv),
+            ("is_head", self.is_head, F::from(state.is_head as u64)),
+            ("seen", self.seen, F::from(state.seen as u64)),
+            ("bit_len", self.bit_len, F::from(state.bit_len)),
+        ];
+        for (carries, carry) in [
+            (&self.carries_lo, row.carry_lo),
+            (&self.carries_hi, row.carry_hi),
+        ] {
+            for (idx, column) in carries.iter().enumerate() {
+                values.push((
+                    "carry",
+                    *column,
+                    F::from((carry >> (8 * idx)) as u64 & 0xff),
+                ));
+            }
+        }
+        for (name, column, value) in values {
+            region.assign_advice(
+                || format!("assign {} {}", name, offset),
+                column,
+                offset,
+                || Value::known(value),
+            )?;
+        }
+        for (name, column, value) in [
+            ("exp_rlc", self.exp_rlc, state.exp_rlc),
+            ("base_rlc", self.base_rlc, row.base_rlc),
+            ("mod_rlc", self.mod_rlc, row.mod_rlc),
+            ("rem_rlc", self.rem_rlc, row.rem_rlc),
+        ] {
+            region.assign_advice(
+                || format!("assign {} {}", name, offset),
+                column,
+                offset,
+                || value,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn annotate_circuit(&self, region: &mut Region<F>) {
+        region.name_column(|| "MODEXP_q_enable", self.q_enable);
+        region.name_column(|| "MODEXP_q_input", self.q_input);
+        region.name_column(|| "MODEXP_q_unit", self.q_unit);
+        region.name_column(|| "MODEXP_byte", self.byte);
+        region.name_column(|| "MODEXP_input_is_event", self.input_is_event);
+        region.name_column(|| "MODEXP_a", self.a);
+        region.name_column(|| "MODEXP_b", self.b);
+        region.name_column(|| "MODEXP_quot", self.quot.value);
+        region.name_column(|| "MODEXP_rem", self.rem.value);
+        region.name_column(|| "MODEXP_modulus", self.modulus.value);
+        region.name_column(|| "MODEXP_base", self.base.value);
+        region.name_column(|| "MODEXP_diff", self.diff.value);
+        region.name_column(|| "MODEXP_is_mod_zero", self.is_mod_zero);
+        region.name_column(|| "MODEXP_unit_is_event", self.unit_is_event);
+        region.name_column(|| "MODEXP_is_first", self.is_first);
+        region.name_column(|| "MODEXP_is_last", self.is_last);
+        region.name_column(|| "MODEXP_is_square", self.is_square);
+        region.name_column(|| "MODEXP_bit", self.bit);
+        region.name_column(|| "MODEXP_exp_rlc", self.exp_rlc);
+        region.name_column(|| "MODEXP_n_bytes", self.n_bytes);
+        region.name_column(|| "MODEXP_bit_len", self.bit_len);
+    }
+}
+
+/// ModExpCircuit
+#[derive(Default, Clone, Debug)]
+pub struct ModExpCircuit<F: Field> {
+    events: Vec<BigModExp>,
+    num_rows: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> SubCircuit<F> for ModExpCircuit<F> {
+    type Config = ModExpCircuitConfig<F>;
+
+    fn unusable_rows() -> usize {
+        // The limbs of the operands are queried at 31 distinct rotations, so
+        // returns (31 + 3) unusable rows.
+        34
+    }
+
+    /// The `chunk.fixed_param.max_vertical_circuit_rows` parameter, when set,
+    /// pads the circuit to a fixed number of input regions and units,
+    /// independently of the ones required by the events.
+    fn new_from_block(block: &Block<F>, chunk: &Chunk<F>) -> Self {
+        Self::new(
+            chunk.fixed_param.max_vertical_circuit_rows,
+            block.precompile_events.get_modexp_events(),
+        )
+    }
+
+    /// Return the minimum number of rows required to prove the block
+    fn min_num_rows_block(block: &Block<F>, chunk: &Chunk<F>) -> (usize, usize) {
+        let events = block.precompile_events.get_modexp_events();
+        let input_rows = events.len() * NUM_ROWS_PER_INPUT;
+        let unit_rows = events
+            .iter()
+            .map(|event| num_units(event.exponent.len()) * NUM_ROWS_PER_UNIT)
+            .sum();
+        (
+            input_rows.max(unit_rows),
+            chunk.fixed_param.max_vertical_circuit_rows,
+        )
+    }
+
+    /// Make the assignments to the ModExpCircuit
+    fn synthesize_sub(
+        &self,
+        config: &Self::Config,
+        challenges: &Challenges<Value<F>>,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let witness = self.generate_witness(*challenges)?;
+        config.assign(layouter, &witness)
+    }
+}
+
+impl<F: Field> ModExpCircuit<F> {
+    /// Creates a new circuit instance
+    pub fn new(num_rows: usize, events: Vec<BigModExp>) -> Self {
+        ModExpCircuit {
+            events,
+            num_rows,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The number of input regions and units, i.e. modular multiplications,
+    /// that fit in this circuit
+    pub fn capacity(&self) -> Option<(usize, usize)> {
+        if self.num_rows > 0 {
+            let num_rows = self.num_rows.saturating_sub(Self::unusable_rows());
+            Some((num_rows / NUM_ROWS_PER_INPUT, num_rows / NUM_ROWS_PER_UNIT))
+        } else {
+            None
+        }
+    }
+
+    /// Sets the witness using the MODEXP events
+    pub(crate) fn generate_witness(
+        &self,
+        challenges: Challenges<Value<F>>,
+    ) -> Result<ModExpWitness<F>, Error> {
+        multi_modexp(self.events.as_slice(), challenges, self.capacity())
+    }
+}
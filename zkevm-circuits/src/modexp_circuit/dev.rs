@@ -0,0 +1,51 @@
+pub use super::ModExpCircuit;
+
+use crate::{
+    modexp_circuit::{ModExpCircuitConfig, ModExpCircuitConfigArgs},
+    table::{ModExpTable, UXTable},
+    util::{Challenges, SubCircuit, SubCircuitConfig},
+};
+use eth_types::Field;
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner},
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+impl<F: Field> Circuit<F> for ModExpCircuit<F> {
+    type Config = (ModExpCircuitConfig<F>, Challenges, UXTable<8>);
+    type FloorPlanner = SimpleFloorPlanner;
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let modexp_table = ModExpTable::construct(meta);
+        let u8_table = UXTable::construct(meta);
+        let challenges = Challenges::construct(meta);
+
+        let config = {
+            let challenges = challenges.exprs(meta);
+            ModExpCircuitConfig::new(
+                meta,
+                ModExpCircuitConfigArgs {
+                    modexp_table,
+                    u8_table,
+                    challenges,
+                },
+            )
+        };
+        (config, challenges, u8_table)
+    }
+
+    fn synthesize(
+        &self,
+        (config, challenges, u8_table): Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let challenges = challenges.values(&mut layouter);
+        u8_table.load(&mut layouter)?;
+        self.synthesize_sub(&config, &challenges, &mut layouter)
+    }
+}
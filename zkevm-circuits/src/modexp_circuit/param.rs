@@ -0,0 +1,23 @@
+pub(crate) use bus_mapping::precompile::{
+    MODEXP_HEADER_LEN as HEADER_LEN, MODEXP_INPUT_LIMIT as INPUT_LIMIT,
+    MODEXP_SIZE_LIMIT as SIZE_LIMIT,
+};
+
+pub(crate) const NUM_BITS_PER_BYTE: usize = 8;
+pub(crate) const NUM_BYTES_PER_LIMB: usize = 8;
+pub(crate) const NUM_BITS_PER_LIMB: usize = NUM_BYTES_PER_LIMB * NUM_BITS_PER_BYTE;
+/// The base, exponent and modulus are split into little-endian 64-bit limbs
+pub(crate) const NUM_LIMBS: usize = SIZE_LIMIT / NUM_BYTES_PER_LIMB;
+/// A modular multiplication is computed in a unit with a row per limb
+pub(crate) const NUM_ROWS_PER_UNIT: usize = NUM_LIMBS;
+/// An input is parsed in a region with a row per input byte
+pub(crate) const NUM_ROWS_PER_INPUT: usize = INPUT_LIMIT;
+pub(crate) const MAX_DEGREE: usize = 5;
+
+/// Only the first 32 bytes of the exponent count for the gas cost
+pub(crate) const NUM_EXP_HEAD_BITS: usize = 32 * NUM_BITS_PER_BYTE;
+
+/// The carries of the limb products are signed and below 2^70 in absolute value.
+/// They are stored with an offset, in `NUM_CARRY_CELLS` bytes.
+pub(crate) const NUM_CARRY_CELLS: usize = 10;
+pub(crate) const CARRY_OFFSET_BITS: usize = 72;
@@ -0,0 +1,202 @@
+use super::*;
+use crate::{evm_circuit::util::rlc, util::unusable_rows};
+use bus_mapping::precompile::{parse_modexp_input, ModExpAuxData};
+use halo2_proofs::{
+    dev::{CellValue, MockProver},
+    halo2curves::bn256::Fr,
+    plonk::Circuit,
+};
+use itertools::izip;
+use log::error;
+use num::{BigUint, Zero};
+
+/// An event from a MODEXP input, computing its output like the precompile
+fn event_from_input(input: Vec<u8>) -> BigModExp {
+    let [base, exponent, modulus] = parse_modexp_input(&input).unwrap();
+    let modulus_value = BigUint::from_bytes_be(&modulus);
+    let mut output = vec![0u8; modulus.len()];
+    if !modulus_value.is_zero() {
+        let value = BigUint::from_bytes_be(&base)
+            .modpow(&BigUint::from_bytes_be(&exponent), &modulus_value);
+        if !value.is_zero() {
+            let bytes = value.to_bytes_be();
+            output[modulus.len() - bytes.len()..].copy_from_slice(&bytes);
+        }
+    }
+    BigModExp {
+        input,
+        base,
+        exponent,
+        modulus,
+        output,
+    }
+}
+
+fn header(lens: [usize; 3]) -> Vec<u8> {
+    lens.iter()
+        .flat_map(|len| {
+            let mut word = [0u8; 32];
+            word[24..].copy_from_slice(&(*len as u64).to_be_bytes());
+            word
+        })
+        .collect()
+}
+
+fn event(base: &[u8], exponent: &[u8], modulus: &[u8]) -> BigModExp {
+    let mut input = header([base.len(), exponent.len(), modulus.len()]);
+    input.extend(base.iter().chain(exponent).chain(modulus));
+    event_from_input(input)
+}
+
+fn events() -> Vec<BigModExp> {
+    vec![
+        // 3^5 % 7
+        event(&[3], &[5], &[7]),
+        // Leading zeros
+        event(&[0, 0, 3], &[0, 5], &[0, 0, 0, 7]),
+        // Empty exponent
+        event(&[3], &[], &[7]),
+        // Zero modulus
+        event(&[3], &[5], &[0, 0]),
+        // Empty modulus
+        event(&[3], &[5], &[]),
+        // Modulus 1
+        event(&[3], &[5], &[1]),
+        // 1024-bit operands
+        event(&[0xfe; SIZE_LIMIT], &[0x01, 0x01], &[0xff; SIZE_LIMIT]),
+        event(&[0xff; SIZE_LIMIT], &[0xff], &[0xfd; SIZE_LIMIT]),
+        // Missing input bytes are zeros
+        event_from_input([header([2, 1, 2]), vec![0x01, 0x02, 0x03, 0x01]].concat()),
+        // Extra input bytes are ignored
+        event_from_input([header([1, 1, 1]), vec![0x02, 0x03, 0x05, 0xff, 0xff]].concat()),
+    ]
+}
+
+#[test]
+fn modexp_circuit_unusable_rows() {
+    assert_eq!(
+        ModExpCircuit::<Fr>::unusable_rows(),
+        unusable_rows::<Fr, ModExpCircuit::<Fr>>(()),
+    )
+}
+
+fn verify<F: Field>(k: u32, events: Vec<BigModExp>, success: bool) {
+    let circuit = ModExpCircuit::new(2usize.pow(k), events.clone());
+    let prover = MockProver::<F>::run(k, &circuit, vec![]).unwrap();
+    let (config, challenges, _) = ModExpCircuit::configure(&mut ConstraintSystem::<F>::default());
+    let input_challenge = prover.get_challenge(challenges.keccak_input());
+
+    // Check constraints.
+    let verify_result = prover.verify();
+    if verify_result.is_ok() != success {
+        if let Some(errors) = verify_result.err() {
+            for error in errors.iter() {
+                error!("{}", error);
+            }
+        }
+        panic!();
+    }
+
+    // Extract the content of the lookup table.
+    let lookup_table = {
+        let table = &config.modexp_table;
+        let is_enabled = prover.advice_values(table.is_enabled);
+        let input_rlc = prover.advice_values(table.input_rlc);
+        let input_len = prover.advice_values(table.input_len);
+        let exp_msb = prover.advice_values(table.exp_msb);
+        let output_rlc = prover.advice_values(table.output_rlc);
+
+        // Keep the rows that are supposed to contain results.
+        izip!(is_enabled, input_rlc, input_len, exp_msb, output_rlc)
+            .filter_map(|(enabled, input_rlc, input_len, exp_msb, output_rlc)| {
+                assigned_non_zero(enabled).then(|| {
+                    (
+                        unwrap(input_rlc),
+                        unwrap(input_len),
+                        unwrap(exp_msb),
+                        unwrap(output_rlc),
+                    )
+                })
+            })
+            .collect::<Vec<(F, F, F, F)>>()
+    };
+
+    let rlc = |bytes: &[u8]| input_challenge.map(|random| rlc::value(bytes.iter().rev(), random));
+
+    // Check that all the outputs are there.
+    assert_eq!(lookup_table.len(), events.len());
+    for (event, row) in events.iter().zip(lookup_table.iter()) {
+        rlc(&event.input).assert_if_known(|value| *value == row.0);
+        assert_eq!(row.1, F::from(event.input.len() as u64));
+        let parts = [
+            event.base.clone(),
+            event.exponent.clone(),
+            event.modulus.clone(),
+        ];
+        let aux_data = ModExpAuxData::new(&parts, &event.input, &event.output, &[]);
+        assert_eq!(row.2, F::from(aux_data.exp_msb));
+        rlc(&event.output).assert_if_known(|value| *value == row.3);
+    }
+}
+
+fn assigned_non_zero<F: Field>(cv: &CellValue<F>) -> bool {
+    match *cv {
+        CellValue::Assigned(v) => !v.is_zero_vartime(),
+        _ => false,
+    }
+}
+
+fn unwrap<F: Field>(cv: &CellValue<F>) -> F {
+    match *cv {
+        CellValue::Assigned(f) => f,
+        _ => panic!("the cell should be assigned"),
+    }
+}
+
+#[test]
+fn modexp_circuit_simple() {
+    verify::<Fr>(13, events(), true);
+}
+
+#[test]
+fn modexp_circuit_long_exponent() {
+    // Only the bit length of the first 32 bytes of the exponent counts for the gas cost
+    let mut exponent = vec![0u8; 33];
+    exponent[1] = 0x11;
+    exponent[32] = 0x01;
+    verify::<Fr>(14, vec![event(&[2], &exponent, &[0x0d])], true);
+}
+
+#[test]
+fn modexp_circuit_wrong_output() {
+    let mut event = event(&[3], &[5], &[7]);
+    event.output = vec![4];
+    verify::<Fr>(12, vec![event], false);
+}
+
+#[test]
+fn modexp_circuit_oversize() {
+    let k = 12;
+    let event = event_from_input(header([1, 1, 1]));
+    let mut input = header([SIZE_LIMIT + 1, 1, 1]);
+    input.extend([0u8; SIZE_LIMIT + 3]);
+    let oversize = BigModExp { input, ..event };
+    let circuit = ModExpCircuit::<Fr>::new(2usize.pow(k), vec![oversize]);
+    assert!(MockProver::<Fr>::run(k, &circuit, vec![]).is_err());
+}
+
+#[test]
+fn modexp_circuit_variadic_size_check() {
+    let k = 13;
+    let num_rows = 2usize.pow(k);
+    // Empty
+    let circuit = ModExpCircuit::new(num_rows, vec![]);
+    let prover1 = MockProver::<Fr>::run(k, &circuit, vec![]).unwrap();
+
+    // Non-empty
+    let circuit = ModExpCircuit::new(num_rows, events());
+    let prover2 = MockProver::<Fr>::run(k, &circuit, vec![]).unwrap();
+
+    assert_eq!(prover1.fixed(), prover2.fixed());
+    assert_eq!(prover1.permutation(), prover2.permutation());
+}
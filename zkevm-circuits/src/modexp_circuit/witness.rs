@@ -0,0 +1,454 @@
+use super::param::*;
+use crate::util::Challenges;
+use bus_mapping::{
+    circuit_input_builder::BigModExp,
+    precompile::{parse_modexp_input, ModExpAuxData},
+};
+use eth_types::Field;
+use halo2_proofs::{circuit::Value, plonk::Error};
+use num::{BigInt, BigUint, One, ToPrimitive, Zero};
+
+/// A row of an input region, i.e. a byte of the input
+#[derive(Clone, Debug)]
+pub(crate) struct InputRow<F> {
+    pub(crate) byte: u8,
+    pub(crate) is_event: bool,
+    /// The numbers of bytes left in the input, base, exponent and modulus
+    pub(crate) lefts: [u64; 4],
+    pub(crate) is_parts: [bool; 3],
+    pub(crate) part_rlcs: [Value<F>; 3],
+    pub(crate) is_enabled: bool,
+    pub(crate) input_rlc: Value<F>,
+    pub(crate) input_len: u64,
+    pub(crate) lens: [u64; 3],
+    pub(crate) exp_msb: u64,
+    pub(crate) output_rlc: Value<F>,
+}
+
+impl<F: Field> InputRow<F> {
+    pub(crate) fn empty() -> Self {
+        Self {
+            byte: 0,
+            is_event: false,
+            lefts: [0; 4],
+            is_parts: [false; 3],
+            part_rlcs: [Value::known(F::ZERO); 3],
+            is_enabled: false,
+            input_rlc: Value::known(F::ZERO),
+            input_len: 0,
+            lens: [0; 3],
+            exp_msb: 0,
+            output_rlc: Value::known(F::ZERO),
+        }
+    }
+}
+
+/// The state of the decomposition of the exponent into bits, updated by every multiplication
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ExpState<F> {
+    pub(crate) byte_acc: u64,
+    pub(crate) bit_idx: u64,
+    pub(crate) bit_idx_inv: F,
+    pub(crate) exp_rlc: Value<F>,
+    pub(crate) n_bytes: u64,
+    pub(crate) head_left: u64,
+    pub(crate) head_left_inv: F,
+    pub(crate) is_head: bool,
+    pub(crate) seen: bool,
+    pub(crate) bit_len: u64,
+}
+
+impl<F: Field> ExpState<F> {
+    fn empty() -> Self {
+        Self {
+            byte_acc: 0,
+            bit_idx: 0,
+            bit_idx_inv: F::ZERO,
+            exp_rlc: Value::known(F::ZERO),
+            n_bytes: 0,
+            head_left: 0,
+            head_left_inv: F::ZERO,
+            is_head: false,
+            seen: false,
+            bit_len: 0,
+        }
+    }
+
+    fn initial() -> Self {
+        Self {
+            head_left: NUM_EXP_HEAD_BITS as u64,
+            ..Self::empty()
+        }
+    }
+
+    /// The state after the next bit of the exponent
+    fn next(&self, bit: bool, r: Value<F>) -> Self {
+        let is_byte_end = self.bit_idx == NUM_BITS_PER_BYTE as u64 - 1;
+        let full = 2 * self.byte_acc + bit as u64;
+        let is_head = self.head_left != 0;
+        let seen = self.seen || (is_head && bit);
+        Self {
+            byte_acc: if is_byte_end { 0 } else { full },
+            bit_idx: if is_byte_end { 0 } else { self.bit_idx + 1 },
+            bit_idx_inv: (F::from(self.bit_idx) - F::from(NUM_BITS_PER_BYTE as u64 - 1))
+                .invert()
+                .unwrap_or(F::ZERO),
+            exp_rlc: if is_byte_end {
+                self.exp_rlc.zip(r).map(|(acc, r)| acc * r + F::from(full))
+            } else {
+                self.exp_rlc
+            },
+            n_bytes: self.n_bytes + is_byte_end as u64,
+            head_left: self.head_left - is_head as u64,
+            head_left_inv: F::from(self.head_left).invert().unwrap_or(F::ZERO),
+            is_head,
+            seen,
+            bit_len: match (is_head, seen) {
+                (true, true) => self.bit_len + 1,
+                (true, false) => 0,
+                (false, _) => self.bit_len,
+            },
+        }
+    }
+}
+
+/// A row of a unit, i.e. a limb of a modular multiplication
+#[derive(Clone, Debug)]
+pub(crate) struct UnitRow<F> {
+    pub(crate) a: u64,
+    pub(crate) b: u64,
+    pub(crate) quot: u64,
+    pub(crate) rem: u64,
+    pub(crate) modulus: u64,
+    pub(crate) base: u64,
+    pub(crate) diff: u64,
+    pub(crate) borrow: bool,
+    /// The carries, with an offset of 2^CARRY_OFFSET_BITS
+    pub(crate) carry_lo: u128,
+    pub(crate) carry_hi: u128,
+    pub(crate) mod_sum: u128,
+    pub(crate) is_mod_zero: bool,
+    pub(crate) is_event: bool,
+    pub(crate) is_first: bool,
+    pub(crate) is_last: bool,
+    pub(crate) is_square: bool,
+    pub(crate) bit: bool,
+    pub(crate) state: ExpState<F>,
+    pub(crate) base_rlc: Value<F>,
+    pub(crate) mod_rlc: Value<F>,
+    pub(crate) rem_rlc: Value<F>,
+}
+
+impl<F: Field> UnitRow<F> {
+    pub(crate) fn empty() -> Self {
+        Self {
+            a: 0,
+            b: 0,
+            quot: 0,
+            rem: 0,
+            modulus: 0,
+            base: 0,
+            diff: 0,
+            borrow: false,
+            carry_lo: 0,
+            carry_hi: 0,
+            mod_sum: 0,
+            is_mod_zero: false,
+            is_event: false,
+            is_first: false,
+            is_last: false,
+            is_square: false,
+            bit: false,
+            state: ExpState::empty(),
+            base_rlc: Value::known(F::ZERO),
+            mod_rlc: Value::known(F::ZERO),
+            rem_rlc: Value::known(F::ZERO),
+        }
+    }
+}
+
+/// The rows of both parts of the circuit
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ModExpWitness<F> {
+    pub(crate) inputs: Vec<InputRow<F>>,
+    pub(crate) units: Vec<UnitRow<F>>,
+}
+
+/// Number of units needed for an exponent of the given length: a squaring
+/// of 1 and then, for every bit, a squaring (except for the first bit) and a
+/// multiplication by the base or 1
+pub(crate) fn num_units(exp_len: usize) -> usize {
+    (2 * exp_len * NUM_BITS_PER_BYTE).max(1)
+}
+
+/// The little-endian limbs of a value below 2^(64 * NUM_LIMBS)
+pub(crate) fn to_limbs(value: &BigUint) -> [u64; NUM_LIMBS] {
+    let digits = value.to_u64_digits();
+    assert!(
+        digits.len() <= NUM_LIMBS,
+        "value exceeds the supported size"
+    );
+    let mut limbs = [0; NUM_LIMBS];
+    limbs[..digits.len()].copy_from_slice(&digits);
+    limbs
+}
+
+fn rlc<F: Field>(bytes: &[u8], r: Value<F>) -> Value<F> {
+    r.map(|r| {
+        bytes
+            .iter()
+            .fold(F::ZERO, |acc, byte| acc * r + F::from(*byte as u64))
+    })
+}
+
+/// The carry with its offset, which makes it non-negative
+fn carry_with_offset(carry: &BigInt) -> u128 {
+    (carry + (BigInt::one() << CARRY_OFFSET_BITS))
+        .to_u128()
+        .expect("carry within bounds")
+}
+
+fn parse_input(event: &BigModExp) -> Result<ModExpAuxData, Error> {
+    let parts = parse_modexp_input(&event.input).map_err(|err| {
+        log::error!("Invalid modexp input: {:?}", err);
+        Error::Synthesis
+    })?;
+    Ok(ModExpAuxData::new(&parts, &event.input, &event.output, &[]))
+}
+
+/// Parse the input of an event, a row per byte
+fn input_rows<F: Field>(
+    rows: &mut Vec<InputRow<F>>,
+    event: &BigModExp,
+    aux_data: &ModExpAuxData,
+    r: Value<F>,
+) {
+    let mut bytes = event.input.clone();
+    bytes.resize(NUM_ROWS_PER_INPUT, 0);
+    let lens = [aux_data.base_len, aux_data.exp_len, aux_data.mod_len].map(|len| len as u64);
+    let output_rlc = rlc(&event.output, r);
+
+    let mut lefts = [event.input.len() as u64, lens[0], lens[1], lens[2]];
+    let mut input_rlc = Value::known(F::ZERO);
+    let mut part_rlcs = [Value::known(F::ZERO); 3];
+    for (idx, byte) in bytes.into_iter().enumerate() {
+        let not_header = idx >= HEADER_LEN;
+        let is_parts = [
+            not_header && lefts[1] != 0,
+            not_header && lefts[1] == 0 && lefts[2] != 0,
+            not_header && lefts[1] == 0 && lefts[2] == 0 && lefts[3] != 0,
+        ];
+        let append = |acc: Value<F>| acc.zip(r).map(|(acc, r)| acc * r + F::from(byte as u64));
+        if lefts[0] != 0 {
+            input_rlc = append(input_rlc);
+        }
+        for (part_rlc, is_part) in part_rlcs.iter_mut().zip(is_parts) {
+            if is_part {
+                *part_rlc = append(*part_rlc);
+            }
+        }
+        rows.push(InputRow {
+            byte,
+            is_event: true,
+            lefts,
+            is_parts,
+            part_rlcs,
+            is_enabled: idx == NUM_ROWS_PER_INPUT - 1,
+            input_rlc,
+            input_len: event.input.len() as u64,
+            lens,
+            exp_msb: aux_data.exp_msb,
+            output_rlc,
+        });
+        lefts[0] -= (lefts[0] != 0) as u64;
+        for (left, is_part) in lefts[1..].iter_mut().zip(is_parts) {
+            *left -= is_part as u64;
+        }
+    }
+}
+
+/// The data shared by all the units of an event
+struct EventData<F> {
+    base: BigUint,
+    modulus: BigUint,
+    is_mod_zero: bool,
+    base_rlc: Value<F>,
+    mod_rlc: Value<F>,
+}
+
+/// Compute a * b = quot * modulus + rem in a unit, with a zero modulus replaced by 1
+fn push_unit<F: Field>(
+    rows: &mut Vec<UnitRow<F>>,
+    data: &EventData<F>,
+    template: UnitRow<F>,
+    a: &BigUint,
+    b: &BigUint,
+    r: Value<F>,
+) -> BigUint {
+    let modulus = if data.is_mod_zero {
+        BigUint::one()
+    } else {
+        data.modulus.clone()
+    };
+    let product = a * b;
+    let (quot, rem) = (&product / &modulus, &product % &modulus);
+
+    let [a_limbs, b_limbs, quot_limbs, rem_limbs, mod_limbs, base_limbs] =
+        [a, b, &quot, &rem, &data.modulus, &data.base].map(to_limbs);
+    let mod_eff_limbs = to_limbs(&modulus);
+    let term = |i: usize, j: usize| {
+        BigInt::from(a_limbs[i]) * BigInt::from(b_limbs[j])
+            - BigInt::from(quot_limbs[i]) * BigInt::from(mod_eff_limbs[j])
+    };
+
+    // The limbs of a * b - quot * modulus - rem are all zero, with carries
+    let mut carry = BigInt::zero();
+    let mut carries_lo = [0; NUM_LIMBS];
+    for (k, carry_lo) in carries_lo.iter_mut().enumerate() {
+        let value =
+            (0..=k).map(|i| term(i, k - i)).sum::<BigInt>() - BigInt::from(rem_limbs[k]) + &carry;
+        carry = &value >> NUM_BITS_PER_LIMB;
+        debug_assert_eq!(&carry << NUM_BITS_PER_LIMB, value);
+        *carry_lo = carry_with_offset(&carry);
+    }
+    let mut carries_hi = [0; NUM_LIMBS];
+    for (k, carry_hi) in carries_hi.iter_mut().enumerate() {
+        let value = (k + 1..NUM_LIMBS)
+            .map(|i| term(i, NUM_LIMBS + k - i))
+            .sum::<BigInt>()
+            + &carry;
+        carry = &value >> NUM_BITS_PER_LIMB;
+        debug_assert_eq!(&carry << NUM_BITS_PER_LIMB, value);
+        *carry_hi = carry_with_offset(&carry);
+    }
+    debug_assert!(carry.is_zero());
+
+    // diff = modulus - rem - 1, which is non-negative iff rem < modulus
+    let mut borrow = false;
+    let mut mod_sum = 0u128;
+    let rem_rlc = rlc(&rem.to_bytes_be(), r);
+    for k in 0..NUM_LIMBS {
+        let value =
+            mod_eff_limbs[k] as i128 - rem_limbs[k] as i128 - (k == 0) as i128 - borrow as i128;
+        borrow = value < 0;
+        mod_sum += mod_limbs[k] as u128;
+        rows.push(UnitRow {
+            a: a_limbs[k],
+            b: b_limbs[k],
+            quot: quot_limbs[k],
+            rem: rem_limbs[k],
+            modulus: mod_limbs[k],
+            base: base_limbs[k],
+            diff: (value + ((borrow as i128) << NUM_BITS_PER_LIMB)) as u64,
+            borrow,
+            carry_lo: carries_lo[k],
+            carry_hi: carries_hi[k],
+            mod_sum,
+            is_mod_zero: data.is_mod_zero,
+            base_rlc: data.base_rlc,
+            mod_rlc: data.mod_rlc,
+            rem_rlc,
+            ..template.clone()
+        });
+    }
+    debug_assert!(!borrow);
+    rem
+}
+
+/// Compute base^exponent % modulus by square-and-multiply, a unit per
+/// modular multiplication
+fn unit_rows<F: Field>(rows: &mut Vec<UnitRow<F>>, event: &BigModExp, r: Value<F>) {
+    let modulus = BigUint::from_bytes_be(&event.modulus);
+    let data = EventData {
+        base: BigUint::from_bytes_be(&event.base),
+        is_mod_zero: modulus.is_zero(),
+        modulus,
+        base_rlc: rlc(&event.base, r),
+        mod_rlc: rlc(&event.modulus, r),
+    };
+    let bits: Vec<bool> = event
+        .exponent
+        .iter()
+        .flat_map(|byte| {
+            (0..NUM_BITS_PER_BYTE)
+                .rev()
+                .map(move |idx| (byte >> idx) & 1 == 1)
+        })
+        .collect();
+    let one = BigUint::one();
+
+    let mut state = ExpState::initial();
+    let template = UnitRow {
+        is_event: true,
+        is_first: true,
+        is_last: bits.is_empty(),
+        is_square: true,
+        state,
+        ..UnitRow::empty()
+    };
+    let mut acc = push_unit(rows, &data, template, &one, &one, r);
+    for (idx, bit) in bits.iter().enumerate() {
+        if idx > 0 {
+            let template = UnitRow {
+                is_event: true,
+                is_square: true,
+                state,
+                ..UnitRow::empty()
+            };
+            acc = push_unit(rows, &data, template, &acc, &acc, r);
+        }
+        state = state.next(*bit, r);
+        let template = UnitRow {
+            is_event: true,
+            is_last: idx == bits.len() - 1,
+            bit: *bit,
+            state,
+            ..UnitRow::empty()
+        };
+        let b = if *bit { &data.base } else { &one };
+        acc = push_unit(rows, &data, template, &acc, b, r);
+    }
+
+    // is_last is only set in the first row of the unit
+    let start = rows.len() - NUM_ROWS_PER_UNIT;
+    for row in rows[start + 1..].iter_mut() {
+        row.is_last = false;
+    }
+    debug_assert_eq!(state.n_bytes as usize, event.exponent.len());
+}
+
+pub(crate) fn multi_modexp<F: Field>(
+    events: &[BigModExp],
+    challenges: Challenges<Value<F>>,
+    capacity: Option<(usize, usize)>,
+) -> Result<ModExpWitness<F>, Error> {
+    let r = challenges.keccak_input();
+    let mut witness = ModExpWitness::default();
+    for event in events {
+        let aux_data = parse_input(event)?;
+        input_rows(&mut witness.inputs, event, &aux_data, r);
+        unit_rows(&mut witness.units, event, r);
+    }
+    if let Some((input_capacity, unit_capacity)) = capacity {
+        // Check that we are not over capacity
+        if events.len() > input_capacity || witness.units.len() > unit_capacity * NUM_ROWS_PER_UNIT
+        {
+            log::error!(
+                "ModExp events exceed capacity. events = {}, capacity = {}, needed_units = {}, available_units = {}",
+                events.len(),
+                input_capacity,
+                witness.units.len() / NUM_ROWS_PER_UNIT,
+                unit_capacity
+            );
+            return Err(Error::BoundsFailure);
+        }
+        // Pad with empty regions and units to the expected capacity
+        witness
+            .inputs
+            .resize(input_capacity * NUM_ROWS_PER_INPUT, InputRow::empty());
+        witness
+            .units
+            .resize(unit_capacity * NUM_ROWS_PER_UNIT, UnitRow::empty());
+    }
+    Ok(witness)
+}
@@ -1,5 +1,5 @@
 //! The MPT circuit implementation.
-use eth_types::Field;
+use eth_types::{Field, Word};
 use gadgets::{impl_expr, util::Scalar};
 use halo2_proofs::{
     circuit::{Layouter, SimpleFloorPlanner, Value},
@@ -50,7 +50,8 @@ use crate::{
         storage_leaf::StorageLeafConfig,
     },
     table::{KeccakTable, MPTProofType, MptTable},
-    util::Challenges,
+    util::{log2_ceil, Challenges},
+    witness,
 };
 
 use extension_branch::ExtensionBranchConfig;
@@ -213,14 +214,13 @@ impl<F: Field> MPTConfig<F> {
     pub fn new(
         meta: &mut ConstraintSystem<F>,
         challenges: Challenges<Expression<F>>,
+        mpt_table: MptTable,
         keccak_table: KeccakTable,
         params: MPTCircuitParams,
     ) -> Self {
         let q_enable = meta.fixed_column();
         let q_first = meta.fixed_column();
 
-        let mpt_table = MptTable::construct(meta);
-
         let fixed_table: [Column<Fixed>; 6] = (0..6)
             .map(|_| meta.fixed_column())
             .collect::<Vec<_>>()
@@ -645,7 +645,7 @@ impl<F: Field> MPTConfig<F> {
 }
 
 /// MPT Circuit for proving the storage modification is valid.
-#[derive(Default)]
+#[derive(Clone, Debug, Default)]
 pub struct MPTCircuit<F: Field> {
     /// MPT nodes
     pub nodes: Vec<Node>,
@@ -673,6 +673,37 @@ pub struct MPTCircuitParams {
     pub max_nodes: usize,
 }
 
+impl<F: Field> MPTCircuit<F> {
+    /// Build the MPT circuit proving the trie modifications of the block.
+    ///
+    /// The circuit is padded to `max_nodes` rows.  When 0, the number of rows is
+    /// calculated from the witness, so the same circuit will not be able to prove
+    /// different witnesses.  The keccak preimages are not loaded, they must be
+    /// provided by the Keccak circuit.
+    pub fn new_from_block(block: &witness::Block<F>, max_nodes: usize) -> Self {
+        let max_nodes = if max_nodes == 0 {
+            Self::min_num_rows(&block.mpt_nodes)
+        } else {
+            max_nodes
+        };
+        Self {
+            nodes: block.mpt_nodes.clone(),
+            keccak_data: vec![],
+            degree: log2_ceil(max_nodes) as usize,
+            max_nodes,
+            disable_preimage_check: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Return the minimum number of rows required to prove the nodes, which
+    /// is at least the height of the mult table.
+    pub fn min_num_rows(nodes: &[Node]) -> usize {
+        let num_rows: usize = nodes.iter().map(|node| node.values.len()).sum();
+        num_rows.max(2 * HASH_WIDTH + 1)
+    }
+}
+
 impl MPTCircuitParams {
     fn is_two_byte_lookup_enabled(&self) -> bool {
         // Currently not enabled because the two byte lookup table does not support msb non-zero
@@ -705,9 +736,10 @@ impl<F: Field> Circuit<F> for MPTCircuit<F> {
     fn configure_with_params(meta: &mut ConstraintSystem<F>, params: Self::Params) -> Self::Config {
         let challenges = Challenges::construct(meta);
         let challenges_expr = challenges.exprs(meta);
+        let mpt_table = MptTable::construct(meta);
         let keccak_table = KeccakTable::construct(meta);
         (
-            MPTConfig::new(meta, challenges_expr, keccak_table, params),
+            MPTConfig::new(meta, challenges_expr, mpt_table, keccak_table, params),
             challenges,
         )
     }
@@ -762,12 +794,35 @@ pub fn load_proof_from_file(path: &str) -> Vec<Node> {
     load_proof(reader).unwrap()
 }
 
+/// Returns the old and new state roots of each of the proofs in the MPT
+/// witness, in order.
+pub fn proof_roots(nodes: &[Node]) -> Vec<(Word, Word)> {
+    nodes
+        .iter()
+        .filter(|node| {
+            node.start
+                .as_ref()
+                .map_or(false, |start| start.proof_type != MPTProofType::Disabled)
+        })
+        .map(|node| {
+            // The roots are RLP encoded hashes: [0xa0, hash...]
+            let root = |row: StartRowType| Word::from_big_endian(&node.values[row as usize][1..33]);
+            (root(StartRowType::RootS), root(StartRowType::RootC))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bus_mapping::{
+        mpt::{ProofType, StateTrie, TrieModification},
+        state_db::{Account, CodeDB, StateDB},
+    };
+    use eth_types::{Address, H256, U64};
     use halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr};
     use itertools::Itertools;
-    use std::{fs, ops::Deref, path::PathBuf};
+    use std::{collections::HashMap, fs, ops::Deref, path::PathBuf};
 
     #[test]
     fn test_mpt() {
@@ -783,6 +838,109 @@ mod tests {
             });
     }
 
+    #[test]
+    fn test_mpt_native_witness() {
+        native_witness_ok(&[
+            TrieModification {
+                typ: ProofType::NonceChanged,
+                address: Address::from_low_u64_be(1),
+                nonce: U64::from(2),
+                ..Default::default()
+            },
+            TrieModification {
+                typ: ProofType::BalanceChanged,
+                address: Address::from_low_u64_be(9),
+                balance: Word::from(9),
+                ..Default::default()
+            },
+            TrieModification {
+                typ: ProofType::StorageChanged,
+                address: Address::from_low_u64_be(2),
+                key: H256::from_low_u64_be(3),
+                value: Word::from(3),
+                ..Default::default()
+            },
+            TrieModification {
+                typ: ProofType::StorageDoesNotExist,
+                address: Address::from_low_u64_be(3),
+                key: H256::from_low_u64_be(4),
+                ..Default::default()
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_mpt_native_witness_account_create() {
+        // the created account keeps its balance, and its storage is cleared
+        native_witness_ok(&[
+            TrieModification {
+                typ: ProofType::AccountCreate,
+                address: Address::from_low_u64_be(4),
+                ..Default::default()
+            },
+            TrieModification {
+                typ: ProofType::StorageChanged,
+                address: Address::from_low_u64_be(4),
+                key: H256::from_low_u64_be(1),
+                value: Word::from(1),
+                ..Default::default()
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_mpt_native_witness_account_destructed() {
+        native_witness_ok(&[TrieModification {
+            typ: ProofType::AccountDestructed,
+            address: Address::from_low_u64_be(5),
+            ..Default::default()
+        }]);
+    }
+
+    #[test]
+    fn test_mpt_native_witness_code_hash_changed() {
+        native_witness_ok(&[TrieModification {
+            typ: ProofType::CodeHashChanged,
+            address: Address::from_low_u64_be(6),
+            code_hash: H256::from_low_u64_be(6),
+            ..Default::default()
+        }]);
+    }
+
+    #[test]
+    fn test_mpt_native_witness_account_does_not_exist() {
+        native_witness_ok(&[TrieModification {
+            typ: ProofType::AccountDoesNotExist,
+            address: Address::from_low_u64_be(9),
+            ..Default::default()
+        }]);
+    }
+
+    /// Verifies the MPT circuit with the witness of `modifications`, generated
+    /// natively on a state of 8 accounts.
+    fn native_witness_ok(modifications: &[TrieModification]) {
+        let mut sdb = StateDB::new();
+        for idx in 1..=8 {
+            sdb.set_account(
+                &Address::from_low_u64_be(idx),
+                Account {
+                    nonce: idx,
+                    balance: Word::from(idx),
+                    storage: HashMap::from([(Word::from(idx), Word::from(idx))]),
+                    code_hash: CodeDB::empty_code_hash(),
+                },
+            );
+        }
+        let witness = StateTrie::from_state_db(&sdb)
+            .unwrap()
+            .gen_witness(modifications)
+            .unwrap();
+
+        let (num_rows, circuit) = mpt_circuit(load_proof(witness.as_bytes()).unwrap());
+        let prover = MockProver::<Fr>::run(15, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify_at_rows(0..num_rows, 0..num_rows), Ok(()));
+    }
+
     #[test]
     fn variadic_size_check() {
         let mut circuits = get_witnesses();
@@ -797,6 +955,31 @@ mod tests {
         assert_eq!(prover_1.permutation(), prover_2.permutation());
     }
 
+    fn mpt_circuit(nodes: Vec<Node>) -> (usize, MPTCircuit<Fr>) {
+        let num_rows: usize = nodes.iter().map(|node| node.values.len()).sum();
+
+        let mut keccak_data = vec![];
+        for node in nodes.iter() {
+            for k in node.keccak_data.iter() {
+                keccak_data.push(k.deref().clone());
+            }
+        }
+        let disable_preimage_check = nodes[0].start.clone().unwrap().disable_preimage_check;
+        let degree = 15;
+        let max_nodes = 520;
+        (
+            num_rows,
+            MPTCircuit::<Fr> {
+                nodes,
+                keccak_data,
+                degree,
+                max_nodes,
+                disable_preimage_check,
+                _marker: PhantomData,
+            },
+        )
+    }
+
     fn get_witnesses() -> impl Iterator<Item = (PathBuf, usize, MPTCircuit<Fr>)> {
         let path = "src/mpt_circuit/tests";
         let files = fs::read_dir(path).unwrap();
@@ -816,29 +999,8 @@ mod tests {
                 parts.next();
 
                 let nodes = load_proof_from_file(path.to_str().unwrap());
-                let num_rows: usize = nodes.iter().map(|node| node.values.len()).sum();
-
-                let mut keccak_data = vec![];
-                for node in nodes.iter() {
-                    for k in node.keccak_data.iter() {
-                        keccak_data.push(k.deref().clone());
-                    }
-                }
-                let disable_preimage_check = nodes[0].start.clone().unwrap().disable_preimage_check;
-                let degree = 15;
-                let max_nodes = 520;
-                (
-                    path,
-                    num_rows,
-                    MPTCircuit::<Fr> {
-                        nodes,
-                        keccak_data,
-                        degree,
-                        max_nodes,
-                        disable_preimage_check,
-                        _marker: PhantomData,
-                    },
-                )
+                let (num_rows, circuit) = mpt_circuit(nodes);
+                (path, num_rows, circuit)
             })
     }
 }
@@ -101,7 +101,9 @@ pub struct PiCircuitConfig<F: Field> {
 
     q_rpi_byte_enable: Selector,
 
-    pi_instance: Column<Instance>, // keccak_digest_hi, keccak_digest_lo
+    // keccak_digest_lo, keccak_digest_hi, then lo, hi of parent hash, block hash, state root and
+    // previous state root
+    pi_instance: Column<Instance>,
 
     _marker: PhantomData<F>,
     // External tables
@@ -1107,7 +1109,8 @@ impl<F: Field> PiCircuitConfig<F> {
 
     /// Assigns the values for block table in the block_table column
     /// and rpi_bytes columns. Copy constraints will be enable
-    /// to assure block_table value cell equal with respective rpi_byte_rlc cell
+    /// to assure block_table value cell equal with respective rpi_byte_rlc cell.
+    /// Returns the assigned parent hash, which is the latest history hash.
     #[allow(clippy::too_many_arguments)]
     fn assign_block_table(
         &self,
@@ -1119,7 +1122,7 @@ impl<F: Field> PiCircuitConfig<F> {
         current_rpi_offset: &mut usize,
         rpi_bytes: &mut [u8],
         zero_cell: AssignedCell<F, F>,
-    ) -> Result<(), Error> {
+    ) -> Result<WordLoHi<AssignedCell<F, F>>, Error> {
         let mut block_copy_cells = vec![];
 
         // coinbase
@@ -1296,6 +1299,60 @@ impl<F: Field> PiCircuitConfig<F> {
         block_copy_cells.push((block_value, word));
         *block_table_offset += 1;
 
+        // excess_blob_gas
+        let block_value = WordLoHi::from(block_values.excess_blob_gas)
+            .into_value()
+            .assign_advice(
+                region,
+                || "excess_blob_gas",
+                self.block_table.value,
+                *block_table_offset,
+            )?;
+        let (_, word) = self.assign_raw_bytes(
+            region,
+            &block_values.excess_blob_gas.to_le_bytes(),
+            rpi_bytes_keccak_rlc,
+            rpi_bytes,
+            current_rpi_offset,
+            challenges,
+            zero_cell.clone(),
+        )?;
+        block_copy_cells.push((block_value, word));
+        *block_table_offset += 1;
+
+        // blob_gas_used
+        let block_value = WordLoHi::from(block_values.blob_gas_used)
+            .into_value()
+            .assign_advice(
+                region,
+                || "blob_gas_used",
+                self.block_table.value,
+                *block_table_offset,
+            )?;
+        let (_, word) = self.assign_raw_bytes(
+            region,
+            &block_values.blob_gas_used.to_le_bytes(),
+            rpi_bytes_keccak_rlc,
+            rpi_bytes,
+            current_rpi_offset,
+            challenges,
+            zero_cell.clone(),
+        )?;
+        block_copy_cells.push((block_value, word));
+        *block_table_offset += 1;
+
+        // blob_base_fee is derived from excess_blob_gas, so it's not part of the
+        // public input bytes.
+        WordLoHi::from(block_values.blob_base_fee)
+            .into_value()
+            .assign_advice(
+                region,
+                || "blob_base_fee",
+                self.block_table.value,
+                *block_table_offset,
+            )?;
+        *block_table_offset += 1;
+
         for prev_hash in block_values.history_hashes {
             let block_value = WordLoHi::from(prev_hash).into_value().assign_advice(
                 region,
@@ -1327,14 +1384,17 @@ impl<F: Field> PiCircuitConfig<F> {
             Ok::<(), Error>(())
         })?;
 
-        Ok(())
+        // history hashes are always assigned last, the latest one being the
+        // parent hash
+        let (_, parent_hash) = block_copy_cells.pop().expect("history hashes are assigned");
+        Ok(parent_hash)
     }
 
     /// Assigns the extra fields (not in block or tx tables):
     ///   - block hash
     ///   - state root
     ///   - previous block state root
-    /// to the rpi_byte column
+    /// to the rpi_byte column, and returns their assigned words in this order.
     #[allow(clippy::too_many_arguments)]
     fn assign_extra_fields(
         &self,
@@ -1345,9 +1405,9 @@ impl<F: Field> PiCircuitConfig<F> {
         current_rpi_offset: &mut usize,
         rpi_bytes: &mut [u8],
         zero_cell: AssignedCell<F, F>,
-    ) -> Result<(), Error> {
+    ) -> Result<[WordLoHi<AssignedCell<F, F>>; 3], Error> {
         // block hash
-        self.assign_raw_bytes(
+        let (_, block_hash) = self.assign_raw_bytes(
             region,
             &extra
                 .block_hash
@@ -1364,7 +1424,7 @@ impl<F: Field> PiCircuitConfig<F> {
         )?;
 
         // block state root
-        self.assign_raw_bytes(
+        let (_, state_root) = self.assign_raw_bytes(
             region,
             &extra
                 .state_root
@@ -1381,7 +1441,7 @@ impl<F: Field> PiCircuitConfig<F> {
         )?;
 
         // previous block state root
-        self.assign_raw_bytes(
+        let (_, prev_state_root) = self.assign_raw_bytes(
             region,
             &extra
                 .prev_state_root
@@ -1397,7 +1457,7 @@ impl<F: Field> PiCircuitConfig<F> {
             zero_cell,
         )?;
 
-        Ok(())
+        Ok([block_hash, state_root, prev_state_root])
     }
 
     /// Assign digest word
@@ -1483,15 +1543,29 @@ impl<F: Field> SubCircuit<F> for PiCircuit<F> {
         )
     }
 
-    /// Compute the public inputs for this circuit.
+    /// Compute the public inputs for this circuit: the digest of the public
+    /// data, followed by the parent hash, block hash, state root and previous
+    /// state root, which link the block to its parent.
     fn instance(&self) -> Vec<Vec<F>> {
         let rpi_digest_byte_field = self.public_data.get_rpi_digest_word(
             self.max_txs,
             self.max_withdrawals,
             self.max_calldata,
         );
-
-        vec![vec![rpi_digest_byte_field.lo(), rpi_digest_byte_field.hi()]]
+        let extra = self.public_data.get_extra_values();
+
+        vec![iter::once(rpi_digest_byte_field)
+            .chain(
+                [
+                    self.public_data.parent_hash(),
+                    extra.block_hash,
+                    extra.state_root,
+                    extra.prev_state_root,
+                ]
+                .map(WordLoHi::from),
+            )
+            .flat_map(|word| [word.lo(), word.hi()])
+            .collect()]
     }
 
     /// Make the assignments to the PiCircuit
@@ -1516,7 +1590,7 @@ impl<F: Field> SubCircuit<F> for PiCircuit<F> {
                 Ok(())
             },
         )?;
-        let digest_word_assigned = layouter.assign_region(
+        let public_words = layouter.assign_region(
             || "region 0",
             |mut region| {
                 // Annotate columns
@@ -1581,7 +1655,7 @@ impl<F: Field> SubCircuit<F> for PiCircuit<F> {
                     zero_cell.clone(),
                 )?;
                 block_table_offset += 1;
-                config.assign_block_table(
+                let parent_hash = config.assign_block_table(
                     &mut region,
                     &mut block_table_offset,
                     block_values,
@@ -1598,7 +1672,7 @@ impl<F: Field> SubCircuit<F> for PiCircuit<F> {
 
                 // Assign extra fields
                 let extra_vals = self.public_data.get_extra_values();
-                config.assign_extra_fields(
+                let [block_hash, state_root, prev_state_root] = config.assign_extra_fields(
                     &mut region,
                     extra_vals,
                     &mut rpi_bytes_keccak_rlc,
@@ -1645,12 +1719,13 @@ impl<F: Field> SubCircuit<F> for PiCircuit<F> {
                     .chain((0..(config.max_txs - txs.len())).map(|_| &tx_default))
                     .enumerate()
                     .try_for_each(|(i, tx)| -> Result<(), Error> {
-                        for (tag, value_bytes) in &[
-                            (TxFieldTag::Nonce, tx.nonce.to_le_bytes().to_vec()),
-                            (TxFieldTag::Gas, tx.gas_limit.to_le_bytes().to_vec()),
-                            (TxFieldTag::GasPrice, tx.gas_price.to_le_bytes().to_vec()),
+                        for (tag, index, value_bytes) in [
+                            (TxFieldTag::Nonce, 0, tx.nonce.to_le_bytes().to_vec()),
+                            (TxFieldTag::Gas, 0, tx.gas_limit.to_le_bytes().to_vec()),
+                            (TxFieldTag::GasPrice, 0, tx.gas_price.to_le_bytes().to_vec()),
                             (
                                 TxFieldTag::CallerAddress,
+                                0,
                                 tx.from_addr
                                     .as_fixed_bytes()
                                     .iter()
@@ -1660,6 +1735,7 @@ impl<F: Field> SubCircuit<F> for PiCircuit<F> {
                             ),
                             (
                                 TxFieldTag::CalleeAddress,
+                                0,
                                 tx.to_addr
                                     .as_fixed_bytes()
                                     .iter()
@@ -1667,28 +1743,48 @@ impl<F: Field> SubCircuit<F> for PiCircuit<F> {
                                     .rev()
                                     .collect_vec(),
                             ),
-                            (TxFieldTag::IsCreate, tx.is_create.to_le_bytes().to_vec()),
-                            (TxFieldTag::Value, tx.value.to_le_bytes().to_vec()),
+                            (TxFieldTag::IsCreate, 0, tx.is_create.to_le_bytes().to_vec()),
+                            (TxFieldTag::Value, 0, tx.value.to_le_bytes().to_vec()),
                             (
                                 TxFieldTag::CallDataLength,
+                                0,
                                 tx.call_data_len.to_le_bytes().to_vec(),
                             ),
                             (
                                 TxFieldTag::CallDataGasCost,
+                                0,
                                 tx.call_data_gas_cost.to_le_bytes().to_vec(),
                             ),
                             // TODO witness tx.tx_sign_hash
-                            (TxFieldTag::TxSignHash, tx.tx_sign_hash.to_vec()),
-                        ] {
+                            (TxFieldTag::TxSignHash, 0, tx.tx_sign_hash.to_vec()),
+                            (
+                                TxFieldTag::BlobVersionedHashesLength,
+                                0,
+                                tx.blob_versioned_hashes_len.to_le_bytes().to_vec(),
+                            ),
+                        ]
+                        .into_iter()
+                        .chain(
+                            tx.blob_versioned_hashes
+                                .iter()
+                                .enumerate()
+                                .map(|(index, hash)| {
+                                    (
+                                        TxFieldTag::BlobVersionedHash,
+                                        index as u64,
+                                        hash.to_fixed_bytes().iter().copied().rev().collect_vec(),
+                                    )
+                                }),
+                        ) {
                             let i: u64 = i.try_into().unwrap();
                             // assign tx field
                             config.assign_tx_row(
                                 &mut region,
                                 tx_table_offset,
                                 i + 1,
-                                *tag,
-                                0,
-                                value_bytes,
+                                tag,
+                                index,
+                                &value_bytes,
                                 &mut rpi_bytes_keccak_rlc,
                                 challenges,
                                 &mut current_rpi_offset,
@@ -1835,13 +1931,24 @@ impl<F: Field> SubCircuit<F> for PiCircuit<F> {
                 // keccak lookup occur on offset 0
                 config.q_rpi_keccak_lookup.enable(&mut region, 0)?;
 
-                Ok(digest_word_assigned)
+                Ok([
+                    digest_word_assigned,
+                    parent_hash,
+                    block_hash,
+                    state_root,
+                    prev_state_root,
+                ])
             },
         )?;
 
         // Constrain raw_public_input cells to public inputs
-        layouter.constrain_instance(digest_word_assigned.lo().cell(), config.pi_instance, 0)?;
-        layouter.constrain_instance(digest_word_assigned.hi().cell(), config.pi_instance, 1)?;
+        public_words
+            .iter()
+            .flat_map(|word| [word.lo(), word.hi()])
+            .enumerate()
+            .try_for_each(|(i, cell)| {
+                layouter.constrain_instance(cell.cell(), config.pi_instance, i)
+            })?;
 
         Ok(())
     }
@@ -90,6 +90,40 @@ fn test_simple_pi() {
     );
 }
 
+#[test]
+fn test_block_link_pi() {
+    let max_txs = 2;
+    let max_withdrawals = 2;
+    let max_calldata = 8;
+    let public_data = PublicData {
+        chain_id: *MOCK_CHAIN_ID,
+        history_hashes: vec![Word::from(0xdeadbeefu64)],
+        block_hash: Some(H256::from_low_u64_be(0xb10c)),
+        state_root: H256::from_low_u64_be(0x5700),
+        prev_state_root: H256::from_low_u64_be(0x5701),
+        ..Default::default()
+    };
+
+    let circuit = PiCircuit::<Fr>::new(max_txs, max_withdrawals, max_calldata, public_data);
+    let public_inputs = circuit.instance();
+    // digest, parent hash, block hash, state root, previous state root
+    assert_eq!(public_inputs[0].len(), 10);
+    assert_eq!(public_inputs[0][2], Fr::from(0xdeadbeef));
+    assert_eq!(public_inputs[0][4], Fr::from(0xb10c));
+    assert_eq!(public_inputs[0][6], Fr::from(0x5700));
+    assert_eq!(public_inputs[0][8], Fr::from(0x5701));
+
+    let k = 17;
+    let prover = MockProver::run(k, &circuit, public_inputs.clone()).unwrap();
+    assert_eq!(prover.verify(), Ok(()));
+
+    // the exposed state root must be the one hashed in the digest
+    let mut public_inputs = public_inputs;
+    public_inputs[0][6] += Fr::from(1);
+    let prover = MockProver::run(k, &circuit, public_inputs).unwrap();
+    assert!(prover.verify().is_err());
+}
+
 #[test]
 fn test_1tx_1maxtx() {
     const MAX_TXS: usize = 1;
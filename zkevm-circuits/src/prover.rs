@@ -0,0 +1,554 @@
+//! Block prover, producing the [`RootCircuit`] proof of a block queried from
+//! a node, and verifying it.
+//!
+//! The [`BlockProver`] chains the steps of the proving pipeline: it generates
+//! the circuit inputs of the block with the [`BuilderClient`], builds the
+//! [`SuperCircuit`] of each chunk, proves the chunks and aggregates their
+//! proofs in the `RootCircuit`.  The keys are loaded from, or generated in,
+//! its [`KeyCache`], which must hold the params of degree `root_k`.
+//! ```ignore
+//! let mut prover = BlockProver::new(
+//!     GethClient::new(Http::from_str(url)?),
+//!     circuits_params,
+//!     FeatureConfig::default(),
+//!     KeyCache::new("keys"),
+//!     26,
+//! )
+//! .await?;
+//! let proof = prover.prove_block(block_number).await?;
+//! prover.verify_block(&proof, block_hash)?;
+//! ```
+//!
+//! The root proof is created with the [`EvmTranscript`], so it can be
+//! verified by the verifier generated with
+//! [`gen_evm_verifier`](crate::root_circuit::evm::gen_evm_verifier) too.
+
+use crate::{
+    keys::{KeyCache, KeyCircuit, KeyConfigHash, KeyError},
+    root_circuit::{
+        compile, exposed_block_hash, Config, EvmTranscript, Gwc, NativeLoader, PlonkVerifier,
+        PoseidonTranscript, RootCircuit, SnarkWitness, UserChallenge, LIMBS,
+    },
+    super_circuit::SuperCircuit,
+    util::{word::WordLoHi, SubCircuitRowUsage},
+};
+use bus_mapping::{
+    circuit_input_builder::{
+        BuilderClient, ChunkRowUsage, CircuitInputBuilder, FeatureConfig, FixedCParams,
+    },
+    rpc::GethClient,
+};
+use eth_types::{evm_types::Hardfork, H256};
+use ethers_providers::JsonRpcClient;
+use halo2_proofs::{
+    circuit::Value,
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::{create_proof, Circuit, ConstraintSystem, ProvingKey},
+    poly::{
+        commitment::{Params, ParamsProver},
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::ProverGWC,
+        },
+    },
+    transcript::TranscriptWriterBuffer,
+};
+use itertools::Itertools;
+use rand::rngs::OsRng;
+use snark_verifier::{
+    pcs::kzg::KzgDecidingKey,
+    verifier::{plonk::PlonkProtocol, SnarkVerifier},
+};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Randomness of the `SuperCircuit`.  It's part of the circuit params, so the
+/// same value must be used to generate the keys and the proofs.
+const MOCK_RANDOMNESS: u64 = 0x100;
+
+/// Stages of the proving of a block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProverStage {
+    /// Generation of the circuit inputs from the node
+    Inputs,
+    /// Generation of the witness of the chunk circuits
+    Witness,
+    /// Derivation of the permutation randomness of the chunk circuits from
+    /// the commitments of their rw tables
+    PermutationRandomness,
+    /// Loading or generation of the params and keys
+    Keys,
+    /// Proving of the chunk of given index
+    ChunkProof(usize),
+    /// Proving of the `RootCircuit` aggregating the chunk proofs
+    RootProof,
+}
+
+/// Errors of the block prover, by stage
+#[derive(Debug, Error)]
+pub enum ProverError {
+    /// Failed to generate the circuit inputs
+    #[error("Inputs({0:?})")]
+    Inputs(bus_mapping::Error),
+    /// Failed to generate the witness of the chunk circuits
+    #[error("Witness({0:?})")]
+    Witness(bus_mapping::Error),
+    /// Failed to load or generate the params or keys
+    #[error("Keys({0})")]
+    Keys(#[from] KeyError),
+    /// The chunk circuits need a degree higher than the one of the params
+    #[error("DegreeTooHigh(super_circuit: {super_circuit}, root_circuit: {root_circuit})")]
+    DegreeTooHigh {
+        /// Degree needed by the `SuperCircuit`
+        super_circuit: u32,
+        /// Degree of the `RootCircuit` params
+        root_circuit: u32,
+    },
+    /// Failed to create a proof
+    #[error("Proof(stage: {stage:?}, reason: {reason:?})")]
+    Proof {
+        /// Stage of the proof
+        stage: ProverStage,
+        /// Reason of the failure
+        reason: halo2_proofs::plonk::Error,
+    },
+    /// The chunk proofs could not be aggregated
+    #[error("Aggregation({0:?})")]
+    Aggregation(snark_verifier::Error),
+    /// The root proof is invalid
+    #[error("Verification({0:?})")]
+    Verification(snark_verifier::Error),
+    /// The root proof is not of the block with the expected hash
+    #[error("WrongBlock(block_number: {0})")]
+    WrongBlock(u64),
+}
+
+/// Statistics of the proving of a block
+#[derive(Clone, Debug, Default)]
+pub struct ProverStats {
+    /// Duration of each stage, in order
+    pub timings: Vec<(ProverStage, Duration)>,
+    /// Degree of the `SuperCircuit`
+    pub super_circuit_k: u32,
+    /// Usage of the circuit capacities by each chunk
+    pub chunk_row_usage: Vec<ChunkRowUsage>,
+    /// Rows used by each sub-circuit of each chunk
+    pub sub_circuit_row_usage: Vec<Vec<SubCircuitRowUsage>>,
+}
+
+impl ProverStats {
+    /// Run `f`, recording its duration for `stage`.
+    fn time<T>(&mut self, stage: ProverStage, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.timings.push((stage, start.elapsed()));
+        result
+    }
+}
+
+/// Proof of a block
+#[derive(Clone, Debug)]
+pub struct BlockProof {
+    /// Number of the block
+    pub block_number: u64,
+    /// Hardfork of the block, which the keys of the circuits depend on
+    pub hardfork: Hardfork,
+    /// Number of chunks the block has been proven in, which the key of the
+    /// `RootCircuit` depends on
+    pub num_chunks: usize,
+    /// Instances of the `RootCircuit`: the public data digest and the linking
+    /// words of the block, followed by the accumulator limbs
+    pub instances: Vec<Fr>,
+    /// Proof of the `RootCircuit`, created with the `EvmTranscript`
+    pub proof: Vec<u8>,
+    /// Statistics of the proving
+    pub stats: ProverStats,
+}
+
+/// Params and keys of the `SuperCircuit` and `RootCircuit`
+struct ProverKeys {
+    super_k: u32,
+    super_params: ParamsKZG<Bn256>,
+    super_pk: ProvingKey<G1Affine>,
+    protocol: PlonkProtocol<G1Affine>,
+    user_challenge: UserChallenge,
+    root_params: ParamsKZG<Bn256>,
+    root_pk: ProvingKey<G1Affine>,
+    root_config_hash: KeyConfigHash,
+}
+
+/// Prover of the blocks of a node, owning the keys of the circuits.
+pub struct BlockProver<P: JsonRpcClient> {
+    cli: BuilderClient<P>,
+    circuits_params: FixedCParams,
+    feature_config: FeatureConfig,
+    cache: KeyCache,
+    root_k: u32,
+    keys: Option<ProverKeys>,
+}
+
+impl<P: JsonRpcClient> BlockProver<P> {
+    /// Create a `BlockProver` of the blocks of the node of `client`, with the
+    /// `RootCircuit` of degree `root_k`.  The params of the `SuperCircuit` are
+    /// the ones of the `RootCircuit` downsized, as the accumulator of the
+    /// chunk proofs is decided with the params of the root proof.
+    pub async fn new(
+        client: GethClient<P>,
+        circuits_params: FixedCParams,
+        feature_config: FeatureConfig,
+        cache: KeyCache,
+        root_k: u32,
+    ) -> Result<Self, ProverError> {
+        let cli = BuilderClient::new_with_features(client, circuits_params, feature_config)
+            .await
+            .map_err(ProverError::Inputs)?;
+        Ok(Self {
+            cli,
+            circuits_params,
+            feature_config,
+            cache,
+            root_k,
+            keys: None,
+        })
+    }
+
+    /// Prove the block `block_number`.
+    pub async fn prove_block(&mut self, block_number: u64) -> Result<BlockProof, ProverError> {
+        let start = Instant::now();
+        let (builder, _) = self
+            .cli
+            .gen_inputs(block_number)
+            .await
+            .map_err(ProverError::Inputs)?;
+        let elapsed = start.elapsed();
+
+        let mut proof = self.prove_inputs(&builder, block_number)?;
+        proof
+            .stats
+            .timings
+            .insert(0, (ProverStage::Inputs, elapsed));
+        Ok(proof)
+    }
+
+    /// Prove the block `block_number` from its circuit inputs.
+    pub fn prove_inputs(
+        &mut self,
+        builder: &CircuitInputBuilder<FixedCParams>,
+        block_number: u64,
+    ) -> Result<BlockProof, ProverError> {
+        let mut stats = ProverStats {
+            chunk_row_usage: builder.chunks.iter().map(|chunk| chunk.row_usage).collect(),
+            ..Default::default()
+        };
+
+        let (k, circuits, _) = stats
+            .time(ProverStage::Witness, || {
+                SuperCircuit::<Fr>::build_from_circuit_input_builder(
+                    builder,
+                    MOCK_RANDOMNESS.into(),
+                )
+            })
+            .map_err(ProverError::Witness)?;
+        stats.super_circuit_k = k;
+        stats.sub_circuit_row_usage = circuits.iter().map(SuperCircuit::row_usage).collect();
+
+        let num_chunks = circuits.len();
+        stats.time(ProverStage::Keys, || {
+            self.load_keys(k, &circuits[0], num_chunks)
+        })?;
+        let keys = self.keys.as_ref().expect("keys are loaded");
+
+        let (circuits, instances) = stats.time(ProverStage::PermutationRandomness, || {
+            SuperCircuit::with_permutation_randomness(circuits, &keys.super_params)
+        });
+
+        let proofs = circuits
+            .into_iter()
+            .zip(instances.iter())
+            .enumerate()
+            .map(|(i, (circuit, instance))| {
+                let stage = ProverStage::ChunkProof(i);
+                stats.time(stage, || {
+                    let mut transcript = PoseidonTranscript::new(Vec::new());
+                    create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
+                        &keys.super_params,
+                        &keys.super_pk,
+                        &[circuit],
+                        &[&instance.iter().map(Vec::as_slice).collect_vec()],
+                        OsRng,
+                        &mut transcript,
+                    )
+                    .map_err(|reason| ProverError::Proof { stage, reason })?;
+                    Ok(transcript.finalize())
+                })
+            })
+            .collect::<Result<Vec<_>, ProverError>>()?;
+
+        let (instances, proof) = stats.time(ProverStage::RootProof, || {
+            let snark_witnesses = proofs
+                .iter()
+                .zip(instances.iter())
+                .map(|(proof, instance)| {
+                    SnarkWitness::new(&keys.protocol, Value::known(instance), Value::known(proof))
+                })
+                .collect();
+            let root_circuit = RootCircuit::<Bn256, Gwc<_>>::new(
+                &keys.super_params,
+                &keys.protocol,
+                snark_witnesses,
+                Some(&keys.user_challenge),
+            )
+            .map_err(ProverError::Aggregation)?;
+            let instances = root_circuit.instance();
+
+            let mut transcript: EvmTranscript<G1Affine, NativeLoader, _, _> =
+                TranscriptWriterBuffer::init(Vec::new());
+            create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
+                &keys.root_params,
+                &keys.root_pk,
+                &[root_circuit],
+                &[&instances.iter().map(Vec::as_slice).collect_vec()],
+                OsRng,
+                &mut transcript,
+            )
+            .map_err(|reason| ProverError::Proof {
+                stage: ProverStage::RootProof,
+                reason,
+            })?;
+            Ok::<_, ProverError>((instances, transcript.finalize()))
+        })?;
+
+        Ok(BlockProof {
+            block_number,
+            hardfork: builder.feature_config.hardfork,
+            num_chunks,
+            instances: instances.into_iter().flatten().collect(),
+            proof,
+            stats,
+        })
+    }
+
+    /// Load the keys of the circuits of degree `k` and configuration of
+    /// `circuit`, with the `RootCircuit` aggregating `num_chunks` chunk
+    /// proofs, unless already loaded.
+    fn load_keys(
+        &mut self,
+        k: u32,
+        circuit: &SuperCircuit<Fr>,
+        num_chunks: usize,
+    ) -> Result<(), ProverError> {
+        let root_config_hash = KeyConfigHash::root_circuit(circuit, num_chunks);
+        let is_loaded = self
+            .keys
+            .as_ref()
+            .is_some_and(|keys| keys.super_k == k && keys.root_config_hash == root_config_hash);
+        if is_loaded {
+            return Ok(());
+        }
+        if k > self.root_k {
+            return Err(ProverError::DegreeTooHigh {
+                super_circuit: k,
+                root_circuit: self.root_k,
+            });
+        }
+
+        let root_params = self.cache.params(self.root_k)?;
+        let mut super_params = root_params.clone();
+        super_params.downsize(k);
+        let super_pk = self.cache.super_circuit_pk(&super_params, circuit)?;
+
+        let mut cs = ConstraintSystem::default();
+        let config = SuperCircuit::configure_with_params(&mut cs, circuit.params());
+        let user_challenge = UserChallenge {
+            column_indexes: config.get_rwtable_columns(),
+            num_challenges: 2, // alpha, gamma
+        };
+        let protocol = compile(
+            &super_params,
+            super_pk.get_vk(),
+            Config::kzg().with_num_instance(circuit.instance().iter().map(Vec::len).collect()),
+        );
+
+        // the root key only depends on the protocol and number of the chunk
+        // proofs
+        let snark_witnesses = (0..num_chunks)
+            .map(|_| SnarkWitness::new(&protocol, Value::unknown(), Value::unknown()))
+            .collect();
+        let root_circuit = RootCircuit::<Bn256, Gwc<_>>::new(
+            &super_params,
+            &protocol,
+            snark_witnesses,
+            Some(&user_challenge),
+        )
+        .map_err(ProverError::Aggregation)?;
+        let root_pk = self
+            .cache
+            .pk(&root_params, &root_circuit, &root_config_hash)?;
+        drop(root_circuit);
+
+        self.keys = Some(ProverKeys {
+            super_k: k,
+            super_params,
+            super_pk,
+            protocol,
+            user_challenge,
+            root_params,
+            root_pk,
+            root_config_hash,
+        });
+        Ok(())
+    }
+
+    /// Verify that `proof` proves the block `proof.block_number`, whose hash
+    /// is `block_hash`.  The block number isn't part of the instances of the
+    /// proof, so the block is identified by the hash exposed in them, which
+    /// must be the one of the block of that number in the chain.
+    ///
+    /// The verifying key of the `RootCircuit` is loaded from the cache if the
+    /// keys of the hardfork of the block are not loaded.
+    pub fn verify_block(&self, proof: &BlockProof, block_hash: H256) -> Result<(), ProverError> {
+        let block_hash = WordLoHi::<Fr>::from(block_hash);
+        if exposed_block_hash(&proof.instances) != Some([block_hash.lo(), block_hash.hi()]) {
+            return Err(ProverError::WrongBlock(proof.block_number));
+        }
+
+        let root_config_hash = KeyConfigHash::new(
+            KeyCircuit::RootCircuit,
+            &FixedCParams {
+                total_chunks: proof.num_chunks,
+                ..self.circuits_params
+            },
+            &FeatureConfig {
+                hardfork: proof.hardfork,
+                ..self.feature_config
+            },
+        );
+        let loaded;
+        let (params, vk) = match &self.keys {
+            Some(keys) if keys.root_config_hash == root_config_hash => {
+                (&keys.root_params, keys.root_pk.get_vk())
+            }
+            _ => {
+                let params = self.cache.params(self.root_k)?;
+                let vk = self.cache.vk::<RootCircuit<Bn256, Gwc<Bn256>>>(
+                    self.root_k,
+                    &root_config_hash,
+                    (),
+                )?;
+                loaded = (params, vk);
+                (&loaded.0, &loaded.1)
+            }
+        };
+
+        // the accumulator limbs are the last instances
+        let num_instance = proof.instances.len();
+        if num_instance < 4 * LIMBS {
+            return Err(ProverError::Verification(
+                snark_verifier::Error::InvalidInstances,
+            ));
+        }
+        let protocol = compile(
+            params,
+            vk,
+            Config::kzg()
+                .with_num_instance(vec![num_instance])
+                .with_accumulator_indices(Some(
+                    (num_instance - 4 * LIMBS..num_instance)
+                        .map(|idx| (0, idx))
+                        .collect(),
+                )),
+        );
+        let dk: KzgDecidingKey<Bn256> = (params.get_g()[0], params.g2(), params.s_g2()).into();
+
+        let instances = vec![proof.instances.clone()];
+        let mut transcript =
+            EvmTranscript::<G1Affine, NativeLoader, _, _>::new(proof.proof.as_slice());
+        let plonk_proof =
+            PlonkVerifier::<Gwc<Bn256>>::read_proof(&dk, &protocol, &instances, &mut transcript)
+                .map_err(ProverError::Verification)?;
+        PlonkVerifier::<Gwc<Bn256>>::verify(&dk, &protocol, &instances, &plonk_proof)
+            .map_err(ProverError::Verification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::super_circuit::test::block_1tx;
+    use bus_mapping::{
+        circuit_input_builder::{KeccakCircuitParams, PrecompileEcParams},
+        mock::BlockData,
+    };
+    use eth_types::U64;
+    use ethers_providers::MockProvider;
+
+    #[ignore = "Due to high memory requirement"]
+    #[tokio::test]
+    async fn prove_block_with_fewer_chunks() {
+        let root_k = 26;
+        let circuits_params = FixedCParams {
+            total_chunks: 2,
+            max_txs: 1,
+            max_withdrawals: 5,
+            max_calldata: 32,
+            max_rws: 256,
+            max_copy_rows: 256,
+            max_exp_steps: 256,
+            max_bytecode: 512,
+            max_evm_rows: 1 << 12,
+            max_keccak_rows: 0,
+            keccak_params: KeccakCircuitParams::default(),
+            max_vertical_circuit_rows: 0,
+            max_ec_ops: PrecompileEcParams {
+                ec_add: 0,
+                ec_mul: 0,
+                ec_pairing: 0,
+            },
+        };
+        let dir = std::env::temp_dir().join(format!("prover_{}", std::process::id()));
+        let cache = KeyCache::new(&dir);
+        cache.insecure_params(root_k).unwrap();
+
+        let provider = MockProvider::new();
+        provider.push(U64::from(1)).unwrap(); // eth_chainId
+        let mut prover = BlockProver::new(
+            GethClient::new(provider),
+            circuits_params,
+            FeatureConfig::default(),
+            cache,
+            root_k,
+        )
+        .await
+        .unwrap();
+
+        // the block fits in a single chunk out of the two configured
+        let block = block_1tx();
+        let builder = BlockData::new_from_geth_data_with_params(block.clone(), circuits_params)
+            .new_circuit_input_builder()
+            .handle_block_with_planned_chunks(&block.eth_block, &block.geth_traces)
+            .unwrap();
+        assert_eq!(builder.chunks.len(), 1);
+
+        let proof = prover.prove_inputs(&builder, 1).unwrap();
+        assert_eq!(proof.num_chunks, 1);
+        let block_hash = block.eth_block.hash.unwrap();
+        prover.verify_block(&proof, block_hash).unwrap();
+
+        // the proof is bound to the hash of the block
+        assert!(matches!(
+            prover.verify_block(&proof, H256::zero()),
+            Err(ProverError::WrongBlock(1))
+        ));
+
+        // the instances must hold the accumulator
+        let truncated = BlockProof {
+            instances: proof.instances[..4 * LIMBS - 1].to_vec(),
+            ..proof
+        };
+        assert!(matches!(
+            prover.verify_block(&truncated, block_hash),
+            Err(ProverError::Verification(_))
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
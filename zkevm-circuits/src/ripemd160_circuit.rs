@@ -0,0 +1,707 @@
+//! The RIPEMD-160 circuit implementation.
+//!
+//! Every 64-byte block of a padded input is processed in `NUM_ROWS_PER_BLOCK`
+//! rows: 5 rows that load the hash value, 80 rows that each do a step of both
+//! the left and the right line (the first 16 of which also absorb the message
+//! words) and 5 rows that combine both lines into the new hash value. All words
+//! are stored as bits, one bit per column.
+//!
+//! A step only outputs a single new word T, the other words of the state are
+//! (rotated) outputs of the previous steps, so every step row only stores T
+//! and the sum before the rotation, for both lines.
+mod param;
+mod util;
+mod witness;
+
+#[cfg(any(test, feature = "test-circuits"))]
+mod dev;
+#[cfg(test)]
+mod test;
+#[cfg(feature = "test-circuits")]
+pub use dev::Ripemd160Circuit as TestRipemd160Circuit;
+
+use self::{
+    param::*,
+    util::{decode, f, query_bits, query_word, rotate_left, to_le_bytes},
+    witness::{multi_ripemd160, num_blocks, to_slots, RipemdRow},
+};
+use crate::{
+    evm_circuit::util::constraint_builder::{BaseConstraintBuilder, ConstrainBuilderCommon},
+    table::{HashTable, LookupTable, Ripemd160Table},
+    util::{Challenges, SubCircuit, SubCircuitConfig},
+    witness::{Block, Chunk},
+};
+use eth_types::Field;
+use gadgets::util::{not, select, sum, Expr};
+use halo2_proofs::{
+    circuit::{AssignedCell, Layouter, Region, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Fixed, SecondPhase},
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+/// Ripemd160CircuitConfig
+#[derive(Clone, Debug)]
+pub struct Ripemd160CircuitConfig<F> {
+    q_enable: Column<Fixed>,
+    q_first: Column<Fixed>,
+    q_start: Column<Fixed>,
+    q_step: Column<Fixed>,
+    q_rounds: [Column<Fixed>; NUM_ROUNDS],
+    q_rotations_left: [Column<Fixed>; NUM_ROTATIONS],
+    q_rotations_right: [Column<Fixed>; NUM_ROTATIONS],
+    q_absorb: Column<Fixed>,
+    q_padding_last: Column<Fixed>,
+    q_ends: [Column<Fixed>; NUM_END_ROWS],
+    t_left: [Column<Advice>; NUM_BITS_PER_WORD_T],
+    s_left: [Column<Advice>; NUM_BITS_PER_WORD_SUM],
+    t_right: [Column<Advice>; NUM_BITS_PER_WORD_T],
+    s_right: [Column<Advice>; NUM_BITS_PER_WORD_SUM],
+    x_bits: [Column<Advice>; NUM_BITS_PER_WORD],
+    x_left: Column<Advice>,
+    x_right: Column<Advice>,
+    is_paddings: [Column<Advice>; NUM_BYTES_PER_WORD],
+    intermediary_data_rlcs: [Column<Advice>; NUM_BYTES_PER_WORD - 1],
+    /// The columns for other circuits to lookup RIPEMD-160 hash results
+    pub ripemd160_table: Ripemd160Table,
+    _marker: PhantomData<F>,
+}
+
+/// Circuit configuration arguments
+pub struct Ripemd160CircuitConfigArgs<F: Field> {
+    /// Ripemd160Table
+    pub ripemd160_table: Ripemd160Table,
+    /// Challenges randomness
+    pub challenges: Challenges<Expression<F>>,
+}
+
+impl<F: Field> SubCircuitConfig<F> for Ripemd160CircuitConfig<F> {
+    type ConfigArgs = Ripemd160CircuitConfigArgs<F>;
+
+    /// Return a new Ripemd160CircuitConfig
+    fn new(
+        meta: &mut ConstraintSystem<F>,
+        Self::ConfigArgs {
+            ripemd160_table,
+            challenges,
+        }: Self::ConfigArgs,
+    ) -> Self {
+        let q_enable = meta.fixed_column();
+        let q_first = meta.fixed_column();
+        let q_start = meta.fixed_column();
+        let q_step = meta.fixed_column();
+        let q_rounds = [(); NUM_ROUNDS].map(|_| meta.fixed_column());
+        let q_rotations_left = [(); NUM_ROTATIONS].map(|_| meta.fixed_column());
+        let q_rotations_right = [(); NUM_ROTATIONS].map(|_| meta.fixed_column());
+        let q_absorb = meta.fixed_column();
+        let q_padding_last = meta.fixed_column();
+        let q_ends = [(); NUM_END_ROWS].map(|_| meta.fixed_column());
+        let t_left = [(); NUM_BITS_PER_WORD_T].map(|_| meta.advice_column());
+        let s_left = [(); NUM_BITS_PER_WORD_SUM].map(|_| meta.advice_column());
+        let t_right = [(); NUM_BITS_PER_WORD_T].map(|_| meta.advice_column());
+        let s_right = [(); NUM_BITS_PER_WORD_SUM].map(|_| meta.advice_column());
+        let x_bits = [(); NUM_BITS_PER_WORD].map(|_| meta.advice_column());
+        let x_left = meta.advice_column();
+        let x_right = meta.advice_column();
+        let is_paddings = [(); NUM_BYTES_PER_WORD].map(|_| meta.advice_column());
+        let intermediary_data_rlcs =
+            [(); NUM_BYTES_PER_WORD - 1].map(|_| meta.advice_column_in(SecondPhase));
+        let is_final = ripemd160_table.is_enabled;
+        let length = ripemd160_table.input_len;
+        let data_rlc = ripemd160_table.input_rlc;
+        let output_rlc = ripemd160_table.output_rlc;
+        let last_is_padding = is_paddings[NUM_BYTES_PER_WORD - 1];
+        let q_squeeze = q_ends[NUM_END_ROWS - 1];
+        let r = challenges.keccak_input();
+
+        // The message words are copied to the steps that use them
+        meta.enable_equality(x_left);
+        meta.enable_equality(x_right);
+
+        meta.create_gate("boolean checks", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            for column in t_left
+                .iter()
+                .chain(s_left.iter())
+                .chain(t_right.iter())
+                .chain(s_right.iter())
+                .chain(x_bits.iter())
+                .chain(is_paddings.iter())
+            {
+                cb.require_boolean("bit", meta.query_advice(*column, Rotation::cur()));
+            }
+            cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+        });
+
+        // The sum before the rotation: s = A + f(B, C, D) + X + K, with
+        // A = rol10(T[-5]), B = T[-1], C = T[-2] and D = rol10(T[-3]).
+        // The right line uses the round functions in reverse order.
+        for (round, q_round) in q_rounds.iter().enumerate() {
+            meta.create_gate("round", |meta| {
+                let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+                for (t_bits, s_bits, x, k, f_round) in [
+                    (&t_left, &s_left, x_left, K_LEFT[round], round),
+                    (
+                        &t_right,
+                        &s_right,
+                        x_right,
+                        K_RIGHT[round],
+                        NUM_ROUNDS - 1 - round,
+                    ),
+                ] {
+                    let sum = decode::expr(&query_bits(meta, s_bits, 0));
+                    let a = rotate_left(&query_word(meta, t_bits, -5), 10);
+                    let b = query_word(meta, t_bits, -1);
+                    let c = query_word(meta, t_bits, -2);
+                    let d = rotate_left(&query_word(meta, t_bits, -3), 10);
+                    cb.require_equal(
+                        "s = A + f(B, C, D) + X + K",
+                        sum,
+                        decode::expr(&a)
+                            + decode::expr(&f(f_round, &b, &c, &d))
+                            + meta.query_advice(x, Rotation::cur())
+                            + (k as u64).expr(),
+                    );
+                }
+                cb.gate(meta.query_fixed(*q_round, Rotation::cur()))
+            });
+        }
+
+        // T = rol_s(s) + E, with E = rol10(T[-4])
+        meta.create_gate("rotate", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            for (t_bits, s_bits, q_rotations) in [
+                (&t_left, &s_left, &q_rotations_left),
+                (&t_right, &s_right, &q_rotations_right),
+            ] {
+                let s_word = query_word(meta, s_bits, 0);
+                let rotated = sum::expr(q_rotations.iter().enumerate().map(|(idx, q_rotation)| {
+                    meta.query_fixed(*q_rotation, Rotation::cur())
+                        * decode::expr(&rotate_left(&s_word, MIN_ROTATION + idx))
+                }));
+                let e = rotate_left(&query_word(meta, t_bits, -4), 10);
+                cb.require_equal(
+                    "T = rol_s(s) + E",
+                    decode::expr(&query_bits(meta, t_bits, 0)),
+                    rotated + decode::expr(&e),
+                );
+            }
+            cb.gate(meta.query_fixed(q_step, Rotation::cur()))
+        });
+
+        // The start rows contain the hash value as (T[-5], ..., T[-1]) for both
+        // lines. It's either the IV when a new hash starts, or the hash value of
+        // the end rows of the previous block.
+        meta.create_gate("start", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let q_first = meta.query_fixed(q_first, Rotation(-(START_ROW as i32)));
+            let start_new_hash = q_first.clone()
+                + not::expr(q_first.clone())
+                    * meta.query_advice(is_final, Rotation(-(START_ROW as i32) - 1));
+            let iv = to_slots(H);
+            for k in 0..NUM_START_ROWS {
+                let rot = -(k as i32);
+                let left = decode::expr(&query_word(meta, &t_left, rot));
+                let previous = decode::expr(&query_word(meta, &s_left, rot - NUM_END_ROWS as i32));
+                cb.require_equal(
+                    "hash value",
+                    left.clone(),
+                    select::expr(
+                        start_new_hash.clone(),
+                        (iv[START_ROW - k] as u64).expr(),
+                        previous,
+                    ),
+                );
+                cb.require_equal(
+                    "both lines start with the same hash value",
+                    decode::expr(&query_word(meta, &t_right, rot)),
+                    left,
+                );
+            }
+            cb.require_equal(
+                "padding continues from the previous block",
+                meta.query_advice(last_is_padding, Rotation::cur()),
+                not::expr(start_new_hash.clone())
+                    * meta.query_advice(
+                        last_is_padding,
+                        Rotation(-((NUM_ROWS_PER_BLOCK + START_ROW + 1 - ABSORB_END_ROW) as i32)),
+                    ),
+            );
+            for column in [length, data_rlc] {
+                cb.require_equal(
+                    "absorbed data is reset or continues from the previous block",
+                    meta.query_advice(column, Rotation::cur()),
+                    not::expr(start_new_hash.clone()) * meta.query_advice(column, Rotation::prev()),
+                );
+            }
+            cb.gate(meta.query_fixed(q_start, Rotation::cur()))
+        });
+
+        meta.create_gate("first row", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            cb.require_zero("length", meta.query_advice(length, Rotation::cur()));
+            cb.require_zero("data_rlc", meta.query_advice(data_rlc, Rotation::cur()));
+            cb.gate(meta.query_fixed(q_first, Rotation::cur()))
+        });
+
+        meta.create_gate("carry absorbed data", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            for column in [length, data_rlc] {
+                cb.require_equal(
+                    "absorbed data stays the same",
+                    meta.query_advice(column, Rotation::cur()),
+                    meta.query_advice(column, Rotation::prev()),
+                );
+            }
+            cb.gate(
+                meta.query_fixed(q_enable, Rotation::cur())
+                    - meta.query_fixed(q_first, Rotation::cur())
+                    - meta.query_fixed(q_start, Rotation::cur())
+                    - meta.query_fixed(q_absorb, Rotation::cur()),
+            )
+        });
+
+        // Absorb the 4 little-endian bytes of the message word X
+        meta.create_gate("absorb", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let x_word = query_bits(meta, &x_bits, 0);
+            cb.require_equal(
+                "message word",
+                meta.query_advice(x_left, Rotation::cur()),
+                decode::expr(&x_word),
+            );
+            let bytes = to_le_bytes(&x_word);
+            let is_paddings = is_paddings.map(|column| meta.query_advice(column, Rotation::cur()));
+            let data_rlcs = intermediary_data_rlcs
+                .map(|column| meta.query_advice(column, Rotation::cur()))
+                .into_iter()
+                .chain(std::iter::once(
+                    meta.query_advice(data_rlc, Rotation::cur()),
+                ));
+            // The length is stored in the last two absorb rows of the final block
+            let is_length_row = meta.query_fixed(q_padding_last, Rotation::cur())
+                * meta.query_advice(last_is_padding, Rotation(-2))
+                + meta.query_fixed(q_padding_last, Rotation::next())
+                    * meta.query_advice(last_is_padding, Rotation::prev());
+
+            let mut prev_is_padding = meta.query_advice(last_is_padding, Rotation::prev());
+            let mut prev_data_rlc = meta.query_advice(data_rlc, Rotation::prev());
+            for ((byte, is_padding), data_rlc) in
+                bytes.iter().zip(is_paddings.iter()).zip(data_rlcs)
+            {
+                cb.require_zero(
+                    "padding is monotonic",
+                    prev_is_padding.clone() * not::expr(is_padding),
+                );
+                cb.require_zero(
+                    "first padding byte is 0x80",
+                    (is_padding.clone() - prev_is_padding.clone()) * (byte.clone() - 0x80.expr()),
+                );
+                cb.require_zero(
+                    "other padding bytes are zero",
+                    prev_is_padding.clone() * not::expr(is_length_row.clone()) * byte.clone(),
+                );
+                cb.require_equal(
+                    "data_rlc absorbs the non-padding bytes",
+                    data_rlc.clone(),
+                    select::expr(
+                        is_padding.clone(),
+                        prev_data_rlc.clone(),
+                        prev_data_rlc * r.clone() + byte.clone(),
+                    ),
+                );
+                prev_is_padding = is_padding.clone();
+                prev_data_rlc = data_rlc;
+            }
+            cb.require_equal(
+                "length increases by the number of non-padding bytes",
+                meta.query_advice(length, Rotation::cur()),
+                meta.query_advice(length, Rotation::prev())
+                    + sum::expr(is_paddings.iter().map(not::expr)),
+            );
+            cb.gate(meta.query_fixed(q_absorb, Rotation::cur()))
+        });
+
+        // The last 8 bytes of the final block are the little-endian length in bits
+        meta.create_gate("length", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let is_final_block = meta.query_advice(
+                last_is_padding,
+                Rotation(FINAL_BLOCK_CHECK_ROW as i32 - (ABSORB_END_ROW - 1) as i32),
+            );
+            let length_in_bits = decode::expr(&query_bits(meta, &x_bits, 0))
+                * (1u64 << NUM_BITS_PER_WORD).expr()
+                + decode::expr(&query_bits(meta, &x_bits, -1));
+            let length = meta.query_advice(length, Rotation::cur());
+            cb.condition(is_final_block, |cb| {
+                cb.require_equal("length in bits", length_in_bits, length * 8.expr());
+            });
+            cb.gate(meta.query_fixed(q_padding_last, Rotation::cur()))
+        });
+
+        // The new hash value combines the hash value with the final states of
+        // both lines and is stored in the end rows in the same way as in the
+        // start rows:
+        //   ror10(h0') = ror10(h1 + T_78 + rol10(T'_77))
+        //   ror10(h4') = ror10(h0 + T_79 + T'_78)
+        //   ror10(h3') = ror10(h4 + rol10(T_75) + T'_79)
+        //   h2' = h3 + rol10(T_76) + rol10(T'_75)
+        //   h1' = h2 + rol10(T_77) + rol10(T'_76)
+        // Each end row is given as whether its word is rotated, followed by the
+        // start row of h, the step of the left line and the step of the right
+        // line, each with whether they are rotated.
+        let end_rows = [
+            (true, (4, false), (78, false), (77, true)),
+            (true, (0, true), (79, false), (78, false)),
+            (true, (1, true), (75, true), (79, false)),
+            (false, (2, true), (76, true), (75, true)),
+            (false, (3, false), (77, true), (76, true)),
+        ];
+        for (idx, (q_end, (is_rotated, hash, left, right))) in
+            q_ends.iter().zip(end_rows).enumerate()
+        {
+            meta.create_gate("end", |meta| {
+                let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+                let row = END_START_ROW + idx;
+                let mut word = |columns: &[Column<Advice>], (target, is_rotated): (usize, bool)| {
+                    let bits = query_word(meta, columns, target as i32 - row as i32);
+                    decode::expr(&if is_rotated {
+                        rotate_left(&bits, 10)
+                    } else {
+                        bits
+                    })
+                };
+                let sum = word(&t_left, hash)
+                    + word(&t_left, (STEP_START_ROW + left.0, left.1))
+                    + word(&t_right, (STEP_START_ROW + right.0, right.1));
+                let new_hash = word(&s_left, (row, is_rotated));
+                let carry = decode::expr(&query_bits(meta, &s_left[NUM_BITS_PER_WORD..], 0));
+                cb.require_equal(
+                    "new hash value",
+                    new_hash + carry * (1u64 << NUM_BITS_PER_WORD).expr(),
+                    sum,
+                );
+                cb.gate(meta.query_fixed(*q_end, Rotation::cur()))
+            });
+        }
+
+        meta.create_gate("squeeze", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            cb.require_equal(
+                "is_final when the length fits in this block",
+                meta.query_advice(is_final, Rotation::cur()),
+                meta.query_advice(
+                    last_is_padding,
+                    Rotation(FINAL_BLOCK_CHECK_ROW as i32 - SQUEEZE_ROW as i32),
+                ),
+            );
+            // h0'..h4' are in end rows 0, 4, 3, 2 and 1, rotated in the first 3
+            let mut digest = Vec::new();
+            for (idx, is_rotated) in [(0, true), (4, false), (3, false), (2, true), (1, true)] {
+                let bits = query_word(
+                    meta,
+                    &s_left,
+                    (END_START_ROW + idx) as i32 - SQUEEZE_ROW as i32,
+                );
+                let bits = if is_rotated {
+                    rotate_left(&bits, 10)
+                } else {
+                    bits
+                };
+                digest.extend(to_le_bytes(&bits));
+            }
+            cb.require_equal(
+                "output_rlc",
+                meta.query_advice(output_rlc, Rotation::cur()),
+                digest
+                    .into_iter()
+                    .fold(0.expr(), |acc, byte| acc * r.clone() + byte),
+            );
+            cb.gate(meta.query_fixed(q_squeeze, Rotation::cur()))
+        });
+
+        meta.create_gate("only the squeeze rows are in the table", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            cb.require_zero("is_final", meta.query_advice(is_final, Rotation::cur()));
+            cb.require_zero("output_rlc", meta.query_advice(output_rlc, Rotation::cur()));
+            cb.gate(
+                meta.query_fixed(q_enable, Rotation::cur())
+                    - meta.query_fixed(q_squeeze, Rotation::cur()),
+            )
+        });
+
+        Self {
+            q_enable,
+            q_first,
+            q_start,
+            q_step,
+            q_rounds,
+            q_rotations_left,
+            q_rotations_right,
+            q_absorb,
+            q_padding_last,
+            q_ends,
+            t_left,
+            s_left,
+            t_right,
+            s_right,
+            x_bits,
+            x_left,
+            x_right,
+            is_paddings,
+            intermediary_data_rlcs,
+            ripemd160_table,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: Field> Ripemd160CircuitConfig<F> {
+    pub(crate) fn assign(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        witness: &[RipemdRow<F>],
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "assign ripemd160 rows",
+            |mut region| {
+                let mut x_cells = Vec::with_capacity(witness.len());
+                for (offset, ripemd_row) in witness.iter().enumerate() {
+                    x_cells.push(self.set_row(&mut region, offset, ripemd_row)?);
+                }
+                // Copy the message words to the steps that use them
+                for block in x_cells.chunks(NUM_ROWS_PER_BLOCK) {
+                    let steps = &block[STEP_START_ROW..END_START_ROW];
+                    for (step, (x_left, x_right)) in steps.iter().enumerate() {
+                        // The first steps of the left line use the words they absorb
+                        if R_LEFT[step] != step {
+                            region.constrain_equal(steps[R_LEFT[step]].0.cell(), x_left.cell())?;
+                        }
+                        region.constrain_equal(steps[R_RIGHT[step]].0.cell(), x_right.cell())?;
+                    }
+                }
+                self.ripemd160_table.annotate_columns_in_region(&mut region);
+                self.annotate_circuit(&mut region);
+                Ok(())
+            },
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn set_row(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        row: &RipemdRow<F>,
+    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>), Error> {
+        let round = offset % NUM_ROWS_PER_BLOCK;
+        let step = (STEP_START_ROW..END_START_ROW)
+            .contains(&round)
+            .then(|| round - STEP_START_ROW);
+
+        // Fixed selectors
+        let mut selectors = vec![
+            ("q_enable", self.q_enable, true),
+            ("q_first", self.q_first, offset == 0),
+            ("q_start", self.q_start, round == START_ROW),
+            ("q_step", self.q_step, step.is_some()),
+            (
+                "q_absorb",
+                self.q_absorb,
+                (ABSORB_START_ROW..ABSORB_END_ROW).contains(&round),
+            ),
+            (
+                "q_padding_last",
+                self.q_padding_last,
+                round == ABSORB_END_ROW - 1,
+            ),
+        ];
+        for (idx, q_round) in self.q_rounds.iter().enumerate() {
+            selectors.push((
+                "q_round",
+                *q_round,
+                step.map(|step| step / NUM_STEPS_PER_ROUND) == Some(idx),
+            ));
+        }
+        for (idx, (q_left, q_right)) in self
+            .q_rotations_left
+            .iter()
+            .zip(self.q_rotations_right.iter())
+            .enumerate()
+        {
+            let rotation = MIN_ROTATION + idx;
+            selectors.push((
+                "q_rotation_left",
+                *q_left,
+                step.map(|step| S_LEFT[step]) == Some(rotation),
+            ));
+            selectors.push((
+                "q_rotation_right",
+                *q_right,
+                step.map(|step| S_RIGHT[step]) == Some(rotation),
+            ));
+        }
+        for (idx, q_end) in self.q_ends.iter().enumerate() {
+            selectors.push(("q_end", *q_end, round == END_START_ROW + idx));
+        }
+        for (name, column, value) in selectors {
+            region.assign_fixed(
+                || format!("assign {} {}", name, offset),
+                column,
+                offset,
+                || Value::known(F::from(value as u64)),
+            )?;
+        }
+
+        // Bits
+        for (name, columns, bits) in [
+            ("t_left", &self.t_left[..], &row.t_left[..]),
+            ("s_left", &self.s_left[..], &row.s_left[..]),
+            ("t_right", &self.t_right[..], &row.t_right[..]),
+            ("s_right", &self.s_right[..], &row.s_right[..]),
+            ("x", &self.x_bits[..], &row.x_bits[..]),
+            ("is_padding", &self.is_paddings[..], &row.is_paddings[..]),
+        ] {
+            for (idx, (column, bit)) in columns.iter().zip(bits.iter()).enumerate() {
+                region.assign_advice(
+                    || format!("assign {} {} {}", name, idx, offset),
+                    *column,
+                    offset,
+                    || Value::known(F::from(*bit as u64)),
+                )?;
+            }
+        }
+
+        // Message words
+        let x_left = region.assign_advice(
+            || format!("assign x left {}", offset),
+            self.x_left,
+            offset,
+            || Value::known(F::from(row.x_left as u64)),
+        )?;
+        let x_right = region.assign_advice(
+            || format!("assign x right {}", offset),
+            self.x_right,
+            offset,
+            || Value::known(F::from(row.x_right as u64)),
+        )?;
+
+        for (idx, (column, data_rlc)) in self
+            .intermediary_data_rlcs
+            .iter()
+            .zip(row.intermediary_data_rlcs.iter())
+            .enumerate()
+        {
+            region.assign_advice(
+                || format!("assign data rlc {} {}", idx, offset),
+                *column,
+                offset,
+                || *data_rlc,
+            )?;
+        }
+
+        self.ripemd160_table.assign_row(
+            region,
+            offset,
+            [
+                Value::known(F::from(row.is_final as u64)),
+                row.data_rlc,
+                Value::known(F::from(row.length as u64)),
+                row.output_rlc,
+            ],
+        )?;
+
+        Ok((x_left, x_right))
+    }
+
+    fn annotate_circuit(&self, region: &mut Region<F>) {
+        region.name_column(|| "RIPEMD160_q_enable", self.q_enable);
+        region.name_column(|| "RIPEMD160_q_first", self.q_first);
+        region.name_column(|| "RIPEMD160_q_start", self.q_start);
+        region.name_column(|| "RIPEMD160_q_step", self.q_step);
+        region.name_column(|| "RIPEMD160_q_absorb", self.q_absorb);
+        region.name_column(|| "RIPEMD160_q_padding_last", self.q_padding_last);
+        region.name_column(|| "RIPEMD160_x_left", self.x_left);
+        region.name_column(|| "RIPEMD160_x_right", self.x_right);
+    }
+}
+
+/// Ripemd160Circuit
+#[derive(Default, Clone, Debug)]
+pub struct Ripemd160Circuit<F: Field> {
+    inputs: Vec<Vec<u8>>,
+    num_rows: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> SubCircuit<F> for Ripemd160Circuit<F> {
+    type Config = Ripemd160CircuitConfig<F>;
+
+    fn unusable_rows() -> usize {
+        // The bits of `s_left` are queried at 10 distinct rotations, so returns
+        // (10 + 3) unusable rows.
+        13
+    }
+
+    /// The `chunk.fixed_param.max_vertical_circuit_rows` parameter, when set,
+    /// pads the circuit to a fixed number of blocks, independently of the
+    /// blocks required by the inputs.
+    fn new_from_block(block: &Block<F>, chunk: &Chunk<F>) -> Self {
+        Self::new(
+            chunk.fixed_param.max_vertical_circuit_rows,
+            block.precompile_events.get_ripemd160_events(),
+        )
+    }
+
+    /// Return the minimum number of rows required to prove the block
+    fn min_num_rows_block(block: &Block<F>, chunk: &Chunk<F>) -> (usize, usize) {
+        (
+            block
+                .precompile_events
+                .get_ripemd160_events()
+                .iter()
+                .map(|input| num_blocks(input.len()) * NUM_ROWS_PER_BLOCK)
+                .sum(),
+            chunk.fixed_param.max_vertical_circuit_rows,
+        )
+    }
+
+    /// Make the assignments to the Ripemd160Circuit
+    fn synthesize_sub(
+        &self,
+        config: &Self::Config,
+        challenges: &Challenges<Value<F>>,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let witness = self.generate_witness(*challenges)?;
+        config.assign(layouter, witness.as_slice())
+    }
+}
+
+impl<F: Field> Ripemd160Circuit<F> {
+    /// Creates a new circuit instance
+    pub fn new(num_rows: usize, inputs: Vec<Vec<u8>>) -> Self {
+        Ripemd160Circuit {
+            inputs,
+            num_rows,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The number of blocks that can be hashed in this circuit
+    pub fn capacity(&self) -> Option<usize> {
+        if self.num_rows > 0 {
+            Some(self.num_rows.saturating_sub(Self::unusable_rows()) / NUM_ROWS_PER_BLOCK)
+        } else {
+            None
+        }
+    }
+
+    /// Sets the witness using the data to be hashed
+    pub(crate) fn generate_witness(
+        &self,
+        challenges: Challenges<Value<F>>,
+    ) -> Result<Vec<RipemdRow<F>>, Error> {
+        multi_ripemd160(self.inputs.as_slice(), challenges, self.capacity())
+    }
+}
@@ -0,0 +1,15 @@
+pub use super::Ripemd160Circuit;
+
+use crate::{
+    impl_hash_circuit,
+    ripemd160_circuit::{Ripemd160CircuitConfig, Ripemd160CircuitConfigArgs},
+    table::Ripemd160Table,
+};
+
+impl_hash_circuit!(
+    Ripemd160Circuit,
+    Ripemd160CircuitConfig,
+    Ripemd160CircuitConfigArgs,
+    Ripemd160Table,
+    ripemd160_table
+);
@@ -0,0 +1,78 @@
+pub(crate) const NUM_BITS_PER_BYTE: usize = 8;
+pub(crate) const NUM_BYTES_PER_WORD: usize = 4;
+pub(crate) const NUM_BITS_PER_WORD: usize = NUM_BYTES_PER_WORD * NUM_BITS_PER_BYTE;
+/// T is the sum of 2 words, so it needs 1 extra bit for the carry
+pub(crate) const NUM_BITS_PER_WORD_T: usize = NUM_BITS_PER_WORD + 1;
+/// The sum before the rotation adds 4 words, so it needs 2 extra bits for the carry
+pub(crate) const NUM_BITS_PER_WORD_SUM: usize = NUM_BITS_PER_WORD + 2;
+pub(crate) const NUM_ROUNDS: usize = 5;
+pub(crate) const NUM_STEPS_PER_ROUND: usize = 16;
+pub(crate) const NUM_STEPS: usize = NUM_ROUNDS * NUM_STEPS_PER_ROUND;
+pub(crate) const NUM_WORDS_TO_ABSORB: usize = 16;
+pub(crate) const RATE: usize = NUM_WORDS_TO_ABSORB * NUM_BYTES_PER_WORD;
+pub(crate) const NUM_PADDING_LENGTH_BYTES: usize = 8;
+pub(crate) const NUM_WORDS_PER_STATE: usize = 5;
+pub(crate) const NUM_START_ROWS: usize = NUM_WORDS_PER_STATE;
+pub(crate) const NUM_END_ROWS: usize = NUM_WORDS_PER_STATE;
+pub(crate) const NUM_ROWS_PER_BLOCK: usize = NUM_START_ROWS + NUM_STEPS + NUM_END_ROWS;
+pub(crate) const MAX_DEGREE: usize = 5;
+
+/// The rotations applied in the steps are all in this range
+pub(crate) const MIN_ROTATION: usize = 5;
+pub(crate) const MAX_ROTATION: usize = 15;
+pub(crate) const NUM_ROTATIONS: usize = MAX_ROTATION - MIN_ROTATION + 1;
+
+/// Offsets of the rows inside a block
+pub(crate) const START_ROW: usize = NUM_START_ROWS - 1;
+pub(crate) const STEP_START_ROW: usize = NUM_START_ROWS;
+pub(crate) const ABSORB_START_ROW: usize = STEP_START_ROW;
+pub(crate) const ABSORB_END_ROW: usize = ABSORB_START_ROW + NUM_WORDS_TO_ABSORB;
+/// The row holding bytes 52..56 of a block: when byte 55 is already padding,
+/// the block is the final one and its last 8 bytes are the length
+pub(crate) const FINAL_BLOCK_CHECK_ROW: usize = ABSORB_END_ROW - 3;
+pub(crate) const END_START_ROW: usize = STEP_START_ROW + NUM_STEPS;
+pub(crate) const SQUEEZE_ROW: usize = NUM_ROWS_PER_BLOCK - 1;
+
+pub(crate) const H: [u32; NUM_WORDS_PER_STATE] =
+    [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+pub(crate) const K_LEFT: [u32; NUM_ROUNDS] =
+    [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+pub(crate) const K_RIGHT: [u32; NUM_ROUNDS] =
+    [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+/// Message word selection of the left line
+pub(crate) const R_LEFT: [usize; NUM_STEPS] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, //
+    7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8, //
+    3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12, //
+    1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2, //
+    4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13, //
+];
+
+/// Message word selection of the right line
+pub(crate) const R_RIGHT: [usize; NUM_STEPS] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12, //
+    6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2, //
+    15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13, //
+    8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14, //
+    12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11, //
+];
+
+/// Rotation amounts of the left line
+pub(crate) const S_LEFT: [usize; NUM_STEPS] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8, //
+    7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12, //
+    11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5, //
+    11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12, //
+    9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6, //
+];
+
+/// Rotation amounts of the right line
+pub(crate) const S_RIGHT: [usize; NUM_STEPS] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6, //
+    9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11, //
+    9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5, //
+    15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8, //
+    8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11, //
+];
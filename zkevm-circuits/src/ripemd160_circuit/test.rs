@@ -0,0 +1,41 @@
+use super::*;
+use crate::{
+    table::hash_table::test::{check_variadic_size, verify_hash_circuit},
+    util::unusable_rows,
+};
+use halo2_proofs::halo2curves::bn256::Fr;
+
+#[test]
+fn ripemd160_circuit_unusable_rows() {
+    assert_eq!(
+        Ripemd160Circuit::<Fr>::unusable_rows(),
+        unusable_rows::<Fr, Ripemd160Circuit::<Fr>>(()),
+    )
+}
+
+fn verify<F: Field>(k: u32, inputs: Vec<Vec<u8>>, success: bool) {
+    let circuit = Ripemd160Circuit::new(2usize.pow(k), inputs.clone());
+    verify_hash_circuit::<F, _, _, _>(k, &circuit, &inputs, Some(&[]), success, |config| {
+        &config.ripemd160_table
+    });
+}
+
+#[test]
+fn ripemd160_circuit_simple() {
+    let k = 11;
+    let inputs = vec![
+        vec![],
+        b"abc".to_vec(),
+        (0u8..55).collect::<Vec<_>>(),
+        (0u8..56).collect::<Vec<_>>(),
+        (0u8..64).collect::<Vec<_>>(),
+        (0..200).map(|i| (1 + 3 * i) as u8).collect::<Vec<_>>(),
+    ];
+    verify::<Fr>(k, inputs, true);
+}
+
+#[test]
+fn ripemd160_circuit_variadic_size_check() {
+    let inputs = vec![vec![], (0u8..1).collect::<Vec<_>>(), (0u8..120).collect()];
+    check_variadic_size::<Fr, _>(11, Ripemd160Circuit::new, inputs);
+}
@@ -0,0 +1,56 @@
+pub(crate) use crate::sha256_circuit::util::{decode, into_bits, query_bits, query_word};
+use eth_types::Field;
+use gadgets::util::{not, xor, Expr};
+use halo2_proofs::plonk::Expression;
+
+/// Rotate little-endian bits to the left.
+pub(crate) fn rotate_left<F: Field>(bits: &[Expression<F>], count: usize) -> Vec<Expression<F>> {
+    (0..bits.len())
+        .map(|idx| bits[(idx + bits.len() - count) % bits.len()].clone())
+        .collect()
+}
+
+/// The bitwise boolean function of the given round.
+///
+/// The left line uses the functions in order, the right line in reverse order.
+pub(crate) fn f<F: Field>(
+    round: usize,
+    x: &[Expression<F>],
+    y: &[Expression<F>],
+    z: &[Expression<F>],
+) -> Vec<Expression<F>> {
+    x.iter()
+        .zip(y)
+        .zip(z)
+        .map(|((x, y), z)| match round {
+            // x ^ y ^ z
+            0 => xor::expr(xor::expr(x, y), z.clone()),
+            // (x & y) | (!x & z)
+            1 => x.clone() * y.clone() + not::expr(x) * z.clone(),
+            // (x | !y) ^ z
+            2 => xor::expr(1.expr() - y.clone() + x.clone() * y.clone(), z.clone()),
+            // (x & z) | (y & !z)
+            3 => x.clone() * z.clone() + y.clone() * not::expr(z),
+            // x ^ (y | !z)
+            4 => xor::expr(x.clone(), 1.expr() - z.clone() + y.clone() * z.clone()),
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// The bitwise boolean function of the given round on native words.
+pub(crate) fn f_value(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        4 => x ^ (y | !z),
+        _ => unreachable!(),
+    }
+}
+
+/// Little-endian bytes of a 32-bit word given as little-endian bits.
+pub(crate) fn to_le_bytes<F: Field>(bits: &[Expression<F>]) -> Vec<Expression<F>> {
+    bits.chunks(8).map(decode::expr).collect()
+}
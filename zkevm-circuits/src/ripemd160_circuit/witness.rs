@@ -0,0 +1,236 @@
+use super::{
+    param::*,
+    util::{f_value, into_bits},
+};
+use crate::util::Challenges;
+use eth_types::Field;
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+/// Ripemd160Row
+#[derive(Clone, Debug)]
+pub(crate) struct RipemdRow<F> {
+    pub(crate) t_left: [bool; NUM_BITS_PER_WORD_T],
+    pub(crate) s_left: [bool; NUM_BITS_PER_WORD_SUM],
+    pub(crate) t_right: [bool; NUM_BITS_PER_WORD_T],
+    pub(crate) s_right: [bool; NUM_BITS_PER_WORD_SUM],
+    pub(crate) x_bits: [bool; NUM_BITS_PER_WORD],
+    pub(crate) x_left: u32,
+    pub(crate) x_right: u32,
+    pub(crate) is_final: bool,
+    pub(crate) length: usize,
+    pub(crate) data_rlc: Value<F>,
+    pub(crate) intermediary_data_rlcs: [Value<F>; NUM_BYTES_PER_WORD - 1],
+    pub(crate) is_paddings: [bool; NUM_BYTES_PER_WORD],
+    pub(crate) output_rlc: Value<F>,
+}
+
+impl<F: Field> RipemdRow<F> {
+    fn new(length: usize, data_rlc: Value<F>) -> Self {
+        Self {
+            t_left: [false; NUM_BITS_PER_WORD_T],
+            s_left: [false; NUM_BITS_PER_WORD_SUM],
+            t_right: [false; NUM_BITS_PER_WORD_T],
+            s_right: [false; NUM_BITS_PER_WORD_SUM],
+            x_bits: [false; NUM_BITS_PER_WORD],
+            x_left: 0,
+            x_right: 0,
+            is_final: false,
+            length,
+            data_rlc,
+            intermediary_data_rlcs: [data_rlc; NUM_BYTES_PER_WORD - 1],
+            is_paddings: [false; NUM_BYTES_PER_WORD],
+            output_rlc: Value::known(F::ZERO),
+        }
+    }
+}
+
+/// Number of blocks needed to hash an input of `len` bytes
+pub(crate) fn num_blocks(len: usize) -> usize {
+    (len + NUM_PADDING_LENGTH_BYTES) / RATE + 1
+}
+
+/// The words of the state as they are stored in the start and end rows.
+///
+/// With T_j the output of step j, the state after step j is
+/// (rol10(T_{j-4}), T_j, T_{j-1}, rol10(T_{j-2}), rol10(T_{j-3})), so the
+/// hash value is stored as (T_{-5}, T_{-4}, T_{-3}, T_{-2}, T_{-1}).
+pub(crate) fn to_slots(hs: [u32; NUM_WORDS_PER_STATE]) -> [u32; NUM_WORDS_PER_STATE] {
+    [
+        hs[0].rotate_right(10),
+        hs[4].rotate_right(10),
+        hs[3].rotate_right(10),
+        hs[2],
+        hs[1],
+    ]
+}
+
+fn ripemd160<F: Field>(
+    rows: &mut Vec<RipemdRow<F>>,
+    bytes: &[u8],
+    challenges: Challenges<Value<F>>,
+) {
+    let r = challenges.keccak_input();
+
+    let mut padded = bytes.to_vec();
+    padded.push(0x80);
+    while (padded.len() + NUM_PADDING_LENGTH_BYTES) % RATE != 0 {
+        padded.push(0);
+    }
+    padded.extend_from_slice(&(bytes.len() as u64 * 8).to_le_bytes());
+    let num_blocks = padded.len() / RATE;
+
+    // The start rows of the first block still carry the data of the previous hash
+    let (prev_length, prev_data_rlc) = rows
+        .last()
+        .map(|row| (row.length, row.data_rlc))
+        .unwrap_or((0, Value::known(F::ZERO)));
+
+    let mut hs = H;
+    let mut length = 0;
+    let mut data_rlc = Value::known(F::ZERO);
+    let mut in_padding = false;
+    for (block_idx, block) in padded.chunks(RATE).enumerate() {
+        let is_final_block = block_idx == num_blocks - 1;
+        let xs: Vec<u32> = block
+            .chunks(NUM_BYTES_PER_WORD)
+            .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+            .collect();
+
+        // Start
+        let slots = to_slots(hs);
+        for (idx, slot) in slots.iter().enumerate() {
+            let mut row = if block_idx == 0 && idx != START_ROW {
+                RipemdRow::new(prev_length, prev_data_rlc)
+            } else {
+                RipemdRow::new(length, data_rlc)
+            };
+            row.t_left = into_bits(*slot as u64);
+            row.t_right = into_bits(*slot as u64);
+            if idx == START_ROW {
+                row.is_paddings[NUM_BYTES_PER_WORD - 1] = in_padding;
+            }
+            rows.push(row);
+        }
+
+        // Steps
+        let mut t_left = slots.to_vec();
+        let mut t_right = slots.to_vec();
+        for step in 0..NUM_STEPS {
+            let round = step / NUM_STEPS_PER_ROUND;
+            let mut row = RipemdRow::new(length, data_rlc);
+            for (ts, t_bits, s_bits, k, x, s, f_round) in [
+                (
+                    &mut t_left,
+                    &mut row.t_left,
+                    &mut row.s_left,
+                    K_LEFT[round],
+                    xs[R_LEFT[step]],
+                    S_LEFT[step],
+                    round,
+                ),
+                (
+                    &mut t_right,
+                    &mut row.t_right,
+                    &mut row.s_right,
+                    K_RIGHT[round],
+                    xs[R_RIGHT[step]],
+                    S_RIGHT[step],
+                    NUM_ROUNDS - 1 - round,
+                ),
+            ] {
+                let [a, e, d, c, b] = [
+                    ts[step].rotate_left(10),
+                    ts[step + 1].rotate_left(10),
+                    ts[step + 2].rotate_left(10),
+                    ts[step + 3],
+                    ts[step + 4],
+                ];
+                let sum = a as u64 + f_value(f_round, b, c, d) as u64 + x as u64 + k as u64;
+                let t = (sum as u32).rotate_left(s as u32) as u64 + e as u64;
+                *s_bits = into_bits(sum);
+                *t_bits = into_bits(t);
+                ts.push(t as u32);
+            }
+            row.x_left = xs[R_LEFT[step]];
+            row.x_right = xs[R_RIGHT[step]];
+
+            // Absorb
+            if step < NUM_WORDS_TO_ABSORB {
+                row.x_bits = into_bits(xs[step] as u64);
+                for (idx, byte) in xs[step].to_le_bytes().into_iter().enumerate() {
+                    let position = block_idx * RATE + step * NUM_BYTES_PER_WORD + idx;
+                    let is_padding = position >= bytes.len();
+                    row.is_paddings[idx] = is_padding;
+                    if !is_padding {
+                        length += 1;
+                        data_rlc = data_rlc * r + Value::known(F::from(byte as u64));
+                    }
+                    if idx < NUM_BYTES_PER_WORD - 1 {
+                        row.intermediary_data_rlcs[idx] = data_rlc;
+                    }
+                }
+                row.length = length;
+                row.data_rlc = data_rlc;
+                in_padding = row.is_paddings[NUM_BYTES_PER_WORD - 1];
+            }
+            rows.push(row);
+        }
+
+        // End
+        let t = |j: usize| t_left[j + NUM_START_ROWS];
+        let t_r = |j: usize| t_right[j + NUM_START_ROWS];
+        let rol10 = |v: u32| v.rotate_left(10) as u64;
+        let new_hs: [u64; NUM_WORDS_PER_STATE] = [
+            hs[1] as u64 + t(78) as u64 + rol10(t_r(77)),
+            hs[2] as u64 + rol10(t(77)) + rol10(t_r(76)),
+            hs[3] as u64 + rol10(t(76)) + rol10(t_r(75)),
+            hs[4] as u64 + rol10(t(75)) + t_r(79) as u64,
+            hs[0] as u64 + t(79) as u64 + t_r(78) as u64,
+        ];
+        hs = new_hs.map(|h| h as u32);
+        let slots = to_slots(hs);
+        let carries = [new_hs[0], new_hs[4], new_hs[3], new_hs[2], new_hs[1]].map(|h| h >> 32);
+        for (slot, carry) in slots.iter().zip(carries) {
+            let mut row = RipemdRow::new(length, data_rlc);
+            row.s_left = into_bits(*slot as u64 | (carry << NUM_BITS_PER_WORD));
+            rows.push(row);
+        }
+
+        // Squeeze
+        let digest: Vec<u8> = hs.iter().flat_map(|h| h.to_le_bytes()).collect();
+        let row = rows.last_mut().unwrap();
+        row.is_final = is_final_block;
+        row.output_rlc = r.map(|r| {
+            digest
+                .iter()
+                .fold(F::ZERO, |acc, byte| acc * r + F::from(*byte as u64))
+        });
+    }
+}
+
+pub(crate) fn multi_ripemd160<F: Field>(
+    bytes: &[Vec<u8>],
+    challenges: Challenges<Value<F>>,
+    capacity: Option<usize>,
+) -> Result<Vec<RipemdRow<F>>, Error> {
+    let mut rows: Vec<RipemdRow<F>> = Vec::new();
+    for bytes in bytes {
+        ripemd160(&mut rows, bytes, challenges);
+    }
+    if let Some(capacity) = capacity {
+        // Check that we are not over capacity
+        if rows.len() > capacity * NUM_ROWS_PER_BLOCK {
+            log::error!(
+                "Ripemd160 inputs exceed capacity.  needed_rows = {}, available_rows = {}",
+                rows.len(),
+                capacity * NUM_ROWS_PER_BLOCK
+            );
+            return Err(Error::BoundsFailure);
+        }
+        // Pad with no data hashes to the expected capacity
+        while rows.len() < capacity * NUM_ROWS_PER_BLOCK {
+            ripemd160(&mut rows, &[], challenges);
+        }
+    }
+    Ok(rows)
+}
@@ -22,6 +22,8 @@ use snark_verifier::{
 use std::{marker::PhantomData, rc::Rc};
 
 mod aggregation;
+mod block_aggregation;
+pub mod evm;
 
 #[cfg(any(test, feature = "test-circuits"))]
 mod dev;
@@ -38,12 +40,65 @@ pub use aggregation::{
     aggregate, AggregationConfig, EccChip, Gwc, Halo2Loader, KzgDk, KzgSvk, PlonkSuccinctVerifier,
     PlonkVerifier, PoseidonTranscript, Shplonk, Snark, SnarkWitness, BITS, LIMBS,
 };
+pub use block_aggregation::BlockAggregationCircuit;
 pub use snark_verifier::{
     loader::native::NativeLoader,
     system::halo2::{compile, transcript::evm::EvmTranscript, Config},
 };
 
-const NUM_OF_SUPERCIRCUIT_INSTANCES: usize = 2;
+const NUM_OF_SUPERCIRCUIT_INSTANCES: usize = 10;
+
+/// BlockInstance is the part of the supercircuit instance exposed by the
+/// `RootCircuit`: the digest of the public data of the block, and the lo, hi
+/// words linking the block to its parent.
+#[derive(Clone)]
+struct BlockInstance<T> {
+    pub pi_digest_lo: T,
+    pub pi_digest_hi: T,
+    pub parent_hash_lo: T,
+    pub parent_hash_hi: T,
+    pub block_hash_lo: T,
+    pub block_hash_hi: T,
+    pub state_root_lo: T,
+    pub state_root_hi: T,
+    pub prev_state_root_lo: T,
+    pub prev_state_root_hi: T,
+}
+
+impl<T: Clone + Copy> BlockInstance<T> {
+    /// Construct `BlockInstance` with the first instances of the iterator
+    pub fn new(instances: impl IntoIterator<Item = T>) -> Self {
+        let mut iter_instances = instances.into_iter();
+        Self {
+            pi_digest_lo: iter_instances.next().unwrap(),
+            pi_digest_hi: iter_instances.next().unwrap(),
+            parent_hash_lo: iter_instances.next().unwrap(),
+            parent_hash_hi: iter_instances.next().unwrap(),
+            block_hash_lo: iter_instances.next().unwrap(),
+            block_hash_hi: iter_instances.next().unwrap(),
+            state_root_lo: iter_instances.next().unwrap(),
+            state_root_hi: iter_instances.next().unwrap(),
+            prev_state_root_lo: iter_instances.next().unwrap(),
+            prev_state_root_hi: iter_instances.next().unwrap(),
+        }
+    }
+
+    /// Returns the instances in the order they are exposed
+    pub fn to_vec(&self) -> Vec<T> {
+        vec![
+            self.pi_digest_lo,
+            self.pi_digest_hi,
+            self.parent_hash_lo,
+            self.parent_hash_hi,
+            self.block_hash_lo,
+            self.block_hash_hi,
+            self.state_root_lo,
+            self.state_root_hi,
+            self.prev_state_root_lo,
+            self.prev_state_root_hi,
+        ]
+    }
+}
 
 /// SuperCircuitInstance is to demystifying supercircuit instance to meaningful name.
 #[derive(Clone)]
@@ -56,8 +111,7 @@ struct SuperCircuitInstance<T> {
     pub end_rwc: T,
 
     // pi circuit
-    pub pi_digest_lo: T,
-    pub pi_digest_hi: T,
+    pub block: BlockInstance<T>,
 
     // state circuit
     pub sc_permu_alpha: T,
@@ -82,12 +136,11 @@ impl<T: Clone + Copy> SuperCircuitInstance<T> {
         let mut iter_instances = instances.into_iter();
         Self {
             chunk_index: iter_instances.next().unwrap(),
+            chunk_index_next: iter_instances.next().unwrap(),
             total_chunk: iter_instances.next().unwrap(),
             initial_rwc: iter_instances.next().unwrap(),
-            chunk_index_next: iter_instances.next().unwrap(),
             end_rwc: iter_instances.next().unwrap(),
-            pi_digest_lo: iter_instances.next().unwrap(),
-            pi_digest_hi: iter_instances.next().unwrap(),
+            block: BlockInstance::new(&mut iter_instances),
             sc_permu_alpha: iter_instances.next().unwrap(),
             sc_permu_gamma: iter_instances.next().unwrap(),
             sc_rwtable_row_prev_fingerprint: iter_instances.next().unwrap(),
@@ -114,6 +167,17 @@ pub struct UserChallenge {
 }
 
 /// RootCircuit for aggregating SuperCircuit into a much smaller proof.
+///
+/// The aggregated proofs must be the ones of all the chunks of a block, in
+/// order: the first chunk has index 0, each chunk continues the chunk index, rw
+/// counter and rw table fingerprints of the previous one, and the last chunk
+/// ends at `total_chunk`, which is the number of aggregated proofs. So the
+/// exposed instances stand for the whole block whatever its number of chunks.
+///
+/// The exposed instances are the digest of the public data of the block, and
+/// its parent hash, block hash, state root and previous state root, which let
+/// the [`BlockAggregationCircuit`] chain the proofs of consecutive blocks,
+/// followed by the `4 * LIMBS` accumulator limbs.
 #[derive(Clone)]
 pub struct RootCircuit<'a, M: MultiMillerLoop, As>
 where
@@ -301,35 +365,39 @@ where
                     .map(SuperCircuitInstance::new)
                     .collect::<Vec<SuperCircuitInstance<_>>>();
 
+                // define 0, 1, total_chunk as constant
+                let (zero_const, one_const, total_chunk_const) = {
+                    let zero_const = loader
+                        .scalar_chip()
+                        .assign_constant(&mut loader.ctx_mut(), M::Fr::from(0))
+                        .unwrap();
+                    let one_const = loader
+                        .scalar_chip()
+                        .assign_constant(&mut loader.ctx_mut(), M::Fr::from(1))
+                        .unwrap();
+                    // a root proof covers all the chunks of a block
+                    let total_chunk_const = loader
+                        .scalar_chip()
+                        .assign_constant(
+                            &mut loader.ctx_mut(),
+                            M::Fr::from(self.snark_witnesses.len() as u64),
+                        )
+                        .unwrap();
+
+                    (zero_const, one_const, total_chunk_const)
+                };
+
                 // constraint first and last chunk
                 supercircuit_instances
                     .first()
                     .zip(supercircuit_instances.last())
-                    .map(|(first_chunk, _last)| {
+                    .map(|(first_chunk, last_chunk)| {
                         // `last.sc_rwtable_next_fingerprint ==
                         // last.ec_rwtable_next_fingerprint` will be checked inside super circuit so
                         // here no need to checked
                         // Other field in last instances already be checked in chunk
                         // continuity
 
-                        // define 0, 1, total_chunk as constant
-                        let (zero_const, one_const, total_chunk_const) = {
-                            let zero_const = loader
-                                .scalar_chip()
-                                .assign_constant(&mut loader.ctx_mut(), M::Fr::from(0))
-                                .unwrap();
-                            let one_const = loader
-                                .scalar_chip()
-                                .assign_constant(&mut loader.ctx_mut(), M::Fr::from(1))
-                                .unwrap();
-                            let total_chunk_const = loader
-                                .scalar_chip()
-                                .assign_constant(&mut loader.ctx_mut(), M::Fr::from(1))
-                                .unwrap();
-
-                            (zero_const, one_const, total_chunk_const)
-                        };
-
                         // `first.sc_rwtable_row_prev_fingerprint ==
                         // first.ec_rwtable_row_prev_fingerprint` will be checked inside circuit
                         vec![
@@ -354,6 +422,8 @@ where
                                 first_chunk.sc_rwtable_prev_fingerprint.assigned(),
                                 &one_const,
                             ),
+                            // the last chunk ends the block
+                            (last_chunk.chunk_index_next.assigned(), &total_chunk_const),
                         ]
                         .iter()
                         .for_each(|(a, b)| {
@@ -367,6 +437,27 @@ where
                     })
                     .expect("error");
 
+                // constraint the chunk index increment, as the super circuit only
+                // constrains `chunk_index` against `total_chunk`
+                supercircuit_instances.iter().for_each(|instance| {
+                    let chunk_index_plus_one = loader
+                        .scalar_chip()
+                        .add_constant(
+                            &mut loader.ctx_mut(),
+                            instance.chunk_index.assigned(),
+                            M::Fr::ONE,
+                        )
+                        .unwrap();
+                    loader
+                        .scalar_chip()
+                        .assert_equal(
+                            &mut loader.ctx_mut(),
+                            instance.chunk_index_next.assigned(),
+                            &chunk_index_plus_one,
+                        )
+                        .unwrap();
+                });
+
                 // constraint consistency between chunk
                 supercircuit_instances.iter().tuple_windows().for_each(
                     |(instance_i, instance_i_plus_one)| {
@@ -383,14 +474,6 @@ where
                                 instance_i.end_rwc.assigned(),
                                 instance_i_plus_one.initial_rwc.assigned(),
                             ),
-                            (
-                                instance_i.pi_digest_lo.assigned(),
-                                instance_i_plus_one.pi_digest_lo.assigned(),
-                            ),
-                            (
-                                instance_i.pi_digest_hi.assigned(),
-                                instance_i_plus_one.pi_digest_hi.assigned(),
-                            ),
                             // state circuit
                             (
                                 instance_i.sc_permu_alpha.assigned(),
@@ -430,11 +513,20 @@ where
                                     .assigned(),
                             ),
                         ]
-                        .iter()
+                        .into_iter()
+                        // all the chunks belong to the same block
+                        .chain(
+                            instance_i
+                                .block
+                                .to_vec()
+                                .into_iter()
+                                .zip_eq(instance_i_plus_one.block.to_vec())
+                                .map(|(a, b)| (a.assigned(), b.assigned())),
+                        )
                         .for_each(|(a, b)| {
                             loader
                                 .scalar_chip()
-                                .assert_equal(&mut loader.ctx_mut(), a, b)
+                                .assert_equal(&mut loader.ctx_mut(), &a, &b)
                                 .unwrap();
                         });
                     },
@@ -461,12 +553,19 @@ where
 }
 
 /// get instances to expose
+/// Returns the lo, hi words of the hash of the block proven by a
+/// `RootCircuit` with `instances`, if there are enough of them.
+pub(crate) fn exposed_block_hash<T: Copy>(instances: &[T]) -> Option<[T; 2]> {
+    if instances.len() < NUM_OF_SUPERCIRCUIT_INSTANCES {
+        return None;
+    }
+    let block = BlockInstance::new(instances.iter().copied());
+    Some([block.block_hash_lo, block.block_hash_hi])
+}
+
 fn exposed_instances<T: Copy>(supercircuit_instances: &SuperCircuitInstance<T>) -> Vec<T> {
-    let instances = vec![
-        // pi circuit
-        supercircuit_instances.pi_digest_lo,
-        supercircuit_instances.pi_digest_hi,
-    ];
+    // pi circuit
+    let instances = supercircuit_instances.block.to_vec();
     assert_eq!(NUM_OF_SUPERCIRCUIT_INSTANCES, instances.len());
     instances
 }
@@ -51,8 +51,10 @@ pub type Shplonk<M> = KzgAs<M, Bdfg21>;
 pub type KzgSvk<M> = KzgSuccinctVerifyingKey<<M as Engine>::G1Affine>;
 /// KZG deciding key
 pub type KzgDk<M> = KzgDecidingKey<M>;
-/// Plonk succinct verifier with `KzgAs`
-pub type PlonkSuccinctVerifier<As> = verifier::plonk::PlonkSuccinctVerifier<As>;
+/// Plonk succinct verifier with `KzgAs` and `LimbsEncoding<LIMBS, BITS>`, so
+/// the accumulator in the instances of an aggregation proof is accumulated too.
+pub type PlonkSuccinctVerifier<As> =
+    verifier::plonk::PlonkSuccinctVerifier<As, LimbsEncoding<LIMBS, BITS>>;
 /// Plonk verifier with `KzgAs` and `LimbsEncoding<LIMBS, BITS>`.
 pub type PlonkVerifier<As> = verifier::plonk::PlonkVerifier<As, LimbsEncoding<LIMBS, BITS>>;
 
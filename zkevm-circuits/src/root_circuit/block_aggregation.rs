@@ -0,0 +1,246 @@
+//! Aggregation of the [`RootCircuit`](super::RootCircuit) proofs of a range of
+//! consecutive blocks, to settle them at once.
+
+use super::{
+    aggregate, AggregationConfig, BlockInstance, Halo2Loader, KzgSvk, Snark, SnarkWitness, LIMBS,
+    NUM_OF_SUPERCIRCUIT_INSTANCES,
+};
+use eth_types::Field;
+use halo2_proofs::{
+    arithmetic::Field as Halo2Field,
+    circuit::{Layouter, SimpleFloorPlanner},
+    halo2curves::{serde::SerdeObject, CurveAffine, CurveExt},
+    plonk::{Circuit, ConstraintSystem, Error},
+    poly::{commitment::ParamsProver, kzg::commitment::ParamsKZG},
+};
+use itertools::Itertools;
+use maingate::{MainGateInstructions, RegionCtx};
+use snark_verifier::{
+    loader::native::NativeLoader,
+    pcs::{
+        kzg::{KzgAccumulator, KzgAsProvingKey, KzgAsVerifyingKey, KzgDecidingKey},
+        AccumulationDecider, AccumulationScheme, AccumulationSchemeProver,
+        PolynomialCommitmentScheme,
+    },
+    util::arithmetic::MultiMillerLoop,
+};
+use std::{marker::PhantomData, rc::Rc};
+
+/// Number of instances exposed for the range of blocks, before the digests of
+/// the blocks: the lo, hi words of the parent hash and previous state root of
+/// the first block, and of the block hash and state root of the last block.
+const NUM_OF_RANGE_INSTANCES: usize = 8;
+
+/// BlockAggregationCircuit aggregates the `RootCircuit` proofs of consecutive
+/// blocks into a single accumulator.
+///
+/// Each block must continue the previous one: its parent hash is the block
+/// hash of the previous block, and its previous state root is the state root
+/// of the previous block.  The exposed instances are the parent hash and
+/// previous state root of the first block, the block hash and state root of
+/// the last block, the public data digests of all the blocks, which the
+/// verifier checks against the data of the blocks, and the `4 * LIMBS`
+/// accumulator limbs.
+///
+/// The protocol of the `RootCircuit` must be compiled with its
+/// [`accumulator_indices`](super::RootCircuit::accumulator_indices), and its
+/// proofs created with the [`PoseidonTranscript`](super::PoseidonTranscript).
+#[derive(Clone)]
+pub struct BlockAggregationCircuit<'a, M: MultiMillerLoop, As>
+where
+    M::G1Affine: CurveAffine,
+{
+    svk: KzgSvk<M>,
+    snark_witnesses: Vec<SnarkWitness<'a, M::G1Affine>>,
+    instance: Vec<M::Fr>,
+    _marker: PhantomData<As>,
+}
+
+impl<'a, M, As> BlockAggregationCircuit<'a, M, As>
+where
+    M: MultiMillerLoop,
+    M::Fr: Field,
+    M::G1: CurveExt<AffineExt = M::G1Affine, ScalarExt = M::Fr>,
+    M::G1Affine: SerdeObject + CurveAffine<ScalarExt = M::Fr, CurveExt = M::G1>,
+    M::G2Affine: SerdeObject + CurveAffine,
+    As: PolynomialCommitmentScheme<
+            M::G1Affine,
+            NativeLoader,
+            VerifyingKey = KzgSvk<M>,
+            Output = KzgAccumulator<M::G1Affine, NativeLoader>,
+        > + AccumulationSchemeProver<
+            M::G1Affine,
+            Accumulator = KzgAccumulator<M::G1Affine, NativeLoader>,
+            ProvingKey = KzgAsProvingKey<M::G1Affine>,
+        > + AccumulationDecider<M::G1Affine, NativeLoader, DecidingKey = KzgDecidingKey<M>>,
+{
+    /// Create a `BlockAggregationCircuit` with accumulator computed given the
+    /// `RootCircuit` proofs of the blocks, in order. Returns error if any
+    /// given proof is invalid.
+    pub fn new(
+        params: &ParamsKZG<M>,
+        snarks: Vec<Snark<'a, M::G1Affine>>,
+    ) -> Result<Self, snark_verifier::Error> {
+        assert!(!snarks.is_empty(), "at least one block is aggregated");
+
+        let accumulator_limbs = aggregate::<M, As>(params, snarks.clone())?;
+        let blocks = snarks
+            .iter()
+            .map(|snark| BlockInstance::new(snark.instances[0].iter().copied()))
+            .collect_vec();
+
+        let mut instance = exposed_instances(&blocks);
+        instance.extend(accumulator_limbs);
+
+        Ok(Self {
+            svk: KzgSvk::<M>::new(params.get_g()[0]),
+            snark_witnesses: snarks.into_iter().map_into().collect(),
+            instance,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns accumulator indices in instance columns, which will be in
+    /// the last `4 * LIMBS` rows of instance column in `MainGate`.
+    pub fn accumulator_indices(&self) -> Vec<(usize, usize)> {
+        (self.instance.len() - 4 * LIMBS..self.instance.len())
+            .map(|idx| (0, idx))
+            .collect()
+    }
+
+    /// Returns number of instance
+    pub fn num_instance(&self) -> Vec<usize> {
+        vec![self.instance.len()]
+    }
+
+    /// Returns instance
+    pub fn instance(&self) -> Vec<Vec<M::Fr>> {
+        vec![self.instance.clone()]
+    }
+}
+
+impl<'a, M, As> Circuit<M::Fr> for BlockAggregationCircuit<'a, M, As>
+where
+    M: MultiMillerLoop,
+    M::Fr: Field,
+    M::G1Affine: CurveAffine<ScalarExt = M::Fr>,
+    for<'b> As: PolynomialCommitmentScheme<
+            M::G1Affine,
+            Rc<Halo2Loader<'b, M::G1Affine>>,
+            VerifyingKey = KzgSvk<M>,
+            Output = KzgAccumulator<M::G1Affine, Rc<Halo2Loader<'b, M::G1Affine>>>,
+        > + AccumulationScheme<
+            M::G1Affine,
+            Rc<Halo2Loader<'b, M::G1Affine>>,
+            Accumulator = KzgAccumulator<M::G1Affine, Rc<Halo2Loader<'b, M::G1Affine>>>,
+            VerifyingKey = KzgAsVerifyingKey,
+        >,
+{
+    type Config = AggregationConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            svk: self.svk,
+            snark_witnesses: self
+                .snark_witnesses
+                .iter()
+                .map(|snark_witness| snark_witness.without_witnesses())
+                .collect_vec(),
+            instance: vec![M::Fr::ZERO; self.instance.len()],
+            _marker: PhantomData,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<M::Fr>) -> Self::Config {
+        AggregationConfig::configure::<M::G1Affine>(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<M::Fr>,
+    ) -> Result<(), Error> {
+        config.load_table(&mut layouter)?;
+        let (instances, accumulator_limbs) = layouter.assign_region(
+            || "Aggregate root snarks",
+            |mut region| {
+                config.named_column_in_region(&mut region);
+                let ctx = RegionCtx::new(region, 0);
+                let (loaded_instances, accumulator_limbs, loader, _) =
+                    config.aggregate::<M, As>(ctx, &self.svk, &self.snark_witnesses)?;
+
+                // the instances of a root proof start with the ones of its block,
+                // followed by its accumulator, which is already aggregated
+                let blocks = loaded_instances
+                    .iter()
+                    .map(|instances| {
+                        BlockInstance::new(instances.iter().take(NUM_OF_SUPERCIRCUIT_INSTANCES))
+                    })
+                    .collect_vec();
+
+                // constraint each block to continue the previous one
+                blocks
+                    .iter()
+                    .tuple_windows()
+                    .for_each(|(block_i, block_i_plus_one)| {
+                        [
+                            (block_i.block_hash_lo, block_i_plus_one.parent_hash_lo),
+                            (block_i.block_hash_hi, block_i_plus_one.parent_hash_hi),
+                            (block_i.state_root_lo, block_i_plus_one.prev_state_root_lo),
+                            (block_i.state_root_hi, block_i_plus_one.prev_state_root_hi),
+                        ]
+                        .iter()
+                        .for_each(|(a, b)| {
+                            loader
+                                .scalar_chip()
+                                .assert_equal(&mut loader.ctx_mut(), &a.assigned(), &b.assigned())
+                                .unwrap();
+                        });
+                    });
+
+                Ok((
+                    exposed_instances(&blocks)
+                        .iter()
+                        .map(|instance| instance.assigned().to_owned())
+                        .collect_vec(),
+                    accumulator_limbs,
+                ))
+            },
+        )?;
+
+        // Constrain equality to instance values
+        let main_gate = config.main_gate();
+        for (row, limb) in instances.into_iter().chain(accumulator_limbs).enumerate() {
+            main_gate.expose_public(layouter.namespace(|| ""), limb, row)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// get instances to expose for the range of blocks
+fn exposed_instances<T: Copy>(blocks: &[BlockInstance<T>]) -> Vec<T> {
+    let (first, last) = (blocks.first().unwrap(), blocks.last().unwrap());
+    let range_instances = vec![
+        first.parent_hash_lo,
+        first.parent_hash_hi,
+        first.prev_state_root_lo,
+        first.prev_state_root_hi,
+        last.block_hash_lo,
+        last.block_hash_hi,
+        last.state_root_lo,
+        last.state_root_hi,
+    ];
+    assert_eq!(NUM_OF_RANGE_INSTANCES, range_instances.len());
+
+    range_instances
+        .into_iter()
+        .chain(
+            blocks
+                .iter()
+                .flat_map(|block| [block.pi_digest_lo, block.pi_digest_hi]),
+        )
+        .collect()
+}
@@ -0,0 +1,155 @@
+//! EVM verifier of [`RootCircuit`](super::RootCircuit) proofs, to settle them
+//! on L1.
+//!
+//! The verifier is generated from the params and the verifying key of the
+//! circuit with the `EvmLoader` of `snark-verifier`, and compiled with `solc`,
+//! which must be in `PATH`, see [`gen_evm_verifier`].  The proofs it verifies must be created with
+//! the keccak based [`EvmTranscript`] and GWC19 multiopen, see [`gen_evm_proof`].
+//!
+//! For a `RootCircuit`, the accumulator of the aggregated proofs is decided by
+//! the verifier, so its indices must be given along the instances:
+//! ```ignore
+//! let bytecode = gen_evm_verifier(
+//!     &params,
+//!     pk.get_vk(),
+//!     root_circuit.num_instance(),
+//!     Some(root_circuit.accumulator_indices()),
+//! )?;
+//! ```
+
+use super::{compile, Config, EvmTranscript, Gwc, PlonkVerifier};
+use halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fq, Fr, G1Affine},
+    plonk::{create_proof, Circuit, ProvingKey, VerifyingKey},
+    poly::{
+        commitment::ParamsProver,
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::ProverGWC,
+        },
+    },
+    transcript::TranscriptWriterBuffer,
+};
+use itertools::Itertools;
+use rand::rngs::OsRng;
+use snark_verifier::{loader::evm::EvmLoader, pcs::kzg::KzgDecidingKey, verifier::SnarkVerifier};
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+    rc::Rc,
+};
+
+pub use snark_verifier::loader::evm::encode_calldata;
+
+/// Generate the Solidity source of the verifier of the proofs of the circuit
+/// with verifying key `vk`, with `num_instance` instances per column, and the
+/// accumulator of aggregated proofs, if any, at `accumulator_indices` of the
+/// instances.
+pub fn gen_evm_verifier_solidity(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    num_instance: Vec<usize>,
+    accumulator_indices: Option<Vec<(usize, usize)>>,
+) -> String {
+    let protocol = compile(
+        params,
+        vk,
+        Config::kzg()
+            .with_num_instance(num_instance.clone())
+            .with_accumulator_indices(accumulator_indices),
+    );
+    let dk: KzgDecidingKey<Bn256> = (params.get_g()[0], params.g2(), params.s_g2()).into();
+
+    let loader = EvmLoader::new::<Fq, Fr>();
+    let protocol = protocol.loaded(&loader);
+    let mut transcript = EvmTranscript::<_, Rc<EvmLoader>, _, _>::new(&loader);
+
+    let instances = transcript.load_instances(num_instance);
+    let proof =
+        PlonkVerifier::<Gwc<Bn256>>::read_proof(&dk, &protocol, &instances, &mut transcript)
+            .expect("verifier generation");
+    PlonkVerifier::<Gwc<Bn256>>::verify(&dk, &protocol, &instances, &proof)
+        .expect("verifier generation");
+
+    loader.solidity_code()
+}
+
+/// Generate the deployment bytecode of the verifier, see
+/// [`gen_evm_verifier_solidity`].
+///
+/// The Solidity source is compiled with the `solc` found in `PATH`.  An error
+/// of kind [`io::ErrorKind::NotFound`] is returned if there is none, and an
+/// error with the output of `solc` if the compilation fails.
+pub fn gen_evm_verifier(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    num_instance: Vec<usize>,
+    accumulator_indices: Option<Vec<(usize, usize)>>,
+) -> io::Result<Vec<u8>> {
+    compile_solidity(&gen_evm_verifier_solidity(
+        params,
+        vk,
+        num_instance,
+        accumulator_indices,
+    ))
+}
+
+/// Compile the Solidity `code` with the `solc` in `PATH`, returning its
+/// deployment bytecode.
+fn compile_solidity(code: &str) -> io::Result<Vec<u8>> {
+    let mut solc = Command::new("solc")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .args(["--bin", "-"])
+        .spawn()
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => {
+                io::Error::new(io::ErrorKind::NotFound, "solc is not found in PATH")
+            }
+            _ => err,
+        })?;
+    solc.stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(code.as_bytes())?;
+    let output = solc.wait_with_output()?;
+    if !output.status.success() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!(
+                "solc failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .split_ascii_whitespace()
+        .last()
+        .and_then(|bytecode| hex::decode(bytecode).ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no bytecode in solc output"))
+}
+
+/// Create a proof of `circuit` verifiable by the EVM verifier, to be sent with
+/// its `instances` in the calldata built by [`encode_calldata`].
+pub fn gen_evm_proof<C: Circuit<Fr>>(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<G1Affine>,
+    circuit: C,
+    instances: &[Vec<Fr>],
+) -> Vec<u8> {
+    let instances = instances.iter().map(Vec::as_slice).collect_vec();
+    let mut transcript = TranscriptWriterBuffer::<_, G1Affine, _>::init(Vec::new());
+    create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<_>, _, _, EvmTranscript<_, _, _, _>, _>(
+        params,
+        pk,
+        &[circuit],
+        &[&instances],
+        OsRng,
+        &mut transcript,
+    )
+    .expect("proof generation");
+    transcript.finalize()
+}
@@ -2,8 +2,9 @@ use std::iter;
 
 use crate::{
     root_circuit::{
-        aggregation::test::SnarkOwned, compile, Config, Gwc, PoseidonTranscript, RootCircuit,
-        SnarkWitness, TestAggregationCircuit, UserChallenge,
+        aggregation::test::SnarkOwned, compile, evm, BlockAggregationCircuit, Config, Gwc,
+        PoseidonTranscript, RootCircuit, Snark, SnarkWitness, TestAggregationCircuit,
+        UserChallenge,
     },
     super_circuit::{test::block_1tx, SuperCircuit},
     table::{
@@ -11,15 +12,21 @@ use crate::{
     },
     witness::Rw,
 };
-use bus_mapping::circuit_input_builder::FixedCParams;
-use eth_types::{address, Address, Field, U256};
+use bus_mapping::circuit_input_builder::{FixedCParams, KeccakCircuitParams, PrecompileEcParams};
+use eth_types::{address, geth_types::GethData, Address, Field, ToWord, H256, U256};
 use gadgets::util::Expr;
 use halo2_proofs::{
     circuit::{Layouter, SimpleFloorPlanner, Value},
-    dev::MockProver,
-    halo2curves::{bn256::Bn256, pairing::Engine},
-    plonk::{create_proof, keygen_pk, keygen_vk, Circuit, ConstraintSystem, Error, Selector},
+    dev::{MockProver, VerifyFailure},
+    halo2curves::{
+        bn256::{Bn256, Fr, G1Affine},
+        pairing::Engine,
+    },
+    plonk::{
+        create_proof, keygen_pk, keygen_vk, Any, Circuit, Column, ConstraintSystem, Error, Selector,
+    },
     poly::{
+        commitment::ParamsProver,
         kzg::{
             commitment::{KZGCommitmentScheme, ParamsKZG},
             multiopen::ProverGWC,
@@ -29,9 +36,13 @@ use halo2_proofs::{
 };
 use itertools::Itertools;
 use rand::rngs::OsRng;
-use snark_verifier::util::transcript::Transcript;
+use snark_verifier::{util::transcript::Transcript, verifier::plonk::PlonkProtocol};
 use table::RwTable;
 
+/// Degree of the params of the root circuit, shared by the circuits it
+/// aggregates.
+const ROOT_K: u32 = 26;
+
 struct RwTableCircuit<'a> {
     rws: &'a [Rw],
     n_rows: usize,
@@ -206,6 +217,7 @@ fn test_user_challenge_aggregation() {
         k.try_into().unwrap(),
         &rows,
         rows.len() + 1,
+        None,
         &params,
     );
     let mut transcript = PoseidonTranscript::new(Vec::<Bn256>::new());
@@ -272,74 +284,333 @@ fn test_user_challenge_aggregation() {
     );
 }
 
+/// Prove the `total_chunks` chunks of `block`, returning the params, protocol,
+/// proofs and instances of the chunks, and the rw table columns of the super
+/// circuit. The returned params are the given ones if any, which are downsized
+/// to prove the chunks, so that they share their setup with the root circuit.
+#[allow(clippy::type_complexity)]
+fn chunk_proofs(
+    block: GethData,
+    total_chunks: usize,
+    max_rws: usize,
+    params: Option<&ParamsKZG<Bn256>>,
+) -> (
+    ParamsKZG<Bn256>,
+    PlonkProtocol<G1Affine>,
+    Vec<Vec<u8>>,
+    Vec<Vec<Vec<Fr>>>,
+    Vec<Column<Any>>,
+) {
+    // Preprocess
+    const TEST_MOCK_RANDOMNESS: u64 = 0x100;
+    let circuits_params = FixedCParams {
+        total_chunks,
+        max_txs: 1,
+        max_withdrawals: 5,
+        max_calldata: 32,
+        max_rws,
+        max_copy_rows: 256,
+        max_exp_steps: 256,
+        max_bytecode: 512,
+        max_evm_rows: 1 << 12,
+        max_keccak_rows: 0,
+        keccak_params: KeccakCircuitParams::default(),
+        max_vertical_circuit_rows: 0,
+        max_ec_ops: PrecompileEcParams {
+            ec_add: 0,
+            ec_mul: 0,
+            ec_pairing: 0,
+        },
+    };
+    let (k, circuits, instances, _) =
+        SuperCircuit::<_>::build(block, circuits_params, TEST_MOCK_RANDOMNESS.into()).unwrap();
+    assert!(!circuits.is_empty());
+    assert!(circuits.len() == instances.len());
+
+    // get chronological_rwtable and byaddr_rwtable columns index
+    let mut cs = ConstraintSystem::default();
+    let config = SuperCircuit::configure_with_params(&mut cs, circuits[0].params());
+    let rwtable_columns = config.get_rwtable_columns();
+
+    let root_params = params
+        .cloned()
+        .unwrap_or_else(|| ParamsKZG::<Bn256>::setup(k, OsRng));
+    let mut params = root_params.clone();
+    params.downsize(k);
+    // derive the permutation challenges checked by the root circuit
+    let (circuits, instances) = SuperCircuit::with_permutation_randomness(circuits, &params);
+    let pk = keygen_pk(
+        &params,
+        keygen_vk(&params, &circuits[0]).unwrap(),
+        &circuits[0],
+    )
+    .unwrap();
+    let protocol = compile(
+        &params,
+        pk.get_vk(),
+        Config::kzg()
+            .with_num_instance(instances[0].iter().map(|instance| instance.len()).collect()),
+    );
+
+    let proofs: Vec<Vec<u8>> = circuits
+        .into_iter()
+        .zip(instances.iter())
+        .map(|(circuit, instance)| {
+            // Create proof
+            let proof = {
+                let mut transcript = PoseidonTranscript::new(Vec::new());
+                create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
+                    &params,
+                    &pk,
+                    &[circuit],
+                    &[&instance.iter().map(Vec::as_slice).collect_vec()],
+                    OsRng,
+                    &mut transcript,
+                )
+                .unwrap();
+                transcript.finalize()
+            };
+            proof
+        })
+        .collect();
+    (root_params, protocol, proofs, instances, rwtable_columns)
+}
+
+/// Prove the chunks of a block of 3 chunks, see [`chunk_proofs`].
+#[allow(clippy::type_complexity)]
+fn multiple_chunk_proofs() -> (
+    ParamsKZG<Bn256>,
+    PlonkProtocol<G1Affine>,
+    Vec<Vec<u8>>,
+    Vec<Vec<Vec<Fr>>>,
+    Vec<Column<Any>>,
+) {
+    chunk_proofs(block_1tx(), 3, 100, None)
+}
+
+/// Run the root circuit aggregating the given chunk proofs with the MockProver
+fn verify_root_circuit(
+    params: &ParamsKZG<Bn256>,
+    protocol: &PlonkProtocol<G1Affine>,
+    proofs: &[Vec<u8>],
+    instances: &[Vec<Vec<Fr>>],
+    rwtable_columns: Vec<Column<Any>>,
+) -> Result<(), Vec<VerifyFailure>> {
+    let user_challenge = UserChallenge {
+        column_indexes: rwtable_columns,
+        num_challenges: 2, // alpha, gamma
+    };
+    let snark_witnesses: Vec<_> = proofs
+        .iter()
+        .zip(instances.iter())
+        .map(|(proof, instance)| {
+            SnarkWitness::new(protocol, Value::known(instance), Value::known(proof))
+        })
+        .collect();
+
+    let root_circuit =
+        RootCircuit::<Bn256, Gwc<_>>::new(params, protocol, snark_witnesses, Some(&user_challenge))
+            .unwrap();
+    MockProver::run(ROOT_K, &root_circuit, root_circuit.instance())
+        .unwrap()
+        .verify()
+}
+
 #[ignore = "Due to high memory requirement"]
 #[test]
 fn test_root_circuit_multiple_chunk() {
-    let (params, protocol, proofs, instances, rwtable_columns) = {
-        // Preprocess
-        const TEST_MOCK_RANDOMNESS: u64 = 0x100;
-        let circuits_params = FixedCParams {
-            total_chunks: 3,
-            max_txs: 1,
-            max_withdrawals: 5,
-            max_calldata: 32,
-            max_rws: 100,
-            max_copy_rows: 256,
-            max_exp_steps: 256,
-            max_bytecode: 512,
-            max_evm_rows: 1 << 12,
-            max_keccak_rows: 0,
-            max_vertical_circuit_rows: 0,
-        };
-        let (k, circuits, instances, _) =
-            SuperCircuit::<_>::build(block_1tx(), circuits_params, TEST_MOCK_RANDOMNESS.into())
-                .unwrap();
-        assert!(!circuits.is_empty());
-        assert!(circuits.len() == instances.len());
-
-        // get chronological_rwtable and byaddr_rwtable columns index
-        let mut cs = ConstraintSystem::default();
-        let config = SuperCircuit::configure_with_params(&mut cs, circuits[0].params());
-        let rwtable_columns = config.get_rwtable_columns();
-
-        let params = ParamsKZG::<Bn256>::setup(k, OsRng);
-        let pk = keygen_pk(
-            &params,
-            keygen_vk(&params, &circuits[0]).unwrap(),
-            &circuits[0],
-        )
-        .unwrap();
-        let protocol = compile(
-            &params,
-            pk.get_vk(),
-            Config::kzg()
-                .with_num_instance(instances[0].iter().map(|instance| instance.len()).collect()),
-        );
-
-        let proofs: Vec<Vec<u8>> = circuits
-            .into_iter()
-            .zip(instances.iter())
-            .map(|(circuit, instance)| {
-                // Create proof
-                let proof = {
-                    let mut transcript = PoseidonTranscript::new(Vec::new());
-                    create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
-                        &params,
-                        &pk,
-                        &[circuit],
-                        &[&instance.iter().map(Vec::as_slice).collect_vec()],
-                        OsRng,
-                        &mut transcript,
-                    )
-                    .unwrap();
-                    transcript.finalize()
-                };
-                proof
-            })
-            .collect();
-        (params, protocol, proofs, instances, rwtable_columns)
+    let (params, protocol, proofs, instances, rwtable_columns) = multiple_chunk_proofs();
+    assert_eq!(
+        verify_root_circuit(&params, &protocol, &proofs, &instances, rwtable_columns),
+        Ok(())
+    );
+}
+
+#[ignore = "Due to high memory requirement"]
+#[test]
+fn test_root_circuit_multiple_chunk_incomplete_block() {
+    let (params, protocol, proofs, instances, rwtable_columns) = multiple_chunk_proofs();
+    assert!(proofs.len() > 1);
+
+    // missing last chunk
+    let n = proofs.len() - 1;
+    assert!(verify_root_circuit(
+        &params,
+        &protocol,
+        &proofs[..n],
+        &instances[..n],
+        rwtable_columns.clone()
+    )
+    .is_err());
+
+    // chunks out of order
+    let (mut proofs, mut instances) = (proofs, instances);
+    proofs.swap(0, 1);
+    instances.swap(0, 1);
+    assert!(verify_root_circuit(&params, &protocol, &proofs, &instances, rwtable_columns).is_err());
+}
+
+/// Prove each block in a single chunk and aggregate its chunk proof with the
+/// root circuit, returning the protocol of the root circuit, and the instances
+/// and proofs of the blocks, created with the `PoseidonTranscript` so that they
+/// can be aggregated again.
+#[allow(clippy::type_complexity)]
+fn root_proofs(
+    params: &ParamsKZG<Bn256>,
+    blocks: Vec<GethData>,
+) -> (PlonkProtocol<G1Affine>, Vec<Vec<Vec<Fr>>>, Vec<Vec<u8>>) {
+    // all the root circuits share the same keys, as they aggregate the chunk
+    // proofs of the same super circuit
+    let (mut pk, mut root_protocol) = (None, None);
+    let (instances, proofs) = blocks
+        .into_iter()
+        .map(|block| {
+            let (_, protocol, proofs, instances, rwtable_columns) =
+                chunk_proofs(block, 1, 256, Some(params));
+            let user_challenge = UserChallenge {
+                column_indexes: rwtable_columns,
+                num_challenges: 2, // alpha, gamma
+            };
+            let snark_witnesses: Vec<_> = proofs
+                .iter()
+                .zip(instances.iter())
+                .map(|(proof, instance)| {
+                    SnarkWitness::new(&protocol, Value::known(instance), Value::known(proof))
+                })
+                .collect();
+            let root_circuit = RootCircuit::<Bn256, Gwc<_>>::new(
+                params,
+                &protocol,
+                snark_witnesses,
+                Some(&user_challenge),
+            )
+            .unwrap();
+
+            let pk = pk.get_or_insert_with(|| {
+                keygen_pk(
+                    params,
+                    keygen_vk(params, &root_circuit).unwrap(),
+                    &root_circuit,
+                )
+                .unwrap()
+            });
+            root_protocol.get_or_insert_with(|| {
+                compile(
+                    params,
+                    pk.get_vk(),
+                    Config::kzg()
+                        .with_num_instance(root_circuit.num_instance())
+                        .with_accumulator_indices(Some(root_circuit.accumulator_indices())),
+                )
+            });
+
+            let instance = root_circuit.instance();
+            let mut transcript = PoseidonTranscript::new(Vec::new());
+            create_proof::<KZGCommitmentScheme<_>, ProverGWC<_>, _, _, _, _>(
+                params,
+                pk,
+                &[root_circuit],
+                &[&instance.iter().map(Vec::as_slice).collect_vec()],
+                OsRng,
+                &mut transcript,
+            )
+            .unwrap();
+            (instance, transcript.finalize())
+        })
+        .unzip();
+    (root_protocol.unwrap(), instances, proofs)
+}
+
+/// Run the block aggregation circuit aggregating the root proofs of `blocks`
+/// with the MockProver
+fn verify_block_aggregation(blocks: Vec<GethData>) -> Result<(), Vec<VerifyFailure>> {
+    let params = ParamsKZG::<Bn256>::setup(ROOT_K, OsRng);
+    let (protocol, instances, proofs) = root_proofs(&params, blocks);
+    let snarks = instances
+        .iter()
+        .zip(proofs.iter())
+        .map(|(instance, proof)| Snark::new(&protocol, instance, proof))
+        .collect();
+    let circuit = BlockAggregationCircuit::<Bn256, Gwc<_>>::new(&params, snarks).unwrap();
+    MockProver::run(ROOT_K, &circuit, circuit.instance())
+        .unwrap()
+        .verify()
+}
+
+/// Returns two blocks, the second one being a child of the first one with the
+/// given parent hash. The mock leaves the state roots of both blocks to zero,
+/// so that the second one continues the state of the first one.
+fn parent_and_child_blocks(parent_hash: H256) -> (GethData, GethData) {
+    let mut parent = block_1tx();
+    parent.eth_block.hash = Some(H256::from_low_u64_be(0xb10c));
+    let mut child = block_1tx();
+    child.history_hashes = vec![parent_hash.to_word()];
+    (parent, child)
+}
+
+#[ignore = "Due to high memory requirement"]
+#[test]
+fn test_block_aggregation_consecutive_blocks() {
+    let (parent, child) = parent_and_child_blocks(H256::from_low_u64_be(0xb10c));
+    assert_eq!(verify_block_aggregation(vec![parent, child]), Ok(()));
+}
+
+#[ignore = "Due to high memory requirement"]
+#[test]
+fn test_block_aggregation_wrong_parent_hash() {
+    let (parent, child) = parent_and_child_blocks(H256::from_low_u64_be(0xbad));
+    assert!(verify_block_aggregation(vec![parent, child]).is_err());
+}
+
+/// Deploy `deployment_code` in an in-memory EVM and call it with `calldata`,
+/// returning the gas used by the call.
+fn deploy_and_call(deployment_code: Vec<u8>, calldata: Vec<u8>) -> Result<u64, String> {
+    use revm::{
+        primitives::{CreateScheme, ExecutionResult, Output, TransactTo, TxEnv},
+        InMemoryDB, EVM,
+    };
+
+    let mut evm = EVM {
+        env: Default::default(),
+        db: Some(InMemoryDB::default()),
     };
 
+    evm.env.tx = TxEnv {
+        gas_limit: u64::MAX,
+        transact_to: TransactTo::Create(CreateScheme::Create),
+        data: deployment_code.into(),
+        ..Default::default()
+    };
+    let result = evm.transact_commit().map_err(|err| format!("{:?}", err))?;
+    let contract = match result {
+        ExecutionResult::Success {
+            output: Output::Create(_, Some(contract)),
+            ..
+        } => contract,
+        result => return Err(format!("Contract deployment failed: {:?}", result)),
+    };
+
+    evm.env.tx = TxEnv {
+        gas_limit: u64::MAX,
+        transact_to: TransactTo::Call(contract),
+        data: calldata.into(),
+        ..Default::default()
+    };
+    match evm.transact_commit().map_err(|err| format!("{:?}", err))? {
+        ExecutionResult::Success { gas_used, .. } => Ok(gas_used),
+        result => Err(format!("Contract call failed: {:?}", result)),
+    }
+}
+
+// Requires solc in PATH
+#[ignore = "Due to high memory requirement"]
+#[test]
+fn test_evm_verifier() {
+    // the chunk params are downsized from the root ones, so that the EVM
+    // verifier decides the accumulator of the chunk proofs with the same setup
+    let params = ParamsKZG::<Bn256>::setup(ROOT_K, OsRng);
+    let (params, protocol, proofs, instances, rwtable_columns) =
+        chunk_proofs(block_1tx(), 1, 256, Some(&params));
     let user_challenge = UserChallenge {
         column_indexes: rwtable_columns,
         num_challenges: 2, // alpha, gamma
@@ -351,7 +622,6 @@ fn test_root_circuit_multiple_chunk() {
             SnarkWitness::new(&protocol, Value::known(instance), Value::known(proof))
         })
         .collect();
-
     let root_circuit = RootCircuit::<Bn256, Gwc<_>>::new(
         &params,
         &protocol,
@@ -359,10 +629,36 @@ fn test_root_circuit_multiple_chunk() {
         Some(&user_challenge),
     )
     .unwrap();
-    assert_eq!(
-        MockProver::run(26, &root_circuit, root_circuit.instance())
-            .unwrap()
-            .verify(),
-        Ok(())
-    );
+
+    let pk = keygen_pk(
+        &params,
+        keygen_vk(&params, &root_circuit).unwrap(),
+        &root_circuit,
+    )
+    .unwrap();
+    let deployment_code = evm::gen_evm_verifier(
+        &params,
+        pk.get_vk(),
+        root_circuit.num_instance(),
+        Some(root_circuit.accumulator_indices()),
+    )
+    .unwrap();
+
+    let instances = root_circuit.instance();
+    let proof = evm::gen_evm_proof(&params, &pk, root_circuit, &instances);
+    let gas_used = deploy_and_call(
+        deployment_code.clone(),
+        evm::encode_calldata(&instances, &proof),
+    )
+    .unwrap();
+    log::info!("evm verifier gas used: {}", gas_used);
+
+    // a tampered proof is rejected
+    let mut tampered_proof = proof;
+    tampered_proof[0] ^= 1;
+    assert!(deploy_and_call(
+        deployment_code,
+        evm::encode_calldata(&instances, &tampered_proof)
+    )
+    .is_err());
 }
@@ -0,0 +1,592 @@
+//! The SHA256 circuit implementation.
+//!
+//! Every 64-byte block of a padded input is processed in `NUM_ROWS_PER_BLOCK`
+//! rows: 4 rows that load the hash value, 64 compression rounds (the first 16
+//! of which absorb the message words) and 4 rows that add the compressed state
+//! to the hash value. All words are stored as bits, one bit per column.
+mod param;
+pub(crate) mod util;
+mod witness;
+
+#[cfg(any(test, feature = "test-circuits"))]
+mod dev;
+#[cfg(test)]
+mod test;
+#[cfg(feature = "test-circuits")]
+pub use dev::Sha256Circuit as TestSha256Circuit;
+
+use self::{
+    param::*,
+    util::{ch, decode, maj, query_bits, query_word, rotate_right, shift_right, to_be_bytes, xor3},
+    witness::{multi_sha256, num_blocks, ShaRow},
+};
+use crate::{
+    evm_circuit::util::constraint_builder::{BaseConstraintBuilder, ConstrainBuilderCommon},
+    table::{HashTable, LookupTable, Sha256Table},
+    util::{Challenges, SubCircuit, SubCircuitConfig},
+    witness::{Block, Chunk},
+};
+use eth_types::Field;
+use gadgets::util::{not, select, sum, Expr};
+use halo2_proofs::{
+    circuit::{Layouter, Region, Value},
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Fixed, SecondPhase},
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+/// Sha256CircuitConfig
+#[derive(Clone, Debug)]
+pub struct Sha256CircuitConfig<F> {
+    q_enable: Column<Fixed>,
+    q_first: Column<Fixed>,
+    q_start: Column<Fixed>,
+    q_compression: Column<Fixed>,
+    q_extend: Column<Fixed>,
+    q_absorb: Column<Fixed>,
+    q_padding_last: Column<Fixed>,
+    q_end: Column<Fixed>,
+    q_squeeze: Column<Fixed>,
+    round_cst: Column<Fixed>,
+    word_w: [Column<Advice>; NUM_BITS_PER_WORD_W],
+    word_a: [Column<Advice>; NUM_BITS_PER_WORD_EXT],
+    word_e: [Column<Advice>; NUM_BITS_PER_WORD_EXT],
+    is_paddings: [Column<Advice>; NUM_BYTES_PER_WORD],
+    intermediary_data_rlcs: [Column<Advice>; NUM_BYTES_PER_WORD - 1],
+    /// The columns for other circuits to lookup SHA256 hash results
+    pub sha256_table: Sha256Table,
+    _marker: PhantomData<F>,
+}
+
+/// Circuit configuration arguments
+pub struct Sha256CircuitConfigArgs<F: Field> {
+    /// Sha256Table
+    pub sha256_table: Sha256Table,
+    /// Challenges randomness
+    pub challenges: Challenges<Expression<F>>,
+}
+
+impl<F: Field> SubCircuitConfig<F> for Sha256CircuitConfig<F> {
+    type ConfigArgs = Sha256CircuitConfigArgs<F>;
+
+    /// Return a new Sha256CircuitConfig
+    fn new(
+        meta: &mut ConstraintSystem<F>,
+        Self::ConfigArgs {
+            sha256_table,
+            challenges,
+        }: Self::ConfigArgs,
+    ) -> Self {
+        let q_enable = meta.fixed_column();
+        let q_first = meta.fixed_column();
+        let q_start = meta.fixed_column();
+        let q_compression = meta.fixed_column();
+        let q_extend = meta.fixed_column();
+        let q_absorb = meta.fixed_column();
+        let q_padding_last = meta.fixed_column();
+        let q_end = meta.fixed_column();
+        let q_squeeze = meta.fixed_column();
+        let round_cst = meta.fixed_column();
+        let word_w = [(); NUM_BITS_PER_WORD_W].map(|_| meta.advice_column());
+        let word_a = [(); NUM_BITS_PER_WORD_EXT].map(|_| meta.advice_column());
+        let word_e = [(); NUM_BITS_PER_WORD_EXT].map(|_| meta.advice_column());
+        let is_paddings = [(); NUM_BYTES_PER_WORD].map(|_| meta.advice_column());
+        let intermediary_data_rlcs =
+            [(); NUM_BYTES_PER_WORD - 1].map(|_| meta.advice_column_in(SecondPhase));
+        let is_final = sha256_table.is_enabled;
+        let length = sha256_table.input_len;
+        let data_rlc = sha256_table.input_rlc;
+        let output_rlc = sha256_table.output_rlc;
+        let last_is_padding = is_paddings[NUM_BYTES_PER_WORD - 1];
+        let r = challenges.keccak_input();
+
+        meta.create_gate("boolean checks", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            for column in word_w
+                .iter()
+                .chain(word_a.iter())
+                .chain(word_e.iter())
+                .chain(is_paddings.iter())
+            {
+                cb.require_boolean("bit", meta.query_advice(*column, Rotation::cur()));
+            }
+            cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+        });
+
+        // Message schedule: w = w[-16] + sigma0(w[-15]) + w[-7] + sigma1(w[-2])
+        meta.create_gate("extend", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let new_w_ext = decode::expr(&query_bits(meta, &word_w, 0));
+            let w_2 = query_word(meta, &word_w, -2);
+            let w_7 = query_word(meta, &word_w, -7);
+            let w_15 = query_word(meta, &word_w, -15);
+            let w_16 = query_word(meta, &word_w, -16);
+            let s0 = xor3(
+                &rotate_right(&w_15, 7),
+                &rotate_right(&w_15, 18),
+                &shift_right(&w_15, 3),
+            );
+            let s1 = xor3(
+                &rotate_right(&w_2, 17),
+                &rotate_right(&w_2, 19),
+                &shift_right(&w_2, 10),
+            );
+            cb.require_equal(
+                "w = w[-16] + s0 + w[-7] + s1",
+                new_w_ext,
+                decode::expr(&w_16) + decode::expr(&s0) + decode::expr(&w_7) + decode::expr(&s1),
+            );
+            cb.gate(meta.query_fixed(q_extend, Rotation::cur()))
+        });
+
+        // A compression round. The working variables a..d are the previous 4
+        // values of `a`, e..h the previous 4 values of `e`.
+        meta.create_gate("compression", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let new_a_ext = decode::expr(&query_bits(meta, &word_a, 0));
+            let new_e_ext = decode::expr(&query_bits(meta, &word_e, 0));
+            let [a, b, c, d] = [-1, -2, -3, -4].map(|rot| query_word(meta, &word_a, rot));
+            let [e, f, g, h] = [-1, -2, -3, -4].map(|rot| query_word(meta, &word_e, rot));
+            let w = query_word(meta, &word_w, 0);
+            let round_cst = meta.query_fixed(round_cst, Rotation::cur());
+
+            let s1 = xor3(
+                &rotate_right(&e, 6),
+                &rotate_right(&e, 11),
+                &rotate_right(&e, 25),
+            );
+            let s0 = xor3(
+                &rotate_right(&a, 2),
+                &rotate_right(&a, 13),
+                &rotate_right(&a, 22),
+            );
+            let t1 = decode::expr(&h)
+                + decode::expr(&s1)
+                + decode::expr(&ch(&e, &f, &g))
+                + round_cst
+                + decode::expr(&w);
+            let t2 = decode::expr(&s0) + decode::expr(&maj(&a, &b, &c));
+            cb.require_equal("e = d + t1", new_e_ext, decode::expr(&d) + t1.clone());
+            cb.require_equal("a = t1 + t2", new_a_ext, t1 + t2);
+            cb.gate(meta.query_fixed(q_compression, Rotation::cur()))
+        });
+
+        // The start rows contain the hash value: row `START_ROW - k` contains
+        // `H[k]` in `a` and `H[4 + k]` in `e`. It's either the IV when a new hash
+        // starts, or the hash value of the end rows of the previous block.
+        meta.create_gate("start", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let q_first = meta.query_fixed(q_first, Rotation(-(START_ROW as i32)));
+            let start_new_hash = q_first.clone()
+                + not::expr(q_first.clone())
+                    * meta.query_advice(is_final, Rotation(-(START_ROW as i32) - 1));
+            for k in 0..NUM_START_ROWS {
+                let rot = -(k as i32);
+                for (column, iv) in [(&word_a, H[k]), (&word_e, H[4 + k])] {
+                    let current = decode::expr(&query_word(meta, column, rot));
+                    let previous =
+                        decode::expr(&query_word(meta, column, rot - NUM_END_ROWS as i32));
+                    cb.require_equal(
+                        "hash value",
+                        current,
+                        select::expr(start_new_hash.clone(), (iv as u64).expr(), previous),
+                    );
+                }
+            }
+            cb.require_equal(
+                "padding continues from the previous block",
+                meta.query_advice(last_is_padding, Rotation::cur()),
+                not::expr(start_new_hash.clone())
+                    * meta.query_advice(
+                        last_is_padding,
+                        Rotation(-((NUM_ROWS_PER_BLOCK + START_ROW + 1 - ABSORB_END_ROW) as i32)),
+                    ),
+            );
+            for column in [length, data_rlc] {
+                cb.require_equal(
+                    "absorbed data is reset or continues from the previous block",
+                    meta.query_advice(column, Rotation::cur()),
+                    not::expr(start_new_hash.clone()) * meta.query_advice(column, Rotation::prev()),
+                );
+            }
+            cb.gate(meta.query_fixed(q_start, Rotation::cur()))
+        });
+
+        meta.create_gate("first row", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            cb.require_zero("length", meta.query_advice(length, Rotation::cur()));
+            cb.require_zero("data_rlc", meta.query_advice(data_rlc, Rotation::cur()));
+            cb.gate(meta.query_fixed(q_first, Rotation::cur()))
+        });
+
+        meta.create_gate("carry absorbed data", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            for column in [length, data_rlc] {
+                cb.require_equal(
+                    "absorbed data stays the same",
+                    meta.query_advice(column, Rotation::cur()),
+                    meta.query_advice(column, Rotation::prev()),
+                );
+            }
+            cb.gate(
+                meta.query_fixed(q_enable, Rotation::cur())
+                    - meta.query_fixed(q_first, Rotation::cur())
+                    - meta.query_fixed(q_start, Rotation::cur())
+                    - meta.query_fixed(q_absorb, Rotation::cur()),
+            )
+        });
+
+        // Absorb the 4 big-endian bytes of the message word `w`
+        meta.create_gate("absorb", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let bytes = to_be_bytes(&query_word(meta, &word_w, 0));
+            let is_paddings = is_paddings.map(|column| meta.query_advice(column, Rotation::cur()));
+            let data_rlcs = intermediary_data_rlcs
+                .map(|column| meta.query_advice(column, Rotation::cur()))
+                .into_iter()
+                .chain(std::iter::once(
+                    meta.query_advice(data_rlc, Rotation::cur()),
+                ));
+            // The length is stored in the last two absorb rows of the final block
+            let is_length_row = meta.query_fixed(q_padding_last, Rotation::cur())
+                * meta.query_advice(last_is_padding, Rotation(-2))
+                + meta.query_fixed(q_padding_last, Rotation::next())
+                    * meta.query_advice(last_is_padding, Rotation::prev());
+
+            let mut prev_is_padding = meta.query_advice(last_is_padding, Rotation::prev());
+            let mut prev_data_rlc = meta.query_advice(data_rlc, Rotation::prev());
+            for ((byte, is_padding), data_rlc) in
+                bytes.iter().zip(is_paddings.iter()).zip(data_rlcs)
+            {
+                cb.require_zero(
+                    "padding is monotonic",
+                    prev_is_padding.clone() * not::expr(is_padding),
+                );
+                cb.require_zero(
+                    "first padding byte is 0x80",
+                    (is_padding.clone() - prev_is_padding.clone()) * (byte.clone() - 0x80.expr()),
+                );
+                cb.require_zero(
+                    "other padding bytes are zero",
+                    prev_is_padding.clone() * not::expr(is_length_row.clone()) * byte.clone(),
+                );
+                cb.require_equal(
+                    "data_rlc absorbs the non-padding bytes",
+                    data_rlc.clone(),
+                    select::expr(
+                        is_padding.clone(),
+                        prev_data_rlc.clone(),
+                        prev_data_rlc * r.clone() + byte.clone(),
+                    ),
+                );
+                prev_is_padding = is_padding.clone();
+                prev_data_rlc = data_rlc;
+            }
+            cb.require_equal(
+                "length increases by the number of non-padding bytes",
+                meta.query_advice(length, Rotation::cur()),
+                meta.query_advice(length, Rotation::prev())
+                    + sum::expr(is_paddings.iter().map(not::expr)),
+            );
+            cb.gate(meta.query_fixed(q_absorb, Rotation::cur()))
+        });
+
+        // The last 8 bytes of the final block are the length in bits
+        meta.create_gate("length", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            let is_final_block = meta.query_advice(
+                last_is_padding,
+                Rotation(FINAL_BLOCK_CHECK_ROW as i32 - (ABSORB_END_ROW - 1) as i32),
+            );
+            let length_in_bits = decode::expr(&query_word(meta, &word_w, -1))
+                * (1u64 << NUM_BITS_PER_WORD).expr()
+                + decode::expr(&query_word(meta, &word_w, 0));
+            let length = meta.query_advice(length, Rotation::cur());
+            cb.condition(is_final_block, |cb| {
+                cb.require_equal("length in bits", length_in_bits, length * 8.expr());
+            });
+            cb.gate(meta.query_fixed(q_padding_last, Rotation::cur()))
+        });
+
+        // The end rows contain the new hash value, stored in the same layout as
+        // the start rows
+        meta.create_gate("end", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            for column in [&word_a, &word_e] {
+                let new_hash = decode::expr(&query_bits(meta, column, 0));
+                let register = decode::expr(&query_word(meta, column, -(NUM_START_ROWS as i32)));
+                let hash = decode::expr(&query_word(meta, column, -(END_START_ROW as i32)));
+                cb.require_equal("new hash value", new_hash, hash + register);
+            }
+            cb.gate(meta.query_fixed(q_end, Rotation::cur()))
+        });
+
+        meta.create_gate("squeeze", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            cb.require_equal(
+                "is_final when the length fits in this block",
+                meta.query_advice(is_final, Rotation::cur()),
+                meta.query_advice(
+                    last_is_padding,
+                    Rotation(FINAL_BLOCK_CHECK_ROW as i32 - SQUEEZE_ROW as i32),
+                ),
+            );
+            let mut digest = Vec::new();
+            for column in [&word_a, &word_e] {
+                for k in 0..NUM_END_ROWS {
+                    digest.extend(to_be_bytes(&query_word(meta, column, -(k as i32))));
+                }
+            }
+            cb.require_equal(
+                "output_rlc",
+                meta.query_advice(output_rlc, Rotation::cur()),
+                digest
+                    .into_iter()
+                    .fold(0.expr(), |acc, byte| acc * r.clone() + byte),
+            );
+            cb.gate(meta.query_fixed(q_squeeze, Rotation::cur()))
+        });
+
+        meta.create_gate("only the squeeze rows are in the table", |meta| {
+            let mut cb = BaseConstraintBuilder::new(MAX_DEGREE);
+            cb.require_zero("is_final", meta.query_advice(is_final, Rotation::cur()));
+            cb.require_zero("output_rlc", meta.query_advice(output_rlc, Rotation::cur()));
+            cb.gate(
+                meta.query_fixed(q_enable, Rotation::cur())
+                    - meta.query_fixed(q_squeeze, Rotation::cur()),
+            )
+        });
+
+        Self {
+            q_enable,
+            q_first,
+            q_start,
+            q_compression,
+            q_extend,
+            q_absorb,
+            q_padding_last,
+            q_end,
+            q_squeeze,
+            round_cst,
+            word_w,
+            word_a,
+            word_e,
+            is_paddings,
+            intermediary_data_rlcs,
+            sha256_table,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: Field> Sha256CircuitConfig<F> {
+    pub(crate) fn assign(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        witness: &[ShaRow<F>],
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "assign sha256 rows",
+            |mut region| {
+                for (offset, sha256_row) in witness.iter().enumerate() {
+                    self.set_row(&mut region, offset, sha256_row)?;
+                }
+                self.sha256_table.annotate_columns_in_region(&mut region);
+                self.annotate_circuit(&mut region);
+                Ok(())
+            },
+        )
+    }
+
+    fn set_row(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        row: &ShaRow<F>,
+    ) -> Result<(), Error> {
+        let round = offset % NUM_ROWS_PER_BLOCK;
+        let is_compression = (NUM_START_ROWS..END_START_ROW).contains(&round);
+
+        // Fixed selectors
+        for (name, column, value) in [
+            ("q_enable", self.q_enable, true),
+            ("q_first", self.q_first, offset == 0),
+            ("q_start", self.q_start, round == START_ROW),
+            ("q_compression", self.q_compression, is_compression),
+            (
+                "q_extend",
+                self.q_extend,
+                (EXTEND_START_ROW..END_START_ROW).contains(&round),
+            ),
+            (
+                "q_absorb",
+                self.q_absorb,
+                (ABSORB_START_ROW..ABSORB_END_ROW).contains(&round),
+            ),
+            (
+                "q_padding_last",
+                self.q_padding_last,
+                round == ABSORB_END_ROW - 1,
+            ),
+            ("q_end", self.q_end, round >= END_START_ROW),
+            ("q_squeeze", self.q_squeeze, round == SQUEEZE_ROW),
+        ] {
+            region.assign_fixed(
+                || format!("assign {} {}", name, offset),
+                column,
+                offset,
+                || Value::known(F::from(value as u64)),
+            )?;
+        }
+
+        // Round constant
+        let round_cst = if is_compression {
+            F::from(ROUND_CST[round - NUM_START_ROWS] as u64)
+        } else {
+            F::ZERO
+        };
+        region.assign_fixed(
+            || format!("assign round cst {}", offset),
+            self.round_cst,
+            offset,
+            || Value::known(round_cst),
+        )?;
+
+        // Bits
+        for (name, columns, bits) in [
+            ("w", &self.word_w[..], &row.w[..]),
+            ("a", &self.word_a[..], &row.a[..]),
+            ("e", &self.word_e[..], &row.e[..]),
+            ("is_padding", &self.is_paddings[..], &row.is_paddings[..]),
+        ] {
+            for (idx, (column, bit)) in columns.iter().zip(bits.iter()).enumerate() {
+                region.assign_advice(
+                    || format!("assign {} {} {}", name, idx, offset),
+                    *column,
+                    offset,
+                    || Value::known(F::from(*bit as u64)),
+                )?;
+            }
+        }
+
+        for (idx, (column, data_rlc)) in self
+            .intermediary_data_rlcs
+            .iter()
+            .zip(row.intermediary_data_rlcs.iter())
+            .enumerate()
+        {
+            region.assign_advice(
+                || format!("assign data rlc {} {}", idx, offset),
+                *column,
+                offset,
+                || *data_rlc,
+            )?;
+        }
+
+        self.sha256_table.assign_row(
+            region,
+            offset,
+            [
+                Value::known(F::from(row.is_final as u64)),
+                row.data_rlc,
+                Value::known(F::from(row.length as u64)),
+                row.output_rlc,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn annotate_circuit(&self, region: &mut Region<F>) {
+        region.name_column(|| "SHA256_q_enable", self.q_enable);
+        region.name_column(|| "SHA256_q_first", self.q_first);
+        region.name_column(|| "SHA256_q_start", self.q_start);
+        region.name_column(|| "SHA256_q_compression", self.q_compression);
+        region.name_column(|| "SHA256_q_extend", self.q_extend);
+        region.name_column(|| "SHA256_q_absorb", self.q_absorb);
+        region.name_column(|| "SHA256_q_padding_last", self.q_padding_last);
+        region.name_column(|| "SHA256_q_end", self.q_end);
+        region.name_column(|| "SHA256_q_squeeze", self.q_squeeze);
+        region.name_column(|| "SHA256_round_cst", self.round_cst);
+    }
+}
+
+/// Sha256Circuit
+#[derive(Default, Clone, Debug)]
+pub struct Sha256Circuit<F: Field> {
+    inputs: Vec<Vec<u8>>,
+    num_rows: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> SubCircuit<F> for Sha256Circuit<F> {
+    type Config = Sha256CircuitConfig<F>;
+
+    fn unusable_rows() -> usize {
+        // The bits of `a` and `e` are queried at 9 distinct rotations, so returns
+        // (9 + 3) unusable rows.
+        12
+    }
+
+    /// The `chunk.fixed_param.max_vertical_circuit_rows` parameter, when set,
+    /// pads the circuit to a fixed number of blocks, independently of the
+    /// blocks required by the inputs.
+    fn new_from_block(block: &Block<F>, chunk: &Chunk<F>) -> Self {
+        Self::new(
+            chunk.fixed_param.max_vertical_circuit_rows,
+            block.precompile_events.get_sha256_events(),
+        )
+    }
+
+    /// Return the minimum number of rows required to prove the block
+    fn min_num_rows_block(block: &Block<F>, chunk: &Chunk<F>) -> (usize, usize) {
+        (
+            block
+                .precompile_events
+                .get_sha256_events()
+                .iter()
+                .map(|input| num_blocks(input.len()) * NUM_ROWS_PER_BLOCK)
+                .sum(),
+            chunk.fixed_param.max_vertical_circuit_rows,
+        )
+    }
+
+    /// Make the assignments to the Sha256Circuit
+    fn synthesize_sub(
+        &self,
+        config: &Self::Config,
+        challenges: &Challenges<Value<F>>,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let witness = self.generate_witness(*challenges)?;
+        config.assign(layouter, witness.as_slice())
+    }
+}
+
+impl<F: Field> Sha256Circuit<F> {
+    /// Creates a new circuit instance
+    pub fn new(num_rows: usize, inputs: Vec<Vec<u8>>) -> Self {
+        Sha256Circuit {
+            inputs,
+            num_rows,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The number of blocks that can be hashed in this circuit
+    pub fn capacity(&self) -> Option<usize> {
+        if self.num_rows > 0 {
+            Some(self.num_rows.saturating_sub(Self::unusable_rows()) / NUM_ROWS_PER_BLOCK)
+        } else {
+            None
+        }
+    }
+
+    /// Sets the witness using the data to be hashed
+    pub(crate) fn generate_witness(
+        &self,
+        challenges: Challenges<Value<F>>,
+    ) -> Result<Vec<ShaRow<F>>, Error> {
+        multi_sha256(self.inputs.as_slice(), challenges, self.capacity())
+    }
+}
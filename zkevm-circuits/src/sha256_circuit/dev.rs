@@ -0,0 +1,15 @@
+pub use super::Sha256Circuit;
+
+use crate::{
+    impl_hash_circuit,
+    sha256_circuit::{Sha256CircuitConfig, Sha256CircuitConfigArgs},
+    table::Sha256Table,
+};
+
+impl_hash_circuit!(
+    Sha256Circuit,
+    Sha256CircuitConfig,
+    Sha256CircuitConfigArgs,
+    Sha256Table,
+    sha256_table
+);
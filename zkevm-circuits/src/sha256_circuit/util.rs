@@ -0,0 +1,111 @@
+use super::param::NUM_BITS_PER_WORD;
+use eth_types::Field;
+use gadgets::util::{not, xor, Expr};
+use halo2_proofs::{
+    plonk::{Advice, Column, Expression, VirtualCells},
+    poly::Rotation,
+};
+
+/// Query the given bit columns at the given rotation.
+pub(crate) fn query_bits<F: Field>(
+    meta: &mut VirtualCells<F>,
+    columns: &[Column<Advice>],
+    rot: i32,
+) -> Vec<Expression<F>> {
+    columns
+        .iter()
+        .map(|column| meta.query_advice(*column, Rotation(rot)))
+        .collect()
+}
+
+/// Query the 32 bits of a word at the given rotation, ignoring the carry bits.
+pub(crate) fn query_word<F: Field>(
+    meta: &mut VirtualCells<F>,
+    columns: &[Column<Advice>],
+    rot: i32,
+) -> Vec<Expression<F>> {
+    query_bits(meta, &columns[..NUM_BITS_PER_WORD], rot)
+}
+
+/// Compose little-endian bits into a value.
+pub(crate) mod decode {
+    use super::*;
+
+    pub(crate) fn expr<F: Field>(bits: &[Expression<F>]) -> Expression<F> {
+        bits.iter()
+            .rev()
+            .fold(0.expr(), |acc, bit| acc * 2.expr() + bit.clone())
+    }
+
+    pub(crate) fn value(bits: &[bool]) -> u64 {
+        bits.iter()
+            .rev()
+            .fold(0, |acc, &bit| (acc << 1) | bit as u64)
+    }
+}
+
+/// Split a value into `N` little-endian bits.
+pub(crate) fn into_bits<const N: usize>(value: u64) -> [bool; N] {
+    std::array::from_fn(|idx| (value >> idx) & 1 == 1)
+}
+
+/// Rotate little-endian bits to the right.
+pub(crate) fn rotate_right<F: Field>(bits: &[Expression<F>], count: usize) -> Vec<Expression<F>> {
+    (0..bits.len())
+        .map(|idx| bits[(idx + count) % bits.len()].clone())
+        .collect()
+}
+
+/// Shift little-endian bits to the right.
+pub(crate) fn shift_right<F: Field>(bits: &[Expression<F>], count: usize) -> Vec<Expression<F>> {
+    (0..bits.len())
+        .map(|idx| bits.get(idx + count).cloned().unwrap_or_else(|| 0.expr()))
+        .collect()
+}
+
+/// Bitwise xor of three bit vectors.
+pub(crate) fn xor3<F: Field>(
+    a: &[Expression<F>],
+    b: &[Expression<F>],
+    c: &[Expression<F>],
+) -> Vec<Expression<F>> {
+    a.iter()
+        .zip(b)
+        .zip(c)
+        .map(|((a, b), c)| xor::expr(xor::expr(a, b), c.clone()))
+        .collect()
+}
+
+/// Bitwise `(e & f) ^ (!e & g)`.
+pub(crate) fn ch<F: Field>(
+    e: &[Expression<F>],
+    f: &[Expression<F>],
+    g: &[Expression<F>],
+) -> Vec<Expression<F>> {
+    e.iter()
+        .zip(f)
+        .zip(g)
+        .map(|((e, f), g)| e.clone() * f.clone() + not::expr(e) * g.clone())
+        .collect()
+}
+
+/// Bitwise `(a & b) ^ (a & c) ^ (b & c)`.
+pub(crate) fn maj<F: Field>(
+    a: &[Expression<F>],
+    b: &[Expression<F>],
+    c: &[Expression<F>],
+) -> Vec<Expression<F>> {
+    a.iter()
+        .zip(b)
+        .zip(c)
+        .map(|((a, b), c)| {
+            a.clone() * b.clone() + a.clone() * c.clone() + b.clone() * c.clone()
+                - 2.expr() * a.clone() * b.clone() * c.clone()
+        })
+        .collect()
+}
+
+/// Big-endian bytes of a 32-bit word given as little-endian bits.
+pub(crate) fn to_be_bytes<F: Field>(bits: &[Expression<F>]) -> Vec<Expression<F>> {
+    bits.chunks(8).rev().map(decode::expr).collect()
+}
@@ -0,0 +1,203 @@
+use super::{param::*, util::into_bits};
+use crate::util::Challenges;
+use eth_types::Field;
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+/// Sha256Row
+#[derive(Clone, Debug)]
+pub(crate) struct ShaRow<F> {
+    pub(crate) w: [bool; NUM_BITS_PER_WORD_W],
+    pub(crate) a: [bool; NUM_BITS_PER_WORD_EXT],
+    pub(crate) e: [bool; NUM_BITS_PER_WORD_EXT],
+    pub(crate) is_final: bool,
+    pub(crate) length: usize,
+    pub(crate) data_rlc: Value<F>,
+    pub(crate) intermediary_data_rlcs: [Value<F>; NUM_BYTES_PER_WORD - 1],
+    pub(crate) is_paddings: [bool; NUM_BYTES_PER_WORD],
+    pub(crate) output_rlc: Value<F>,
+}
+
+impl<F: Field> ShaRow<F> {
+    fn new(w: u64, a: u64, e: u64, length: usize, data_rlc: Value<F>) -> Self {
+        Self {
+            w: into_bits(w),
+            a: into_bits(a),
+            e: into_bits(e),
+            is_final: false,
+            length,
+            data_rlc,
+            intermediary_data_rlcs: [data_rlc; NUM_BYTES_PER_WORD - 1],
+            is_paddings: [false; NUM_BYTES_PER_WORD],
+            output_rlc: Value::known(F::ZERO),
+        }
+    }
+}
+
+/// Number of blocks needed to hash an input of `len` bytes
+pub(crate) fn num_blocks(len: usize) -> usize {
+    (len + NUM_PADDING_LENGTH_BYTES) / RATE + 1
+}
+
+fn big_sigma0(a: u32) -> u32 {
+    a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22)
+}
+
+fn big_sigma1(e: u32) -> u32 {
+    e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25)
+}
+
+fn small_sigma0(w: u32) -> u32 {
+    w.rotate_right(7) ^ w.rotate_right(18) ^ (w >> 3)
+}
+
+fn small_sigma1(w: u32) -> u32 {
+    w.rotate_right(17) ^ w.rotate_right(19) ^ (w >> 10)
+}
+
+fn sha256<F: Field>(rows: &mut Vec<ShaRow<F>>, bytes: &[u8], challenges: Challenges<Value<F>>) {
+    let r = challenges.keccak_input();
+
+    let mut padded = bytes.to_vec();
+    padded.push(0x80);
+    while (padded.len() + NUM_PADDING_LENGTH_BYTES) % RATE != 0 {
+        padded.push(0);
+    }
+    padded.extend_from_slice(&(bytes.len() as u64 * 8).to_be_bytes());
+    let num_blocks = padded.len() / RATE;
+
+    // The start rows of the first block still carry the data of the previous hash
+    let (prev_length, prev_data_rlc) = rows
+        .last()
+        .map(|row| (row.length, row.data_rlc))
+        .unwrap_or((0, Value::known(F::ZERO)));
+
+    let mut hs = H;
+    let mut length = 0;
+    let mut data_rlc = Value::known(F::ZERO);
+    let mut in_padding = false;
+    for (block_idx, block) in padded.chunks(RATE).enumerate() {
+        let is_final_block = block_idx == num_blocks - 1;
+
+        // Start: row `START_ROW - k` contains `H[k]` in `a` and `H[4 + k]` in `e`
+        for k in (0..NUM_START_ROWS).rev() {
+            let mut row = if block_idx == 0 && k != 0 {
+                ShaRow::new(
+                    0,
+                    hs[k] as u64,
+                    hs[4 + k] as u64,
+                    prev_length,
+                    prev_data_rlc,
+                )
+            } else {
+                ShaRow::new(0, hs[k] as u64, hs[4 + k] as u64, length, data_rlc)
+            };
+            if k == 0 {
+                row.is_paddings[NUM_BYTES_PER_WORD - 1] = in_padding;
+            }
+            rows.push(row);
+        }
+
+        // Rounds
+        let mut ws: Vec<u64> = block
+            .chunks(NUM_BYTES_PER_WORD)
+            .map(|word| u32::from_be_bytes(word.try_into().unwrap()) as u64)
+            .collect();
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = hs;
+        for round in 0..NUM_ROUNDS {
+            if round >= NUM_WORDS_TO_ABSORB {
+                let w = |idx: usize| ws[round - idx] as u32;
+                let w_ext = w(16) as u64
+                    + small_sigma0(w(15)) as u64
+                    + w(7) as u64
+                    + small_sigma1(w(2)) as u64;
+                ws.push(w_ext);
+            }
+            let w = ws[round] as u32;
+
+            let t1 = h as u64
+                + big_sigma1(e) as u64
+                + ((e & f) ^ (!e & g)) as u64
+                + ROUND_CST[round] as u64
+                + w as u64;
+            let t2 = big_sigma0(a) as u64 + ((a & b) ^ (a & c) ^ (b & c)) as u64;
+            let e_ext = d as u64 + t1;
+            let a_ext = t1 + t2;
+            h = g;
+            g = f;
+            f = e;
+            e = e_ext as u32;
+            d = c;
+            c = b;
+            b = a;
+            a = a_ext as u32;
+
+            let mut row = ShaRow::new(ws[round], a_ext, e_ext, length, data_rlc);
+            if round < NUM_WORDS_TO_ABSORB {
+                for (idx, byte) in w.to_be_bytes().into_iter().enumerate() {
+                    let position = block_idx * RATE + round * NUM_BYTES_PER_WORD + idx;
+                    let is_padding = position >= bytes.len();
+                    row.is_paddings[idx] = is_padding;
+                    if !is_padding {
+                        length += 1;
+                        data_rlc = data_rlc * r + Value::known(F::from(byte as u64));
+                    }
+                    if idx < NUM_BYTES_PER_WORD - 1 {
+                        row.intermediary_data_rlcs[idx] = data_rlc;
+                    }
+                }
+                row.length = length;
+                row.data_rlc = data_rlc;
+                in_padding = row.is_paddings[NUM_BYTES_PER_WORD - 1];
+            }
+            rows.push(row);
+        }
+
+        // End: row `SQUEEZE_ROW - k` contains the new `H[k]` in `a` and `H[4 + k]` in `e`
+        let new_hs: Vec<u64> = hs
+            .iter()
+            .zip([a, b, c, d, e, f, g, h])
+            .map(|(h, reg)| *h as u64 + reg as u64)
+            .collect();
+        for k in (0..NUM_END_ROWS).rev() {
+            rows.push(ShaRow::new(0, new_hs[k], new_hs[4 + k], length, data_rlc));
+        }
+        hs = std::array::from_fn(|idx| new_hs[idx] as u32);
+
+        // Squeeze
+        let digest: Vec<u8> = hs.iter().flat_map(|h| h.to_be_bytes()).collect();
+        let row = rows.last_mut().unwrap();
+        row.is_final = is_final_block;
+        row.output_rlc = r.map(|r| {
+            digest
+                .iter()
+                .fold(F::ZERO, |acc, byte| acc * r + F::from(*byte as u64))
+        });
+    }
+}
+
+pub(crate) fn multi_sha256<F: Field>(
+    bytes: &[Vec<u8>],
+    challenges: Challenges<Value<F>>,
+    capacity: Option<usize>,
+) -> Result<Vec<ShaRow<F>>, Error> {
+    let mut rows: Vec<ShaRow<F>> = Vec::new();
+    for bytes in bytes {
+        sha256(&mut rows, bytes, challenges);
+    }
+    if let Some(capacity) = capacity {
+        // Check that we are not over capacity
+        if rows.len() > capacity * NUM_ROWS_PER_BLOCK {
+            log::error!(
+                "Sha256 inputs exceed capacity.  needed_rows = {}, available_rows = {}",
+                rows.len(),
+                capacity * NUM_ROWS_PER_BLOCK
+            );
+            return Err(Error::BoundsFailure);
+        }
+        // Pad with no data hashes to the expected capacity
+        while rows.len() < capacity * NUM_ROWS_PER_BLOCK {
+            sha256(&mut rows, &[], challenges);
+        }
+    }
+    Ok(rows)
+}
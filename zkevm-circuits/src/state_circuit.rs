@@ -500,8 +500,10 @@ impl<F: Field> StateCircuit<F> {
 impl<F: Field> SubCircuit<F> for StateCircuit<F> {
     type Config = StateCircuitConfig<F>;
 
-    fn new_from_block(_block: &witness::Block<F>, chunk: &Chunk<F>) -> Self {
-        Self::new(chunk)
+    fn new_from_block(block: &witness::Block<F>, chunk: &Chunk<F>) -> Self {
+        let mut circuit = Self::new(chunk);
+        circuit.updates = block.mpt_updates(&circuit.rows);
+        circuit
     }
 
     fn unusable_rows() -> usize {
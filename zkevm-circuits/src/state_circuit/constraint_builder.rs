@@ -148,6 +148,9 @@ impl<F: Field> ConstraintBuilder<F> {
         self.condition(q.tag_matches(Target::TxAccessListAccountStorage), |cb| {
             cb.build_tx_access_list_account_storage_constraints(q)
         });
+        self.condition(q.tag_matches(Target::TxCreatedAccount), |cb| {
+            cb.build_tx_created_account_constraints(q)
+        });
         self.condition(q.tag_matches(Target::TxRefund), |cb| {
             cb.build_tx_refund_constraints(q)
         });
@@ -416,6 +419,30 @@ impl<F: Field> ConstraintBuilder<F> {
         });
     }
 
+    fn build_tx_created_account_constraints(&mut self, q: &Queries<F>) {
+        self.require_zero("field_tag is 0 for TxCreatedAccount", q.field_tag());
+        self.require_word_zero(
+            "storage_key is 0 for TxCreatedAccount",
+            q.rw_table.storage_key.clone(),
+        );
+        self.require_word_boolean("TxCreatedAccount value is boolean", q.value());
+        self.require_word_zero("initial TxCreatedAccount value is false", q.initial_value());
+
+        self.require_word_equal(
+            "state_root is unchanged for TxCreatedAccount",
+            q.state_root(),
+            q.state_root_prev(),
+        );
+
+        self.condition(q.not_first_access.clone(), |cb| {
+            cb.require_word_equal(
+                "value column at Rotation::prev() equals value_prev at Rotation::cur()",
+                q.rw_table.value_prev.clone(),
+                q.value_prev_column(),
+            );
+        });
+    }
+
     fn build_tx_access_list_account_storage_constraints(&mut self, q: &Queries<F>) {
         self.require_zero(
             "field_tag is 0 for TxAccessListAccountStorage",
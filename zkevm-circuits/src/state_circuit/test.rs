@@ -35,7 +35,6 @@ fn state_circuit_unusable_rows() {
 }
 
 fn new_chunk_from_rw_map<F: Field>(rws: &RwMap, padding_start_rw: Option<Rw>) -> Chunk<F> {
-    let (alpha, gamma) = get_permutation_randomness();
     let mut chunk = Chunk {
         by_address_rws: rws.clone(),
         ..Default::default()
@@ -44,8 +43,8 @@ fn new_chunk_from_rw_map<F: Field>(rws: &RwMap, padding_start_rw: Option<Rw>) ->
     let rw_fingerprints = get_permutation_fingerprint_of_rwmap(
         &chunk.by_address_rws,
         chunk.fixed_param.max_rws,
-        alpha,
-        gamma,
+        chunk.permu_alpha,
+        chunk.permu_gamma,
         F::from(1),
         false,
         padding_start_rw,
@@ -895,6 +894,30 @@ fn bad_initial_tx_access_list_account_value() {
     );
 }
 
+#[test]
+fn bad_initial_tx_created_account_value() {
+    let rows = vec![Rw::TxCreatedAccount {
+        rw_counter: 1,
+        is_write: true,
+        tx_id: 1,
+        account_address: address!("0x0000000000000000000000000000000004356002"),
+        is_created: true,
+        is_created_prev: false,
+    }];
+
+    let overrides = HashMap::from([
+        ((AdviceColumn::InitialValueHi, 0), Fr::ZERO),
+        ((AdviceColumn::InitialValueLo, 0), Fr::from(1)),
+        ((AdviceColumn::ValuePrevHi, 0), Fr::ZERO),
+        ((AdviceColumn::ValuePrevLo, 0), Fr::from(1)),
+    ]);
+
+    assert_error_matches(
+        verify_with_overrides(rows, overrides),
+        "initial TxCreatedAccount value is false",
+    );
+}
+
 #[test]
 fn bad_initial_tx_refund_value() {
     let rows = vec![Rw::TxRefund {
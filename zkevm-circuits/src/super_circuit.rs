@@ -8,13 +8,14 @@
 //! The current implementation contains the following circuits:
 //!
 //! - [x] EVM Circuit
-//! - [ ] State Circuit
+//! - [x] State Circuit
 //! - [x] Tx Circuit
 //! - [x] Bytecode Circuit
 //! - [x] Copy Circuit
 //! - [x] Exponentiation Circuit
-//! - [ ] Keccak Circuit
-//! - [ ] MPT Circuit
+//! - [x] ECC Circuit
+//! - [x] Keccak Circuit
+//! - [x] MPT Circuit (enabled with `FeatureConfig::mpt_circuit`)
 //! - [x] PublicInputs Circuit
 //!
 //! And the following shared tables, with the circuits that use them:
@@ -24,10 +25,13 @@
 //!   - [x] EVM Circuit
 //! - [x] Exponentiation Table
 //!   - [x] EVM Circuit
-//! - [ ] Rw Table
-//!   - [ ] State Circuit
-//!   - [ ] EVM Circuit
-//!   - [ ] Copy Circuit
+//! - [x] ECC Table
+//!   - [x] ECC Circuit
+//!   - [x] EVM Circuit
+//! - [x] Rw Table
+//!   - [x] State Circuit
+//!   - [x] EVM Circuit
+//!   - [x] Copy Circuit
 //! - [x] Tx Table
 //!   - [x] Tx Circuit
 //!   - [x] EVM Circuit
@@ -37,36 +41,67 @@
 //!   - [x] Bytecode Circuit
 //!   - [x] EVM Circuit
 //!   - [x] Copy Circuit
-//! - [ ] Block Table
-//!   - [ ] EVM Circuit
+//! - [x] Block Table
+//!   - [x] EVM Circuit
 //!   - [x] PublicInputs Circuit
-//! - [ ] MPT Table
-//!   - [ ] MPT Circuit
-//!   - [ ] State Circuit
+//! - [x] MPT Table
+//!   - [x] MPT Circuit
+//!   - [x] State Circuit
 //! - [x] Keccak Table
-//!   - [ ] Keccak Circuit
-//!   - [ ] EVM Circuit
+//!   - [x] Keccak Circuit
+//!   - [x] EVM Circuit
 //!   - [x] Bytecode Circuit
 //!   - [x] Tx Circuit
-//!   - [ ] MPT Circuit
+//!   - [x] PublicInputs Circuit
+//!   - [x] MPT Circuit
+//!
+//! The EVM and Copy circuits look up the chronologically sorted Rw Table, while
+//! the State Circuit constrains the same rows sorted by address. Both tables are
+//! tied together by the permutation fingerprints checked at the first and last
+//! chunk, so the State Circuit backs every Rw lookup of the EVM Circuit.
+//! The permutation challenges of the fingerprints are derived from the
+//! commitments of the Rw Table columns of all the chunks (see
+//! [`SuperCircuit::with_permutation_randomness`]), and checked by the
+//! `RootCircuit` when aggregating the chunk proofs.
+//!
+//! When the MPT circuit is enabled, the account and storage updates of the
+//! State Circuit are looked up in the MPT Table assigned by the MPT circuit,
+//! whose proofs chain from the previous state root to the state root of the
+//! block. Otherwise the MPT Table is filled with mock roots.
 
 #[cfg(test)]
 pub(crate) mod test;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::witness::chunk::{get_permutation_randomness, set_permutation_randomness};
 use crate::{
+    blake2f_circuit::{Blake2fCircuit, Blake2fCircuitConfig, Blake2fCircuitConfigArgs},
     bytecode_circuit::{BytecodeCircuit, BytecodeCircuitConfig, BytecodeCircuitConfigArgs},
     copy_circuit::{CopyCircuit, CopyCircuitConfig, CopyCircuitConfigArgs},
+    ecc_circuit::{EccCircuit, EccCircuitConfig, EccCircuitConfigArgs},
     evm_circuit::{EvmCircuit, EvmCircuitConfig, EvmCircuitConfigArgs},
     exp_circuit::{ExpCircuit, ExpCircuitConfig},
-    keccak_circuit::{KeccakCircuit, KeccakCircuitConfig, KeccakCircuitConfigArgs},
+    keccak_circuit::{
+        KeccakCircuit, KeccakCircuitConfig, KeccakCircuitConfigArgs, KeccakCircuitParams,
+    },
+    modexp_circuit::{ModExpCircuit, ModExpCircuitConfig, ModExpCircuitConfigArgs},
+    mpt_circuit::{MPTCircuit, MPTCircuitParams, MPTConfig},
     pi_circuit::{PiCircuit, PiCircuitConfig, PiCircuitConfigArgs},
+    ripemd160_circuit::{Ripemd160Circuit, Ripemd160CircuitConfig, Ripemd160CircuitConfigArgs},
+    sha256_circuit::{Sha256Circuit, Sha256CircuitConfig, Sha256CircuitConfigArgs},
     state_circuit::{StateCircuit, StateCircuitConfig, StateCircuitConfigArgs},
     table::{
-        BlockTable, BytecodeTable, CopyTable, ExpTable, KeccakTable, LookupTable, MptTable,
-        RwTable, SigTable, TxTable, UXTable, WdTable,
+        Blake2fTable, BlockTable, BytecodeTable, CopyTable, EccTable, ExpTable, KeccakTable,
+        LookupTable, ModExpTable, MptTable, Ripemd160Table, RwTable, Sha256Table, SigTable,
+        TxTable, UXTable, WdTable,
     },
     tx_circuit::{TxCircuit, TxCircuitConfig, TxCircuitConfigArgs},
-    util::{chunk_ctx::ChunkContextConfig, log2_ceil, Challenges, SubCircuit, SubCircuitConfig},
+    util::{
+        chunk_ctx::ChunkContextConfig,
+        log2_ceil,
+        parallel::{assign_in_parallel, LayouterTask},
+        Challenges, SubCircuit, SubCircuitConfig, SubCircuitRowUsage,
+    },
     witness::{block_convert, chunk_convert, Block, Chunk, MptUpdates},
 };
 use bus_mapping::{
@@ -79,6 +114,11 @@ use halo2_proofs::{
     circuit::{Layouter, SimpleFloorPlanner, Value},
     plonk::{Any, Circuit, Column, ConstraintSystem, Error, Expression},
 };
+#[cfg(not(target_arch = "wasm32"))]
+use halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fr},
+    poly::kzg::commitment::ParamsKZG,
+};
 use itertools::Itertools;
 
 use std::array;
@@ -97,8 +137,14 @@ pub struct SuperCircuitConfig<F: Field> {
     bytecode_circuit: BytecodeCircuitConfig<F>,
     copy_circuit: CopyCircuitConfig<F>,
     keccak_circuit: KeccakCircuitConfig<F>,
+    sha256_circuit: Sha256CircuitConfig<F>,
+    ripemd160_circuit: Ripemd160CircuitConfig<F>,
+    blake2f_circuit: Blake2fCircuitConfig<F>,
+    modexp_circuit: ModExpCircuitConfig<F>,
+    ecc_circuit: EccCircuitConfig<F>,
     pi_circuit: PiCircuitConfig<F>,
     exp_circuit: ExpCircuitConfig<F>,
+    mpt_circuit: Option<MPTConfig<F>>,
     chunk_ctx_config: ChunkContextConfig<F>,
     #[cfg(not(feature = "mock-challenge"))]
     challenges: Challenges<halo2_proofs::plonk::Challenge>,
@@ -126,7 +172,7 @@ impl<F: Field> SuperCircuitConfig<F> {
     }
 }
 
-impl<F: Field> SubCircuitConfig<F> for SuperCircuitConfig<F> {
+impl<F: Field + halo2_base::utils::ScalarField> SubCircuitConfig<F> for SuperCircuitConfig<F> {
     type ConfigArgs = SuperCircuitParams<F>;
 
     /// Configure SuperCircuitConfig
@@ -138,6 +184,8 @@ impl<F: Field> SubCircuitConfig<F> for SuperCircuitConfig<F> {
             max_calldata,
             mock_randomness,
             feature_config,
+            max_mpt_rows,
+            keccak_params,
         }: Self::ConfigArgs,
     ) -> Self {
         let tx_table = TxTable::construct(meta);
@@ -153,6 +201,11 @@ impl<F: Field> SubCircuitConfig<F> for SuperCircuitConfig<F> {
         let copy_table = CopyTable::construct(meta, q_copy_table);
         let exp_table = ExpTable::construct(meta);
         let keccak_table = KeccakTable::construct(meta);
+        let sha256_table = Sha256Table::construct(meta);
+        let ripemd160_table = Ripemd160Table::construct(meta);
+        let blake2f_table = Blake2fTable::construct(meta);
+        let modexp_table = ModExpTable::construct(meta);
+        let ecc_table = EccTable::construct(meta);
         let u8_table = UXTable::construct(meta);
         let u10_table = UXTable::construct(meta);
         let u16_table = UXTable::construct(meta);
@@ -185,6 +238,43 @@ impl<F: Field> SubCircuitConfig<F> for SuperCircuitConfig<F> {
             KeccakCircuitConfigArgs {
                 keccak_table: keccak_table.clone(),
                 challenges: challenges_exprs.clone(),
+                params: keccak_params,
+            },
+        );
+        let sha256_circuit = Sha256CircuitConfig::new(
+            meta,
+            Sha256CircuitConfigArgs {
+                sha256_table: sha256_table.clone(),
+                challenges: challenges_exprs.clone(),
+            },
+        );
+        let ripemd160_circuit = Ripemd160CircuitConfig::new(
+            meta,
+            Ripemd160CircuitConfigArgs {
+                ripemd160_table: ripemd160_table.clone(),
+                challenges: challenges_exprs.clone(),
+            },
+        );
+        let blake2f_circuit = Blake2fCircuitConfig::new(
+            meta,
+            Blake2fCircuitConfigArgs {
+                blake2f_table: blake2f_table.clone(),
+                challenges: challenges_exprs.clone(),
+            },
+        );
+        let modexp_circuit = ModExpCircuitConfig::new(
+            meta,
+            ModExpCircuitConfigArgs {
+                modexp_table: modexp_table.clone(),
+                u8_table,
+                challenges: challenges_exprs.clone(),
+            },
+        );
+        let ecc_circuit = EccCircuitConfig::new(
+            meta,
+            EccCircuitConfigArgs {
+                ecc_table,
+                challenges: challenges_exprs.clone(),
             },
         );
 
@@ -239,6 +329,20 @@ impl<F: Field> SubCircuitConfig<F> for SuperCircuitConfig<F> {
                 challenges: challenges_exprs.clone(),
             },
         );
+        // The MPT circuit proves the updates looked up by the State circuit.
+        let mpt_circuit = feature_config.mpt_circuit.then(|| {
+            MPTConfig::new(
+                meta,
+                challenges_exprs.clone(),
+                mpt_table,
+                keccak_table.clone(),
+                MPTCircuitParams {
+                    degree: log2_ceil(max_mpt_rows) as usize,
+                    disable_preimage_check: false,
+                    max_nodes: max_mpt_rows,
+                },
+            )
+        });
         let exp_circuit = ExpCircuitConfig::new(meta, exp_table);
         let evm_circuit = EvmCircuitConfig::new(
             meta,
@@ -254,6 +358,11 @@ impl<F: Field> SubCircuitConfig<F> for SuperCircuitConfig<F> {
                 u8_table,
                 u16_table,
                 sig_table,
+                sha256_table,
+                ripemd160_table,
+                blake2f_table,
+                modexp_table,
+                ecc_table,
                 chunk_ctx_config: chunk_ctx_config.clone(),
                 feature_config,
             },
@@ -328,8 +437,14 @@ impl<F: Field> SubCircuitConfig<F> for SuperCircuitConfig<F> {
             tx_circuit,
             bytecode_circuit,
             keccak_circuit,
+            sha256_circuit,
+            ripemd160_circuit,
+            blake2f_circuit,
+            modexp_circuit,
+            ecc_circuit,
             pi_circuit,
             exp_circuit,
+            mpt_circuit,
             chunk_ctx_config,
             #[cfg(not(feature = "mock-challenge"))]
             challenges,
@@ -358,40 +473,95 @@ pub struct SuperCircuit<F: Field> {
     pub exp_circuit: ExpCircuit<F>,
     /// Keccak Circuit
     pub keccak_circuit: KeccakCircuit<F>,
+    /// Sha256 Circuit
+    pub sha256_circuit: Sha256Circuit<F>,
+    /// Ripemd160 Circuit
+    pub ripemd160_circuit: Ripemd160Circuit<F>,
+    /// Blake2f Circuit
+    pub blake2f_circuit: Blake2fCircuit<F>,
+    /// ModExp Circuit
+    pub modexp_circuit: ModExpCircuit<F>,
+    /// ECC Circuit
+    pub ecc_circuit: EccCircuit<F, 9>,
+    /// MPT Circuit, only synthesized when `feature_config.mpt_circuit` is set
+    pub mpt_circuit: MPTCircuit<F>,
     /// Circuits Parameters
     pub circuits_params: FixedCParams,
     /// Feature Config
     pub feature_config: FeatureConfig,
     /// Mock randomness
     pub mock_randomness: F,
+    /// Assign the regions of the sub-circuits in parallel, enabled by default
+    /// with the `parallel` feature.  The assignments are the same either way.
+    pub parallel_assignment: bool,
 }
 
-impl<F: Field> SuperCircuit<F> {
+impl<F: Field + halo2_base::utils::ScalarField> SuperCircuit<F> {
+    /// Assign the regions of the sub-circuits in parallel or not, in the
+    /// `SuperCircuit` and its `EvmCircuit`.
+    pub fn with_parallel_assignment(mut self, parallel: bool) -> Self {
+        self.parallel_assignment = parallel;
+        self.evm_circuit.parallel_assignment = parallel;
+        self
+    }
+
+    /// Assign the MPT circuit, when enabled.
+    fn synthesize_mpt(
+        &self,
+        config: &SuperCircuitConfig<F>,
+        challenges: &Challenges<Value<F>>,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        if let Some(mpt_config) = &config.mpt_circuit {
+            mpt_config.assign(layouter, &self.mpt_circuit.nodes, challenges)?;
+            mpt_config.load_fixed_table(layouter)?;
+            mpt_config.load_mult_table(layouter, challenges, self.mpt_circuit.max_nodes)?;
+        }
+        Ok(())
+    }
+
     /// Return the number of rows required to verify a given block
     pub fn get_num_rows_required(block: &Block<F>, chunk: &Chunk<F>) -> usize {
         let num_rows_evm_circuit = EvmCircuit::<F>::get_num_rows_required(block, chunk);
         let num_rows_tx_circuit =
             TxCircuitConfig::<F>::get_num_rows_required(chunk.fixed_param.max_txs);
-        num_rows_evm_circuit.max(num_rows_tx_circuit)
+        let (_, num_rows_state_circuit) = StateCircuit::<F>::min_num_rows_block(block, chunk);
+        let (num_rows_keccak_circuit, _) = KeccakCircuit::<F>::min_num_rows_block(block, chunk);
+        itertools::max([
+            num_rows_evm_circuit,
+            num_rows_tx_circuit,
+            num_rows_state_circuit,
+            num_rows_keccak_circuit,
+        ])
+        .unwrap()
     }
 }
 
 // Even though the SuperCircuit is not a subcircuit we implement the SubCircuit
 // trait for it in order to get the `new_from_block` and `instance` methods that
 // allow us to generalize integration tests.
-impl<F: Field> SubCircuit<F> for SuperCircuit<F> {
+impl<F: Field + halo2_base::utils::ScalarField> SubCircuit<F> for SuperCircuit<F> {
     type Config = SuperCircuitConfig<F>;
 
     fn unusable_rows() -> usize {
+        Self::unusable_rows_with_params(&FixedCParams::default())
+    }
+
+    fn unusable_rows_with_params(params: &FixedCParams) -> usize {
         itertools::max([
-            EvmCircuit::<F>::unusable_rows(),
-            StateCircuit::<F>::unusable_rows(),
-            TxCircuit::<F>::unusable_rows(),
-            PiCircuit::<F>::unusable_rows(),
-            BytecodeCircuit::<F>::unusable_rows(),
-            CopyCircuit::<F>::unusable_rows(),
-            ExpCircuit::<F>::unusable_rows(),
-            KeccakCircuit::<F>::unusable_rows(),
+            EvmCircuit::<F>::unusable_rows_with_params(params),
+            StateCircuit::<F>::unusable_rows_with_params(params),
+            TxCircuit::<F>::unusable_rows_with_params(params),
+            PiCircuit::<F>::unusable_rows_with_params(params),
+            BytecodeCircuit::<F>::unusable_rows_with_params(params),
+            CopyCircuit::<F>::unusable_rows_with_params(params),
+            ExpCircuit::<F>::unusable_rows_with_params(params),
+            KeccakCircuit::<F>::unusable_rows_with_params(params),
+            Sha256Circuit::<F>::unusable_rows_with_params(params),
+            Ripemd160Circuit::<F>::unusable_rows_with_params(params),
+            Blake2fCircuit::<F>::unusable_rows_with_params(params),
+            ModExpCircuit::<F>::unusable_rows_with_params(params),
+            EccCircuit::<F, 9>::unusable_rows_with_params(params),
         ])
         .unwrap()
     }
@@ -405,6 +575,13 @@ impl<F: Field> SubCircuit<F> for SuperCircuit<F> {
         let copy_circuit = CopyCircuit::new_from_block_no_external(block, chunk);
         let exp_circuit = ExpCircuit::new_from_block(block, chunk);
         let keccak_circuit = KeccakCircuit::new_from_block(block, chunk);
+        let sha256_circuit = Sha256Circuit::new_from_block(block, chunk);
+        let ripemd160_circuit = Ripemd160Circuit::new_from_block(block, chunk);
+        let blake2f_circuit = Blake2fCircuit::new_from_block(block, chunk);
+        let modexp_circuit = ModExpCircuit::new_from_block(block, chunk);
+        let ecc_circuit = EccCircuit::new_from_block(block, chunk);
+        let mpt_circuit =
+            MPTCircuit::new_from_block(block, chunk.fixed_param.max_vertical_circuit_rows);
 
         SuperCircuit::<_> {
             chunk: Some(chunk.clone()),
@@ -416,9 +593,16 @@ impl<F: Field> SubCircuit<F> for SuperCircuit<F> {
             copy_circuit,
             exp_circuit,
             keccak_circuit,
+            sha256_circuit,
+            ripemd160_circuit,
+            blake2f_circuit,
+            modexp_circuit,
+            ecc_circuit,
+            mpt_circuit,
             circuits_params: chunk.fixed_param,
             feature_config: block.feature_config,
             mock_randomness: block.randomness,
+            parallel_assignment: cfg!(feature = "parallel"),
         }
     }
 
@@ -452,21 +636,10 @@ impl<F: Field> SubCircuit<F> for SuperCircuit<F> {
 
     /// Return the minimum number of rows required to prove the block
     fn min_num_rows_block(block: &Block<F>, chunk: &Chunk<F>) -> (usize, usize) {
-        let evm = EvmCircuit::min_num_rows_block(block, chunk);
-        let state = StateCircuit::min_num_rows_block(block, chunk);
-        let bytecode = BytecodeCircuit::min_num_rows_block(block, chunk);
-        let copy = CopyCircuit::min_num_rows_block(block, chunk);
-        let keccak = KeccakCircuit::min_num_rows_block(block, chunk);
-        let tx = TxCircuit::min_num_rows_block(block, chunk);
-        let exp = ExpCircuit::min_num_rows_block(block, chunk);
-        let pi = PiCircuit::min_num_rows_block(block, chunk);
-
-        let rows: Vec<(usize, usize)> = vec![evm, state, bytecode, copy, keccak, tx, exp, pi];
-        let (rows_without_padding, rows_with_padding): (Vec<usize>, Vec<usize>) =
-            rows.into_iter().unzip();
+        let row_usage = Self::row_usage_block(block, chunk);
         (
-            itertools::max(rows_without_padding).unwrap(),
-            itertools::max(rows_with_padding).unwrap(),
+            itertools::max(row_usage.iter().map(|usage| usage.row_num_real)).unwrap(),
+            itertools::max(row_usage.iter().map(|usage| usage.row_num_total)).unwrap(),
         )
     }
 
@@ -483,8 +656,92 @@ impl<F: Field> SubCircuit<F> for SuperCircuit<F> {
             &self.chunk.as_ref().unwrap().chunk_context,
             self.chunk.as_ref().unwrap().fixed_param.max_rws - 1,
         )?;
+        if self.parallel_assignment {
+            // The regions of each sub-circuit are recorded in parallel, and then
+            // assigned in the same order as below.
+            let tasks: Vec<LayouterTask<F>> = vec![
+                Box::new(|layouter| {
+                    self.keccak_circuit
+                        .synthesize_sub(&config.keccak_circuit, challenges, layouter)
+                }),
+                Box::new(|layouter| {
+                    self.sha256_circuit
+                        .synthesize_sub(&config.sha256_circuit, challenges, layouter)
+                }),
+                Box::new(|layouter| {
+                    self.ripemd160_circuit.synthesize_sub(
+                        &config.ripemd160_circuit,
+                        challenges,
+                        layouter,
+                    )
+                }),
+                Box::new(|layouter| {
+                    self.blake2f_circuit.synthesize_sub(
+                        &config.blake2f_circuit,
+                        challenges,
+                        layouter,
+                    )
+                }),
+                Box::new(|layouter| {
+                    self.modexp_circuit
+                        .synthesize_sub(&config.modexp_circuit, challenges, layouter)
+                }),
+                Box::new(|layouter| {
+                    self.ecc_circuit
+                        .synthesize_sub(&config.ecc_circuit, challenges, layouter)
+                }),
+                Box::new(|layouter| {
+                    self.bytecode_circuit.synthesize_sub(
+                        &config.bytecode_circuit,
+                        challenges,
+                        layouter,
+                    )
+                }),
+                Box::new(|layouter| {
+                    self.tx_circuit
+                        .synthesize_sub(&config.tx_circuit, challenges, layouter)
+                }),
+                Box::new(|layouter| {
+                    self.state_circuit
+                        .synthesize_sub(&config.state_circuit, challenges, layouter)
+                }),
+                Box::new(|layouter| {
+                    self.copy_circuit
+                        .synthesize_sub(&config.copy_circuit, challenges, layouter)
+                }),
+                Box::new(|layouter| {
+                    self.exp_circuit
+                        .synthesize_sub(&config.exp_circuit, challenges, layouter)
+                }),
+                Box::new(|layouter| {
+                    self.evm_circuit
+                        .synthesize_sub(&config.evm_circuit, challenges, layouter)
+                }),
+                Box::new(|layouter| {
+                    self.pi_circuit
+                        .synthesize_sub(&config.pi_circuit, challenges, layouter)
+                }),
+                Box::new(|layouter| self.synthesize_mpt(config, challenges, layouter)),
+            ];
+            #[cfg(not(feature = "mock-challenge"))]
+            let forwarded_challenges = config.challenges.indexed().map(|challenge| *challenge);
+            #[cfg(feature = "mock-challenge")]
+            let forwarded_challenges: [halo2_proofs::plonk::Challenge; 0] = [];
+            return assign_in_parallel(layouter, &forwarded_challenges, &tasks);
+        }
+
         self.keccak_circuit
             .synthesize_sub(&config.keccak_circuit, challenges, layouter)?;
+        self.sha256_circuit
+            .synthesize_sub(&config.sha256_circuit, challenges, layouter)?;
+        self.ripemd160_circuit
+            .synthesize_sub(&config.ripemd160_circuit, challenges, layouter)?;
+        self.blake2f_circuit
+            .synthesize_sub(&config.blake2f_circuit, challenges, layouter)?;
+        self.modexp_circuit
+            .synthesize_sub(&config.modexp_circuit, challenges, layouter)?;
+        self.ecc_circuit
+            .synthesize_sub(&config.ecc_circuit, challenges, layouter)?;
         self.bytecode_circuit
             .synthesize_sub(&config.bytecode_circuit, challenges, layouter)?;
         self.tx_circuit
@@ -499,7 +756,7 @@ impl<F: Field> SubCircuit<F> for SuperCircuit<F> {
             .synthesize_sub(&config.evm_circuit, challenges, layouter)?;
         self.pi_circuit
             .synthesize_sub(&config.pi_circuit, challenges, layouter)?;
-        Ok(())
+        self.synthesize_mpt(config, challenges, layouter)
     }
 }
 
@@ -511,9 +768,11 @@ pub struct SuperCircuitParams<F: Field> {
     max_calldata: usize,
     mock_randomness: F,
     feature_config: FeatureConfig,
+    max_mpt_rows: usize,
+    keccak_params: KeccakCircuitParams,
 }
 
-impl<F: Field> Circuit<F> for SuperCircuit<F> {
+impl<F: Field + halo2_base::utils::ScalarField> Circuit<F> for SuperCircuit<F> {
     type Config = SuperCircuitConfig<F>;
     type FloorPlanner = SimpleFloorPlanner;
     type Params = SuperCircuitParams<F>;
@@ -529,6 +788,8 @@ impl<F: Field> Circuit<F> for SuperCircuit<F> {
             max_calldata: self.circuits_params.max_calldata,
             mock_randomness: self.mock_randomness,
             feature_config: self.feature_config,
+            max_mpt_rows: self.mpt_circuit.max_nodes,
+            keccak_params: self.circuits_params.keccak_params,
         }
     }
 
@@ -558,9 +819,13 @@ impl<F: Field> Circuit<F> for SuperCircuit<F> {
 
         config.block_table.load(&mut layouter, &block.context)?;
 
-        config
-            .mpt_table
-            .load(&mut layouter, &MptUpdates::mock_from(rws))?;
+        // With the MPT circuit enabled the MptTable is assigned by it, proving
+        // the state root transition of the block.
+        if config.mpt_circuit.is_none() {
+            config
+                .mpt_table
+                .load(&mut layouter, &MptUpdates::mock_from(rws))?;
+        }
 
         config.u8_table.load(&mut layouter)?;
         config.u10_table.load(&mut layouter)?;
@@ -570,7 +835,64 @@ impl<F: Field> Circuit<F> for SuperCircuit<F> {
     }
 }
 
-impl<F: Field> SuperCircuit<F> {
+impl<F: Field + halo2_base::utils::ScalarField> SuperCircuit<F> {
+    /// Return the rows required by each sub-circuit to prove `chunk` of
+    /// `block`, versus the rows it has.  The SuperCircuit can't prove the chunk
+    /// if any sub-circuit overflows.
+    pub fn row_usage_block(block: &Block<F>, chunk: &Chunk<F>) -> Vec<SubCircuitRowUsage> {
+        let mut row_usage = vec![
+            SubCircuitRowUsage::new::<_, EvmCircuit<_>>("evm", block, chunk),
+            SubCircuitRowUsage::new::<_, StateCircuit<_>>("state", block, chunk),
+            SubCircuitRowUsage::new::<_, BytecodeCircuit<_>>("bytecode", block, chunk),
+            SubCircuitRowUsage::new::<_, CopyCircuit<_>>("copy", block, chunk),
+            SubCircuitRowUsage::new::<_, KeccakCircuit<_>>("keccak", block, chunk),
+            SubCircuitRowUsage::new::<_, Sha256Circuit<_>>("sha256", block, chunk),
+            SubCircuitRowUsage::new::<_, Ripemd160Circuit<_>>("ripemd160", block, chunk),
+            SubCircuitRowUsage::new::<_, Blake2fCircuit<_>>("blake2f", block, chunk),
+            SubCircuitRowUsage::new::<_, ModExpCircuit<_>>("modexp", block, chunk),
+            SubCircuitRowUsage::new::<_, EccCircuit<_, 9>>("ecc", block, chunk),
+            // the tx signatures are verified in the tx circuit
+            SubCircuitRowUsage::new::<_, TxCircuit<_>>("tx", block, chunk),
+            SubCircuitRowUsage::new::<_, ExpCircuit<_>>("exp", block, chunk),
+            SubCircuitRowUsage::new::<_, PiCircuit<_>>("pi", block, chunk),
+        ];
+        if block.feature_config.mpt_circuit {
+            row_usage.push(SubCircuitRowUsage {
+                name: "mpt",
+                row_num_real: MPTCircuit::<F>::min_num_rows(&block.mpt_nodes),
+                row_num_total: chunk.fixed_param.max_vertical_circuit_rows,
+            });
+        }
+        row_usage
+    }
+
+    /// Return the row usage of the sub-circuits of this circuit, see
+    /// [`Self::row_usage_block`].
+    pub fn row_usage(&self) -> Vec<SubCircuitRowUsage> {
+        Self::row_usage_block(
+            self.evm_circuit
+                .block
+                .as_ref()
+                .expect("circuit built from a block"),
+            self.chunk.as_ref().expect("circuit built from a chunk"),
+        )
+    }
+
+    /// Return the row usage of the sub-circuits for each chunk of the block
+    /// handled by `builder`, without building the circuits, so that a
+    /// sequencer can check that the block is still provable before adding a
+    /// transaction to it.
+    pub fn row_usage_from_circuit_input_builder(
+        builder: &CircuitInputBuilder<FixedCParams>,
+    ) -> Result<Vec<Vec<SubCircuitRowUsage>>, bus_mapping::Error> {
+        let block = block_convert(builder)?;
+        let chunks = chunk_convert(&block, builder)?;
+        Ok(chunks
+            .iter()
+            .map(|chunk| Self::row_usage_block(&block, chunk))
+            .collect())
+    }
+
     /// From the witness data, generate a SuperCircuit instance with all of the
     /// sub-circuits filled with their corresponding witnesses.
     ///
@@ -639,10 +961,51 @@ impl<F: Field> SuperCircuit<F> {
                 )
             });
 
-        let k = log2_ceil(Self::unusable_rows() + rows_needed[0]);
+        let k =
+            log2_ceil(Self::unusable_rows_with_params(&builder.circuits_params) + rows_needed[0]);
         log::debug!("super circuit uses k = {}", k);
 
         let (circuits, instances) = circuit_instance_pairs.into_iter().unzip();
         Ok((k, circuits, instances))
     }
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SuperCircuit<Fr> {
+    /// Rebuild the chunk circuits with the Rw Table permutation challenges
+    /// derived from the commitments of the Rw Tables of all the chunks under
+    /// `params`, which the `RootCircuit` requires to aggregate their proofs.
+    ///
+    /// The circuits built by [`Self::build`] use mock challenges, which are
+    /// only suitable for the `MockProver`.  Return the rebuilt circuits with
+    /// their Public Inputs.
+    #[allow(clippy::type_complexity)]
+    pub fn with_permutation_randomness(
+        circuits: Vec<Self>,
+        params: &ParamsKZG<Bn256>,
+    ) -> (Vec<Self>, Vec<Vec<Vec<Fr>>>) {
+        let mut cs = ConstraintSystem::default();
+        Self::configure_with_params(&mut cs, circuits[0].params());
+        let block = circuits[0]
+            .evm_circuit
+            .block
+            .clone()
+            .expect("circuit built from a block");
+        let mut chunks = circuits
+            .into_iter()
+            .map(|circuit| circuit.chunk.expect("circuit built from a chunk"))
+            .collect_vec();
+
+        let (alpha, gamma) = get_permutation_randomness(params, cs.degree(), &chunks);
+        set_permutation_randomness(&mut chunks, alpha, gamma);
+
+        chunks
+            .iter()
+            .map(|chunk| {
+                let circuit = Self::new_from_block(&block, chunk);
+                let instance = circuit.instance();
+                (circuit, instance)
+            })
+            .unzip()
+    }
+}
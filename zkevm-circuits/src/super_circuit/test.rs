@@ -1,8 +1,15 @@
-use crate::{table::rw_table::get_rwtable_cols_commitment, witness::RwMap};
+use crate::{
+    table::rw_table::get_rwtable_cols_commitment,
+    witness::{Rw, RwMap},
+};
 
 pub use super::*;
-use bus_mapping::operation::OperationContainer;
-use eth_types::{address, bytecode, geth_types::GethData, Word};
+use bus_mapping::{
+    circuit_input_builder::{CapacityExceeded, PrecompileEcParams},
+    mpt::StateTrie,
+    operation::OperationContainer,
+};
+use eth_types::{address, bytecode, geth_types::GethData, ToWord, Word, H256};
 use ethers_signers::{LocalWallet, Signer};
 use halo2_proofs::{
     dev::MockProver,
@@ -30,6 +37,8 @@ fn super_circuit_degree() {
         max_calldata: 32,
         mock_randomness: Fr::from(0x100),
         feature_config: FeatureConfig::default(),
+        max_mpt_rows: 0,
+        keccak_params: KeccakCircuitParams::default(),
     };
     SuperCircuit::configure_with_params(&mut cs, params);
     log::info!("super circuit degree: {}", cs.degree());
@@ -140,8 +149,79 @@ fn block_2tx() -> GethData {
     block
 }
 
+fn block_1tx_sha3() -> GethData {
+    let mut rng = ChaCha20Rng::seed_from_u64(2);
+
+    let chain_id = (*MOCK_CHAIN_ID).as_u64();
+
+    // SHA3 adds a keccak lookup from the EVM circuit, and the memory writes add
+    // Rw lookups checked by the state circuit.
+    let bytecode = bytecode! {
+        PUSH32(0x1234)
+        PUSH1(0x00)
+        MSTORE
+        PUSH1(0x20)
+        PUSH1(0x00)
+        SHA3
+        POP
+        STOP
+    };
+
+    let wallet_a = LocalWallet::new(&mut rng).with_chain_id(chain_id);
+
+    let addr_a = wallet_a.address();
+    let addr_b = address!("0x000000000000000000000000000000000000BBBB");
+
+    let mut wallets = HashMap::new();
+    wallets.insert(wallet_a.address(), wallet_a);
+
+    let mut block: GethData = TestContext::<2, 1>::new(
+        None,
+        |accs| {
+            accs[0]
+                .address(addr_b)
+                .balance(Word::from(1u64 << 20))
+                .code(bytecode);
+            accs[1].address(addr_a).balance(Word::from(1u64 << 20));
+        },
+        |mut txs, accs| {
+            txs[0]
+                .from(accs[1].address)
+                .to(accs[0].address)
+                .gas(Word::from(1_000_000u64));
+        },
+        |block, _tx| block.number(0xcafeu64),
+    )
+    .unwrap()
+    .into();
+    block.sign(&wallets);
+    block
+}
+
 const TEST_MOCK_RANDOMNESS: u64 = 0x100;
 
+fn sha3_circuits_params() -> FixedCParams {
+    FixedCParams {
+        total_chunks: 1,
+        max_txs: 1,
+        max_withdrawals: 5,
+        max_calldata: 32,
+        max_rws: 256,
+        max_copy_rows: 256,
+        max_exp_steps: 256,
+        max_bytecode: 512,
+        max_evm_rows: 0,
+        max_keccak_rows: 0,
+        keccak_params: KeccakCircuitParams::default(),
+        max_vertical_circuit_rows: 0,
+        max_ec_ops: PrecompileEcParams {
+            ec_add: 0,
+            ec_mul: 0,
+            ec_pairing: 0,
+        },
+    }
+}
+
 // High memory usage test.  Run in serial with:
 // `cargo test [...] serial_ -- --ignored --test-threads 1`
 #[ignore]
@@ -159,7 +239,13 @@ fn serial_test_super_circuit_1tx_1max_tx() {
         max_bytecode: 512,
         max_evm_rows: 0,
         max_keccak_rows: 0,
+        keccak_params: KeccakCircuitParams::default(),
         max_vertical_circuit_rows: 0,
+        max_ec_ops: PrecompileEcParams {
+            ec_add: 0,
+            ec_mul: 0,
+            ec_pairing: 0,
+        },
     };
     test_super_circuit(block, circuits_params, Fr::from(TEST_MOCK_RANDOMNESS));
 }
@@ -178,7 +264,13 @@ fn serial_test_super_circuit_1tx_2max_tx() {
         max_bytecode: 512,
         max_evm_rows: 0,
         max_keccak_rows: 0,
+        keccak_params: KeccakCircuitParams::default(),
         max_vertical_circuit_rows: 0,
+        max_ec_ops: PrecompileEcParams {
+            ec_add: 0,
+            ec_mul: 0,
+            ec_pairing: 0,
+        },
     };
     test_super_circuit(block, circuits_params, Fr::from(TEST_MOCK_RANDOMNESS));
 }
@@ -197,11 +289,57 @@ fn serial_test_super_circuit_2tx_2max_tx() {
         max_bytecode: 512,
         max_evm_rows: 0,
         max_keccak_rows: 0,
+        keccak_params: KeccakCircuitParams::default(),
         max_vertical_circuit_rows: 0,
+        max_ec_ops: PrecompileEcParams {
+            ec_add: 0,
+            ec_mul: 0,
+            ec_pairing: 0,
+        },
     };
     test_super_circuit(block, circuits_params, Fr::from(TEST_MOCK_RANDOMNESS));
 }
 
+#[ignore]
+#[test]
+fn serial_test_super_circuit_parallel_assignment() {
+    let block = block_2tx();
+    let circuits_params = FixedCParams {
+        total_chunks: 1,
+        max_txs: 2,
+        max_withdrawals: 5,
+        max_calldata: 32,
+        max_rws: 256,
+        max_copy_rows: 256,
+        max_exp_steps: 256,
+        max_bytecode: 512,
+        max_evm_rows: 0,
+        max_keccak_rows: 0,
+        keccak_params: KeccakCircuitParams::default(),
+        max_vertical_circuit_rows: 0,
+        max_ec_ops: PrecompileEcParams {
+            ec_add: 0,
+            ec_mul: 0,
+            ec_pairing: 0,
+        },
+    };
+    let (k, mut circuits, mut instances, _) =
+        SuperCircuit::<Fr>::build(block, circuits_params, Fr::from(TEST_MOCK_RANDOMNESS)).unwrap();
+    let (circuit, instance) = (circuits.remove(0), instances.remove(0));
+
+    let serial = MockProver::run(
+        k,
+        &circuit.clone().with_parallel_assignment(false),
+        instance.clone(),
+    )
+    .unwrap();
+    let parallel = MockProver::run(k, &circuit.with_parallel_assignment(true), instance).unwrap();
+    assert_eq!(serial.advice(), parallel.advice());
+    assert_eq!(serial.fixed(), parallel.fixed());
+    assert_eq!(serial.permutation(), parallel.permutation());
+    assert_eq!(parallel.verify(), Ok(()));
+}
+
 #[ignore]
 #[test]
 fn serial_test_multi_chunk_super_circuit_2tx_2max_tx() {
@@ -217,11 +355,282 @@ fn serial_test_multi_chunk_super_circuit_2tx_2max_tx() {
         max_bytecode: 512,
         max_evm_rows: 0,
         max_keccak_rows: 0,
+        keccak_params: KeccakCircuitParams::default(),
         max_vertical_circuit_rows: 0,
+        max_ec_ops: PrecompileEcParams {
+            ec_add: 0,
+            ec_mul: 0,
+            ec_pairing: 0,
+        },
     };
     test_super_circuit(block, circuits_params, Fr::from(TEST_MOCK_RANDOMNESS));
 }
 
+#[ignore]
+#[test]
+fn serial_test_multi_chunk_permutation_randomness() {
+    let circuits_params = FixedCParams {
+        total_chunks: 3,
+        max_txs: 1,
+        max_withdrawals: 5,
+        max_calldata: 32,
+        max_rws: 100,
+        max_copy_rows: 256,
+        max_exp_steps: 256,
+        max_bytecode: 512,
+        max_evm_rows: 1 << 12,
+        max_keccak_rows: 0,
+        keccak_params: KeccakCircuitParams::default(),
+        max_vertical_circuit_rows: 0,
+        max_ec_ops: PrecompileEcParams {
+            ec_add: 0,
+            ec_mul: 0,
+            ec_pairing: 0,
+        },
+    };
+    let (k, circuits, _, _) =
+        SuperCircuit::build(block_1tx(), circuits_params, Fr::from(TEST_MOCK_RANDOMNESS)).unwrap();
+    let params = ParamsKZG::<Bn256>::setup(k, OsRng);
+    let (circuits, instances) = SuperCircuit::with_permutation_randomness(circuits, &params);
+
+    // all the chunks use the same challenges, derived from their rw tables
+    let chunks = circuits
+        .iter()
+        .map(|circuit| circuit.chunk.as_ref().unwrap())
+        .collect_vec();
+    let (alpha, gamma) = (chunks[0].permu_alpha, chunks[0].permu_gamma);
+    assert_ne!((alpha, gamma), (Fr::from(103), Fr::from(101)));
+    assert!(chunks
+        .iter()
+        .all(|chunk| (chunk.permu_alpha, chunk.permu_gamma) == (alpha, gamma)));
+    let last_chunk = chunks.last().unwrap();
+    assert_eq!(
+        last_chunk.chrono_rw_fingerprints.mul_acc,
+        last_chunk.by_address_rw_fingerprints.mul_acc
+    );
+
+    for (circuit, instance) in circuits.into_iter().zip(instances) {
+        let prover = MockProver::run(k, &circuit, instance).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+}
+
+#[ignore]
+#[test]
+fn serial_test_super_circuit_1tx_sha3() {
+    test_super_circuit(
+        block_1tx_sha3(),
+        sha3_circuits_params(),
+        Fr::from(TEST_MOCK_RANDOMNESS),
+    );
+}
+
+#[ignore]
+#[test]
+fn serial_test_super_circuit_missing_keccak_input() {
+    let (k, mut circuits, instances, _) = SuperCircuit::<Fr>::build(
+        block_1tx_sha3(),
+        sha3_circuits_params(),
+        Fr::from(TEST_MOCK_RANDOMNESS),
+    )
+    .unwrap();
+    let (circuit, instance) = (&mut circuits[0], instances[0].clone());
+
+    // Drop the SHA3 preimage from the keccak circuit, the EVM circuit lookup
+    // into the shared keccak table must fail.
+    let block = circuit.evm_circuit.block.as_ref().unwrap();
+    let sha3_input = vec![0u8; 30]
+        .into_iter()
+        .chain([0x12, 0x34])
+        .collect::<Vec<_>>();
+    let inputs = block
+        .keccak_inputs
+        .iter()
+        .filter(|input| **input != sha3_input)
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(inputs.len() + 1, block.keccak_inputs.len());
+    circuit.keccak_circuit = KeccakCircuit::new(circuit.circuits_params.max_keccak_rows, inputs)
+        .with_params(circuit.circuits_params.keccak_params);
+
+    let prover = MockProver::run(k, &*circuit, instance).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn super_circuit_mpt_circuit_requires_mpt_witness() {
+    let block = block_1tx();
+    let builder = BlockData::new_from_geth_data_with_params(block.clone(), sha3_circuits_params())
+        .new_circuit_input_builder_with_feature(FeatureConfig {
+            mpt_circuit: true,
+            ..Default::default()
+        })
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+    assert!(!builder.block.trie_modifications().is_empty());
+
+    // Without the MPT witness the state root transition can't be proven.
+    assert!(matches!(
+        block_convert::<Fr>(&builder),
+        Err(bus_mapping::Error::InternalError(_))
+    ));
+}
+
+/// Handle `block` with the MPT circuit enabled, and generate its MPT witness
+/// from the state before the block.  The roots of the mock block are replaced
+/// by the ones of the witness.
+fn builder_with_mpt_witness(block: &GethData) -> CircuitInputBuilder<FixedCParams> {
+    let builder = BlockData::new_from_geth_data_with_params(block.clone(), sha3_circuits_params())
+        .new_circuit_input_builder_with_feature(FeatureConfig {
+            mpt_circuit: true,
+            ..Default::default()
+        });
+    let mut state_trie = StateTrie::from_state_db(&builder.sdb).unwrap();
+    let prev_state_root = state_trie.root();
+
+    let mut builder = builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+    builder.gen_mpt_witness(&mut state_trie).unwrap();
+    builder.block.prev_state_root = prev_state_root.to_word();
+    builder.block.eth_block.state_root = state_trie.root();
+    builder
+}
+
+#[test]
+fn super_circuit_mpt_witness_chains_block_roots() {
+    let block = block_1tx();
+    let mut builder = builder_with_mpt_witness(&block);
+    assert!(!block_convert::<Fr>(&builder).unwrap().mpt_nodes.is_empty());
+
+    // The proofs must start from the state root of the previous block...
+    let prev_state_root = builder.block.prev_state_root;
+    builder.block.prev_state_root = Word::zero();
+    assert!(matches!(
+        block_convert::<Fr>(&builder),
+        Err(bus_mapping::Error::InternalError(_))
+    ));
+    builder.block.prev_state_root = prev_state_root;
+
+    // ...and end at the state root of the block.
+    builder.block.eth_block.state_root = H256::zero();
+    assert!(matches!(
+        block_convert::<Fr>(&builder),
+        Err(bus_mapping::Error::InternalError(_))
+    ));
+}
+
+// High memory usage test.  Run in serial with:
+// `cargo test [...] serial_ -- --ignored --test-threads 1`
+#[ignore]
+#[test]
+fn serial_test_super_circuit_mpt_circuit() {
+    let block = block_1tx();
+    let builder = builder_with_mpt_witness(&block);
+
+    let (k, circuits, instances) = SuperCircuit::<Fr>::build_from_circuit_input_builder(
+        &builder,
+        Fr::from(TEST_MOCK_RANDOMNESS),
+    )
+    .unwrap();
+    let prover = MockProver::run(k, &circuits[0], instances[0].clone()).unwrap();
+    if let Err(err) = prover.verify() {
+        error!(
+            "supercircuit with MPT circuit verification failures: {:#?}",
+            err
+        );
+        panic!("Failed verification");
+    }
+}
+
+#[test]
+fn super_circuit_row_usage() {
+    let block = block_1tx();
+    let builder = BlockData::new_from_geth_data_with_params(block.clone(), sha3_circuits_params())
+        .new_circuit_input_builder()
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+
+    let row_usage = SuperCircuit::<Fr>::row_usage_from_circuit_input_builder(&builder).unwrap();
+    assert_eq!(row_usage.len(), 1);
+    let state = row_usage[0]
+        .iter()
+        .find(|usage| usage.name == "state")
+        .unwrap();
+    assert_eq!(state.row_num_total, 256);
+    assert!(state.row_num_real > 1 && !state.is_overflow());
+
+    // the rws of the block don't fit in a smaller state circuit
+    let block_witness = block_convert::<Fr>(&builder).unwrap();
+    let mut chunk = chunk_convert(&block_witness, &builder).unwrap().remove(0);
+    chunk.fixed_param.max_rws = state.row_num_real - 1;
+    let row_usage = SuperCircuit::row_usage_block(&block_witness, &chunk);
+    assert!(row_usage
+        .iter()
+        .any(|usage| usage.name == "state" && usage.is_overflow()));
+}
+
+#[test]
+fn super_circuit_layout_capacities_exceeded() {
+    let block = block_1tx();
+    let chunks_with_params = |circuits_params| {
+        let builder = BlockData::new_from_geth_data_with_params(block.clone(), circuits_params)
+            .new_circuit_input_builder()
+            .handle_block_with_planned_chunks(&block.eth_block, &block.geth_traces)
+            .unwrap();
+        let block_witness = block_convert::<Fr>(&builder).unwrap();
+        chunk_convert(&block_witness, &builder)
+    };
+
+    assert!(chunks_with_params(sha3_circuits_params()).is_ok());
+    assert!(matches!(
+        chunks_with_params(FixedCParams {
+            max_evm_rows: 16,
+            ..sha3_circuits_params()
+        }),
+        Err(bus_mapping::Error::CapacityExceeded(
+            CapacityExceeded::EvmRows
+        ))
+    ));
+    assert!(matches!(
+        chunks_with_params(FixedCParams {
+            max_keccak_rows: 1,
+            ..sha3_circuits_params()
+        }),
+        Err(bus_mapping::Error::CapacityExceeded(
+            CapacityExceeded::KeccakRows
+        ))
+    ));
+}
+
+#[ignore]
+#[test]
+fn serial_test_super_circuit_tampered_state_rw() {
+    let (k, mut circuits, instances, _) = SuperCircuit::<Fr>::build(
+        block_1tx_sha3(),
+        sha3_circuits_params(),
+        Fr::from(TEST_MOCK_RANDOMNESS),
+    )
+    .unwrap();
+    let (circuit, instance) = (&mut circuits[0], instances[0].clone());
+
+    // Change a stack value in the by-address rw table only, so it no longer
+    // matches the chronological rw table looked up by the EVM circuit.
+    let value = circuit
+        .state_circuit
+        .rows
+        .iter_mut()
+        .find_map(|row| match row {
+            Rw::Stack { value, .. } => Some(value),
+            _ => None,
+        })
+        .unwrap();
+    *value = *value + Word::one();
+
+    let prover = MockProver::run(k, &*circuit, instance).unwrap();
+    assert!(prover.verify().is_err());
+}
+
 #[ignore]
 #[test]
 fn test_rw_table_commitment() {
@@ -232,7 +641,8 @@ fn test_rw_table_commitment() {
 
 fn rw_table_commitment<Scheme: CommitmentScheme>(params: &Scheme::ParamsProver)
 where
-    <Scheme as CommitmentScheme>::Scalar: WithSmallOrderMulGroup<3> + eth_types::Field,
+    <Scheme as CommitmentScheme>::Scalar:
+        WithSmallOrderMulGroup<3> + eth_types::Field + halo2_base::utils::ScalarField,
 {
     let circuits_params = FixedCParams {
         max_txs: 1,
@@ -244,8 +654,14 @@ where
         max_bytecode: 512,
         max_evm_rows: 0,
         max_keccak_rows: 0,
+        keccak_params: KeccakCircuitParams::default(),
         total_chunks: 1,
         max_vertical_circuit_rows: 0,
+        max_ec_ops: PrecompileEcParams {
+            ec_add: 0,
+            ec_mul: 0,
+            ec_pairing: 0,
+        },
     };
     let rw_map = RwMap::from(&OperationContainer {
         ..Default::default()
@@ -264,11 +680,13 @@ where
             max_calldata: circuits_params.max_calldata,
             mock_randomness: TEST_MOCK_RANDOMNESS.into(),
             feature_config: FeatureConfig::default(),
+            max_mpt_rows: 0,
+            keccak_params: KeccakCircuitParams::default(),
         },
     );
     let degree = cs.degree();
 
     let advice_commitments =
-        get_rwtable_cols_commitment::<Scheme>(degree, &rows, circuits_params.max_rws, params);
+        get_rwtable_cols_commitment::<Scheme>(degree, &rows, circuits_params.max_rws, None, params);
     println!("advice_commitments len() {:?}", advice_commitments.len());
 }
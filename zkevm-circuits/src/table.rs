@@ -23,6 +23,8 @@ use itertools::Itertools;
 use std::array;
 use strum_macros::{EnumCount, EnumIter};
 
+/// blake2f table
+pub(crate) mod blake2f_table;
 /// block table
 pub(crate) mod block_table;
 /// bytecode table
@@ -31,10 +33,16 @@ pub(crate) mod bytecode_table;
 pub(crate) mod chunk_ctx_table;
 /// copy Table
 pub(crate) mod copy_table;
+/// ecc table
+pub(crate) mod ecc_table;
 /// exp(exponentiation) table
 pub(crate) mod exp_table;
+/// hash function tables
+pub(crate) mod hash_table;
 /// keccak table
 pub(crate) mod keccak_table;
+/// modexp table
+pub(crate) mod modexp_table;
 /// mpt table
 pub mod mpt_table;
 /// rw table
@@ -48,12 +56,16 @@ pub(crate) mod ux_table;
 /// withdrawal table
 pub(crate) mod wd_table;
 
+pub use blake2f_table::Blake2fTable;
 pub use block_table::{BlockContextFieldTag, BlockTable};
 pub use bytecode_table::{BytecodeFieldTag, BytecodeTable};
 pub use chunk_ctx_table::ChunkCtxTable;
 pub use copy_table::CopyTable;
+pub use ecc_table::EccTable;
 pub use exp_table::ExpTable;
+pub use hash_table::{HashTable, Ripemd160Table, Sha256Table};
 pub use keccak_table::KeccakTable;
+pub use modexp_table::ModExpTable;
 pub use ux_table::UXTable;
 
 pub use mpt_table::{MPTProofType, MptTable};
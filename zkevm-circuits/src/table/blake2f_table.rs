@@ -0,0 +1,70 @@
+use super::*;
+
+use crate::blake2f_circuit::util::blake2f;
+
+/// BLAKE2F Table, used to verify the BLAKE2 compression function F from RLC'ed input.
+#[derive(Clone, Debug)]
+pub struct Blake2fTable {
+    /// True when the row is enabled
+    pub is_enabled: Column<Advice>,
+    /// Number of rounds, i.e. the first 4 input bytes read as a big-endian integer
+    pub rounds: Column<Advice>,
+    /// 213-byte input as `RLC(reversed(input))`
+    pub input_rlc: Column<Advice>,
+    /// 64-byte output as `RLC(reversed(output))`
+    pub output_rlc: Column<Advice>,
+}
+
+impl<F: Field> LookupTable<F> for Blake2fTable {
+    fn columns(&self) -> Vec<Column<Any>> {
+        vec![
+            self.is_enabled.into(),
+            self.rounds.into(),
+            self.input_rlc.into(),
+            self.output_rlc.into(),
+        ]
+    }
+
+    fn annotations(&self) -> Vec<String> {
+        vec![
+            String::from("is_enabled"),
+            String::from("rounds"),
+            String::from("input_rlc"),
+            String::from("output_rlc"),
+        ]
+    }
+}
+
+impl<F: Field> HashTable<F> for Blake2fTable {
+    const NAME: &'static str = "blake2f table";
+
+    fn row(input: &[u8], challenges: &Challenges<Value<F>>) -> [Value<F>; 4] {
+        let output = blake2f(input).expect("valid blake2f input");
+        let rounds = u32::from_be_bytes(input[0..4].try_into().unwrap());
+        let input_rlc = challenges
+            .keccak_input()
+            .map(|challenge| rlc::value(input.iter().rev(), challenge));
+        let output_rlc = challenges
+            .keccak_input()
+            .map(|challenge| rlc::value(output.iter().rev(), challenge));
+
+        [
+            Value::known(F::ONE),
+            Value::known(F::from(rounds as u64)),
+            input_rlc,
+            output_rlc,
+        ]
+    }
+}
+
+impl Blake2fTable {
+    /// Construct a new Blake2fTable
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+        Self {
+            is_enabled: meta.advice_column(),
+            rounds: meta.advice_column(),
+            input_rlc: meta.advice_column_in(SecondPhase),
+            output_rlc: meta.advice_column_in(SecondPhase),
+        }
+    }
+}
@@ -25,6 +25,13 @@ pub enum BlockContextFieldTag {
     ChainId,
     /// Withdrawal Root field
     WithdrawalRoot,
+    /// Excess Blob Gas field
+    ExcessBlobGas,
+    /// Blob Gas Used field
+    BlobGasUsed,
+    /// Blob Base Fee field.  Derived from the excess blob gas, we add it here
+    /// for convenience.
+    BlobBaseFee,
 }
 impl_expr!(BlockContextFieldTag);
 
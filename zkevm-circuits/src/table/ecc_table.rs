@@ -0,0 +1,208 @@
+use super::*;
+
+use bus_mapping::{
+    circuit_input_builder::{EcAddOp, EcMulOp, EcPairingOp, EcPairingPair},
+    precompile::PrecompileCalls,
+};
+use eth_types::{ToBigEndian, Word};
+use halo2_proofs::halo2curves::{bn256::G1Affine, group::prime::PrimeCurveAffine};
+
+/// The ECC table is used to verify the ecAdd, ecMul and ecPairing precompile calls. The big-endian
+/// words of the I/O are RLC'd with the keccak input challenge.
+#[derive(Clone, Copy, Debug)]
+pub struct EccTable {
+    /// Since the current design of the ECC circuit assigns fixed cells for each operation, this
+    /// column indicates the type of the operation at that row, i.e. the address of the precompile.
+    pub op_type: Column<Fixed>,
+
+    /// Whether the inputs are valid, i.e. whether the precompile call succeeds.
+    pub is_valid: Column<Advice>,
+    /// RLC of Px for ecAdd and ecMul.
+    pub arg1_rlc: Column<Advice>,
+    /// RLC of Py for ecAdd and ecMul.
+    pub arg2_rlc: Column<Advice>,
+    /// RLC of Qx for ecAdd, and the scalar s, reduced modulo the order of the curve, for ecMul.
+    pub arg3_rlc: Column<Advice>,
+    /// RLC of Qy for ecAdd.
+    pub arg4_rlc: Column<Advice>,
+    /// RLC of the `N_PAIRING_PER_OP * N_BYTES_PER_PAIR` input bytes for ecPairing, padded with
+    /// zeros.
+    pub input_rlc: Column<Advice>,
+    /// RLC of Rx for ecAdd and ecMul, and the success of the pairing check for ecPairing.
+    pub output1_rlc: Column<Advice>,
+    /// RLC of Ry for ecAdd and ecMul.
+    pub output2_rlc: Column<Advice>,
+}
+
+impl<F: Field> LookupTable<F> for EccTable {
+    fn columns(&self) -> Vec<Column<Any>> {
+        vec![
+            self.op_type.into(),
+            self.is_valid.into(),
+            self.arg1_rlc.into(),
+            self.arg2_rlc.into(),
+            self.arg3_rlc.into(),
+            self.arg4_rlc.into(),
+            self.input_rlc.into(),
+            self.output1_rlc.into(),
+            self.output2_rlc.into(),
+        ]
+    }
+
+    fn annotations(&self) -> Vec<String> {
+        vec![
+            String::from("op_type"),
+            String::from("is_valid"),
+            String::from("arg1_rlc"),
+            String::from("arg2_rlc"),
+            String::from("arg3_rlc"),
+            String::from("arg4_rlc"),
+            String::from("input_rlc"),
+            String::from("output1_rlc"),
+            String::from("output2_rlc"),
+        ]
+    }
+}
+
+impl EccTable {
+    /// Construct the ECC table.
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+        Self {
+            op_type: meta.fixed_column(),
+            is_valid: meta.advice_column(),
+            arg1_rlc: meta.advice_column_in(SecondPhase),
+            arg2_rlc: meta.advice_column_in(SecondPhase),
+            arg3_rlc: meta.advice_column_in(SecondPhase),
+            arg4_rlc: meta.advice_column_in(SecondPhase),
+            input_rlc: meta.advice_column_in(SecondPhase),
+            output1_rlc: meta.advice_column_in(SecondPhase),
+            output2_rlc: meta.advice_column_in(SecondPhase),
+        }
+    }
+
+    /// Generate the ECC table assignments from the ECC ops, in the same layout as the ECC
+    /// circuit.
+    pub fn assignments<F: Field>(
+        add_ops: &[EcAddOp],
+        mul_ops: &[EcMulOp],
+        pairing_ops: &[EcPairingOp],
+        challenges: &Challenges<Value<F>>,
+    ) -> Vec<(PrecompileCalls, [Value<F>; 8])> {
+        let word_rlc = |word: Word| {
+            challenges
+                .keccak_input()
+                .map(|challenge| rlc::value(word.to_be_bytes().iter().rev(), challenge))
+        };
+        let g1_rlc = |point: Option<G1Affine>| {
+            let point = point.unwrap_or(G1Affine::identity());
+            (
+                word_rlc(Word::from_little_endian(&point.x.to_bytes())),
+                word_rlc(Word::from_little_endian(&point.y.to_bytes())),
+            )
+        };
+        let zero = Value::known(F::ZERO);
+        let is_valid = |is_valid: bool| Value::known(F::from(is_valid as u64));
+
+        let add_rows = add_ops.iter().map(|op| {
+            let (rx_rlc, ry_rlc) = g1_rlc(op.r);
+            (
+                PrecompileCalls::Bn128Add,
+                [
+                    is_valid(op.is_valid()),
+                    word_rlc(op.p.0),
+                    word_rlc(op.p.1),
+                    word_rlc(op.q.0),
+                    word_rlc(op.q.1),
+                    zero,
+                    rx_rlc,
+                    ry_rlc,
+                ],
+            )
+        });
+        let mul_rows = mul_ops.iter().map(|op| {
+            let (rx_rlc, ry_rlc) = g1_rlc(op.r);
+            (
+                PrecompileCalls::Bn128Mul,
+                [
+                    is_valid(op.is_valid()),
+                    word_rlc(op.p.0),
+                    word_rlc(op.p.1),
+                    Value::known(
+                        Word::from_little_endian(&op.s.to_bytes())
+                            .to_scalar()
+                            .expect("scalar fits in the field"),
+                    ),
+                    zero,
+                    zero,
+                    rx_rlc,
+                    ry_rlc,
+                ],
+            )
+        });
+        let pairing_rows = pairing_ops.iter().map(|op| {
+            let input_bytes = op
+                .pairs
+                .iter()
+                .flat_map(EcPairingPair::to_bytes_be)
+                .collect::<Vec<u8>>();
+            (
+                PrecompileCalls::Bn128Pairing,
+                [
+                    is_valid(op.is_valid()),
+                    zero,
+                    zero,
+                    zero,
+                    zero,
+                    challenges
+                        .keccak_input()
+                        .map(|challenge| rlc::value(input_bytes.iter().rev(), challenge)),
+                    Value::known(op.output.to_scalar().expect("EcPairing output = {0, 1}")),
+                    zero,
+                ],
+            )
+        });
+
+        add_rows.chain(mul_rows).chain(pairing_rows).collect()
+    }
+
+    /// Provide this function for the case that we want to consume an ECC table but without
+    /// running the full ECC circuit.
+    pub fn dev_load<F: Field>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        add_ops: &[EcAddOp],
+        mul_ops: &[EcMulOp],
+        pairing_ops: &[EcPairingOp],
+        challenges: &Challenges<Value<F>>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "ecc table dev load",
+            |mut region| {
+                for (offset, (op_type, row)) in
+                    Self::assignments(add_ops, mul_ops, pairing_ops, challenges)
+                        .into_iter()
+                        .enumerate()
+                {
+                    region.assign_fixed(
+                        || format!("ecc table op_type {offset}"),
+                        self.op_type,
+                        offset,
+                        || Value::known(F::from(u64::from(op_type))),
+                    )?;
+                    for (&column, value) in <EccTable as LookupTable<F>>::advice_columns(self)
+                        .iter()
+                        .zip(row.iter())
+                    {
+                        region.assign_advice(
+                            || format!("ecc table row {offset}"),
+                            column,
+                            offset,
+                            || *value,
+                        )?;
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+}
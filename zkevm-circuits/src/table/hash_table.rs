@@ -0,0 +1,316 @@
+use super::*;
+
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use std::{fmt::Debug, marker::PhantomData};
+
+/// Table of a hash function with one row per hashed byte array input, whose
+/// columns are `is_enabled` followed by three values of the input and its
+/// output.
+pub trait HashTable<F: Field>: LookupTable<F> {
+    /// Name of the table, used to annotate its region
+    const NAME: &'static str;
+
+    /// Generate the table row of the hashing of `input`.
+    fn row(input: &[u8], challenges: &Challenges<Value<F>>) -> [Value<F>; 4];
+
+    /// Assign a table row
+    fn assign_row(
+        &self,
+        region: &mut Region<F>,
+        offset: usize,
+        values: [Value<F>; 4],
+    ) -> Result<(), Error> {
+        for (column, value) in self.advice_columns().into_iter().zip(values) {
+            region.assign_advice(|| format!("assign {}", offset), column, offset, || value)?;
+        }
+        Ok(())
+    }
+
+    /// Provide this function for the case that we want to consume the table
+    /// but without running the full hash circuit
+    fn dev_load<'a>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        inputs: impl IntoIterator<Item = &'a Vec<u8>> + Clone,
+        challenges: &Challenges<Value<F>>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || Self::NAME,
+            |mut region| {
+                self.assign_row(&mut region, 0, [Value::known(F::ZERO); 4])?;
+                for (offset, input) in inputs.clone().into_iter().enumerate() {
+                    self.assign_row(&mut region, offset + 1, Self::row(input, challenges))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+/// Hash function computing the digest of a byte array input of any length.
+pub trait TableDigest: Clone + Debug {
+    /// Name of the table of the hash function
+    const NAME: &'static str;
+
+    /// Digest of `input`
+    fn digest(input: &[u8]) -> Vec<u8>;
+}
+
+/// SHA256 hash function
+#[derive(Clone, Copy, Debug)]
+pub struct Sha256Digest;
+
+impl TableDigest for Sha256Digest {
+    const NAME: &'static str = "sha256 table";
+
+    fn digest(input: &[u8]) -> Vec<u8> {
+        Sha256::digest(input).to_vec()
+    }
+}
+
+/// RIPEMD-160 hash function
+#[derive(Clone, Copy, Debug)]
+pub struct Ripemd160Digest;
+
+impl TableDigest for Ripemd160Digest {
+    const NAME: &'static str = "ripemd160 table";
+
+    fn digest(input: &[u8]) -> Vec<u8> {
+        Ripemd160::digest(input).to_vec()
+    }
+}
+
+/// Table used to verify the digests of a [`TableDigest`] from RLC'ed input.
+#[derive(Clone, Debug)]
+pub struct DigestTable<D: TableDigest> {
+    /// True when the row is enabled
+    pub is_enabled: Column<Advice>,
+    /// Byte array input as `RLC(reversed(input))`
+    pub input_rlc: Column<Advice>,
+    /// Byte array input length
+    pub input_len: Column<Advice>,
+    /// Digest as `RLC(reversed(digest))`
+    pub output_rlc: Column<Advice>,
+    _marker: PhantomData<D>,
+}
+
+/// SHA256 Table, used to verify SHA256 hashing from RLC'ed input.
+pub type Sha256Table = DigestTable<Sha256Digest>;
+
+/// RIPEMD-160 Table, used to verify RIPEMD-160 hashing from RLC'ed input.
+pub type Ripemd160Table = DigestTable<Ripemd160Digest>;
+
+impl<F: Field, D: TableDigest> LookupTable<F> for DigestTable<D> {
+    fn columns(&self) -> Vec<Column<Any>> {
+        vec![
+            self.is_enabled.into(),
+            self.input_rlc.into(),
+            self.input_len.into(),
+            self.output_rlc.into(),
+        ]
+    }
+
+    fn annotations(&self) -> Vec<String> {
+        vec![
+            String::from("is_enabled"),
+            String::from("input_rlc"),
+            String::from("input_len"),
+            String::from("output_rlc"),
+        ]
+    }
+}
+
+impl<F: Field, D: TableDigest> HashTable<F> for DigestTable<D> {
+    const NAME: &'static str = D::NAME;
+
+    fn row(input: &[u8], challenges: &Challenges<Value<F>>) -> [Value<F>; 4] {
+        let input_rlc = challenges
+            .keccak_input()
+            .map(|challenge| rlc::value(input.iter().rev(), challenge));
+        let digest = D::digest(input);
+        let output_rlc = challenges
+            .keccak_input()
+            .map(|challenge| rlc::value(digest.iter().rev(), challenge));
+
+        [
+            Value::known(F::ONE),
+            input_rlc,
+            Value::known(F::from(input.len() as u64)),
+            output_rlc,
+        ]
+    }
+}
+
+impl<D: TableDigest> DigestTable<D> {
+    /// Construct a new DigestTable
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+        Self {
+            is_enabled: meta.advice_column(),
+            input_rlc: meta.advice_column_in(SecondPhase),
+            input_len: meta.advice_column(),
+            output_rlc: meta.advice_column_in(SecondPhase),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Implement the `Circuit` trait of a hash circuit, whose config is built from
+/// its [`HashTable`] and the challenges.
+#[cfg(any(test, feature = "test-circuits"))]
+#[macro_export]
+macro_rules! impl_hash_circuit {
+    ($circuit:ident, $config:ident, $config_args:ident, $table:ident, $table_field:ident) => {
+        impl<F: eth_types::Field> halo2_proofs::plonk::Circuit<F> for $circuit<F> {
+            type Config = ($config<F>, $crate::util::Challenges);
+            type FloorPlanner = halo2_proofs::circuit::SimpleFloorPlanner;
+            type Params = ();
+
+            fn without_witnesses(&self) -> Self {
+                Self::default()
+            }
+
+            fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+                let $table_field = $table::construct(meta);
+                let challenges = $crate::util::Challenges::construct(meta);
+
+                let config = {
+                    let challenges = challenges.exprs(meta);
+                    <$config<F> as $crate::util::SubCircuitConfig<F>>::new(
+                        meta,
+                        $config_args {
+                            $table_field,
+                            challenges,
+                        },
+                    )
+                };
+                (config, challenges)
+            }
+
+            fn synthesize(
+                &self,
+                (config, challenges): Self::Config,
+                mut layouter: impl halo2_proofs::circuit::Layouter<F>,
+            ) -> Result<(), halo2_proofs::plonk::Error> {
+                let challenges = challenges.values(&mut layouter);
+                <Self as $crate::util::SubCircuit<F>>::synthesize_sub(
+                    self,
+                    &config,
+                    &challenges,
+                    &mut layouter,
+                )
+            }
+        }
+    };
+}
+
+/// Test harness shared by the circuits filling a [`HashTable`]
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use halo2_proofs::{
+        dev::{CellValue, MockProver},
+        plonk::Circuit,
+    };
+    use log::error;
+
+    /// Run `circuit` with the `MockProver`, check that its verification
+    /// result is `success`, and that its hash table, returned by `table`,
+    /// holds the rows of `inputs`, followed by rows of the `padding` input if
+    /// any.
+    pub(crate) fn verify_hash_circuit<F, C, Config, T>(
+        k: u32,
+        circuit: &C,
+        inputs: &[Vec<u8>],
+        padding: Option<&[u8]>,
+        success: bool,
+        table: fn(&Config) -> &T,
+    ) where
+        F: Field,
+        C: Circuit<F, Config = (Config, Challenges), Params = ()>,
+        T: HashTable<F>,
+    {
+        let prover = MockProver::<F>::run(k, circuit, vec![]).unwrap();
+        let (config, challenges) = C::configure(&mut ConstraintSystem::<F>::default());
+        let challenges = Challenges::mock(
+            prover.get_challenge(challenges.keccak_input()),
+            prover.get_challenge(challenges.lookup_input()),
+        );
+
+        // Check constraints.
+        let verify_result = prover.verify();
+        if verify_result.is_ok() != success {
+            if let Some(errors) = verify_result.err() {
+                for error in errors.iter() {
+                    error!("{}", error);
+                }
+            }
+            panic!();
+        }
+
+        // Extract the content of the lookup table, keeping the enabled rows.
+        let columns = table(&config)
+            .advice_columns()
+            .into_iter()
+            .map(|column| prover.advice_values(column))
+            .collect::<Vec<_>>();
+        let rows = (0..columns[0].len())
+            .filter(|&offset| assigned_non_zero(&columns[0][offset]))
+            .map(|offset| {
+                columns
+                    .iter()
+                    .map(|column| unwrap(&column[offset]))
+                    .collect::<Vec<F>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Check that all the inputs are there, and that the other rows are of
+        // the padding input.
+        match padding {
+            Some(_) => assert!(rows.len() >= inputs.len()),
+            None => assert_eq!(rows.len(), inputs.len()),
+        }
+        for (offset, row) in rows.iter().enumerate() {
+            let input = inputs
+                .get(offset)
+                .map_or_else(|| padding.unwrap(), |input| &input[..]);
+            for (expected, value) in T::row(input, &challenges).iter().zip(row) {
+                expected.assert_if_known(|expected| expected == value);
+            }
+        }
+    }
+
+    /// Check that the fixed columns and permutation of the circuits built by
+    /// `new` do not depend on the inputs.
+    pub(crate) fn check_variadic_size<F: Field, C: Circuit<F, Params = ()>>(
+        k: u32,
+        new: impl Fn(usize, Vec<Vec<u8>>) -> C,
+        inputs: Vec<Vec<u8>>,
+    ) {
+        let num_rows = 2usize.pow(k);
+        // Empty
+        let circuit = new(num_rows, vec![]);
+        let prover1 = MockProver::<F>::run(k, &circuit, vec![]).unwrap();
+
+        // Non-empty
+        let circuit = new(num_rows, inputs);
+        let prover2 = MockProver::<F>::run(k, &circuit, vec![]).unwrap();
+
+        assert_eq!(prover1.fixed(), prover2.fixed());
+        assert_eq!(prover1.permutation(), prover2.permutation());
+    }
+
+    fn assigned_non_zero<F: Field>(cv: &CellValue<F>) -> bool {
+        match *cv {
+            CellValue::Assigned(v) => !v.is_zero_vartime(),
+            _ => false,
+        }
+    }
+
+    fn unwrap<F: Field>(cv: &CellValue<F>) -> F {
+        match *cv {
+            CellValue::Assigned(f) => f,
+            _ => panic!("the cell should be assigned"),
+        }
+    }
+}
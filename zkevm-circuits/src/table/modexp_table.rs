@@ -0,0 +1,140 @@
+use super::*;
+
+use bus_mapping::{
+    circuit_input_builder::BigModExp,
+    precompile::{parse_modexp_input, ModExpAuxData},
+};
+
+/// ModExp Table, used to verify MODEXP calls from RLC'ed input.
+#[derive(Clone, Debug)]
+pub struct ModExpTable {
+    /// True when the row is enabled
+    pub is_enabled: Column<Advice>,
+    /// Input bytes, at most `MODEXP_INPUT_LIMIT`, as `RLC(reversed(input))`
+    pub input_rlc: Column<Advice>,
+    /// Number of input bytes
+    pub input_len: Column<Advice>,
+    /// Length of the base
+    pub base_len: Column<Advice>,
+    /// Length of the exponent
+    pub exp_len: Column<Advice>,
+    /// Length of the modulus
+    pub mod_len: Column<Advice>,
+    /// Index of the most significant bit of the first 32 bytes of the exponent, 0 when they
+    /// are all zero
+    pub exp_msb: Column<Advice>,
+    /// Output, i.e. `mod_len` bytes, as `RLC(reversed(output))`
+    pub output_rlc: Column<Advice>,
+}
+
+impl<F: Field> LookupTable<F> for ModExpTable {
+    fn columns(&self) -> Vec<Column<Any>> {
+        vec![
+            self.is_enabled.into(),
+            self.input_rlc.into(),
+            self.input_len.into(),
+            self.base_len.into(),
+            self.exp_len.into(),
+            self.mod_len.into(),
+            self.exp_msb.into(),
+            self.output_rlc.into(),
+        ]
+    }
+
+    fn annotations(&self) -> Vec<String> {
+        vec![
+            String::from("is_enabled"),
+            String::from("input_rlc"),
+            String::from("input_len"),
+            String::from("base_len"),
+            String::from("exp_len"),
+            String::from("mod_len"),
+            String::from("exp_msb"),
+            String::from("output_rlc"),
+        ]
+    }
+}
+
+impl ModExpTable {
+    /// Construct a new ModExpTable
+    pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+        Self {
+            is_enabled: meta.advice_column(),
+            input_rlc: meta.advice_column_in(SecondPhase),
+            input_len: meta.advice_column(),
+            base_len: meta.advice_column(),
+            exp_len: meta.advice_column(),
+            mod_len: meta.advice_column(),
+            exp_msb: meta.advice_column(),
+            output_rlc: meta.advice_column_in(SecondPhase),
+        }
+    }
+
+    /// Generate the modexp table assignments from a MODEXP event.
+    pub fn assignments<F: Field>(
+        event: &BigModExp,
+        challenges: &Challenges<Value<F>>,
+    ) -> Vec<[Value<F>; 8]> {
+        let parts = parse_modexp_input(&event.input).expect("modexp input within size limits");
+        let aux_data = ModExpAuxData::new(&parts, &event.input, &event.output, &[]);
+        let input_rlc = challenges
+            .keccak_input()
+            .map(|challenge| rlc::value(event.input.iter().rev(), challenge));
+        let output_rlc = challenges
+            .keccak_input()
+            .map(|challenge| rlc::value(event.output.iter().rev(), challenge));
+
+        vec![[
+            Value::known(F::ONE),
+            input_rlc,
+            Value::known(F::from(event.input.len() as u64)),
+            Value::known(F::from(aux_data.base_len as u64)),
+            Value::known(F::from(aux_data.exp_len as u64)),
+            Value::known(F::from(aux_data.mod_len as u64)),
+            Value::known(F::from(aux_data.exp_msb)),
+            output_rlc,
+        ]]
+    }
+
+    /// Assign a table row for modexp table
+    pub fn assign_row<F: Field>(
+        &self,
+        region: &mut Region<F>,
+        offset: usize,
+        values: [Value<F>; 8],
+    ) -> Result<(), Error> {
+        for (&column, value) in <ModExpTable as LookupTable<F>>::advice_columns(self)
+            .iter()
+            .zip(values.iter())
+        {
+            region.assign_advice(|| format!("assign {}", offset), column, offset, || *value)?;
+        }
+        Ok(())
+    }
+
+    /// Provide this function for the case that we want to consume a modexp
+    /// table but without running the full modexp circuit
+    pub fn dev_load<'a, F: Field>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        events: impl IntoIterator<Item = &'a BigModExp> + Clone,
+        challenges: &Challenges<Value<F>>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "modexp table",
+            |mut region| {
+                let mut offset = 0;
+                self.assign_row(&mut region, offset, [Value::known(F::ZERO); 8])?;
+                offset += 1;
+
+                for event in events.clone() {
+                    for row in Self::assignments(event, challenges) {
+                        self.assign_row(&mut region, offset, row)?;
+                        offset += 1;
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+}
@@ -185,13 +185,14 @@ impl RwTable {
     }
 }
 
-/// get rw table column commitment
+/// get rw table column commitment, with the table loaded as in the circuits
+/// from `rws` padded to `n_rows` after `prev_chunk_last_rw`.
 /// implementation snippet from halo2 `create_proof` https://github.com/privacy-scaling-explorations/halo2/blob/9b33f9ce524dbb9133fc8b9638b2afd0571659a8/halo2_proofs/src/plonk/prover.rs#L37
-#[allow(unused)]
 pub fn get_rwtable_cols_commitment<Scheme: CommitmentScheme>(
     degree: usize,
     rws: &[Rw],
     n_rows: usize,
+    prev_chunk_last_rw: Option<Rw>,
     params_prover: &Scheme::ParamsProver,
 ) -> Vec<<Scheme as CommitmentScheme>::Curve>
 where
@@ -321,7 +322,7 @@ where
         }
     }
 
-    let rwtable_circuit = RwTableCircuit::new(rws, n_rows, None);
+    let rwtable_circuit = RwTableCircuit::new(rws, n_rows, prev_chunk_last_rw);
 
     let domain = EvaluationDomain::<<Scheme as CommitmentScheme>::Scalar>::new(
         degree as u32,
@@ -379,7 +380,6 @@ struct RwTableCircuit<'a> {
 }
 
 impl<'a> RwTableCircuit<'a> {
-    #[allow(dead_code)]
     pub(crate) fn new(rws: &'a [Rw], n_rows: usize, prev_chunk_last_rw: Option<Rw>) -> Self {
         Self {
             rws,
@@ -1,4 +1,5 @@
 use super::*;
+use eth_types::evm_types::MAX_BLOB_HASHES_PER_TX;
 
 /// Tag used to identify each field in the transaction in a row of the
 /// transaction table.
@@ -29,6 +30,11 @@ pub enum TxFieldTag {
     TxSignHash,
     /// CallData
     CallData,
+    /// Number of blob versioned hashes (EIP-4844)
+    BlobVersionedHashesLength,
+    /// BlobVersionedHash, padded with zeros up to `MAX_BLOB_HASHES_PER_TX`
+    /// hashes for every tx
+    BlobVersionedHash,
 }
 impl_expr!(TxFieldTag);
 
@@ -177,13 +183,26 @@ impl TxTable {
                             TxContextFieldTag::CallDataGasCost,
                             WordLoHi::from(tx.call_data_gas_cost()),
                         ),
+                        (
+                            TxContextFieldTag::BlobVersionedHashesLength,
+                            WordLoHi::from(tx.blob_versioned_hashes.len() as u64),
+                        ),
                     ]
-                    .iter()
-                    .map(|&(tag, word)| {
+                    .into_iter()
+                    .map(|(tag, word)| (tag, 0, word))
+                    .chain((0..MAX_BLOB_HASHES_PER_TX).map(|idx| {
+                        let hash = tx.blob_versioned_hashes.get(idx).copied();
+                        (
+                            TxContextFieldTag::BlobVersionedHash,
+                            idx,
+                            WordLoHi::from(hash.unwrap_or_default()),
+                        )
+                    }))
+                    .map(|(tag, idx, word)| {
                         [
                             tx_id,
                             Value::known(F::from(tag as u64)),
-                            Value::known(F::ZERO),
+                            Value::known(F::from(idx as u64)),
                             Value::known(word.lo()),
                             Value::known(word.hi()),
                         ]
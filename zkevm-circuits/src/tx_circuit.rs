@@ -18,7 +18,9 @@ use crate::{
     util::{word::WordLoHi, Challenges, SubCircuit, SubCircuitConfig},
     witness::{self, Chunk},
 };
-use eth_types::{geth_types::Transaction, sign_types::SignData, Field};
+use eth_types::{
+    evm_types::MAX_BLOB_HASHES_PER_TX, geth_types::Transaction, sign_types::SignData, Field,
+};
 use halo2_proofs::{
     circuit::{AssignedCell, Layouter, Region, Value},
     plonk::{Advice, Column, ConstraintSystem, Error, Expression, Fixed},
@@ -30,10 +32,11 @@ use std::{marker::PhantomData, ops::Deref};
 
 /// Number of static fields per tx: [nonce, gas, gas_price,
 /// caller_address, callee_address, is_create, value, call_data_length,
-/// call_data_gas_cost, tx_sign_hash].
+/// call_data_gas_cost, tx_sign_hash, blob_versioned_hashes_length] followed by
+/// `MAX_BLOB_HASHES_PER_TX` blob versioned hashes.
 /// Note that call data bytes are laid out in the TxTable after all the static
 /// fields arranged by txs.
-pub(crate) const TX_LEN: usize = 10;
+pub(crate) const TX_LEN: usize = 11 + MAX_BLOB_HASHES_PER_TX;
 
 /// Config for TxCircuit
 #[derive(Clone, Debug)]
@@ -264,6 +267,28 @@ impl<F: Field> TxCircuit<F> {
                             _ => (),
                         }
                     }
+
+                    config.assign_row(
+                        &mut region,
+                        offset,
+                        i + 1,
+                        TxFieldTag::BlobVersionedHashesLength,
+                        0,
+                        WordLoHi::from(tx.blob_versioned_hashes.len() as u64).into_value(),
+                    )?;
+                    offset += 1;
+                    for index in 0..MAX_BLOB_HASHES_PER_TX {
+                        let hash = tx.blob_versioned_hashes.get(index).copied();
+                        config.assign_row(
+                            &mut region,
+                            offset,
+                            i + 1,
+                            TxFieldTag::BlobVersionedHash,
+                            index,
+                            WordLoHi::from(hash.unwrap_or_default()).into_value(),
+                        )?;
+                        offset += 1;
+                    }
                 }
 
                 // Assign call data
@@ -2,7 +2,7 @@
 pub mod int_decomposition;
 pub mod word;
 
-use bus_mapping::evm::OpcodeId;
+use bus_mapping::{circuit_input_builder::FixedCParams, evm::OpcodeId};
 use halo2_proofs::{
     circuit::{Layouter, Value},
     plonk::{
@@ -26,6 +26,9 @@ pub mod cell_placement_strategy;
 /// Chunk context config
 pub mod chunk_ctx;
 
+/// Assignment of regions in parallel
+pub(crate) mod parallel;
+
 /// Steal the expression from gate
 pub fn query_expression<F: Field, T>(
     meta: &mut ConstraintSystem<F>,
@@ -157,6 +160,12 @@ pub trait SubCircuit<F: Field> {
     /// `meta.blinding_factors() + 1`.
     fn unusable_rows() -> usize;
 
+    /// Returns number of unusable rows of the SubCircuit configured with
+    /// `params`, for the SubCircuits whose layout depends on them.
+    fn unusable_rows_with_params(_params: &FixedCParams) -> usize {
+        Self::unusable_rows()
+    }
+
     /// Create a new SubCircuit from a witness Block
     fn new_from_block(block: &witness::Block<F>, chunk: &Chunk<F>) -> Self;
 
@@ -180,6 +189,41 @@ pub trait SubCircuit<F: Field> {
     fn min_num_rows_block(block: &witness::Block<F>, chunk: &Chunk<F>) -> (usize, usize);
 }
 
+/// Rows required by a SubCircuit to prove a chunk of a block, versus the rows
+/// it has, as returned by [`SubCircuit::min_num_rows_block`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubCircuitRowUsage {
+    /// Name of the SubCircuit
+    pub name: &'static str,
+    /// Number of rows required, without padding
+    pub row_num_real: usize,
+    /// Number of rows of the SubCircuit, with padding.  For the SubCircuits
+    /// whose capacity is set to 0 in the `FixedCParams`, it is computed from
+    /// the block, so it's not a limit.
+    pub row_num_total: usize,
+}
+
+impl SubCircuitRowUsage {
+    /// Row usage of the SubCircuit `C` proving `chunk` of `block`
+    pub fn new<F: Field, C: SubCircuit<F>>(
+        name: &'static str,
+        block: &witness::Block<F>,
+        chunk: &Chunk<F>,
+    ) -> Self {
+        let (row_num_real, row_num_total) = C::min_num_rows_block(block, chunk);
+        Self {
+            name,
+            row_num_real,
+            row_num_total,
+        }
+    }
+
+    /// Returns true if the SubCircuit lacks rows to prove the chunk.
+    pub fn is_overflow(&self) -> bool {
+        self.row_num_real > self.row_num_total
+    }
+}
+
 /// SubCircuit configuration
 pub trait SubCircuitConfig<F: Field> {
     /// Config constructor arguments
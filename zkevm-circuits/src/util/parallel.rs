@@ -0,0 +1,570 @@
+//! Assignment of regions in parallel.
+//!
+//! halo2's [`Region`] and [`Layouter`] can't be shared between threads, so the
+//! assignments made on the worker threads are recorded, by a
+//! [`RecordingLayouter`] or in a [`RegionRecord`], and then replayed in order
+//! into the actual layouter or region.  The cells returned while recording are
+//! mapped to the assigned ones on replay, so the copy and constant constraints
+//! made with them are kept.
+//!
+//! The annotations of the cells are dropped, only the names of the regions and
+//! columns are replayed.
+//!
+//! The assignments of a task are recorded without the actual layouter, so they
+//! fail with [`Error::Synthesis`] when they need something outside of the task:
+//! - the instance values, with `assign_advice_from_instance` or `instance_value`, which are unknown
+//!   while recording;
+//! - a cell assigned by another task, in a copy or instance constraint, as the cells are only
+//!   mapped to the assigned ones within a task on replay.
+//!
+//! The challenges given to [`assign_in_parallel`] are forwarded to the tasks,
+//! the other ones are unknown to them.
+
+use eth_types::Field;
+use halo2_proofs::{
+    circuit::{
+        layouter::{RegionLayouter, TableLayouter},
+        Cell, Layouter, Region, RegionIndex, Table, Value,
+    },
+    plonk::{
+        Advice, Any, Assigned, Challenge, Column, Error, Fixed, Instance, Selector, TableColumn,
+    },
+};
+use std::{collections::HashMap, ops::Range};
+
+/// Number of threads the assignments are split between.
+pub(crate) fn num_threads() -> usize {
+    #[cfg(feature = "parallel")]
+    {
+        rayon::current_num_threads()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        1
+    }
+}
+
+/// Map `f` over `items`, in parallel with the `parallel` feature and one after
+/// the other otherwise.
+pub(crate) fn par_map<T, R, M>(items: &[T], f: M) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    M: Fn(&T) -> R + Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        items.par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        items.iter().map(f).collect()
+    }
+}
+
+/// Split `0..len` in contiguous ranges, one per thread.
+pub(crate) fn split_range(len: usize) -> Vec<Range<usize>> {
+    let size = len.div_ceil(num_threads()).max(1);
+    (0..len)
+        .step_by(size)
+        .map(|start| start..(start + size).min(len))
+        .collect()
+}
+
+/// Assignments of a sub-circuit, made on a [`RecordingLayouter`].
+pub(crate) type LayouterTask<'a, F> =
+    Box<dyn Fn(&mut RecordingLayouter<F>) -> Result<(), Error> + Send + Sync + 'a>;
+
+/// Run each of `tasks` on its own [`RecordingLayouter`], in parallel with the
+/// `parallel` feature, and replay them in order into `layouter`.  The values of
+/// `challenges` in `layouter` are forwarded to the tasks.
+pub(crate) fn assign_in_parallel<F: Field>(
+    layouter: &mut impl Layouter<F>,
+    challenges: &[Challenge],
+    tasks: &[LayouterTask<'_, F>],
+) -> Result<(), Error> {
+    let challenges = challenges
+        .iter()
+        .map(|challenge| (*challenge, layouter.get_challenge(*challenge)))
+        .collect::<Vec<_>>();
+    let recordings = par_map(tasks, |task| {
+        let mut recording = RecordingLayouter::new(challenges.clone());
+        task(&mut recording).map(|_| recording)
+    });
+    for recording in recordings {
+        recording?.replay(layouter)?;
+    }
+    Ok(())
+}
+
+/// Assigned cells by the cells returned while recording, within a task
+#[derive(Debug, Default)]
+struct CellMap(HashMap<Cell, Cell>);
+
+impl CellMap {
+    fn insert(&mut self, recorded: Cell, assigned: Cell) {
+        self.0.insert(recorded, assigned);
+    }
+
+    /// Returns the assigned cell of `recorded`, or [`Error::Synthesis`] if it
+    /// hasn't been assigned by the same task.
+    fn get(&self, recorded: Cell) -> Result<Cell, Error> {
+        self.0.get(&recorded).copied().ok_or(Error::Synthesis)
+    }
+}
+
+#[derive(Debug)]
+enum RegionOp<F: Field> {
+    NameColumn(String, Column<Any>),
+    EnableSelector(Selector, usize),
+    AssignAdvice(Column<Advice>, usize, Value<Assigned<F>>),
+    AssignAdviceFromConstant(Column<Advice>, usize, Assigned<F>),
+    AssignFixed(Column<Fixed>, usize, Value<Assigned<F>>),
+    ConstrainConstant(Cell, Assigned<F>),
+    ConstrainEqual(Cell, Cell),
+}
+
+/// Assignments of a region, recorded to be replayed into another one.  It's
+/// turned into a [`Region`] with `Region::from(&mut record as &mut dyn
+/// RegionLayouter<F>)`.
+#[derive(Debug)]
+pub(crate) struct RegionRecord<F: Field> {
+    index: usize,
+    // The assignments of the measuring pass of a region aren't recorded
+    is_recording: bool,
+    ops: Vec<RegionOp<F>>,
+}
+
+impl<F: Field> Default for RegionRecord<F> {
+    fn default() -> Self {
+        Self::new(0, true)
+    }
+}
+
+impl<F: Field> RegionRecord<F> {
+    fn new(index: usize, is_recording: bool) -> Self {
+        Self {
+            index,
+            is_recording,
+            ops: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, op: impl FnOnce() -> RegionOp<F>) {
+        if self.is_recording {
+            self.ops.push(op());
+        }
+    }
+
+    fn cell(&self, column: Column<Any>, offset: usize) -> Cell {
+        Cell {
+            region_index: RegionIndex::from(self.index),
+            row_offset: offset,
+            column,
+        }
+    }
+
+    /// Replay the assignments into `region`.
+    pub(crate) fn replay(&self, region: &mut Region<'_, F>) -> Result<(), Error> {
+        self.replay_with(region, &mut CellMap::default())
+    }
+
+    fn replay_with(&self, region: &mut Region<'_, F>, cells: &mut CellMap) -> Result<(), Error> {
+        for op in &self.ops {
+            match op {
+                RegionOp::NameColumn(name, column) => region.name_column(|| name, *column),
+                RegionOp::EnableSelector(selector, offset) => {
+                    region.enable_selector(|| "", selector, *offset)?
+                }
+                RegionOp::AssignAdvice(column, offset, value) => {
+                    let assigned = region.assign_advice(|| "", *column, *offset, || *value)?;
+                    cells.insert(self.cell((*column).into(), *offset), assigned.cell());
+                }
+                RegionOp::AssignAdviceFromConstant(column, offset, constant) => {
+                    let assigned =
+                        region.assign_advice_from_constant(|| "", *column, *offset, *constant)?;
+                    cells.insert(self.cell((*column).into(), *offset), assigned.cell());
+                }
+                RegionOp::AssignFixed(column, offset, value) => {
+                    let assigned = region.assign_fixed(|| "", *column, *offset, || *value)?;
+                    cells.insert(self.cell((*column).into(), *offset), assigned.cell());
+                }
+                RegionOp::ConstrainConstant(cell, constant) => {
+                    region.constrain_constant(cells.get(*cell)?, *constant)?
+                }
+                RegionOp::ConstrainEqual(left, right) => {
+                    region.constrain_equal(cells.get(*left)?, cells.get(*right)?)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<F: Field> RegionLayouter<F> for RegionRecord<F> {
+    fn enable_selector<'v>(
+        &'v mut self,
+        _annotation: &'v (dyn Fn() -> String + 'v),
+        selector: &Selector,
+        offset: usize,
+    ) -> Result<(), Error> {
+        self.record(|| RegionOp::EnableSelector(*selector, offset));
+        Ok(())
+    }
+
+    fn name_column<'v>(
+        &'v mut self,
+        annotation: &'v (dyn Fn() -> String + 'v),
+        column: Column<Any>,
+    ) {
+        self.record(|| RegionOp::NameColumn(annotation(), column));
+    }
+
+    fn assign_advice<'v>(
+        &'v mut self,
+        _annotation: &'v (dyn Fn() -> String + 'v),
+        column: Column<Advice>,
+        offset: usize,
+        to: &'v mut (dyn FnMut() -> Value<Assigned<F>> + 'v),
+    ) -> Result<Cell, Error> {
+        self.record(|| RegionOp::AssignAdvice(column, offset, to()));
+        Ok(self.cell(column.into(), offset))
+    }
+
+    fn assign_advice_from_constant<'v>(
+        &'v mut self,
+        _annotation: &'v (dyn Fn() -> String + 'v),
+        column: Column<Advice>,
+        offset: usize,
+        constant: Assigned<F>,
+    ) -> Result<Cell, Error> {
+        self.record(|| RegionOp::AssignAdviceFromConstant(column, offset, constant));
+        Ok(self.cell(column.into(), offset))
+    }
+
+    fn assign_advice_from_instance<'v>(
+        &mut self,
+        _annotation: &'v (dyn Fn() -> String + 'v),
+        _instance: Column<Instance>,
+        _row: usize,
+        _advice: Column<Advice>,
+        _offset: usize,
+    ) -> Result<(Cell, Value<F>), Error> {
+        // The instance values aren't known while recording
+        Err(Error::Synthesis)
+    }
+
+    fn instance_value(
+        &mut self,
+        _instance: Column<Instance>,
+        _row: usize,
+    ) -> Result<Value<F>, Error> {
+        // The instance values aren't known while recording
+        Err(Error::Synthesis)
+    }
+
+    fn assign_fixed<'v>(
+        &'v mut self,
+        _annotation: &'v (dyn Fn() -> String + 'v),
+        column: Column<Fixed>,
+        offset: usize,
+        to: &'v mut (dyn FnMut() -> Value<Assigned<F>> + 'v),
+    ) -> Result<Cell, Error> {
+        self.record(|| RegionOp::AssignFixed(column, offset, to()));
+        Ok(self.cell(column.into(), offset))
+    }
+
+    fn constrain_constant(&mut self, cell: Cell, constant: Assigned<F>) -> Result<(), Error> {
+        self.record(|| RegionOp::ConstrainConstant(cell, constant));
+        Ok(())
+    }
+
+    fn constrain_equal(&mut self, left: Cell, right: Cell) -> Result<(), Error> {
+        self.record(|| RegionOp::ConstrainEqual(left, right));
+        Ok(())
+    }
+}
+
+/// Assignments of a lookup table, recorded to be replayed into another one.
+#[derive(Debug)]
+struct TableRecord<F: Field> {
+    cells: Vec<(TableColumn, usize, Value<Assigned<F>>)>,
+}
+
+impl<F: Field> TableRecord<F> {
+    fn replay(&self, table: &mut Table<'_, F>) -> Result<(), Error> {
+        for (column, offset, value) in &self.cells {
+            table.assign_cell(|| "", *column, *offset, || *value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<F: Field> TableLayouter<F> for TableRecord<F> {
+    fn assign_cell<'v>(
+        &'v mut self,
+        _annotation: &'v (dyn Fn() -> String + 'v),
+        column: TableColumn,
+        offset: usize,
+        to: &'v mut (dyn FnMut() -> Value<Assigned<F>> + 'v),
+    ) -> Result<(), Error> {
+        self.cells.push((column, offset, to()));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+enum LayouterOp<F: Field> {
+    Region(String, RegionRecord<F>),
+    Table(String, TableRecord<F>),
+    ConstrainInstance(Cell, Column<Instance>, usize),
+}
+
+/// Layouter recording the regions and tables assigned with it, to be replayed
+/// into another layouter.  Only the challenges it's created with are known
+/// while recording.
+#[derive(Debug)]
+pub(crate) struct RecordingLayouter<F: Field> {
+    num_regions: usize,
+    ops: Vec<LayouterOp<F>>,
+    challenges: Vec<(Challenge, Value<F>)>,
+}
+
+impl<F: Field> RecordingLayouter<F> {
+    fn new(challenges: Vec<(Challenge, Value<F>)>) -> Self {
+        Self {
+            num_regions: 0,
+            ops: Vec::new(),
+            challenges,
+        }
+    }
+
+    /// Replay the recorded regions, tables and instance constraints into
+    /// `layouter`.
+    pub(crate) fn replay(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+        let mut cells = CellMap::default();
+        for op in &self.ops {
+            match op {
+                LayouterOp::Region(name, record) => layouter.assign_region(
+                    || name,
+                    |mut region| record.replay_with(&mut region, &mut cells),
+                )?,
+                LayouterOp::Table(name, record) => {
+                    layouter.assign_table(|| name, |mut table| record.replay(&mut table))?
+                }
+                LayouterOp::ConstrainInstance(cell, column, row) => {
+                    layouter.constrain_instance(cells.get(*cell)?, *column, *row)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<F: Field> Layouter<F> for RecordingLayouter<F> {
+    type Root = Self;
+
+    fn assign_region<A, AR, N, NR>(&mut self, name: N, mut assignment: A) -> Result<AR, Error>
+    where
+        A: FnMut(Region<'_, F>) -> Result<AR, Error>,
+        N: Fn() -> NR,
+        NR: Into<String>,
+    {
+        let index = self.num_regions;
+        self.num_regions += 1;
+
+        // Like the `SimpleFloorPlanner`, call the assignment a first time to
+        // measure the region, as some circuits skip this pass.
+        let mut shape = RegionRecord::new(index, false);
+        assignment((&mut shape as &mut dyn RegionLayouter<F>).into())?;
+
+        let mut record = RegionRecord::new(index, true);
+        let result = assignment((&mut record as &mut dyn RegionLayouter<F>).into())?;
+        self.ops.push(LayouterOp::Region(name().into(), record));
+        Ok(result)
+    }
+
+    fn assign_table<A, N, NR>(&mut self, name: N, mut assignment: A) -> Result<(), Error>
+    where
+        A: FnMut(Table<'_, F>) -> Result<(), Error>,
+        N: Fn() -> NR,
+        NR: Into<String>,
+    {
+        let mut record = TableRecord { cells: Vec::new() };
+        assignment((&mut record as &mut dyn TableLayouter<F>).into())?;
+        self.ops.push(LayouterOp::Table(name().into(), record));
+        Ok(())
+    }
+
+    fn constrain_instance(
+        &mut self,
+        cell: Cell,
+        column: Column<Instance>,
+        row: usize,
+    ) -> Result<(), Error> {
+        self.ops
+            .push(LayouterOp::ConstrainInstance(cell, column, row));
+        Ok(())
+    }
+
+    fn get_challenge(&self, challenge: Challenge) -> Value<F> {
+        self.challenges
+            .iter()
+            .find(|(forwarded, _)| *forwarded == challenge)
+            .map_or_else(Value::unknown, |(_, value)| *value)
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn push_namespace<NR, N>(&mut self, _name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _gadget_name: Option<String>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{
+        circuit::SimpleFloorPlanner,
+        dev::MockProver,
+        halo2curves::bn256::Fr,
+        plonk::{Circuit, ConstraintSystem, FirstPhase, SecondPhase},
+        poly::Rotation,
+    };
+
+    #[derive(Clone, Debug)]
+    struct TestCircuitConfig {
+        q_enable: Selector,
+        fixed: Column<Fixed>,
+        value: Column<Advice>,
+        value_rlc: Column<Advice>,
+        instance: Column<Instance>,
+        challenge: Challenge,
+    }
+
+    /// Assign `value` and its product by the challenge, and expose it at
+    /// `row` of the instance through a copy in another region.
+    fn assign_value(
+        config: &TestCircuitConfig,
+        value: u64,
+        row: usize,
+        layouter: &mut impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let challenge = layouter.get_challenge(config.challenge);
+        let value = Value::known(Fr::from(value));
+        let cell = layouter.assign_region(
+            || "value",
+            |mut region| {
+                config.q_enable.enable(&mut region, 0)?;
+                region.assign_fixed(|| "fixed", config.fixed, 0, || value)?;
+                region.assign_advice(|| "value_rlc", config.value_rlc, 0, || value * challenge)?;
+                Ok(region
+                    .assign_advice(|| "value", config.value, 0, || value)?
+                    .cell())
+            },
+        )?;
+        let copy = layouter.assign_region(
+            || "copy",
+            |mut region| {
+                let copy = region.assign_advice(|| "value", config.value, 0, || value)?;
+                region.constrain_equal(cell, copy.cell())?;
+                Ok(copy.cell())
+            },
+        )?;
+        layouter.constrain_instance(copy, config.instance, row)
+    }
+
+    #[derive(Default)]
+    struct TestCircuit {
+        parallel: bool,
+    }
+
+    impl Circuit<Fr> for TestCircuit {
+        type Config = TestCircuitConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                parallel: self.parallel,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let q_enable = meta.selector();
+            let fixed = meta.fixed_column();
+            let value = meta.advice_column();
+            let challenge = meta.challenge_usable_after(FirstPhase);
+            let value_rlc = meta.advice_column_in(SecondPhase);
+            let instance = meta.instance_column();
+            meta.enable_equality(value);
+            meta.enable_equality(instance);
+
+            meta.create_gate("value_rlc = fixed * challenge", |meta| {
+                let q_enable = meta.query_selector(q_enable);
+                let fixed = meta.query_fixed(fixed, Rotation::cur());
+                let value = meta.query_advice(value, Rotation::cur());
+                let value_rlc = meta.query_advice(value_rlc, Rotation::cur());
+                let challenge = meta.query_challenge(challenge);
+                vec![
+                    q_enable.clone() * (value.clone() - fixed),
+                    q_enable * (value_rlc - value * challenge),
+                ]
+            });
+
+            TestCircuitConfig {
+                q_enable,
+                fixed,
+                value,
+                value_rlc,
+                instance,
+                challenge,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let config = &config;
+            if !self.parallel {
+                for row in 0..2 {
+                    assign_value(config, row as u64 + 1, row, &mut layouter)?;
+                }
+                return Ok(());
+            }
+
+            let tasks: Vec<LayouterTask<Fr>> = (0..2)
+                .map(|row| {
+                    Box::new(move |layouter: &mut RecordingLayouter<Fr>| {
+                        assign_value(config, row as u64 + 1, row, layouter)
+                    }) as LayouterTask<Fr>
+                })
+                .collect();
+            assign_in_parallel(&mut layouter, &[config.challenge], &tasks)
+        }
+    }
+
+    #[test]
+    fn parallel_assignment_matches_serial() {
+        let k = 5;
+        let instance = vec![vec![Fr::from(1), Fr::from(2)]];
+        let serial =
+            MockProver::run(k, &TestCircuit { parallel: false }, instance.clone()).unwrap();
+        let parallel = MockProver::run(k, &TestCircuit { parallel: true }, instance).unwrap();
+        assert_eq!(serial.advice(), parallel.advice());
+        assert_eq!(serial.fixed(), parallel.fixed());
+        assert_eq!(serial.permutation(), parallel.permutation());
+        assert_eq!(serial.verify(), Ok(()));
+        assert_eq!(parallel.verify(), Ok(()));
+    }
+}
@@ -2,12 +2,13 @@ use std::collections::BTreeMap;
 
 use super::{
     rw::{RwFingerprints, ToVec},
-    ExecStep, Rw, RwMap, Transaction,
+    ExecStep, MptUpdates, Rw, RwMap, Transaction,
 };
 use crate::{
     evm_circuit::{detect_fixed_table_tags, EvmCircuit},
     exp_circuit::param::OFFSET_INCREMENT,
     instance::public_data_convert,
+    mpt_circuit::{load_proof, proof_roots, witness_row::Node},
     table::BlockContextFieldTag,
     util::{log2_ceil, unwrap_value, word::WordLoHi, SubCircuit},
     witness::Chunk,
@@ -19,7 +20,7 @@ use bus_mapping::{
     state_db::CodeDB,
     Error,
 };
-use eth_types::{sign_types::SignData, Address, Field, ToScalar, Word, H256};
+use eth_types::{sign_types::SignData, Address, Field, ToScalar, ToWord, Word, H256};
 
 use gadgets::permutation::get_permutation_fingerprints;
 use halo2_proofs::circuit::Value;
@@ -67,6 +68,9 @@ pub struct Block<F> {
     pub eth_block: eth_types::Block<eth_types::Transaction>,
     /// rw_table padding meta data
     pub rw_padding_meta: BTreeMap<usize, i32>,
+    /// MPT witness proving the account and storage updates of the block, empty
+    /// when the MPT circuit is disabled
+    pub mpt_nodes: Vec<Node>,
 }
 
 impl<F: Field> Block<F> {
@@ -87,6 +91,20 @@ impl<F: Field> Block<F> {
         }
     }
 
+    /// Get the MPT updates of the `rows` sorted by address, with the state roots
+    /// proven by the MPT witness or mock roots when there is none.
+    pub(crate) fn mpt_updates(&self, rows: &[Rw]) -> MptUpdates {
+        if self.mpt_nodes.is_empty() {
+            MptUpdates::mock_from(rows)
+        } else {
+            MptUpdates::from_rows_with_roots(
+                rows,
+                self.prev_state_root,
+                proof_roots(&self.mpt_nodes),
+            )
+        }
+    }
+
     /// Get signature (witness) from the block for tx signatures and ecRecover calls.
     pub(crate) fn get_sign_data(&self, padding: bool) -> Vec<SignData> {
         let mut signatures: Vec<SignData> = self
@@ -145,7 +163,7 @@ impl<F: Field> Block<F> {
         let num_rows_required_for_rw_table: usize = self.circuits_params.max_rws;
         let num_rows_required_for_fixed_table: usize = detect_fixed_table_tags(self)
             .iter()
-            .map(|tag| tag.build::<F>().count())
+            .map(|tag| tag.build::<F>(self.feature_config.hardfork).count())
             .sum();
         let num_rows_required_for_bytecode_table =
             self.bytecodes.num_rows_required_for_bytecode_table();
@@ -205,6 +223,12 @@ pub struct BlockContext {
     pub difficulty: Word,
     /// The base fee, the minimum amount of gas fee for a transaction
     pub base_fee: Word,
+    /// The excess blob gas of the block
+    pub excess_blob_gas: u64,
+    /// The blob gas used by the block
+    pub blob_gas_used: u64,
+    /// The blob base fee, the price of a unit of blob gas
+    pub blob_base_fee: Word,
     /// The hash of previous blocks
     pub history_hashes: Vec<Word>,
     /// The chain id
@@ -266,6 +290,24 @@ impl BlockContext {
                     Value::known(WordLoHi::from(self.withdrawals_root).lo()),
                     Value::known(WordLoHi::from(self.withdrawals_root).hi()),
                 ],
+                [
+                    Value::known(F::from(BlockContextFieldTag::ExcessBlobGas as u64)),
+                    Value::known(F::ZERO),
+                    Value::known(F::from(self.excess_blob_gas)),
+                    Value::known(F::ZERO),
+                ],
+                [
+                    Value::known(F::from(BlockContextFieldTag::BlobGasUsed as u64)),
+                    Value::known(F::ZERO),
+                    Value::known(F::from(self.blob_gas_used)),
+                    Value::known(F::ZERO),
+                ],
+                [
+                    Value::known(F::from(BlockContextFieldTag::BlobBaseFee as u64)),
+                    Value::known(F::ZERO),
+                    Value::known(WordLoHi::from(self.blob_base_fee).lo()),
+                    Value::known(WordLoHi::from(self.blob_base_fee).hi()),
+                ],
             ],
             {
                 let len_history = self.history_hashes.len();
@@ -296,6 +338,9 @@ impl From<&circuit_input_builder::Block> for BlockContext {
             timestamp: block.timestamp,
             difficulty: block.difficulty,
             base_fee: block.base_fee,
+            excess_blob_gas: block.excess_blob_gas,
+            blob_gas_used: block.blob_gas_used,
+            blob_base_fee: block.blob_base_fee,
             history_hashes: block.history_hashes.clone(),
             chain_id: block.chain_id,
             withdrawals_root: block.withdrawals_root().as_fixed_bytes().into(),
@@ -332,6 +377,31 @@ pub fn block_convert<F: Field>(
             map
         });
 
+    let mpt_nodes = match &block.mpt_witness {
+        Some(mpt_witness) => load_proof(mpt_witness.as_bytes()).map_err(Error::SerdeError)?,
+        None if builder.feature_config.mpt_circuit => {
+            return Err(Error::InternalError(
+                "mpt_circuit is enabled but the block has no MPT witness",
+            ))
+        }
+        None => Vec::new(),
+    };
+    if !mpt_nodes.is_empty() && builder.circuits_params.total_chunks > 1 {
+        // The updates of a chunk can't be matched with the proofs of the whole
+        // block yet.
+        return Err(Error::InternalError(
+            "the MPT circuit only supports blocks proven in a single chunk",
+        ));
+    }
+    let roots = proof_roots(&mpt_nodes);
+    if let (Some((old_root, _)), Some((_, new_root))) = (roots.first(), roots.last()) {
+        if *old_root != block.prev_state_root || *new_root != block.eth_block.state_root.to_word() {
+            return Err(Error::InternalError(
+                "MPT witness doesn't transition between the block state roots",
+            ));
+        }
+    }
+
     let mut block = Block {
         // randomness: F::from(0x100), // Special value to reveal elements after RLC
         randomness: F::from(0xcafeu64),
@@ -352,6 +422,7 @@ pub fn block_convert<F: Field>(
         eth_block: block.eth_block.clone(),
         end_block: block.end_block.clone(),
         rw_padding_meta,
+        mpt_nodes,
     };
     let public_data = public_data_convert(&block);
 
@@ -364,6 +435,12 @@ pub fn block_convert<F: Field>(
     );
     // PI Circuit
     block.keccak_inputs.extend_from_slice(&[rpi_bytes]);
+    // MPT Circuit
+    for node in block.mpt_nodes.iter() {
+        block
+            .keccak_inputs
+            .extend(node.keccak_data.iter().map(|data| data.to_vec()));
+    }
 
     Ok(block)
 }
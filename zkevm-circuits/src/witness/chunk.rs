@@ -5,16 +5,27 @@ use super::{
     rw::{RwFingerprints, ToVec},
     Block, ExecStep, Rw, RwMap, RwRow,
 };
-use crate::util::unwrap_value;
+use crate::{
+    evm_circuit::step::HasExecutionState, keccak_circuit::KeccakCircuit, util::unwrap_value,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{root_circuit::PoseidonTranscript, table::rw_table::get_rwtable_cols_commitment};
 use bus_mapping::{
-    circuit_input_builder::{self, Call, ChunkContext, FixedCParams},
+    circuit_input_builder::{self, Call, CapacityExceeded, ChunkContext, FixedCParams},
     operation::Target,
     Error,
 };
 use eth_types::Field;
 use gadgets::permutation::get_permutation_fingerprints;
 use halo2_proofs::circuit::Value;
+#[cfg(not(target_arch = "wasm32"))]
+use halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fr},
+    poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG},
+};
 use itertools::Itertools;
+#[cfg(not(target_arch = "wasm32"))]
+use snark_verifier::util::transcript::Transcript;
 
 /// [`Chunk`]` is the struct used by all circuits, which contains chunkwise
 /// data for witness generation. Used with [`Block`] for blockwise witness.
@@ -83,15 +94,10 @@ pub fn chunk_convert<F: Field>(
 ) -> Result<Vec<Chunk<F>>, Error> {
     let (by_address_rws, padding_meta) = (&block.by_address_rws, &block.rw_padding_meta);
 
-    // Todo: poseidon hash to compute alpha/gamma
-    let alpha = F::from(103);
-    let gamma = F::from(101);
-
     let mut chunks: Vec<Chunk<F>> = Vec::with_capacity(builder.chunks.len());
-    for (i, (prev_chunk, chunk)) in iter::once(None) // left append `None` to make iteration easier
+    for (prev_chunk, chunk) in iter::once(None) // left append `None` to make iteration easier
         .chain(builder.chunks.iter().map(Some))
         .tuple_windows()
-        .enumerate()
     {
         let chunk = chunk.unwrap(); // current chunk always there
         let prev_chunk_last_chrono_rw = prev_chunk.map(|prev_chunk| {
@@ -151,39 +157,7 @@ pub fn chunk_convert<F: Field>(
             }
         };
 
-        // Compute cur fingerprints from last fingerprints and current Rw rows
-        let by_address_rw_fingerprints = get_permutation_fingerprint_of_rwmap(
-            &by_address_rws,
-            chunk.fixed_param.max_rws,
-            alpha,
-            gamma,
-            if i == 0 {
-                F::from(1)
-            } else {
-                chunks[i - 1].by_address_rw_fingerprints.mul_acc
-            },
-            false,
-            prev_chunk_last_by_address_rw,
-        );
-
-        let chrono_rw_fingerprints = get_permutation_fingerprint_of_rwmap(
-            &chrono_rws,
-            chunk.fixed_param.max_rws,
-            alpha,
-            gamma,
-            if i == 0 {
-                F::from(1)
-            } else {
-                chunks[i - 1].chrono_rw_fingerprints.mul_acc
-            },
-            true,
-            prev_chunk_last_chrono_rw,
-        );
         chunks.push(Chunk {
-            permu_alpha: alpha,
-            permu_gamma: gamma,
-            by_address_rw_fingerprints,
-            chrono_rw_fingerprints,
             begin_chunk: chunk.begin_chunk.clone(),
             end_chunk: chunk.end_chunk.clone(),
             padding: chunk.padding.clone(),
@@ -194,8 +168,15 @@ pub fn chunk_convert<F: Field>(
             prev_last_call: chunk.prev_last_call.clone(),
             prev_chunk_last_chrono_rw,
             prev_chunk_last_by_address_rw,
+            ..Default::default()
         });
     }
+    check_layout_capacities(block, &chunks)?;
+
+    // Mock challenges, which are replaced by the ones derived from the rw table
+    // commitments of all the chunks with `get_permutation_randomness` when
+    // generating real proofs.
+    set_permutation_randomness(&mut chunks, F::from(103), F::from(101));
 
     if log::log_enabled!(log::Level::Debug) {
         chunks
@@ -207,6 +188,53 @@ pub fn chunk_convert<F: Field>(
     Ok(chunks)
 }
 
+/// Check the capacities of the circuits params which depend on the layout of
+/// the circuits, so that they can't be checked in bus-mapping: the rows of the
+/// EVM circuit in each chunk, and of the Keccak circuit.  A capacity of 0 is
+/// computed from the block, so it is never exceeded.
+fn check_layout_capacities<F: Field>(block: &Block<F>, chunks: &[Chunk<F>]) -> Result<(), Error> {
+    for (idx, chunk) in chunks.iter().enumerate() {
+        let ctx = &chunk.chunk_context;
+        let max_evm_rows = chunk.fixed_param.max_evm_rows;
+        if max_evm_rows > 0 {
+            let step_rows: usize = chunk
+                .begin_chunk
+                .iter()
+                .chain(
+                    block.txs[ctx.initial_tx_index..ctx.end_tx_index]
+                        .iter()
+                        .flat_map(|tx| tx.steps())
+                        .filter(|step| (ctx.initial_rwc..ctx.end_rwc).contains(&step.rwc.0)),
+                )
+                .map(|step| step.execution_state().get_step_height())
+                .sum();
+            // The steps are followed by at least one Padding row, and the
+            // EndChunk or EndBlock row
+            if step_rows + 2 > max_evm_rows {
+                log::error!(
+                    "chunk {} needs {} evm rows, more than the {} available",
+                    idx,
+                    step_rows + 2,
+                    max_evm_rows
+                );
+                return Err(Error::CapacityExceeded(CapacityExceeded::EvmRows));
+            }
+        }
+    }
+
+    let params = &block.circuits_params;
+    if params.max_keccak_rows > 0 {
+        let capacity = KeccakCircuit::<F>::new(params.max_keccak_rows, Vec::new())
+            .with_params(params.keccak_params)
+            .capacity()
+            .expect("max_keccak_rows is not 0");
+        if KeccakCircuit::<F>::num_permutations(&block.keccak_inputs) > capacity {
+            return Err(Error::CapacityExceeded(CapacityExceeded::KeccakRows));
+        }
+    }
+    Ok(())
+}
+
 ///
 pub fn get_rwtable_fingerprints<F: Field>(
     alpha: F,
@@ -310,8 +338,75 @@ pub fn get_permutation_fingerprint_of_rwrowvec<F: Field>(
         .unwrap_or_default()
 }
 
+/// Set the permutation challenges of all the chunks to `alpha` and `gamma`,
+/// and recompute their chained rw table fingerprints accordingly.
+pub fn set_permutation_randomness<F: Field>(chunks: &mut [Chunk<F>], alpha: F, gamma: F) {
+    let (mut by_address_mul_acc, mut chrono_mul_acc) = (F::from(1), F::from(1));
+    for chunk in chunks.iter_mut() {
+        // Compute cur fingerprints from last fingerprints and current Rw rows
+        chunk.by_address_rw_fingerprints = get_permutation_fingerprint_of_rwmap(
+            &chunk.by_address_rws,
+            chunk.fixed_param.max_rws,
+            alpha,
+            gamma,
+            by_address_mul_acc,
+            false,
+            chunk.prev_chunk_last_by_address_rw,
+        );
+        chunk.chrono_rw_fingerprints = get_permutation_fingerprint_of_rwmap(
+            &chunk.chrono_rws,
+            chunk.fixed_param.max_rws,
+            alpha,
+            gamma,
+            chrono_mul_acc,
+            true,
+            chunk.prev_chunk_last_chrono_rw,
+        );
+        chunk.permu_alpha = alpha;
+        chunk.permu_gamma = gamma;
+        by_address_mul_acc = chunk.by_address_rw_fingerprints.mul_acc;
+        chrono_mul_acc = chunk.chrono_rw_fingerprints.mul_acc;
+    }
+}
+
+/// Derive the permutation challenges `(alpha, gamma)` of the rw table
+/// fingerprints from the commitments of the rw table columns of all the
+/// chunks.
 ///
-pub fn get_permutation_randomness<F: Field>() -> (F, F) {
-    // Todo
-    (F::from(1), F::from(1))
+/// The commitments are absorbed chunk by chunk, chronological table first and
+/// then by address table, in the same order as the `UserChallenge` of the
+/// `RootCircuit` built from `SuperCircuitConfig::get_rwtable_columns`, which
+/// checks that the chunk proofs used these challenges.  `degree` is the degree
+/// of the constraint system the chunks are proven with.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn get_permutation_randomness(
+    params: &ParamsKZG<Bn256>,
+    degree: usize,
+    chunks: &[Chunk<Fr>],
+) -> (Fr, Fr) {
+    let mut transcript = PoseidonTranscript::new(Vec::<u8>::new());
+    for chunk in chunks {
+        let chrono_commitments = get_rwtable_cols_commitment::<KZGCommitmentScheme<_>>(
+            degree,
+            &chunk.chrono_rws.table_assignments(true),
+            chunk.fixed_param.max_rws,
+            chunk.prev_chunk_last_chrono_rw,
+            params,
+        );
+        let by_address_commitments = get_rwtable_cols_commitment::<KZGCommitmentScheme<_>>(
+            degree,
+            &chunk.by_address_rws.table_assignments(false),
+            chunk.fixed_param.max_rws,
+            chunk.prev_chunk_last_by_address_rw,
+            params,
+        );
+        for commitment in chrono_commitments
+            .iter()
+            .chain(by_address_commitments.iter())
+        {
+            transcript.common_ec_point(commitment).unwrap();
+        }
+    }
+    let challenges = transcript.squeeze_n_challenges(2);
+    (challenges[0], challenges[1])
 }
@@ -64,23 +64,43 @@ impl MptUpdates {
 
     pub(crate) fn mock_from(rows: &[Rw]) -> Self {
         let mock_old_root = Word::from(0xcafeu64);
+        Self::from_rows_with_roots(
+            rows,
+            mock_old_root,
+            (0..).map(|i: u64| {
+                (
+                    Word::from(i) + mock_old_root,
+                    Word::from(i + 1) + mock_old_root,
+                )
+            }),
+        )
+    }
+
+    /// Build the updates of the rows sorted by address, where the i-th update
+    /// transitions the state trie between the i-th pair of (old, new) `roots`.
+    pub(crate) fn from_rows_with_roots(
+        rows: &[Rw],
+        old_root: Word,
+        roots: impl IntoIterator<Item = (Word, Word)>,
+    ) -> Self {
+        let mut roots = roots.into_iter();
         let map: BTreeMap<_, _> = rows
             .iter()
             .group_by(|row| key(row))
             .into_iter()
             .filter_map(|(key, rows)| key.map(|key| (key, rows)))
-            .enumerate()
-            .map(|(i, (key, mut rows))| {
+            .map(|(key, mut rows)| {
                 let first = rows.next().unwrap();
                 let last = rows.last().unwrap_or(first);
                 let key_exists = key;
                 let key = key.set_non_exists(value_prev(first), value(last));
+                let (old_root, new_root) = roots.next().expect("missing roots for mpt update");
                 (
                     key_exists,
                     MptUpdate {
                         key,
-                        old_root: Word::from(i as u64) + mock_old_root,
-                        new_root: Word::from(i as u64 + 1) + mock_old_root,
+                        old_root,
+                        new_root,
                         old_value: value_prev(first),
                         new_value: value(last),
                     },
@@ -89,7 +109,7 @@ impl MptUpdates {
             .collect();
         MptUpdates {
             updates: map,
-            old_root: mock_old_root,
+            old_root,
         }
     }
 
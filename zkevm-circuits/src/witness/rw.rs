@@ -248,6 +248,15 @@ pub enum Rw {
         is_warm: bool,
         is_warm_prev: bool,
     },
+    /// TxCreatedAccount
+    TxCreatedAccount {
+        rw_counter: usize,
+        is_write: bool,
+        tx_id: usize,
+        account_address: Address,
+        is_created: bool,
+        is_created_prev: bool,
+    },
     /// TxRefund
     TxRefund {
         rw_counter: usize,
@@ -545,6 +554,17 @@ impl Rw {
         }
     }
 
+    pub(crate) fn tx_created_account_value_pair(&self) -> (bool, bool) {
+        match self {
+            Self::TxCreatedAccount {
+                is_created,
+                is_created_prev,
+                ..
+            } => (*is_created, *is_created_prev),
+            _ => unreachable!(),
+        }
+    }
+
     pub(crate) fn tx_refund_value_pair(&self) -> (u64, u64) {
         match self {
             Self::TxRefund {
@@ -684,6 +704,7 @@ impl Rw {
             | Self::AccountTransientStorage { rw_counter, .. }
             | Self::TxAccessListAccount { rw_counter, .. }
             | Self::TxAccessListAccountStorage { rw_counter, .. }
+            | Self::TxCreatedAccount { rw_counter, .. }
             | Self::TxRefund { rw_counter, .. }
             | Self::Account { rw_counter, .. }
             | Self::CallContext { rw_counter, .. }
@@ -702,6 +723,7 @@ impl Rw {
             | Self::AccountTransientStorage { is_write, .. }
             | Self::TxAccessListAccount { is_write, .. }
             | Self::TxAccessListAccountStorage { is_write, .. }
+            | Self::TxCreatedAccount { is_write, .. }
             | Self::TxRefund { is_write, .. }
             | Self::Account { is_write, .. }
             | Self::CallContext { is_write, .. }
@@ -721,6 +743,7 @@ impl Rw {
             Self::AccountTransientStorage { .. } => Target::TransientStorage,
             Self::TxAccessListAccount { .. } => Target::TxAccessListAccount,
             Self::TxAccessListAccountStorage { .. } => Target::TxAccessListAccountStorage,
+            Self::TxCreatedAccount { .. } => Target::TxCreatedAccount,
             Self::TxRefund { .. } => Target::TxRefund,
             Self::Account { .. } => Target::Account,
             Self::CallContext { .. } => Target::CallContext,
@@ -736,6 +759,7 @@ impl Rw {
             | Self::AccountTransientStorage { tx_id, .. }
             | Self::TxAccessListAccount { tx_id, .. }
             | Self::TxAccessListAccountStorage { tx_id, .. }
+            | Self::TxCreatedAccount { tx_id, .. }
             | Self::TxRefund { tx_id, .. }
             | Self::TxLog { tx_id, .. }
             | Self::TxReceipt { tx_id, .. } => Some(*tx_id),
@@ -757,6 +781,9 @@ impl Rw {
             | Self::TxAccessListAccountStorage {
                 account_address, ..
             }
+            | Self::TxCreatedAccount {
+                account_address, ..
+            }
             | Self::Account {
                 account_address, ..
             }
@@ -802,6 +829,7 @@ impl Rw {
             | Self::AccountTransientStorage { .. }
             | Self::TxAccessListAccount { .. }
             | Self::TxAccessListAccountStorage { .. }
+            | Self::TxCreatedAccount { .. }
             | Self::TxRefund { .. }
             | Self::TxLog { .. } => None,
         }
@@ -821,6 +849,7 @@ impl Rw {
             | Self::TxRefund { .. }
             | Self::Account { .. }
             | Self::TxAccessListAccount { .. }
+            | Self::TxCreatedAccount { .. }
             | Self::TxLog { .. }
             | Self::TxReceipt { .. } => None,
         }
@@ -838,6 +867,7 @@ impl Rw {
             | Self::TxLog { value, .. } => *value,
             Self::TxAccessListAccount { is_warm, .. }
             | Self::TxAccessListAccountStorage { is_warm, .. } => U256::from(*is_warm as u64),
+            Self::TxCreatedAccount { is_created, .. } => U256::from(*is_created as u64),
             Self::Memory { byte, .. } => U256::from(u64::from(*byte)),
             Self::TxRefund { value, .. } | Self::TxReceipt { value, .. } => U256::from(*value),
         }
@@ -852,6 +882,9 @@ impl Rw {
             | Self::TxAccessListAccountStorage { is_warm_prev, .. } => {
                 Some(U256::from(*is_warm_prev as u64))
             }
+            Self::TxCreatedAccount {
+                is_created_prev, ..
+            } => Some(U256::from(*is_created_prev as u64)),
             Self::TxRefund { value_prev, .. } => Some(U256::from(*value_prev)),
             Self::Padding { .. }
             | Self::Start { .. }
@@ -914,6 +947,13 @@ impl From<Vec<Rw>> for RwMap {
                         rw_map.insert(Target::TxAccessListAccountStorage, vec![rw]);
                     }
                 }
+                Rw::TxCreatedAccount { .. } => {
+                    if let Some(vrw) = rw_map.get_mut(&Target::TxCreatedAccount) {
+                        vrw.push(rw)
+                    } else {
+                        rw_map.insert(Target::TxCreatedAccount, vec![rw]);
+                    }
+                }
                 Rw::Padding { .. } => {
                     if let Some(vrw) = rw_map.get_mut(&Target::Padding) {
                         vrw.push(rw)
@@ -1039,6 +1079,21 @@ impl From<&operation::OperationContainer> for RwMap {
                 })
                 .collect(),
         );
+        rws.insert(
+            Target::TxCreatedAccount,
+            container
+                .tx_created_account
+                .iter()
+                .map(|op| Rw::TxCreatedAccount {
+                    rw_counter: op.rwc().into(),
+                    is_write: op.rw().is_write(),
+                    tx_id: op.op().tx_id,
+                    account_address: op.op().address,
+                    is_created: op.op().is_created,
+                    is_created_prev: op.op().is_created_prev,
+                })
+                .collect(),
+        );
         rws.insert(
             Target::TxRefund,
             container